@@ -3,232 +3,33 @@
 //!
 //! Run with: cargo run --example hello
 
-use bs::{
-    engine::{
-        source::{
-            Animation, Coordinate, FrameRange, HLine, Header, Label, Position, Rect, SceneObject,
-            SourcePresentation, TextAlign, VerticalAlign,
-        },
-        Engine,
-    },
-    player::Player,
-    renderer::Renderer,
-    types::{Color, NamedColor, Style, TerminalContract},
-};
+use bs::{builder::Presentation, player::Player, types::NamedColor};
 
 fn main() -> anyhow::Result<()> {
-    let source = SourcePresentation {
-        width: 80,
-        height: 24,
-        frame_count: 25,
-        objects: vec![
-            // ── Slide 1: big header (frames 0–4) ──────────────
-            SceneObject::Header(Header {
-                text: "Gnosis VPN".to_string(),
-                position: Position {
-                    x: Coordinate::Fixed(25.0),
-                    y: Coordinate::Fixed(9.0),
-                },
-                style: Style {
-                    fg: Some(Color::Named(NamedColor::Cyan)),
-                    bold: true,
-                    ..Default::default()
-                },
-                frames: FrameRange { start: 0, end: 1 },
-                z_order: 1,
-                ch: '█',
-            }),
-            SceneObject::Label(Label {
-                text: "Welcome to bs".to_string(),
-                position: Position {
-                    x: Coordinate::Fixed(27.0),
-                    y: Coordinate::Fixed(16.0),
-                },
-                width: Coordinate::Fixed(0.0),
-                height: Coordinate::Fixed(0.0),
-                framed: false,
-                frame_style: None,
-                align: TextAlign::Left,
-                valign: VerticalAlign::Top,
-                style: Style {
-                    dim: true,
-                    ..Default::default()
-                },
-                frames: FrameRange { start: 0, end: 1 },
-                z_order: 0,
-            }),
-            // ── Slide 2: big "HOPR" header (frames 5–9) ──────────────
-            SceneObject::Header(Header {
-                text: "HOPR".to_string(),
-                position: Position {
-                    x: Coordinate::Fixed(28.0),
-                    y: Coordinate::Fixed(9.0),
-                },
-                style: Style {
-                    fg: Some(Color::Named(NamedColor::Green)),
-                    bold: true,
-                    ..Default::default()
-                },
-                frames: FrameRange { start: 2, end: 3 },
-                z_order: 1,
-                ch: '█',
-            }),
-            SceneObject::Label(Label {
-                text: "Let's see it in action...".to_string(),
-                position: Position {
-                    x: Coordinate::Fixed(28.0),
-                    y: Coordinate::Fixed(16.0),
-                },
-                width: Coordinate::Fixed(0.0),
-                height: Coordinate::Fixed(0.0),
-                framed: false,
-                frame_style: None,
-                align: TextAlign::Left,
-                valign: VerticalAlign::Top,
-                style: Style {
-                    dim: true,
-                    ..Default::default()
-                },
-                frames: FrameRange { start: 2, end: 3 },
-                z_order: 0,
-            }),
-            // ── Original slides (frames 10–24) ───────────────────────
-
-            // Title (visible from the start of this section)
-            SceneObject::Label(Label {
-                text: "bs".to_string(),
-                position: Position {
-                    x: Coordinate::Fixed(32.0),
-                    y: Coordinate::Fixed(2.0),
-                },
-                width: Coordinate::Fixed(0.0),
-                height: Coordinate::Fixed(0.0),
-                framed: false,
-                frame_style: None,
-                align: TextAlign::Left,
-                valign: VerticalAlign::Top,
-                style: Style {
-                    bold: true,
-                    ..Default::default()
-                },
-                frames: FrameRange { start: 4, end: 25 },
-                z_order: 1,
-            }),
-            // Horizontal divider (appears on frame 11)
-            SceneObject::HLine(HLine {
-                y: Coordinate::Fixed(4.0),
-                x_start: Coordinate::Fixed(20.0),
-                x_end: Coordinate::Fixed(60.0),
-                ch: '─',
-                style: Style {
-                    fg: Some(Color::Named(NamedColor::Cyan)),
-                    ..Default::default()
-                },
-                frames: FrameRange { start: 11, end: 25 },
-                z_order: 0,
-            }),
-            // Animated packet moving along the divider
-            SceneObject::Label(Label {
-                text: "[*]".to_string(),
-                position: Position {
-                    // References the animation below for its timing (span lives
-                    // on the `Animation`, the single source of truth).
-                    x: Coordinate::Animated { from: 20, to: 57, anim: 1 },
-                    y: Coordinate::Fixed(4.0),
-                },
-                width: Coordinate::Fixed(0.0),
-                height: Coordinate::Fixed(0.0),
-                framed: false,
-                frame_style: None,
-                align: TextAlign::Left,
-                valign: VerticalAlign::Top,
-                style: Style {
-                    fg: Some(Color::Named(NamedColor::Green)),
-                    bold: true,
-                    ..Default::default()
-                },
-                frames: FrameRange { start: 13, end: 22 },
-                z_order: 10,
-            }),
-            // The packet's animation span (owns the timing; the label's animated
-            // x references it by id).
-            SceneObject::Animation(Animation {
-                id: 1,
-                frames: FrameRange { start: 13, end: 23 },
-                auto_play: true,
-                delay_ms: 500,
-                gap_frames: 0,
-            }),
-            // Status box (appears mid-presentation)
-            SceneObject::Rect(Rect {
-                position: Position {
-                    x: Coordinate::Fixed(25.0),
-                    y: Coordinate::Fixed(7.0),
-                },
-                width: Coordinate::Fixed(30.0),
-                height: Coordinate::Fixed(5.0),
-                style: Style {
-                    fg: Some(Color::Named(NamedColor::Yellow)),
-                    ..Default::default()
-                },
-                frames: FrameRange { start: 15, end: 25 },
-                z_order: 0,
-                title: Some("Status".to_string()),
-            }),
-            // Text inside the box
-            SceneObject::Label(Label {
-                text: "Packet delivered!".to_string(),
-                position: Position {
-                    x: Coordinate::Fixed(31.0),
-                    y: Coordinate::Fixed(9.0),
-                },
-                width: Coordinate::Fixed(0.0),
-                height: Coordinate::Fixed(0.0),
-                framed: false,
-                frame_style: None,
-                align: TextAlign::Left,
-                valign: VerticalAlign::Top,
-                style: Style::default(),
-                frames: FrameRange { start: 18, end: 25 },
-                z_order: 1,
-            }),
-            // Footer
-            SceneObject::Label(Label {
-                text: "A minimal boilerplate example".to_string(),
-                position: Position {
-                    x: Coordinate::Fixed(25.0),
-                    y: Coordinate::Fixed(20.0),
-                },
-                width: Coordinate::Fixed(0.0),
-                height: Coordinate::Fixed(0.0),
-                framed: false,
-                frame_style: None,
-                align: TextAlign::Left,
-                valign: VerticalAlign::Top,
-                style: Style {
-                    dim: true,
-                    ..Default::default()
-                },
-                frames: FrameRange { start: 10, end: 25 },
-                z_order: 0,
-            }),
-        ],
-        links: Vec::new(),
-    };
-
-    // ── Pipeline: Source → Engine → Renderer → Player ──
-
-    // 1. Compile: evaluate the source over all frames
-    let scenes = Engine::compile(&source);
-
-    // 2. Render: rasterize resolved scenes into a playable presentation
-    let contract = TerminalContract {
-        width: source.width,
-        height: source.height,
-    };
-    let presentation = Renderer::render(&scenes, contract);
-
-    // 3. Play: drive the presentation to the terminal
+    let presentation = Presentation::new(80, 24)
+        // ── Slide 1: big header ──────────────
+        .header("Gnosis VPN").at(25, 9).fg(NamedColor::Cyan).bold().z(1)
+        .label("Welcome to bs").at(27, 16).dim()
+        // ── Slide 2: big "HOPR" header ──────────────
+        .frame(2)
+        .header("HOPR").at(28, 9).fg(NamedColor::Green).bold().z(1)
+        .label("Let's see it in action...").at(28, 16).dim()
+        // ── Original slides (frames 4–24) ───────────────────────
+        .label("bs").at(32, 2).bold().z(1).appears(4..25)
+        .label("A minimal boilerplate example").at(25, 20).dim().appears(10..25)
+        // Horizontal divider (appears on frame 11)
+        .hline(40).at(20, 4).fg(NamedColor::Cyan).appears(11..25)
+        // Animated packet moving along the divider (the builder adds the
+        // `Animation` that owns the timing)
+        .label("[*]").at(20, 4).fg(NamedColor::Green).bold().z(10).move_to(57, 4, 13..23)
+        .appears(13..22)
+        // Status box with text inside (appears mid-presentation)
+        .rect(30, 5).at(25, 7).fg(NamedColor::Yellow).title("Status").appears(15..25)
+        .label("Packet delivered!").at(31, 9).z(1).appears(18..25)
+        // ── Pipeline: Source → Engine → Renderer (+ sidecars) ──
+        .compile()?;
+
+    // Play: drive the presentation to the terminal
     let mut player = Player::new(presentation);
     player.play()?;
 
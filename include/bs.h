@@ -0,0 +1,54 @@
+/* C interface to the bs compile/render pipeline.
+ *
+ * Build the shared library with `cargo build --release --features ffi`
+ * (target/release/libbs.so / libbs.dylib / bs.dll). See src/ffi.rs for the
+ * full contract: strings are NUL-terminated UTF-8; strings returned here are
+ * freed with bs_string_free; on failure a function returns NULL / false and
+ * bs_last_error() describes why.
+ */
+#ifndef BS_H
+#define BS_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct BsDeck BsDeck;
+
+/* One cell. Colours are 0xRRGGBB, or -1 for the terminal default. ch is 0
+   for the right half of a double-width character. */
+typedef struct BsCell {
+    uint32_t ch;
+    int32_t fg;
+    int32_t bg;
+    bool bold;
+    bool dim;
+    bool underline;
+} BsCell;
+
+const char *bs_last_error(void);
+void bs_string_free(char *s);
+
+/* Source JSON -> compiled JSON (as `bs compile`). */
+char *bs_compile(const char *source_json);
+
+/* Load a source or compiled deck. */
+BsDeck *bs_deck_load(const char *json);
+void bs_deck_free(BsDeck *deck);
+uint32_t bs_deck_width(const BsDeck *deck);
+uint32_t bs_deck_height(const BsDeck *deck);
+size_t bs_deck_frame_count(const BsDeck *deck);
+
+/* Frame indices are 0-based and clamped to the last frame. */
+bool bs_deck_cell(BsDeck *deck, size_t frame, uint32_t x, uint32_t y, BsCell *out);
+char *bs_deck_frame_text(const BsDeck *deck, size_t frame, bool ansi);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* BS_H */
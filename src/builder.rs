@@ -0,0 +1,1083 @@
+//! Fluent builder for code-generated decks.
+//!
+//! Writing `SourcePresentation` literals by hand means spelling out every
+//! field of every object (see the history of `examples/hello.rs`). The builder
+//! keeps one chain per deck: each object method (`label`, `rect`, …) appends an
+//! object with the same defaults an omitted JSON field would get, and the
+//! modifiers after it (`at`, `appears`, `fg`, …) apply to that most recently
+//! added object:
+//!
+//! ```
+//! use bs::builder::Presentation;
+//! use bs::types::NamedColor;
+//!
+//! let source = Presentation::new(80, 24)
+//!     .header("Hello").at(20, 8).fg(NamedColor::Cyan).bold()
+//!     .next_frame()
+//!     .label("[*]").at(10, 4).move_to(60, 4, 1..6)
+//!     .rect(30, 5).at(25, 10).title("Status").appears(3..6)
+//!     .build();
+//! assert_eq!(source.frame_count, 6);
+//! ```
+//!
+//! New objects appear on the *current frame* only (`0`, moved by `frame` /
+//! `next_frame`) unless `appears` gives a range. A modifier that doesn't apply
+//! to the last object (`title` on a label, `size` on a header) is ignored, and
+//! modifiers before the first object do nothing. `build` sets `frame_count` to
+//! the explicit `frames(n)` if given, otherwise just past the last range end.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use anyhow::Result;
+
+use crate::engine::objects::{
+    default_animation_delay_ms, default_ansi_width, default_bullet, default_diameter,
+    default_effect_density, default_effect_z, default_diff_context, default_fill_char,
+    default_gantt_width, default_gauge_max, default_gauge_size, default_header_char,
+    default_heatmap_cell_width, default_heatmap_high, default_heatmap_low, default_hline_char,
+    default_marquee_gap, default_marquee_speed, default_pie_char, default_plot_height,
+    default_plot_width, default_spacing, default_spinner_glyphs, default_step_secs, default_ticks,
+    default_keycap_border, default_keycap_separator, default_watermark_z,
+};
+use crate::engine::source::{
+    AnimId, Animation, AnsiArt, Arrow, Art, BackgroundEffect, BorderStyle, Cast, Circle, Clock,
+    Connector, Coordinate, Countdown, Diff, Effect, EnterPreset, ExitPreset, FileTree, Footer,
+    FrameRange, Gantt, GanttTask, Gauge, HLine, Header, Heatmap, Keycap, Label, Layer, List,
+    Marquee, Motion, Padding, PieChart, PieSegment, Position, Rect, Scalar, ScatterPlot, SceneObject,
+    Shadow, SourcePresentation, Spinner, TextAlign, Toc, TreeEntry, VerticalAlign, Watermark,
+    SOURCE_FORMAT_VERSION,
+};
+use crate::types::{Color, Metadata, PlayablePresentation, Style};
+
+/// A deck under construction. See the module docs.
+#[derive(Debug, Clone)]
+pub struct Presentation {
+    width: u16,
+    height: u16,
+    frame_count: Option<usize>,
+    background: Option<Color>,
+    layers: Vec<Layer>,
+    vars: BTreeMap<String, String>,
+    auto_z: bool,
+    metadata: Metadata,
+    objects: Vec<SceneObject>,
+    sections: Vec<Option<String>>,
+    frame: usize,
+}
+
+fn fixed(v: u16) -> Coordinate {
+    Coordinate::Fixed(v as f64)
+}
+
+fn origin() -> Position {
+    Position { x: fixed(0), y: fixed(0) }
+}
+
+impl Presentation {
+    /// An empty `width`×`height` deck; new objects go on frame 0.
+    pub fn new(width: u16, height: u16) -> Self {
+        Presentation {
+            width,
+            height,
+            frame_count: None,
+            background: None,
+            layers: Vec::new(),
+            vars: BTreeMap::new(),
+            auto_z: false,
+            metadata: Metadata::default(),
+            objects: Vec::new(),
+            sections: Vec::new(),
+            frame: 0,
+        }
+    }
+
+    /// The deck's title, author, date and description.
+    pub fn metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Deck-wide background colour.
+    pub fn background(mut self, color: impl Into<Color>) -> Self {
+        self.background = Some(color.into());
+        self
+    }
+
+    /// Register layer `name` as hidden, so objects put on it with
+    /// [`layer`](Self::layer) are left out when compiling.
+    pub fn hide_layer(mut self, name: &str) -> Self {
+        self.layers.push(Layer { visible: false, ..Layer::new(name) });
+        self
+    }
+
+    /// Define `${name}` for label, header, footer and table text.
+    pub fn var(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.vars.insert(name.into(), value.into());
+        self
+    }
+
+    /// Stack objects by the order they were added, later ones on top,
+    /// whatever their [`z`](Self::z).
+    pub fn auto_z(mut self) -> Self {
+        self.auto_z = true;
+        self
+    }
+
+    /// Fix the deck's frame count instead of deriving it from the ranges.
+    pub fn frames(mut self, n: usize) -> Self {
+        self.frame_count = Some(n);
+        self
+    }
+
+    /// Place subsequent objects on frame `i` (0-based).
+    pub fn frame(mut self, i: usize) -> Self {
+        self.frame = i;
+        self
+    }
+
+    /// Place subsequent objects on the frame after the current one.
+    pub fn next_frame(mut self) -> Self {
+        self.frame += 1;
+        self
+    }
+
+    /// Start a section called `name` on the current frame, for
+    /// [`toc`](Self::toc) objects to list.
+    pub fn section(mut self, name: impl Into<String>) -> Self {
+        if self.sections.len() <= self.frame {
+            self.sections.resize(self.frame + 1, None);
+        }
+        self.sections[self.frame] = Some(name.into());
+        self
+    }
+
+    fn current_range(&self) -> FrameRange {
+        FrameRange { start: self.frame, end: self.frame + 1 }
+    }
+
+    fn push(mut self, obj: SceneObject) -> Self {
+        self.objects.push(obj);
+        self
+    }
+
+    // ── Objects ──────────────────────────────────────────────────────────
+
+    /// A text label (auto-sized; `size` gives it a box).
+    pub fn label(self, text: impl Into<String>) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::Label(Label {
+            text: text.into(),
+            position: origin(),
+            width: fixed(0),
+            height: fixed(0),
+            link: None,
+            reveal: false,
+            markup: false,
+            framed: false,
+            padding: Padding::default(),
+            border: BorderStyle::Single,
+            shadow: None,
+            frame_style: None,
+            align: TextAlign::Left,
+            valign: VerticalAlign::Top,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// Large block-letter text.
+    pub fn header(self, text: impl Into<String>) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::Header(Header {
+            text: text.into(),
+            position: origin(),
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            ch: default_header_char(),
+            gradient: None,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// A `width`×`height` box.
+    pub fn rect(self, width: u16, height: u16) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::Rect(Rect {
+            position: origin(),
+            width: fixed(width),
+            height: fixed(height),
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            title: None,
+            border: BorderStyle::Single,
+            shadow: None,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// A horizontal line `len` cells long; `at` places its left end.
+    pub fn hline(self, len: u16) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::HLine(HLine {
+            y: fixed(0),
+            x_start: fixed(0),
+            x_end: fixed(len),
+            ch: default_hline_char(),
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// An arrow from `(x1, y1)` to `(x2, y2)` with a head at the end.
+    pub fn arrow(self, x1: u16, y1: u16, x2: u16, y2: u16) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::Arrow(Arrow {
+            x1: fixed(x1),
+            y1: fixed(y1),
+            x2: fixed(x2),
+            y2: fixed(y2),
+            head: true,
+            head_start: false,
+            head_ch: None,
+            body_ch: None,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// A filled circle `diameter` rows tall.
+    pub fn circle(self, diameter: u16) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::Circle(Circle {
+            position: origin(),
+            diameter: if diameter == 0 { default_diameter() } else { diameter },
+            ch: default_fill_char(),
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// A piece of multi-line ASCII art.
+    pub fn art(self, art: impl Into<String>) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::Art(Art {
+            position: origin(),
+            art: art.into(),
+            name: String::new(),
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// ANSI art: text with SGR escapes (e.g. an `.ans` file's contents),
+    /// drawn with its own colours and wrapped at 80 columns.
+    pub fn ansi_art(self, ansi: impl Into<String>) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::AnsiArt(AnsiArt {
+            position: origin(),
+            ansi: ansi.into(),
+            name: String::new(),
+            width: default_ansi_width(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// A snippet of an asciinema v2 recording (a `.cast` file's contents),
+    /// played from start to end across the current frames.
+    pub fn cast(self, cast: impl Into<String>) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::Cast(Cast {
+            position: origin(),
+            cast: cast.into(),
+            name: String::new(),
+            start: 0.0,
+            end: None,
+            width: None,
+            height: None,
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// An `mm:ss` countdown from `from_secs`, a second less on each of the
+    /// current frames. Set `auto_play` on the result to have the player step
+    /// through them on its own.
+    pub fn countdown(self, from_secs: u64) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::Countdown(Countdown {
+            position: origin(),
+            from_secs,
+            step_secs: default_step_secs(),
+            auto_play: false,
+            big: false,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// The time of day as `HH:MM`, filled in by the player (`--:--` in the
+    /// compiled frames).
+    pub fn clock(self) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::Clock(Clock {
+            position: origin(),
+            seconds: false,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+        }))
+    }
+
+    /// A footer line; `{frame}`, `{total}` and `{section}` in `text` are
+    /// filled in per frame. Give it the deck's frames with
+    /// [`appears`](Self::appears).
+    pub fn footer(self, text: impl Into<String>) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::Footer(Footer {
+            text: text.into(),
+            position: origin(),
+            width: 0,
+            align: TextAlign::Left,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// Dim background text under everything else; give it the deck's frames
+    /// with [`appears`](Self::appears).
+    pub fn watermark(self, text: impl Into<String>) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::Watermark(Watermark {
+            text: text.into(),
+            position: origin(),
+            big: false,
+            style: Style::default(),
+            frames,
+            z_order: default_watermark_z(),
+            layer: None,
+        }))
+    }
+
+    /// A seeded background `effect` over the whole canvas, under everything
+    /// else; give it the deck's frames with [`appears`](Self::appears).
+    pub fn background_effect(self, effect: Effect) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::BackgroundEffect(BackgroundEffect {
+            effect,
+            seed: 0,
+            density: default_effect_density(),
+            position: origin(),
+            width: 0,
+            height: 0,
+            style: Style::default(),
+            frames,
+            z_order: default_effect_z(),
+            layer: None,
+        }))
+    }
+
+    /// `text` scrolling left a cell per frame through a window `width` cells wide.
+    pub fn marquee(self, text: impl Into<String>, width: u16) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::Marquee(Marquee {
+            text: text.into(),
+            position: origin(),
+            width,
+            speed: default_marquee_speed(),
+            gap: default_marquee_gap(),
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// A braille spinner turning a step per frame, followed by `text`.
+    pub fn spinner(self, text: impl Into<String>) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::Spinner(Spinner {
+            position: origin(),
+            glyphs: default_spinner_glyphs(),
+            text: text.into(),
+            done: None,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// A box-drawn line through `points`, offsets from the object's
+    /// position (see [`at`](Self::at)), with no head.
+    pub fn connector(self, points: &[(u16, u16)]) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::Connector(Connector {
+            position: origin(),
+            points: points.iter().map(|&(x, y)| [x, y]).collect(),
+            line: BorderStyle::default(),
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// A keyboard shortcut drawn as boxed key caps, `keys` joined by `+`.
+    pub fn keycap(self, keys: &[&str]) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::Keycap(Keycap {
+            position: origin(),
+            keys: keys.iter().map(|k| k.to_string()).collect(),
+            separator: default_keycap_separator(),
+            border: default_keycap_border(),
+            compact: false,
+            cap: None,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// A 0–100 gauge reading `value`.
+    pub fn gauge(self, value: f64) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::Gauge(Gauge {
+            position: origin(),
+            value: Scalar::Fixed(value),
+            min: Scalar::Fixed(0.0),
+            max: default_gauge_max(),
+            size: default_gauge_size(),
+            thresholds: Vec::new(),
+            blocks: false,
+            show_value: true,
+            unit: String::new(),
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// A heatmap of `values`, one inner `Vec` per row.
+    pub fn heatmap(self, values: Vec<Vec<f64>>) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::Heatmap(Heatmap {
+            position: origin(),
+            values,
+            row_labels: Vec::new(),
+            col_labels: Vec::new(),
+            cell_width: default_heatmap_cell_width(),
+            min: None,
+            max: None,
+            low: default_heatmap_low(),
+            high: default_heatmap_high(),
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// A diff of `before` against `after`, line by line.
+    pub fn diff(self, before: &str, after: &str) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::Diff(Diff {
+            position: origin(),
+            before: before.to_string(),
+            after: after.to_string(),
+            diff: String::new(),
+            context: default_diff_context(),
+            reveal: false,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// A file tree of `paths` (`/`-separated; a trailing `/` for an empty folder).
+    pub fn file_tree(self, paths: &[&str]) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::FileTree(FileTree {
+            position: origin(),
+            entries: paths.iter().map(|p| TreeEntry { path: p.to_string(), style: None, open_at: 0 }).collect(),
+            glyphs: true,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// A Gantt chart with one bar per `(label, start, end)` task, each in its
+    /// own row.
+    pub fn gantt(self, tasks: &[(&str, f64, f64)]) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::Gantt(Gantt {
+            position: origin(),
+            tasks: tasks
+                .iter()
+                .map(|&(label, start, end)| GanttTask {
+                    label: label.to_string(),
+                    start,
+                    end,
+                    lane: String::new(),
+                    color: None,
+                })
+                .collect(),
+            width: default_gantt_width(),
+            min: None,
+            max: None,
+            today: None,
+            border: BorderStyle::default(),
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// A pie chart with one slice per `(label, value)` segment.
+    pub fn pie_chart(self, segments: &[(&str, f64)]) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::PieChart(PieChart {
+            position: origin(),
+            segments: segments
+                .iter()
+                .map(|&(label, value)| PieSegment { label: label.to_string(), value, color: None })
+                .collect(),
+            diameter: default_diameter(),
+            hole: 0.0,
+            ch: default_pie_char(),
+            legend: true,
+            sweep: false,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// A braille scatter plot of `(x, y)` points, its axes fitted to the data.
+    pub fn scatter_plot(self, points: &[(f64, f64)]) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::ScatterPlot(ScatterPlot {
+            position: origin(),
+            points: points.iter().map(|&(x, y)| [x, y]).collect(),
+            width: default_plot_width(),
+            height: default_plot_height(),
+            x_min: None,
+            x_max: None,
+            y_min: None,
+            y_max: None,
+            x_label: String::new(),
+            y_label: String::new(),
+            ticks: default_ticks(),
+            color: None,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// A table of contents listing the deck's [`section`](Self::section)s.
+    pub fn toc(self) -> Self {
+        let frames = self.current_range();
+        self.push(SceneObject::Toc(Toc {
+            position: origin(),
+            numbers: true,
+            highlight: None,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    /// A bulleted list, one item per entry (`ordered` numbers them instead).
+    pub fn list<S: AsRef<str>>(self, items: &[S]) -> Self {
+        let frames = self.current_range();
+        let text: Vec<&str> = items.iter().map(|s| s.as_ref()).collect();
+        self.push(SceneObject::List(List {
+            text: text.join("\n"),
+            position: origin(),
+            width: fixed(0),
+            height: fixed(0),
+            ordered: false,
+            bullet: default_bullet(),
+            spacing: default_spacing(),
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }))
+    }
+
+    // ── Modifiers (apply to the last object) ─────────────────────────────
+
+    fn last(&mut self) -> Option<&mut SceneObject> {
+        self.objects.last_mut()
+    }
+
+    /// Move the last object's anchor to `(x, y)`: the top-left corner, a line's
+    /// left end, or an arrow's start (its end moves along with it).
+    pub fn at(mut self, x: u16, y: u16) -> Self {
+        match self.last() {
+            Some(SceneObject::HLine(h)) => {
+                let len = h.x_end.start_value().saturating_sub(h.x_start.start_value());
+                h.y = fixed(y);
+                h.x_start = fixed(x);
+                h.x_end = fixed(x.saturating_add(len));
+            }
+            Some(SceneObject::Arrow(a)) => {
+                let (dx, dy) = (
+                    a.x2.start_value() as i32 - a.x1.start_value() as i32,
+                    a.y2.start_value() as i32 - a.y1.start_value() as i32,
+                );
+                let shift = |base: u16, d: i32| fixed((base as i32 + d).clamp(0, u16::MAX as i32) as u16);
+                a.x1 = fixed(x);
+                a.y1 = fixed(y);
+                a.x2 = shift(x, dx);
+                a.y2 = shift(y, dy);
+            }
+            Some(obj) => {
+                if let Some(p) = position_mut(obj) {
+                    *p = Position { x: fixed(x), y: fixed(y) };
+                }
+            }
+            None => {}
+        }
+        self
+    }
+
+    /// Animate the last object from its `at` position to `(x, y)` over
+    /// `frames` (adding the `Animation` that owns the span, auto-playing), and
+    /// widen the object's own range to cover the motion.
+    pub fn move_to(mut self, x: u16, y: u16, frames: Range<usize>) -> Self {
+        let id = self.next_anim_id();
+        let Some(p) = self.last().and_then(position_mut) else {
+            return self;
+        };
+        let (x0, y0) = (p.x.start_value(), p.y.start_value());
+        p.x = if x0 == x { fixed(x) } else { Coordinate::Animated { from: x0, to: x, anim: id } };
+        p.y = if y0 == y { fixed(y) } else { Coordinate::Animated { from: y0, to: y, anim: id } };
+        if let Some(obj) = self.last() {
+            let r = obj.declared_frame_range().unwrap_or(FrameRange { start: frames.start, end: frames.end });
+            obj.set_frame_range(FrameRange { start: r.start.min(frames.start), end: r.end.max(frames.end) });
+        }
+        // Insert the animation *before* the moving object so later modifiers
+        // still apply to the object.
+        let at = self.objects.len() - 1;
+        self.objects.insert(
+            at,
+            SceneObject::Animation(Animation {
+                id,
+                frames: FrameRange { start: frames.start, end: frames.end },
+                auto_play: true,
+                delay_ms: default_animation_delay_ms(),
+                gap_frames: 0,
+                layer: None,
+            }),
+        );
+        self
+    }
+
+    fn next_anim_id(&self) -> AnimId {
+        self.objects
+            .iter()
+            .filter_map(|o| match o {
+                SceneObject::Animation(a) => Some(a.id),
+                _ => None,
+            })
+            .max()
+            .map_or(1, |m| m + 1)
+    }
+
+    /// Show the last object on `frames` (end exclusive) instead of the
+    /// current frame.
+    pub fn appears(mut self, frames: Range<usize>) -> Self {
+        if let Some(obj) = self.last() {
+            obj.set_frame_range(FrameRange { start: frames.start, end: frames.end });
+        }
+        self
+    }
+
+    /// Give a label, rect or list an explicit size.
+    pub fn size(mut self, width: u16, height: u16) -> Self {
+        match self.last() {
+            Some(SceneObject::Label(l)) => (l.width, l.height) = (fixed(width), fixed(height)),
+            Some(SceneObject::Rect(r)) => (r.width, r.height) = (fixed(width), fixed(height)),
+            Some(SceneObject::List(l)) => (l.width, l.height) = (fixed(width), fixed(height)),
+            _ => {}
+        }
+        self
+    }
+
+    /// Stacking order (higher draws on top).
+    pub fn z(mut self, z_order: i32) -> Self {
+        if let Some(z) = self.last().and_then(z_order_mut) {
+            *z = z_order;
+        }
+        self
+    }
+
+    /// Put the object on the named layer (see `SourcePresentation::layers`).
+    pub fn layer(mut self, name: &str) -> Self {
+        if let Some(o) = self.last() {
+            o.set_layer(Some(name.to_string()));
+        }
+        self
+    }
+
+    /// Slide the object in from an edge over its first `frames` frames.
+    pub fn enter(mut self, preset: EnterPreset, frames: usize) -> Self {
+        if let Some(o) = self.last() {
+            o.set_enter(Some(Motion::new(preset, frames)));
+        }
+        self
+    }
+
+    /// Slide the object out to an edge over its last `frames` frames.
+    pub fn exit(mut self, preset: ExitPreset, frames: usize) -> Self {
+        if let Some(o) = self.last() {
+            o.set_exit(Some(Motion::new(preset, frames)));
+        }
+        self
+    }
+
+    /// Foreground colour.
+    pub fn fg(mut self, color: impl Into<Color>) -> Self {
+        if let Some(s) = self.last().and_then(style_mut) {
+            s.fg = Some(color.into());
+        }
+        self
+    }
+
+    /// Background colour.
+    pub fn bg(mut self, color: impl Into<Color>) -> Self {
+        if let Some(s) = self.last().and_then(style_mut) {
+            s.bg = Some(color.into());
+        }
+        self
+    }
+
+    /// Bold text.
+    pub fn bold(mut self) -> Self {
+        if let Some(s) = self.last().and_then(style_mut) {
+            s.bold = true;
+        }
+        self
+    }
+
+    /// Dim text.
+    pub fn dim(mut self) -> Self {
+        if let Some(s) = self.last().and_then(style_mut) {
+            s.dim = true;
+        }
+        self
+    }
+
+    /// The drawing character of a header, line or circle.
+    pub fn ch(mut self, ch: char) -> Self {
+        match self.last() {
+            Some(SceneObject::Header(h)) => h.ch = ch,
+            Some(SceneObject::HLine(h)) => h.ch = ch,
+            Some(SceneObject::Circle(c)) => c.ch = ch,
+            _ => {}
+        }
+        self
+    }
+
+    /// A header's foreground blended from `from` on the left to `to` on the
+    /// right.
+    pub fn gradient(mut self, from: Color, to: Color) -> Self {
+        if let Some(SceneObject::Header(h)) = self.last() {
+            h.gradient = Some([from, to]);
+        }
+        self
+    }
+
+    /// A rect's title (drawn on its top edge).
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        if let Some(SceneObject::Rect(r)) = self.last() {
+            r.title = Some(title.into());
+        }
+        self
+    }
+
+    /// Draw a border around a label.
+    pub fn framed(mut self) -> Self {
+        if let Some(SceneObject::Label(l)) = self.last() {
+            l.framed = true;
+        }
+        self
+    }
+
+    /// Hyperlink a label's text to `url`.
+    pub fn link(mut self, url: impl Into<String>) -> Self {
+        if let Some(SceneObject::Label(l)) = self.last() {
+            l.link = Some(url.into());
+        }
+        self
+    }
+
+    /// Show a label's list items one per frame.
+    pub fn reveal(mut self) -> Self {
+        if let Some(SceneObject::Label(l)) = self.last() {
+            l.reveal = true;
+        }
+        self
+    }
+
+    /// `n` blank cells on every side between a label's text and its frame or
+    /// `bg` fill.
+    pub fn padding(mut self, n: u16) -> Self {
+        if let Some(SceneObject::Label(l)) = self.last() {
+            l.padding = Padding::uniform(n);
+        }
+        self
+    }
+
+    /// The character set of a rect's border or a framed label's frame.
+    pub fn border(mut self, border: BorderStyle) -> Self {
+        match self.last() {
+            Some(SceneObject::Rect(r)) => r.border = border,
+            Some(SceneObject::Label(l)) => l.border = border,
+            _ => {}
+        }
+        self
+    }
+
+    /// A default drop shadow behind a rect or a framed label.
+    pub fn shadow(mut self) -> Self {
+        match self.last() {
+            Some(SceneObject::Rect(r)) => r.shadow = Some(Shadow::default()),
+            Some(SceneObject::Label(l)) => l.shadow = Some(Shadow::default()),
+            _ => {}
+        }
+        self
+    }
+
+    /// Horizontal alignment of a label within its `size`, or of a footer
+    /// within its row.
+    pub fn align(mut self, align: TextAlign) -> Self {
+        match self.last() {
+            Some(SceneObject::Label(l)) => l.align = align,
+            Some(SceneObject::Footer(f)) => f.align = align,
+            _ => {}
+        }
+        self
+    }
+
+    /// Number a list's items instead of bulleting them.
+    pub fn ordered(mut self) -> Self {
+        if let Some(SceneObject::List(l)) = self.last() {
+            l.ordered = true;
+        }
+        self
+    }
+
+    // ── Finishing ────────────────────────────────────────────────────────
+
+    /// The finished source deck.
+    pub fn build(self) -> SourcePresentation {
+        let derived = self
+            .objects
+            .iter()
+            .filter_map(|o| o.declared_frame_range())
+            .map(|r| r.end)
+            .max()
+            .unwrap_or(0)
+            .max(self.frame + 1);
+        SourcePresentation {
+            format_version: SOURCE_FORMAT_VERSION,
+            width: self.width,
+            height: self.height,
+            frame_count: self.frame_count.unwrap_or(derived),
+            objects: self.objects,
+            links: Vec::new(),
+            background: self.background,
+            layers: self.layers,
+            components: Default::default(),
+            vars: self.vars,
+            slides: Vec::new(),
+            lint: Default::default(),
+            auto_z: self.auto_z,
+            durations: Vec::new(),
+            on_enter: Vec::new(),
+            sections: self.sections,
+            metadata: self.metadata,
+        }
+    }
+
+    /// Build and compile in one step (see [`crate::pipeline::compile`]).
+    pub fn compile(self) -> Result<PlayablePresentation> {
+        crate::pipeline::compile(&self.build())
+    }
+}
+
+fn position_mut(obj: &mut SceneObject) -> Option<&mut Position> {
+    match obj {
+        SceneObject::Label(o) => Some(&mut o.position),
+        SceneObject::Header(o) => Some(&mut o.position),
+        SceneObject::Rect(o) => Some(&mut o.position),
+        SceneObject::Circle(o) => Some(&mut o.position),
+        SceneObject::Art(o) => Some(&mut o.position),
+        SceneObject::AnsiArt(o) => Some(&mut o.position),
+        SceneObject::Cast(o) => Some(&mut o.position),
+        SceneObject::Countdown(o) => Some(&mut o.position),
+        SceneObject::Toc(o) => Some(&mut o.position),
+        SceneObject::Footer(o) => Some(&mut o.position),
+        SceneObject::Gauge(o) => Some(&mut o.position),
+        SceneObject::Heatmap(o) => Some(&mut o.position),
+        SceneObject::Diff(o) => Some(&mut o.position),
+        SceneObject::FileTree(o) => Some(&mut o.position),
+        SceneObject::Gantt(o) => Some(&mut o.position),
+        SceneObject::PieChart(o) => Some(&mut o.position),
+        SceneObject::ScatterPlot(o) => Some(&mut o.position),
+        SceneObject::Marquee(o) => Some(&mut o.position),
+        SceneObject::Spinner(o) => Some(&mut o.position),
+        SceneObject::Connector(o) => Some(&mut o.position),
+        SceneObject::Keycap(o) => Some(&mut o.position),
+        SceneObject::Watermark(o) => Some(&mut o.position),
+        SceneObject::BackgroundEffect(o) => Some(&mut o.position),
+        SceneObject::Clock(o) => Some(&mut o.position),
+        SceneObject::List(o) => Some(&mut o.position),
+        _ => None,
+    }
+}
+
+fn style_mut(obj: &mut SceneObject) -> Option<&mut Style> {
+    match obj {
+        SceneObject::Label(o) => Some(&mut o.style),
+        SceneObject::Header(o) => Some(&mut o.style),
+        SceneObject::Rect(o) => Some(&mut o.style),
+        SceneObject::HLine(o) => Some(&mut o.style),
+        SceneObject::Arrow(o) => Some(&mut o.style),
+        SceneObject::Circle(o) => Some(&mut o.style),
+        SceneObject::Art(o) => Some(&mut o.style),
+        SceneObject::Countdown(o) => Some(&mut o.style),
+        SceneObject::Toc(o) => Some(&mut o.style),
+        SceneObject::Footer(o) => Some(&mut o.style),
+        SceneObject::Gauge(o) => Some(&mut o.style),
+        SceneObject::Heatmap(o) => Some(&mut o.style),
+        SceneObject::Diff(o) => Some(&mut o.style),
+        SceneObject::FileTree(o) => Some(&mut o.style),
+        SceneObject::Gantt(o) => Some(&mut o.style),
+        SceneObject::PieChart(o) => Some(&mut o.style),
+        SceneObject::ScatterPlot(o) => Some(&mut o.style),
+        SceneObject::Marquee(o) => Some(&mut o.style),
+        SceneObject::Spinner(o) => Some(&mut o.style),
+        SceneObject::Connector(o) => Some(&mut o.style),
+        SceneObject::Keycap(o) => Some(&mut o.style),
+        SceneObject::Watermark(o) => Some(&mut o.style),
+        SceneObject::BackgroundEffect(o) => Some(&mut o.style),
+        SceneObject::Clock(o) => Some(&mut o.style),
+        SceneObject::List(o) => Some(&mut o.style),
+        _ => None,
+    }
+}
+
+fn z_order_mut(obj: &mut SceneObject) -> Option<&mut i32> {
+    match obj {
+        SceneObject::Label(o) => Some(&mut o.z_order),
+        SceneObject::Header(o) => Some(&mut o.z_order),
+        SceneObject::Rect(o) => Some(&mut o.z_order),
+        SceneObject::HLine(o) => Some(&mut o.z_order),
+        SceneObject::Arrow(o) => Some(&mut o.z_order),
+        SceneObject::Circle(o) => Some(&mut o.z_order),
+        SceneObject::Art(o) => Some(&mut o.z_order),
+        SceneObject::AnsiArt(o) => Some(&mut o.z_order),
+        SceneObject::Cast(o) => Some(&mut o.z_order),
+        SceneObject::Countdown(o) => Some(&mut o.z_order),
+        SceneObject::Toc(o) => Some(&mut o.z_order),
+        SceneObject::Footer(o) => Some(&mut o.z_order),
+        SceneObject::Gauge(o) => Some(&mut o.z_order),
+        SceneObject::Heatmap(o) => Some(&mut o.z_order),
+        SceneObject::Diff(o) => Some(&mut o.z_order),
+        SceneObject::FileTree(o) => Some(&mut o.z_order),
+        SceneObject::Gantt(o) => Some(&mut o.z_order),
+        SceneObject::PieChart(o) => Some(&mut o.z_order),
+        SceneObject::ScatterPlot(o) => Some(&mut o.z_order),
+        SceneObject::Marquee(o) => Some(&mut o.z_order),
+        SceneObject::Spinner(o) => Some(&mut o.z_order),
+        SceneObject::Connector(o) => Some(&mut o.z_order),
+        SceneObject::Keycap(o) => Some(&mut o.z_order),
+        SceneObject::Watermark(o) => Some(&mut o.z_order),
+        SceneObject::BackgroundEffect(o) => Some(&mut o.z_order),
+        SceneObject::Clock(o) => Some(&mut o.z_order),
+        SceneObject::List(o) => Some(&mut o.z_order),
+        _ => None,
+    }
+}
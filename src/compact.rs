@@ -0,0 +1,310 @@
+//! Compact on-disk form of a [`PlayablePresentation`].
+//!
+//! In memory a frame is a grid of [`Cell`]s (or a list of [`CellChange`]s);
+//! written verbatim that repeats every blank cell and every style. The
+//! serialized form instead carries a `styles` table and run-length encodes
+//! frames, each run naming its style by index:
+//!
+//! ```json
+//! {"contract": {"width": 80, "height": 24},
+//!  "styles": [{}, {"fg": "red", "bold": true}],
+//!  "frames": [
+//!    {"type": "full", "runs": [[" ", 0, 1920]]},
+//!    {"type": "diff", "runs": [[3, 2, "=", 1, 10]]}]}
+//! ```
+//!
+//! - A full run `[ch, style, count]` covers `count` cells row-major from the
+//!   canvas origin, so runs may wrap across rows; a full frame's runs cover
+//!   exactly `width × height` cells.
+//! - A diff run `[x, y, ch, style, count]` sets `count` cells rightwards from
+//!   `(x, y)`. Overlays keep their per-frame changes as diff runs too.
+//!
+//! `PlayablePresentation` (de)serializes through [`Wire`], so every reader —
+//! player, exporters, inspectors — gets ordinary frames back. Files written
+//! before compression (`cells` / `changes`) still load. The document also
+//! carries its `format_version`, checked before anything else is decoded.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{
+    AnimationRegion, AutoAdvanceRegion, Cell, CellChange, ClockRegion, CommandRegion, Frame, LoopRegion,
+    Marker, Metadata, Overlay, PlayablePresentation, Style, StyleRef, TerminalContract, COMPILED_FORMAT_VERSION,
+};
+
+/// `[ch, style, count]`.
+type FullRun = (char, usize, usize);
+
+/// `[x, y, ch, style, count]`.
+type DiffRun = (u16, u16, char, usize, usize);
+
+/// The serialized shape of a [`PlayablePresentation`].
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Wire {
+    #[serde(default = "unversioned")]
+    format_version: u32,
+    contract: TerminalContract,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    styles: Vec<Style>,
+    frames: Vec<WireFrame>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    markers: Vec<Marker>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    commands: Vec<CommandRegion>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    clocks: Vec<ClockRegion>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    loops: Vec<LoopRegion>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    animations: Vec<AnimationRegion>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    auto_advances: Vec<AutoAdvanceRegion>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    durations: Vec<Option<u64>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    on_enter: Vec<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Metadata::is_empty")]
+    metadata: Metadata,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    overlays: Vec<WireOverlay>,
+}
+
+/// An [`Overlay`] with its changes as diff runs under the document's styles.
+#[derive(Serialize, Deserialize)]
+struct WireOverlay {
+    name: String,
+    frames: Vec<(usize, Vec<DiffRun>)>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum WireFrame {
+    Full {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        runs: Option<Vec<FullRun>>,
+        /// Verbatim rows (uncompressed files).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cells: Option<Vec<Vec<Cell>>>,
+    },
+    Diff {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        runs: Option<Vec<DiffRun>>,
+        /// Verbatim changes (uncompressed files).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        changes: Option<Vec<CellChange>>,
+    },
+}
+
+fn unversioned() -> u32 {
+    1
+}
+
+/// Numbers styles in order of first use.
+#[derive(Default)]
+pub(crate) struct StyleTable {
+    styles: Vec<Style>,
+    index: HashMap<StyleRef, usize>,
+}
+
+impl StyleTable {
+    fn intern(&mut self, style: StyleRef) -> usize {
+        *self.index.entry(style.clone()).or_insert_with(|| {
+            self.styles.push(Style::clone(&style));
+            self.styles.len() - 1
+        })
+    }
+}
+
+impl From<PlayablePresentation> for Wire {
+    fn from(p: PlayablePresentation) -> Self {
+        let mut table = StyleTable::default();
+        let frames = p.frames.iter().map(|frame| encode_frame(frame, &mut table)).collect();
+        Wire { frames, ..Wire::without_frames(p, table) }
+    }
+}
+
+impl Wire {
+    /// `p`'s contract and sidecars under the style table `table` built, with
+    /// no frames (the [stream](crate::stream) container stores those apart).
+    pub(crate) fn without_frames(p: PlayablePresentation, mut table: StyleTable) -> Self {
+        let overlays = p
+            .overlays
+            .iter()
+            .map(|o| WireOverlay {
+                name: o.name.clone(),
+                frames: o.frames.iter().map(|(f, changes)| (*f, diff_runs(changes, &mut table))).collect(),
+            })
+            .collect();
+        Wire {
+            format_version: COMPILED_FORMAT_VERSION,
+            contract: p.contract,
+            styles: table.styles,
+            frames: Vec::new(),
+            markers: p.markers,
+            commands: p.commands,
+            clocks: p.clocks,
+            loops: p.loops,
+            animations: p.animations,
+            auto_advances: p.auto_advances,
+            durations: p.durations,
+            on_enter: p.on_enter,
+            metadata: p.metadata,
+            overlays,
+        }
+    }
+
+    /// Refuse a document written by a newer build.
+    pub(crate) fn check_version(&self) -> Result<(), String> {
+        if self.format_version > COMPILED_FORMAT_VERSION {
+            return Err(format!(
+                "this presentation is format_version {}, newer than this bs understands \
+                 ({COMPILED_FORMAT_VERSION}); upgrade bs or recompile the source",
+                self.format_version
+            ));
+        }
+        Ok(())
+    }
+
+    /// The presentation without its frames, and the style table frames index.
+    pub(crate) fn into_skeleton(self) -> Result<(PlayablePresentation, Vec<StyleRef>), String> {
+        let styles: Vec<StyleRef> = self.styles.iter().map(StyleRef::from).collect();
+        let overlays = self
+            .overlays
+            .into_iter()
+            .map(|o| {
+                let frames = o.frames.into_iter().map(|(f, runs)| Ok((f, decode_diff(runs, &styles, f)?)));
+                Ok(Overlay { name: o.name, frames: frames.collect::<Result<_, String>>()? })
+            })
+            .collect::<Result<_, String>>()?;
+        let skeleton = PlayablePresentation {
+            contract: self.contract,
+            frames: Vec::new(),
+            markers: self.markers,
+            commands: self.commands,
+            clocks: self.clocks,
+            loops: self.loops,
+            animations: self.animations,
+            auto_advances: self.auto_advances,
+            durations: self.durations,
+            on_enter: self.on_enter,
+            metadata: self.metadata,
+            overlays,
+        };
+        Ok((skeleton, styles))
+    }
+}
+
+/// Run-length encode `frame`, numbering its styles in `table`.
+pub(crate) fn encode_frame(frame: &Frame, table: &mut StyleTable) -> WireFrame {
+    match frame {
+        Frame::Full { cells } => WireFrame::Full { runs: Some(full_runs(cells, table)), cells: None },
+        Frame::Diff { changes } => WireFrame::Diff { runs: Some(diff_runs(changes, table)), changes: None },
+    }
+}
+
+fn full_runs(cells: &[Vec<Cell>], table: &mut StyleTable) -> Vec<FullRun> {
+    let mut runs: Vec<FullRun> = Vec::new();
+    for cell in cells.iter().flatten() {
+        let style = table.intern(cell.style.clone());
+        match runs.last_mut() {
+            Some((ch, s, n)) if *ch == cell.ch && *s == style => *n += 1,
+            _ => runs.push((cell.ch, style, 1)),
+        }
+    }
+    runs
+}
+
+fn diff_runs(changes: &[CellChange], table: &mut StyleTable) -> Vec<DiffRun> {
+    let mut runs: Vec<DiffRun> = Vec::new();
+    for c in changes {
+        let style = table.intern(c.cell.style.clone());
+        match runs.last_mut() {
+            Some((x, y, ch, s, n))
+                if *y == c.y
+                    && usize::from(*x) + *n == usize::from(c.x)
+                    && *ch == c.cell.ch
+                    && *s == style =>
+            {
+                *n += 1
+            }
+            _ => runs.push((c.x, c.y, c.cell.ch, style, 1)),
+        }
+    }
+    runs
+}
+
+impl TryFrom<Wire> for PlayablePresentation {
+    type Error = String;
+
+    fn try_from(mut w: Wire) -> Result<Self, String> {
+        w.check_version()?;
+        let wire_frames = std::mem::take(&mut w.frames);
+        let (mut p, styles) = w.into_skeleton()?;
+        p.frames = wire_frames
+            .into_iter()
+            .enumerate()
+            .map(|(index, frame)| decode_frame(frame, &styles, &p.contract, index))
+            .collect::<Result<_, _>>()?;
+        Ok(p)
+    }
+}
+
+/// Expand frame `index` of a `contract`-sized deck whose style table is
+/// `styles`.
+pub(crate) fn decode_frame(
+    frame: WireFrame,
+    styles: &[StyleRef],
+    contract: &TerminalContract,
+    index: usize,
+) -> Result<Frame, String> {
+    let width = usize::from(contract.width);
+    let height = usize::from(contract.height);
+    Ok(match frame {
+        WireFrame::Full { cells: Some(cells), .. } => Frame::Full { cells },
+        WireFrame::Full { runs, .. } => {
+            let mut flat = Vec::with_capacity(width * height);
+            for (ch, s, n) in runs.unwrap_or_default() {
+                let cell = Cell { ch, style: style_at(styles, s)? };
+                // Checked before expanding, so a bogus count can't allocate.
+                if flat.len().checked_add(n).is_none_or(|end| end > width * height) {
+                    return Err(format!("frame {index}: runs cover more than the {width}x{height} canvas"));
+                }
+                flat.extend(std::iter::repeat_n(cell, n));
+            }
+            if flat.len() != width * height {
+                return Err(format!(
+                    "frame {index}: runs cover {} cells, canvas is {width}x{height}",
+                    flat.len()
+                ));
+            }
+            let cells = if width == 0 {
+                vec![Vec::new(); height]
+            } else {
+                flat.chunks(width).map(<[Cell]>::to_vec).collect()
+            };
+            Frame::Full { cells }
+        }
+        WireFrame::Diff { changes: Some(changes), .. } => Frame::Diff { changes },
+        WireFrame::Diff { runs, .. } => Frame::Diff { changes: decode_diff(runs.unwrap_or_default(), styles, index)? },
+    })
+}
+
+/// Expand the diff runs of frame `index`.
+fn decode_diff(runs: Vec<DiffRun>, styles: &[StyleRef], index: usize) -> Result<Vec<CellChange>, String> {
+    let mut changes = Vec::new();
+    for (x, y, ch, s, n) in runs {
+        let cell = Cell { ch, style: style_at(styles, s)? };
+        if usize::from(x).checked_add(n).is_none_or(|end| end > usize::from(u16::MAX) + 1) {
+            return Err(format!("frame {index}: diff run past column 65535"));
+        }
+        for dx in 0..n {
+            changes.push(CellChange { x: x + dx as u16, y, cell: cell.clone() });
+        }
+    }
+    Ok(changes)
+}
+
+fn style_at(styles: &[StyleRef], i: usize) -> Result<StyleRef, String> {
+    styles.get(i).cloned().ok_or_else(|| format!("style index {i} out of range ({} styles)", styles.len()))
+}
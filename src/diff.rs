@@ -0,0 +1,270 @@
+//! Deck comparison (`bs diff a.json b.json`).
+//!
+//! Two views of what changed between two versions of a deck, for reviewing a
+//! deck change the way code is reviewed:
+//!
+//! - **Objects** (only when both inputs are sources): every object of `a` is
+//!   paired with an identical one in `b`, then with one that differs *only* in
+//!   where it sits (`position`, an `HLine`'s `y`/`x_start`/`x_end`, an
+//!   `Arrow`'s endpoints) — a **move**. Whatever is left over was removed from
+//!   `a` or added in `b`; an edited object therefore shows as a remove + add.
+//! - **Frames**: the compiled grids are compared cell by cell (character and
+//!   style), frame by frame, so a change that no object census would catch —
+//!   a retimed range, a restyled label — still shows up where it is visible.
+//!
+//! `--side-by-side` additionally prints each differing frame as `a │ b` with
+//! the changed rows marked.
+
+use serde_json::Value;
+
+use anyhow::Result;
+
+use crate::engine::source::{Coordinate, SceneObject, SourcePresentation};
+use crate::export::GridReplay;
+use crate::renderer::ansi::grid_to_plain;
+use crate::types::{str_width, Cell, PlayablePresentation};
+
+/// Top-level object fields that say *where* an object is rather than *what*
+/// it is; objects that differ only in these have moved.
+const LOCATION_KEYS: &[&str] = &["position", "x_start", "x_end", "y", "x1", "y1", "x2", "y2"];
+
+/// One object-level difference.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObjectChange {
+    /// `objects[index]` of `b` has no counterpart in `a`.
+    Added { index: usize, kind: &'static str },
+    /// `objects[index]` of `a` has no counterpart in `b`.
+    Removed { index: usize, kind: &'static str },
+    /// `objects[from]` of `a` is `objects[to]` of `b` at a new location;
+    /// `changes` lists each location coordinate that differs as
+    /// `(path, old, new)`.
+    Moved { from: usize, to: usize, kind: &'static str, changes: Vec<(&'static str, String, String)> },
+}
+
+/// How many cells of one frame differ (`cells` counts cells present in only
+/// one grid too, when the canvases differ in size).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameDiff {
+    pub frame: usize,
+    pub cells: usize,
+}
+
+/// Everything `bs diff` reports.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeckDiff {
+    /// Canvas sizes `(a, b)` when they differ.
+    pub canvas: Option<((u16, u16), (u16, u16))>,
+    /// Frame counts `(a, b)`.
+    pub frame_counts: (usize, usize),
+    /// Object changes; `None` when either input is a compiled file.
+    pub objects: Option<Vec<ObjectChange>>,
+    /// Frames that differ visually (including frames only one deck has).
+    pub frames: Vec<FrameDiff>,
+}
+
+impl DeckDiff {
+    /// True when the two decks look and are built the same.
+    pub fn is_empty(&self) -> bool {
+        self.canvas.is_none()
+            && self.frame_counts.0 == self.frame_counts.1
+            && self.objects.as_ref().is_none_or(|o| o.is_empty())
+            && self.frames.is_empty()
+    }
+}
+
+fn shape(obj: &SceneObject) -> Value {
+    let mut v = serde_json::to_value(obj).expect("scene objects always serialize");
+    if let Value::Object(map) = &mut v {
+        for key in LOCATION_KEYS {
+            map.remove(*key);
+        }
+    }
+    v
+}
+
+fn fmt_coord(c: &Coordinate) -> String {
+    match c {
+        Coordinate::Fixed(v) => format!("{v}"),
+        Coordinate::Animated { from, to, anim } => format!("{from}..{to} (anim {anim})"),
+    }
+}
+
+fn location_changes(a: &SceneObject, b: &SceneObject) -> Vec<(&'static str, String, String)> {
+    let is_location = |path: &str| LOCATION_KEYS.iter().any(|k| path == *k || path.starts_with(&format!("{k}.")));
+    a.coordinates()
+        .into_iter()
+        .zip(b.coordinates())
+        .filter(|((path, ca), (_, cb))| is_location(path) && ca != cb)
+        .map(|((path, ca), (_, cb))| (path, fmt_coord(ca), fmt_coord(cb)))
+        .collect()
+}
+
+/// Pair up the objects of `a` and `b` (exact matches first, then moves) and
+/// report what is left: removals, additions and moves, in that order.
+pub fn diff_objects(a: &SourcePresentation, b: &SourcePresentation) -> Vec<ObjectChange> {
+    let full = |o: &SceneObject| serde_json::to_value(o).expect("scene objects always serialize");
+    let (a_full, b_full): (Vec<Value>, Vec<Value>) =
+        (a.objects.iter().map(full).collect(), b.objects.iter().map(full).collect());
+    let mut a_used = vec![false; a.objects.len()];
+    let mut b_used = vec![false; b.objects.len()];
+
+    for (i, va) in a_full.iter().enumerate() {
+        if let Some(j) = (0..b_full.len()).find(|&j| !b_used[j] && b_full[j] == *va) {
+            a_used[i] = true;
+            b_used[j] = true;
+        }
+    }
+
+    let mut moves = Vec::new();
+    let b_shapes: Vec<Value> = b.objects.iter().map(shape).collect();
+    for (i, obj) in a.objects.iter().enumerate() {
+        if a_used[i] {
+            continue;
+        }
+        let sa = shape(obj);
+        if let Some(j) = (0..b_shapes.len()).find(|&j| !b_used[j] && b_shapes[j] == sa) {
+            a_used[i] = true;
+            b_used[j] = true;
+            moves.push(ObjectChange::Moved {
+                from: i,
+                to: j,
+                kind: obj.kind(),
+                changes: location_changes(obj, &b.objects[j]),
+            });
+        }
+    }
+
+    let removed = a.objects.iter().enumerate().filter(|(i, _)| !a_used[*i]);
+    let added = b.objects.iter().enumerate().filter(|(j, _)| !b_used[*j]);
+    removed
+        .map(|(index, o)| ObjectChange::Removed { index, kind: o.kind() })
+        .chain(added.map(|(index, o)| ObjectChange::Added { index, kind: o.kind() }))
+        .chain(moves)
+        .collect()
+}
+
+/// Number of cells that differ between two grids; where the grids differ in
+/// size, cells present in only one of them count as different.
+pub fn cell_difference(a: &[Vec<Cell>], b: &[Vec<Cell>]) -> usize {
+    let rows = a.len().max(b.len());
+    let mut n = 0;
+    for y in 0..rows {
+        let (ra, rb) = (a.get(y).map_or(&[][..], |r| &r[..]), b.get(y).map_or(&[][..], |r| &r[..]));
+        for x in 0..ra.len().max(rb.len()) {
+            if ra.get(x) != rb.get(x) {
+                n += 1;
+            }
+        }
+    }
+    n
+}
+
+/// Compare the visible grids of every frame; a frame only one deck has
+/// counts all of its cells. Both decks are replayed once, side by side.
+pub fn diff_frames(a: &PlayablePresentation, b: &PlayablePresentation) -> Vec<FrameDiff> {
+    let frames = a.frames.len().max(b.frames.len());
+    let (mut ra, mut rb) = (GridReplay::new(a), GridReplay::new(b));
+    (0..frames)
+        .map(|frame| {
+            let cells = cell_difference(ra.next_grid().unwrap_or(&[]), rb.next_grid().unwrap_or(&[]));
+            FrameDiff { frame, cells }
+        })
+        .filter(|d| d.cells > 0)
+        .collect()
+}
+
+/// Compare two loaded decks. Object changes are only computed when both
+/// sources are given.
+pub fn diff_decks(
+    a: &PlayablePresentation,
+    b: &PlayablePresentation,
+    sources: Option<(&SourcePresentation, &SourcePresentation)>,
+) -> DeckDiff {
+    let size = |p: &PlayablePresentation| (p.contract.width, p.contract.height);
+    DeckDiff {
+        canvas: (size(a) != size(b)).then(|| (size(a), size(b))),
+        frame_counts: (a.frames.len(), b.frames.len()),
+        objects: sources.map(|(sa, sb)| diff_objects(sa, sb)),
+        frames: diff_frames(a, b),
+    }
+}
+
+/// Render `d` as the report `bs diff` prints.
+pub fn format_diff(d: &DeckDiff) -> String {
+    if d.is_empty() {
+        return "no differences\n".into();
+    }
+    let mut out = String::new();
+    if let Some(((aw, ah), (bw, bh))) = d.canvas {
+        out += &format!("canvas: {aw}x{ah} -> {bw}x{bh}\n");
+    }
+    if d.frame_counts.0 != d.frame_counts.1 {
+        out += &format!("frame count: {} -> {}\n", d.frame_counts.0, d.frame_counts.1);
+    }
+    if let Some(objects) = d.objects.as_ref().filter(|o| !o.is_empty()) {
+        out += "objects:\n";
+        for change in objects {
+            out += &match change {
+                ObjectChange::Removed { index, kind } => format!("  - {kind} objects[{index}]\n"),
+                ObjectChange::Added { index, kind } => format!("  + {kind} objects[{index}]\n"),
+                ObjectChange::Moved { from, to, kind, changes } => {
+                    let what: Vec<String> =
+                        changes.iter().map(|(p, old, new)| format!("{p} {old} -> {new}")).collect();
+                    format!("  ~ {kind} objects[{from}] -> objects[{to}]: {}\n", what.join(", "))
+                }
+            };
+        }
+    }
+    if !d.frames.is_empty() {
+        let total = d.frame_counts.0.max(d.frame_counts.1);
+        out += &format!("frames: {} of {total} differ\n", d.frames.len());
+        for f in &d.frames {
+            out += &format!("  frame {:>4}: {} cells\n", f.frame + 1, f.cells);
+        }
+    }
+    out
+}
+
+/// Two grids next to each other (`a │ b`), plain characters only; rows that
+/// differ are marked with `*`.
+pub fn side_by_side(a: &[Vec<Cell>], b: &[Vec<Cell>]) -> String {
+    let width = a.iter().map(|r| r.len()).max().unwrap_or(0);
+    let text = |row: Option<&Vec<Cell>>| {
+        row.map_or(String::new(), |r| grid_to_plain(std::slice::from_ref(r)))
+    };
+    let mut out = String::new();
+    for y in 0..a.len().max(b.len()) {
+        let mark = if a.get(y) != b.get(y) { '*' } else { ' ' };
+        let left = text(a.get(y));
+        // Pad by columns, not chars: a wide glyph is one char over two cells.
+        let pad = " ".repeat(width.saturating_sub(str_width(&left)));
+        out += &format!("{mark} {left}{pad} │ {}\n", text(b.get(y)));
+    }
+    out
+}
+
+/// Print the diff between the decks at `a_path` and `b_path` (each a source
+/// or a compiled file), plus side-by-side renders of differing frames when
+/// asked.
+pub fn diff_files(a_path: &str, b_path: &str, side_by_side_frames: bool) -> Result<()> {
+    let a = crate::pipeline::load_deck(a_path)?;
+    let b = crate::pipeline::load_deck(b_path)?;
+    let sources = a.source.as_ref().zip(b.source.as_ref());
+    let d = diff_decks(&a.playable, &b.playable, sources);
+    println!("--- {a_path}\n+++ {b_path}");
+    print!("{}", format_diff(&d));
+    if side_by_side_frames {
+        let (mut ra, mut rb) = (GridReplay::new(&a.playable), GridReplay::new(&b.playable));
+        let mut differing = d.frames.iter().map(|f| f.frame).peekable();
+        for frame in 0.. {
+            let Some(&next) = differing.peek() else { break };
+            let (ga, gb) = (ra.next_grid().unwrap_or(&[]), rb.next_grid().unwrap_or(&[]));
+            if frame == next {
+                differing.next();
+                println!("\nframe {}:", frame + 1);
+                print!("{}", side_by_side(ga, gb));
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,9 +1,219 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::style;
 use serde::{Deserialize, Serialize};
 
+use crate::engine::source::SourcePresentation;
+use crate::engine::{LintLevel, LintRule};
+use crate::types::Color;
+
+/// The names a project config, shared with a deck repository, goes by: looked
+/// for in the deck's directory and then each one above it, and laid over the
+/// user config. `.ascii-presenter.json` is the name the format was first
+/// given; `.bs.json`, after the binary, wins when a directory has both.
+pub const PROJECT_FILES: [&str; 2] = [".bs.json", ".ascii-presenter.json"];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EditorConfig {
+    /// The preset the bindings start from; `key_bindings` entries in the file
+    /// are laid over it, so a config can name a keymap and change just a few.
+    #[serde(default)]
+    pub keymap: Keymap,
     pub key_bindings: KeyBindings,
+    /// Cells moved (in `SelectedObject`) or resized (in `ResizeObject`) per
+    /// arrow press with the `coarse_modifier` held.
+    #[serde(default = "default_coarse_step")]
+    pub coarse_step: u16,
+    /// Colours for the editor's own chrome.
+    #[serde(default)]
+    pub theme: Theme,
+    /// Lint levels for every deck edited with this config, under each deck's
+    /// own `lint` map.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub lint: BTreeMap<LintRule, LintLevel>,
+    /// The file this config was loaded from, and is saved back to; `None`
+    /// means the default `~/.config/bs/editor.json`.
+    #[serde(skip)]
+    pub file: Option<PathBuf>,
+    /// The project config (one of [`PROJECT_FILES`]) laid over this config, if any.
+    #[serde(skip)]
+    pub project: Option<PathBuf>,
+    /// With a project laid over it, the user's own config beneath — what
+    /// [`Self::save`] writes back.
+    #[serde(skip)]
+    beneath: Option<Box<Beneath>>,
+}
+
+/// The user's config under a project config, and what the two made together
+/// when the deck was opened.
+#[derive(Debug, Clone)]
+struct Beneath {
+    user: EditorConfig,
+    layered: serde_json::Value,
+}
+
+/// The nearest of the [`PROJECT_FILES`] in `deck`'s directory or one above it.
+fn project_file(deck: &Path) -> Option<PathBuf> {
+    let deck = std::path::absolute(deck).ok()?;
+    deck.parent()?
+        .ancestors()
+        .flat_map(|dir| PROJECT_FILES.map(|name| dir.join(name)))
+        .find(|path| path.is_file())
+}
+
+/// Carry the changes from `before` to `after` onto `base`, object entry by
+/// entry: only what changed is written, so whatever else `base` holds stays.
+fn carry(base: &mut serde_json::Value, before: &serde_json::Value, after: &serde_json::Value) {
+    if before == after {
+        return;
+    }
+    match (base, before, after) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(before), serde_json::Value::Object(after)) => {
+            for (key, value) in after.iter().filter(|(key, value)| before.get(*key) != Some(*value)) {
+                let old = before.get(key).unwrap_or(&serde_json::Value::Null);
+                carry(base.entry(key.clone()).or_insert(serde_json::Value::Null), old, value);
+            }
+            for key in before.keys().filter(|key| !after.contains_key(*key)) {
+                base.remove(key);
+            }
+        }
+        (base, _, after) => *base = after.clone(),
+    }
+}
+
+/// Lay `over` onto `base`, merging objects key by key.
+fn merge(base: &mut serde_json::Value, over: serde_json::Value) {
+    match (base, over) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(over)) => {
+            for (key, value) in over {
+                merge(base.entry(key).or_insert(serde_json::Value::Null), value);
+            }
+        }
+        (base, over) => *base = over,
+    }
+}
+
+/// Colours for the menubar, borders, selection highlight and secondary text,
+/// written as in a deck (`"red"`, `{"r": 40, "g": 40, "b": 90}`). Anything left unset keeps the terminal's own
+/// reverse video and dim, which can be hard to read on a light background.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Theme {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub menubar_fg: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub menubar_bg: Option<Color>,
+    /// The right panel's and the canvas's borders.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub border: Option<Color>,
+    /// Selected rows, active fields and the current frame; with neither set
+    /// these are drawn in reverse video.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selection_fg: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selection_bg: Option<Color>,
+    /// Hints, headings and the status line, in place of the dim attribute.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dim: Option<Color>,
+}
+
+impl Theme {
+    /// Selected rows and active fields.
+    pub fn selection(&self) -> style::SetStyle {
+        styled(&self.selection_fg, &self.selection_bg, Some(style::Attribute::Reverse))
+    }
+
+    /// Secondary text.
+    pub fn dimmed(&self) -> style::SetStyle {
+        styled(&self.dim, &None, Some(style::Attribute::Dim))
+    }
+
+    /// Panel borders; plain when unset.
+    pub fn borders(&self) -> style::SetStyle {
+        styled(&self.border, &None, None)
+    }
+
+    /// The menubar's text, with the `[key]`s bold on top of it; unset, the
+    /// text between keys is dim.
+    pub fn menubar(&self) -> style::ContentStyle {
+        styled(&self.menubar_fg, &self.menubar_bg, Some(style::Attribute::Dim)).0
+    }
+}
+
+/// `fg` and `bg`, or the `fallback` attribute when neither is set.
+fn styled(fg: &Option<Color>, bg: &Option<Color>, fallback: Option<style::Attribute>) -> style::SetStyle {
+    let mut cs = style::ContentStyle {
+        foreground_color: fg.as_ref().map(crate::player::to_ct_color),
+        background_color: bg.as_ref().map(crate::player::to_ct_color),
+        ..Default::default()
+    };
+    if let (None, None, Some(attr)) = (fg, bg, fallback) {
+        cs.attributes.set(attr);
+    }
+    style::SetStyle(cs)
+}
+
+/// A preset of key bindings, chosen with `"keymap"` in the config.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Keymap {
+    #[default]
+    Default,
+    /// `h`/`l` step frames, `j`/`k` move through lists, `w`/`b` jump past
+    /// animations, `y`/`p` copy and paste, `/` finds. Layers and the
+    /// presentations hub move to `L` and `P` to make room.
+    Vim,
+    /// `Ctrl-f`/`Ctrl-b` step frames, `Ctrl-n`/`Ctrl-p` move through lists,
+    /// `Alt-f`/`Alt-b` jump past animations, `Ctrl-g` cancels, `Alt-w`/`Ctrl-y`
+    /// copy and paste, `Alt-%` finds and replaces.
+    Emacs,
+}
+
+impl Keymap {
+    pub const ALL: [Keymap; 3] = [Keymap::Default, Keymap::Vim, Keymap::Emacs];
+
+    /// The preset's changes from the default bindings, as `(name, chord)`.
+    fn overrides(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Keymap::Default => &[],
+            Keymap::Vim => &[
+                ("next_frame", "l"),
+                ("prev_frame", "h"),
+                ("move_down", "j"),
+                ("move_up", "k"),
+                ("anim_skip_next", "w"),
+                ("anim_skip_prev", "b"),
+                ("copy", "y"),
+                ("paste", "p"),
+                ("find_replace", "/"),
+                ("layers_menu", "L"),
+                ("presentations_menu", "P"),
+            ],
+            Keymap::Emacs => &[
+                ("next_frame", "Ctrl-f"),
+                ("prev_frame", "Ctrl-b"),
+                ("move_down", "Ctrl-n"),
+                ("move_up", "Ctrl-p"),
+                ("anim_skip_next", "Alt-f"),
+                ("anim_skip_prev", "Alt-b"),
+                ("cancel", "Ctrl-g"),
+                ("copy", "Alt-w"),
+                ("paste", "Ctrl-y"),
+                ("find_replace", "Alt-%"),
+            ],
+        }
+    }
+
+    /// The preset's full set of bindings.
+    pub fn bindings(self) -> KeyBindings {
+        let mut bindings = EditorConfig::default().key_bindings;
+        for (name, chord) in self.overrides() {
+            bindings.set(name, chord);
+        }
+        bindings
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +232,18 @@ pub struct KeyBindings {
     #[serde(default = "default_paste")]
     pub paste: String,
     pub save: String,
+    /// Stamp the selected group: a deep copy of it and its members, placed just
+    /// right of it and selected, so repeated presses lay out a row of copies.
+    #[serde(default = "default_stamp_group")]
+    pub stamp_group: String,
+    /// Stamp the selected object across a frame range: one single-frame copy
+    /// per frame, optionally stepped a few cells each frame.
+    #[serde(default = "default_stamp_frames")]
+    pub stamp_frames: String,
+    /// Group the objects toggled in the select flow (or the highlighted one)
+    /// straight from the canvas, without the Add-Object member picker.
+    #[serde(default = "default_group_selection")]
+    pub group_selection: String,
     /// Save under a new filename (prompts for the path).
     #[serde(default = "default_save_as")]
     pub save_as: String,
@@ -34,6 +256,10 @@ pub struct KeyBindings {
     pub fullscreen: String,
     pub animate: String,
     pub insert_newline: String,
+    /// While editing a Label's text (or any text property) or a Table cell's
+    /// content: suspend the editor and open the value in `$VISUAL`/`$EDITOR`.
+    #[serde(default = "default_external_editor")]
+    pub external_editor: String,
     // Table-specific bindings (active only when editing a Table object or in table modes)
     #[serde(default = "default_table_add_col_after")]
     pub table_add_col_after: String,
@@ -51,9 +277,24 @@ pub struct KeyBindings {
     /// Open the presentation settings (frame size) from Normal mode.
     #[serde(default = "default_open_settings")]
     pub open_settings: String,
+    /// From the selected-object menu: type a label's text (or a table's cells)
+    /// in place on the canvas.
+    #[serde(default = "default_edit_in_place")]
+    pub edit_in_place: String,
+    /// On a Coordinate property in the properties panel: pick its value with a
+    /// crosshair on the canvas.
+    #[serde(default = "default_pick_coordinate")]
+    pub pick_coordinate: String,
+    /// Held with an arrow to move or resize by `coarse_step` cells instead of
+    /// one: `Ctrl` or `Alt`.
+    #[serde(default = "default_coarse_modifier")]
+    pub coarse_modifier: String,
     /// Enter resize mode (arrow-key resize) from the selected-object menu.
     #[serde(default = "default_resize_object")]
     pub resize_object: String,
+    /// Find and replace across label, header and table text, a match at a time.
+    #[serde(default = "default_find_replace")]
+    pub find_replace: String,
     /// Open the frame operations sub-menu (add/copy/delete/move) from Normal.
     #[serde(default = "default_frame_menu")]
     pub frame_menu: String,
@@ -69,6 +310,13 @@ pub struct KeyBindings {
     /// Within the frame sub-menu: move (relocate) the current frame.
     #[serde(default = "default_frame_move")]
     pub frame_move: String,
+    /// With a frame range selected: duplicate it straight after its last frame.
+    #[serde(default = "default_frame_duplicate")]
+    pub frame_duplicate: String,
+    /// With a frame range selected: keep only it, deleting every frame before
+    /// and after.
+    #[serde(default = "default_frame_trim")]
+    pub frame_trim: String,
     /// Within the frame sub-menu: overlay (paste) the current frame's objects
     /// on top of another existing frame.
     #[serde(default = "default_frame_overlay")]
@@ -83,6 +331,18 @@ pub struct KeyBindings {
     /// delay (the auto-advance / auto-transition action).
     #[serde(default = "default_frame_auto")]
     pub frame_auto: String,
+    /// Within the frame sub-menu: set how long the current frame shows (its
+    /// per-frame duration metadata).
+    #[serde(default = "default_frame_duration")]
+    pub frame_duration: String,
+    /// Within the frame sub-menu: name the section the current frame starts
+    /// (what `toc` objects list).
+    #[serde(default = "default_frame_section")]
+    pub frame_section: String,
+    /// Within the frame sub-menu: ripple-shift every frame range from the
+    /// current frame onward by a typed ±N.
+    #[serde(default = "default_frame_ripple")]
+    pub frame_ripple: String,
     /// While placing a moved frame: drop it *before* the shown frame
     /// (Enter drops it after).
     #[serde(default = "default_frame_move_before")]
@@ -117,6 +377,21 @@ pub struct KeyBindings {
     /// Shift+→.
     #[serde(default = "default_anim_skip_next")]
     pub anim_skip_next: String,
+    /// Open the layer panel from Normal mode.
+    #[serde(default = "default_layers_menu")]
+    pub layers_menu: String,
+    /// In the layer panel: show/hide the highlighted layer.
+    #[serde(default = "default_layer_toggle_visible")]
+    pub layer_toggle_visible: String,
+    /// In the layer panel: lock/unlock the highlighted layer.
+    #[serde(default = "default_layer_toggle_lock")]
+    pub layer_toggle_lock: String,
+    /// Open the lanes view (one frame-range bar per object) from Normal mode.
+    #[serde(default = "default_lanes_view")]
+    pub lanes_view: String,
+    /// Open the diagnostics panel (validation and lint findings) from Normal mode.
+    #[serde(default = "default_diagnostics_menu")]
+    pub diagnostics_menu: String,
 }
 
 fn default_table_add_col_after() -> String { "Alt-a".into() }
@@ -127,6 +402,10 @@ fn default_table_add_list() -> String { "l".into() }
 fn default_table_edit_cell_style() -> String { "s".into() }
 fn default_open_settings() -> String { "g".into() }
 fn default_resize_object() -> String { "r".into() }
+fn default_edit_in_place() -> String { "i".into() }
+fn default_pick_coordinate() -> String { "p".into() }
+fn default_coarse_modifier() -> String { "Ctrl".into() }
+fn default_coarse_step() -> u16 { 5 }
 fn default_fullscreen() -> String { "F".into() }
 fn default_copy() -> String { "c".into() }
 // A plain capital `S` (like `F` for fullscreen): reliably reported by every
@@ -134,15 +413,25 @@ fn default_copy() -> String { "c".into() }
 // terminal supported keyboard-enhancement, so it silently did nothing elsewhere.
 fn default_save_as() -> String { "S".into() }
 fn default_paste() -> String { "v".into() }
+fn default_stamp_group() -> String { "t".into() }
+fn default_stamp_frames() -> String { "m".into() }
+fn default_group_selection() -> String { "G".into() }
+fn default_external_editor() -> String { "Ctrl-e".into() }
+fn default_find_replace() -> String { "R".into() }
 fn default_frame_menu() -> String { "f".into() }
 fn default_frame_add() -> String { "a".into() }
 fn default_frame_copy() -> String { "c".into() }
 fn default_frame_delete() -> String { "d".into() }
 fn default_frame_move() -> String { "m".into() }
+fn default_frame_duplicate() -> String { "D".into() }
+fn default_frame_trim() -> String { "k".into() }
 fn default_frame_overlay() -> String { "o".into() }
 fn default_frame_jump() -> String { "j".into() }
 fn default_frame_select() -> String { "s".into() }
 fn default_frame_auto() -> String { "t".into() }
+fn default_frame_duration() -> String { "u".into() }
+fn default_frame_section() -> String { "n".into() }
+fn default_frame_ripple() -> String { "r".into() }
 fn default_frame_move_before() -> String { "b".into() }
 fn default_presentations_menu() -> String { "p".into() }
 fn default_presentation_open() -> String { "o".into() }
@@ -155,10 +444,16 @@ fn default_frame_clip_paste() -> String { "p".into() }
 // the animation-skip jump usable everywhere.
 fn default_anim_skip_prev() -> String { "[".into() }
 fn default_anim_skip_next() -> String { "]".into() }
+fn default_layers_menu() -> String { "l".into() }
+fn default_layer_toggle_visible() -> String { "v".into() }
+fn default_layer_toggle_lock() -> String { "l".into() }
+fn default_diagnostics_menu() -> String { "d".into() }
+fn default_lanes_view() -> String { "T".into() }
 
 impl Default for EditorConfig {
     fn default() -> Self {
         EditorConfig {
+            keymap: Keymap::Default,
             key_bindings: KeyBindings {
                 next_frame: "Right".into(),
                 prev_frame: "Left".into(),
@@ -168,6 +463,9 @@ impl Default for EditorConfig {
                 delete_object: "d".into(),
                 copy: default_copy(),
                 paste: default_paste(),
+                stamp_group: default_stamp_group(),
+                stamp_frames: default_stamp_frames(),
+                group_selection: default_group_selection(),
                 save: "Ctrl-s".into(),
                 save_as: default_save_as(),
                 quit: "q".into(),
@@ -178,6 +476,7 @@ impl Default for EditorConfig {
                 fullscreen: default_fullscreen(),
                 animate: "a".into(),
                 insert_newline: "Alt-Enter".into(),
+                external_editor: default_external_editor(),
                 table_add_col_after: default_table_add_col_after(),
                 table_add_col_before: default_table_add_col_before(),
                 table_remove_col: default_table_remove_col(),
@@ -186,15 +485,24 @@ impl Default for EditorConfig {
                 table_edit_cell_style: default_table_edit_cell_style(),
                 open_settings: default_open_settings(),
                 resize_object: default_resize_object(),
+                edit_in_place: default_edit_in_place(),
+                pick_coordinate: default_pick_coordinate(),
+                coarse_modifier: default_coarse_modifier(),
+                find_replace: default_find_replace(),
                 frame_menu: default_frame_menu(),
                 frame_add: default_frame_add(),
                 frame_copy: default_frame_copy(),
                 frame_delete: default_frame_delete(),
                 frame_move: default_frame_move(),
+                frame_duplicate: default_frame_duplicate(),
+                frame_trim: default_frame_trim(),
                 frame_overlay: default_frame_overlay(),
                 frame_jump: default_frame_jump(),
                 frame_select: default_frame_select(),
                 frame_auto: default_frame_auto(),
+                frame_duration: default_frame_duration(),
+                frame_section: default_frame_section(),
+                frame_ripple: default_frame_ripple(),
                 frame_move_before: default_frame_move_before(),
                 presentations_menu: default_presentations_menu(),
                 presentation_open: default_presentation_open(),
@@ -204,16 +512,81 @@ impl Default for EditorConfig {
                 frame_clip_paste: default_frame_clip_paste(),
                 anim_skip_prev: default_anim_skip_prev(),
                 anim_skip_next: default_anim_skip_next(),
+                layers_menu: default_layers_menu(),
+                layer_toggle_visible: default_layer_toggle_visible(),
+                layer_toggle_lock: default_layer_toggle_lock(),
+                diagnostics_menu: default_diagnostics_menu(),
+                lanes_view: default_lanes_view(),
             },
+            coarse_step: default_coarse_step(),
+            theme: Theme::default(),
+            lint: BTreeMap::new(),
+            file: None,
+            project: None,
+            beneath: None,
         }
     }
 }
 
 impl EditorConfig {
+    /// Load an explicitly chosen config file (`bs --config PATH`). Unlike
+    /// [`Self::load`], a missing or invalid file is an error: the user asked
+    /// for this one.
+    pub fn load_file(path: &Path) -> anyhow::Result<Self> {
+        use anyhow::Context;
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config {}", path.display()))?;
+        let config = Self::from_json(&json).with_context(|| format!("Failed to parse config {}", path.display()))?;
+        Ok(EditorConfig { file: Some(path.to_path_buf()), ..config })
+    }
+
+    /// Parse a config file's JSON: the `keymap` preset's bindings with the
+    /// file's own `key_bindings` entries laid over them.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let mut value: serde_json::Value = serde_json::from_str(json)?;
+        let keymap: Keymap = match value.get("keymap") {
+            Some(k) => serde_json::from_value(k.clone())?,
+            None => Keymap::Default,
+        };
+        if let (serde_json::Value::Object(config), Ok(serde_json::Value::Object(mut bindings))) =
+            (&mut value, serde_json::to_value(keymap.bindings()))
+        {
+            if let Some(serde_json::Value::Object(own)) = config.get("key_bindings") {
+                bindings.extend(own.clone());
+            }
+            config.insert("key_bindings".into(), serde_json::Value::Object(bindings));
+        }
+        serde_json::from_value(value)
+    }
+
+    /// Write the config back to the file it came from (the default path if it
+    /// wasn't loaded from one), creating the directory if needed. Returns the
+    /// path written. Under a project config only the user's own layer is
+    /// written: what it held, with the changes made since the deck opened —
+    /// never the project's settings.
+    pub fn save(&self) -> anyhow::Result<PathBuf> {
+        use anyhow::Context;
+        let path = self.file.clone().unwrap_or_else(Self::config_path);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let value = match &self.beneath {
+            Some(beneath) => {
+                let mut user = serde_json::to_value(&beneath.user)?;
+                carry(&mut user, &beneath.layered, &serde_json::to_value(self)?);
+                user
+            }
+            None => serde_json::to_value(self)?,
+        };
+        let json = serde_json::to_string_pretty(&value)?;
+        std::fs::write(&path, json + "\n").with_context(|| format!("Failed to write config {}", path.display()))?;
+        Ok(path)
+    }
+
     pub fn load() -> Self {
         let config_path = Self::config_path();
         match std::fs::read_to_string(&config_path) {
-            Ok(json) => match serde_json::from_str(&json) {
+            Ok(json) => match Self::from_json(&json) {
                 Ok(config) => config,
                 Err(e) => {
                     eprintln!("Warning: invalid editor config ({e}), using defaults");
@@ -224,9 +597,52 @@ impl EditorConfig {
         }
     }
 
-    fn config_path() -> std::path::PathBuf {
+    /// This config with the project config for the deck at `deck` — the
+    /// nearest of the [`PROJECT_FILES`] in its directory or above — laid over it, or
+    /// unchanged when there is none.
+    pub fn for_deck(&self, deck: &str) -> anyhow::Result<Self> {
+        use anyhow::Context;
+        let Some(path) = project_file(Path::new(deck)) else {
+            return Ok(self.clone());
+        };
+        let json = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read project config {}", path.display()))?;
+        let config = self.layered(&json).with_context(|| format!("Failed to parse project config {}", path.display()))?;
+        let user = EditorConfig { project: None, beneath: None, ..self.clone() };
+        let beneath = Beneath { user, layered: serde_json::to_value(&config)? };
+        Ok(EditorConfig { project: Some(path), beneath: Some(Box::new(beneath)), ..config })
+    }
+
+    /// Lay a project config's JSON over this config: objects (`theme`,
+    /// `lint`, `key_bindings`) merge entry by entry, anything else is
+    /// replaced. Only the bindings this config changed from its keymap are
+    /// carried over, so a project `keymap` still applies beneath them.
+    pub fn layered(&self, json: &str) -> serde_json::Result<Self> {
+        let mut value = serde_json::to_value(self)?;
+        let preset = serde_json::to_value(self.keymap.bindings())?;
+        if let Some(serde_json::Value::Object(own)) = value.get_mut("key_bindings") {
+            own.retain(|name, chord| preset.get(name) != Some(chord));
+        }
+        merge(&mut value, serde_json::from_str(json)?);
+        let config = Self::from_json(&value.to_string())?;
+        Ok(EditorConfig { file: self.file.clone(), ..config })
+    }
+
+    /// `source` with this config's lint levels filled in under its own.
+    pub fn lint_source<'a>(&self, source: &'a SourcePresentation) -> Cow<'a, SourcePresentation> {
+        if self.lint.is_empty() {
+            return Cow::Borrowed(source);
+        }
+        let mut source = source.clone();
+        for (rule, level) in &self.lint {
+            source.lint.entry(*rule).or_insert(*level);
+        }
+        Cow::Owned(source)
+    }
+
+    fn config_path() -> PathBuf {
         let home = std::env::var("HOME").unwrap_or_else(|_| ".".into());
-        let mut path = std::path::PathBuf::from(home);
+        let mut path = PathBuf::from(home);
         path.push(".config");
         path.push("bs");
         path.push("editor.json");
@@ -234,6 +650,159 @@ impl EditorConfig {
     }
 }
 
+impl KeyBindings {
+    /// Every binding as `(name, chord)`, sorted by name — the names are the
+    /// config file's keys.
+    pub fn entries(&self) -> Vec<(String, String)> {
+        match serde_json::to_value(self) {
+            Ok(serde_json::Value::Object(map)) => map
+                .into_iter()
+                .filter_map(|(name, chord)| Some((name, chord.as_str()?.to_string())))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Rebind `name` to `chord`. False (and nothing changed) for an unknown
+    /// name.
+    pub fn set(&mut self, name: &str, chord: &str) -> bool {
+        let Ok(serde_json::Value::Object(mut map)) = serde_json::to_value(&*self) else {
+            return false;
+        };
+        if !map.contains_key(name) {
+            return false;
+        }
+        map.insert(name.to_string(), chord.into());
+        match serde_json::from_value(serde_json::Value::Object(map)) {
+            Ok(bindings) => {
+                *self = bindings;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// The other bindings on the same chord as `name` in a context they share
+    /// (see [`binding_scopes`]), by name.
+    pub fn clashes(&self, name: &str) -> Vec<String> {
+        let entries = self.entries();
+        let Some((_, chord)) = entries.iter().find(|(n, _)| n == name) else {
+            return Vec::new();
+        };
+        let scopes = binding_scopes(name);
+        entries
+            .iter()
+            .filter(|(n, c)| n != name && c == chord)
+            .filter(|(n, _)| binding_scopes(n).iter().any(|s| scopes.contains(s)))
+            .map(|(n, _)| n.clone())
+            .collect()
+    }
+}
+
+/// Every context with keys of its own but no text being typed.
+const MENU_SCOPES: [&str; 10] = [
+    "normal",
+    "object",
+    "select",
+    "frame menu",
+    "frame selection",
+    "frame placing",
+    "presentations",
+    "layers",
+    "properties",
+    "table cells",
+];
+
+/// The contexts a binding is live in: two bindings clash only when they share
+/// one, so `a` can add an object in Normal and a frame in the frame menu.
+/// `coarse_modifier` is a modifier rather than a key, and never clashes.
+pub fn binding_scopes(name: &str) -> &'static [&'static str] {
+    match name {
+        "confirm" | "cancel" => &[
+            "normal",
+            "object",
+            "select",
+            "frame menu",
+            "frame selection",
+            "frame placing",
+            "presentations",
+            "layers",
+            "properties",
+            "table cells",
+            "text",
+        ],
+        "fullscreen" => &MENU_SCOPES,
+        "move_up" | "move_down" => &["select", "presentations", "layers", "properties", "table cells"],
+        "next_frame" | "prev_frame" => &["normal", "frame placing"],
+        "anim_skip_prev" | "anim_skip_next" | "add_object" | "select_object" | "save" | "save_as" | "quit"
+        | "frame_menu" | "presentations_menu" | "layers_menu" | "lanes_view" | "diagnostics_menu" | "find_replace" => {
+            &["normal"]
+        }
+        "open_settings" => &["normal", "presentations"],
+        "paste" => &["normal", "object"],
+        "delete_object" => &["object", "select"],
+        "edit_object" | "copy" | "stamp_group" | "stamp_frames" | "resize_object" | "edit_in_place" => &["object"],
+        "group_selection" => &["select"],
+        "frame_add" | "frame_overlay" | "frame_jump" | "frame_select" | "frame_auto" | "frame_duration"
+        | "frame_section" | "frame_ripple" | "frame_clip_paste" => &["frame menu"],
+        "frame_copy" | "frame_delete" | "frame_move" => &["frame menu", "frame selection"],
+        "frame_duplicate" | "frame_trim" | "frame_clip_copy" => &["frame selection"],
+        "frame_move_before" => &["frame placing"],
+        "presentation_open" | "presentation_save_as" | "presentation_fullscreen" => &["presentations"],
+        "layer_toggle_visible" | "layer_toggle_lock" => &["layers"],
+        "animate" | "pick_coordinate" | "table_add_col_after" | "table_add_col_before" | "table_remove_col"
+        | "table_edit_cells" => &["properties"],
+        "table_add_list" | "table_edit_cell_style" => &["table cells"],
+        "insert_newline" | "external_editor" => &["text"],
+        _ => &[],
+    }
+}
+
+/// The binding string for a pressed key — what [`matches_binding`] reads back
+/// as that key. `None` for a key no binding can name (PageUp, Delete, …).
+pub fn chord_name(event: &KeyEvent) -> Option<String> {
+    let ctrl = event.modifiers.contains(KeyModifiers::CONTROL);
+    let alt = event.modifiers.contains(KeyModifiers::ALT);
+    let shift = event.modifiers.contains(KeyModifiers::SHIFT);
+    let key = match event.code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) if ctrl || alt => c.to_ascii_lowercase().to_string(),
+        KeyCode::Char(c) if shift => c.to_ascii_uppercase().to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("F{n}"),
+        KeyCode::Right => "Right".into(),
+        KeyCode::Left => "Left".into(),
+        KeyCode::Up => "Up".into(),
+        KeyCode::Down => "Down".into(),
+        KeyCode::Enter => "Enter".into(),
+        KeyCode::Esc => "Esc".into(),
+        KeyCode::Tab => "Tab".into(),
+        KeyCode::Backspace => "Backspace".into(),
+        KeyCode::Home => "Home".into(),
+        KeyCode::End => "End".into(),
+        _ => return None,
+    };
+    let is_char = matches!(event.code, KeyCode::Char(c) if c != ' ');
+    Some(match (ctrl, alt) {
+        (true, false) if shift && is_char => format!("Ctrl-Shift-{key}"),
+        (true, false) if is_char => format!("Ctrl-{key}"),
+        (false, true) if is_char || event.code == KeyCode::Enter => format!("Alt-{key}"),
+        (false, false) => key,
+        _ => return None,
+    })
+}
+
+/// Whether `event` is pressed with exactly the modifier `name` (`Ctrl` or
+/// `Alt`) held — the coarse-step modifier's test.
+pub fn holds_modifier(name: &str, event: &KeyEvent) -> bool {
+    let modifier = match name {
+        "Ctrl" => KeyModifiers::CONTROL,
+        "Alt" => KeyModifiers::ALT,
+        _ => return false,
+    };
+    event.modifiers == modifier
+}
+
 /// Check whether a crossterm `KeyEvent` matches a binding string from config.
 pub fn matches_binding(binding: &str, event: &KeyEvent) -> bool {
     // Handle Ctrl-Shift- prefix (must come before the bare Ctrl- check). Requires
@@ -384,4 +953,179 @@ mod tests {
         assert!(!matches_binding("s", &ev(KeyCode::Char('s'), KeyModifiers::SHIFT)));
         assert!(!matches_binding("s", &ev(KeyCode::Char('S'), KeyModifiers::SHIFT)));
     }
+
+    #[test]
+    fn captured_chords_name_the_key_matches_binding_reads_back() {
+        let cs = KeyModifiers::CONTROL | KeyModifiers::SHIFT;
+        let keys = [
+            (ev(KeyCode::Char('x'), KeyModifiers::NONE), "x"),
+            (ev(KeyCode::Char('x'), KeyModifiers::SHIFT), "X"),
+            (ev(KeyCode::Char('X'), KeyModifiers::SHIFT), "X"),
+            (ev(KeyCode::Char('k'), KeyModifiers::CONTROL), "Ctrl-k"),
+            (ev(KeyCode::Char('K'), cs), "Ctrl-Shift-k"),
+            (ev(KeyCode::Char('b'), KeyModifiers::ALT), "Alt-b"),
+            (ev(KeyCode::Enter, KeyModifiers::ALT), "Alt-Enter"),
+            (ev(KeyCode::Char(' '), KeyModifiers::NONE), "Space"),
+            (ev(KeyCode::F(5), KeyModifiers::NONE), "F5"),
+            (ev(KeyCode::Char('['), KeyModifiers::NONE), "["),
+        ];
+        for (event, name) in keys {
+            assert_eq!(chord_name(&event).as_deref(), Some(name));
+            assert!(matches_binding(name, &event), "{name} reads back");
+        }
+        assert_eq!(chord_name(&ev(KeyCode::PageUp, KeyModifiers::NONE)), None);
+        assert_eq!(chord_name(&ev(KeyCode::Up, KeyModifiers::CONTROL)), None);
+    }
+
+    #[test]
+    fn defaults_have_no_clashes_and_a_rebinding_reports_one() {
+        let mut bindings = EditorConfig::default().key_bindings;
+        for (name, _) in bindings.entries() {
+            assert_eq!(bindings.clashes(&name), Vec::<String>::new(), "{name}");
+            assert!(name == "coarse_modifier" || !binding_scopes(&name).is_empty(), "{name} has a scope");
+        }
+        assert!(bindings.set("frame_add", "q"));
+        assert!(bindings.clashes("frame_add").is_empty(), "quit is Normal-only");
+        assert!(bindings.set("lanes_view", "a"));
+        assert_eq!(bindings.clashes("lanes_view"), ["add_object"]);
+        assert_eq!(bindings.clashes("add_object"), ["lanes_view"]);
+        assert!(!bindings.set("no_such_binding", "z"));
+    }
+
+    #[test]
+    fn keymap_presets_lay_the_file_s_own_bindings_over_theirs() {
+        let vim = EditorConfig::from_json(r#"{"keymap":"vim"}"#).unwrap();
+        assert_eq!(vim.keymap, Keymap::Vim);
+        let b = &vim.key_bindings;
+        assert_eq!((b.prev_frame.as_str(), b.move_down.as_str(), b.move_up.as_str(), b.next_frame.as_str()), ("h", "j", "k", "l"));
+        assert_eq!(b.quit, "q", "the rest stay at their defaults");
+
+        let own = EditorConfig::from_json(r#"{"keymap":"emacs","key_bindings":{"quit":"Ctrl-q"}}"#).unwrap();
+        assert_eq!(own.key_bindings.quit, "Ctrl-q");
+        assert_eq!(own.key_bindings.cancel, "Ctrl-g");
+        assert!(matches_binding(&own.key_bindings.next_frame, &ev(KeyCode::Char('f'), KeyModifiers::CONTROL)));
+
+        let plain = EditorConfig::from_json(r#"{"key_bindings":{"save":"F2"}}"#).unwrap();
+        assert_eq!((plain.keymap, plain.key_bindings.save.as_str()), (Keymap::Default, "F2"));
+        assert_eq!(plain.key_bindings.next_frame, "Right", "a partial key_bindings falls back to the defaults");
+        assert!(EditorConfig::from_json(r#"{"keymap":"nano"}"#).is_err());
+
+        for keymap in Keymap::ALL {
+            let bindings = keymap.bindings();
+            for (name, chord) in keymap.overrides() {
+                assert_eq!(bindings.entries().iter().find(|(n, _)| n == name).map(|(_, c)| c.as_str()), Some(*chord));
+            }
+            for (name, _) in bindings.entries() {
+                assert!(bindings.clashes(&name).is_empty(), "{keymap:?}: {name} clashes");
+            }
+        }
+    }
+
+    #[test]
+    fn saved_config_loads_back_from_the_same_file() {
+        let path = std::env::temp_dir().join(format!("bs_editor_config_{}.json", std::process::id()));
+        let mut config = EditorConfig { file: Some(path.clone()), ..EditorConfig::default() };
+        config.key_bindings.set("find_replace", "Ctrl-h");
+        assert_eq!(config.save().unwrap(), path);
+        let loaded = EditorConfig::load_file(&path).unwrap();
+        assert_eq!(loaded.key_bindings.find_replace, "Ctrl-h");
+        assert_eq!(loaded.file.as_deref(), Some(path.as_path()));
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn theme_colours_replace_reverse_and_dim_only_where_set() {
+        let config = EditorConfig::from_json(r#"{"theme":{"selection_bg":{"r":221,"g":228,"b":255},"dim":"blue"}}"#).unwrap();
+        let selection = config.theme.selection().0;
+        assert_eq!(selection.background_color, Some(style::Color::Rgb { r: 0xdd, g: 0xe4, b: 0xff }));
+        assert!(!selection.attributes.has(style::Attribute::Reverse));
+        assert_eq!(config.theme.dimmed().0.foreground_color, Some(style::Color::Blue));
+        assert!(config.theme.menubar().attributes.has(style::Attribute::Dim), "an unset menubar stays dim");
+        assert_eq!(config.theme.borders().0, style::ContentStyle::default());
+
+        let plain = Theme::default();
+        assert!(plain.selection().0.attributes.has(style::Attribute::Reverse));
+        assert!(plain.dimmed().0.attributes.has(style::Attribute::Dim));
+        assert!(EditorConfig::from_json(r#"{"theme":{"border":"mauve"}}"#).is_err());
+    }
+
+    #[test]
+    fn project_config_is_found_above_the_deck_and_laid_over_the_user_s() {
+        let root = std::env::temp_dir().join(format!("bs_project_config_{}", std::process::id()));
+        std::fs::create_dir_all(root.join("talks")).unwrap();
+        std::fs::write(
+            root.join(PROJECT_FILES[0]),
+            r#"{"keymap":"vim","key_bindings":{"quit":"Q"},"theme":{"border":"blue"},"lint":{"empty_frame":"allow"}}"#,
+        )
+        .unwrap();
+        let deck = root.join("talks").join("deck.json");
+
+        let mut user = EditorConfig { coarse_step: 3, ..EditorConfig::default() };
+        user.key_bindings.set("save", "F2");
+        user.theme.dim = Some(crate::types::NamedColor::Cyan.into());
+        let config = user.for_deck(deck.to_str().unwrap()).unwrap();
+        assert_eq!(config.project.as_deref(), Some(root.join(PROJECT_FILES[0]).as_path()));
+        assert_eq!(config.keymap, Keymap::Vim);
+        let b = &config.key_bindings;
+        assert_eq!((b.next_frame.as_str(), b.quit.as_str(), b.save.as_str()), ("l", "Q", "F2"), "the user's own change survives the project keymap");
+        assert_eq!(config.coarse_step, 3);
+        assert_eq!((config.theme.border.is_some(), config.theme.dim.is_some()), (true, true), "themes merge entry by entry");
+        assert_eq!(config.lint.get(&LintRule::EmptyFrame), Some(&LintLevel::Allow));
+
+        let mut source: SourcePresentation = serde_json::from_str(r#"{"width":10,"height":5,"frame_count":1,"objects":[],"lint":{"empty_frame":"deny"}}"#).unwrap();
+        assert_eq!(config.lint_source(&source).lint[&LintRule::EmptyFrame], LintLevel::Deny, "the deck's own level wins");
+        source.lint.clear();
+        assert_eq!(config.lint_source(&source).lint[&LintRule::EmptyFrame], LintLevel::Allow);
+
+        std::fs::write(root.join(PROJECT_FILES[0]), "{").unwrap();
+        assert!(user.for_deck(deck.to_str().unwrap()).is_err());
+        std::fs::remove_dir_all(&root).ok();
+        let alone = user.for_deck(deck.to_str().unwrap()).unwrap();
+        assert!(alone.project.is_none() && alone.keymap == Keymap::Default);
+    }
+
+    #[test]
+    fn either_project_file_name_is_found_and_the_nearest_wins() {
+        let root = std::env::temp_dir().join(format!("bs_project_names_{}", std::process::id()));
+        std::fs::create_dir_all(root.join("talks")).unwrap();
+        let deck = root.join("talks").join("deck.json");
+        let found = |root: &Path| EditorConfig::default().for_deck(deck.to_str().unwrap()).unwrap().project.map(|p| p.strip_prefix(root).unwrap().to_path_buf());
+
+        std::fs::write(root.join(".ascii-presenter.json"), "{}").unwrap();
+        assert_eq!(found(&root), Some(PathBuf::from(".ascii-presenter.json")));
+        std::fs::write(root.join(".bs.json"), "{}").unwrap();
+        assert_eq!(found(&root), Some(PathBuf::from(".bs.json")), "in one directory `.bs.json` wins");
+        std::fs::write(root.join("talks").join(".ascii-presenter.json"), "{}").unwrap();
+        assert_eq!(found(&root), Some(Path::new("talks").join(".ascii-presenter.json")), "the nearer directory wins");
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn saving_under_a_project_config_writes_only_the_user_s_layer() {
+        let root = std::env::temp_dir().join(format!("bs_project_save_{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(
+            root.join(PROJECT_FILES[0]),
+            r#"{"keymap":"vim","key_bindings":{"quit":"Q"},"theme":{"border":"blue"},"lint":{"empty_frame":"allow"}}"#,
+        )
+        .unwrap();
+        let file = root.join("editor.json");
+        std::fs::write(&file, r#"{"key_bindings":{"save":"F2"},"coarse_step":3}"#).unwrap();
+        let user = EditorConfig::load_file(&file).unwrap();
+
+        let mut config = user.for_deck(root.join("deck.json").to_str().unwrap()).unwrap();
+        config.key_bindings.set("find_replace", "Ctrl-h");
+        config.theme.dim = Some(crate::types::NamedColor::Cyan.into());
+        assert_eq!(config.save().unwrap(), file);
+
+        let saved = EditorConfig::load_file(&file).unwrap();
+        let b = &saved.key_bindings;
+        assert_eq!((b.find_replace.as_str(), b.save.as_str()), ("Ctrl-h", "F2"), "the new binding joins the user's own");
+        assert_eq!((b.next_frame.as_str(), b.quit.as_str()), ("Right", "q"), "nothing of the project's keymap or bindings");
+        assert_eq!(saved.keymap, Keymap::Default);
+        assert_eq!((saved.theme.dim.is_some(), saved.theme.border), (true, None));
+        assert!(saved.lint.is_empty());
+        assert_eq!(saved.coarse_step, 3);
+        std::fs::remove_dir_all(&root).ok();
+    }
 }
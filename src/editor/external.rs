@@ -0,0 +1,46 @@
+//! Editing a long text value in the user's own editor: `$VISUAL`, then
+//! `$EDITOR`, then `vi`. The TUI is suspended around the call by the `Editor`.
+
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+/// The editor command to run, as the user configured it (may carry arguments,
+/// e.g. `code --wait`).
+pub fn editor_command() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|cmd| !cmd.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+/// Write `text` to a scratch file, run `command` on it and return what was
+/// saved. The single trailing newline editors append on save is dropped, so
+/// an unchanged buffer round-trips exactly.
+pub fn edit_with(command: &str, text: &str) -> Result<String> {
+    let path = std::env::temp_dir().join(format!("bs-edit-{}.txt", std::process::id()));
+    std::fs::write(&path, text)
+        .with_context(|| format!("cannot write {}", path.display()))?;
+    // Through the shell, like git does, so `$EDITOR` may carry arguments.
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{command} \"$1\""))
+        .arg("bs")
+        .arg(&path)
+        .status();
+    let edited = std::fs::read_to_string(&path);
+    let _ = std::fs::remove_file(&path);
+    let status = status.with_context(|| format!("cannot run {command}"))?;
+    if !status.success() {
+        bail!("{command} exited with {status}");
+    }
+    let mut edited = edited.with_context(|| format!("cannot read {}", path.display()))?;
+    if edited.ends_with('\n') {
+        edited.pop();
+        if edited.ends_with('\r') {
+            edited.pop();
+        }
+    }
+    Ok(edited)
+}
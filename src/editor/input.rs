@@ -1,14 +1,18 @@
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 use crossterm::terminal;
 
 use crate::engine::objects::table::{table_add_column, table_remove_column};
 use crate::engine::objects::Group;
 use crate::engine::source::{AnimId, AnimSpans, Coordinate, SceneObject, SourcePresentation};
 use crate::types::Style;
-use super::config::matches_binding;
+use super::config::{chord_name, holds_modifier, matches_binding};
 use super::object_defaults;
-use super::properties;
+use super::properties::{self, Align, Axis, CanvasFit};
+use super::search::{self, TextMatch};
 use super::textedit::{TextAction, TextEdit};
+use super::ui::Layout;
 use super::state::{
     adjust_frames_after_delete, adjust_group_members_after_delete, copy_frame,
     frame_auto_advance_delay, insert_blank_frame, move_frame, overlay_frame, ArtPick,
@@ -27,12 +31,19 @@ pub enum Action {
     SwitchDeck(usize),
     /// Open the file at this path as a new deck (or focus it if already open).
     OpenDeck(String),
+    /// Open the file at this path in place of the active deck — the empty one
+    /// behind the start screen.
+    OpenInPlace(String),
     /// Capture the contiguous frame block `[lo, hi]` of the active deck into the
     /// cross-deck frame clipboard (owned by the `Editor`).
     CopyFrameBlock { lo: usize, hi: usize },
     /// Paste the cross-deck frame clipboard into the active deck, dropping the
     /// block before/after `target`.
     PasteFrameBlock { target: usize, before: bool },
+    /// Open this text buffer in the user's external editor. The `Editor`
+    /// suspends the TUI, runs it, and hands the result back through
+    /// [`replace_edit_buffer`].
+    ExternalEdit(String),
 }
 
 /// Whether the current mode is actively capturing typed characters into a text
@@ -51,7 +62,17 @@ fn mode_accepts_text(mode: &Mode) -> bool {
         | Mode::OpenFile { .. }
         | Mode::FrameJump { .. }
         | Mode::FrameSelectInput { .. }
-        | Mode::FrameAutoInput { .. } => true,
+        | Mode::FrameAutoInput { .. }
+        | Mode::FrameDurationInput { .. }
+        | Mode::FrameSectionInput { .. }
+        | Mode::FrameRippleInput { .. }
+        | Mode::StampFramesInput { .. }
+        | Mode::FindInput { .. }
+        | Mode::ReplaceInput { .. }
+        | Mode::NewDeck { .. }
+        | Mode::EditInPlace { .. } => true,
+        // Capturing a chord: every key, letters included, is the new binding.
+        Mode::KeyBindings { capturing, .. } => *capturing,
         Mode::TableEditCellProps { sub_state, .. } => match sub_state {
             TableCellSubState::EditingContent { .. } => true,
             TableCellSubState::EditingStyle { editing_value, .. } => editing_value.is_some(),
@@ -72,6 +93,38 @@ pub fn handle_event(state: &mut EditorState, event: Event) -> Action {
     }
 }
 
+/// A mouse event, against the screen `layout` it was reported on. In Normal mode
+/// a click or drag along the timeline's scrubber row jumps to the frame under
+/// the pointer; the lanes view drags frame-range ends. Everything else is
+/// ignored.
+pub fn handle_mouse(state: &mut EditorState, mouse: MouseEvent, layout: &Layout) -> Action {
+    if matches!(state.mode, Mode::Lanes { .. }) {
+        return lanes_mouse(state, mouse, layout);
+    }
+    if matches!(state.mode, Mode::PickCoordinate { .. }) {
+        return pick_mouse(state, mouse, layout);
+    }
+    let pressed = matches!(
+        mouse.kind,
+        MouseEventKind::Down(MouseButton::Left) | MouseEventKind::Drag(MouseButton::Left)
+    );
+    if !pressed
+        || state.fullscreen
+        || !matches!(state.mode, Mode::Normal)
+        || mouse.row != layout.timeline_y
+        || state.source.frame_count == 0
+    {
+        return Action::Continue;
+    }
+    let frame = super::timeline::scrub_frame(mouse.column, layout.term_width, state.source.frame_count);
+    if frame == state.current_frame {
+        return Action::Continue;
+    }
+    state.current_frame = frame;
+    state.status_message = None;
+    Action::Redraw
+}
+
 fn handle_key(state: &mut EditorState, key: KeyEvent) -> Action {
     // Global shortcut: works from any mode *except* while a text field is being
     // typed into — otherwise a plain-letter binding (e.g. "f") would be swallowed
@@ -90,10 +143,19 @@ fn handle_key(state: &mut EditorState, key: KeyEvent) -> Action {
     match &state.mode {
         Mode::Normal => handle_normal(state, key),
         Mode::SaveAs { .. } => handle_save_as(state, key),
+        Mode::Start { .. } => handle_start(state, key),
+        Mode::NewDeck { .. } => handle_new_deck(state, key),
         Mode::FrameMenu => handle_frame_menu(state, key),
         Mode::FrameJump { .. } => handle_frame_jump(state, key),
         Mode::FrameSelectInput { .. } => handle_frame_select_input(state, key),
         Mode::FrameAutoInput { .. } => handle_frame_auto_input(state, key),
+        Mode::FrameDurationInput { .. } => handle_frame_duration_input(state, key),
+        Mode::FrameSectionInput { .. } => handle_frame_section_input(state, key),
+        Mode::FrameRippleInput { .. } => handle_frame_ripple_input(state, key),
+        Mode::StampFramesInput { .. } => handle_stamp_frames_input(state, key),
+        Mode::FindInput { .. } => handle_find_input(state, key),
+        Mode::ReplaceInput { .. } => handle_replace_input(state, key),
+        Mode::ReplaceConfirm { .. } => handle_replace_confirm(state, key),
         Mode::FrameSelected { .. } => handle_frame_selected(state, key),
         Mode::FrameRangePlace { .. } => handle_frame_range_place(state, key),
         Mode::FrameMove { .. } => handle_frame_move(state, key),
@@ -101,11 +163,16 @@ fn handle_key(state: &mut EditorState, key: KeyEvent) -> Action {
         Mode::FrameOverlay { .. } => handle_frame_overlay(state, key),
         Mode::FramePastePlace => handle_frame_paste_place(state, key),
         Mode::PresentationMenu { .. } => handle_presentation_menu(state, key),
+        Mode::Layers { .. } => handle_layers(state, key),
+        Mode::Lanes { .. } => handle_lanes(state, key),
+        Mode::Diagnostics { .. } => handle_diagnostics(state, key),
         Mode::OpenFile { .. } => handle_open_file(state, key),
         Mode::AddObject { .. } => handle_add_object(state, key),
         Mode::SelectAction { .. } => handle_select_action(state, key),
         Mode::SelectedObject { .. } => handle_selected_object(state, key),
         Mode::ResizeObject { .. } => handle_resize_object(state, key),
+        Mode::EditInPlace { .. } => handle_edit_in_place(state, key),
+        Mode::PickCoordinate { .. } => handle_pick_coordinate(state, key),
         Mode::EditProperties { editing_value, dropdown, .. } => {
             let has_dropdown = dropdown.is_some();
             let is_editing = editing_value.is_some();
@@ -155,6 +222,7 @@ fn handle_key(state: &mut EditorState, key: KeyEvent) -> Action {
         Mode::AddArt { .. } => handle_add_art(state, key),
         Mode::LoadArtFile { .. } => handle_load_art_file(state, key),
         Mode::Settings { .. } => handle_settings(state, key),
+        Mode::KeyBindings { .. } => handle_key_bindings(state, key),
     }
 }
 
@@ -282,9 +350,9 @@ fn edit_props_action_index() -> usize {
 }
 
 /// Vertical scroll so the property row at `selected_row` stays on screen. Mirrors
-/// the panel layout: menu(1) + timeline(2) + title(1) + separator(1) = 5 reserved.
+/// the panel layout: menu(1) + timeline + title(1) + separator(1) reserved.
 fn follow_panel_scroll(selected_row: usize, panel_scroll: usize, term_h: usize) -> usize {
-    let avail = term_h.saturating_sub(5);
+    let avail = term_h.saturating_sub(3 + super::ui::TIMELINE_HEIGHT as usize);
     if selected_row < panel_scroll {
         selected_row
     } else if avail > 0 && selected_row >= panel_scroll + avail {
@@ -412,6 +480,24 @@ fn add_art_item(state: &mut EditorState, art: String, name: String) {
     state.status_message = Some(format!("Added art: {name}"));
 }
 
+fn add_ansi_art_item(state: &mut EditorState, ansi: String, name: String) {
+    let obj = object_defaults::create_ansi_art(ansi, name.clone(), state.current_frame);
+    state.source.objects.push(obj);
+    state.dirty = true;
+    let new_index = state.source.objects.len() - 1;
+    state.mode = ep_browse(new_index, 0, 0);
+    state.status_message = Some(format!("Added ANSI art: {name}"));
+}
+
+fn add_cast_item(state: &mut EditorState, cast: String, name: String) {
+    let obj = object_defaults::create_cast(cast, name.clone(), state.current_frame);
+    state.source.objects.push(obj);
+    state.dirty = true;
+    let new_index = state.source.objects.len() - 1;
+    state.mode = ep_browse(new_index, 0, 0);
+    state.status_message = Some(format!("Added cast: {name}"));
+}
+
 /// Route a chosen art piece according to the picker's `purpose`: add it as a
 /// standalone `Art`, capture it as a morph's *from* (and re-open the picker for
 /// the *to* piece), or finish a morph with the chosen *to* piece.
@@ -507,7 +593,42 @@ fn handle_load_art_file(state: &mut EditorState, key: KeyEvent) -> Action {
             state.status_message = Some("Enter a file path".into());
             return Action::Redraw;
         }
-        match crate::art_library::load_file(std::path::Path::new(path)) {
+        let path = std::path::Path::new(path);
+        // ANSI art and recordings become their own objects; morphs only take
+        // plain art.
+        let is_cast = path.extension().is_some_and(|e| e.eq_ignore_ascii_case("cast"));
+        if matches!(purpose, ArtPick::Art) && is_cast {
+            let loaded = std::fs::read_to_string(path)
+                .map_err(anyhow::Error::from)
+                .and_then(|text| crate::import::Recording::parse(&text).map(|_| text));
+            match loaded {
+                Ok(cast) => {
+                    let name = path.file_stem().and_then(|s| s.to_str()).unwrap_or("custom");
+                    add_cast_item(state, cast, name.to_string());
+                }
+                Err(e) => {
+                    state.status_message = Some(format!("Load failed: {e}"));
+                    state.mode = Mode::LoadArtFile { buf, cursor, purpose };
+                }
+            }
+            return Action::Redraw;
+        }
+        if matches!(purpose, ArtPick::Art) {
+            match crate::import::ansi::load_file(path) {
+                Ok(Some(ansi)) => {
+                    let name = path.file_stem().and_then(|s| s.to_str()).unwrap_or("custom");
+                    add_ansi_art_item(state, ansi, name.to_string());
+                    return Action::Redraw;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    state.status_message = Some(format!("Load failed: {e}"));
+                    state.mode = Mode::LoadArtFile { buf, cursor, purpose };
+                    return Action::Redraw;
+                }
+            }
+        }
+        match crate::art_library::load_file(path) {
             Ok(item) => route_picked_art(state, item.art, item.name, purpose),
             Err(e) => {
                 // Stay in the input so the path can be corrected.
@@ -536,9 +657,7 @@ fn handle_load_art_file(state: &mut EditorState, key: KeyEvent) -> Action {
             return Action::Redraw;
         }
         KeyCode::Left if key.modifiers == KeyModifiers::NONE => {
-            if cursor > 0 {
-                cursor -= 1;
-            }
+            cursor = cursor.saturating_sub(1);
             state.mode = Mode::LoadArtFile { buf, cursor, purpose };
             return Action::Redraw;
         }
@@ -558,9 +677,9 @@ fn handle_load_art_file(state: &mut EditorState, key: KeyEvent) -> Action {
 fn handle_settings(state: &mut EditorState, key: KeyEvent) -> Action {
     let bindings = state.config.key_bindings.clone();
 
-    let (mut selected_field, mut width_buf, mut height_buf, mut cursor) = match &state.mode {
-        Mode::Settings { selected_field, width_buf, height_buf, cursor } => {
-            (*selected_field, width_buf.clone(), height_buf.clone(), *cursor)
+    let (mut selected_field, mut width_buf, mut height_buf, mut fit, mut cursor) = match &state.mode {
+        Mode::Settings { selected_field, width_buf, height_buf, fit, cursor } => {
+            (*selected_field, width_buf.clone(), height_buf.clone(), *fit, *cursor)
         }
         _ => return Action::Continue,
     };
@@ -570,13 +689,24 @@ fn handle_settings(state: &mut EditorState, key: KeyEvent) -> Action {
         return Action::Redraw;
     }
 
+    if selected_field == 3 && matches_binding(&bindings.confirm, &key) {
+        state.mode = Mode::KeyBindings { selected: 0, capturing: false };
+        state.status_message = None;
+        return Action::Redraw;
+    }
     if matches_binding(&bindings.confirm, &key) {
         match (width_buf.trim().parse::<u16>(), height_buf.trim().parse::<u16>()) {
             (Ok(w), Ok(h)) if w >= 1 && h >= 1 => {
-                state.source.width = w;
-                state.source.height = h;
+                let outside = super::state::resize_canvas(&mut state.source, w, h, fit);
                 state.dirty = true;
-                state.status_message = Some(format!("Frame size set to {w}×{h}"));
+                state.status_message = Some(match outside.first() {
+                    None => format!("Frame size set to {w}×{h}"),
+                    Some(&first) => format!(
+                        "Frame size set to {w}×{h} — ⚠ {} object(s) now draw outside it (first: {})",
+                        outside.len(),
+                        super::state::scene_object_summary(&state.source.objects[first]),
+                    ),
+                });
                 state.mode = Mode::Normal;
             }
             _ => {
@@ -587,23 +717,41 @@ fn handle_settings(state: &mut EditorState, key: KeyEvent) -> Action {
         return Action::Redraw;
     }
 
-    // Switch between the width and height fields; park the cursor at the end of
-    // the newly-selected field.
-    if matches_binding(&bindings.move_up, &key)
+    // Cycle the fields (width, height, content fit, key bindings); park the
+    // cursor at the end of the newly-selected one.
+    let back = matches_binding(&bindings.move_up, &key) || key.code == KeyCode::BackTab;
+    if back
         || matches_binding(&bindings.move_down, &key)
         || (key.code == KeyCode::Tab && key.modifiers == KeyModifiers::NONE)
     {
-        selected_field = if selected_field == 0 { 1 } else { 0 };
-        cursor = if selected_field == 0 {
-            width_buf.chars().count()
-        } else {
-            height_buf.chars().count()
+        selected_field = if back { (selected_field + 3) % 4 } else { (selected_field + 1) % 4 };
+        cursor = match selected_field {
+            0 => width_buf.chars().count(),
+            1 => height_buf.chars().count(),
+            _ => 0,
+        };
+        state.mode = Mode::Settings { selected_field, width_buf, height_buf, fit, cursor };
+        return Action::Redraw;
+    }
+
+    // The content-fit row: ←/→ (or Space) step through the choices.
+    if selected_field == 2 {
+        let i = CanvasFit::ALL.iter().position(|&f| f == fit).unwrap_or(0);
+        let n = CanvasFit::ALL.len();
+        fit = match key.code {
+            KeyCode::Left => CanvasFit::ALL[(i + n - 1) % n],
+            KeyCode::Right | KeyCode::Char(' ') => CanvasFit::ALL[(i + 1) % n],
+            _ => return Action::Continue,
         };
-        state.mode = Mode::Settings { selected_field, width_buf, height_buf, cursor };
+        state.mode = Mode::Settings { selected_field, width_buf, height_buf, fit, cursor };
         return Action::Redraw;
     }
 
-    // Edit the selected field (digits only).
+    if selected_field == 3 {
+        return Action::Continue;
+    }
+
+    // Edit the selected size field (digits only).
     let buf = if selected_field == 0 { &mut width_buf } else { &mut height_buf };
     match key.code {
         KeyCode::Char(c)
@@ -632,10 +780,104 @@ fn handle_settings(state: &mut EditorState, key: KeyEvent) -> Action {
         }
         _ => return Action::Continue,
     }
-    state.mode = Mode::Settings { selected_field, width_buf, height_buf, cursor };
+    state.mode = Mode::Settings { selected_field, width_buf, height_buf, fit, cursor };
     Action::Redraw
 }
 
+/// The key-binding editor: Enter waits for a key and binds the highlighted
+/// action to it (Esc keeps the old one), Backspace restores the keymap's
+/// binding, and the save binding writes the config file — refused while any
+/// two bindings clash. Esc goes back to the settings.
+fn handle_key_bindings(state: &mut EditorState, key: KeyEvent) -> Action {
+    let bindings = state.config.key_bindings.clone();
+    let (selected, capturing) = match &state.mode {
+        Mode::KeyBindings { selected, capturing } => (*selected, *capturing),
+        _ => return Action::Continue,
+    };
+    let entries = bindings.entries();
+    let Some((name, chord)) = entries.get(selected).cloned() else {
+        return Action::Continue;
+    };
+
+    if capturing {
+        if key.code == KeyCode::Esc && key.modifiers == KeyModifiers::NONE {
+            state.mode = Mode::KeyBindings { selected, capturing: false };
+            state.status_message = None;
+            return Action::Redraw;
+        }
+        // The coarse-step modifier is the Ctrl or Alt held, whatever the key.
+        let new = if name == "coarse_modifier" {
+            if key.modifiers == KeyModifiers::CONTROL {
+                Some("Ctrl".to_string())
+            } else if key.modifiers == KeyModifiers::ALT {
+                Some("Alt".to_string())
+            } else {
+                None
+            }
+        } else {
+            chord_name(&key)
+        };
+        let Some(new) = new else {
+            state.status_message = Some(if name == "coarse_modifier" {
+                "⚠ hold Ctrl or Alt and press any key".into()
+            } else {
+                "⚠ that key can't be bound — try another".into()
+            });
+            return Action::Redraw;
+        };
+        state.config.key_bindings.set(&name, &new);
+        let clashes = state.config.key_bindings.clashes(&name);
+        state.status_message = Some(if clashes.is_empty() {
+            format!("{name} = {new}")
+        } else {
+            format!("⚠ {name} = {new} clashes with {}", clashes.join(", "))
+        });
+        state.mode = Mode::KeyBindings { selected, capturing: false };
+        return Action::Redraw;
+    }
+
+    if matches_binding(&bindings.cancel, &key) {
+        let width_buf = state.source.width.to_string();
+        let height_buf = state.source.height.to_string();
+        state.mode = Mode::Settings { selected_field: 3, width_buf, height_buf, fit: CanvasFit::TopLeft, cursor: 0 };
+        state.status_message = None;
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.move_up, &key) || matches_binding(&bindings.move_down, &key) {
+        let n = entries.len();
+        let selected = if matches_binding(&bindings.move_up, &key) { (selected + n - 1) % n } else { (selected + 1) % n };
+        state.mode = Mode::KeyBindings { selected, capturing: false };
+        state.status_message = None;
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.confirm, &key) {
+        state.mode = Mode::KeyBindings { selected, capturing: true };
+        state.status_message = Some(format!("Press the new key for {name} (Esc keeps {chord})"));
+        return Action::Redraw;
+    }
+    if key.code == KeyCode::Backspace && key.modifiers == KeyModifiers::NONE {
+        let default = state.config.keymap.bindings().entries().into_iter().find(|(n, _)| *n == name);
+        if let Some((_, default)) = default {
+            state.config.key_bindings.set(&name, &default);
+            state.status_message = Some(format!("{name} = {default} (default)"));
+        }
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.save, &key) {
+        let clashing = entries.iter().filter(|(n, _)| !bindings.clashes(n).is_empty()).count();
+        state.status_message = Some(if clashing > 0 {
+            format!("⚠ {clashing} bindings clash — rebind them before saving")
+        } else {
+            match state.config.save() {
+                Ok(path) => format!("Saved key bindings to {}", path.display()),
+                Err(e) => format!("Save failed: {e}"),
+            }
+        });
+        return Action::Redraw;
+    }
+    Action::Continue
+}
+
 /// Frames moved per Shift+arrow jump in top-level (Normal) frame navigation.
 const FRAMES_PER_JUMP: usize = 10;
 
@@ -800,7 +1042,7 @@ fn handle_normal(state: &mut EditorState, key: KeyEvent) -> Action {
         let width_buf = state.source.width.to_string();
         let height_buf = state.source.height.to_string();
         let cursor = width_buf.chars().count();
-        state.mode = Mode::Settings { selected_field: 0, width_buf, height_buf, cursor };
+        state.mode = Mode::Settings { selected_field: 0, width_buf, height_buf, fit: CanvasFit::TopLeft, cursor };
         state.status_message = None;
         return Action::Redraw;
     }
@@ -817,6 +1059,37 @@ fn handle_normal(state: &mut EditorState, key: KeyEvent) -> Action {
         state.status_message = None;
         return Action::Redraw;
     }
+    if matches_binding(&bindings.layers_menu, &key) {
+        if state.source.layer_names().is_empty() {
+            state.status_message =
+                Some("No layers — set an object's `layer` property to create one".into());
+        } else {
+            state.mode = Mode::Layers { selected: 0 };
+            state.status_message = None;
+        }
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.lanes_view, &key) {
+        state.mode = Mode::Lanes { selected: 0, at_end: false };
+        state.status_message = None;
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.find_replace, &key) {
+        state.mode = Mode::FindInput { buf: String::new(), cursor: 0, regex: false };
+        state.status_message = None;
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.diagnostics_menu, &key) {
+        let mut items = crate::validate::validate(&state.source);
+        items.extend(crate::validate::lint(&state.config.lint_source(&state.source)));
+        if items.is_empty() {
+            state.status_message = Some("No problems found".into());
+        } else {
+            state.status_message = Some(describe_diagnostic(&items[0]));
+            state.mode = Mode::Diagnostics { selected: 0, items };
+        }
+        return Action::Redraw;
+    }
     // Paste: place the clipboard's clones as a movable ghost on this frame.
     // (Copy and Converge are now reached via `s` select → action sub-menu.)
     if matches_binding(&bindings.paste, &key) {
@@ -921,6 +1194,25 @@ fn handle_frame_menu(state: &mut EditorState, key: KeyEvent) -> Action {
         state.status_message = None;
         return Action::Redraw;
     }
+    if matches_binding(&bindings.frame_duration, &key) {
+        let buf = state.source.frame_duration(state.current_frame).map(secs_string).unwrap_or_default();
+        let cursor = buf.chars().count();
+        state.mode = Mode::FrameDurationInput { buf, cursor };
+        state.status_message = None;
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.frame_section, &key) {
+        let buf = state.source.frame_section(state.current_frame).unwrap_or_default().to_string();
+        let cursor = buf.chars().count();
+        state.mode = Mode::FrameSectionInput { buf, cursor };
+        state.status_message = None;
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.frame_ripple, &key) {
+        state.mode = Mode::FrameRippleInput { buf: String::new(), cursor: 0, members: None };
+        state.status_message = None;
+        return Action::Redraw;
+    }
 
     Action::Continue
 }
@@ -962,6 +1254,104 @@ fn handle_save_as(state: &mut EditorState, key: KeyEvent) -> Action {
     Action::Continue
 }
 
+/// The start screen: ↑/↓ pick "New presentation…" or a recent deck, Enter
+/// opens the wizard or the deck, Esc quits.
+fn handle_start(state: &mut EditorState, key: KeyEvent) -> Action {
+    let bindings = state.config.key_bindings.clone();
+    let (selected, recent) = match &state.mode {
+        Mode::Start { selected, recent } => (*selected, recent.clone()),
+        _ => return Action::Continue,
+    };
+    let rows = recent.len() + 1;
+
+    if matches_binding(&bindings.cancel, &key) || matches_binding(&bindings.quit, &key) {
+        return Action::Quit;
+    }
+    if matches_binding(&bindings.move_up, &key) {
+        state.mode = Mode::Start { selected: (selected + rows - 1) % rows, recent };
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.move_down, &key) {
+        state.mode = Mode::Start { selected: (selected + 1) % rows, recent };
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.confirm, &key) {
+        if selected > 0 {
+            return Action::OpenInPlace(recent[selected - 1].path.clone());
+        }
+        // Seeded with the path `edit` was given (or the untitled default).
+        let fields = [state.file_path.clone(), state.source.width.to_string(), state.source.height.to_string(), "1".into()];
+        let cursor = fields[0].chars().count();
+        state.mode = Mode::NewDeck { selected_field: 0, fields, cursor, recent };
+        return Action::Redraw;
+    }
+    Action::Continue
+}
+
+/// The new-presentation wizard: ↑/↓/Tab move between the fields (the numeric
+/// ones take digits only), Enter creates the deck in memory — the first Ctrl-s
+/// writes it — and Esc goes back to the start screen.
+fn handle_new_deck(state: &mut EditorState, key: KeyEvent) -> Action {
+    let bindings = state.config.key_bindings.clone();
+    let (mut selected_field, mut fields, mut cursor, recent) = match &state.mode {
+        Mode::NewDeck { selected_field, fields, cursor, recent } => {
+            (*selected_field, fields.clone(), *cursor, recent.clone())
+        }
+        _ => return Action::Continue,
+    };
+
+    if matches_binding(&bindings.cancel, &key) {
+        state.mode = Mode::Start { selected: 0, recent };
+        state.status_message = None;
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.confirm, &key) {
+        let path = fields[0].trim();
+        let size = (fields[1].trim().parse::<u16>(), fields[2].trim().parse::<u16>(), fields[3].trim().parse::<usize>());
+        if path.is_empty() {
+            state.status_message = Some("Enter a filename".into());
+        } else if std::path::Path::new(path).exists() {
+            state.status_message = Some(format!("{path} already exists — pick another name"));
+        } else if let (Ok(w @ 1..), Ok(h @ 1..), Ok(n @ 1..)) = size {
+            state.file_path = path.to_string();
+            state.source.width = w;
+            state.source.height = h;
+            state.source.frame_count = n;
+            state.current_frame = 0;
+            state.dirty = true;
+            state.status_message = Some(format!("New {w}×{h} deck, {n} frame(s) — Ctrl-s writes {path}"));
+            state.mode = Mode::Normal;
+            return Action::Redraw;
+        } else {
+            state.status_message = Some("Width, height and frames must be whole numbers ≥ 1".into());
+        }
+        state.mode = Mode::NewDeck { selected_field, fields, cursor, recent };
+        return Action::Redraw;
+    }
+
+    let back = matches_binding(&bindings.move_up, &key) || key.code == KeyCode::BackTab;
+    if back
+        || matches_binding(&bindings.move_down, &key)
+        || (key.code == KeyCode::Tab && key.modifiers == KeyModifiers::NONE)
+    {
+        let n = fields.len();
+        selected_field = if back { (selected_field + n - 1) % n } else { (selected_field + 1) % n };
+        cursor = fields[selected_field].chars().count();
+        state.mode = Mode::NewDeck { selected_field, fields, cursor, recent };
+        return Action::Redraw;
+    }
+
+    let numeric = selected_field > 0;
+    if matches!(key.code, KeyCode::Char(c) if numeric && !c.is_ascii_digit()) {
+        return Action::Continue;
+    }
+    if frame_text_key(&key, &mut fields[selected_field], &mut cursor) {
+        state.mode = Mode::NewDeck { selected_field, fields, cursor, recent };
+        return Action::Redraw;
+    }
+    Action::Continue
+}
+
 /// Typing a 1-based frame number to jump to. Enter jumps (clamped to the deck);
 /// Esc returns to the frame menu.
 fn handle_frame_jump(state: &mut EditorState, key: KeyEvent) -> Action {
@@ -1094,12 +1484,12 @@ fn handle_frame_auto_input(state: &mut EditorState, key: KeyEvent) -> Action {
     Action::Continue
 }
 
-/// A multi-frame selection is active: `d` deletes the set (with confirm); Esc
-/// returns to the frame menu.
-fn handle_frame_selected(state: &mut EditorState, key: KeyEvent) -> Action {
+/// Typing the current frame's duration (in seconds). Enter stores it on the
+/// source (`0` or an empty field clears it); Esc returns to the frame menu.
+fn handle_frame_duration_input(state: &mut EditorState, key: KeyEvent) -> Action {
     let bindings = state.config.key_bindings.clone();
-    let frames = match &state.mode {
-        Mode::FrameSelected { frames } => frames.clone(),
+    let (mut buf, mut cursor) = match &state.mode {
+        Mode::FrameDurationInput { buf, cursor } => (buf.clone(), *cursor),
         _ => return Action::Continue,
     };
 
@@ -1108,73 +1498,422 @@ fn handle_frame_selected(state: &mut EditorState, key: KeyEvent) -> Action {
         state.status_message = None;
         return Action::Redraw;
     }
-    // [y] yank the (contiguous) block to the cross-deck frame clipboard, ready to
-    // paste into another open presentation.
-    if matches_binding(&bindings.frame_clip_copy, &key) {
-        if !is_contiguous_range(&frames) {
-            state.status_message =
-                Some("Select a contiguous range (e.g. 5-12) to copy across decks".into());
-            return Action::Redraw;
-        }
-        return Action::CopyFrameBlock {
-            lo: frames[0],
-            hi: frames[frames.len() - 1],
-        };
-    }
-    if matches_binding(&bindings.frame_delete, &key) {
-        if frames.len() >= state.source.frame_count {
-            state.status_message = Some("Can't delete every frame".into());
-            return Action::Redraw;
-        }
-        state.mode = Mode::Confirm {
-            message: format!("Delete {} selected frame(s)?", frames.len()),
-            selected: 0,
-            action: ConfirmAction::DeleteFrames { frames: frames.clone() },
-            return_mode: Box::new(Mode::FrameSelected { frames }),
+    if matches_binding(&bindings.confirm, &key) {
+        let trimmed = buf.trim();
+        let secs: f64 = if trimmed.is_empty() {
+            0.0
+        } else {
+            match trimmed.parse::<f64>() {
+                Ok(v) if v >= 0.0 => v,
+                _ => {
+                    state.status_message = Some("⚠ enter a duration in seconds (0 = none)".into());
+                    return Action::Redraw;
+                }
+            }
         };
+        let ms = (secs * 1000.0).round() as u64;
+        let frame = state.current_frame;
+        state.source.set_frame_duration(frame, (ms > 0).then_some(ms));
+        state.dirty = true;
+        state.status_message = Some(if ms > 0 {
+            format!("Frame {} shows for {}", frame + 1, super::state::format_secs(ms))
+        } else {
+            format!("Frame {} duration cleared", frame + 1)
+        });
+        state.mode = Mode::Normal;
         return Action::Redraw;
     }
-    // [m]ove / [c]opy the selected block. Both need a *contiguous* range — moving
-    // or duplicating a scattered set as a block has no clear meaning.
-    let copy = matches_binding(&bindings.frame_copy, &key);
-    if copy || matches_binding(&bindings.frame_move, &key) {
-        if !is_contiguous_range(&frames) {
-            state.status_message =
-                Some("Select a contiguous range (e.g. 5-12) to move/copy".into());
-            return Action::Redraw;
-        }
-        if !copy && frames.len() >= state.source.frame_count {
-            state.status_message = Some("Nowhere to move every frame".into());
-            return Action::Redraw;
-        }
-        // Start the scroll cursor at the block so the target is easy to pick.
-        state.current_frame = frames[0];
-        let verb = if copy { "Copy" } else { "Move" };
-        state.status_message = Some(format!(
-            "{verb} {} frame(s) — ←/→ pick target, [Enter] after / [b] before",
-            frames.len()
-        ));
-        state.mode = Mode::FrameRangePlace { frames, copy };
+    if frame_text_key(&key, &mut buf, &mut cursor) {
+        state.status_message = None;
+        state.mode = Mode::FrameDurationInput { buf, cursor };
         return Action::Redraw;
     }
     Action::Continue
 }
 
-/// A sorted, de-duplicated frame selection is contiguous iff it spans exactly its
-/// own length (no gaps) — `parse_frame_selection` already sorts and dedups.
-fn is_contiguous_range(frames: &[usize]) -> bool {
-    match (frames.first(), frames.last()) {
-        (Some(&lo), Some(&hi)) => hi - lo + 1 == frames.len(),
-        _ => false,
-    }
-}
-
-/// Scrolling the deck to place a moved/copied contiguous frame block: ←/→ pick a
-/// target slide, Enter drops the block after it, `b` before it.
-fn handle_frame_range_place(state: &mut EditorState, key: KeyEvent) -> Action {
+fn handle_frame_section_input(state: &mut EditorState, key: KeyEvent) -> Action {
     let bindings = state.config.key_bindings.clone();
-    let (frames, copy) = match &state.mode {
-        Mode::FrameRangePlace { frames, copy } => (frames.clone(), *copy),
+    let (mut buf, mut cursor) = match &state.mode {
+        Mode::FrameSectionInput { buf, cursor } => (buf.clone(), *cursor),
+        _ => return Action::Continue,
+    };
+
+    if matches_binding(&bindings.cancel, &key) {
+        state.mode = Mode::FrameMenu;
+        state.status_message = None;
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.confirm, &key) {
+        let name = buf.trim();
+        let frame = state.current_frame;
+        state.source.set_frame_section(frame, (!name.is_empty()).then(|| name.to_string()));
+        state.dirty = true;
+        state.status_message = Some(if name.is_empty() {
+            format!("Frame {} starts no section", frame + 1)
+        } else {
+            format!("Frame {} starts section \"{name}\"", frame + 1)
+        });
+        state.mode = Mode::Normal;
+        return Action::Redraw;
+    }
+    if frame_text_key(&key, &mut buf, &mut cursor) {
+        state.status_message = None;
+        state.mode = Mode::FrameSectionInput { buf, cursor };
+        return Action::Redraw;
+    }
+    Action::Continue
+}
+
+/// Typing a ripple shift: Enter moves the frame ranges (all objects, or the
+/// multi-select set it was opened for) by the signed count from the current
+/// frame onward.
+fn handle_frame_ripple_input(state: &mut EditorState, key: KeyEvent) -> Action {
+    let bindings = state.config.key_bindings.clone();
+    let (mut buf, mut cursor, members) = match &state.mode {
+        Mode::FrameRippleInput { buf, cursor, members } => (buf.clone(), *cursor, members.clone()),
+        _ => return Action::Continue,
+    };
+
+    if matches_binding(&bindings.cancel, &key) {
+        state.mode = match members {
+            Some(members) => Mode::SelectAction { members, selected: 0 },
+            None => Mode::FrameMenu,
+        };
+        state.status_message = None;
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.confirm, &key) {
+        let delta = match buf.trim().trim_start_matches('+').parse::<isize>() {
+            Ok(d) if d != 0 => d,
+            _ => {
+                state.status_message = Some("⚠ enter a frame count, e.g. +3 or -2".into());
+                return Action::Redraw;
+            }
+        };
+        let from = state.current_frame;
+        let moved = super::state::ripple_shift(&mut state.source, from, delta, members.as_deref());
+        state.current_frame = state.current_frame.min(state.source.frame_count.saturating_sub(1));
+        state.dirty = true;
+        state.status_message = Some(format!(
+            "Shifted {moved} object(s) {delta:+} frame(s) from frame {}",
+            from + 1
+        ));
+        state.mode = Mode::Normal;
+        return Action::Redraw;
+    }
+    if frame_text_key(&key, &mut buf, &mut cursor) {
+        state.status_message = None;
+        state.mode = Mode::FrameRippleInput { buf, cursor, members };
+        return Action::Redraw;
+    }
+    Action::Continue
+}
+
+/// Typing a stamp spec: Enter copies the selected object onto each frame of
+/// the range, stepping it by the optional `dx,dy` per frame.
+fn handle_stamp_frames_input(state: &mut EditorState, key: KeyEvent) -> Action {
+    let bindings = state.config.key_bindings.clone();
+    let (object_index, mut buf, mut cursor) = match &state.mode {
+        Mode::StampFramesInput { object_index, buf, cursor } => (*object_index, buf.clone(), *cursor),
+        _ => return Action::Continue,
+    };
+
+    if matches_binding(&bindings.cancel, &key) {
+        state.mode = Mode::SelectedObject { object_index };
+        state.status_message = None;
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.confirm, &key) {
+        let stamped = super::state::parse_stamp_spec(&buf, state.source.frame_count).and_then(|(first, last, dx, dy)| {
+            super::state::stamp_across_frames(&mut state.source, object_index, first, last, dx, dy)
+                .map(|n| (n, first, last))
+        });
+        match stamped {
+            Ok((n, first, last)) => {
+                state.dirty = true;
+                state.status_message = Some(format!(
+                    "Stamped {n} cop{} across frames {}-{}",
+                    if n == 1 { "y" } else { "ies" },
+                    first + 1,
+                    last + 1
+                ));
+                state.mode = Mode::SelectedObject { object_index };
+            }
+            Err(e) => state.status_message = Some(format!("⚠ {e}")),
+        }
+        return Action::Redraw;
+    }
+    if frame_text_key(&key, &mut buf, &mut cursor) {
+        state.status_message = None;
+        state.mode = Mode::StampFramesInput { object_index, buf, cursor };
+        return Action::Redraw;
+    }
+    Action::Continue
+}
+
+/// Typing the text to find: Tab toggles literal ↔ regex, and Enter checks the
+/// pattern matches something before asking for the replacement.
+fn handle_find_input(state: &mut EditorState, key: KeyEvent) -> Action {
+    let bindings = state.config.key_bindings.clone();
+    let (mut buf, mut cursor, mut regex) = match &state.mode {
+        Mode::FindInput { buf, cursor, regex } => (buf.clone(), *cursor, *regex),
+        _ => return Action::Continue,
+    };
+
+    if matches_binding(&bindings.cancel, &key) {
+        state.mode = Mode::Normal;
+        state.status_message = None;
+        return Action::Redraw;
+    }
+    if key.code == KeyCode::Tab && key.modifiers == KeyModifiers::NONE {
+        regex = !regex;
+        state.mode = Mode::FindInput { buf, cursor, regex };
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.confirm, &key) {
+        match search::compile(&buf, regex) {
+            Ok(re) => match search::count(&state.source, &re) {
+                0 => state.status_message = Some(format!("⚠ no matches for '{buf}'")),
+                n => {
+                    state.status_message = Some(format!("{n} match(es) for '{buf}'"));
+                    state.mode = Mode::ReplaceInput { find: buf, regex, buf: String::new(), cursor: 0 };
+                }
+            },
+            Err(e) => state.status_message = Some(format!("⚠ {e}")),
+        }
+        return Action::Redraw;
+    }
+    if frame_text_key(&key, &mut buf, &mut cursor) {
+        state.status_message = None;
+        state.mode = Mode::FindInput { buf, cursor, regex };
+        return Action::Redraw;
+    }
+    Action::Continue
+}
+
+/// Typing the replacement: Enter steps to the first match to confirm it; Esc
+/// goes back to the search text.
+fn handle_replace_input(state: &mut EditorState, key: KeyEvent) -> Action {
+    let bindings = state.config.key_bindings.clone();
+    let (find, regex, mut buf, mut cursor) = match &state.mode {
+        Mode::ReplaceInput { find, regex, buf, cursor } => (find.clone(), *regex, buf.clone(), *cursor),
+        _ => return Action::Continue,
+    };
+
+    if matches_binding(&bindings.cancel, &key) {
+        let cursor = find.chars().count();
+        state.mode = Mode::FindInput { buf: find, cursor, regex };
+        state.status_message = None;
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.confirm, &key) {
+        let first = search::compile(&find, regex).ok().and_then(|re| search::find_from(&state.source, &re, 0, None, 0));
+        match first {
+            Some(at) => {
+                show_match(state, &at);
+                state.mode = Mode::ReplaceConfirm { find, regex, replace: buf, at, replaced: 0, skipped: 0 };
+            }
+            None => state.status_message = Some(format!("⚠ no matches for '{find}'")),
+        }
+        return Action::Redraw;
+    }
+    if frame_text_key(&key, &mut buf, &mut cursor) {
+        state.status_message = None;
+        state.mode = Mode::ReplaceInput { find, regex, buf, cursor };
+        return Action::Redraw;
+    }
+    Action::Continue
+}
+
+/// Bring match `at` into view — on the current frame if its object is shown
+/// there, else the object's first — and quote it in the status line.
+fn show_match(state: &mut EditorState, at: &TextMatch) {
+    let range = state.source.effective_frame_range(at.object_index);
+    if !range.contains(state.current_frame) {
+        state.current_frame = range.start.min(state.source.frame_count.saturating_sub(1));
+    }
+    state.status_message = Some(format!("Replace? {}", search::context(&state.source, at)));
+}
+
+/// A match is up for confirmation: `y`/Enter replaces it, `n` skips it, `a`
+/// replaces it and all the rest; Esc stops. Each step moves on to the next
+/// match, and the last one ends back in Normal with a tally.
+fn handle_replace_confirm(state: &mut EditorState, key: KeyEvent) -> Action {
+    let bindings = state.config.key_bindings.clone();
+    let (find, regex, replace, mut at, mut replaced, mut skipped) = match &state.mode {
+        Mode::ReplaceConfirm { find, regex, replace, at, replaced, skipped } => {
+            (find.clone(), *regex, replace.clone(), at.clone(), *replaced, *skipped)
+        }
+        _ => return Action::Continue,
+    };
+    let Ok(re) = search::compile(&find, regex) else {
+        state.mode = Mode::Normal;
+        return Action::Redraw;
+    };
+    let plain = key.modifiers == KeyModifiers::NONE;
+
+    let next = if matches_binding(&bindings.confirm, &key) || (plain && key.code == KeyCode::Char('y')) {
+        replaced += 1;
+        state.dirty = true;
+        let resume = search::replace(&mut state.source, &re, &at, &replace, regex);
+        search::find_from(&state.source, &re, at.object_index, at.cell, resume)
+    } else if plain && key.code == KeyCode::Char('n') {
+        skipped += 1;
+        let resume = search::after(&state.source, &at);
+        search::find_from(&state.source, &re, at.object_index, at.cell, resume)
+    } else if plain && key.code == KeyCode::Char('a') {
+        state.dirty = true;
+        loop {
+            replaced += 1;
+            let resume = search::replace(&mut state.source, &re, &at, &replace, regex);
+            match search::find_from(&state.source, &re, at.object_index, at.cell, resume) {
+                Some(m) => at = m,
+                None => break,
+            }
+        }
+        None
+    } else if matches_binding(&bindings.cancel, &key) {
+        None
+    } else {
+        return Action::Continue;
+    };
+
+    match next {
+        Some(at) => {
+            show_match(state, &at);
+            state.mode = Mode::ReplaceConfirm { find, regex, replace, at, replaced, skipped };
+        }
+        None => {
+            state.status_message = Some(format!("Replaced {replaced} match(es), skipped {skipped}"));
+            state.mode = Mode::Normal;
+        }
+    }
+    Action::Redraw
+}
+
+/// A multi-frame selection is active: `d` deletes the set (with confirm); Esc
+/// returns to the frame menu.
+fn handle_frame_selected(state: &mut EditorState, key: KeyEvent) -> Action {
+    let bindings = state.config.key_bindings.clone();
+    let frames = match &state.mode {
+        Mode::FrameSelected { frames } => frames.clone(),
+        _ => return Action::Continue,
+    };
+
+    if matches_binding(&bindings.cancel, &key) {
+        state.mode = Mode::FrameMenu;
+        state.status_message = None;
+        return Action::Redraw;
+    }
+    // [y] yank the (contiguous) block to the cross-deck frame clipboard, ready to
+    // paste into another open presentation.
+    if matches_binding(&bindings.frame_clip_copy, &key) {
+        if !is_contiguous_range(&frames) {
+            state.status_message =
+                Some("Select a contiguous range (e.g. 5-12) to copy across decks".into());
+            return Action::Redraw;
+        }
+        return Action::CopyFrameBlock {
+            lo: frames[0],
+            hi: frames[frames.len() - 1],
+        };
+    }
+    if matches_binding(&bindings.frame_delete, &key) {
+        if frames.len() >= state.source.frame_count {
+            state.status_message = Some("Can't delete every frame".into());
+            return Action::Redraw;
+        }
+        state.mode = Mode::Confirm {
+            message: format!("Delete {} selected frame(s)?", frames.len()),
+            selected: 0,
+            action: ConfirmAction::DeleteFrames { frames: frames.clone() },
+            return_mode: Box::new(Mode::FrameSelected { frames }),
+        };
+        return Action::Redraw;
+    }
+    // [k]eep only the (contiguous) block: trim the deck down to it.
+    if matches_binding(&bindings.frame_trim, &key) {
+        if !is_contiguous_range(&frames) {
+            state.status_message = Some("Select a contiguous range (e.g. 5-12) to keep".into());
+            return Action::Redraw;
+        }
+        let (lo, hi) = (frames[0], frames[frames.len() - 1]);
+        let cut = state.source.frame_count - frames.len();
+        if cut == 0 {
+            state.status_message = Some("The selection is already the whole deck".into());
+            return Action::Redraw;
+        }
+        state.mode = Mode::Confirm {
+            message: format!("Keep only frames {}–{} (delete {cut} other frame(s))?", lo + 1, hi + 1),
+            selected: 0,
+            action: ConfirmAction::TrimFrames { lo, hi },
+            return_mode: Box::new(Mode::FrameSelected { frames }),
+        };
+        return Action::Redraw;
+    }
+    // [D]uplicate the block right after itself, and select the copy so it can
+    // be tweaked (or duplicated again) straight away — the repeat-a-build case.
+    if matches_binding(&bindings.frame_duplicate, &key) {
+        if !is_contiguous_range(&frames) {
+            state.status_message = Some("Select a contiguous range (e.g. 5-12) to duplicate".into());
+            return Action::Redraw;
+        }
+        let (lo, hi) = (frames[0], frames[frames.len() - 1]);
+        let (first, count) = super::state::copy_frames(&mut state.source, lo, hi, hi, false);
+        state.current_frame = first;
+        state.clipboard_sources.clear();
+        state.dirty = true;
+        state.status_message = Some(format!(
+            "Duplicated frames {}–{} as {}–{}",
+            lo + 1,
+            hi + 1,
+            first + 1,
+            first + count
+        ));
+        state.mode = Mode::FrameSelected { frames: (first..first + count).collect() };
+        return Action::Redraw;
+    }
+    // [m]ove / [c]opy the selected block. Both need a *contiguous* range — moving
+    // or duplicating a scattered set as a block has no clear meaning.
+    let copy = matches_binding(&bindings.frame_copy, &key);
+    if copy || matches_binding(&bindings.frame_move, &key) {
+        if !is_contiguous_range(&frames) {
+            state.status_message =
+                Some("Select a contiguous range (e.g. 5-12) to move/copy".into());
+            return Action::Redraw;
+        }
+        if !copy && frames.len() >= state.source.frame_count {
+            state.status_message = Some("Nowhere to move every frame".into());
+            return Action::Redraw;
+        }
+        // Start the scroll cursor at the block so the target is easy to pick.
+        state.current_frame = frames[0];
+        let verb = if copy { "Copy" } else { "Move" };
+        state.status_message = Some(format!(
+            "{verb} {} frame(s) — ←/→ pick target, [Enter] after / [b] before",
+            frames.len()
+        ));
+        state.mode = Mode::FrameRangePlace { frames, copy };
+        return Action::Redraw;
+    }
+    Action::Continue
+}
+
+/// A sorted, de-duplicated frame selection is contiguous iff it spans exactly its
+/// own length (no gaps) — `parse_frame_selection` already sorts and dedups.
+fn is_contiguous_range(frames: &[usize]) -> bool {
+    match (frames.first(), frames.last()) {
+        (Some(&lo), Some(&hi)) => hi - lo + 1 == frames.len(),
+        _ => false,
+    }
+}
+
+/// Scrolling the deck to place a moved/copied contiguous frame block: ←/→ pick a
+/// target slide, Enter drops the block after it, `b` before it.
+fn handle_frame_range_place(state: &mut EditorState, key: KeyEvent) -> Action {
+    let bindings = state.config.key_bindings.clone();
+    let (frames, copy) = match &state.mode {
+        Mode::FrameRangePlace { frames, copy } => (frames.clone(), *copy),
         _ => return Action::Continue,
     };
     let (lo, hi) = (frames[0], frames[frames.len() - 1]);
@@ -1446,6 +2185,213 @@ fn handle_frame_paste_place(state: &mut EditorState, key: KeyEvent) -> Action {
     }
 }
 
+/// The layer panel: Up/Down pick a layer, `v` shows/hides it, `l` locks or
+/// unlocks it. Toggling a layer only objects mention adds it to the registry.
+/// The lanes view: ↑/↓ pick an object, Tab swaps which end of its frame range
+/// is held, ←/→ move that end a frame, Enter opens the object's menu.
+fn handle_lanes(state: &mut EditorState, key: KeyEvent) -> Action {
+    let bindings = state.config.key_bindings.clone();
+    let Mode::Lanes { selected, at_end } = state.mode else {
+        return Action::Continue;
+    };
+    let count = state.source.objects.len();
+    if count == 0 || matches_binding(&bindings.cancel, &key) {
+        state.mode = Mode::Normal;
+        return Action::Redraw;
+    }
+    let selected = selected.min(count - 1);
+
+    let up = matches_binding(&bindings.move_up, &key);
+    if up || matches_binding(&bindings.move_down, &key) {
+        let selected = match up {
+            true if selected == 0 => count - 1,
+            true => selected - 1,
+            false => (selected + 1) % count,
+        };
+        state.mode = Mode::Lanes { selected, at_end };
+        state.status_message = lane_status(&state.source.objects[selected]);
+        return Action::Redraw;
+    }
+    if key.code == KeyCode::Tab {
+        state.mode = Mode::Lanes { selected, at_end: !at_end };
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.next_frame, &key) || matches_binding(&bindings.prev_frame, &key) {
+        let Some(range) = super::state::scene_object_frame_range(&state.source.objects[selected]) else {
+            state.status_message = Some("This object spans its members' frames".into());
+            return Action::Redraw;
+        };
+        let held = if at_end { range.end.saturating_sub(1) } else { range.start };
+        let frame = if matches_binding(&bindings.next_frame, &key) {
+            held + 1
+        } else {
+            held.saturating_sub(1)
+        };
+        set_lane_end(state, selected, at_end, frame);
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.confirm, &key) {
+        state.mode = Mode::SelectedObject { object_index: selected };
+        return Action::Redraw;
+    }
+    Action::Continue
+}
+
+/// "Frames a–b" (1-based, inclusive) for a lane, or `None` for an auto-span group.
+fn lane_status(obj: &SceneObject) -> Option<String> {
+    super::state::scene_object_frame_range(obj)
+        .map(|r| format!("Frames {}–{}", r.start + 1, r.end))
+}
+
+/// Move one end of object `index`'s frame range to `frame` (the start, or the
+/// last frame shown when `at_end`), keeping at least one frame and staying in
+/// the deck. Moving an `Animation`'s span re-locks the objects it drives.
+fn set_lane_end(state: &mut EditorState, index: usize, at_end: bool, frame: usize) {
+    let frame_count = state.source.frame_count.max(1);
+    let Some(range) = super::state::scene_object_frame_range_mut(&mut state.source.objects[index]) else {
+        return;
+    };
+    let (start, end) = (range.start, range.end);
+    if at_end {
+        range.end = (frame + 1).clamp(start + 1, frame_count.max(start + 1));
+    } else {
+        range.start = frame.min(end.saturating_sub(1));
+    }
+    if (range.start, range.end) == (start, end) {
+        return;
+    }
+    state.dirty = true;
+    if let SceneObject::Animation(a) = &state.source.objects[index] {
+        let id = a.id;
+        for i in 0..state.source.objects.len() {
+            if super::state::referenced_anim_ids(&state.source.objects[i]).contains(&id) {
+                lock_range_to_animation(&mut state.source, i);
+            }
+        }
+    }
+    state.status_message = lane_status(&state.source.objects[index]);
+}
+
+/// A click or drag in the lanes view: a press on a lane selects it and grabs
+/// the nearer end of its range; pressing or dragging moves the held end to the
+/// frame under the pointer.
+fn lanes_mouse(state: &mut EditorState, mouse: MouseEvent, layout: &Layout) -> Action {
+    let Mode::Lanes { selected, at_end } = state.mode else {
+        return Action::Continue;
+    };
+    let (x0, len) = super::lanes::track(layout);
+    let rows = layout.canvas_height as usize;
+    if len == 0 || mouse.column < x0 || mouse.row < layout.canvas_y || mouse.row >= layout.canvas_y + layout.canvas_height {
+        return Action::Continue;
+    }
+    let frame = super::timeline::frame_at((mouse.column - x0) as usize, len, state.source.frame_count);
+    let (index, at_end) = match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            let index = super::lanes::first_lane(selected, rows) + (mouse.row - layout.canvas_y) as usize;
+            let Some(range) = state.source.objects.get(index).and_then(super::state::scene_object_frame_range) else {
+                return Action::Continue;
+            };
+            let to_end = frame.abs_diff(range.end.saturating_sub(1)) < frame.abs_diff(range.start);
+            (index, to_end)
+        }
+        MouseEventKind::Drag(MouseButton::Left) => (selected, at_end),
+        _ => return Action::Continue,
+    };
+    if index >= state.source.objects.len() {
+        return Action::Continue;
+    }
+    state.mode = Mode::Lanes { selected: index, at_end };
+    set_lane_end(state, index, at_end, frame);
+    Action::Redraw
+}
+
+fn handle_layers(state: &mut EditorState, key: KeyEvent) -> Action {
+    let bindings = state.config.key_bindings.clone();
+    let Mode::Layers { selected } = state.mode else {
+        return Action::Continue;
+    };
+    let names = state.source.layer_names();
+    if names.is_empty() || matches_binding(&bindings.cancel, &key) {
+        state.mode = Mode::Normal;
+        return Action::Redraw;
+    }
+    let count = names.len();
+    let selected = selected.min(count - 1);
+
+    if matches_binding(&bindings.move_up, &key) {
+        state.mode = Mode::Layers { selected: if selected == 0 { count - 1 } else { selected - 1 } };
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.move_down, &key) {
+        state.mode = Mode::Layers { selected: (selected + 1) % count };
+        return Action::Redraw;
+    }
+    let name = &names[selected];
+    if matches_binding(&bindings.layer_toggle_visible, &key) {
+        let layer = state.source.layer_entry(name);
+        layer.visible = !layer.visible;
+        let shown = if layer.visible { "shown" } else { "hidden" };
+        state.status_message = Some(format!("Layer {name} {shown}"));
+        state.dirty = true;
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.layer_toggle_lock, &key) {
+        let layer = state.source.layer_entry(name);
+        layer.locked = !layer.locked;
+        let locked = if layer.locked { "locked" } else { "unlocked" };
+        state.status_message = Some(format!("Layer {name} {locked}"));
+        state.dirty = true;
+        return Action::Redraw;
+    }
+    Action::Continue
+}
+
+/// The diagnostics panel: Up/Down pick a finding (its full text shows in the
+/// status line); Enter jumps to the frame it is about and selects its object.
+fn handle_diagnostics(state: &mut EditorState, key: KeyEvent) -> Action {
+    let bindings = state.config.key_bindings.clone();
+    let Mode::Diagnostics { selected, items } = &mut state.mode else {
+        return Action::Continue;
+    };
+    if items.is_empty() || matches_binding(&bindings.cancel, &key) {
+        state.mode = Mode::Normal;
+        state.status_message = None;
+        return Action::Redraw;
+    }
+    let count = items.len();
+    if matches_binding(&bindings.move_up, &key) || matches_binding(&bindings.move_down, &key) {
+        *selected = if matches_binding(&bindings.move_up, &key) {
+            if *selected == 0 { count - 1 } else { *selected - 1 }
+        } else {
+            (*selected + 1) % count
+        };
+        state.status_message = Some(describe_diagnostic(&items[*selected]));
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.confirm, &key) {
+        let d = items[(*selected).min(count - 1)].clone();
+        let object = d.object.filter(|&i| i < state.source.objects.len());
+        let range = object.and_then(|i| super::state::scene_object_frame_range(&state.source.objects[i]).cloned());
+        if let Some(frame) = d.frame.or(range.as_ref().map(|r| r.start)) {
+            state.current_frame = frame.min(state.source.frame_count.saturating_sub(1));
+        }
+        state.mode = match object {
+            Some(object_index) if range.is_none_or(|r| r.contains(state.current_frame)) => {
+                Mode::SelectedObject { object_index }
+            }
+            _ => Mode::Normal,
+        };
+        state.status_message = Some(describe_diagnostic(&d));
+        return Action::Redraw;
+    }
+    Action::Continue
+}
+
+/// A diagnostic as one status-line message.
+fn describe_diagnostic(d: &crate::validate::Diagnostic) -> String {
+    format!("{}: {}: {}", d.severity, d.path, d.message)
+}
+
 /// The presentations hub: a list of the open decks (Enter switches to the
 /// highlighted one) plus open-file / save-as / settings / fullscreen actions.
 /// Switching decks is an `Editor`-level action (a deck can't reach its siblings),
@@ -1492,7 +2438,7 @@ fn handle_presentation_menu(state: &mut EditorState, key: KeyEvent) -> Action {
         let width_buf = state.source.width.to_string();
         let height_buf = state.source.height.to_string();
         let cursor = width_buf.chars().count();
-        state.mode = Mode::Settings { selected_field: 0, width_buf, height_buf, cursor };
+        state.mode = Mode::Settings { selected_field: 0, width_buf, height_buf, fit: CanvasFit::TopLeft, cursor };
         state.status_message = None;
         return Action::Redraw;
     }
@@ -1607,7 +2553,19 @@ fn commit_add_object(state: &mut EditorState, index: usize) -> Action {
         };
         state.status_message = Some("Morph: pick the starting art".into());
     } else {
-        let obj = object_defaults::create_default(index, state.current_frame);
+        let mut obj = object_defaults::create_default(index, state.current_frame);
+        // Footers, watermarks and background effects are meant for every
+        // frame: they span the deck, a footer on the bottom row.
+        let deck = state.source.frame_count.max(1);
+        match &mut obj {
+            SceneObject::Footer(f) => {
+                (f.frames.start, f.frames.end) = (0, deck);
+                f.position.y = Coordinate::Fixed(state.source.height.saturating_sub(1) as f64);
+            }
+            SceneObject::Watermark(w) => (w.frames.start, w.frames.end) = (0, deck),
+            SceneObject::BackgroundEffect(b) => (b.frames.start, b.frames.end) = (0, deck),
+            _ => {}
+        }
         state.source.objects.push(obj);
         state.dirty = true;
         let new_index = state.source.objects.len() - 1;
@@ -1689,6 +2647,36 @@ fn handle_multi_select(state: &mut EditorState, key: KeyEvent) -> Action {
         };
         return Action::Redraw;
     }
+    // G: group the selection in place — the highlighted set becomes a new
+    // group without detouring through the Add-Object member picker.
+    if purpose == MultiSelectPurpose::Select && matches_binding(&bindings.group_selection, &key) {
+        let mut chosen = members;
+        if chosen.is_empty() {
+            chosen.push(visible[selected]);
+        }
+        add_group(state, chosen);
+        return Action::Redraw;
+    }
+    // e: straight to the properties — the shared ones of a toggled set, or
+    // the highlighted object's own when nothing (or one thing) is toggled.
+    if purpose == MultiSelectPurpose::Select && matches_binding(&bindings.edit_object, &key) {
+        match members.as_slice() {
+            [] | [_] => {
+                let object_index = members.first().copied().unwrap_or(visible[selected]);
+                state.mode = Mode::EditProperties {
+                    object_index,
+                    selected_property: 0,
+                    editing_value: None,
+                    cursor: 0,
+                    scroll: 0,
+                    panel_scroll: 0,
+                    dropdown: None,
+                };
+            }
+            _ => open_multi_properties(state, members, edit_props_action_index()),
+        }
+        return Action::Redraw;
+    }
     // Enter: commit. Group builds a group from the toggled set directly; the
     // general Select routes to the single-object menu or the action sub-menu
     // (where copy/converge/delete live). With nothing explicitly toggled, fall
@@ -1699,20 +2687,7 @@ fn handle_multi_select(state: &mut EditorState, key: KeyEvent) -> Action {
             chosen.push(visible[selected]);
         }
         match purpose {
-            MultiSelectPurpose::Group => {
-                let group = SceneObject::Group(Group {
-                    members: chosen,
-                    // Auto by default: the group's span follows its members'
-                    // ranges until an explicit range is set in the props panel.
-                    frames: None,
-                    z_order: 0,
-                });
-                state.source.objects.push(group);
-                state.dirty = true;
-                let new_index = state.source.objects.len() - 1;
-                state.mode = ep_browse(new_index, 0, 0);
-                state.status_message = Some("Added Group".into());
-            }
+            MultiSelectPurpose::Group => add_group(state, chosen),
             MultiSelectPurpose::Select => {
                 // One object → its menu; many → the action sub-menu.
                 state.mode = if chosen.len() == 1 {
@@ -1728,6 +2703,23 @@ fn handle_multi_select(state: &mut EditorState, key: KeyEvent) -> Action {
     Action::Continue
 }
 
+/// Push a new `Group` of `members` and open its properties.
+fn add_group(state: &mut EditorState, members: Vec<usize>) {
+    let group = SceneObject::Group(Group {
+        members,
+        // Auto by default: the group's span follows its members'
+        // ranges until an explicit range is set in the props panel.
+        frames: None,
+        z_order: 0,
+        layer: None,
+    });
+    state.source.objects.push(group);
+    state.dirty = true;
+    let new_index = state.source.objects.len() - 1;
+    state.mode = ep_browse(new_index, 0, 0);
+    state.status_message = Some("Added Group".into());
+}
+
 /// Actions offered by the multi-object select sub-menu ([`Mode::SelectAction`]),
 /// in display order.
 #[derive(Clone, Copy, PartialEq)]
@@ -1736,6 +2728,9 @@ enum SelectActionKind {
     Converge,
     Delete,
     EditProps,
+    Ripple,
+    Align(Align),
+    Distribute(Axis),
 }
 
 const SELECT_ACTIONS: &[(SelectActionKind, &str)] = &[
@@ -1743,6 +2738,15 @@ const SELECT_ACTIONS: &[(SelectActionKind, &str)] = &[
     (SelectActionKind::Converge, "Converge"),
     (SelectActionKind::Delete, "Delete"),
     (SelectActionKind::EditProps, "Edit Props"),
+    (SelectActionKind::Ripple, "Ripple Shift"),
+    (SelectActionKind::Align(Align::Left), "Align Left"),
+    (SelectActionKind::Align(Align::Right), "Align Right"),
+    (SelectActionKind::Align(Align::Top), "Align Top"),
+    (SelectActionKind::Align(Align::Bottom), "Align Bottom"),
+    (SelectActionKind::Align(Align::Center), "Align Center"),
+    (SelectActionKind::Align(Align::Middle), "Align Middle"),
+    (SelectActionKind::Distribute(Axis::Horizontal), "Distribute H"),
+    (SelectActionKind::Distribute(Axis::Vertical), "Distribute V"),
 ];
 
 /// The action sub-menu's row labels, in display order — for the panel renderer.
@@ -1751,7 +2755,8 @@ pub(crate) fn select_action_labels() -> Vec<&'static str> {
 }
 
 /// The action sub-menu shown after selecting 2+ objects: pick what to do with
-/// the whole set (copy to clipboard, converge onto a shared point, or delete).
+/// the whole set (copy to clipboard, converge onto a shared point, delete,
+/// align or distribute it, …).
 fn handle_select_action(state: &mut EditorState, key: KeyEvent) -> Action {
     let bindings = state.config.key_bindings.clone();
     let (members, selected) = match &state.mode {
@@ -1794,22 +2799,23 @@ fn handle_select_action(state: &mut EditorState, key: KeyEvent) -> Action {
                     return_mode: Box::new(Mode::SelectAction { members, selected }),
                 };
             }
-            SelectActionKind::EditProps => {
-                // Bulk-edit the shared properties. Refuse if the selection has no
-                // common editable property (e.g. a Label + a Loop).
-                let common = properties::common_properties(&state.source.objects, &members);
-                if common.is_empty() {
-                    state.status_message =
-                        Some("These objects share no editable properties".into());
-                    state.mode = Mode::SelectAction { members, selected };
+            SelectActionKind::EditProps => open_multi_properties(state, members, selected),
+            SelectActionKind::Ripple => {
+                state.mode = Mode::FrameRippleInput { buf: String::new(), cursor: 0, members: Some(members) };
+            }
+            // Arranging keeps the selection, so another align can follow.
+            SelectActionKind::Align(align) => {
+                properties::align_objects(&mut state.source.objects, &members, align);
+                state.dirty = true;
+                state.status_message = Some(format!("Aligned {} objects", members.len()));
+            }
+            SelectActionKind::Distribute(axis) => {
+                if members.len() < 3 {
+                    state.status_message = Some("Distributing needs 3 or more objects".into());
                 } else {
-                    state.status_message = Some(format!(
-                        "Editing {} shared propert{} across {} objects",
-                        common.len(),
-                        if common.len() == 1 { "y" } else { "ies" },
-                        members.len(),
-                    ));
-                    state.mode = emp_browse(members, 0, 0);
+                    properties::distribute_objects(&mut state.source.objects, &members, axis);
+                    state.dirty = true;
+                    state.status_message = Some(format!("Distributed {} objects", members.len()));
                 }
             }
         }
@@ -1819,6 +2825,24 @@ fn handle_select_action(state: &mut EditorState, key: KeyEvent) -> Action {
     Action::Continue
 }
 
+/// Open the bulk-edit panel on the properties `members` share. Refused (back to
+/// the action sub-menu at `selected`) if they share none, e.g. a Label + a Loop.
+fn open_multi_properties(state: &mut EditorState, members: Vec<usize>, selected: usize) {
+    let common = properties::common_properties(&state.source.objects, &members);
+    if common.is_empty() {
+        state.status_message = Some("These objects share no editable properties".into());
+        state.mode = Mode::SelectAction { members, selected };
+    } else {
+        state.status_message = Some(format!(
+            "Editing {} shared propert{} across {} objects",
+            common.len(),
+            if common.len() == 1 { "y" } else { "ies" },
+            members.len(),
+        ));
+        state.mode = emp_browse(members, 0, 0);
+    }
+}
+
 /// The confirm-dialog message for deleting object `idx`. A `Group` ungroups
 /// (members kept); an `Animation` spells out that the objects it drives will be
 /// frozen (deleting it removes the motion, not just the auto-play sidecar);
@@ -2006,7 +3030,7 @@ fn handle_paste_placing(state: &mut EditorState, key: KeyEvent) -> Action {
 fn is_placement_prop(name: &str) -> bool {
     matches!(
         name,
-        "x" | "y" | "width" | "height" | "first_frame" | "last_frame" | "z_order"
+        "x" | "y" | "width" | "height" | "first_frame" | "last_frame" | "z_order" | "layer"
     )
 }
 
@@ -2070,6 +3094,53 @@ fn handle_selected_object(state: &mut EditorState, key: KeyEvent) -> Action {
     let is_group = matches!(state.source.objects[object_index], SceneObject::Group(_));
     let is_table = matches!(state.source.objects[object_index], SceneObject::Table(_));
 
+    // [i]n place: type a label's text on the canvas, or a table's first cell
+    // (arrows in the cell view then reach the others).
+    if matches_binding(&bindings.edit_in_place, &key) {
+        match &state.source.objects[object_index] {
+            SceneObject::Label(l) => {
+                state.mode = Mode::EditInPlace { object_index, buf: l.text.clone(), cursor: l.text.chars().count() };
+            }
+            SceneObject::Table(_) => {
+                let buf = table_cell_content(state, object_index, 0, 0);
+                state.mode = Mode::TableEditCellProps {
+                    object_index,
+                    cursor_row: 0,
+                    cursor_col: 0,
+                    selected_cells: Vec::new(),
+                    sub_state: TableCellSubState::EditingContent { row: 0, col: 0, cursor: buf.chars().count(), buf },
+                };
+            }
+            _ => return Action::Continue,
+        }
+        state.status_message = None;
+        return Action::Redraw;
+    }
+
+    // s[t]amp a group: copy it one column clear of its right edge and select
+    // the copy, so the next press stamps beside that.
+    if is_group && matches_binding(&bindings.stamp_group, &key) {
+        let (_, _, width, _) = properties::group_bounds(&state.source.objects, object_index);
+        let before = state.source.objects.len();
+        match state.source.clone_group(object_index, width.ceil() as i32 + 1, 0) {
+            Ok(copy) => {
+                let n = state.source.objects.len() - before;
+                state.status_message = Some(format!("Stamped group ({} object{})", n, if n == 1 { "" } else { "s" }));
+                state.mode = Mode::SelectedObject { object_index: copy };
+                state.dirty = true;
+            }
+            Err(e) => state.status_message = Some(e),
+        }
+        return Action::Redraw;
+    }
+
+    // sta[m]p across frames: prompt for the range and per-frame step.
+    if matches_binding(&bindings.stamp_frames, &key) {
+        state.mode = Mode::StampFramesInput { object_index, buf: String::new(), cursor: 0 };
+        state.status_message = None;
+        return Action::Redraw;
+    }
+
     // Ctrl+Shift+Arrow: shrink from that edge
     if key.modifiers.contains(KeyModifiers::CONTROL | KeyModifiers::SHIFT) {
         let (dw, dh) = match key.code {
@@ -2137,8 +3208,10 @@ fn handle_selected_object(state: &mut EditorState, key: KeyEvent) -> Action {
         }
     }
 
-    // Plain Arrow keys: move
-    if key.modifiers == KeyModifiers::NONE {
+    // Plain Arrow keys: move; with the coarse modifier, `coarse_step` cells.
+    let coarse = holds_modifier(&bindings.coarse_modifier, &key);
+    if key.modifiers == KeyModifiers::NONE || coarse {
+        let step = if coarse { i32::from(state.config.coarse_step.max(1)) } else { 1 };
         let (dx, dy) = match key.code {
             KeyCode::Left => (-1, 0),
             KeyCode::Right => (1, 0),
@@ -2146,6 +3219,7 @@ fn handle_selected_object(state: &mut EditorState, key: KeyEvent) -> Action {
             KeyCode::Down => (0, 1),
             _ => (0, 0),
         };
+        let (dx, dy) = (dx * step, dy * step);
         if dx != 0 || dy != 0 {
             if is_group {
                 properties::move_group(&mut state.source.objects, object_index, dx, dy);
@@ -2162,8 +3236,9 @@ fn handle_selected_object(state: &mut EditorState, key: KeyEvent) -> Action {
 
 /// Resize mode: plain arrow keys adjust the selected object's far edge —
 /// Left/Right change width, Up/Down change height (Down/Right grow, Up/Left
-/// shrink). Plain arrows are delivered by every terminal, so this works where
-/// Shift+Up/Down (captured for scrollback by many terminals) does not.
+/// shrink) — by one cell, or `coarse_step` with the coarse modifier. Plain
+/// arrows are delivered by every terminal, so this works where Shift+Up/Down
+/// (captured for scrollback by many terminals) does not.
 fn handle_resize_object(state: &mut EditorState, key: KeyEvent) -> Action {
     let bindings = state.config.key_bindings.clone();
 
@@ -2178,7 +3253,8 @@ fn handle_resize_object(state: &mut EditorState, key: KeyEvent) -> Action {
         return Action::Redraw;
     }
 
-    if key.modifiers != KeyModifiers::NONE
+    let coarse = holds_modifier(&bindings.coarse_modifier, &key);
+    if (key.modifiers != KeyModifiers::NONE && !coarse)
         || !matches!(key.code, KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down)
     {
         return Action::Continue;
@@ -2189,32 +3265,37 @@ fn handle_resize_object(state: &mut EditorState, key: KeyEvent) -> Action {
     let frame = state.current_frame;
     let anims = AnimSpans::of(&state.source);
     let objects = &mut state.source.objects;
-
-    if is_group {
-        // Grow/shrink the group's bounding box, anchored at its top-left.
-        match key.code {
-            KeyCode::Right => properties::resize_group(objects, object_index, 1, 0, true, true),
-            KeyCode::Left  => properties::resize_group(objects, object_index, -1, 0, true, true),
-            KeyCode::Down  => properties::resize_group(objects, object_index, 0, 1, true, true),
-            KeyCode::Up    => properties::resize_group(objects, object_index, 0, -1, true, true),
-            _ => {}
-        }
-    } else if is_table {
-        // Table height auto-fits content, so seed vertical resizes from natural.
-        match key.code {
-            KeyCode::Right => properties::resize_object(&mut objects[object_index], 1, 0),
-            KeyCode::Left  => properties::shrink_object(&mut objects[object_index], 1, 0),
-            KeyCode::Down  => grow_table_height(&mut objects[object_index], frame, &anims, 1),
-            KeyCode::Up    => grow_table_height(&mut objects[object_index], frame, &anims, -1),
-            _ => {}
-        }
-    } else {
-        match key.code {
-            KeyCode::Right => properties::resize_object(&mut objects[object_index], 1, 0),
-            KeyCode::Left  => properties::shrink_object(&mut objects[object_index], 1, 0),
-            KeyCode::Down  => properties::resize_object(&mut objects[object_index], 0, 1),
-            KeyCode::Up    => properties::shrink_object(&mut objects[object_index], 0, 1),
-            _ => {}
+    // A coarse step is that many single steps, so tables and groups resize
+    // exactly as they do a cell at a time.
+    let steps = if coarse { state.config.coarse_step.max(1) } else { 1 };
+
+    for _ in 0..steps {
+        if is_group {
+            // Grow/shrink the group's bounding box, anchored at its top-left.
+            match key.code {
+                KeyCode::Right => properties::resize_group(objects, object_index, 1, 0, true, true),
+                KeyCode::Left  => properties::resize_group(objects, object_index, -1, 0, true, true),
+                KeyCode::Down  => properties::resize_group(objects, object_index, 0, 1, true, true),
+                KeyCode::Up    => properties::resize_group(objects, object_index, 0, -1, true, true),
+                _ => {}
+            }
+        } else if is_table {
+            // Table height auto-fits content, so seed vertical resizes from natural.
+            match key.code {
+                KeyCode::Right => properties::resize_object(&mut objects[object_index], 1, 0),
+                KeyCode::Left  => properties::shrink_object(&mut objects[object_index], 1, 0),
+                KeyCode::Down  => grow_table_height(&mut objects[object_index], frame, &anims, 1),
+                KeyCode::Up    => grow_table_height(&mut objects[object_index], frame, &anims, -1),
+                _ => {}
+            }
+        } else {
+            match key.code {
+                KeyCode::Right => properties::resize_object(&mut objects[object_index], 1, 0),
+                KeyCode::Left  => properties::shrink_object(&mut objects[object_index], 1, 0),
+                KeyCode::Down  => properties::resize_object(&mut objects[object_index], 0, 1),
+                KeyCode::Up    => properties::shrink_object(&mut objects[object_index], 0, 1),
+                _ => {}
+            }
         }
     }
     state.dirty = true;
@@ -2293,6 +3374,17 @@ fn handle_confirm(state: &mut EditorState, key: KeyEvent) -> Action {
                         ));
                         Mode::Normal
                     }
+                    ConfirmAction::TrimFrames { lo, hi } => {
+                        let removed = super::state::trim_frames(&mut state.source, lo, hi);
+                        state.clipboard_sources.clear();
+                        state.current_frame = state.current_frame.clamp(lo, hi) - lo;
+                        state.dirty = true;
+                        state.status_message = Some(format!(
+                            "Trimmed {removed} frame(s) (now {})",
+                            state.source.frame_count
+                        ));
+                        Mode::Normal
+                    }
                     ConfirmAction::DeleteObject { object_index } => {
                         if object_index < state.source.objects.len() {
                             // Deleting an `Animation` removes the *whole* animation:
@@ -2355,11 +3447,9 @@ fn handle_confirm(state: &mut EditorState, key: KeyEvent) -> Action {
                             state.dirty = true;
                             state.status_message = Some(format!("Removed column {}", col_index + 1));
                         }
-                        let new_count =
-                            properties::get_properties(&state.source.objects, object_index).len();
                         Mode::EditProperties {
                             object_index,
-                            selected_property: 0_usize.min(new_count.saturating_sub(1)),
+                            selected_property: 0,
                             editing_value: None,
                             cursor: 0,
                             scroll: 0,
@@ -2483,6 +3573,9 @@ fn handle_edit_properties(state: &mut EditorState, key: KeyEvent) -> Action {
         if let Some(opts) = properties::dropdown_options_for(&prop_kind) {
             // Open dropdown; pre-select the matching option if recognised.
             let dd_sel = opts.iter().position(|&o| o == prop_value).unwrap_or(0);
+            if previews_live(&prop_kind) {
+                state.dropdown_preview = Some(prop_value.clone());
+            }
             state.mode = ep_dropdown(object_index, selected_property, dd_sel, panel_scroll);
         } else if prop_kind == PropertyKind::Coordinate {
             if let Some(coord) = properties::get_coord(&state.source.objects[object_index], prop_name) {
@@ -2564,6 +3657,22 @@ fn handle_edit_properties(state: &mut EditorState, key: KeyEvent) -> Action {
         }
     }
 
+    // [p]ick: set a Coordinate property by pointing at the canvas.
+    if prop_kind == PropertyKind::Coordinate && matches_binding(&bindings.pick_coordinate, &key) {
+        if let Some((col, row)) = pick_start(state, object_index, prop_name) {
+            state.mode = Mode::PickCoordinate {
+                object_index,
+                return_property: selected_property,
+                panel_scroll,
+                property_name: prop_name,
+                col,
+                row,
+            };
+            state.status_message = Some(pick_status(state));
+        }
+        return Action::Redraw;
+    }
+
     Action::Continue
 }
 
@@ -2584,6 +3693,9 @@ fn handle_edit_value(state: &mut EditorState, key: KeyEvent) -> Action {
     let is_text = props[selected_property].kind == properties::PropertyKind::Text;
     let prefix0 = prop_name.chars().count() + 2;
 
+    if is_text && matches_binding(&state.config.key_bindings.external_editor, &key) {
+        return Action::ExternalEdit(editing_value.unwrap_or_default());
+    }
     let newline = matches_binding(&state.config.key_bindings.insert_newline, &key);
     let mut te = TextEdit::new(editing_value.unwrap_or_default(), cursor);
 
@@ -2610,6 +3722,55 @@ fn handle_edit_value(state: &mut EditorState, key: KeyEvent) -> Action {
     }
 }
 
+/// Typing a label's text in place on the canvas. Enter applies the buffer as
+/// its `text` (through the same path as the properties panel, so linked copies
+/// follow); Esc drops it.
+fn handle_edit_in_place(state: &mut EditorState, key: KeyEvent) -> Action {
+    let (object_index, buf, cursor) = match &state.mode {
+        Mode::EditInPlace { object_index, buf, cursor } => (*object_index, buf.clone(), *cursor),
+        _ => return Action::Continue,
+    };
+    if matches_binding(&state.config.key_bindings.external_editor, &key) {
+        return Action::ExternalEdit(buf);
+    }
+    let newline = matches_binding(&state.config.key_bindings.insert_newline, &key);
+    let mut te = TextEdit::new(buf, cursor);
+    match te.handle_key(&key, newline) {
+        TextAction::Ignored => Action::Continue,
+        TextAction::Cancel => {
+            state.mode = Mode::SelectedObject { object_index };
+            Action::Redraw
+        }
+        TextAction::Commit => {
+            apply_property(state, object_index, "text", &te.buf);
+            state.mode = Mode::SelectedObject { object_index };
+            Action::Redraw
+        }
+        TextAction::Edited => {
+            state.mode = Mode::EditInPlace { object_index, buf: te.buf, cursor: te.cursor };
+            Action::Redraw
+        }
+    }
+}
+
+/// Replace the buffer of the text being edited (a property value or a table
+/// cell's content) with `text` from the external editor, cursor at its end.
+/// The edit stays open, so Enter still applies it and Esc still discards it.
+pub fn replace_edit_buffer(state: &mut EditorState, text: String) {
+    let end = text.chars().count();
+    match &mut state.mode {
+        Mode::EditProperties { editing_value: Some(buf), cursor, .. }
+        | Mode::EditInPlace { buf, cursor, .. }
+        | Mode::TableEditCellProps {
+            sub_state: TableCellSubState::EditingContent { buf, cursor, .. }, ..
+        } => {
+            *buf = text;
+            *cursor = end;
+        }
+        _ => {}
+    }
+}
+
 /// Horizontal + vertical scroll for editing a short value inside the narrow
 /// right panel (coordinates, colors, char pickers). Keeps the cursor visible
 /// without jumping the window while it is already in view.
@@ -2664,6 +3825,20 @@ fn dropdown_key(
     }
 }
 
+/// Whether a dropdown of `kind` tries the highlighted option on the canvas as
+/// it moves — colours and the arrow's head and body characters, which are hard
+/// to judge from the list alone.
+fn previews_live(kind: &properties::PropertyKind) -> bool {
+    use properties::PropertyKind;
+    matches!(kind, PropertyKind::Color | PropertyKind::HeadChar | PropertyKind::BodyChar)
+}
+
+/// Show `value` for `name` on the object without marking the deck dirty; the
+/// dropdown puts the original back or applies the choice properly on close.
+fn preview_property(state: &mut EditorState, object_index: usize, name: &str, value: &str) {
+    let _ = properties::set_property(&mut state.source.objects[object_index], name, value);
+}
+
 fn handle_dropdown(state: &mut EditorState, key: KeyEvent) -> Action {
     let (object_index, selected_property, dd_sel, panel_scroll) = match &state.mode {
         Mode::EditProperties { object_index, selected_property, panel_scroll, dropdown: Some(sel), .. } =>
@@ -2679,18 +3854,33 @@ fn handle_dropdown(state: &mut EditorState, key: KeyEvent) -> Action {
     let sentinel = properties::dropdown_custom_sentinel(&prop_kind);
     let bindings = state.config.key_bindings.clone();
 
+    // The value before any live preview, for Esc and the custom entry.
+    let original = state.dropdown_preview.clone();
+    let prop_value = original.clone().unwrap_or(prop_value);
+
     match dropdown_key(&key, &bindings, dd_sel, options.len()) {
         DropdownKey::Ignored => Action::Continue,
         DropdownKey::Cancel => {
+            if let Some(value) = state.dropdown_preview.take() {
+                preview_property(state, object_index, prop_name, &value);
+            }
             state.mode = ep_browse(object_index, selected_property, panel_scroll);
             Action::Redraw
         }
         DropdownKey::Move(n) => {
+            if let Some(value) = &original {
+                // The custom entry has nothing to show yet, so it shows the original.
+                let shown = if options[n] == sentinel { value.as_str() } else { options[n] };
+                preview_property(state, object_index, prop_name, shown);
+            }
             state.mode = ep_dropdown(object_index, selected_property, n, panel_scroll);
             Action::Redraw
         }
         DropdownKey::Choose(n) => {
             let chosen = options[n];
+            if let Some(value) = state.dropdown_preview.take() {
+                preview_property(state, object_index, prop_name, &value);
+            }
             if chosen == sentinel {
                 // Switch to text input, seeding with the current value if useful.
                 let initial = if prop_kind == properties::PropertyKind::Color {
@@ -3004,12 +4194,12 @@ fn anim_mode(
     selected_field: usize, editing: Option<String>, cursor: usize,
     from: u16, to: u16, from_y: u16, to_y: u16, two_axis: bool,
     start_frame: usize, end_frame: usize,
-    add_frames: bool, auto_play: bool, delay_ms: u64, gap_frames: usize,
+    add_frames: bool, auto_play: bool, delay_ms: u64, gap_frames: usize, home_frame: usize,
 ) -> Mode {
     Mode::AnimateProperty {
         object_index, return_property, property_name, selected_field, editing, cursor,
         from, to, from_y, to_y, two_axis, start_frame, end_frame,
-        add_frames, auto_play, delay_ms, gap_frames,
+        add_frames, auto_play, delay_ms, gap_frames, home_frame,
     }
 }
 
@@ -3018,6 +4208,122 @@ fn anim_mode(
 /// both becomes a **two-axis** session (x and y set together); every other
 /// coordinate (width/height) stays single-axis. The span and auto-play config
 /// are seeded from an existing animation on the coordinate / matching span.
+/// The other axis of a point coordinate (`x`↔`y`, `x1`↔`y1`, …), when the
+/// object has one, so picking either sets the whole point.
+fn pick_partner(obj: &SceneObject, name: &str) -> Option<String> {
+    let partner: String = name
+        .chars()
+        .map(|c| match c {
+            'x' => 'y',
+            'y' => 'x',
+            c => c,
+        })
+        .collect();
+    (partner != name && properties::get_coord(obj, &partner).is_some()).then_some(partner)
+}
+
+/// Where the crosshair starts when picking coordinate `name` of an object: on
+/// the point it sets, or a size's far edge. `None` for an animated coordinate,
+/// which the animate panel edits.
+pub(super) fn pick_start(state: &EditorState, object_index: usize, name: &str) -> Option<(u16, u16)> {
+    let obj = state.source.objects.get(object_index)?;
+    let anims = AnimSpans::of(&state.source);
+    let at = |n: &str| properties::get_coord(obj, n).map(|c| c.evaluate(state.current_frame, &anims));
+    if !matches!(properties::get_coord(obj, name)?, Coordinate::Fixed(_)) {
+        return None;
+    }
+    let (x, y) = (at("x").unwrap_or(0), at("y").unwrap_or(0));
+    let start = match name {
+        "width" => (x + at("width")?.saturating_sub(1), y),
+        "height" => (x, y + at("height")?.saturating_sub(1)),
+        n if n.contains('x') => (at(n)?, pick_partner(obj, n).and_then(|p| at(&p)).unwrap_or(y)),
+        n if n.contains('y') => (pick_partner(obj, n).and_then(|p| at(&p)).unwrap_or(x), at(n)?),
+        _ => return None,
+    };
+    let (w, h) = (state.source.width.max(1), state.source.height.max(1));
+    Some((start.0.min(w - 1), start.1.min(h - 1)))
+}
+
+/// What setting the crosshair at (`col`, `row`) writes: `(property, value)`
+/// pairs — the point's two axes, one axis, or the size from the object's
+/// left/top edge to the crosshair, inclusive.
+fn pick_values(state: &EditorState, object_index: usize, name: &str, col: u16, row: u16) -> Vec<(String, u16)> {
+    let obj = &state.source.objects[object_index];
+    let anims = AnimSpans::of(&state.source);
+    let at = |n: &str| properties::get_coord(obj, n).map_or(0, |c| c.evaluate(state.current_frame, &anims));
+    let axis = |n: &str| (n.to_string(), if n.contains('x') { col } else { row });
+    match name {
+        "width" => vec![("width".into(), (col + 1).saturating_sub(at("x")).max(1))],
+        "height" => vec![("height".into(), (row + 1).saturating_sub(at("y")).max(1))],
+        n => std::iter::once(axis(n)).chain(pick_partner(obj, n).map(|p| axis(&p))).collect(),
+    }
+}
+
+/// The status-line readout of what Enter would write.
+fn pick_status(state: &EditorState) -> String {
+    let Mode::PickCoordinate { object_index, property_name, col, row, .. } = state.mode else {
+        return String::new();
+    };
+    let values = pick_values(state, object_index, property_name, col, row);
+    values.iter().map(|(n, v)| format!("{n} = {v}")).collect::<Vec<_>>().join(", ")
+}
+
+fn handle_pick_coordinate(state: &mut EditorState, key: KeyEvent) -> Action {
+    let Mode::PickCoordinate { object_index, return_property, panel_scroll, property_name, col, row } = state.mode
+    else {
+        return Action::Continue;
+    };
+    let bindings = state.config.key_bindings.clone();
+    if matches_binding(&bindings.cancel, &key) {
+        state.status_message = None;
+        state.mode = ep_browse(object_index, return_property, panel_scroll);
+        return Action::Redraw;
+    }
+    if matches_binding(&bindings.confirm, &key) {
+        state.status_message = None;
+        for (name, value) in pick_values(state, object_index, property_name, col, row) {
+            apply_property(state, object_index, &name, &value.to_string());
+        }
+        state.mode = ep_browse(object_index, return_property, panel_scroll);
+        return Action::Redraw;
+    }
+    let step = if key.modifiers.contains(KeyModifiers::SHIFT) { 5 } else { 1 };
+    let (w, h) = (state.source.width.max(1), state.source.height.max(1));
+    let (col, row) = match key.code {
+        KeyCode::Left => (col.saturating_sub(step), row),
+        KeyCode::Right => ((col + step).min(w - 1), row),
+        KeyCode::Up => (col, row.saturating_sub(step)),
+        KeyCode::Down => (col, (row + step).min(h - 1)),
+        _ => return Action::Continue,
+    };
+    set_pick_point(state, col, row);
+    Action::Redraw
+}
+
+/// Move the crosshair to (`col`, `row`) and refresh the readout.
+fn set_pick_point(state: &mut EditorState, to_col: u16, to_row: u16) {
+    if let Mode::PickCoordinate { col, row, .. } = &mut state.mode {
+        (*col, *row) = (to_col, to_row);
+    }
+    state.status_message = Some(pick_status(state));
+}
+
+/// A press or drag on the canvas puts the crosshair under the pointer.
+fn pick_mouse(state: &mut EditorState, mouse: MouseEvent, layout: &Layout) -> Action {
+    if !matches!(mouse.kind, MouseEventKind::Down(MouseButton::Left) | MouseEventKind::Drag(MouseButton::Left)) {
+        return Action::Continue;
+    }
+    let (ox, oy) = super::preview::content_origin(layout, state.source.width, state.source.height);
+    let (Some(col), Some(row)) = (mouse.column.checked_sub(ox), mouse.row.checked_sub(oy)) else {
+        return Action::Continue;
+    };
+    if col >= state.source.width || row >= state.source.height {
+        return Action::Continue;
+    }
+    set_pick_point(state, col, row);
+    Action::Redraw
+}
+
 fn enter_animate(
     state: &EditorState, object_index: usize, return_property: usize, property_name: &'static str,
 ) -> Mode {
@@ -3060,22 +4366,25 @@ fn enter_animate(
     anim_mode(
         object_index, return_property, property_name, 0, None, 0,
         from, to, from_y, to_y, two_axis, start_frame, end_frame, true, auto_play, delay_ms, gap_frames,
+        state.current_frame,
     )
 }
 
 fn handle_animate_property(state: &mut EditorState, key: KeyEvent) -> Action {
     let (object_index, return_property, property_name, selected_field, editing, cursor,
          mut from, mut to, mut from_y, mut to_y, two_axis,
-         mut start_frame, mut end_frame, mut add_frames, mut auto_play, mut delay_ms, mut gap_frames) =
+         mut start_frame, mut end_frame, mut add_frames, mut auto_play, mut delay_ms, mut gap_frames,
+         home_frame) =
         match &state.mode {
             Mode::AnimateProperty {
                 object_index, return_property, property_name, selected_field, editing, cursor,
                 from, to, from_y, to_y, two_axis, start_frame, end_frame,
-                add_frames, auto_play, delay_ms, gap_frames,
+                add_frames, auto_play, delay_ms, gap_frames, home_frame,
             } => (
                 *object_index, *return_property, *property_name, *selected_field,
                 editing.clone(), *cursor, *from, *to, *from_y, *to_y, *two_axis,
                 *start_frame, *end_frame, *add_frames, *auto_play, *delay_ms, *gap_frames,
+                *home_frame,
             ),
             _ => return Action::Continue,
         };
@@ -3088,7 +4397,7 @@ fn handle_animate_property(state: &mut EditorState, key: KeyEvent) -> Action {
         ($editing:expr, $cursor:expr, $field:expr) => {
             anim_mode(object_index, return_property, property_name, $field, $editing, $cursor,
                 from, to, from_y, to_y, two_axis, start_frame, end_frame,
-                add_frames, auto_play, delay_ms, gap_frames)
+                add_frames, auto_play, delay_ms, gap_frames, home_frame)
         };
     }
 
@@ -3143,6 +4452,23 @@ fn handle_animate_property(state: &mut EditorState, key: KeyEvent) -> Action {
             state.mode = rebuild!(None, 0, new_sel);
             return Action::Redraw;
         }
+        // ←/→ scrub the preview through the span, one frame at a time, so the
+        // pending from/to can be judged in motion before saving.
+        KeyCode::Left | KeyCode::Right if key.modifiers == KeyModifiers::NONE => {
+            let last = end_frame.min(state.source.frame_count.saturating_sub(1)).max(start_frame);
+            let frame = if key.code == KeyCode::Left {
+                state.current_frame.saturating_sub(1)
+            } else {
+                state.current_frame + 1
+            };
+            state.current_frame = frame.clamp(start_frame, last);
+            state.status_message = Some(format!(
+                "Scrub {}/{}",
+                state.current_frame - start_frame + 1,
+                end_frame.saturating_sub(start_frame) + 1,
+            ));
+            return Action::Redraw;
+        }
         // Space / Enter on a boolean field toggles it in place (no text detour).
         KeyCode::Char(' ') | KeyCode::Enter if role.is_toggle() => {
             match role {
@@ -3162,6 +4488,7 @@ fn handle_animate_property(state: &mut EditorState, key: KeyEvent) -> Action {
         }
         // [s] apply → animate the coordinate(s) (+ optional add-frames/auto-play).
         KeyCode::Char('s') if key.modifiers == KeyModifiers::NONE => {
+            state.current_frame = home_frame;
             apply_animation(state, object_index, property_name, from, to, from_y, to_y, two_axis,
                 start_frame, end_frame, add_frames, auto_play, delay_ms, gap_frames);
             state.mode = ep_browse(object_index, return_property, 0);
@@ -3170,6 +4497,7 @@ fn handle_animate_property(state: &mut EditorState, key: KeyEvent) -> Action {
         // [x] clear → Fixed coordinate(s). Also removes any gap-strobe copies so
         // clearing doesn't leave the element scattered on its old sample frames.
         KeyCode::Char('x') if key.modifiers == KeyModifiers::NONE => {
+            state.current_frame = home_frame;
             let anims = AnimSpans::of(&state.source);
             if let Some((lo, hi)) = super::state::scene_object_animation_span(&state.source.objects[object_index], &anims) {
                 super::state::clear_gap_clones(&mut state.source, object_index, lo, hi.saturating_sub(1));
@@ -3199,6 +4527,7 @@ fn handle_animate_property(state: &mut EditorState, key: KeyEvent) -> Action {
             return Action::Redraw;
         }
         KeyCode::Esc => {
+            state.current_frame = home_frame;
             state.mode = ep_browse(object_index, return_property, 0);
             return Action::Redraw;
         }
@@ -3208,12 +4537,35 @@ fn handle_animate_property(state: &mut EditorState, key: KeyEvent) -> Action {
     Action::Continue
 }
 
+/// The source as it would look with the Animate sub-menu's pending config
+/// applied — what the canvas previews (and scrubs through) before `[s]` saves
+/// it. `None` outside that mode, or while the span is a single frame.
+pub(super) fn animation_preview(state: &EditorState) -> Option<SourcePresentation> {
+    let Mode::AnimateProperty {
+        object_index, property_name, from, to, from_y, to_y, two_axis,
+        start_frame, end_frame, auto_play, delay_ms, ..
+    } = state.mode else {
+        return None;
+    };
+    if start_frame >= end_frame {
+        return None;
+    }
+    let mut source = state.source.clone();
+    let id = super::state::referenced_anim_ids(&source.objects[object_index])
+        .first()
+        .copied()
+        .unwrap_or_else(|| super::state::next_anim_id(&source));
+    super::state::ensure_animation(&mut source, id, start_frame, end_frame + 1, auto_play, delay_ms, 0);
+    set_object_animation(&mut source, object_index, property_name, from, to, from_y, to_y, two_axis, true, id).ok()?;
+    lock_range_to_animation(&mut source, object_index);
+    Some(source)
+}
+
 /// Apply the animation configured in the Animate sub-menu: optionally insert the
 /// spanned frames and share the current frame's elements across them, set the
 /// animated coordinate, keep the object's own range in lock-step with its
 /// animation, and create/update the Animation span entity (auto-play config).
 #[allow(clippy::too_many_arguments)]
-#[allow(clippy::too_many_arguments)]
 fn apply_animation(
     state: &mut EditorState, object_index: usize, property_name: &'static str,
     from: u16, to: u16, from_y: u16, to_y: u16, two_axis: bool,
@@ -3334,6 +4686,7 @@ const CONVERGE_ROLES: &[AnimRole] = &[
 
 /// The Converge config's `(label, value)` rows, in display order — used by the
 /// panel to render the fields without duplicating the role layout.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn converge_field_rows(
     to: u16, to_y: u16, start_frame: usize, end_frame: usize,
     add_frames: bool, auto_play: bool, delay_ms: u64, gap_frames: usize,
@@ -3370,8 +4723,8 @@ fn enter_converge(state: &EditorState, members: Vec<usize>) -> Mode {
             ny += 1;
         }
     }
-    let to = if nx > 0 { (sx / nx) as u16 } else { state.source.width / 2 };
-    let to_y = if ny > 0 { (sy / ny) as u16 } else { state.source.height / 2 };
+    let to = sx.checked_div(nx).map_or(state.source.width / 2, |v| v as u16);
+    let to_y = sy.checked_div(ny).map_or(state.source.height / 2, |v| v as u16);
     Mode::ConvergeConfig {
         members, selected_field: 0, editing: None, cursor: 0,
         to, to_y, start_frame, end_frame,
@@ -3921,6 +5274,10 @@ fn handle_table_cell_edit_content(state: &mut EditorState, key: KeyEvent) -> Act
         *cursor = at;
     };
 
+    if matches_binding(&bindings.external_editor, &key) {
+        return Action::ExternalEdit(buf);
+    }
+
     // Shift-Enter (or Alt-Enter, where Shift isn't reported): insert a newline
     // instead of saving. The cursor lands on the newline, rendered as a caret at
     // the start of the new line, so typing continues there.
@@ -4045,6 +5402,7 @@ fn cell_style_prop_value(style: &Style, prop: &str) -> String {
 }
 
 /// Build the `EditingStyle` sub-mode of `TableEditCellProps`.
+#[allow(clippy::too_many_arguments)]
 fn cell_style_mode(
     object_index: usize,
     cursor_row: usize,
@@ -4262,6 +5620,7 @@ fn apply_cell_style(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::editor::state::RecentDeck;
 
     fn anim(id: AnimId, start: usize, end: usize, auto_play: bool) -> SceneObject {
         use crate::engine::objects::Animation;
@@ -4272,6 +5631,7 @@ mod tests {
             auto_play,
             delay_ms: 500,
             gap_frames: 0,
+            layer: None,
         })
     }
 
@@ -4458,10 +5818,14 @@ mod tests {
     #[test]
     fn select_action_submenu_offers_copy_converge_delete_and_edit_props() {
         // The post-multi-select action sub-menu lists Copy, Converge, Delete,
-        // then Edit Props (bulk-edit the shared properties).
+        // then Edit Props (bulk-edit the shared properties), Ripple Shift and
+        // the align/distribute rows.
         assert_eq!(
             select_action_labels(),
-            vec!["Copy", "Converge", "Delete", "Edit Props"]
+            vec![
+                "Copy", "Converge", "Delete", "Edit Props", "Ripple Shift", "Align Left", "Align Right",
+                "Align Top", "Align Bottom", "Align Center", "Align Middle", "Distribute H", "Distribute V",
+            ]
         );
     }
 
@@ -4590,4 +5954,856 @@ mod tests {
             "object range re-locked to the new span",
         );
     }
+
+    #[test]
+    fn layer_panel_locks_a_layer_out_of_the_selection_list() {
+        use crate::editor::object_defaults::create_default;
+
+        let mut state = EditorState::open("/tmp/bs_layer_panel_absent_5.json").unwrap();
+        state.source.objects = vec![create_default(0, 0), create_default(0, 0)];
+        properties::set_property(&mut state.source.objects[1], "layer", "notes").unwrap();
+        assert_eq!(state.objects_on_current_frame(), vec![0, 1]);
+
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        handle_key(&mut state, key('l'));
+        assert!(matches!(state.mode, Mode::Layers { selected: 0 }));
+        handle_key(&mut state, key('l'));
+        assert!(state.source.layers[0].locked, "`l` in the panel locks the layer");
+        assert_eq!(state.objects_on_current_frame(), vec![0], "locked objects can't be picked");
+
+        handle_key(&mut state, key('l'));
+        handle_key(&mut state, key('v'));
+        assert!(!state.source.layers[0].visible && !state.source.layers[0].locked);
+        assert_eq!(state.objects_on_current_frame(), vec![0], "hidden objects can't be picked");
+    }
+
+    #[test]
+    fn diagnostics_panel_jumps_to_the_finding() {
+        let mut state = EditorState::open("/tmp/bs_diagnostics_panel_absent_1.json").unwrap();
+        state.source.width = 10;
+        state.source.frame_count = 3;
+        state.source.objects = vec![serde_json::from_str(
+            r#"{"type":"label","text":"far too wide","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":2,"end":3}}"#,
+        )
+        .unwrap()];
+
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        handle_key(&mut state, key('d'));
+        let Mode::Diagnostics { items, .. } = &state.mode else { panic!("panel should open") };
+        let paths: Vec<_> = items.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(paths, ["objects[0]", "lint.empty_frame"]);
+
+        handle_key(&mut state, enter);
+        assert_eq!(state.current_frame, 2, "jumped to the label's frame");
+        assert!(matches!(state.mode, Mode::SelectedObject { object_index: 0 }));
+
+        handle_key(&mut state, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        handle_key(&mut state, key('d'));
+        handle_key(&mut state, KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        handle_key(&mut state, enter);
+        assert_eq!(state.current_frame, 0, "jumped to the first empty frame");
+        assert!(matches!(state.mode, Mode::Normal));
+    }
+
+    #[test]
+    fn stamping_a_group_lays_copies_out_to_its_right() {
+        let mut state = EditorState::open("/tmp/bs_stamp_group_absent_1.json").unwrap();
+        state.source.objects = vec![
+            serde_json::from_str(r#"{"type":"rect","position":{"x":{"fixed":1},"y":{"fixed":2}},"width":4,"height":3,"frames":{"start":0,"end":1}}"#).unwrap(),
+            serde_json::from_str(r#"{"type":"label","text":"n","position":{"x":{"fixed":2},"y":{"fixed":3}},"frames":{"start":0,"end":1}}"#).unwrap(),
+            serde_json::from_str(r#"{"type":"group","members":[0,1]}"#).unwrap(),
+        ];
+        state.mode = Mode::SelectedObject { object_index: 2 };
+
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        handle_key(&mut state, key('t'));
+        handle_key(&mut state, key('t'));
+        assert!(matches!(state.mode, Mode::SelectedObject { object_index: 8 }), "the newest copy is selected");
+        assert!(state.dirty);
+        let xs: Vec<u16> = state.source.objects.iter().filter_map(|o| match o {
+            SceneObject::Rect(r) => Some(r.position.x.start_value()),
+            _ => None,
+        }).collect();
+        assert_eq!(xs, [1, 6, 11], "each copy one column clear of the last");
+
+        state.mode = Mode::SelectedObject { object_index: 0 };
+        handle_key(&mut state, key('t'));
+        assert_eq!(state.source.objects.len(), 9, "only groups stamp");
+    }
+
+    #[test]
+    fn g_groups_the_toggled_selection_or_the_highlighted_object() {
+        let mut state = EditorState::open("/tmp/bs_group_selection_absent_1.json").unwrap();
+        let rect = r#"{"type":"rect","position":{"x":{"fixed":1},"y":{"fixed":1}},"width":2,"height":2,"frames":{"start":0,"end":1}}"#;
+        state.source.objects = (0..3).map(|_| serde_json::from_str(rect).unwrap()).collect();
+        let select = || Mode::MultiSelect { purpose: MultiSelectPurpose::Select, selected: 0, members: Vec::new() };
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+
+        state.mode = select();
+        handle_key(&mut state, key(' '));
+        handle_key(&mut state, KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        handle_key(&mut state, KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        handle_key(&mut state, key(' '));
+        handle_key(&mut state, key('G'));
+        match &state.source.objects[3] {
+            SceneObject::Group(g) => assert_eq!(g.members, [0, 2]),
+            other => panic!("expected a group, got {other:?}"),
+        }
+        assert!(matches!(state.mode, Mode::EditProperties { object_index: 3, .. }));
+        assert!(state.dirty);
+
+        state.mode = select();
+        handle_key(&mut state, KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        handle_key(&mut state, key('G'));
+        assert!(matches!(&state.source.objects[4], SceneObject::Group(g) if g.members == [1]), "nothing toggled: the highlighted object");
+    }
+
+    #[test]
+    fn e_in_select_opens_the_shared_properties_and_edits_recolor_every_member() {
+        let mut state = EditorState::open("/tmp/bs_bulk_props_absent_1.json").unwrap();
+        let label = |c: &str| serde_json::from_str(&format!(
+            r#"{{"type":"label","text":"{c}","position":{{"x":{{"fixed":0}},"y":{{"fixed":0}}}},"style":{{"fg":"blue"}},"frames":{{"start":0,"end":1}}}}"#
+        )).unwrap();
+        state.source.objects = vec![label("a"), label("b"), label("c")];
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+
+        // Nothing toggled: the highlighted object's own panel.
+        state.mode = Mode::MultiSelect { purpose: MultiSelectPurpose::Select, selected: 1, members: Vec::new() };
+        handle_key(&mut state, key('e'));
+        assert!(matches!(state.mode, Mode::EditProperties { object_index: 1, .. }));
+
+        state.mode = Mode::MultiSelect { purpose: MultiSelectPurpose::Select, selected: 0, members: vec![0, 2] };
+        handle_key(&mut state, key('e'));
+        let Mode::EditMultiProperties { members, .. } = &state.mode else {
+            panic!("expected the bulk panel, got {:?}", state.mode);
+        };
+        assert_eq!(members, &[0, 2]);
+
+        let fg = properties::common_properties(&state.source.objects, &[0, 2])
+            .iter()
+            .position(|p| p.name == "fg_color")
+            .unwrap();
+        let red = properties::COLOR_OPTIONS.iter().position(|&o| o == "red").unwrap();
+        state.mode = emp_dropdown(vec![0, 2], fg, red, 0);
+        handle_key(&mut state, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        let fg_of = |state: &EditorState, i| properties::get_properties(&state.source.objects, i)
+            .into_iter()
+            .find(|p| p.name == "fg_color")
+            .unwrap()
+            .value;
+        assert_eq!(fg_of(&state, 0), "red");
+        assert_eq!(fg_of(&state, 2), "red");
+        assert_eq!(fg_of(&state, 1), "blue", "the untoggled label keeps its colour");
+        assert!(state.dirty);
+    }
+
+    #[test]
+    fn settings_resize_refits_the_content_and_warns_about_what_falls_outside() {
+        let mut state = EditorState::open("/tmp/bs_resize_absent_1.json").unwrap();
+        state.source.objects = vec![
+            serde_json::from_str(r#"{"type":"rect","position":{"x":{"fixed":10},"y":{"fixed":2}},"width":4,"height":2,"frames":{"start":0,"end":1}}"#).unwrap(),
+            serde_json::from_str(r#"{"type":"label","text":"far","position":{"x":{"fixed":70},"y":{"fixed":20}},"frames":{"start":0,"end":1}}"#).unwrap(),
+        ];
+        let press = |state: &mut EditorState, code| handle_key(state, KeyEvent::new(code, KeyModifiers::NONE));
+
+        state.mode = Mode::Settings { selected_field: 0, width_buf: "60".into(), height_buf: "24".into(), fit: CanvasFit::TopLeft, cursor: 2 };
+        press(&mut state, KeyCode::Tab);
+        press(&mut state, KeyCode::Tab);
+        press(&mut state, KeyCode::Right);
+        assert!(matches!(state.mode, Mode::Settings { selected_field: 2, fit: CanvasFit::Center, .. }));
+        press(&mut state, KeyCode::Enter);
+
+        assert_eq!((state.source.width, state.source.height), (60, 24));
+        assert_eq!(properties::get_properties(&state.source.objects, 0)[0].value, "0", "shifted left by half the 20 lost columns");
+        let status = state.status_message.clone().unwrap_or_default();
+        assert!(status.contains("1 object(s) now draw outside"), "{status}");
+        assert!(status.contains("far"), "{status}");
+        assert!(state.dirty);
+    }
+
+    #[test]
+    fn start_screen_opens_a_recent_deck_or_walks_the_new_deck_wizard() {
+        let mut state = EditorState::open("/tmp/bs_start_absent_1.json").unwrap();
+        let recent: Vec<RecentDeck> = ["/tmp/a.json", "/tmp/b.json"]
+            .map(|path| RecentDeck { path: path.into(), title: None })
+            .into();
+        let press = |state: &mut EditorState, code| handle_key(state, KeyEvent::new(code, KeyModifiers::NONE));
+
+        state.mode = Mode::Start { selected: 0, recent: recent.clone() };
+        press(&mut state, KeyCode::Up);
+        assert!(matches!(state.mode, Mode::Start { selected: 2, .. }), "Up wraps to the last recent deck");
+        assert!(matches!(press(&mut state, KeyCode::Enter), Action::OpenInPlace(p) if p == "/tmp/b.json"));
+
+        state.mode = Mode::Start { selected: 0, recent };
+        press(&mut state, KeyCode::Enter);
+        let Mode::NewDeck { fields, .. } = &state.mode else {
+            panic!("expected the wizard, got {:?}", state.mode);
+        };
+        assert_eq!(fields, &["/tmp/bs_start_absent_1.json", "80", "24", "1"], "seeded with the given path");
+
+        // Frames: letters are refused, digits typed.
+        for _ in 0..3 {
+            press(&mut state, KeyCode::Tab);
+        }
+        press(&mut state, KeyCode::Char('x'));
+        press(&mut state, KeyCode::Backspace);
+        press(&mut state, KeyCode::Char('1'));
+        press(&mut state, KeyCode::Char('2'));
+        assert!(matches!(&state.mode, Mode::NewDeck { fields, selected_field: 3, .. } if fields[3] == "12"));
+
+        press(&mut state, KeyCode::Enter);
+        assert!(matches!(state.mode, Mode::Normal));
+        assert_eq!((state.source.width, state.source.height, state.source.frame_count), (80, 24, 12));
+        assert!(state.dirty, "the new deck is unsaved until Ctrl-s");
+
+        // An existing file is refused rather than clobbered.
+        state.mode = Mode::NewDeck {
+            selected_field: 0,
+            fields: ["/tmp".into(), "80".into(), "24".into(), "1".into()],
+            cursor: 4,
+            recent: Vec::new(),
+        };
+        press(&mut state, KeyCode::Enter);
+        assert!(matches!(state.mode, Mode::NewDeck { .. }));
+        press(&mut state, KeyCode::Esc);
+        assert!(matches!(state.mode, Mode::Start { selected: 0, .. }));
+    }
+
+    #[test]
+    fn k_keeps_only_the_selected_range_after_confirming() {
+        let mut state = EditorState::open("/tmp/bs_trim_absent_1.json").unwrap();
+        state.source.frame_count = 8;
+        state.source.objects = vec![
+            serde_json::from_str(r#"{"type":"label","text":"a","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":8}}"#).unwrap(),
+            serde_json::from_str(r#"{"type":"label","text":"b","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":6,"end":8}}"#).unwrap(),
+        ];
+        state.current_frame = 4;
+        state.mode = Mode::FrameSelected { frames: vec![2, 4] };
+        handle_key(&mut state, KeyEvent::new(KeyCode::Char('k'), KeyModifiers::NONE));
+        assert!(matches!(state.mode, Mode::FrameSelected { .. }), "a scattered selection is refused");
+
+        state.mode = Mode::FrameSelected { frames: vec![2, 3, 4] };
+        handle_key(&mut state, KeyEvent::new(KeyCode::Char('k'), KeyModifiers::NONE));
+        assert!(matches!(&state.mode, Mode::Confirm { action: ConfirmAction::TrimFrames { lo: 2, hi: 4 }, .. }));
+        handle_key(&mut state, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(state.source.frame_count, 3);
+        assert_eq!(state.source.objects.len(), 1, "b lived only in the cut tail");
+        assert_eq!(state.current_frame, 2, "still on the same slide");
+        assert!(state.dirty);
+    }
+
+    #[test]
+    fn u_sets_and_clears_the_current_frames_duration() {
+        let mut state = EditorState::open("/tmp/bs_duration_absent_1.json").unwrap();
+        state.source.frame_count = 3;
+        state.current_frame = 1;
+        state.mode = Mode::FrameMenu;
+        handle_key(&mut state, KeyEvent::new(KeyCode::Char('u'), KeyModifiers::NONE));
+        assert!(matches!(&state.mode, Mode::FrameDurationInput { buf, .. } if buf.is_empty()));
+        for c in "2.5".chars() {
+            handle_key(&mut state, KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        handle_key(&mut state, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(state.source.frame_duration(1), Some(2500));
+        assert_eq!(state.mode, Mode::Normal);
+        assert!(state.dirty);
+
+        // Reopening seeds the field with the stored value; 0 clears it.
+        state.mode = Mode::FrameMenu;
+        handle_key(&mut state, KeyEvent::new(KeyCode::Char('u'), KeyModifiers::NONE));
+        assert!(matches!(&state.mode, Mode::FrameDurationInput { buf, .. } if buf == "2.5"));
+        state.mode = Mode::FrameDurationInput { buf: "0".into(), cursor: 1 };
+        handle_key(&mut state, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(state.source.durations.is_empty());
+    }
+
+    #[test]
+    fn n_names_and_clears_the_section_the_current_frame_starts() {
+        let mut state = EditorState::open("/tmp/bs_section_absent_1.json").unwrap();
+        state.source.frame_count = 3;
+        state.current_frame = 1;
+        state.mode = Mode::FrameMenu;
+        handle_key(&mut state, KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+        assert!(matches!(&state.mode, Mode::FrameSectionInput { buf, .. } if buf.is_empty()));
+        for c in "Demo".chars() {
+            handle_key(&mut state, KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        handle_key(&mut state, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(state.source.frame_section(1), Some("Demo"));
+        assert_eq!(state.mode, Mode::Normal);
+        assert!(state.dirty);
+
+        state.mode = Mode::FrameMenu;
+        handle_key(&mut state, KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+        assert!(matches!(&state.mode, Mode::FrameSectionInput { buf, .. } if buf == "Demo"));
+        state.mode = Mode::FrameSectionInput { buf: " ".into(), cursor: 1 };
+        handle_key(&mut state, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(state.source.sections.is_empty());
+    }
+
+    #[test]
+    fn duplicating_a_frame_range_places_the_copy_right_after_it() {
+        let mut state = EditorState::open("/tmp/bs_dup_range_absent_1.json").unwrap();
+        state.source.frame_count = 6;
+        state.source.objects = vec![
+            serde_json::from_str(r#"{"type":"rect","position":{"x":{"fixed":0},"y":{"fixed":0}},"width":9,"height":4,"frames":{"start":0,"end":6}}"#).unwrap(),
+            serde_json::from_str(r#"{"type":"label","text":"a","position":{"x":{"fixed":1},"y":{"fixed":1}},"frames":{"start":1,"end":2}}"#).unwrap(),
+            serde_json::from_str(r#"{"type":"label","text":"m","position":{"x":{"animated":{"from":0,"to":4,"anim":1}},"y":{"fixed":2}},"frames":{"start":1,"end":3}}"#).unwrap(),
+            anim(1, 1, 3, true),
+        ];
+        state.mode = Mode::FrameSelected { frames: vec![1, 2] };
+        handle_key(&mut state, KeyEvent::new(KeyCode::Char('D'), KeyModifiers::NONE));
+
+        assert_eq!(state.source.frame_count, 8);
+        assert!(matches!(&state.mode, Mode::FrameSelected { frames } if frames == &[3, 4]), "the copy is selected");
+        assert_eq!(state.current_frame, 3);
+        assert!(state.dirty);
+        let ranges: Vec<(usize, usize)> = state.source.objects.iter()
+            .filter_map(super::super::state::scene_object_frame_range)
+            .map(|r| (r.start, r.end))
+            .collect();
+        assert_eq!(ranges, [(0, 8), (1, 2), (1, 3), (1, 3), (3, 4), (3, 5), (3, 5)], "the background stretches, the build is cloned");
+        let ids: Vec<AnimId> = state.source.objects.iter().filter_map(|o| match o {
+            SceneObject::Animation(a) => Some(a.id),
+            _ => None,
+        }).collect();
+        assert_eq!(ids.len(), 2);
+        assert_ne!(ids[0], ids[1], "the copy gets its own animation");
+
+        state.mode = Mode::FrameSelected { frames: vec![0, 2] };
+        handle_key(&mut state, KeyEvent::new(KeyCode::Char('D'), KeyModifiers::NONE));
+        assert_eq!(state.source.frame_count, 8, "a scattered selection is refused");
+    }
+
+    fn lanes_state() -> EditorState {
+        let mut state = EditorState::open("/tmp/bs_lanes_absent_1.json").unwrap();
+        state.source.frame_count = 10;
+        state.source.objects = vec![
+            serde_json::from_str(r#"{"type":"rect","position":{"x":{"fixed":0},"y":{"fixed":0}},"width":2,"height":2,"frames":{"start":2,"end":6}}"#).unwrap(),
+            serde_json::from_str(r#"{"type":"label","text":"m","position":{"x":{"animated":{"from":0,"to":9,"anim":1}},"y":{"fixed":3}},"frames":{"start":4,"end":6}}"#).unwrap(),
+            anim(1, 4, 6, true),
+        ];
+        state
+    }
+
+    #[test]
+    fn lanes_draw_each_range_with_its_animated_frames_marked() {
+        let state = lanes_state();
+        let anims = AnimSpans::of(&state.source);
+        let cells = |i: usize| super::super::lanes::lane_cells(&state.source.objects[i], &anims, 10, 10);
+        assert_eq!(cells(0), "  ████    ");
+        assert_eq!(cells(1), "    ▓▓    ");
+        assert_eq!(cells(2), "    ██    ", "an animation's own span is a plain bar");
+    }
+
+    #[test]
+    fn lane_keys_move_either_end_of_a_range_within_the_deck() {
+        let mut state = lanes_state();
+        let press = |s: &mut EditorState, code| handle_key(s, KeyEvent::new(code, KeyModifiers::NONE));
+        let range = |s: &EditorState, i: usize| {
+            let r = super::super::state::scene_object_frame_range(&s.source.objects[i]).unwrap();
+            (r.start, r.end)
+        };
+
+        handle_key(&mut state, KeyEvent::new(KeyCode::Char('T'), KeyModifiers::NONE));
+        assert!(matches!(state.mode, Mode::Lanes { selected: 0, at_end: false }));
+        press(&mut state, KeyCode::Left);
+        assert_eq!(range(&state, 0), (1, 6));
+        press(&mut state, KeyCode::Tab);
+        for _ in 0..6 {
+            press(&mut state, KeyCode::Right);
+        }
+        assert_eq!(range(&state, 0), (1, 10), "the end stops at the deck's last frame");
+        for _ in 0..12 {
+            press(&mut state, KeyCode::Left);
+        }
+        assert_eq!(range(&state, 0), (1, 2), "and never passes the start");
+        assert_eq!(state.status_message.as_deref(), Some("Frames 2–2"));
+        assert!(state.dirty);
+
+        // Stretching the animation's span re-locks the label it drives.
+        press(&mut state, KeyCode::Up);
+        press(&mut state, KeyCode::Right);
+        assert_eq!(range(&state, 2), (4, 7));
+        assert_eq!(range(&state, 1), (4, 7));
+
+        press(&mut state, KeyCode::Enter);
+        assert!(matches!(state.mode, Mode::SelectedObject { object_index: 2 }));
+    }
+
+    #[test]
+    fn dragging_a_lane_moves_the_nearer_end_of_its_range() {
+        let mut state = lanes_state();
+        state.mode = Mode::Lanes { selected: 2, at_end: false };
+        let layout = Layout::compute(80, 24, &state.mode, false);
+        let (x0, len) = super::super::lanes::track(&layout);
+        let col = |f: usize| x0 + super::super::timeline::thumb_col(f, len, 10) as u16;
+        let at = |kind, column, row| MouseEvent { kind, column, row, modifiers: KeyModifiers::NONE };
+
+        handle_mouse(&mut state, at(MouseEventKind::Down(MouseButton::Left), col(5), layout.canvas_y), &layout);
+        assert!(matches!(state.mode, Mode::Lanes { selected: 0, at_end: true }), "frame 5 is nearer the end of 2..6");
+        handle_mouse(&mut state, at(MouseEventKind::Drag(MouseButton::Left), col(8), layout.canvas_y + 3), &layout);
+        let r = super::super::state::scene_object_frame_range(&state.source.objects[0]).unwrap();
+        assert_eq!((r.start, r.end), (2, 9), "a drag keeps the held end, whatever row it crosses");
+
+        handle_mouse(&mut state, at(MouseEventKind::Down(MouseButton::Left), col(0), layout.canvas_y), &layout);
+        let r = super::super::state::scene_object_frame_range(&state.source.objects[0]).unwrap();
+        assert_eq!((r.start, r.end), (0, 9));
+    }
+
+    #[test]
+    fn clicking_and_dragging_the_scrubber_moves_the_current_frame() {
+        let mut state = EditorState::open("/tmp/bs_mouse_scrub_absent_1.json").unwrap();
+        state.source.frame_count = 100;
+        let layout = Layout::compute(80, 24, &state.mode, false);
+        let at = |kind, column, row| MouseEvent { kind, column, row, modifiers: KeyModifiers::NONE };
+        let down = MouseEventKind::Down(MouseButton::Left);
+
+        assert!(matches!(handle_mouse(&mut state, at(down, 79, layout.timeline_y), &layout), Action::Redraw));
+        assert_eq!(state.current_frame, 99, "the right end is the last frame");
+        handle_mouse(&mut state, at(MouseEventKind::Drag(MouseButton::Left), 39, layout.timeline_y), &layout);
+        assert_eq!(state.current_frame, 49);
+        handle_mouse(&mut state, at(MouseEventKind::Moved, 1, layout.timeline_y), &layout);
+        handle_mouse(&mut state, at(down, 1, layout.timeline_y + 1), &layout);
+        assert_eq!(state.current_frame, 49, "bare motion and other rows are ignored");
+
+        state.mode = Mode::FrameMenu;
+        handle_mouse(&mut state, at(down, 1, layout.timeline_y), &layout);
+        assert_eq!(state.current_frame, 49, "only in Normal mode");
+    }
+
+    #[test]
+    fn arrows_scrub_the_pending_animation_and_leaving_restores_the_frame() {
+        let mut state = EditorState::open("/tmp/bs_anim_scrub_absent_1.json").unwrap();
+        state.source.frame_count = 10;
+        state.current_frame = 2;
+        state.source.objects = vec![serde_json::from_str(
+            r#"{"type":"label","text":"o","position":{"x":{"fixed":0},"y":{"fixed":1}},"frames":{"start":0,"end":10}}"#,
+        ).unwrap()];
+        state.mode = enter_animate(&state, 0, 0, "x");
+        if let Mode::AnimateProperty { to, end_frame, .. } = &mut state.mode {
+            *to = 6;
+            *end_frame = 5;
+        }
+        let x_at = |s: &EditorState| {
+            let src = animation_preview(s).unwrap();
+            let anims = AnimSpans::of(&src);
+            match &src.objects[0] {
+                SceneObject::Label(l) => l.position.x.evaluate(s.current_frame, &anims),
+                _ => unreachable!(),
+            }
+        };
+        let press = |s: &mut EditorState, code| handle_key(s, KeyEvent::new(code, KeyModifiers::NONE));
+
+        assert_eq!(x_at(&state), 0, "the span starts on the frame it was opened on");
+        press(&mut state, KeyCode::Right);
+        press(&mut state, KeyCode::Right);
+        assert_eq!(state.current_frame, 4);
+        assert_eq!(x_at(&state), 4, "the preview follows the unsaved from/to");
+        press(&mut state, KeyCode::Right);
+        press(&mut state, KeyCode::Right);
+        assert_eq!(state.current_frame, 5, "scrubbing stops at the span's last frame");
+        assert_eq!(x_at(&state), 6);
+        for _ in 0..5 {
+            press(&mut state, KeyCode::Left);
+        }
+        assert_eq!(state.current_frame, 2, "and at its first");
+        press(&mut state, KeyCode::Right);
+
+        press(&mut state, KeyCode::Esc);
+        assert_eq!(state.current_frame, 2, "leaving puts the frame back");
+        assert!(animation_preview(&state).is_none());
+        assert!(matches!(&state.source.objects[0], SceneObject::Label(l) if l.position.x == Coordinate::Fixed(0.0)), "nothing saved");
+    }
+
+    #[test]
+    fn ctrl_e_hands_text_to_the_external_editor_and_takes_the_result_back() {
+        let mut state = EditorState::open("/tmp/bs_external_edit_absent_1.json").unwrap();
+        state.source.objects = vec![
+            serde_json::from_str(r#"{"type":"label","text":"old","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":1}}"#).unwrap(),
+            serde_json::from_str(r#"{"type":"table","position":{"x":{"fixed":0},"y":{"fixed":5}},"width":10,"col_widths":[1.0],"rows":1,"frames":{"start":0,"end":1},"cells":[[{"content":"cell"}]]}"#).unwrap(),
+        ];
+        let ctrl_e = KeyEvent::new(KeyCode::Char('e'), KeyModifiers::CONTROL);
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+
+        let text = properties::get_properties(&state.source.objects, 0)
+            .iter()
+            .position(|p| p.name == "text")
+            .unwrap();
+        state.mode = ep_editing(0, text, "draft".into(), 5, 0, 0);
+        let Action::ExternalEdit(buf) = handle_key(&mut state, ctrl_e) else { panic!("expected ExternalEdit") };
+        assert_eq!(buf, "draft");
+        let edited = super::super::external::edit_with("printf 'para\\n' >", &buf).unwrap();
+        assert_eq!(edited, "para", "the newline the editor saved is dropped");
+        replace_edit_buffer(&mut state, format!("{edited}\nline two"));
+        assert!(matches!(&state.mode, Mode::EditProperties { editing_value: Some(v), cursor: 13, .. } if v == "para\nline two"));
+        handle_key(&mut state, enter);
+        assert!(matches!(&state.source.objects[0], SceneObject::Label(l) if l.text == "para\nline two"));
+
+        state.mode = Mode::TableEditCellProps {
+            object_index: 1, cursor_row: 0, cursor_col: 0, selected_cells: Vec::new(),
+            sub_state: TableCellSubState::EditingContent { row: 0, col: 0, buf: "cell".into(), cursor: 4 },
+        };
+        assert!(matches!(handle_key(&mut state, ctrl_e), Action::ExternalEdit(b) if b == "cell"));
+        replace_edit_buffer(&mut state, "a\nb".into());
+        handle_key(&mut state, enter);
+        assert!(matches!(&state.source.objects[1], SceneObject::Table(t) if t.cells[0][0].content == "a\nb"));
+    }
+
+    #[test]
+    fn arrow_char_dropdowns_preview_the_highlighted_option_and_esc_reverts_it() {
+        let mut state = EditorState::open("/tmp/bs_head_char_preview_absent_1.json").unwrap();
+        state.source.objects = vec![serde_json::from_str(
+            r#"{"type":"arrow","x1":{"fixed":0},"y1":{"fixed":0},"x2":{"fixed":6},"y2":{"fixed":0},"frames":{"start":0,"end":1}}"#,
+        )
+        .unwrap()];
+        let press = |s: &mut EditorState, code| handle_key(s, KeyEvent::new(code, KeyModifiers::NONE));
+        let head = |s: &EditorState| match &s.source.objects[0] {
+            SceneObject::Arrow(a) => a.head_ch,
+            _ => unreachable!(),
+        };
+        let prop = properties::get_properties(&state.source.objects, 0)
+            .iter()
+            .position(|p| p.name == "head_char")
+            .unwrap();
+
+        // Opening on "auto" highlights the first option; moving shows each in turn.
+        state.mode = ep_browse(0, prop, 0);
+        press(&mut state, KeyCode::Enter);
+        press(&mut state, KeyCode::Down);
+        assert_eq!(head(&state), Some('▶'));
+        press(&mut state, KeyCode::Down);
+        assert_eq!(head(&state), Some('→'));
+        assert!(!state.dirty, "a preview is not an edit");
+        press(&mut state, KeyCode::Esc);
+        assert_eq!(head(&state), None, "Esc puts the original back");
+
+        // Confirming keeps the highlighted option as a real edit.
+        press(&mut state, KeyCode::Enter);
+        press(&mut state, KeyCode::Down);
+        press(&mut state, KeyCode::Enter);
+        assert_eq!(head(&state), Some('▶'));
+        assert!(state.dirty);
+        assert!(state.dropdown_preview.is_none());
+    }
+
+    #[test]
+    fn colour_dropdowns_preview_on_the_object_and_rgb_shows_the_original() {
+        let mut state = EditorState::open("/tmp/bs_colour_preview_absent_1.json").unwrap();
+        state.source.objects = vec![serde_json::from_str(
+            r#"{"type":"label","text":"hi","position":{"x":{"fixed":0},"y":{"fixed":0}},"style":{"fg":{"r":16,"g":32,"b":48}},"frames":{"start":0,"end":1}}"#,
+        )
+        .unwrap()];
+        let press = |s: &mut EditorState, code| handle_key(s, KeyEvent::new(code, KeyModifiers::NONE));
+        let fg = |s: &EditorState| properties::get_properties(&s.source.objects, 0)
+            .into_iter()
+            .find(|p| p.name == "fg_color")
+            .unwrap()
+            .value;
+        let prop = properties::get_properties(&state.source.objects, 0)
+            .iter()
+            .position(|p| p.name == "fg_color")
+            .unwrap();
+
+        // A hex colour isn't listed, so the list opens on "RGB" (index 0).
+        state.mode = ep_browse(0, prop, 0);
+        press(&mut state, KeyCode::Enter);
+        press(&mut state, KeyCode::Down);
+        assert_eq!(fg(&state), "none");
+        press(&mut state, KeyCode::Down);
+        press(&mut state, KeyCode::Down);
+        assert_eq!(fg(&state), "red");
+        press(&mut state, KeyCode::Up);
+        press(&mut state, KeyCode::Up);
+        press(&mut state, KeyCode::Up);
+        assert_eq!(fg(&state), "#102030", "the custom entry shows the original");
+
+        // Choosing RGB opens the hex entry seeded with the original, not a preview.
+        press(&mut state, KeyCode::Enter);
+        assert!(matches!(&state.mode, Mode::EditProperties { editing_value: Some(v), .. } if v == "#102030"));
+        assert!(!state.dirty);
+    }
+
+    #[test]
+    fn labels_are_typed_into_in_place_and_esc_leaves_them_alone() {
+        let mut state = EditorState::open("/tmp/bs_edit_in_place_absent_1.json").unwrap();
+        state.source.objects = vec![
+            serde_json::from_str(
+                r#"{"type":"label","text":"hi","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":1}}"#,
+            )
+            .unwrap(),
+            serde_json::from_str(
+                r#"{"type":"table","position":{"x":{"fixed":0},"y":{"fixed":3}},"col_widths":[1.0],"rows":1,"cells":[[{"content":"a"}]],"frames":{"start":0,"end":1}}"#,
+            )
+            .unwrap(),
+        ];
+        let press = |s: &mut EditorState, code, mods| handle_key(s, KeyEvent::new(code, mods));
+        let text = |s: &EditorState| match &s.source.objects[0] {
+            SceneObject::Label(l) => l.text.clone(),
+            _ => unreachable!(),
+        };
+
+        state.mode = Mode::SelectedObject { object_index: 0 };
+        press(&mut state, KeyCode::Char('i'), KeyModifiers::NONE);
+        assert_eq!(state.mode, Mode::EditInPlace { object_index: 0, buf: "hi".into(), cursor: 2 });
+        press(&mut state, KeyCode::Char('!'), KeyModifiers::NONE);
+        press(&mut state, KeyCode::Enter, KeyModifiers::ALT);
+        press(&mut state, KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(text(&state), "hi", "nothing is applied while typing");
+        press(&mut state, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(text(&state), "hi!\nx");
+        assert_eq!(state.mode, Mode::SelectedObject { object_index: 0 });
+        assert!(state.dirty);
+
+        press(&mut state, KeyCode::Char('i'), KeyModifiers::NONE);
+        press(&mut state, KeyCode::Backspace, KeyModifiers::NONE);
+        press(&mut state, KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(text(&state), "hi!\nx");
+        assert_eq!(state.mode, Mode::SelectedObject { object_index: 0 });
+
+        // A table opens on its first cell's content, drawn in the cell.
+        state.mode = Mode::SelectedObject { object_index: 1 };
+        press(&mut state, KeyCode::Char('i'), KeyModifiers::NONE);
+        assert!(matches!(
+            &state.mode,
+            Mode::TableEditCellProps { sub_state: TableCellSubState::EditingContent { row: 0, col: 0, buf, cursor: 1 }, .. }
+                if buf == "a"
+        ));
+    }
+
+    #[test]
+    fn picking_a_coordinate_writes_the_crosshair_point_back() {
+        let mut state = EditorState::open("/tmp/bs_pick_coordinate_absent_1.json").unwrap();
+        state.source.width = 20;
+        state.source.height = 8;
+        state.source.objects = vec![serde_json::from_str(
+            r#"{"type":"rect","position":{"x":{"fixed":2},"y":{"fixed":1}},"width":{"fixed":4},"height":{"fixed":2},"frames":{"start":0,"end":1}}"#,
+        )
+        .unwrap()];
+        let press = |s: &mut EditorState, code, mods| handle_key(s, KeyEvent::new(code, mods));
+        let prop = |s: &EditorState, name: &str| {
+            let props = properties::get_properties(&s.source.objects, 0);
+            let i = props.iter().position(|p| p.name == name).unwrap();
+            (i, props[i].value.clone())
+        };
+
+        // Picking `x` moves the whole point; Shift steps by five.
+        state.mode = ep_browse(0, prop(&state, "x").0, 0);
+        press(&mut state, KeyCode::Char('p'), KeyModifiers::NONE);
+        assert!(matches!(state.mode, Mode::PickCoordinate { col: 2, row: 1, .. }));
+        press(&mut state, KeyCode::Right, KeyModifiers::SHIFT);
+        press(&mut state, KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(state.status_message.as_deref(), Some("x = 7, y = 2"));
+        press(&mut state, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!((prop(&state, "x").1, prop(&state, "y").1), ("7".into(), "2".into()));
+        assert!(matches!(state.mode, Mode::EditProperties { editing_value: None, .. }));
+
+        // A size reaches from the object's edge to the crosshair; the mouse
+        // moves it too, and Esc leaves the value alone.
+        state.mode = ep_browse(0, prop(&state, "width").0, 0);
+        press(&mut state, KeyCode::Char('p'), KeyModifiers::NONE);
+        assert!(matches!(state.mode, Mode::PickCoordinate { col: 10, row: 2, .. }));
+        let layout = Layout::compute(80, 24, &state.mode, false);
+        let (ox, oy) = super::super::preview::content_origin(&layout, 20, 8);
+        let click = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: ox + 15,
+            row: oy + 6,
+            modifiers: KeyModifiers::NONE,
+        };
+        handle_mouse(&mut state, click, &layout);
+        assert!(matches!(state.mode, Mode::PickCoordinate { col: 15, row: 6, .. }));
+        press(&mut state, KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(prop(&state, "width").1, "4");
+        press(&mut state, KeyCode::Char('p'), KeyModifiers::NONE);
+        press(&mut state, KeyCode::Left, KeyModifiers::NONE);
+        press(&mut state, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(prop(&state, "width").1, "3");
+    }
+
+    #[test]
+    fn coarse_modifier_moves_and_resizes_by_the_configured_step() {
+        let mut state = EditorState::open("/tmp/bs_coarse_step_absent_1.json").unwrap();
+        state.source.objects = vec![serde_json::from_str(
+            r#"{"type":"rect","position":{"x":{"fixed":10},"y":{"fixed":4}},"width":{"fixed":6},"height":{"fixed":3},"frames":{"start":0,"end":1}}"#,
+        )
+        .unwrap()];
+        let press = |s: &mut EditorState, code, mods| handle_key(s, KeyEvent::new(code, mods));
+        let geometry = |s: &EditorState| {
+            let props = properties::get_properties(&s.source.objects, 0);
+            ["x", "y", "width", "height"].map(|n| props.iter().find(|p| p.name == n).unwrap().value.clone())
+        };
+
+        state.mode = Mode::SelectedObject { object_index: 0 };
+        press(&mut state, KeyCode::Right, KeyModifiers::CONTROL);
+        press(&mut state, KeyCode::Up, KeyModifiers::CONTROL);
+        press(&mut state, KeyCode::Left, KeyModifiers::NONE);
+        assert_eq!(geometry(&state), ["14", "0", "6", "3"]);
+
+        press(&mut state, KeyCode::Char('r'), KeyModifiers::NONE);
+        press(&mut state, KeyCode::Right, KeyModifiers::CONTROL);
+        press(&mut state, KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(geometry(&state), ["14", "0", "11", "4"]);
+
+        // Both the step and the modifier are configurable.
+        state.config.coarse_step = 2;
+        state.config.key_bindings.coarse_modifier = "Alt".into();
+        press(&mut state, KeyCode::Left, KeyModifiers::ALT);
+        press(&mut state, KeyCode::Left, KeyModifiers::CONTROL);
+        assert_eq!(geometry(&state), ["14", "0", "9", "4"]);
+    }
+
+    #[test]
+    fn stamping_across_frames_makes_one_stepped_copy_per_frame() {
+        let mut state = EditorState::open("/tmp/bs_stamp_frames_absent_1.json").unwrap();
+        state.source.frame_count = 6;
+        state.source.objects = vec![
+            serde_json::from_str(r#"{"type":"label","text":"o","position":{"x":{"fixed":2},"y":{"fixed":3}},"frames":{"start":0,"end":1}}"#).unwrap(),
+        ];
+        state.mode = Mode::SelectedObject { object_index: 0 };
+
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        handle_key(&mut state, key('m'));
+        for c in "3-9 +2,-1".chars() {
+            handle_key(&mut state, key(c));
+        }
+        handle_key(&mut state, enter);
+        assert!(matches!(state.mode, Mode::SelectedObject { object_index: 0 }));
+        assert!(state.dirty);
+        let copies: Vec<(usize, usize, u16, u16)> = state.source.objects[1..].iter().map(|o| match o {
+            SceneObject::Label(l) => (l.frames.start, l.frames.end, l.position.x.start_value(), l.position.y.start_value()),
+            _ => panic!("a label copy"),
+        }).collect();
+        assert_eq!(copies, [(2, 3, 2, 3), (3, 4, 4, 2), (4, 5, 6, 1), (5, 6, 8, 0)], "clamped to the deck, stepped per frame");
+
+        handle_key(&mut state, key('m'));
+        for c in "4-2".chars() {
+            handle_key(&mut state, key(c));
+        }
+        handle_key(&mut state, enter);
+        assert!(matches!(state.mode, Mode::StampFramesInput { .. }), "a bad range stays in the prompt");
+        assert!(state.status_message.as_deref().is_some_and(|m| m.starts_with('⚠')));
+        assert_eq!(state.source.objects.len(), 5);
+    }
+
+    #[test]
+    fn find_and_replace_steps_through_matches_with_a_confirm_each() {
+        let mut state = EditorState::open("/tmp/bs_find_replace_absent_1.json").unwrap();
+        state.source.frame_count = 3;
+        state.source.objects = vec![
+            serde_json::from_str(r#"{"type":"label","text":"Acme v1","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":1}}"#).unwrap(),
+            serde_json::from_str(r#"{"type":"header","text":"ACME","position":{"x":{"fixed":0},"y":{"fixed":2}},"frames":{"start":1,"end":2}}"#).unwrap(),
+            serde_json::from_str(r#"{"type":"table","position":{"x":{"fixed":0},"y":{"fixed":3}},"col_widths":[1.0],"rows":1,"cells":[[{"content":"Acme v2"}]],"frames":{"start":2,"end":3}}"#).unwrap(),
+        ];
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        let press = |state: &mut EditorState, code| handle_key(state, KeyEvent::new(code, KeyModifiers::NONE));
+        let typed = |state: &mut EditorState, text: &str| {
+            for c in text.chars() {
+                handle_key(state, key(c));
+            }
+        };
+        let texts = |state: &EditorState| -> Vec<String> {
+            state.source.objects.iter().map(|o| match o {
+                SceneObject::Label(l) => l.text.clone(),
+                SceneObject::Header(h) => h.text.clone(),
+                SceneObject::Table(t) => t.cells[0][0].content.clone(),
+                _ => unreachable!(),
+            }).collect()
+        };
+
+        handle_key(&mut state, key('R'));
+        typed(&mut state, "Nope");
+        press(&mut state, KeyCode::Enter);
+        assert!(matches!(state.mode, Mode::FindInput { .. }), "nothing to replace stays in the prompt");
+        assert!(state.status_message.as_deref().is_some_and(|m| m.starts_with('⚠')));
+
+        for _ in 0..4 {
+            press(&mut state, KeyCode::Backspace);
+        }
+        press(&mut state, KeyCode::Tab);
+        typed(&mut state, "(?i)acme");
+        press(&mut state, KeyCode::Enter);
+        typed(&mut state, "Initech");
+        press(&mut state, KeyCode::Enter);
+        assert!(matches!(&state.mode, Mode::ReplaceConfirm { at, .. } if at.object_index == 0));
+
+        handle_key(&mut state, key('y'));
+        assert!(matches!(&state.mode, Mode::ReplaceConfirm { at, .. } if at.object_index == 1));
+        assert_eq!(state.current_frame, 1, "the preview follows the match to its frame");
+        handle_key(&mut state, key('n'));
+        assert!(matches!(&state.mode, Mode::ReplaceConfirm { at, .. } if at.cell == Some((0, 0))));
+        handle_key(&mut state, key('a'));
+        assert!(matches!(state.mode, Mode::Normal));
+        assert!(state.dirty);
+        assert_eq!(texts(&state), ["Initech v1", "ACME", "Initech v2"]);
+        assert_eq!(state.status_message.as_deref(), Some("Replaced 2 match(es), skipped 1"));
+    }
+
+    #[test]
+    fn key_bindings_are_rebound_from_the_settings_with_clashes_refused_at_save() {
+        let mut state = EditorState::open("/tmp/bs_key_bindings_absent_1.json").unwrap();
+        let path = std::env::temp_dir().join(format!("bs_key_bindings_{}.json", std::process::id()));
+        state.config.file = Some(path.clone());
+        let press = |state: &mut EditorState, code, mods| handle_key(state, KeyEvent::new(code, mods));
+        let none = KeyModifiers::NONE;
+
+        press(&mut state, KeyCode::Char('g'), none);
+        for _ in 0..3 {
+            press(&mut state, KeyCode::Tab, none);
+        }
+        press(&mut state, KeyCode::Enter, none);
+        assert!(matches!(state.mode, Mode::KeyBindings { selected: 0, capturing: false }));
+        let at = state.config.key_bindings.entries().iter().position(|(n, _)| n == "find_replace").unwrap();
+        for _ in 0..at {
+            press(&mut state, KeyCode::Down, none);
+        }
+
+        press(&mut state, KeyCode::Enter, none);
+        assert!(matches!(state.mode, Mode::KeyBindings { capturing: true, .. }));
+        press(&mut state, KeyCode::Char('a'), none);
+        assert_eq!(state.config.key_bindings.find_replace, "a");
+        assert_eq!(state.status_message.as_deref(), Some("⚠ find_replace = a clashes with add_object"));
+        press(&mut state, KeyCode::Char('s'), KeyModifiers::CONTROL);
+        assert!(state.status_message.as_deref().is_some_and(|m| m.starts_with("⚠ 2 bindings clash")));
+        assert!(!path.exists(), "nothing saved while bindings clash");
+
+        press(&mut state, KeyCode::Backspace, none);
+        assert_eq!(state.config.key_bindings.find_replace, "R", "Backspace restores the default");
+        press(&mut state, KeyCode::Enter, none);
+        press(&mut state, KeyCode::Char('h'), KeyModifiers::CONTROL);
+        assert_eq!(state.config.key_bindings.find_replace, "Ctrl-h");
+        press(&mut state, KeyCode::Char('s'), KeyModifiers::CONTROL);
+        let saved = crate::editor::config::EditorConfig::load_file(&path).unwrap();
+        assert_eq!(saved.key_bindings.find_replace, "Ctrl-h");
+        std::fs::remove_file(&path).ok();
+
+        press(&mut state, KeyCode::Esc, none);
+        assert!(matches!(state.mode, Mode::Settings { selected_field: 3, .. }));
+        press(&mut state, KeyCode::Esc, none);
+        press(&mut state, KeyCode::Char('h'), KeyModifiers::CONTROL);
+        assert!(matches!(state.mode, Mode::FindInput { .. }), "the new chord works straight away");
+    }
+
+    #[test]
+    fn a_deck_under_a_project_config_opens_with_its_lint_levels() {
+        let root = std::env::temp_dir().join(format!("bs_project_deck_{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join(crate::editor::config::PROJECT_FILES[0]), r#"{"lint":{"empty_frame":"allow"}}"#).unwrap();
+        let path = root.join("absent.json");
+        let mut state = EditorState::open(path.to_str().unwrap()).unwrap();
+        assert!(state.config.project.is_some());
+
+        handle_key(&mut state, KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE));
+        assert!(matches!(state.mode, Mode::Normal));
+        assert_eq!(state.status_message.as_deref(), Some("No problems found"), "the project allows empty frames");
+
+        std::fs::write(root.join(crate::editor::config::PROJECT_FILES[0]), "{").unwrap();
+        let state = EditorState::open(path.to_str().unwrap()).unwrap();
+        assert!(state.config.project.is_none());
+        assert!(state.status_message.unwrap().starts_with("⚠ Failed to parse project config"));
+        std::fs::remove_dir_all(&root).ok();
+    }
 }
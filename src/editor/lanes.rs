@@ -0,0 +1,114 @@
+//! The lanes view: an alternate timeline drawn over the canvas while in
+//! [`Mode::Lanes`] — one row per object, a bar over its frame range, and a
+//! heavier fill where an animation drives one of its coordinates.
+
+use std::io;
+
+use crossterm::{cursor, queue, style};
+
+use crate::engine::source::{AnimSpans, SceneObject};
+
+use super::state::{self, EditorState, Mode};
+use super::timeline::{frame_at, thumb_col};
+use super::ui::Layout;
+
+/// Columns given to each lane's object summary, left of its track.
+const LABEL_WIDTH: u16 = 22;
+
+/// Screen column of the first track cell, and the track's length, for `layout`.
+pub(super) fn track(layout: &Layout) -> (u16, usize) {
+    let x0 = layout.canvas_x + LABEL_WIDTH + 1;
+    (x0, layout.canvas_width.saturating_sub(LABEL_WIDTH + 2) as usize)
+}
+
+/// The first lane on screen, scrolled just far enough to keep `selected` in a
+/// window of `rows`.
+pub(super) fn first_lane(selected: usize, rows: usize) -> usize {
+    selected.saturating_sub(rows.saturating_sub(1))
+}
+
+/// One lane's track, one char per column: blank outside the object's frame
+/// range, `█` inside it, `▓` where one of its animations runs. An object with
+/// no range of its own (an auto-span `Group`) gets an empty lane.
+pub(super) fn lane_cells(obj: &SceneObject, anims: &AnimSpans, frame_count: usize, len: usize) -> String {
+    let Some(range) = state::scene_object_frame_range(obj).cloned() else {
+        return " ".repeat(len);
+    };
+    let spans: Vec<_> = state::referenced_anim_ids(obj)
+        .into_iter()
+        .filter_map(|id| anims.span(id))
+        .collect();
+    (0..len)
+        .map(|col| {
+            let f = frame_at(col, len, frame_count);
+            if !(range.start <= f && f < range.end) {
+                ' '
+            } else if spans.iter().any(|s| s.start <= f && f < s.end) {
+                '▓'
+            } else {
+                '█'
+            }
+        })
+        .collect()
+}
+
+pub fn render_lanes(stdout: &mut io::Stdout, layout: &Layout, state: &EditorState) -> anyhow::Result<()> {
+    let Mode::Lanes { selected, at_end } = state.mode else {
+        return Ok(());
+    };
+    let (x0, len) = track(layout);
+    let frame_count = state.source.frame_count;
+    let anims = AnimSpans::of(&state.source);
+    let rows = layout.canvas_height as usize;
+    let first = first_lane(selected, rows);
+    let now = thumb_col(state.current_frame, len, frame_count);
+
+    for (row, (i, obj)) in state.source.objects.iter().enumerate().skip(first).take(rows).enumerate() {
+        let y = layout.canvas_y + row as u16;
+        let label: String = state::scene_object_summary(obj).chars().take(LABEL_WIDTH as usize).collect();
+        queue!(stdout, cursor::MoveTo(layout.canvas_x, y))?;
+        if i == selected {
+            queue!(stdout, state.config.theme.selection())?;
+        }
+        queue!(
+            stdout,
+            style::Print(format!("{label:<width$}", width = LABEL_WIDTH as usize)),
+            style::SetAttribute(style::Attribute::Reset),
+            cursor::MoveTo(x0 - 1, y),
+            style::Print("│"),
+        )?;
+
+        // The end being moved, on the selected lane only.
+        let handle = (i == selected)
+            .then(|| state::scene_object_frame_range(obj))
+            .flatten()
+            .map(|r| {
+                let f = if at_end { r.end.saturating_sub(1) } else { r.start };
+                thumb_col(f, len, frame_count)
+            });
+        for (col, ch) in lane_cells(obj, &anims, frame_count, len).chars().enumerate() {
+            let ch = if ch == ' ' && col == now { '┊' } else { ch };
+            if Some(col) == handle {
+                queue!(
+                    stdout,
+                    style::SetForegroundColor(style::Color::Yellow),
+                    style::Print(ch),
+                    style::ResetColor,
+                )?;
+            } else if col == now {
+                queue!(
+                    stdout,
+                    style::SetAttribute(style::Attribute::Bold),
+                    style::Print(ch),
+                    style::SetAttribute(style::Attribute::Reset),
+                )?;
+            } else {
+                queue!(stdout, style::Print(ch))?;
+            }
+        }
+    }
+    if state.source.objects.is_empty() {
+        queue!(stdout, cursor::MoveTo(layout.canvas_x, layout.canvas_y), style::Print(" (no objects)"))?;
+    }
+    Ok(())
+}
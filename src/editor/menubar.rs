@@ -3,7 +3,7 @@ use std::io;
 use crossterm::{cursor, queue, style, terminal};
 
 use crate::engine::source::SceneObject;
-use crate::menubar::print_menu_item;
+use crate::menubar::print_menu_item_styled;
 
 use super::properties::{self, PropertyKind};
 use super::state::{EditorState, Mode, TableCellSubState};
@@ -23,6 +23,10 @@ fn mode_items(state: &EditorState) -> Vec<&'static str> {
                 "[s]elect",
                 "[f]rame",
                 "[p]resentations",
+                "[l]ayers",
+                "[d]iagnostics",
+                "[T]lanes",
+                "[R]eplace",
                 "[Ctrl-s]ave",
                 "[q]uit",
             ];
@@ -43,6 +47,35 @@ fn mode_items(state: &EditorState) -> Vec<&'static str> {
             "[f]ullscreen",
             "[Esc] back",
         ],
+        Mode::Layers { .. } => vec![
+            "[↑][↓] layer",
+            "[v]isible",
+            "[l]ock",
+            "[Esc] back",
+        ],
+        Mode::Lanes { .. } => vec![
+            "[↑][↓] object",
+            "[Tab] start/end",
+            "[←][→] move end",
+            "[drag] ends",
+            "[Enter] select",
+            "[Esc] back",
+        ],
+        Mode::Diagnostics { .. } => vec![
+            "[↑][↓] finding",
+            "[Enter] go to",
+            "[Esc] back",
+        ],
+        Mode::Start { .. } => vec![
+            "[↑][↓] pick",
+            "[Enter] open",
+            "[Esc] quit",
+        ],
+        Mode::NewDeck { .. } => vec![
+            "[↑↓][Tab] field",
+            "[Enter] create",
+            "[Esc] back",
+        ],
         Mode::OpenFile { .. } => vec![
             "[type] file path",
             "[Enter] open",
@@ -68,6 +101,9 @@ fn mode_items(state: &EditorState) -> Vec<&'static str> {
                 "[j]ump",
                 "[s]elect",
                 "[t] auto-advance",
+                "[u] duration",
+                "[n] section",
+                "[r]ipple",
                 "[d]elete",
                 "[m]ove",
                 "[Esc] back",
@@ -76,7 +112,7 @@ fn mode_items(state: &EditorState) -> Vec<&'static str> {
             // Paste-frames is offered only when the cross-deck frame clipboard
             // holds something (mirrors the [v] paste convention).
             if state.workspace.frame_clip_frames > 0 {
-                items.insert(8, "[p]aste frames");
+                items.insert(11, "[p]aste frames");
             }
             items
         }
@@ -85,6 +121,43 @@ fn mode_items(state: &EditorState) -> Vec<&'static str> {
             "[Enter] set",
             "[Esc] cancel",
         ],
+        Mode::FrameDurationInput { .. } => vec![
+            "[type] seconds",
+            "[Enter] set",
+            "[Esc] cancel",
+        ],
+        Mode::FrameSectionInput { .. } => vec![
+            "[type] name",
+            "[Enter] set",
+            "[Esc] cancel",
+        ],
+        Mode::FindInput { .. } => vec![
+            "[type] text",
+            "[Tab] regex",
+            "[Enter] next",
+            "[Esc] cancel",
+        ],
+        Mode::ReplaceInput { .. } => vec![
+            "[type] replacement",
+            "[Enter] find",
+            "[Esc] back",
+        ],
+        Mode::ReplaceConfirm { .. } => vec![
+            "[y] replace",
+            "[n] skip",
+            "[a]ll",
+            "[Esc] stop",
+        ],
+        Mode::StampFramesInput { .. } => vec![
+            "[type] 5-12 dx,dy",
+            "[Enter] stamp",
+            "[Esc] cancel",
+        ],
+        Mode::FrameRippleInput { .. } => vec![
+            "[type] ±frames",
+            "[Enter] shift",
+            "[Esc] cancel",
+        ],
         Mode::FrameJump { .. } => vec![
             "[type] frame #",
             "[Enter] jump",
@@ -99,6 +172,8 @@ fn mode_items(state: &EditorState) -> Vec<&'static str> {
             "[d]elete selected",
             "[m]ove range",
             "[c]opy range",
+            "[D]uplicate",
+            "[k]eep only",
             "[y] yank→deck",
             "[Esc] cancel",
             "[F]ull",
@@ -128,12 +203,28 @@ fn mode_items(state: &EditorState) -> Vec<&'static str> {
             "[Esc] cancel",
             "[F]ull",
         ],
-        Mode::Settings { .. } => vec![
+        Mode::Settings { selected_field: 3, .. } => vec![
+            "[↑↓][Tab] field",
+            "[Enter] key bindings",
+            "[Esc] cancel",
+        ],
+        Mode::Settings { selected_field, .. } => vec![
             "[↑↓][Tab] field",
-            "[0-9] edit",
+            if *selected_field == 2 { "[←][→] fit" } else { "[0-9] edit" },
             "[Enter] apply",
             "[Esc] cancel",
         ],
+        Mode::KeyBindings { capturing: true, .. } => vec![
+            "[any key] bind",
+            "[Esc] keep",
+        ],
+        Mode::KeyBindings { .. } => vec![
+            "[↑][↓] action",
+            "[Enter] rebind",
+            "[Backspace] default",
+            "[Ctrl-s]ave",
+            "[Esc] back",
+        ],
         Mode::AddObject { .. } => {
             vec!["[↑][↓] type", "[Enter] add", "[Esc] cancel", "[F]ull"]
         }
@@ -142,27 +233,51 @@ fn mode_items(state: &EditorState) -> Vec<&'static str> {
             "[Enter] do",
             "[Esc] cancel",
         ],
-        Mode::SelectedObject { .. } => {
+        Mode::SelectedObject { object_index } => {
             let mut items = vec![
                 "[←→↑↓] move",
+                "[Ctrl+←→↑↓] big step",
                 "[r]esize",
                 "[Shift+←→↑↓] grow",
                 "[e]dit props",
                 "[c]opy",
                 "[d]el",
+                "sta[m]p frames",
                 "[Esc] back",
                 "[F]ull",
             ];
             // Paste shows right after [c]opy, but only when the clipboard has
             // something to drop.
             if !state.clipboard.is_empty() {
-                items.insert(5, "[v] paste");
+                items.insert(6, "[v] paste");
+            }
+            if matches!(state.source.objects.get(*object_index), Some(SceneObject::Group(_))) {
+                items.insert(items.len() - 4, "s[t]amp");
+            }
+            if matches!(state.source.objects.get(*object_index), Some(SceneObject::Label(_) | SceneObject::Table(_))) {
+                items.insert(5, "[i]n place");
             }
             items
         }
+        Mode::PickCoordinate { .. } => vec![
+            "[←→↑↓] move",
+            "[Shift+←→↑↓] ×5",
+            "[click] jump",
+            "[Enter] set",
+            "[Esc] cancel",
+        ],
+        Mode::EditInPlace { .. } => vec![
+            "[←→↑↓] move cursor",
+            "[type] insert",
+            "[Alt+Enter] newline",
+            "[Ctrl-e] $EDITOR",
+            "[Enter] save",
+            "[Esc] cancel",
+        ],
         Mode::ResizeObject { .. } => vec![
             "[←→] width",
             "[↑↓] height",
+            "[Ctrl] big step",
             "[Enter][Esc] done",
             "[F]ull",
         ],
@@ -183,6 +298,9 @@ fn mode_items(state: &EditorState) -> Vec<&'static str> {
                 items.push("[Enter] edit");
                 if is_coord {
                     items.push("[a]nimate");
+                    if super::input::pick_start(state, *object_index, prop.name).is_some() {
+                        items.push("[p]ick");
+                    }
                 }
             }
             if is_table {
@@ -196,12 +314,15 @@ fn mode_items(state: &EditorState) -> Vec<&'static str> {
             items
         }
         // Editing a property value
-        Mode::EditProperties { editing_value: Some(_), .. } => vec![
-            "[←][→] cursor",
-            "[Alt+Enter] newline",
-            "[Enter] apply",
-            "[Esc] cancel",
-        ],
+        Mode::EditProperties { editing_value: Some(_), object_index, selected_property, .. } => {
+            let mut items = vec!["[←][→] cursor", "[Alt+Enter] newline"];
+            let props = properties::get_properties(&state.source.objects, *object_index);
+            if props.get(*selected_property).is_some_and(|p| p.kind == PropertyKind::Text) {
+                items.push("[Ctrl-e] $EDITOR");
+            }
+            items.extend(["[Enter] apply", "[Esc] cancel"]);
+            items
+        }
         // Bulk-editing the shared properties of a multi-object selection.
         Mode::EditMultiProperties { editing_value: None, members, selected_property, .. } => {
             let props = properties::common_properties(&state.source.objects, members);
@@ -222,6 +343,7 @@ fn mode_items(state: &EditorState) -> Vec<&'static str> {
         ],
         Mode::AnimateProperty { editing: None, .. } => vec![
             "[↑][↓] field",
+            "[←][→] scrub",
             "[Enter] edit",
             "[Space] toggle",
             "[s]ave anim",
@@ -246,6 +368,8 @@ fn mode_items(state: &EditorState) -> Vec<&'static str> {
                 super::state::MultiSelectPurpose::Select => "[Enter] act",
             });
             if matches!(purpose, super::state::MultiSelectPurpose::Select) {
+                hints.push("[e]dit");
+                hints.push("[G]roup");
                 hints.push("[d]el");
             }
             hints.push("[Esc] cancel");
@@ -302,6 +426,7 @@ fn mode_items(state: &EditorState) -> Vec<&'static str> {
                 "[←→] move cursor",
                 "[type] insert after",
                 "[Alt+Enter] newline",
+                "[Ctrl-e] $EDITOR",
                 "[Backspace] delete",
                 "[Enter] save",
                 "[Esc] cancel",
@@ -331,6 +456,17 @@ pub fn render_menubar(
     }
 
     let items = mode_items(state);
+    let text = state.config.theme.menubar();
+    // With a menubar background, the gaps between items and the rest of each
+    // line are filled with it too.
+    let fill = style::ContentStyle { background_color: text.background_color, ..Default::default() };
+    let pad = |stdout: &mut io::Stdout, x: u16| -> anyhow::Result<()> {
+        if fill.background_color.is_some() {
+            let rest = " ".repeat(layout.term_width.saturating_sub(x) as usize);
+            queue!(stdout, style::PrintStyledContent(fill.apply(rest)))?;
+        }
+        Ok(())
+    };
 
     let mut line: u16 = 0;
     let mut x: u16 = 1; // leading space
@@ -340,7 +476,7 @@ pub fn render_menubar(
         stdout,
         cursor::MoveTo(0, 0),
         terminal::Clear(terminal::ClearType::CurrentLine),
-        style::Print(" "),
+        style::PrintStyledContent(fill.apply(" ")),
     )?;
 
     for (i, item) in items.iter().enumerate() {
@@ -351,19 +487,20 @@ pub fn render_menubar(
             if x + 2 + item_w > layout.term_width {
                 // Wrap if another menu line is available
                 if line + 1 < layout.menu_h {
+                    pad(stdout, x)?;
                     line += 1;
                     x = 1;
                     queue!(
                         stdout,
                         cursor::MoveTo(0, line),
                         terminal::Clear(terminal::ClearType::CurrentLine),
-                        style::Print(" "),
+                        style::PrintStyledContent(fill.apply(" ")),
                     )?;
                 } else {
                     break; // No more lines — drop remaining items
                 }
             } else {
-                queue!(stdout, style::Print("  "))?;
+                queue!(stdout, style::PrintStyledContent(fill.apply("  ")))?;
                 x += 2;
             }
         }
@@ -372,9 +509,10 @@ pub fn render_menubar(
             break; // Item wider than remaining space even alone
         }
 
-        print_menu_item(stdout, item)?;
+        print_menu_item_styled(stdout, item, text)?;
         x += item_w;
     }
+    pad(stdout, x)?;
 
     // Clear any remaining allocated menu lines that were not used
     for l in line + 1..layout.menu_h {
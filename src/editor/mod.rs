@@ -1,10 +1,14 @@
 pub mod config;
+mod external;
 mod input;
+mod lanes;
 mod menubar;
 mod object_defaults;
 mod panel;
 mod preview;
 mod properties;
+mod search;
+mod start;
 pub mod state;
 mod textedit;
 mod timeline;
@@ -20,6 +24,7 @@ use crossterm::event::{
 };
 use crossterm::{cursor, event, execute, queue, terminal};
 
+use config::EditorConfig;
 use input::Action;
 use state::{EditorState, FrameClipboard, Mode};
 use ui::Layout;
@@ -32,6 +37,13 @@ pub struct Editor {
     /// The cross-deck frame clipboard: a contiguous block of frames yanked from
     /// one deck, ready to paste into another. Shared across all decks.
     frame_clip: Option<FrameClipboard>,
+    /// Config chosen on the command line (`--config`); applied to every deck,
+    /// including ones opened later from the hub. `None` ⇒ each deck loads the
+    /// default `~/.config/bs/editor.json`.
+    config: Option<EditorConfig>,
+    /// Whether keyboard enhancement was pushed on entering the TUI, so it can
+    /// be popped (and re-pushed) around a suspension.
+    enhanced: bool,
 }
 
 impl Editor {
@@ -45,11 +57,30 @@ impl Editor {
         let mut decks = Vec::with_capacity(paths.len());
         for p in paths {
             decks.push(EditorState::open(p)?);
+            start::remember(p);
         }
         if decks.is_empty() {
             decks.push(EditorState::open("untitled.json")?);
         }
-        Ok(Editor { decks, active: 0, frame_clip: None })
+        Ok(Editor { decks, active: 0, frame_clip: None, config: None, enhanced: false })
+    }
+
+    /// Open on the start screen: recent decks and a new-presentation wizard,
+    /// seeded with `path` (a file that does not exist yet) when one was given.
+    pub fn start(path: Option<&str>) -> Result<Self> {
+        let mut deck = EditorState::open(path.unwrap_or("untitled.json"))?;
+        deck.mode = Mode::Start { selected: 0, recent: start::load_recent() };
+        Ok(Editor { decks: vec![deck], active: 0, frame_clip: None, config: None, enhanced: false })
+    }
+
+    /// Use `config` instead of the default config file for every deck (each
+    /// still with its project config laid over it).
+    pub fn with_config(mut self, config: EditorConfig) -> Self {
+        for deck in &mut self.decks {
+            deck.use_config(config.clone());
+        }
+        self.config = Some(config);
+        self
     }
 
     fn active(&self) -> &EditorState {
@@ -94,6 +125,7 @@ impl Editor {
         execute!(
             stdout,
             terminal::EnterAlternateScreen,
+            event::EnableMouseCapture,
             cursor::Hide,
             terminal::Clear(terminal::ClearType::All),
         )?;
@@ -101,8 +133,8 @@ impl Editor {
         // Ask the terminal to disambiguate key events so modifier+Enter combos
         // (e.g. Shift-Enter to insert a newline in a cell) are reported distinctly.
         // Not all terminals support this; fall back silently when unsupported.
-        let enhanced = terminal::supports_keyboard_enhancement().unwrap_or(false);
-        if enhanced {
+        self.enhanced = terminal::supports_keyboard_enhancement().unwrap_or(false);
+        if self.enhanced {
             let _ = execute!(
                 stdout,
                 PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
@@ -110,14 +142,58 @@ impl Editor {
         }
 
         let result = self.main_loop(&mut stdout);
+        self.leave_terminal(&mut stdout);
+        result
+    }
 
-        if enhanced {
+    /// Hand the terminal back: pop keyboard enhancement, leave the alternate
+    /// screen and raw mode. Best-effort — it also runs on the error path.
+    fn leave_terminal(&self, stdout: &mut io::Stdout) {
+        if self.enhanced {
             let _ = execute!(stdout, PopKeyboardEnhancementFlags);
         }
-        let _ = execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = execute!(
+            stdout,
+            event::DisableMouseCapture,
+            cursor::Show,
+            terminal::LeaveAlternateScreen
+        );
         let _ = terminal::disable_raw_mode();
+    }
 
-        result
+    /// Take the terminal back after [`Self::leave_terminal`].
+    fn reenter_terminal(&self, stdout: &mut io::Stdout) -> Result<()> {
+        terminal::enable_raw_mode()?;
+        execute!(
+            stdout,
+            terminal::EnterAlternateScreen,
+            event::EnableMouseCapture,
+            cursor::Hide,
+            terminal::Clear(terminal::ClearType::All),
+        )?;
+        if self.enhanced {
+            let _ = execute!(
+                stdout,
+                PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+            );
+        }
+        Ok(())
+    }
+
+    /// Suspend the TUI, edit `text` in the external editor and put the result
+    /// back into the buffer being edited. A failed or aborted editor leaves the
+    /// buffer as it was and says why.
+    fn edit_externally(&mut self, stdout: &mut io::Stdout, text: &str) -> Result<()> {
+        let command = external::editor_command();
+        self.leave_terminal(stdout);
+        let edited = external::edit_with(&command, text);
+        self.reenter_terminal(stdout)?;
+        let st = self.active_mut();
+        match edited {
+            Ok(new) => input::replace_edit_buffer(st, new),
+            Err(e) => st.status_message = Some(format!("External editor failed: {e:#}")),
+        }
+        Ok(())
     }
 
     fn main_loop(&mut self, stdout: &mut io::Stdout) -> Result<()> {
@@ -134,7 +210,16 @@ impl Editor {
 
         loop {
             let event = event::read()?;
-            let action = input::handle_event(self.active_mut(), event);
+            let action = match event {
+                // Mouse hits are resolved against the layout they landed on.
+                event::Event::Mouse(mouse) => {
+                    let state = self.active();
+                    let (term_w, term_h) = terminal::size()?;
+                    let layout = Layout::compute(term_w, term_h, &state.mode, state.fullscreen);
+                    input::handle_mouse(self.active_mut(), mouse, &layout)
+                }
+                event => input::handle_event(self.active_mut(), event),
+            };
 
             match action {
                 Action::Continue => {}
@@ -168,6 +253,11 @@ impl Editor {
                     self.full_redraw(stdout)?;
                     pending_redraw = false;
                 }
+                Action::OpenInPlace(path) => {
+                    self.open_in_place(&path);
+                    self.full_redraw(stdout)?;
+                    pending_redraw = false;
+                }
                 Action::CopyFrameBlock { lo, hi } => {
                     let clip = state::copy_frame_block(&self.active().source, lo, hi);
                     let n = clip.frame_count;
@@ -185,6 +275,11 @@ impl Editor {
                     self.full_redraw(stdout)?;
                     pending_redraw = false;
                 }
+                Action::ExternalEdit(text) => {
+                    self.edit_externally(stdout, &text)?;
+                    self.full_redraw(stdout)?;
+                    pending_redraw = false;
+                }
                 Action::Quit => {
                     if self.handle_quit(stdout)? {
                         break;
@@ -216,10 +311,14 @@ impl Editor {
             return;
         }
         match EditorState::open(path) {
-            Ok(st) => {
+            Ok(mut st) => {
+                if let Some(config) = &self.config {
+                    st.use_config(config.clone());
+                }
                 self.decks.push(st);
                 self.active = self.decks.len() - 1;
-                self.active_mut().status_message = Some(format!("Opened {path}"));
+                self.active_mut().status_message.get_or_insert_with(|| format!("Opened {path}"));
+                start::remember(path);
             }
             Err(e) => {
                 self.active_mut().mode = Mode::Normal;
@@ -228,6 +327,22 @@ impl Editor {
         }
     }
 
+    /// Replace the active deck with the one at `path` (picked on the start
+    /// screen). On failure the start screen stays up with the reason.
+    fn open_in_place(&mut self, path: &str) {
+        match EditorState::open(path) {
+            Ok(mut st) => {
+                if let Some(config) = &self.config {
+                    st.use_config(config.clone());
+                }
+                st.status_message.get_or_insert_with(|| format!("Opened {path}"));
+                *self.active_mut() = st;
+                start::remember(path);
+            }
+            Err(e) => self.active_mut().status_message = Some(format!("Open failed: {e}")),
+        }
+    }
+
     /// Paste the cross-deck frame clipboard into the active deck at `target`.
     fn paste_frame_block(&mut self, target: usize, before: bool) {
         let Some(clip) = self.frame_clip.clone() else {
@@ -276,21 +391,29 @@ impl Editor {
             dirty.join(", ")
         ));
         self.full_redraw(stdout)?;
-        if let event::Event::Key(k) = event::read()? {
-            if k.code == event::KeyCode::Char('q') {
-                return Ok(true);
+        // Wait for a key: with mouse capture on, pointer motion also arrives
+        // here and must not count as the answer.
+        let k = loop {
+            if let event::Event::Key(k) = event::read()? {
+                break k;
             }
-            if k.code == event::KeyCode::Char('s')
-                && k.modifiers.contains(event::KeyModifiers::CONTROL)
-            {
-                match self.active_mut().save() {
-                    Ok(()) => {
-                        self.full_redraw(stdout)?;
-                        thread::sleep(Duration::from_secs(2));
-                    }
-                    Err(e) => {
-                        self.active_mut().status_message = Some(format!("Save failed: {e}"));
-                    }
+        };
+        if k.code == event::KeyCode::Char('q') {
+            return Ok(true);
+        }
+        if k.code == event::KeyCode::Char('s')
+            && k.modifiers.contains(event::KeyModifiers::CONTROL)
+        {
+            match self.active_mut().save() {
+                Ok(()) => {
+                    self.full_redraw(stdout)?;
+                    thread::sleep(Duration::from_secs(2));
+                }
+                Err(e) => {
+                    // Left up (a deck written with `slides` asks for a second save).
+                    self.active_mut().status_message = Some(format!("Save failed: {e}"));
+                    self.full_redraw(stdout)?;
+                    return Ok(false);
                 }
             }
         }
@@ -317,8 +440,15 @@ impl Editor {
             menubar::render_menubar(stdout, &layout, state)?;
         }
 
-        // Draw canvas
-        preview::render_canvas_production(stdout, &layout, state)?;
+        // Draw canvas (or, in the lanes view, the per-object lanes over it; on
+        // the start screen, its box over the empty deck)
+        if matches!(state.mode, Mode::Lanes { .. }) {
+            lanes::render_lanes(stdout, &layout, state)?;
+        } else if matches!(state.mode, Mode::Start { .. } | Mode::NewDeck { .. }) {
+            start::render_start(stdout, &layout, state)?;
+        } else {
+            preview::render_canvas_production(stdout, &layout, state)?;
+        }
 
         // Draw right panel (handles AddObject, MultiSelect, SelectAction, Confirm, EditProperties, AnimateProperty)
         panel::render_right_panel(stdout, &layout, state)?;
@@ -1,18 +1,32 @@
+use crate::engine::objects::{
+    default_spinner_glyphs, default_marquee_gap, default_marquee_speed, default_effect_density,
+    default_effect_z, default_diff_context, default_gantt_width, default_gauge_max,
+    default_gauge_size, default_heatmap_cell_width, default_heatmap_high, default_heatmap_low,
+    default_pie_char, default_plot_height, default_plot_width, default_step_secs, default_ticks,
+    default_keycap_border, default_keycap_separator, default_watermark_z,
+};
 use crate::engine::source::*;
 use crate::types::Style;
 
 pub const OBJECT_TYPES: &[&str] = &[
     "Label", "HLine", "Rect", "Header", "Group", "Arrow", "Table", "Art", "Command", "List",
-    "Loop", "Morph", "Circle",
+    "Loop", "Morph", "Circle", "Countdown", "Clock", "Toc", "Footer", "Watermark", "Gauge",
+    "Heatmap", "Diff", "FileTree", "Gantt", "PieChart", "ScatterPlot", "BackgroundEffect", "Marquee", "Spinner",
+    "Connector", "Keycap",
 ];
 
 /// One quick-add shortcut per object type, aligned by index with `OBJECT_TYPES`.
 /// The Add-Object menu shows each key (`[l] Label`); pressing it adds that type
 /// directly. Keys are unique and avoid the global fullscreen key (`f`). They are
 /// the type's initial where free, else another distinctive letter (Header→`e`,
-/// Arrow→`w`, Art→`a`, List→`i`, Loop→`p`, Morph→`m`, Circle→`o`).
-pub const OBJECT_TYPE_KEYS: &[char] =
-    &['l', 'h', 'r', 'e', 'g', 'w', 't', 'a', 'c', 'i', 'p', 'm', 'o'];
+/// Arrow→`w`, Art→`a`, List→`i`, Loop→`p`, Morph→`m`, Circle→`o`,
+/// Countdown→`d`, Clock→`k`, Toc→`n`, Footer→`b`, Watermark→`v`, Gauge→`u`,
+/// Heatmap→`x`, Diff→`y`, FileTree→`j`, Gantt→`s`,
+/// PieChart→`q`, ScatterPlot→`z`), and digits once the letters run out
+/// (BackgroundEffect→`1`, Marquee→`2`, Spinner→`3`, Connector→`4`, Keycap→`5`).
+pub const OBJECT_TYPE_KEYS: &[char] = &[
+    'l', 'h', 'r', 'e', 'g', 'w', 't', 'a', 'c', 'i', 'p', 'm', 'o', 'd', 'k', 'n', 'b', 'v', 'u', 'x', 'y', 'j', 's', 'q', 'z', '1', '2', '3', '4', '5',
+];
 
 /// Map a pressed character (case-insensitive) to an object-type index, if it is
 /// a quick-add shortcut.
@@ -36,6 +50,52 @@ pub fn create_art(art: String, name: String, current_frame: usize) -> SceneObjec
         // New objects live on the current slide only (end is exclusive).
         frames: FrameRange { start: current_frame, end: current_frame + 1 },
         z_order: 0,
+        layer: None,
+        enter: None,
+        exit: None,
+    })
+}
+
+/// Build an `AnsiArt` object from an ANSI art file's text. Used by the art
+/// picker's "Load from file…" entry when the file carries escape sequences.
+pub fn create_ansi_art(ansi: String, name: String, current_frame: usize) -> SceneObject {
+    SceneObject::AnsiArt(AnsiArt {
+        position: Position {
+            x: Coordinate::Fixed(0.0),
+            y: Coordinate::Fixed(0.0),
+        },
+        ansi,
+        name,
+        width: 80,
+        frames: FrameRange { start: current_frame, end: current_frame + 1 },
+        z_order: 0,
+        layer: None,
+        enter: None,
+        exit: None,
+    })
+}
+
+/// Build a `Cast` object playing the whole of a `.cast` recording. Used by
+/// the art picker's "Load from file…" entry for `.cast` files; it spans only
+/// the current slide, so it shows the recording's last screen until its frame
+/// range is widened.
+pub fn create_cast(cast: String, name: String, current_frame: usize) -> SceneObject {
+    SceneObject::Cast(Cast {
+        position: Position {
+            x: Coordinate::Fixed(0.0),
+            y: Coordinate::Fixed(0.0),
+        },
+        cast,
+        name,
+        start: 0.0,
+        end: None,
+        width: None,
+        height: None,
+        frames: FrameRange { start: current_frame, end: current_frame + 1 },
+        z_order: 0,
+        layer: None,
+        enter: None,
+        exit: None,
     })
 }
 
@@ -62,6 +122,9 @@ pub fn create_morph(
         style: Style::default(),
         frames: FrameRange { start: current_frame, end: current_frame + 1 },
         z_order: 0,
+        layer: None,
+        enter: None,
+        exit: None,
     })
 }
 
@@ -81,13 +144,23 @@ pub fn create_default(type_index: usize, current_frame: usize) -> SceneObject {
             },
             width: Coordinate::Fixed(0.0),
             height: Coordinate::Fixed(0.0),
+            link: None,
+            reveal: false,
+            // New text can use markup; existing labels keep their own setting.
+            markup: true,
             framed: false,
+            padding: Padding::default(),
+            border: BorderStyle::Single,
+            shadow: None,
             frame_style: None,
             align: TextAlign::default(),
             valign: VerticalAlign::default(),
             style: Style::default(),
             frames,
             z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
         }),
         1 => SceneObject::HLine(HLine {
             y: Coordinate::Fixed(0.0),
@@ -97,6 +170,9 @@ pub fn create_default(type_index: usize, current_frame: usize) -> SceneObject {
             style: Style::default(),
             frames,
             z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
         }),
         2 => SceneObject::Rect(Rect {
             position: Position {
@@ -109,6 +185,11 @@ pub fn create_default(type_index: usize, current_frame: usize) -> SceneObject {
             frames,
             z_order: 0,
             title: None,
+            border: BorderStyle::Single,
+            shadow: None,
+            layer: None,
+            enter: None,
+            exit: None,
         }),
         3 => SceneObject::Header(Header {
             text: "TITLE".into(),
@@ -120,12 +201,17 @@ pub fn create_default(type_index: usize, current_frame: usize) -> SceneObject {
             frames,
             z_order: 0,
             ch: '█',
+            gradient: None,
+            layer: None,
+            enter: None,
+            exit: None,
         }),
         4 => SceneObject::Group(Group {
             members: vec![],
             // Auto range by default (derived from members; none here).
             frames: None,
             z_order: 0,
+            layer: None,
         }),
         5 => SceneObject::Arrow(Arrow {
             x1: Coordinate::Fixed(5.0),
@@ -139,6 +225,9 @@ pub fn create_default(type_index: usize, current_frame: usize) -> SceneObject {
             style: Style::default(),
             frames,
             z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
         }),
         6 => {
             use crate::engine::objects::table::TableCell;
@@ -162,6 +251,9 @@ pub fn create_default(type_index: usize, current_frame: usize) -> SceneObject {
                 style: Style::default(),
                 frames,
                 z_order: 0,
+                layer: None,
+                enter: None,
+                exit: None,
             })
         }
         7 => {
@@ -185,6 +277,7 @@ pub fn create_default(type_index: usize, current_frame: usize) -> SceneObject {
             style: Style::default(),
             frames,
             z_order: 0,
+            layer: None,
         }),
         9 => SceneObject::List(List {
             text: "Item one\nItem two\nItem three".into(),
@@ -200,6 +293,9 @@ pub fn create_default(type_index: usize, current_frame: usize) -> SceneObject {
             style: Style::default(),
             frames,
             z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
         }),
         10 => SceneObject::Loop(Loop {
             // A new loop spans only the current slide; widen its range (and tune
@@ -208,6 +304,7 @@ pub fn create_default(type_index: usize, current_frame: usize) -> SceneObject {
             delay_ms: 500,
             count: 0,
             bounce: true,
+            layer: None,
         }),
         11 => {
             // Fallback only — the editor adds Morph via the two-stage art picker
@@ -233,6 +330,294 @@ pub fn create_default(type_index: usize, current_frame: usize) -> SceneObject {
             style: Style::default(),
             frames,
             z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }),
+        // A five-minute break timer, counting down once it is given frames.
+        13 => SceneObject::Countdown(Countdown {
+            position: Position {
+                x: Coordinate::Fixed(0.0),
+                y: Coordinate::Fixed(0.0),
+            },
+            from_secs: 300,
+            step_secs: default_step_secs(),
+            auto_play: false,
+            big: false,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }),
+        14 => SceneObject::Clock(Clock {
+            position: Position {
+                x: Coordinate::Fixed(0.0),
+                y: Coordinate::Fixed(0.0),
+            },
+            seconds: false,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+        }),
+        15 => SceneObject::Toc(Toc {
+            position: Position {
+                x: Coordinate::Fixed(0.0),
+                y: Coordinate::Fixed(0.0),
+            },
+            numbers: true,
+            highlight: None,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }),
+        16 => SceneObject::Footer(Footer {
+            text: "{frame}/{total}".into(),
+            position: Position {
+                x: Coordinate::Fixed(0.0),
+                y: Coordinate::Fixed(0.0),
+            },
+            width: 0,
+            align: TextAlign::Right,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }),
+        17 => SceneObject::Watermark(Watermark {
+            text: "DRAFT".into(),
+            position: Position {
+                x: Coordinate::Fixed(0.0),
+                y: Coordinate::Fixed(0.0),
+            },
+            big: true,
+            style: Style::default(),
+            frames,
+            z_order: default_watermark_z(),
+            layer: None,
+        }),
+        18 => SceneObject::Gauge(Gauge {
+            position: Position {
+                x: Coordinate::Fixed(0.0),
+                y: Coordinate::Fixed(0.0),
+            },
+            value: Scalar::Fixed(50.0),
+            min: Scalar::Fixed(0.0),
+            max: default_gauge_max(),
+            size: default_gauge_size(),
+            thresholds: Vec::new(),
+            blocks: false,
+            show_value: true,
+            unit: String::new(),
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }),
+        19 => SceneObject::Heatmap(Heatmap {
+            position: Position {
+                x: Coordinate::Fixed(0.0),
+                y: Coordinate::Fixed(0.0),
+            },
+            values: vec![vec![1.0, 2.0, 3.0], vec![2.0, 3.0, 4.0], vec![3.0, 4.0, 5.0]],
+            row_labels: Vec::new(),
+            col_labels: Vec::new(),
+            cell_width: default_heatmap_cell_width(),
+            min: None,
+            max: None,
+            low: default_heatmap_low(),
+            high: default_heatmap_high(),
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }),
+        20 => SceneObject::Diff(Diff {
+            position: Position {
+                x: Coordinate::Fixed(0.0),
+                y: Coordinate::Fixed(0.0),
+            },
+            before: "let x = 1;".to_string(),
+            after: "let x = 2;".to_string(),
+            diff: String::new(),
+            context: default_diff_context(),
+            reveal: false,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }),
+        21 => SceneObject::FileTree(FileTree {
+            position: Position {
+                x: Coordinate::Fixed(0.0),
+                y: Coordinate::Fixed(0.0),
+            },
+            entries: ["src/main.rs", "src/lib.rs", "Cargo.toml"]
+                .into_iter()
+                .map(|path| TreeEntry { path: path.to_string(), style: None, open_at: 0 })
+                .collect(),
+            glyphs: true,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }),
+        22 => SceneObject::Gantt(Gantt {
+            position: Position {
+                x: Coordinate::Fixed(0.0),
+                y: Coordinate::Fixed(0.0),
+            },
+            tasks: vec![
+                GanttTask { label: "design".to_string(), start: 0.0, end: 4.0, lane: String::new(), color: None },
+                GanttTask { label: "build".to_string(), start: 3.0, end: 10.0, lane: String::new(), color: None },
+            ],
+            width: default_gantt_width(),
+            min: None,
+            max: None,
+            today: None,
+            border: BorderStyle::default(),
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }),
+        23 => SceneObject::PieChart(PieChart {
+            position: Position {
+                x: Coordinate::Fixed(0.0),
+                y: Coordinate::Fixed(0.0),
+            },
+            segments: [("yes", 3.0), ("no", 2.0), ("maybe", 1.0)]
+                .into_iter()
+                .map(|(label, value)| PieSegment { label: label.to_string(), value, color: None })
+                .collect(),
+            diameter: 8,
+            hole: 0.0,
+            ch: default_pie_char(),
+            legend: true,
+            sweep: false,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }),
+        24 => SceneObject::ScatterPlot(ScatterPlot {
+            position: Position {
+                x: Coordinate::Fixed(0.0),
+                y: Coordinate::Fixed(0.0),
+            },
+            points: vec![[1.0, 2.0], [2.0, 3.5], [3.0, 3.0], [4.0, 5.0], [5.0, 4.5]],
+            width: default_plot_width(),
+            height: default_plot_height(),
+            x_min: None,
+            x_max: None,
+            y_min: None,
+            y_max: None,
+            x_label: String::new(),
+            y_label: String::new(),
+            ticks: default_ticks(),
+            color: None,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }),
+        25 => SceneObject::BackgroundEffect(BackgroundEffect {
+            effect: Effect::Starfield,
+            seed: 0,
+            density: default_effect_density(),
+            position: Position {
+                x: Coordinate::Fixed(0.0),
+                y: Coordinate::Fixed(0.0),
+            },
+            width: 0,
+            height: 0,
+            style: Style::default(),
+            frames,
+            z_order: default_effect_z(),
+            layer: None,
+        }),
+        26 => SceneObject::Marquee(Marquee {
+            text: "Breaking: terminal slides now scroll".to_string(),
+            position: Position {
+                x: Coordinate::Fixed(0.0),
+                y: Coordinate::Fixed(0.0),
+            },
+            width: 20,
+            speed: default_marquee_speed(),
+            gap: default_marquee_gap(),
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }),
+        27 => SceneObject::Spinner(Spinner {
+            position: Position {
+                x: Coordinate::Fixed(0.0),
+                y: Coordinate::Fixed(0.0),
+            },
+            glyphs: default_spinner_glyphs(),
+            text: "Loading…".to_string(),
+            done: None,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }),
+        28 => SceneObject::Connector(Connector {
+            position: Position {
+                x: Coordinate::Fixed(0.0),
+                y: Coordinate::Fixed(0.0),
+            },
+            points: vec![[0, 0], [8, 0], [8, 3], [16, 3]],
+            line: BorderStyle::default(),
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
+        }),
+        29 => SceneObject::Keycap(Keycap {
+            position: Position {
+                x: Coordinate::Fixed(0.0),
+                y: Coordinate::Fixed(0.0),
+            },
+            keys: vec!["Ctrl".to_string(), "S".to_string()],
+            separator: default_keycap_separator(),
+            border: default_keycap_border(),
+            compact: false,
+            cap: None,
+            style: Style::default(),
+            frames,
+            z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
         }),
         _ => unreachable!(),
     }
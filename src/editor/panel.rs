@@ -3,6 +3,8 @@ use std::io;
 use crossterm::{cursor, queue, style};
 
 use crate::engine::source::SceneObject;
+use crate::validate::Severity;
+use super::config::Theme;
 use super::object_defaults;
 use super::properties::{self, PropertyKind};
 use super::state::{scene_object_summary, scene_object_type_name, ArtPick, EditorState, Mode, MultiSelectPurpose, TableCellSubState};
@@ -24,14 +26,48 @@ fn draw_color_swatch(stdout: &mut io::Stdout, x: u16, y: u16, value: &str) -> an
     Ok(())
 }
 
+/// One row of a dropdown list at `(x, y)`, `width` cells wide: a `>` marker
+/// and the selection colours on the highlighted row, and — in a colour list — a swatch
+/// of the option between the marker and its name.
+#[allow(clippy::too_many_arguments)]
+fn draw_dropdown_row(
+    stdout: &mut io::Stdout,
+    theme: &Theme,
+    x: u16,
+    y: u16,
+    width: usize,
+    opt: &str,
+    highlighted: bool,
+    swatch: bool,
+) -> anyhow::Result<()> {
+    let marker = if highlighted { ">" } else { " " };
+    let gutter = if swatch { "   " } else { "" };
+    let line: String = format!("{marker} {gutter}{opt}").chars().chain(std::iter::repeat(' ')).take(width).collect();
+    queue!(stdout, cursor::MoveTo(x, y))?;
+    if highlighted {
+        queue!(
+            stdout,
+            theme.selection(),
+            style::Print(line),
+            style::SetAttribute(style::Attribute::Reset),
+        )?;
+    } else {
+        queue!(stdout, style::Print(line))?;
+    }
+    if swatch {
+        draw_color_swatch(stdout, x + 2, y, opt)?;
+    }
+    Ok(())
+}
+
 /// Draw one already-laid-out text line at `(x, y)`, rendering exactly `width`
 /// cells (padding short lines with spaces, ignoring overflow). This is the
 /// single place every editor text field paints its caret, so the convention
 /// can't drift between widgets:
 ///
-/// * `reverse` paints the whole line in reverse video — the "this field/row is
-///   active" affordance, the same meaning reverse carries for selected list
-///   rows.
+/// * `reverse` paints the whole line in the theme's selection colours (reverse
+///   video by default) — the "this field/row is active" affordance, the same
+///   meaning it carries for selected list rows.
 /// * `caret` is the column of the **insertion point**: the gap *before* the
 ///   character at that column. It is marked with an underline, never a block or
 ///   an inserted glyph, so it reads as "the next character lands here, pushing
@@ -42,8 +78,10 @@ fn draw_color_swatch(stdout: &mut io::Stdout, x: u16, y: u16, value: &str) -> an
 /// The two attributes are orthogonal and compose, so an active field still
 /// shows its caret. `display` is expected to be pre-composed by the caller
 /// (prefixes, horizontal scroll already applied); this helper only rasterizes.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn draw_caret_line(
     stdout: &mut io::Stdout,
+    theme: &Theme,
     x: u16,
     y: u16,
     display: &str,
@@ -57,7 +95,7 @@ pub(crate) fn draw_caret_line(
         let ch = chars.get(col).copied().unwrap_or(' ');
         let is_caret = caret == Some(col);
         if reverse {
-            queue!(stdout, style::SetAttribute(style::Attribute::Reverse))?;
+            queue!(stdout, theme.selection())?;
         }
         if is_caret {
             queue!(stdout, style::SetAttribute(style::Attribute::Underlined))?;
@@ -83,12 +121,15 @@ pub fn render_right_panel(
     let pw = layout.right_panel_width;
     let cy = layout.canvas_y;
     let max_width = (pw - 3) as usize;
+    let theme = &state.config.theme;
 
     // Helper: draw the shared vertical border + title + separator
     let draw_header = |stdout: &mut io::Stdout, title: &str| -> anyhow::Result<()> {
+        queue!(stdout, theme.borders())?;
         for y in 0..layout.canvas_height {
             queue!(stdout, cursor::MoveTo(panel_x, cy + y), style::Print("\u{2502}"))?;
         }
+        queue!(stdout, style::SetAttribute(style::Attribute::Reset))?;
         queue!(
             stdout,
             cursor::MoveTo(panel_x + 2, cy),
@@ -96,10 +137,11 @@ pub fn render_right_panel(
             style::Print(title),
             style::SetAttribute(style::Attribute::Reset),
         )?;
-        queue!(stdout, cursor::MoveTo(panel_x, cy + 1), style::Print("\u{253c}"))?;
+        queue!(stdout, cursor::MoveTo(panel_x, cy + 1), theme.borders(), style::Print("\u{253c}"))?;
         for _ in 1..pw {
             queue!(stdout, style::Print("\u{2500}"))?;
         }
+        queue!(stdout, style::SetAttribute(style::Attribute::Reset))?;
         Ok(())
     };
 
@@ -120,7 +162,7 @@ pub fn render_right_panel(
             if i == selected {
                 queue!(
                     stdout,
-                    style::SetAttribute(style::Attribute::Reverse),
+                    theme.selection(),
                     style::Print(format!("> {:<width$}", label, width = max_width.saturating_sub(2))),
                     style::SetAttribute(style::Attribute::Reset),
                 )?;
@@ -156,7 +198,7 @@ pub fn render_right_panel(
             if i == selected {
                 queue!(
                     stdout,
-                    style::SetAttribute(style::Attribute::Reverse),
+                    theme.selection(),
                     style::Print(format!("> {:<width$}", text, width = max_width.saturating_sub(2))),
                     style::SetAttribute(style::Attribute::Reset),
                 )?;
@@ -177,18 +219,18 @@ pub fn render_right_panel(
         if cy + 2 < cy + layout.canvas_height {
             let instr: String = "Path to art file:".chars().take(max_width).collect();
             queue!(stdout, cursor::MoveTo(panel_x + 2, cy + 2),
-                style::SetAttribute(style::Attribute::Dim),
+                theme.dimmed(),
                 style::Print(instr),
                 style::SetAttribute(style::Attribute::Reset))?;
         }
         if cy + 3 < cy + layout.canvas_height {
             let caret = cursor.min(buf.chars().count());
-            draw_caret_line(stdout, panel_x + 2, cy + 3, buf, Some(caret), true, max_width)?;
+            draw_caret_line(stdout, theme, panel_x + 2, cy + 3, buf, Some(caret), true, max_width)?;
         }
         if cy + 5 < cy + layout.canvas_height {
             let hint: String = "Enter = load   Esc = back".chars().take(max_width).collect();
             queue!(stdout, cursor::MoveTo(panel_x + 2, cy + 5),
-                style::SetAttribute(style::Attribute::Dim),
+                theme.dimmed(),
                 style::Print(hint),
                 style::SetAttribute(style::Attribute::Reset))?;
         }
@@ -196,12 +238,12 @@ pub fn render_right_panel(
     }
 
     // === Settings (frame size) ===
-    if let Mode::Settings { selected_field, width_buf, height_buf, cursor } = &state.mode {
+    if let Mode::Settings { selected_field, width_buf, height_buf, fit, cursor } = &state.mode {
         draw_header(stdout, "Frame Size")?;
         if cy + 2 < cy + layout.canvas_height {
             let instr: String = "Output size (cells):".chars().take(max_width).collect();
             queue!(stdout, cursor::MoveTo(panel_x + 2, cy + 2),
-                style::SetAttribute(style::Attribute::Dim),
+                theme.dimmed(),
                 style::Print(instr),
                 style::SetAttribute(style::Attribute::Reset))?;
         }
@@ -222,23 +264,76 @@ pub fn render_right_panel(
                 // Active field: underline caret marks where the next digit lands.
                 let cur = (*cursor).min(buf.chars().count());
                 let field_w = max_width.saturating_sub(prefix.chars().count()).max(1);
-                draw_caret_line(stdout, vx, y, buf, Some(cur), false, field_w)?;
+                draw_caret_line(stdout, theme, vx, y, buf, Some(cur), false, field_w)?;
             } else {
                 let val: String = buf.chars().take(max_width).collect();
                 queue!(stdout, cursor::MoveTo(vx, y), style::Print(val))?;
             }
         }
 
+        // How the content follows the new size.
+        if cy + 6 < cy + layout.canvas_height {
+            let marker = if *selected_field == 2 { "\u{203a} " } else { "  " };
+            let text: String = format!("{marker}{:>6}: \u{2039} {} \u{203a}", "fit", fit.label())
+                .chars().take(max_width).collect();
+            queue!(stdout, cursor::MoveTo(panel_x + 2, cy + 6))?;
+            if *selected_field == 2 {
+                queue!(stdout, theme.selection())?;
+            }
+            queue!(stdout, style::Print(text), style::SetAttribute(style::Attribute::Reset))?;
+        }
+
+        // The way into the key-binding editor.
         if cy + 7 < cy + layout.canvas_height {
+            let marker = if *selected_field == 3 { "\u{203a} " } else { "  " };
+            let text: String = format!("{marker}key bindings \u{25b8}").chars().take(max_width).collect();
+            queue!(stdout, cursor::MoveTo(panel_x + 2, cy + 7))?;
+            if *selected_field == 3 {
+                queue!(stdout, theme.selection())?;
+            }
+            queue!(stdout, style::Print(text), style::SetAttribute(style::Attribute::Reset))?;
+        }
+
+        if cy + 9 < cy + layout.canvas_height {
             let hint: String = "Enter = apply   Esc = cancel".chars().take(max_width).collect();
-            queue!(stdout, cursor::MoveTo(panel_x + 2, cy + 7),
-                style::SetAttribute(style::Attribute::Dim),
+            queue!(stdout, cursor::MoveTo(panel_x + 2, cy + 9),
+                theme.dimmed(),
                 style::Print(hint),
                 style::SetAttribute(style::Attribute::Reset))?;
         }
         return Ok(());
     }
 
+    // === KeyBindings (rebind the editor's keys) ===
+    if let Mode::KeyBindings { selected, capturing } = &state.mode {
+        draw_header(stdout, "Key Bindings")?;
+        let bindings = &state.config.key_bindings;
+        let entries = bindings.entries();
+        // Keep the highlighted binding in view, as the diagnostics list does.
+        let rows = layout.canvas_height.saturating_sub(2) as usize;
+        let first = selected.saturating_sub(rows.saturating_sub(1));
+        for (i, (name, chord)) in entries.iter().enumerate().skip(first).take(rows) {
+            let y = cy + (i - first + 2) as u16;
+            let chord = if *capturing && i == *selected { "…" } else { chord.as_str() };
+            // `!` marks a binding that clashes with another in a shared context.
+            let mark = if bindings.clashes(name).is_empty() { ' ' } else { '!' };
+            let name_w = max_width.saturating_sub(chord.chars().count() + 2);
+            let label: String = format!("{mark}{name:<name_w$.name_w$} {chord}").chars().take(max_width).collect();
+            queue!(stdout, cursor::MoveTo(panel_x + 2, y))?;
+            if i == *selected {
+                queue!(
+                    stdout,
+                    theme.selection(),
+                    style::Print(format!("{:<width$}", label, width = max_width)),
+                    style::SetAttribute(style::Attribute::Reset),
+                )?;
+            } else {
+                queue!(stdout, style::Print(format!("{:<width$}", label, width = max_width)))?;
+            }
+        }
+        return Ok(());
+    }
+
     // === SelectAction (act on a multi-object selection) ===
     if let Mode::SelectAction { members, selected } = &state.mode {
         let selected = *selected;
@@ -254,7 +349,7 @@ pub fn render_right_panel(
             if i == selected {
                 queue!(
                     stdout,
-                    style::SetAttribute(style::Attribute::Reverse),
+                    theme.selection(),
                     style::Print(format!("{:<width$}", label, width = max_width)),
                     style::SetAttribute(style::Attribute::Reset),
                 )?;
@@ -286,7 +381,7 @@ pub fn render_right_panel(
             if i == selected {
                 queue!(
                     stdout,
-                    style::SetAttribute(style::Attribute::Reverse),
+                    theme.selection(),
                     style::Print(format!("> {:<width$}", label, width = max_width.saturating_sub(2))),
                     style::SetAttribute(style::Attribute::Reset),
                 )?;
@@ -300,6 +395,69 @@ pub fn render_right_panel(
         return Ok(());
     }
 
+    // === Layers (visibility / lock per layer) ===
+    if let Mode::Layers { selected } = &state.mode {
+        draw_header(stdout, "Layers")?;
+        let names = state.source.layer_names();
+        for (i, name) in names.iter().enumerate() {
+            let y = cy + (i + 2) as u16;
+            if y >= cy + layout.canvas_height {
+                break;
+            }
+            let entry = state.source.layers.iter().find(|l| &l.name == name);
+            let visible = entry.is_none_or(|l| l.visible);
+            let locked = entry.is_some_and(|l| l.locked);
+            let label: String = format!(
+                "{} {} {name}",
+                if visible { "[v]" } else { "[ ]" },
+                if locked { "[l]" } else { "[ ]" },
+            )
+            .chars()
+            .take(max_width)
+            .collect();
+            queue!(stdout, cursor::MoveTo(panel_x + 2, y))?;
+            if i == *selected {
+                queue!(
+                    stdout,
+                    theme.selection(),
+                    style::Print(format!("{:<width$}", label, width = max_width)),
+                    style::SetAttribute(style::Attribute::Reset),
+                )?;
+            } else {
+                queue!(stdout, style::Print(format!("{:<width$}", label, width = max_width)))?;
+            }
+        }
+        return Ok(());
+    }
+
+    // === Diagnostics (validation and lint findings) ===
+    if let Mode::Diagnostics { selected, items } = &state.mode {
+        draw_header(stdout, &format!("Diagnostics ({})", items.len()))?;
+        // Keep the highlighted finding in view when the list outgrows the panel.
+        let rows = layout.canvas_height.saturating_sub(2) as usize;
+        let first = selected.saturating_sub(rows.saturating_sub(1));
+        for (i, d) in items.iter().enumerate().skip(first).take(rows) {
+            let y = cy + (i - first + 2) as u16;
+            let mark = match d.severity {
+                Severity::Error => "E",
+                Severity::Warning => "W",
+            };
+            let label: String = format!("{mark} {}", d.message).chars().take(max_width).collect();
+            queue!(stdout, cursor::MoveTo(panel_x + 2, y))?;
+            if i == *selected {
+                queue!(
+                    stdout,
+                    theme.selection(),
+                    style::Print(format!("{:<width$}", label, width = max_width)),
+                    style::SetAttribute(style::Attribute::Reset),
+                )?;
+            } else {
+                queue!(stdout, style::Print(format!("{:<width$}", label, width = max_width)))?;
+            }
+        }
+        return Ok(());
+    }
+
     // === OpenFile (path prompt for opening another deck) ===
     if let Mode::OpenFile { buf, cursor } = &state.mode {
         let cursor = *cursor;
@@ -307,18 +465,18 @@ pub fn render_right_panel(
         if cy + 2 < cy + layout.canvas_height {
             let instr: String = "Path to .json:".chars().take(max_width).collect();
             queue!(stdout, cursor::MoveTo(panel_x + 2, cy + 2),
-                style::SetAttribute(style::Attribute::Dim),
+                theme.dimmed(),
                 style::Print(instr),
                 style::SetAttribute(style::Attribute::Reset))?;
         }
         if cy + 3 < cy + layout.canvas_height {
             let caret = cursor.min(buf.chars().count());
-            draw_caret_line(stdout, panel_x + 2, cy + 3, buf, Some(caret), true, max_width)?;
+            draw_caret_line(stdout, theme, panel_x + 2, cy + 3, buf, Some(caret), true, max_width)?;
         }
         if cy + 5 < cy + layout.canvas_height {
             let hint: String = "Enter = open   Esc = back".chars().take(max_width).collect();
             queue!(stdout, cursor::MoveTo(panel_x + 2, cy + 5),
-                style::SetAttribute(style::Attribute::Dim),
+                theme.dimmed(),
                 style::Print(hint),
                 style::SetAttribute(style::Attribute::Reset))?;
         }
@@ -376,7 +534,7 @@ pub fn render_right_panel(
                         .collect();
                     let display = format!("{prefix}{display_line}");
                     let caret = Some(prefix_len + cursor.saturating_sub(scroll));
-                    draw_caret_line(stdout, panel_x + 2, screen_y, &display, caret, true, max_width)?;
+                    draw_caret_line(stdout, theme, panel_x + 2, screen_y, &display, caret, true, max_width)?;
                     selected_screen_y = Some(screen_y);
                     screen_y += 1;
                     visual_row += 1;
@@ -385,20 +543,23 @@ pub fn render_right_panel(
             }
 
             // Single-row display path (selected-not-editing, or any other row).
+            // Values the members disagree on read `(mixed)` / `[~]`.
             queue!(stdout, cursor::MoveTo(panel_x + 2, screen_y))?;
+            let mixed = properties::is_mixed(&state.source.objects, members, prop.name);
+            let value = if mixed { "(mixed)".to_string() } else { fmt_val(&prop.value) };
             let display: String = if prop.kind == PropertyKind::Bool {
-                let mark = if prop.value.trim() == "true" { "x" } else { " " };
+                let mark = if mixed { "~" } else if prop.value.trim() == "true" { "x" } else { " " };
                 format!("[{}] {}", mark, prop.name).chars().take(max_width).collect()
             } else if i == selected_prop && dropdown.is_some() {
-                format!("{}: \u{25bc} {}", prop.name, fmt_val(&prop.value)).chars().take(max_width).collect()
+                format!("{}: \u{25bc} {}", prop.name, value).chars().take(max_width).collect()
             } else {
-                format!("{}: {}", prop.name, fmt_val(&prop.value)).chars().take(max_width).collect()
+                format!("{}: {}", prop.name, value).chars().take(max_width).collect()
             };
 
             if i == selected_prop {
                 queue!(
                     stdout,
-                    style::SetAttribute(style::Attribute::Reverse),
+                    theme.selection(),
                     style::Print(format!("{:<width$}", display, width = max_width)),
                     style::SetAttribute(style::Attribute::Reset),
                 )?;
@@ -420,8 +581,8 @@ pub fn render_right_panel(
 
         // Dropdown overlay (colour / alignment / …).
         if let Some(dd_sel) = dropdown {
-            let options = properties::dropdown_options_for(&props[selected_prop].kind)
-                .unwrap_or(properties::COLOR_OPTIONS);
+            let kind = &props[selected_prop].kind;
+            let options = properties::dropdown_options_for(kind).unwrap_or(properties::COLOR_OPTIONS);
             let dd_start_y = selected_screen_y
                 .map(|y| y + 1)
                 .unwrap_or(cy + (selected_prop + 3) as u16);
@@ -430,21 +591,7 @@ pub fn render_right_panel(
                 if y >= cy + layout.canvas_height {
                     break;
                 }
-                queue!(stdout, cursor::MoveTo(panel_x + 2, y))?;
-                let marker = if i == dd_sel { ">" } else { " " };
-                let line: String = format!("{} {}", marker, opt)
-                    .chars().chain(std::iter::repeat(' ')).take(max_width).collect();
-                if i == dd_sel {
-                    queue!(
-                        stdout,
-                        style::SetAttribute(style::Attribute::Reverse),
-                        style::Print(line),
-                        style::SetAttribute(style::Attribute::Reset),
-                    )?;
-                } else {
-                    queue!(stdout, style::Print(line))?;
-                }
-                draw_color_swatch(stdout, panel_x + 2 + (max_width as u16).saturating_sub(2), y, opt)?;
+                draw_dropdown_row(stdout, theme, panel_x + 2, y, max_width, opt, i == dd_sel, *kind == PropertyKind::Color)?;
             }
         }
 
@@ -462,7 +609,7 @@ pub fn render_right_panel(
             queue!(
                 stdout,
                 cursor::MoveTo(panel_x + 2, cy + 2),
-                style::SetAttribute(style::Attribute::Dim),
+                theme.dimmed(),
                 style::Print(msg),
                 style::SetAttribute(style::Attribute::Reset),
             )?;
@@ -478,7 +625,7 @@ pub fn render_right_panel(
             if i == selected {
                 queue!(
                     stdout,
-                    style::SetAttribute(style::Attribute::Reverse),
+                    theme.selection(),
                     style::Print(format!("{:<width$}", label, width = max_width)),
                     style::SetAttribute(style::Attribute::Reset),
                 )?;
@@ -519,7 +666,7 @@ pub fn render_right_panel(
             if vi == selected {
                 queue!(
                     stdout,
-                    style::SetAttribute(style::Attribute::Reverse),
+                    theme.selection(),
                     style::Print(format!("{:<width$}", text, width = max_width)),
                     style::SetAttribute(style::Attribute::Reset),
                 )?;
@@ -578,7 +725,7 @@ pub fn render_right_panel(
             };
 
             if i == selected_field {
-                draw_caret_line(stdout, panel_x + 2, y, &display, caret, true, max_width)?;
+                draw_caret_line(stdout, theme, panel_x + 2, y, &display, caret, true, max_width)?;
             } else {
                 queue!(stdout, cursor::MoveTo(panel_x + 2, y), style::Print(display))?;
             }
@@ -594,7 +741,7 @@ pub fn render_right_panel(
                 .collect();
             queue!(
                 stdout,
-                style::SetAttribute(style::Attribute::Dim),
+                theme.dimmed(),
                 style::Print(hint),
                 style::SetAttribute(style::Attribute::Reset),
             )?;
@@ -642,7 +789,7 @@ pub fn render_right_panel(
             };
 
             if i == selected_field {
-                draw_caret_line(stdout, panel_x + 2, y, &display, caret, true, max_width)?;
+                draw_caret_line(stdout, theme, panel_x + 2, y, &display, caret, true, max_width)?;
             } else {
                 queue!(stdout, cursor::MoveTo(panel_x + 2, y), style::Print(display))?;
             }
@@ -657,7 +804,7 @@ pub fn render_right_panel(
                 .collect();
             queue!(
                 stdout,
-                style::SetAttribute(style::Attribute::Dim),
+                theme.dimmed(),
                 style::Print(hint),
                 style::SetAttribute(style::Attribute::Reset),
             )?;
@@ -683,14 +830,14 @@ pub fn render_right_panel(
             let instr = format!("Col {} (1–{}):", direction, ncols);
             let instr: String = instr.chars().take(max_width).collect();
             queue!(stdout, cursor::MoveTo(panel_x + 2, cy + 2),
-                style::SetAttribute(style::Attribute::Dim),
+                theme.dimmed(),
                 style::Print(instr),
                 style::SetAttribute(style::Attribute::Reset))?;
         }
         // Value field
         if cy + 3 < cy + layout.canvas_height {
             let caret = cursor.min(buf.chars().count());
-            draw_caret_line(stdout, panel_x + 2, cy + 3, buf, Some(caret), true, max_width)?;
+            draw_caret_line(stdout, theme, panel_x + 2, cy + 3, buf, Some(caret), true, max_width)?;
         }
         // Column list
         if let Some(SceneObject::Table(t)) = state.source.objects.get(object_index) {
@@ -720,13 +867,13 @@ pub fn render_right_panel(
             let instr = format!("Column (1–{}):", ncols);
             let instr: String = instr.chars().take(max_width).collect();
             queue!(stdout, cursor::MoveTo(panel_x + 2, cy + 2),
-                style::SetAttribute(style::Attribute::Dim),
+                theme.dimmed(),
                 style::Print(instr),
                 style::SetAttribute(style::Attribute::Reset))?;
         }
         if cy + 3 < cy + layout.canvas_height {
             let caret = cursor.min(buf.chars().count());
-            draw_caret_line(stdout, panel_x + 2, cy + 3, buf, Some(caret), true, max_width)?;
+            draw_caret_line(stdout, theme, panel_x + 2, cy + 3, buf, Some(caret), true, max_width)?;
         }
         if let Some(SceneObject::Table(t)) = state.source.objects.get(object_index) {
             for (i, w) in t.col_widths.iter().enumerate() {
@@ -739,7 +886,7 @@ pub fn render_right_panel(
                 queue!(stdout, cursor::MoveTo(panel_x + 2, y))?;
                 if highlighted {
                     queue!(stdout,
-                        style::SetAttribute(style::Attribute::Reverse),
+                        theme.selection(),
                         style::Print(format!("{:<width$}", line, width = max_width)),
                         style::SetAttribute(style::Attribute::Reset))?;
                 } else {
@@ -769,7 +916,7 @@ pub fn render_right_panel(
                         format!("{} selected", selected_cells.len())
                     };
                     queue!(stdout, cursor::MoveTo(panel_x + 2, cy + 3),
-                        style::SetAttribute(style::Attribute::Dim),
+                        theme.dimmed(),
                         style::Print(sel_str.chars().take(max_width).collect::<String>()),
                         style::SetAttribute(style::Attribute::Reset))?;
                 }
@@ -786,7 +933,7 @@ pub fn render_right_panel(
                     let y = cy + (i + 4) as u16;
                     if y >= cy + layout.canvas_height { break; }
                     queue!(stdout, cursor::MoveTo(panel_x + 2, y),
-                        style::SetAttribute(style::Attribute::Dim),
+                        theme.dimmed(),
                         style::Print(hint.chars().take(max_width).collect::<String>()),
                         style::SetAttribute(style::Attribute::Reset))?;
                 }
@@ -798,7 +945,7 @@ pub fn render_right_panel(
                     let hint: String = "Shift+Enter=newline, Enter=save"
                         .chars().take(max_width).collect();
                     queue!(stdout, cursor::MoveTo(panel_x + 2, cy + 2),
-                        style::SetAttribute(style::Attribute::Dim),
+                        theme.dimmed(),
                         style::Print(hint),
                         style::SetAttribute(style::Attribute::Reset))?;
                 }
@@ -807,8 +954,7 @@ pub fn render_right_panel(
                 // plus the append slot just past its end / the newline boundary).
                 let cursor_pos = (*cursor).min(buf.chars().count());
                 let mut base = 0usize;
-                let mut screen_y = cy + 3u16;
-                for line in buf.split('\n') {
+                for (screen_y, line) in (cy + 3u16..).zip(buf.split('\n')) {
                     if screen_y >= cy + layout.canvas_height { break; }
                     let line_len = line.chars().count();
                     let caret = if cursor_pos >= base && cursor_pos <= base + line_len {
@@ -816,9 +962,8 @@ pub fn render_right_panel(
                     } else {
                         None
                     };
-                    draw_caret_line(stdout, panel_x + 2, screen_y, line, caret, false, max_width)?;
+                    draw_caret_line(stdout, theme, panel_x + 2, screen_y, line, caret, false, max_width)?;
                     base += line_len + 1; // +1 for the newline
-                    screen_y += 1;
                 }
             }
             TableCellSubState::EditingStyle { selected_prop, editing_value, cursor, dropdown } => {
@@ -882,7 +1027,7 @@ pub fn render_right_panel(
                     };
                     let display: String = val.chars().take(max_width).collect();
                     if i == selected_prop {
-                        draw_caret_line(stdout, panel_x + 2, y, &display, caret, true, max_width)?;
+                        draw_caret_line(stdout, theme, panel_x + 2, y, &display, caret, true, max_width)?;
                         sel_screen_y = Some(y);
                     } else {
                         queue!(stdout, style::Print(display))?;
@@ -903,19 +1048,7 @@ pub fn render_right_panel(
                     for (i, opt) in opts.iter().enumerate() {
                         let y = dd_start + i as u16;
                         if y >= cy + layout.canvas_height { break; }
-                        queue!(stdout, cursor::MoveTo(panel_x + 2, y))?;
-                        let marker = if i == *dd_sel { ">" } else { " " };
-                        let line: String = format!("{} {}", marker, opt).chars()
-                            .chain(std::iter::repeat(' ')).take(max_width).collect();
-                        if i == *dd_sel {
-                            queue!(stdout,
-                                style::SetAttribute(style::Attribute::Reverse),
-                                style::Print(line),
-                                style::SetAttribute(style::Attribute::Reset))?;
-                        } else {
-                            queue!(stdout, style::Print(line))?;
-                        }
-                        draw_color_swatch(stdout, panel_x + 2 + (max_width as u16).saturating_sub(2), y, opt)?;
+                        draw_dropdown_row(stdout, theme, panel_x + 2, y, max_width, opt, i == *dd_sel, true)?;
                     }
                 }
             }
@@ -1002,7 +1135,7 @@ pub fn render_right_panel(
                         let display = format!("{prefix}{display_line}");
                         let caret = (line_idx == cursor_line_idx)
                             .then(|| prefix_len + cursor_col_in_line.saturating_sub(line_scroll));
-                        draw_caret_line(stdout, panel_x + 2, screen_y, &display, caret, true, max_width)?;
+                        draw_caret_line(stdout, theme, panel_x + 2, screen_y, &display, caret, true, max_width)?;
 
                         if line_idx == 0 { selected_screen_y = Some(screen_y); }
                         screen_y += 1;
@@ -1045,8 +1178,8 @@ pub fn render_right_panel(
                     let display: String = fmt_prop_display(prop);
                     queue!(
                         stdout,
-                        style::SetAttribute(style::Attribute::Reverse),
-                        style::SetAttribute(style::Attribute::Dim),
+                        theme.selection(),
+                        theme.dimmed(),
                         style::Print(format!("{:<width$}", display, width = max_width)),
                         style::SetAttribute(style::Attribute::Reset),
                     )?;
@@ -1059,7 +1192,7 @@ pub fn render_right_panel(
                     };
                     queue!(
                         stdout,
-                        style::SetAttribute(style::Attribute::Reverse),
+                        theme.selection(),
                         style::Print(format!("{:<width$}", display, width = max_width)),
                         style::SetAttribute(style::Attribute::Reset),
                     )?;
@@ -1070,7 +1203,7 @@ pub fn render_right_panel(
                 let display = fmt_prop_display(prop);
                 queue!(
                     stdout,
-                    style::SetAttribute(style::Attribute::Dim),
+                    theme.dimmed(),
                     style::Print(display),
                     style::SetAttribute(style::Attribute::Reset),
                 )?;
@@ -1104,24 +1237,7 @@ pub fn render_right_panel(
             if y >= cy + layout.canvas_height {
                 break;
             }
-            queue!(stdout, cursor::MoveTo(panel_x + 2, y))?;
-            let marker = if i == dd_sel { ">" } else { " " };
-            let line: String = format!("{} {}", marker, opt)
-                .chars()
-                .chain(std::iter::repeat(' '))
-                .take(max_width)
-                .collect();
-            if i == dd_sel {
-                queue!(
-                    stdout,
-                    style::SetAttribute(style::Attribute::Reverse),
-                    style::Print(line),
-                    style::SetAttribute(style::Attribute::Reset),
-                )?;
-            } else {
-                queue!(stdout, style::Print(line))?;
-            }
-            draw_color_swatch(stdout, panel_x + 2 + (max_width as u16).saturating_sub(2), y, opt)?;
+            draw_dropdown_row(stdout, theme, panel_x + 2, y, max_width, opt, i == dd_sel, *prop_kind == PropertyKind::Color)?;
         }
     }
 
@@ -1180,12 +1296,12 @@ pub fn render_text_overlay(
 
     let top: String = std::iter::once('\u{250c}')
         .chain(title.chars())
-        .chain(std::iter::repeat('\u{2500}').take(inner_w.saturating_sub(title.chars().count())))
+        .chain(std::iter::repeat_n('\u{2500}', inner_w.saturating_sub(title.chars().count())))
         .chain(std::iter::once('\u{2510}'))
         .collect();
     let bottom: String = std::iter::once('\u{2514}')
         .chain(hint.chars())
-        .chain(std::iter::repeat('\u{2500}').take(inner_w.saturating_sub(hint.chars().count())))
+        .chain(std::iter::repeat_n('\u{2500}', inner_w.saturating_sub(hint.chars().count())))
         .chain(std::iter::once('\u{2518}'))
         .collect();
 
@@ -1201,7 +1317,7 @@ pub fn render_text_overlay(
             (0..inner_w).map(|col| chars.get(line_h_off + col).copied().unwrap_or(' ')).collect();
         let caret = (li == cur_line).then(|| cur_col - line_h_off);
         queue!(stdout, cursor::MoveTo(bx, y), style::Print("\u{2502}"))?;
-        draw_caret_line(stdout, bx + 1, y, &window, caret, false, inner_w)?;
+        draw_caret_line(stdout, &state.config.theme, bx + 1, y, &window, caret, false, inner_w)?;
         queue!(stdout, cursor::MoveTo(bx + 1 + inner_w as u16, y), style::Print("\u{2502}"))?;
     }
 
@@ -1236,18 +1352,18 @@ pub fn render_save_as_overlay(
     let hint: String = " Enter: save · Esc: cancel ".chars().take(inner_w).collect();
     let top: String = std::iter::once('\u{250c}')
         .chain(title.chars())
-        .chain(std::iter::repeat('\u{2500}').take(inner_w.saturating_sub(title.chars().count())))
+        .chain(std::iter::repeat_n('\u{2500}', inner_w.saturating_sub(title.chars().count())))
         .chain(std::iter::once('\u{2510}'))
         .collect();
     let bottom: String = std::iter::once('\u{2514}')
         .chain(hint.chars())
-        .chain(std::iter::repeat('\u{2500}').take(inner_w.saturating_sub(hint.chars().count())))
+        .chain(std::iter::repeat_n('\u{2500}', inner_w.saturating_sub(hint.chars().count())))
         .chain(std::iter::once('\u{2518}'))
         .collect();
 
     queue!(stdout, cursor::MoveTo(bx, by), style::Print(top))?;
     queue!(stdout, cursor::MoveTo(bx, by + 1), style::Print("\u{2502}"))?;
-    draw_caret_line(stdout, bx + 1, by + 1, &window, Some(caret), false, inner_w)?;
+    draw_caret_line(stdout, &state.config.theme, bx + 1, by + 1, &window, Some(caret), false, inner_w)?;
     queue!(stdout, cursor::MoveTo(bx + 1 + inner_w as u16, by + 1), style::Print("\u{2502}"))?;
     queue!(stdout, cursor::MoveTo(bx, by + 2), style::Print(bottom))?;
 
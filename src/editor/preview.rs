@@ -1,13 +1,14 @@
 use std::io;
 
+use crossterm::style::Stylize;
 use crossterm::{cursor, queue, style};
 
-use crate::engine::objects::{Resolve, ResolveCtx};
+use crate::engine::objects::{Label, Resolve, ResolveCtx};
 use crate::engine::Engine;
-use crate::engine::source::{AnimSpans, SceneObject};
+use crate::engine::source::{AnimSpans, SceneObject, SourcePresentation};
 use crate::player::to_content_style;
 use crate::renderer::Renderer;
-use crate::types::{Color, NamedColor, ResolvedScene, Style, TerminalContract};
+use crate::types::{Color, DrawOp, NamedColor, ResolvedScene, Style};
 
 use super::state::{EditorState, Mode, TableCellSubState};
 use super::ui::Layout;
@@ -19,7 +20,9 @@ fn focus_indices(state: &EditorState) -> Option<Vec<usize>> {
     match &state.mode {
         Mode::SelectedObject { object_index }
         | Mode::ResizeObject { object_index }
-        | Mode::EditProperties { object_index, .. } => {
+        | Mode::EditProperties { object_index, .. }
+        | Mode::EditInPlace { object_index, .. }
+        | Mode::PickCoordinate { object_index, .. } => {
             // When a Group is selected, highlight its members instead.
             match state.source.objects.get(*object_index) {
                 Some(SceneObject::Group(g)) if !g.members.is_empty() => {
@@ -30,6 +33,7 @@ fn focus_indices(state: &EditorState) -> Option<Vec<usize>> {
             }
         }
         Mode::AnimateProperty { object_index, .. } => Some(vec![*object_index]),
+        Mode::ReplaceConfirm { at, .. } => Some(vec![at.object_index]),
         Mode::ConvergeConfig { members, .. } => Some(members.clone()),
         Mode::MultiSelect { selected, .. } => {
             // Highlight the object under the cursor (a Group expands to its
@@ -67,6 +71,8 @@ const fn dim_style() -> Style {
         bg: None,
         bold: false,
         dim: true,
+        underline: false,
+        link: None,
     }
 }
 
@@ -77,9 +83,18 @@ const fn selected_style() -> Style {
         bg: None,
         bold: false,
         dim: false,
+        underline: false,
+        link: None,
     }
 }
 
+/// Screen cell where the presentation's top-left is painted: inset by one
+/// inside the dim outline when a `pres_w` × `pres_h` deck leaves room for it.
+pub fn content_origin(layout: &Layout, pres_w: u16, pres_h: u16) -> (u16, u16) {
+    let (cx, cy) = (layout.canvas_x, layout.canvas_y);
+    if pres_w + 2 <= layout.canvas_width && pres_h + 2 <= layout.canvas_height { (cx + 1, cy + 1) } else { (cx, cy) }
+}
+
 /// Render the current frame using the production Engine + Renderer pipeline,
 /// positioned within the canvas area of the editor layout.
 pub fn render_canvas_production(
@@ -87,6 +102,11 @@ pub fn render_canvas_production(
     layout: &Layout,
     state: &EditorState,
 ) -> anyhow::Result<()> {
+    // While an animation is being configured, preview (and scrub) its pending
+    // values rather than the saved ones.
+    let pending = super::input::animation_preview(state);
+    let source = pending.as_ref().unwrap_or(&state.source);
+
     // Clear canvas area
     for y in layout.canvas_y..layout.canvas_y + layout.canvas_height {
         queue!(stdout, cursor::MoveTo(layout.canvas_x, y))?;
@@ -99,15 +119,15 @@ pub fn render_canvas_production(
     // in the ring just outside the content, so the presentation is inset by one
     // cell — otherwise the painted cells below would cover the border. When the
     // outline wouldn't fit in the canvas, skip it and paint at the origin.
-    let pres_w = state.source.width;
-    let pres_h = state.source.height;
+    let pres_w = source.width;
+    let pres_h = source.height;
     let cx = layout.canvas_x;
     let cy = layout.canvas_y;
 
     let draw_border =
         pres_w + 2 <= layout.canvas_width && pres_h + 2 <= layout.canvas_height;
     // Origin where presentation content is painted (inset when framed).
-    let (ox, oy) = if draw_border { (cx + 1, cy + 1) } else { (cx, cy) };
+    let (ox, oy) = content_origin(layout, pres_w, pres_h);
 
     if draw_border {
         let left = cx;
@@ -115,7 +135,8 @@ pub fn render_canvas_production(
         let top = cy;
         let bottom = cy + pres_h + 1;
 
-        queue!(stdout, style::SetAttribute(style::Attribute::Dim))?;
+        let theme = &state.config.theme;
+        queue!(stdout, if theme.border.is_some() { theme.borders() } else { theme.dimmed() })?;
         // Top/bottom edges
         for x in left..=right {
             queue!(stdout, cursor::MoveTo(x, top), style::Print("\u{2500}"))?;
@@ -141,156 +162,25 @@ pub fn render_canvas_production(
         queue!(stdout, style::SetAttribute(style::Attribute::Reset))?;
     }
 
-    // Determine table-specific overlay parameters.
-    let table_cell_overlay = match &state.mode {
-        Mode::TableEditCellProps { object_index, cursor_row, cursor_col, selected_cells, sub_state } => {
-            // During navigation (Selecting) the cursor borders blink; in all other
-            // sub-states (editing content, editing style) they are shown steadily.
-            let cursor = if state.blink_hidden && matches!(sub_state, TableCellSubState::Selecting) {
-                None
-            } else {
-                Some((*cursor_row, *cursor_col))
-            };
-            Some((*object_index, None::<usize>, selected_cells.clone(), cursor))
-        }
-        Mode::TableRemoveColumn { object_index, col_num, .. } => {
-            let col_idx = col_num.saturating_sub(1);
-            Some((*object_index, Some(col_idx), vec![], None))
-        }
-        _ => None,
+    // Resolve just the frame on show, reusing the scenes an edit left alone.
+    let view = View::of(state);
+    let scene = {
+        let mut cache = state.scene_cache.borrow_mut();
+        cache.sync(source, &view);
+        let frame = state.current_frame.min(source.frame_count.saturating_sub(1));
+        cache.scene(frame, || resolve_frame(source, frame, &view)).cloned()
     };
+    let presentation = Renderer::render(scene.as_slice(), source.contract());
 
-    // Compile and rasterize — dim non-focused objects when a focus set is active.
-    let is_select_mode = matches!(state.mode, Mode::MultiSelect { .. });
-    let scenes = if let Some(focused) = focus_indices(state) {
-        // For a single focused object (non-group) we boost its z_order above others.
-        let single_focus = if focused.len() == 1 { Some(focused[0]) } else { None };
-        // Single source of truth for animation timing, threaded into resolve.
-        let anims = AnimSpans::of(&state.source);
-
-        (0..state.source.frame_count)
-            .map(|frame| {
-                let mut ops = Vec::new();
-                let ctx = ResolveCtx { frame, canvas_width: state.source.width, anims: &anims };
-                let mut single_start = 0;
-                let mut single_end = 0;
-                for (i, obj) in state.source.objects.iter().enumerate() {
-                    let before = ops.len();
-                    // For table objects with editor overlay, use the specialized resolve.
-                    if let Some((tbl_idx, highlighted_col, ref sel_cells, cursor_cell)) = table_cell_overlay {
-                        if i == tbl_idx {
-                            if let SceneObject::Table(t) = obj {
-                                // When actively editing a cell, apply the current edit buffer
-                                // so the typed text is visible live in the canvas (WYSIWYG),
-                                // and pass the caret so the cell renders a block cursor.
-                                let editing_buf = match &state.mode {
-                                    Mode::TableEditCellProps {
-                                        sub_state: TableCellSubState::EditingContent { row, col, buf, cursor },
-                                        ..
-                                    } => Some((*row, *col, buf.clone(), *cursor)),
-                                    _ => None,
-                                };
-                                if let Some((er, ec, ref buf, caret)) = editing_buf {
-                                    let mut t_clone = t.clone();
-                                    t_clone.normalize_cells();
-                                    if let Some(row_vec) = t_clone.cells.get_mut(er) {
-                                        if let Some(cell) = row_vec.get_mut(ec) {
-                                            cell.content = buf.clone();
-                                        }
-                                    }
-                                    t_clone.resolve_with_editor_overlay(
-                                        frame,
-                                        &anims,
-                                        highlighted_col,
-                                        sel_cells,
-                                        cursor_cell,
-                                        state.blink_hidden,
-                                        Some((er, ec, caret)),
-                                        &mut ops,
-                                    );
-                                } else {
-                                    t.resolve_with_editor_overlay(
-                                        frame,
-                                        &anims,
-                                        highlighted_col,
-                                        sel_cells,
-                                        cursor_cell,
-                                        state.blink_hidden,
-                                        None,
-                                        &mut ops,
-                                    );
-                                }
-                            } else {
-                                obj.resolve(&ctx, &mut ops);
-                            }
-                        } else {
-                            obj.resolve(&ctx, &mut ops);
-                        }
-                    } else {
-                        obj.resolve(&ctx, &mut ops);
-                    }
+    // Build the cell grid through `grid_at`, the one implementation shared
+    // with the player and the test harness, so the WYSIWYG preview can never
+    // disagree with playback.
+    let grid = presentation.grid_at(0);
 
-                    if focused.contains(&i) {
-                        if Some(i) == single_focus {
-                            single_start = before;
-                            single_end = ops.len();
-                        }
-                        if is_select_mode {
-                            let s = if state.blink_hidden { dim_style() } else { selected_style() };
-                            for op in &mut ops[before..] {
-                                op.style = s.clone();
-                            }
-                        }
-                        // For table overlay modes: do NOT override styles (already set by resolve_with_editor_overlay)
-                        // else: keep original style for focused objects
-                    } else {
-                        let ds = dim_style();
-                        for op in &mut ops[before..] {
-                            op.style = ds.clone();
-                        }
-                    }
-                }
-                // Boost single focused object's z_order above all others
-                if single_start < single_end {
-                    let max_other_z = ops[..single_start]
-                        .iter()
-                        .chain(ops[single_end..].iter())
-                        .map(|op| op.z_order)
-                        .max()
-                        .unwrap_or(0);
-                    let min_focused_z = ops[single_start..single_end]
-                        .iter()
-                        .map(|op| op.z_order)
-                        .min()
-                        .unwrap_or(0);
-                    if min_focused_z <= max_other_z {
-                        let boost = max_other_z + 1 - min_focused_z;
-                        for op in &mut ops[single_start..single_end] {
-                            op.z_order += boost;
-                        }
-                    }
-                }
-                ResolvedScene {
-                    width: state.source.width,
-                    height: state.source.height,
-                    ops,
-                }
-            })
-            .collect()
-    } else {
-        Engine::compile(&state.source)
-    };
-    let contract = TerminalContract {
-        width: state.source.width,
-        height: state.source.height,
+    let pick = match state.mode {
+        Mode::PickCoordinate { col, row, .. } => Some((col as usize, row as usize)),
+        _ => None,
     };
-    let presentation = Renderer::render(&scenes, contract);
-
-    // Build the cell grid by replaying frames 0..=current. Shares one
-    // implementation with the player and the test harness so the WYSIWYG
-    // preview can never disagree with playback (see `grid_at`).
-    let grid = presentation.grid_at(state.current_frame);
-
     // Paint cells within the canvas at the (possibly inset) content origin.
     for (y, row) in grid.iter().enumerate() {
         let sy = oy + y as u16;
@@ -302,14 +192,364 @@ pub fn render_canvas_production(
             if sx >= cx + layout.canvas_width {
                 break;
             }
-            let cs = to_content_style(&cell.style);
+            if cell.is_continuation() {
+                continue; // covered by the wide glyph to its left
+            }
+            let (mut ch, mut cs) = (cell.ch, to_content_style(&cell.style));
+            // The pick crosshair: its centre cell inverted, with guides
+            // through the blanks along its row and column.
+            if let Some((pc, pr)) = pick {
+                if (x, y) == (pc, pr) {
+                    ch = if ch == ' ' { '┼' } else { ch };
+                    cs = cs.reverse();
+                } else if ch == ' ' && (x == pc || y == pr) {
+                    ch = if x == pc { '│' } else { '─' };
+                    cs = cs.dim();
+                }
+            }
             queue!(
                 stdout,
                 cursor::MoveTo(sx, sy),
-                style::PrintStyledContent(style::StyledContent::new(cs, cell.ch)),
+                style::PrintStyledContent(style::StyledContent::new(cs, ch)),
             )?;
         }
     }
 
     Ok(())
 }
+
+/// A table drawn with the editor's cell overlay: `(object index, highlighted
+/// column, selected cells, cursor cell)`.
+type TableOverlay = (usize, Option<usize>, Vec<(usize, usize)>, Option<(usize, usize)>);
+
+/// Everything besides the deck that changes how the preview draws it.
+#[derive(Clone, PartialEq)]
+struct View {
+    /// Objects drawn in their own style; the rest are dimmed. `None`: all of
+    /// them, as compiled.
+    focused: Option<Vec<usize>>,
+    /// Multi-select: the focused objects blink between white and dim.
+    select_mode: bool,
+    /// The blink's current phase, where something blinks.
+    blink_hidden: bool,
+    table: Option<TableOverlay>,
+    /// A table cell being typed into: `(row, col, buffer, caret)`.
+    editing: Option<(usize, usize, String, usize)>,
+    /// A label being typed into in place: `(object index, buffer, caret)`.
+    label_editing: Option<(usize, String, usize)>,
+}
+
+impl View {
+    fn of(state: &EditorState) -> Self {
+        let table = match &state.mode {
+            Mode::TableEditCellProps { object_index, cursor_row, cursor_col, selected_cells, sub_state } => {
+                // During navigation (Selecting) the cursor borders blink; in all other
+                // sub-states (editing content, editing style) they are shown steadily.
+                let cursor = if state.blink_hidden && matches!(sub_state, TableCellSubState::Selecting) {
+                    None
+                } else {
+                    Some((*cursor_row, *cursor_col))
+                };
+                Some((*object_index, None::<usize>, selected_cells.clone(), cursor))
+            }
+            Mode::TableRemoveColumn { object_index, col_num, .. } => {
+                let col_idx = col_num.saturating_sub(1);
+                Some((*object_index, Some(col_idx), vec![], None))
+            }
+            _ => None,
+        };
+        // When actively editing a cell, the current edit buffer is drawn so the
+        // typed text is visible live in the canvas (WYSIWYG), with a caret.
+        let editing = match &state.mode {
+            Mode::TableEditCellProps {
+                sub_state: TableCellSubState::EditingContent { row, col, buf, cursor },
+                ..
+            } => Some((*row, *col, buf.clone(), *cursor)),
+            _ => None,
+        };
+        let label_editing = match &state.mode {
+            Mode::EditInPlace { object_index, buf, cursor } => Some((*object_index, buf.clone(), *cursor)),
+            _ => None,
+        };
+        let select_mode = matches!(state.mode, Mode::MultiSelect { .. });
+        View {
+            focused: focus_indices(state),
+            select_mode,
+            blink_hidden: state.blink_hidden && (select_mode || table.is_some()),
+            table,
+            editing,
+            label_editing,
+        }
+    }
+}
+
+/// The preview's resolved scenes, kept between redraws so a keystroke only
+/// re-resolves what it changed: each frame is resolved when first shown, and
+/// an edit to one object forgets just the frames that object draws on, before
+/// and after. An edit that can reach other objects' frames — to a group, an
+/// animation, an include, the deck's settings or its list of objects — or a
+/// change of [`View`] forgets them all.
+#[derive(Default)]
+pub struct SceneCache {
+    view: Option<View>,
+    /// The deck's settings (all but its objects) the scenes were resolved with.
+    settings: String,
+    objects: Vec<Resolved>,
+    scenes: Vec<Option<ResolvedScene>>,
+}
+
+/// What the cache remembers of an object it resolved.
+#[derive(PartialEq)]
+struct Resolved {
+    json: String,
+    /// The frames it draws on (its group's, for a member of an explicit group).
+    frames: (usize, usize),
+    /// Whether editing it can change how other objects draw.
+    shared: bool,
+}
+
+impl SceneCache {
+    /// Forget the scenes that no longer match `source` drawn as `view`.
+    fn sync(&mut self, source: &SourcePresentation, view: &View) {
+        let settings = deck_settings(source);
+        let overrides = source.member_overrides();
+        let objects: Vec<Resolved> = source
+            .objects
+            .iter()
+            .enumerate()
+            .map(|(i, obj)| {
+                let range = overrides[i].clone().unwrap_or_else(|| source.effective_frame_range(i));
+                Resolved {
+                    json: serde_json::to_string(obj).unwrap_or_default(),
+                    frames: (range.start, range.end),
+                    shared: matches!(
+                        obj,
+                        SceneObject::Group(_) | SceneObject::Animation(_) | SceneObject::Include(_)
+                    ),
+                }
+            })
+            .collect();
+
+        if self.view.as_ref() != Some(view) || self.settings != settings || self.objects.len() != objects.len() {
+            self.scenes = vec![None; source.frame_count];
+        } else {
+            for (old, new) in self.objects.iter().zip(&objects).filter(|(old, new)| old != new) {
+                if old.shared || new.shared {
+                    self.scenes.fill(None);
+                    break;
+                }
+                for (start, end) in [old.frames, new.frames] {
+                    let end = end.min(self.scenes.len());
+                    self.scenes[start.min(end)..end].fill(None);
+                }
+            }
+        }
+        self.view = Some(view.clone());
+        self.settings = settings;
+        self.objects = objects;
+    }
+
+    /// Frame `frame`'s scene, resolved with `resolve` unless it is cached.
+    /// `None` for a deck without frames.
+    fn scene(&mut self, frame: usize, resolve: impl FnOnce() -> ResolvedScene) -> Option<&ResolvedScene> {
+        let slot = self.scenes.get_mut(frame)?;
+        Some(slot.get_or_insert_with(resolve))
+    }
+}
+
+/// The deck's settings — everything that is not an object — as one string,
+/// so a change to any of them shows.
+fn deck_settings(source: &SourcePresentation) -> String {
+    // Destructured so a new field has to be sorted into one list or the other.
+    let SourcePresentation {
+        format_version: _,
+        width,
+        height,
+        frame_count,
+        objects: _,
+        links,
+        background,
+        layers,
+        components,
+        vars,
+        slides: _,
+        lint: _,
+        auto_z,
+        durations: _,
+        on_enter: _,
+        sections,
+        metadata: _,
+    } = source;
+    serde_json::to_string(&(width, height, frame_count, links, background, layers, components, vars, auto_z, sections))
+        .unwrap_or_default()
+}
+
+/// Resolve `frame` of `source` for the preview: as compiled, or with the
+/// objects outside `view`'s focus dimmed and its overlays drawn.
+fn resolve_frame(source: &SourcePresentation, frame: usize, view: &View) -> ResolvedScene {
+    let Some(focused) = &view.focused else {
+        return Engine::compile_frame(source, frame);
+    };
+    // For a single focused object (non-group) we boost its z_order above others.
+    let single_focus = if focused.len() == 1 { Some(focused[0]) } else { None };
+    // Single source of truth for animation timing, threaded into resolve.
+    let anims = AnimSpans::of(source);
+    let sections = source.section_starts();
+    let expansions = source.instance_expansions();
+    let resolve = |i: usize, obj: &SceneObject, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>| {
+        match (obj, expansions[i].as_deref()) {
+            (SceneObject::Instance(inst), Some(expansion)) => {
+                inst.resolve_expansion(expansion, &inst.frames, ctx, ops)
+            }
+            _ => obj.resolve(ctx, ops),
+        }
+    };
+
+    let mut ops = Vec::new();
+    let ctx = ResolveCtx {
+        frame,
+        canvas_width: source.width,
+        canvas_height: source.height,
+        frame_count: source.frame_count,
+        anims: &anims,
+        sections: &sections,
+    };
+    let mut owners = Vec::new();
+    for (i, obj) in source.objects.iter().enumerate() {
+        let before = ops.len();
+        // For table objects with editor overlay, use the specialized resolve.
+        match (obj, &view.table, &view.label_editing) {
+            (SceneObject::Table(t), Some((tbl_idx, highlighted_col, sel_cells, cursor_cell)), _) if i == *tbl_idx => {
+                // Apply the edit buffer, if any, and pass the caret so the
+                // cell renders a block cursor.
+                if let Some((er, ec, buf, caret)) = &view.editing {
+                    let mut t_clone = t.clone();
+                    t_clone.normalize_cells();
+                    if let Some(row_vec) = t_clone.cells.get_mut(*er) {
+                        if let Some(cell) = row_vec.get_mut(*ec) {
+                            cell.content = buf.clone();
+                        }
+                    }
+                    t_clone.resolve_with_editor_overlay(
+                        frame,
+                        &anims,
+                        *highlighted_col,
+                        sel_cells,
+                        *cursor_cell,
+                        view.blink_hidden,
+                        Some((*er, *ec, *caret)),
+                        &mut ops,
+                    );
+                } else {
+                    t.resolve_with_editor_overlay(
+                        frame,
+                        &anims,
+                        *highlighted_col,
+                        sel_cells,
+                        *cursor_cell,
+                        view.blink_hidden,
+                        None,
+                        &mut ops,
+                    );
+                }
+            }
+            // A label being typed into shows the buffer, with its caret.
+            (SceneObject::Label(l), _, Some((label_idx, buf, caret))) if i == *label_idx => {
+                let label = Label { text: buf.clone(), ..l.clone() };
+                label.resolve_with_caret(&ctx, *caret, &mut ops);
+            }
+            _ => resolve(i, obj, &ctx, &mut ops),
+        }
+
+        owners.resize(ops.len(), i);
+        if focused.contains(&i) {
+            if view.select_mode {
+                let s = if view.blink_hidden { dim_style() } else { selected_style() };
+                for op in &mut ops[before..] {
+                    op.style = s.clone();
+                }
+            }
+            // For table overlay modes: do NOT override styles (already set by resolve_with_editor_overlay)
+            // else: keep original style for focused objects
+        } else {
+            let ds = dim_style();
+            for op in &mut ops[before..] {
+                op.style = ds.clone();
+            }
+        }
+    }
+    Engine::stack(&mut ops, &mut owners, source.auto_z);
+    // Boost single focused object's z_order above all others
+    if let Some(focus) = single_focus {
+        let z_of = |mine: bool| {
+            ops.iter().zip(&owners).filter(move |&(_, &o)| (o == focus) == mine).map(|(op, _)| op.z_order)
+        };
+        let max_other_z = z_of(false).max().unwrap_or(0);
+        if let Some(min_focused_z) = z_of(true).min() {
+            if min_focused_z <= max_other_z {
+                let boost = max_other_z + 1 - min_focused_z;
+                for (op, _) in ops.iter_mut().zip(&owners).filter(|&(_, &o)| o == focus) {
+                    op.z_order += boost;
+                }
+            }
+        }
+    }
+    ResolvedScene {
+        width: source.width,
+        height: source.height,
+        ops,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::objects::Label;
+    use crate::engine::source::FrameRange;
+
+    /// The frames `cache` has to resolve to show every frame of `source`.
+    fn resolved(cache: &mut SceneCache, source: &SourcePresentation, view: &View) -> Vec<usize> {
+        cache.sync(source, view);
+        (0..source.frame_count)
+            .filter(|&frame| {
+                let mut missed = false;
+                cache.scene(frame, || {
+                    missed = true;
+                    resolve_frame(source, frame, view)
+                });
+                missed
+            })
+            .collect()
+    }
+
+    #[test]
+    fn an_edit_forgets_only_the_frames_its_object_draws_on() {
+        let mut state = EditorState::open("/tmp/bs_scene_cache_absent.json").unwrap();
+        state.source.frame_count = 6;
+        state.source.objects = vec![
+            serde_json::from_str(r#"{"type":"label","text":"a","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":2}}"#).unwrap(),
+            serde_json::from_str(r#"{"type":"label","text":"b","position":{"x":{"fixed":0},"y":{"fixed":1}},"frames":{"start":3,"end":6}}"#).unwrap(),
+        ];
+        let view = View::of(&state);
+        let mut cache = SceneCache::default();
+        assert_eq!(resolved(&mut cache, &state.source, &view), [0, 1, 2, 3, 4, 5]);
+        assert_eq!(resolved(&mut cache, &state.source, &view), [] as [usize; 0]);
+
+        let mut edit = |state: &mut EditorState, f: fn(&mut Label)| {
+            if let SceneObject::Label(l) = &mut state.source.objects[1] {
+                f(l);
+            }
+            resolved(&mut cache, &state.source, &view)
+        };
+        assert_eq!(edit(&mut state, |b| b.text = "c".into()), [3, 4, 5]);
+        assert_eq!(edit(&mut state, |b| b.frames = FrameRange { start: 1, end: 4 }), [1, 2, 3, 4, 5], "old range and new");
+        let scene = cache.scene(2, || unreachable!()).unwrap();
+        assert_eq!(scene.ops.iter().map(|op| op.ch).collect::<String>(), "c");
+
+        state.mode = Mode::SelectedObject { object_index: 0 };
+        let focused = View::of(&state);
+        assert_eq!(resolved(&mut cache, &state.source, &focused).len(), 6, "a new view redraws everything");
+        state.source.width += 1;
+        assert_eq!(resolved(&mut cache, &state.source, &focused).len(), 6, "and so does a deck setting");
+    }
+}
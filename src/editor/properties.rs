@@ -1,11 +1,13 @@
 use anyhow::{bail, Result};
 
 use crate::engine::source::{
-    Animation, Arrow, Art, AutoAdvance, Circle, Command, Coordinate, FrameRange, Group, HLine,
-    Header, Label, List, Loop, Morph, MorphMode, Rect, SceneObject, Table, TextAlign,
-    VerticalAlign,
+    Animation, AnsiArt, Arrow, Art, AutoAdvance, BackgroundEffect, BorderStyle, Cast, Circle, Clock, Command, Connector, Coordinate, Countdown,
+    Scalar,
+    Diff, Effect, FileTree, Footer, FrameRange, Gantt, GanttTask, Gauge, Group, HLine, Header, Heatmap, Include, Instance, Keycap, Label, List, Loop, Marquee, Morph, MorphMode, PieChart, PieSegment, Rect, ScatterPlot,
+    SceneObject, Shadow, Spinner, Table, TextAlign, Threshold, Toc, TreeEntry, VerticalAlign, Watermark,
 };
-use crate::types::{Color, NamedColor};
+use crate::engine::objects::{default_heatmap_high, default_heatmap_low, default_spinner_glyphs, font, parse_keys};
+use crate::types::{char_width, ClockRegion, Color, NamedColor, Style};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PropertyKind {
@@ -36,6 +38,10 @@ pub enum PropertyKind {
     TextAlign,
     /// Dropdown for a label's vertical alignment.
     VerticalAlign,
+    /// Dropdown for a rect's border or a label's frame character set.
+    BorderStyle,
+    /// Dropdown for a background effect's kind.
+    Effect,
     /// Table column width (stored as percentage 0..100).
     TableColWidth,
 }
@@ -77,6 +83,8 @@ pub const MORPH_MODE_OPTIONS: &[&str] =
     &["dissolve", "wipe-right", "wipe-left", "wipe-down", "wipe-up"];
 pub const TEXT_ALIGN_OPTIONS: &[&str] = &["left", "center", "right"];
 pub const VERTICAL_ALIGN_OPTIONS: &[&str] = &["top", "center", "bottom"];
+pub const BORDER_STYLE_OPTIONS: &[&str] = &["single", "double", "heavy", "rounded", "ascii", "dashed"];
+pub const EFFECT_OPTIONS: &[&str] = &["starfield", "matrix", "noise"];
 
 /// Returns the dropdown option list for a property kind, if it uses a dropdown.
 pub fn dropdown_options_for(kind: &PropertyKind) -> Option<&'static [&'static str]> {
@@ -87,6 +95,8 @@ pub fn dropdown_options_for(kind: &PropertyKind) -> Option<&'static [&'static st
         PropertyKind::MorphMode     => Some(MORPH_MODE_OPTIONS),
         PropertyKind::TextAlign     => Some(TEXT_ALIGN_OPTIONS),
         PropertyKind::VerticalAlign => Some(VERTICAL_ALIGN_OPTIONS),
+        PropertyKind::BorderStyle   => Some(BORDER_STYLE_OPTIONS),
+        PropertyKind::Effect        => Some(EFFECT_OPTIONS),
         _                           => None,
     }
 }
@@ -175,6 +185,25 @@ fn as_editable(obj: &SceneObject) -> &dyn Editable {
         SceneObject::Group(o) => o,
         SceneObject::Table(o) => o,
         SceneObject::Art(o) => o,
+        SceneObject::AnsiArt(o) => o,
+        SceneObject::Cast(o) => o,
+        SceneObject::Countdown(o) => o,
+        SceneObject::Toc(o) => o,
+        SceneObject::Footer(o) => o,
+        SceneObject::Gauge(o) => o,
+        SceneObject::Heatmap(o) => o,
+        SceneObject::Diff(o) => o,
+        SceneObject::FileTree(o) => o,
+        SceneObject::Gantt(o) => o,
+        SceneObject::PieChart(o) => o,
+        SceneObject::ScatterPlot(o) => o,
+        SceneObject::Marquee(o) => o,
+        SceneObject::Spinner(o) => o,
+        SceneObject::Connector(o) => o,
+        SceneObject::Keycap(o) => o,
+        SceneObject::Watermark(o) => o,
+        SceneObject::BackgroundEffect(o) => o,
+        SceneObject::Clock(o) => o,
         SceneObject::Command(o) => o,
         SceneObject::List(o) => o,
         SceneObject::Loop(o) => o,
@@ -182,6 +211,8 @@ fn as_editable(obj: &SceneObject) -> &dyn Editable {
         SceneObject::Animation(o) => o,
         SceneObject::AutoAdvance(o) => o,
         SceneObject::Circle(o) => o,
+        SceneObject::Instance(o) => o,
+        SceneObject::Include(o) => o,
     }
 }
 
@@ -195,6 +226,25 @@ fn as_editable_mut(obj: &mut SceneObject) -> &mut dyn Editable {
         SceneObject::Group(o) => o,
         SceneObject::Table(o) => o,
         SceneObject::Art(o) => o,
+        SceneObject::AnsiArt(o) => o,
+        SceneObject::Cast(o) => o,
+        SceneObject::Countdown(o) => o,
+        SceneObject::Toc(o) => o,
+        SceneObject::Footer(o) => o,
+        SceneObject::Gauge(o) => o,
+        SceneObject::Heatmap(o) => o,
+        SceneObject::Diff(o) => o,
+        SceneObject::FileTree(o) => o,
+        SceneObject::Gantt(o) => o,
+        SceneObject::PieChart(o) => o,
+        SceneObject::ScatterPlot(o) => o,
+        SceneObject::Marquee(o) => o,
+        SceneObject::Spinner(o) => o,
+        SceneObject::Connector(o) => o,
+        SceneObject::Keycap(o) => o,
+        SceneObject::Watermark(o) => o,
+        SceneObject::BackgroundEffect(o) => o,
+        SceneObject::Clock(o) => o,
         SceneObject::Command(o) => o,
         SceneObject::List(o) => o,
         SceneObject::Loop(o) => o,
@@ -202,6 +252,8 @@ fn as_editable_mut(obj: &mut SceneObject) -> &mut dyn Editable {
         SceneObject::Animation(o) => o,
         SceneObject::AutoAdvance(o) => o,
         SceneObject::Circle(o) => o,
+        SceneObject::Instance(o) => o,
+        SceneObject::Include(o) => o,
     }
 }
 
@@ -357,6 +409,104 @@ impl Editable for Loop {
     fn move_by(&mut self, _dx: i32, _dy: i32) {}
 }
 
+impl Editable for Instance {
+    fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        vec![
+            Property { name: "component", value: self.component.clone(), kind: PropertyKind::Text },
+            Property { name: "args", value: serde_json::Value::Object(self.args.clone()).to_string(), kind: PropertyKind::Text },
+            Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
+            Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
+            Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "component" => self.component = value.trim().to_string(),
+            "args" => {
+                let value = value.trim();
+                self.args = if value.is_empty() { Default::default() } else { serde_json::from_str(value)? };
+            }
+            "first_frame" => self.frames.start = value.parse()?,
+            "last_frame" => self.frames.end = value.parse()?,
+            "z_order" => self.z_order = value.trim().parse()?,
+            _ => bail!("Unknown property: {name}"),
+        }
+        Ok(())
+    }
+
+    // An instance is placed by its arguments, not by geometry of its own.
+    fn get_coord(&self, _name: &str) -> Option<Coordinate> { None }
+    fn set_coord(&mut self, _name: &str, _coord: Coordinate) -> Result<()> {
+        bail!("Instances have no coordinate properties")
+    }
+    fn origin_x(&self) -> f64 { 0.0 }
+    fn origin_y(&self) -> f64 { 0.0 }
+    fn dim_x(&self) -> f64 { 0.0 }
+    fn dim_y(&self) -> f64 { 0.0 }
+    fn set_origin_x(&mut self, _v: f64) {}
+    fn set_origin_y(&mut self, _v: f64) {}
+    fn set_dim_x(&mut self, _v: f64) {}
+    fn set_dim_y(&mut self, _v: f64) {}
+    fn move_by(&mut self, _dx: i32, _dy: i32) {}
+}
+
+impl Editable for Include {
+    fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        let (first, last) = match &self.frames {
+            Some(fr) => (fr.start.to_string(), fr.end.to_string()),
+            None => (String::new(), String::new()),
+        };
+        vec![
+            Property { name: "path", value: self.path.clone(), kind: PropertyKind::Text },
+            Property { name: "at", value: self.at.to_string(), kind: PropertyKind::Number },
+            Property { name: "first_frame", value: first, kind: PropertyKind::Number },
+            Property { name: "last_frame", value: last, kind: PropertyKind::Number },
+            Property {
+                name: "note",
+                value: "included objects show when compiled (blank frames = all)".into(),
+                kind: PropertyKind::Note,
+            },
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        let frame = |v: &str| -> Result<Option<usize>> {
+            let v = v.trim();
+            Ok(if v.is_empty() { None } else { Some(v.parse()?) })
+        };
+        match name {
+            "path" => self.path = value.trim().to_string(),
+            "at" => self.at = value.trim().parse()?,
+            "first_frame" | "last_frame" => {
+                let Some(v) = frame(value)? else {
+                    self.frames = None;
+                    return Ok(());
+                };
+                let fr = self.frames.get_or_insert(FrameRange { start: v, end: v + 1 });
+                if name == "first_frame" { fr.start = v } else { fr.end = v }
+            }
+            _ => bail!("Unknown property: {name}"),
+        }
+        Ok(())
+    }
+
+    // An include has no geometry: the included objects keep their own.
+    fn get_coord(&self, _name: &str) -> Option<Coordinate> { None }
+    fn set_coord(&mut self, _name: &str, _coord: Coordinate) -> Result<()> {
+        bail!("Includes have no coordinate properties")
+    }
+    fn origin_x(&self) -> f64 { 0.0 }
+    fn origin_y(&self) -> f64 { 0.0 }
+    fn dim_x(&self) -> f64 { 0.0 }
+    fn dim_y(&self) -> f64 { 0.0 }
+    fn set_origin_x(&mut self, _v: f64) {}
+    fn set_origin_y(&mut self, _v: f64) {}
+    fn set_dim_x(&mut self, _v: f64) {}
+    fn set_dim_y(&mut self, _v: f64) {}
+    fn move_by(&mut self, _dx: i32, _dy: i32) {}
+}
+
 impl Editable for Animation {
     fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
         vec![
@@ -517,7 +667,7 @@ impl Editable for Command {
 
 impl Editable for Label {
     fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
-        vec![
+        let mut props = vec![
             Property { name: "text", value: self.text.clone(), kind: PropertyKind::Text },
             Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
             Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
@@ -525,7 +675,14 @@ impl Editable for Label {
             Property { name: "height", value: format_coordinate(&self.height), kind: PropertyKind::Coordinate },
             Property { name: "align", value: self.align.as_str().to_string(), kind: PropertyKind::TextAlign },
             Property { name: "valign", value: self.valign.as_str().to_string(), kind: PropertyKind::VerticalAlign },
+            Property { name: "link", value: self.link.clone().unwrap_or_default(), kind: PropertyKind::Text },
+            Property { name: "reveal", value: self.reveal.to_string(), kind: PropertyKind::Bool },
+            Property { name: "padding_top", value: self.padding.top.to_string(), kind: PropertyKind::Number },
+            Property { name: "padding_right", value: self.padding.right.to_string(), kind: PropertyKind::Number },
+            Property { name: "padding_bottom", value: self.padding.bottom.to_string(), kind: PropertyKind::Number },
+            Property { name: "padding_left", value: self.padding.left.to_string(), kind: PropertyKind::Number },
             Property { name: "framed", value: self.framed.to_string(), kind: PropertyKind::Bool },
+            Property { name: "border", value: self.border.as_str().to_string(), kind: PropertyKind::BorderStyle },
             Property { name: "frame_fg_color", value: format_opt_color(&self.frame_style.as_ref().and_then(|s| s.fg.clone())), kind: PropertyKind::Color },
             Property { name: "frame_bg_color", value: format_opt_color(&self.frame_style.as_ref().and_then(|s| s.bg.clone())), kind: PropertyKind::Color },
             Property { name: "fg_color", value: format_opt_color(&self.style.fg), kind: PropertyKind::Color },
@@ -535,7 +692,9 @@ impl Editable for Label {
             Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
             Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
             Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
-        ]
+        ];
+        props.extend(shadow_properties(&self.shadow));
+        props
     }
 
     fn set(&mut self, name: &str, value: &str) -> Result<()> {
@@ -553,7 +712,19 @@ impl Editable for Label {
                 self.valign = VerticalAlign::from_str_opt(value)
                     .ok_or_else(|| anyhow::anyhow!("Unknown vertical alignment: {value}"))?
             }
+            "link" => {
+                self.link = if value.trim().is_empty() { None } else { Some(value.trim().to_string()) };
+            }
+            "reveal" => self.reveal = parse_bool(value)?,
+            "padding_top" => self.padding.top = value.trim().parse()?,
+            "padding_right" => self.padding.right = value.trim().parse()?,
+            "padding_bottom" => self.padding.bottom = value.trim().parse()?,
+            "padding_left" => self.padding.left = value.trim().parse()?,
             "framed" => self.framed = parse_bool(value)?,
+            "border" => {
+                self.border = BorderStyle::from_str_opt(value)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown border style: {value}"))?
+            }
             "frame_fg_color" => {
                 let color = parse_opt_color(value)?;
                 let fs = self.frame_style.get_or_insert_with(Default::default);
@@ -577,6 +748,9 @@ impl Editable for Label {
             "first_frame" => self.frames.start = value.parse()?,
             "last_frame" => self.frames.end = value.parse()?,
             "z_order" => self.z_order = value.parse()?,
+            "shadow" | "shadow_dx" | "shadow_dy" | "shadow_color" => {
+                set_shadow_property(&mut self.shadow, name, value)?
+            }
             _ => bail!("Unknown property: {name}"),
         }
         Ok(())
@@ -899,12 +1073,13 @@ impl Editable for HLine {
 
 impl Editable for Rect {
     fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
-        vec![
+        let mut props = vec![
             Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
             Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
             Property { name: "width", value: format_coordinate(&self.width), kind: PropertyKind::Coordinate },
             Property { name: "height", value: format_coordinate(&self.height), kind: PropertyKind::Coordinate },
             Property { name: "title", value: self.title.clone().unwrap_or_default(), kind: PropertyKind::Text },
+            Property { name: "border", value: self.border.as_str().to_string(), kind: PropertyKind::BorderStyle },
             Property { name: "fg_color", value: format_opt_color(&self.style.fg), kind: PropertyKind::Color },
             Property { name: "bg_color", value: format_opt_color(&self.style.bg), kind: PropertyKind::Color },
             Property { name: "bold", value: self.style.bold.to_string(), kind: PropertyKind::Bool },
@@ -912,7 +1087,9 @@ impl Editable for Rect {
             Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
             Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
             Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
-        ]
+        ];
+        props.extend(shadow_properties(&self.shadow));
+        props
     }
 
     fn set(&mut self, name: &str, value: &str) -> Result<()> {
@@ -924,6 +1101,10 @@ impl Editable for Rect {
             "title" => {
                 self.title = if value.is_empty() { None } else { Some(value.to_string()) };
             }
+            "border" => {
+                self.border = BorderStyle::from_str_opt(value)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown border style: {value}"))?
+            }
             "fg_color" => self.style.fg = parse_opt_color(value)?,
             "bg_color" => self.style.bg = parse_opt_color(value)?,
             "bold" => self.style.bold = parse_bool(value)?,
@@ -931,6 +1112,9 @@ impl Editable for Rect {
             "first_frame" => self.frames.start = value.parse()?,
             "last_frame" => self.frames.end = value.parse()?,
             "z_order" => self.z_order = value.parse()?,
+            "shadow" | "shadow_dx" | "shadow_dy" | "shadow_color" => {
+                set_shadow_property(&mut self.shadow, name, value)?
+            }
             _ => bail!("Unknown property: {name}"),
         }
         Ok(())
@@ -1002,33 +1186,1413 @@ impl Editable for Rect {
                 adjust_coordinate(&mut self.position.x, -dw);
             }
         }
-        if dh > 0 {
-            if let Coordinate::Fixed(v) = &mut self.height {
-                *v = (*v - dh as f64).max(1.0);
-            }
-        } else if dh < 0 {
-            let delta = (-dh) as f64;
-            if coordinate_val(&self.height) > 1 {
-                if let Coordinate::Fixed(v) = &mut self.height {
-                    *v = (*v - delta).max(1.0);
-                }
-                adjust_coordinate(&mut self.position.y, -dh);
-            }
+        if dh > 0 {
+            if let Coordinate::Fixed(v) = &mut self.height {
+                *v = (*v - dh as f64).max(1.0);
+            }
+        } else if dh < 0 {
+            let delta = (-dh) as f64;
+            if coordinate_val(&self.height) > 1 {
+                if let Coordinate::Fixed(v) = &mut self.height {
+                    *v = (*v - delta).max(1.0);
+                }
+                adjust_coordinate(&mut self.position.y, -dh);
+            }
+        }
+    }
+}
+
+impl Editable for Header {
+    fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        vec![
+            Property { name: "text", value: self.text.clone(), kind: PropertyKind::Text },
+            Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
+            Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
+            Property { name: "draw_char", value: self.ch.to_string(), kind: PropertyKind::Text },
+            Property { name: "fg_color", value: format_opt_color(&self.style.fg), kind: PropertyKind::Color },
+            Property { name: "gradient_from", value: format_opt_color(&self.gradient.as_ref().map(|[a, _]| a.clone())), kind: PropertyKind::Color },
+            Property { name: "gradient_to", value: format_opt_color(&self.gradient.as_ref().map(|[_, b]| b.clone())), kind: PropertyKind::Color },
+            Property { name: "bg_color", value: format_opt_color(&self.style.bg), kind: PropertyKind::Color },
+            Property { name: "bold", value: self.style.bold.to_string(), kind: PropertyKind::Bool },
+            Property { name: "dimmed", value: self.style.dim.to_string(), kind: PropertyKind::Bool },
+            Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
+            Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
+            Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "text" => self.text = value.to_string(),
+            "x" => self.position.x = parse_coordinate(value)?,
+            "y" => self.position.y = parse_coordinate(value)?,
+            "draw_char" => self.ch = parse_char(value)?,
+            "fg_color" => self.style.fg = parse_opt_color(value)?,
+            // Setting one end of an unset gradient starts the other at the
+            // plain foreground; clearing either end drops the gradient.
+            "gradient_from" | "gradient_to" => {
+                let end = usize::from(name == "gradient_to");
+                self.gradient = match parse_opt_color(value)? {
+                    None => None,
+                    Some(color) => {
+                        let plain = self.style.fg.clone().unwrap_or(Color::Named(NamedColor::White));
+                        let mut ends = self.gradient.clone().unwrap_or([plain.clone(), plain]);
+                        ends[end] = color;
+                        Some(ends)
+                    }
+                };
+            }
+            "bg_color" => self.style.bg = parse_opt_color(value)?,
+            "bold" => self.style.bold = parse_bool(value)?,
+            "dimmed" => self.style.dim = parse_bool(value)?,
+            "first_frame" => self.frames.start = value.parse()?,
+            "last_frame" => self.frames.end = value.parse()?,
+            "z_order" => self.z_order = value.parse()?,
+            _ => bail!("Unknown property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn get_coord(&self, name: &str) -> Option<Coordinate> {
+        match name {
+            "x" => Some(self.position.x.clone()),
+            "y" => Some(self.position.y.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_coord(&mut self, name: &str, coord: Coordinate) -> Result<()> {
+        match name {
+            "x" => self.position.x = coord,
+            "y" => self.position.y = coord,
+            _ => bail!("Unknown coordinate property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn origin_x(&self) -> f64 { coord_val_f(&self.position.x) }
+    fn origin_y(&self) -> f64 { coord_val_f(&self.position.y) }
+    fn dim_x(&self) -> f64 { 0.0 }
+    fn dim_y(&self) -> f64 { 1.0 }
+    fn set_origin_x(&mut self, v: f64) { set_fixed(&mut self.position.x, v); }
+    fn set_origin_y(&mut self, v: f64) { set_fixed(&mut self.position.y, v); }
+    fn set_dim_x(&mut self, _v: f64) {}
+    fn set_dim_y(&mut self, _v: f64) {}
+
+    fn move_by(&mut self, dx: i32, dy: i32) {
+        adjust_coordinate(&mut self.position.x, dx);
+        adjust_coordinate(&mut self.position.y, dy);
+    }
+    // resize_by / shrink_by: default no-op (a header has no resizable box).
+}
+
+impl Editable for Countdown {
+    fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        vec![
+            Property { name: "from_secs", value: self.from_secs.to_string(), kind: PropertyKind::Number },
+            Property { name: "step_secs", value: self.step_secs.to_string(), kind: PropertyKind::Number },
+            Property { name: "auto_play", value: self.auto_play.to_string(), kind: PropertyKind::Bool },
+            Property { name: "big", value: self.big.to_string(), kind: PropertyKind::Bool },
+            Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
+            Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
+            Property { name: "fg_color", value: format_opt_color(&self.style.fg), kind: PropertyKind::Color },
+            Property { name: "bg_color", value: format_opt_color(&self.style.bg), kind: PropertyKind::Color },
+            Property { name: "bold", value: self.style.bold.to_string(), kind: PropertyKind::Bool },
+            Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
+            Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
+            Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "from_secs" => self.from_secs = value.trim().parse()?,
+            "step_secs" => self.step_secs = value.trim().parse()?,
+            "auto_play" => self.auto_play = parse_bool(value)?,
+            "big" => self.big = parse_bool(value)?,
+            "x" => self.position.x = parse_coordinate(value)?,
+            "y" => self.position.y = parse_coordinate(value)?,
+            "fg_color" => self.style.fg = parse_opt_color(value)?,
+            "bg_color" => self.style.bg = parse_opt_color(value)?,
+            "bold" => self.style.bold = parse_bool(value)?,
+            "first_frame" => self.frames.start = value.parse()?,
+            "last_frame" => self.frames.end = value.parse()?,
+            "z_order" => self.z_order = value.parse()?,
+            _ => bail!("Unknown property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn get_coord(&self, name: &str) -> Option<Coordinate> {
+        match name {
+            "x" => Some(self.position.x.clone()),
+            "y" => Some(self.position.y.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_coord(&mut self, name: &str, coord: Coordinate) -> Result<()> {
+        match name {
+            "x" => self.position.x = coord,
+            "y" => self.position.y = coord,
+            _ => bail!("Unknown coordinate property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn origin_x(&self) -> f64 { coord_val_f(&self.position.x) }
+    fn origin_y(&self) -> f64 { coord_val_f(&self.position.y) }
+    fn dim_x(&self) -> f64 {
+        let text = self.text(self.frames.start);
+        if self.big { font::text_width(&text) as f64 } else { text.len() as f64 }
+    }
+    fn dim_y(&self) -> f64 { if self.big { font::GLYPH_HEIGHT as f64 } else { 1.0 } }
+    fn set_origin_x(&mut self, v: f64) { set_fixed(&mut self.position.x, v); }
+    fn set_origin_y(&mut self, v: f64) { set_fixed(&mut self.position.y, v); }
+    fn set_dim_x(&mut self, _v: f64) {} // sized by its text
+    fn set_dim_y(&mut self, _v: f64) {}
+
+    fn move_by(&mut self, dx: i32, dy: i32) {
+        adjust_coordinate(&mut self.position.x, dx);
+        adjust_coordinate(&mut self.position.y, dy);
+    }
+}
+
+impl Editable for Clock {
+    fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        vec![
+            Property { name: "seconds", value: self.seconds.to_string(), kind: PropertyKind::Bool },
+            Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
+            Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
+            Property { name: "fg_color", value: format_opt_color(&self.style.fg), kind: PropertyKind::Color },
+            Property { name: "bg_color", value: format_opt_color(&self.style.bg), kind: PropertyKind::Color },
+            Property { name: "bold", value: self.style.bold.to_string(), kind: PropertyKind::Bool },
+            Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
+            Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
+            Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "seconds" => self.seconds = parse_bool(value)?,
+            "x" => self.position.x = parse_coordinate(value)?,
+            "y" => self.position.y = parse_coordinate(value)?,
+            "fg_color" => self.style.fg = parse_opt_color(value)?,
+            "bg_color" => self.style.bg = parse_opt_color(value)?,
+            "bold" => self.style.bold = parse_bool(value)?,
+            "first_frame" => self.frames.start = value.parse()?,
+            "last_frame" => self.frames.end = value.parse()?,
+            "z_order" => self.z_order = value.parse()?,
+            _ => bail!("Unknown property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn get_coord(&self, name: &str) -> Option<Coordinate> {
+        match name {
+            "x" => Some(self.position.x.clone()),
+            "y" => Some(self.position.y.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_coord(&mut self, name: &str, coord: Coordinate) -> Result<()> {
+        match name {
+            "x" => self.position.x = coord,
+            "y" => self.position.y = coord,
+            _ => bail!("Unknown coordinate property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn origin_x(&self) -> f64 { coord_val_f(&self.position.x) }
+    fn origin_y(&self) -> f64 { coord_val_f(&self.position.y) }
+    fn dim_x(&self) -> f64 { ClockRegion::placeholder(self.seconds).len() as f64 }
+    fn dim_y(&self) -> f64 { 1.0 }
+    fn set_origin_x(&mut self, v: f64) { set_fixed(&mut self.position.x, v); }
+    fn set_origin_y(&mut self, v: f64) { set_fixed(&mut self.position.y, v); }
+    fn set_dim_x(&mut self, _v: f64) {} // sized by its text
+    fn set_dim_y(&mut self, _v: f64) {}
+
+    fn move_by(&mut self, dx: i32, dy: i32) {
+        adjust_coordinate(&mut self.position.x, dx);
+        adjust_coordinate(&mut self.position.y, dy);
+    }
+}
+
+impl Editable for Footer {
+    fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        vec![
+            Property { name: "text", value: self.text.clone(), kind: PropertyKind::Text },
+            Property { name: "width", value: self.width.to_string(), kind: PropertyKind::Number },
+            Property { name: "align", value: self.align.as_str().to_string(), kind: PropertyKind::TextAlign },
+            Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
+            Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
+            Property { name: "fg_color", value: format_opt_color(&self.style.fg), kind: PropertyKind::Color },
+            Property { name: "bg_color", value: format_opt_color(&self.style.bg), kind: PropertyKind::Color },
+            Property { name: "bold", value: self.style.bold.to_string(), kind: PropertyKind::Bool },
+            Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
+            Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
+            Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "text" => self.text = value.to_string(),
+            "width" => self.width = value.trim().parse()?,
+            "align" => {
+                self.align = TextAlign::from_str_opt(value)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown alignment: {value}"))?
+            }
+            "x" => self.position.x = parse_coordinate(value)?,
+            "y" => self.position.y = parse_coordinate(value)?,
+            "fg_color" => self.style.fg = parse_opt_color(value)?,
+            "bg_color" => self.style.bg = parse_opt_color(value)?,
+            "bold" => self.style.bold = parse_bool(value)?,
+            "first_frame" => self.frames.start = value.parse()?,
+            "last_frame" => self.frames.end = value.parse()?,
+            "z_order" => self.z_order = value.parse()?,
+            _ => bail!("Unknown property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn get_coord(&self, name: &str) -> Option<Coordinate> {
+        match name {
+            "x" => Some(self.position.x.clone()),
+            "y" => Some(self.position.y.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_coord(&mut self, name: &str, coord: Coordinate) -> Result<()> {
+        match name {
+            "x" => self.position.x = coord,
+            "y" => self.position.y = coord,
+            _ => bail!("Unknown coordinate property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn origin_x(&self) -> f64 { coord_val_f(&self.position.x) }
+    fn origin_y(&self) -> f64 { coord_val_f(&self.position.y) }
+    fn dim_x(&self) -> f64 { self.width as f64 }
+    fn dim_y(&self) -> f64 { 1.0 }
+    fn set_origin_x(&mut self, v: f64) { set_fixed(&mut self.position.x, v); }
+    fn set_origin_y(&mut self, v: f64) { set_fixed(&mut self.position.y, v); }
+    fn set_dim_x(&mut self, v: f64) { self.width = v.max(1.0) as u16; }
+    fn set_dim_y(&mut self, _v: f64) {} // one line
+
+    fn move_by(&mut self, dx: i32, dy: i32) {
+        adjust_coordinate(&mut self.position.x, dx);
+        adjust_coordinate(&mut self.position.y, dy);
+    }
+}
+impl Editable for BackgroundEffect {
+    fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        vec![
+            Property { name: "effect", value: self.effect.as_str().to_string(), kind: PropertyKind::Effect },
+            Property { name: "seed", value: self.seed.to_string(), kind: PropertyKind::Number },
+            Property { name: "density", value: self.density.to_string(), kind: PropertyKind::Number },
+            Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
+            Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
+            Property { name: "width", value: self.width.to_string(), kind: PropertyKind::Number },
+            Property { name: "height", value: self.height.to_string(), kind: PropertyKind::Number },
+            Property { name: "fg_color", value: format_opt_color(&self.style.fg), kind: PropertyKind::Color },
+            Property { name: "bg_color", value: format_opt_color(&self.style.bg), kind: PropertyKind::Color },
+            Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
+            Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
+            Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "effect" => {
+                self.effect = Effect::from_str_opt(value)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown effect: {value}"))?
+            }
+            "seed" => self.seed = value.trim().parse()?,
+            "density" => self.density = value.trim().parse::<f64>()?.clamp(0.0, 1.0),
+            "x" => self.position.x = parse_coordinate(value)?,
+            "y" => self.position.y = parse_coordinate(value)?,
+            "width" => self.width = value.trim().parse()?,
+            "height" => self.height = value.trim().parse()?,
+            "fg_color" => self.style.fg = parse_opt_color(value)?,
+            "bg_color" => self.style.bg = parse_opt_color(value)?,
+            "first_frame" => self.frames.start = value.parse()?,
+            "last_frame" => self.frames.end = value.parse()?,
+            "z_order" => self.z_order = value.parse()?,
+            _ => bail!("Unknown property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn get_coord(&self, name: &str) -> Option<Coordinate> {
+        match name {
+            "x" => Some(self.position.x.clone()),
+            "y" => Some(self.position.y.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_coord(&mut self, name: &str, coord: Coordinate) -> Result<()> {
+        match name {
+            "x" => self.position.x = coord,
+            "y" => self.position.y = coord,
+            _ => bail!("Unknown coordinate property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn origin_x(&self) -> f64 { coord_val_f(&self.position.x) }
+    fn origin_y(&self) -> f64 { coord_val_f(&self.position.y) }
+    fn dim_x(&self) -> f64 { self.width as f64 }
+    fn dim_y(&self) -> f64 { self.height as f64 }
+    fn set_origin_x(&mut self, v: f64) { set_fixed(&mut self.position.x, v); }
+    fn set_origin_y(&mut self, v: f64) { set_fixed(&mut self.position.y, v); }
+    fn set_dim_x(&mut self, v: f64) { self.width = v.max(0.0) as u16; }
+    fn set_dim_y(&mut self, v: f64) { self.height = v.max(0.0) as u16; }
+
+    fn move_by(&mut self, dx: i32, dy: i32) {
+        adjust_coordinate(&mut self.position.x, dx);
+        adjust_coordinate(&mut self.position.y, dy);
+    }
+}
+
+impl Editable for Watermark {
+    fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        vec![
+            Property { name: "text", value: self.text.clone(), kind: PropertyKind::Text },
+            Property { name: "big", value: self.big.to_string(), kind: PropertyKind::Bool },
+            Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
+            Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
+            Property { name: "fg_color", value: format_opt_color(&self.style.fg), kind: PropertyKind::Color },
+            Property { name: "bg_color", value: format_opt_color(&self.style.bg), kind: PropertyKind::Color },
+            Property { name: "bold", value: self.style.bold.to_string(), kind: PropertyKind::Bool },
+            Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
+            Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
+            Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "text" => self.text = value.to_string(),
+            "big" => self.big = parse_bool(value)?,
+            "x" => self.position.x = parse_coordinate(value)?,
+            "y" => self.position.y = parse_coordinate(value)?,
+            "fg_color" => self.style.fg = parse_opt_color(value)?,
+            "bg_color" => self.style.bg = parse_opt_color(value)?,
+            "bold" => self.style.bold = parse_bool(value)?,
+            "first_frame" => self.frames.start = value.parse()?,
+            "last_frame" => self.frames.end = value.parse()?,
+            "z_order" => self.z_order = value.parse()?,
+            _ => bail!("Unknown property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn get_coord(&self, name: &str) -> Option<Coordinate> {
+        match name {
+            "x" => Some(self.position.x.clone()),
+            "y" => Some(self.position.y.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_coord(&mut self, name: &str, coord: Coordinate) -> Result<()> {
+        match name {
+            "x" => self.position.x = coord,
+            "y" => self.position.y = coord,
+            _ => bail!("Unknown coordinate property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn origin_x(&self) -> f64 { coord_val_f(&self.position.x) }
+    fn origin_y(&self) -> f64 { coord_val_f(&self.position.y) }
+    fn dim_x(&self) -> f64 { self.size().0 as f64 }
+    fn dim_y(&self) -> f64 { self.size().1 as f64 }
+    fn set_origin_x(&mut self, v: f64) { set_fixed(&mut self.position.x, v); }
+    fn set_origin_y(&mut self, v: f64) { set_fixed(&mut self.position.y, v); }
+    fn set_dim_x(&mut self, _v: f64) {} // sized by its text
+    fn set_dim_y(&mut self, _v: f64) {}
+
+    fn move_by(&mut self, dx: i32, dy: i32) {
+        adjust_coordinate(&mut self.position.x, dx);
+        adjust_coordinate(&mut self.position.y, dy);
+    }
+}
+impl Editable for Gauge {
+    fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        vec![
+            Property { name: "value", value: format_scalar(&self.value), kind: PropertyKind::Text },
+            Property { name: "min", value: format_scalar(&self.min), kind: PropertyKind::Text },
+            Property { name: "max", value: format_scalar(&self.max), kind: PropertyKind::Text },
+            Property { name: "size", value: self.size.to_string(), kind: PropertyKind::Number },
+            Property { name: "thresholds", value: format_thresholds(&self.thresholds), kind: PropertyKind::Text },
+            Property { name: "unit", value: self.unit.clone(), kind: PropertyKind::Text },
+            Property { name: "show_value", value: self.show_value.to_string(), kind: PropertyKind::Bool },
+            Property { name: "blocks", value: self.blocks.to_string(), kind: PropertyKind::Bool },
+            Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
+            Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
+            Property { name: "fg_color", value: format_opt_color(&self.style.fg), kind: PropertyKind::Color },
+            Property { name: "bg_color", value: format_opt_color(&self.style.bg), kind: PropertyKind::Color },
+            Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
+            Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
+            Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "value" => self.value = parse_scalar(value)?,
+            "min" => self.min = parse_scalar(value)?,
+            "max" => self.max = parse_scalar(value)?,
+            "size" => self.size = value.trim().parse::<u16>()?.max(2),
+            "thresholds" => self.thresholds = parse_thresholds(value)?,
+            "unit" => self.unit = value.to_string(),
+            "show_value" => self.show_value = parse_bool(value)?,
+            "blocks" => self.blocks = parse_bool(value)?,
+            "x" => self.position.x = parse_coordinate(value)?,
+            "y" => self.position.y = parse_coordinate(value)?,
+            "fg_color" => self.style.fg = parse_opt_color(value)?,
+            "bg_color" => self.style.bg = parse_opt_color(value)?,
+            "first_frame" => self.frames.start = value.parse()?,
+            "last_frame" => self.frames.end = value.parse()?,
+            "z_order" => self.z_order = value.parse()?,
+            _ => bail!("Unknown property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn get_coord(&self, name: &str) -> Option<Coordinate> {
+        match name {
+            "x" => Some(self.position.x.clone()),
+            "y" => Some(self.position.y.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_coord(&mut self, name: &str, coord: Coordinate) -> Result<()> {
+        match name {
+            "x" => self.position.x = coord,
+            "y" => self.position.y = coord,
+            _ => bail!("Unknown coordinate property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn origin_x(&self) -> f64 { coord_val_f(&self.position.x) }
+    fn origin_y(&self) -> f64 { coord_val_f(&self.position.y) }
+    fn dim_x(&self) -> f64 { self.size as f64 }
+    fn dim_y(&self) -> f64 { self.rows() as f64 }
+    fn set_origin_x(&mut self, v: f64) { set_fixed(&mut self.position.x, v); }
+    fn set_origin_y(&mut self, v: f64) { set_fixed(&mut self.position.y, v); }
+    fn set_dim_x(&mut self, v: f64) { self.size = v.max(2.0) as u16; }
+    fn set_dim_y(&mut self, _v: f64) {} // follows the width
+
+    fn move_by(&mut self, dx: i32, dy: i32) {
+        adjust_coordinate(&mut self.position.x, dx);
+        adjust_coordinate(&mut self.position.y, dy);
+    }
+}
+
+/// A gauge's thresholds as `at:colour` pairs, e.g. `50:yellow, 80:red`.
+fn format_thresholds(thresholds: &[Threshold]) -> String {
+    thresholds
+        .iter()
+        .map(|t| format!("{}:{}", t.at, format_opt_color(&Some(t.color.clone()))))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn parse_thresholds(s: &str) -> Result<Vec<Threshold>> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(|t| {
+            let (at, color) = t.split_once(':').ok_or_else(|| anyhow::anyhow!("expected value:colour, got {t}"))?;
+            let color = parse_opt_color(color)?.ok_or_else(|| anyhow::anyhow!("threshold {t} has no colour"))?;
+            Ok(Threshold { at: at.trim().parse()?, color })
+        })
+        .collect()
+}
+impl Editable for Diff {
+    fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        vec![
+            Property { name: "before", value: self.before.clone(), kind: PropertyKind::Text },
+            Property { name: "after", value: self.after.clone(), kind: PropertyKind::Text },
+            Property { name: "diff", value: self.diff.clone(), kind: PropertyKind::Text },
+            Property { name: "context", value: self.context.to_string(), kind: PropertyKind::Number },
+            Property { name: "reveal", value: self.reveal.to_string(), kind: PropertyKind::Bool },
+            Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
+            Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
+            Property { name: "fg_color", value: format_opt_color(&self.style.fg), kind: PropertyKind::Color },
+            Property { name: "bg_color", value: format_opt_color(&self.style.bg), kind: PropertyKind::Color },
+            Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
+            Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
+            Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "before" => self.before = value.to_string(),
+            "after" => self.after = value.to_string(),
+            "diff" => self.diff = value.to_string(),
+            "context" => self.context = value.trim().parse()?,
+            "reveal" => self.reveal = parse_bool(value)?,
+            "x" => self.position.x = parse_coordinate(value)?,
+            "y" => self.position.y = parse_coordinate(value)?,
+            "fg_color" => self.style.fg = parse_opt_color(value)?,
+            "bg_color" => self.style.bg = parse_opt_color(value)?,
+            "first_frame" => self.frames.start = value.parse()?,
+            "last_frame" => self.frames.end = value.parse()?,
+            "z_order" => self.z_order = value.parse()?,
+            _ => bail!("Unknown property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn get_coord(&self, name: &str) -> Option<Coordinate> {
+        match name {
+            "x" => Some(self.position.x.clone()),
+            "y" => Some(self.position.y.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_coord(&mut self, name: &str, coord: Coordinate) -> Result<()> {
+        match name {
+            "x" => self.position.x = coord,
+            "y" => self.position.y = coord,
+            _ => bail!("Unknown coordinate property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn origin_x(&self) -> f64 { coord_val_f(&self.position.x) }
+    fn origin_y(&self) -> f64 { coord_val_f(&self.position.y) }
+    fn dim_x(&self) -> f64 { self.size().0 as f64 }
+    fn dim_y(&self) -> f64 { self.size().1 as f64 }
+    fn set_origin_x(&mut self, v: f64) { set_fixed(&mut self.position.x, v); }
+    fn set_origin_y(&mut self, v: f64) { set_fixed(&mut self.position.y, v); }
+    fn set_dim_x(&mut self, _v: f64) {} // follows the text
+    fn set_dim_y(&mut self, _v: f64) {}
+
+    fn move_by(&mut self, dx: i32, dy: i32) {
+        adjust_coordinate(&mut self.position.x, dx);
+        adjust_coordinate(&mut self.position.y, dy);
+    }
+}
+impl Editable for FileTree {
+    fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        vec![
+            Property { name: "entries", value: format_tree_entries(&self.entries), kind: PropertyKind::Text },
+            Property { name: "glyphs", value: self.glyphs.to_string(), kind: PropertyKind::Bool },
+            Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
+            Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
+            Property { name: "fg_color", value: format_opt_color(&self.style.fg), kind: PropertyKind::Color },
+            Property { name: "bg_color", value: format_opt_color(&self.style.bg), kind: PropertyKind::Color },
+            Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
+            Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
+            Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "entries" => self.entries = parse_tree_entries(value, &self.entries)?,
+            "glyphs" => self.glyphs = parse_bool(value)?,
+            "x" => self.position.x = parse_coordinate(value)?,
+            "y" => self.position.y = parse_coordinate(value)?,
+            "fg_color" => self.style.fg = parse_opt_color(value)?,
+            "bg_color" => self.style.bg = parse_opt_color(value)?,
+            "first_frame" => self.frames.start = value.parse()?,
+            "last_frame" => self.frames.end = value.parse()?,
+            "z_order" => self.z_order = value.parse()?,
+            _ => bail!("Unknown property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn get_coord(&self, name: &str) -> Option<Coordinate> {
+        match name {
+            "x" => Some(self.position.x.clone()),
+            "y" => Some(self.position.y.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_coord(&mut self, name: &str, coord: Coordinate) -> Result<()> {
+        match name {
+            "x" => self.position.x = coord,
+            "y" => self.position.y = coord,
+            _ => bail!("Unknown coordinate property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn origin_x(&self) -> f64 { coord_val_f(&self.position.x) }
+    fn origin_y(&self) -> f64 { coord_val_f(&self.position.y) }
+    fn dim_x(&self) -> f64 {
+        self.lines(usize::MAX).iter().map(|(l, _)| l.chars().count()).max().unwrap_or(0) as f64
+    }
+    fn dim_y(&self) -> f64 { self.lines(usize::MAX).len() as f64 }
+    fn set_origin_x(&mut self, v: f64) { set_fixed(&mut self.position.x, v); }
+    fn set_origin_y(&mut self, v: f64) { set_fixed(&mut self.position.y, v); }
+    fn set_dim_x(&mut self, _v: f64) {} // follows the entries
+    fn set_dim_y(&mut self, _v: f64) {}
+
+    fn move_by(&mut self, dx: i32, dy: i32) {
+        adjust_coordinate(&mut self.position.x, dx);
+        adjust_coordinate(&mut self.position.y, dy);
+    }
+}
+
+/// A file tree's entries, one path per line, a folder's `open_at` after an `@`
+/// (`src/ @2`).
+fn format_tree_entries(entries: &[TreeEntry]) -> String {
+    entries
+        .iter()
+        .map(|e| if e.open_at > 0 { format!("{} @{}", e.path, e.open_at) } else { e.path.clone() })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parse [`format_tree_entries`] text; paths already in `old` keep their style.
+fn parse_tree_entries(s: &str, old: &[TreeEntry]) -> Result<Vec<TreeEntry>> {
+    s.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|l| {
+            let (path, open_at) = match l.rsplit_once(" @") {
+                Some((path, n)) => (path.trim_end(), n.trim().parse()?),
+                None => (l, 0),
+            };
+            let style = old.iter().find(|e| e.path == path).and_then(|e| e.style.clone());
+            Ok(TreeEntry { path: path.to_string(), style, open_at })
+        })
+        .collect()
+}
+impl Editable for Gantt {
+    fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        let bound = |b: Option<f64>| b.map(|v| v.to_string()).unwrap_or_default();
+        vec![
+            Property { name: "tasks", value: format_gantt_tasks(&self.tasks), kind: PropertyKind::Text },
+            Property { name: "width", value: self.width.to_string(), kind: PropertyKind::Number },
+            Property { name: "min", value: bound(self.min), kind: PropertyKind::Text },
+            Property { name: "max", value: bound(self.max), kind: PropertyKind::Text },
+            Property { name: "today", value: bound(self.today), kind: PropertyKind::Text },
+            Property { name: "border", value: self.border.as_str().to_string(), kind: PropertyKind::BorderStyle },
+            Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
+            Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
+            Property { name: "fg_color", value: format_opt_color(&self.style.fg), kind: PropertyKind::Color },
+            Property { name: "bg_color", value: format_opt_color(&self.style.bg), kind: PropertyKind::Color },
+            Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
+            Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
+            Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        let bound = |s: &str| -> Result<Option<f64>> {
+            let s = s.trim();
+            Ok(if s.is_empty() { None } else { Some(s.parse()?) })
+        };
+        match name {
+            "tasks" => self.tasks = parse_gantt_tasks(value)?,
+            "width" => self.width = value.trim().parse::<u16>()?.max(2),
+            "min" => self.min = bound(value)?,
+            "max" => self.max = bound(value)?,
+            "today" => self.today = bound(value)?,
+            "border" => {
+                self.border = BorderStyle::from_str_opt(value)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown border style: {value}"))?
+            }
+            "x" => self.position.x = parse_coordinate(value)?,
+            "y" => self.position.y = parse_coordinate(value)?,
+            "fg_color" => self.style.fg = parse_opt_color(value)?,
+            "bg_color" => self.style.bg = parse_opt_color(value)?,
+            "first_frame" => self.frames.start = value.parse()?,
+            "last_frame" => self.frames.end = value.parse()?,
+            "z_order" => self.z_order = value.parse()?,
+            _ => bail!("Unknown property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn get_coord(&self, name: &str) -> Option<Coordinate> {
+        match name {
+            "x" => Some(self.position.x.clone()),
+            "y" => Some(self.position.y.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_coord(&mut self, name: &str, coord: Coordinate) -> Result<()> {
+        match name {
+            "x" => self.position.x = coord,
+            "y" => self.position.y = coord,
+            _ => bail!("Unknown coordinate property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn origin_x(&self) -> f64 { coord_val_f(&self.position.x) }
+    fn origin_y(&self) -> f64 { coord_val_f(&self.position.y) }
+    fn dim_x(&self) -> f64 { self.size().0 as f64 }
+    fn dim_y(&self) -> f64 { self.size().1 as f64 }
+    fn set_origin_x(&mut self, v: f64) { set_fixed(&mut self.position.x, v); }
+    fn set_origin_y(&mut self, v: f64) { set_fixed(&mut self.position.y, v); }
+    fn set_dim_x(&mut self, v: f64) { self.width = v.max(2.0) as u16; }
+    fn set_dim_y(&mut self, _v: f64) {} // one row per lane
+
+    fn move_by(&mut self, dx: i32, dy: i32) {
+        adjust_coordinate(&mut self.position.x, dx);
+        adjust_coordinate(&mut self.position.y, dy);
+    }
+}
+
+/// A Gantt chart's tasks, one per line as `label, start, end[, lane[, colour]]`.
+fn format_gantt_tasks(tasks: &[GanttTask]) -> String {
+    tasks
+        .iter()
+        .map(|t| {
+            let mut fields = vec![t.label.clone(), t.start.to_string(), t.end.to_string()];
+            if !t.lane.is_empty() || t.color.is_some() {
+                fields.push(t.lane.clone());
+            }
+            if t.color.is_some() {
+                fields.push(format_opt_color(&t.color));
+            }
+            fields.join(", ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn parse_gantt_tasks(s: &str) -> Result<Vec<GanttTask>> {
+    s.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let fields: Vec<&str> = l.split(',').map(str::trim).collect();
+            let [label, start, end, rest @ ..] = fields.as_slice() else {
+                bail!("expected label, start, end[, lane[, colour]], got {l}");
+            };
+            Ok(GanttTask {
+                label: label.to_string(),
+                start: start.parse()?,
+                end: end.parse()?,
+                lane: rest.first().map(|l| l.to_string()).unwrap_or_default(),
+                color: rest.get(1).map(|c| parse_opt_color(c)).transpose()?.flatten(),
+            })
+        })
+        .collect()
+}
+impl Editable for PieChart {
+    fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        vec![
+            Property { name: "segments", value: format_pie_segments(&self.segments), kind: PropertyKind::Text },
+            Property { name: "diameter", value: self.diameter.to_string(), kind: PropertyKind::Number },
+            Property { name: "hole", value: self.hole.to_string(), kind: PropertyKind::Number },
+            Property { name: "ch", value: self.ch.to_string(), kind: PropertyKind::Text },
+            Property { name: "legend", value: self.legend.to_string(), kind: PropertyKind::Bool },
+            Property { name: "sweep", value: self.sweep.to_string(), kind: PropertyKind::Bool },
+            Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
+            Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
+            Property { name: "fg_color", value: format_opt_color(&self.style.fg), kind: PropertyKind::Color },
+            Property { name: "bg_color", value: format_opt_color(&self.style.bg), kind: PropertyKind::Color },
+            Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
+            Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
+            Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "segments" => self.segments = parse_pie_segments(value)?,
+            "diameter" => self.diameter = value.trim().parse::<u16>()?.max(1),
+            "hole" => self.hole = value.trim().parse::<f64>()?.clamp(0.0, 0.95),
+            "ch" => self.ch = parse_char(value)?,
+            "legend" => self.legend = parse_bool(value)?,
+            "sweep" => self.sweep = parse_bool(value)?,
+            "x" => self.position.x = parse_coordinate(value)?,
+            "y" => self.position.y = parse_coordinate(value)?,
+            "fg_color" => self.style.fg = parse_opt_color(value)?,
+            "bg_color" => self.style.bg = parse_opt_color(value)?,
+            "first_frame" => self.frames.start = value.parse()?,
+            "last_frame" => self.frames.end = value.parse()?,
+            "z_order" => self.z_order = value.parse()?,
+            _ => bail!("Unknown property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn get_coord(&self, name: &str) -> Option<Coordinate> {
+        match name {
+            "x" => Some(self.position.x.clone()),
+            "y" => Some(self.position.y.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_coord(&mut self, name: &str, coord: Coordinate) -> Result<()> {
+        match name {
+            "x" => self.position.x = coord,
+            "y" => self.position.y = coord,
+            _ => bail!("Unknown coordinate property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn origin_x(&self) -> f64 { coord_val_f(&self.position.x) }
+    fn origin_y(&self) -> f64 { coord_val_f(&self.position.y) }
+    fn dim_x(&self) -> f64 { Circle::columns(self.diameter) as f64 }
+    fn dim_y(&self) -> f64 { self.diameter as f64 }
+    fn set_origin_x(&mut self, v: f64) { set_fixed(&mut self.position.x, v); }
+    fn set_origin_y(&mut self, v: f64) { set_fixed(&mut self.position.y, v); }
+    fn set_dim_x(&mut self, v: f64) { self.diameter = Circle::rows_for_width(v).round().max(1.0) as u16; }
+    fn set_dim_y(&mut self, v: f64) { self.diameter = v.max(1.0) as u16; }
+
+    fn move_by(&mut self, dx: i32, dy: i32) {
+        adjust_coordinate(&mut self.position.x, dx);
+        adjust_coordinate(&mut self.position.y, dy);
+    }
+}
+
+/// A pie chart's segments, one per line as `label, value[, colour]`.
+fn format_pie_segments(segments: &[PieSegment]) -> String {
+    segments
+        .iter()
+        .map(|s| match &s.color {
+            Some(_) => format!("{}, {}, {}", s.label, s.value, format_opt_color(&s.color)),
+            None => format!("{}, {}", s.label, s.value),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn parse_pie_segments(s: &str) -> Result<Vec<PieSegment>> {
+    s.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let fields: Vec<&str> = l.split(',').map(str::trim).collect();
+            let [label, value, rest @ ..] = fields.as_slice() else {
+                bail!("expected label, value[, colour], got {l}");
+            };
+            Ok(PieSegment {
+                label: label.to_string(),
+                value: value.parse()?,
+                color: rest.first().map(|c| parse_opt_color(c)).transpose()?.flatten(),
+            })
+        })
+        .collect()
+}
+impl Editable for ScatterPlot {
+    fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        let bound = |b: Option<f64>| b.map(|v| v.to_string()).unwrap_or_default();
+        vec![
+            Property { name: "points", value: format_points(&self.points), kind: PropertyKind::Text },
+            Property { name: "width", value: self.width.to_string(), kind: PropertyKind::Number },
+            Property { name: "height", value: self.height.to_string(), kind: PropertyKind::Number },
+            Property { name: "x_min", value: bound(self.x_min), kind: PropertyKind::Text },
+            Property { name: "x_max", value: bound(self.x_max), kind: PropertyKind::Text },
+            Property { name: "y_min", value: bound(self.y_min), kind: PropertyKind::Text },
+            Property { name: "y_max", value: bound(self.y_max), kind: PropertyKind::Text },
+            Property { name: "x_label", value: self.x_label.clone(), kind: PropertyKind::Text },
+            Property { name: "y_label", value: self.y_label.clone(), kind: PropertyKind::Text },
+            Property { name: "ticks", value: self.ticks.to_string(), kind: PropertyKind::Number },
+            Property { name: "point_color", value: format_opt_color(&self.color), kind: PropertyKind::Color },
+            Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
+            Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
+            Property { name: "fg_color", value: format_opt_color(&self.style.fg), kind: PropertyKind::Color },
+            Property { name: "bg_color", value: format_opt_color(&self.style.bg), kind: PropertyKind::Color },
+            Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
+            Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
+            Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        let bound = |s: &str| -> Result<Option<f64>> {
+            let s = s.trim();
+            Ok(if s.is_empty() { None } else { Some(s.parse()?) })
+        };
+        match name {
+            "points" => self.points = parse_points(value)?,
+            "width" => self.width = value.trim().parse::<u16>()?.max(2),
+            "height" => self.height = value.trim().parse::<u16>()?.max(2),
+            "x_min" => self.x_min = bound(value)?,
+            "x_max" => self.x_max = bound(value)?,
+            "y_min" => self.y_min = bound(value)?,
+            "y_max" => self.y_max = bound(value)?,
+            "x_label" => self.x_label = value.to_string(),
+            "y_label" => self.y_label = value.to_string(),
+            "ticks" => self.ticks = value.trim().parse()?,
+            "point_color" => self.color = parse_opt_color(value)?,
+            "x" => self.position.x = parse_coordinate(value)?,
+            "y" => self.position.y = parse_coordinate(value)?,
+            "fg_color" => self.style.fg = parse_opt_color(value)?,
+            "bg_color" => self.style.bg = parse_opt_color(value)?,
+            "first_frame" => self.frames.start = value.parse()?,
+            "last_frame" => self.frames.end = value.parse()?,
+            "z_order" => self.z_order = value.parse()?,
+            _ => bail!("Unknown property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn get_coord(&self, name: &str) -> Option<Coordinate> {
+        match name {
+            "x" => Some(self.position.x.clone()),
+            "y" => Some(self.position.y.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_coord(&mut self, name: &str, coord: Coordinate) -> Result<()> {
+        match name {
+            "x" => self.position.x = coord,
+            "y" => self.position.y = coord,
+            _ => bail!("Unknown coordinate property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn origin_x(&self) -> f64 { coord_val_f(&self.position.x) }
+    fn origin_y(&self) -> f64 { coord_val_f(&self.position.y) }
+    fn dim_x(&self) -> f64 { self.width as f64 }
+    fn dim_y(&self) -> f64 { self.height as f64 }
+    fn set_origin_x(&mut self, v: f64) { set_fixed(&mut self.position.x, v); }
+    fn set_origin_y(&mut self, v: f64) { set_fixed(&mut self.position.y, v); }
+    fn set_dim_x(&mut self, v: f64) { self.width = v.max(2.0) as u16; }
+    fn set_dim_y(&mut self, v: f64) { self.height = v.max(2.0) as u16; }
+
+    fn move_by(&mut self, dx: i32, dy: i32) {
+        adjust_coordinate(&mut self.position.x, dx);
+        adjust_coordinate(&mut self.position.y, dy);
+    }
+}
+
+/// A scatter plot's points, one per line as `x, y`.
+fn format_points(points: &[[f64; 2]]) -> String {
+    points.iter().map(|[x, y]| format!("{x}, {y}")).collect::<Vec<_>>().join("\n")
+}
+
+fn parse_points(s: &str) -> Result<Vec<[f64; 2]>> {
+    s.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let Some((x, y)) = l.split_once(',') else {
+                bail!("expected x, y, got {l}");
+            };
+            Ok([x.trim().parse()?, y.trim().parse()?])
+        })
+        .collect()
+}
+impl Editable for Marquee {
+    fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        vec![
+            Property { name: "text", value: self.text.clone(), kind: PropertyKind::Text },
+            Property { name: "width", value: self.width.to_string(), kind: PropertyKind::Number },
+            Property { name: "speed", value: self.speed.to_string(), kind: PropertyKind::Number },
+            Property { name: "gap", value: self.gap.to_string(), kind: PropertyKind::Number },
+            Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
+            Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
+            Property { name: "fg_color", value: format_opt_color(&self.style.fg), kind: PropertyKind::Color },
+            Property { name: "bg_color", value: format_opt_color(&self.style.bg), kind: PropertyKind::Color },
+            Property { name: "bold", value: self.style.bold.to_string(), kind: PropertyKind::Bool },
+            Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
+            Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
+            Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "text" => self.text = value.to_string(),
+            "width" => self.width = value.trim().parse()?,
+            "speed" => self.speed = value.trim().parse()?,
+            "gap" => self.gap = value.trim().parse()?,
+            "x" => self.position.x = parse_coordinate(value)?,
+            "y" => self.position.y = parse_coordinate(value)?,
+            "fg_color" => self.style.fg = parse_opt_color(value)?,
+            "bg_color" => self.style.bg = parse_opt_color(value)?,
+            "bold" => self.style.bold = parse_bool(value)?,
+            "first_frame" => self.frames.start = value.parse()?,
+            "last_frame" => self.frames.end = value.parse()?,
+            "z_order" => self.z_order = value.parse()?,
+            _ => bail!("Unknown property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn get_coord(&self, name: &str) -> Option<Coordinate> {
+        match name {
+            "x" => Some(self.position.x.clone()),
+            "y" => Some(self.position.y.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_coord(&mut self, name: &str, coord: Coordinate) -> Result<()> {
+        match name {
+            "x" => self.position.x = coord,
+            "y" => self.position.y = coord,
+            _ => bail!("Unknown coordinate property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn origin_x(&self) -> f64 { coord_val_f(&self.position.x) }
+    fn origin_y(&self) -> f64 { coord_val_f(&self.position.y) }
+    fn dim_x(&self) -> f64 { self.width as f64 }
+    fn dim_y(&self) -> f64 { 1.0 }
+    fn set_origin_x(&mut self, v: f64) { set_fixed(&mut self.position.x, v); }
+    fn set_origin_y(&mut self, v: f64) { set_fixed(&mut self.position.y, v); }
+    fn set_dim_x(&mut self, v: f64) { self.width = v.max(1.0) as u16; }
+    fn set_dim_y(&mut self, _v: f64) {} // one line
+
+    fn move_by(&mut self, dx: i32, dy: i32) {
+        adjust_coordinate(&mut self.position.x, dx);
+        adjust_coordinate(&mut self.position.y, dy);
+    }
+}
+impl Editable for Spinner {
+    fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        vec![
+            Property { name: "glyphs", value: self.glyphs.clone(), kind: PropertyKind::Text },
+            Property { name: "text", value: self.text.clone(), kind: PropertyKind::Text },
+            Property { name: "done", value: self.done.map(String::from).unwrap_or_default(), kind: PropertyKind::Text },
+            Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
+            Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
+            Property { name: "fg_color", value: format_opt_color(&self.style.fg), kind: PropertyKind::Color },
+            Property { name: "bg_color", value: format_opt_color(&self.style.bg), kind: PropertyKind::Color },
+            Property { name: "bold", value: self.style.bold.to_string(), kind: PropertyKind::Bool },
+            Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
+            Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
+            Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "glyphs" => {
+                let glyphs: String = value.chars().filter(|c| !c.is_whitespace()).collect();
+                self.glyphs = if glyphs.is_empty() { default_spinner_glyphs() } else { glyphs };
+            }
+            "text" => self.text = value.to_string(),
+            "done" => self.done = parse_opt_char(value)?,
+            "x" => self.position.x = parse_coordinate(value)?,
+            "y" => self.position.y = parse_coordinate(value)?,
+            "fg_color" => self.style.fg = parse_opt_color(value)?,
+            "bg_color" => self.style.bg = parse_opt_color(value)?,
+            "bold" => self.style.bold = parse_bool(value)?,
+            "first_frame" => self.frames.start = value.parse()?,
+            "last_frame" => self.frames.end = value.parse()?,
+            "z_order" => self.z_order = value.parse()?,
+            _ => bail!("Unknown property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn get_coord(&self, name: &str) -> Option<Coordinate> {
+        match name {
+            "x" => Some(self.position.x.clone()),
+            "y" => Some(self.position.y.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_coord(&mut self, name: &str, coord: Coordinate) -> Result<()> {
+        match name {
+            "x" => self.position.x = coord,
+            "y" => self.position.y = coord,
+            _ => bail!("Unknown coordinate property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn origin_x(&self) -> f64 { coord_val_f(&self.position.x) }
+    fn origin_y(&self) -> f64 { coord_val_f(&self.position.y) }
+    fn dim_x(&self) -> f64 { (self.text_offset() as usize + self.text.chars().map(char_width).sum::<usize>()) as f64 }
+    fn dim_y(&self) -> f64 { 1.0 }
+    fn set_origin_x(&mut self, v: f64) { set_fixed(&mut self.position.x, v); }
+    fn set_origin_y(&mut self, v: f64) { set_fixed(&mut self.position.y, v); }
+    fn set_dim_x(&mut self, _v: f64) {} // follows the text
+    fn set_dim_y(&mut self, _v: f64) {} // one line
+
+    fn move_by(&mut self, dx: i32, dy: i32) {
+        adjust_coordinate(&mut self.position.x, dx);
+        adjust_coordinate(&mut self.position.y, dy);
+    }
+}
+impl Editable for Connector {
+    fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        vec![
+            Property { name: "points", value: format_connector_points(&self.points), kind: PropertyKind::Text },
+            Property { name: "line", value: self.line.as_str().to_string(), kind: PropertyKind::BorderStyle },
+            Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
+            Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
+            Property { name: "fg_color", value: format_opt_color(&self.style.fg), kind: PropertyKind::Color },
+            Property { name: "bg_color", value: format_opt_color(&self.style.bg), kind: PropertyKind::Color },
+            Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
+            Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
+            Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "points" => self.points = parse_connector_points(value)?,
+            "line" => {
+                self.line = BorderStyle::from_str_opt(value)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown border style: {value}"))?
+            }
+            "x" => self.position.x = parse_coordinate(value)?,
+            "y" => self.position.y = parse_coordinate(value)?,
+            "fg_color" => self.style.fg = parse_opt_color(value)?,
+            "bg_color" => self.style.bg = parse_opt_color(value)?,
+            "first_frame" => self.frames.start = value.parse()?,
+            "last_frame" => self.frames.end = value.parse()?,
+            "z_order" => self.z_order = value.parse()?,
+            _ => bail!("Unknown property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn get_coord(&self, name: &str) -> Option<Coordinate> {
+        match name {
+            "x" => Some(self.position.x.clone()),
+            "y" => Some(self.position.y.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_coord(&mut self, name: &str, coord: Coordinate) -> Result<()> {
+        match name {
+            "x" => self.position.x = coord,
+            "y" => self.position.y = coord,
+            _ => bail!("Unknown coordinate property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn origin_x(&self) -> f64 { coord_val_f(&self.position.x) }
+    fn origin_y(&self) -> f64 { coord_val_f(&self.position.y) }
+    fn dim_x(&self) -> f64 { self.size().0 as f64 }
+    fn dim_y(&self) -> f64 { self.size().1 as f64 }
+    fn set_origin_x(&mut self, v: f64) { set_fixed(&mut self.position.x, v); }
+    fn set_origin_y(&mut self, v: f64) { set_fixed(&mut self.position.y, v); }
+    fn set_dim_x(&mut self, _v: f64) {} // follows the points
+    fn set_dim_y(&mut self, _v: f64) {}
+
+    fn move_by(&mut self, dx: i32, dy: i32) {
+        adjust_coordinate(&mut self.position.x, dx);
+        adjust_coordinate(&mut self.position.y, dy);
+    }
+}
+
+/// A connector's points as `x,y` pairs separated by spaces (`0,0 8,0 8,3`).
+fn format_connector_points(points: &[[u16; 2]]) -> String {
+    points.iter().map(|[x, y]| format!("{x},{y}")).collect::<Vec<_>>().join(" ")
+}
+
+fn parse_connector_points(s: &str) -> Result<Vec<[u16; 2]>> {
+    s.split_whitespace()
+        .map(|p| {
+            let Some((x, y)) = p.split_once(',') else {
+                bail!("expected x,y, got {p}");
+            };
+            Ok([x.trim().parse()?, y.trim().parse()?])
+        })
+        .collect()
+}
+impl Editable for Keycap {
+    fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        vec![
+            Property { name: "keys", value: self.keys.join("+"), kind: PropertyKind::Text },
+            Property { name: "separator", value: self.separator.clone(), kind: PropertyKind::Text },
+            Property { name: "border", value: self.border.as_str().to_string(), kind: PropertyKind::BorderStyle },
+            Property { name: "compact", value: self.compact.to_string(), kind: PropertyKind::Bool },
+            Property { name: "cap_color", value: format_opt_color(&self.cap), kind: PropertyKind::Color },
+            Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
+            Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
+            Property { name: "fg_color", value: format_opt_color(&self.style.fg), kind: PropertyKind::Color },
+            Property { name: "bg_color", value: format_opt_color(&self.style.bg), kind: PropertyKind::Color },
+            Property { name: "bold", value: self.style.bold.to_string(), kind: PropertyKind::Bool },
+            Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
+            Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
+            Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "keys" => self.keys = parse_keys(value),
+            "separator" => self.separator = value.to_string(),
+            "border" => {
+                self.border = BorderStyle::from_str_opt(value)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown border style: {value}"))?
+            }
+            "compact" => self.compact = parse_bool(value)?,
+            "cap_color" => self.cap = parse_opt_color(value)?,
+            "x" => self.position.x = parse_coordinate(value)?,
+            "y" => self.position.y = parse_coordinate(value)?,
+            "fg_color" => self.style.fg = parse_opt_color(value)?,
+            "bg_color" => self.style.bg = parse_opt_color(value)?,
+            "bold" => self.style.bold = parse_bool(value)?,
+            "first_frame" => self.frames.start = value.parse()?,
+            "last_frame" => self.frames.end = value.parse()?,
+            "z_order" => self.z_order = value.parse()?,
+            _ => bail!("Unknown property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn get_coord(&self, name: &str) -> Option<Coordinate> {
+        match name {
+            "x" => Some(self.position.x.clone()),
+            "y" => Some(self.position.y.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_coord(&mut self, name: &str, coord: Coordinate) -> Result<()> {
+        match name {
+            "x" => self.position.x = coord,
+            "y" => self.position.y = coord,
+            _ => bail!("Unknown coordinate property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn origin_x(&self) -> f64 { coord_val_f(&self.position.x) }
+    fn origin_y(&self) -> f64 { coord_val_f(&self.position.y) }
+    fn dim_x(&self) -> f64 { self.size().0 as f64 }
+    fn dim_y(&self) -> f64 { self.size().1 as f64 }
+    fn set_origin_x(&mut self, v: f64) { set_fixed(&mut self.position.x, v); }
+    fn set_origin_y(&mut self, v: f64) { set_fixed(&mut self.position.y, v); }
+    fn set_dim_x(&mut self, _v: f64) {} // follows the keys
+    fn set_dim_y(&mut self, _v: f64) {}
+
+    fn move_by(&mut self, dx: i32, dy: i32) {
+        adjust_coordinate(&mut self.position.x, dx);
+        adjust_coordinate(&mut self.position.y, dy);
+    }
+}
+
+impl Editable for Heatmap {
+    fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        let bound = |b: Option<f64>| b.map(|v| v.to_string()).unwrap_or_default();
+        vec![
+            Property { name: "values", value: format_matrix(&self.values), kind: PropertyKind::Text },
+            Property { name: "row_labels", value: self.row_labels.join(", "), kind: PropertyKind::Text },
+            Property { name: "col_labels", value: self.col_labels.join(", "), kind: PropertyKind::Text },
+            Property { name: "cell_width", value: self.cell_width.to_string(), kind: PropertyKind::Number },
+            Property { name: "min", value: bound(self.min), kind: PropertyKind::Text },
+            Property { name: "max", value: bound(self.max), kind: PropertyKind::Text },
+            Property { name: "low_color", value: format_opt_color(&Some(self.low.clone())), kind: PropertyKind::Color },
+            Property { name: "high_color", value: format_opt_color(&Some(self.high.clone())), kind: PropertyKind::Color },
+            Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
+            Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
+            Property { name: "fg_color", value: format_opt_color(&self.style.fg), kind: PropertyKind::Color },
+            Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
+            Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
+            Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        let labels = |s: &str| s.split(',').map(|l| l.trim().to_string()).filter(|l| !l.is_empty()).collect();
+        let bound = |s: &str| -> Result<Option<f64>> {
+            let s = s.trim();
+            Ok(if s.is_empty() { None } else { Some(s.parse()?) })
+        };
+        match name {
+            "values" => self.values = parse_matrix(value)?,
+            "row_labels" => self.row_labels = labels(value),
+            "col_labels" => self.col_labels = labels(value),
+            "cell_width" => self.cell_width = value.trim().parse::<u16>()?.max(1),
+            "min" => self.min = bound(value)?,
+            "max" => self.max = bound(value)?,
+            "low_color" => self.low = parse_opt_color(value)?.unwrap_or_else(default_heatmap_low),
+            "high_color" => self.high = parse_opt_color(value)?.unwrap_or_else(default_heatmap_high),
+            "x" => self.position.x = parse_coordinate(value)?,
+            "y" => self.position.y = parse_coordinate(value)?,
+            "fg_color" => self.style.fg = parse_opt_color(value)?,
+            "first_frame" => self.frames.start = value.parse()?,
+            "last_frame" => self.frames.end = value.parse()?,
+            "z_order" => self.z_order = value.parse()?,
+            _ => bail!("Unknown property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn get_coord(&self, name: &str) -> Option<Coordinate> {
+        match name {
+            "x" => Some(self.position.x.clone()),
+            "y" => Some(self.position.y.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_coord(&mut self, name: &str, coord: Coordinate) -> Result<()> {
+        match name {
+            "x" => self.position.x = coord,
+            "y" => self.position.y = coord,
+            _ => bail!("Unknown coordinate property: {name}"),
         }
+        Ok(())
+    }
+
+    fn origin_x(&self) -> f64 { coord_val_f(&self.position.x) }
+    fn origin_y(&self) -> f64 { coord_val_f(&self.position.y) }
+    fn dim_x(&self) -> f64 { self.size().0 as f64 }
+    fn dim_y(&self) -> f64 { self.size().1 as f64 }
+    fn set_origin_x(&mut self, v: f64) { set_fixed(&mut self.position.x, v); }
+    fn set_origin_y(&mut self, v: f64) { set_fixed(&mut self.position.y, v); }
+    fn set_dim_x(&mut self, _v: f64) {} // follows the data
+    fn set_dim_y(&mut self, _v: f64) {}
+
+    fn move_by(&mut self, dx: i32, dy: i32) {
+        adjust_coordinate(&mut self.position.x, dx);
+        adjust_coordinate(&mut self.position.y, dy);
     }
 }
 
-impl Editable for Header {
+/// A heatmap's values: rows split by `;`, values within a row by `,`.
+fn format_matrix(values: &[Vec<f64>]) -> String {
+    values
+        .iter()
+        .map(|row| row.iter().map(f64::to_string).collect::<Vec<_>>().join(", "))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn parse_matrix(s: &str) -> Result<Vec<Vec<f64>>> {
+    s.split(';')
+        .map(str::trim)
+        .filter(|row| !row.is_empty())
+        .map(|row| row.split(',').map(|v| Ok(v.trim().parse()?)).collect())
+        .collect()
+}
+impl Editable for Toc {
     fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        let highlight = self.highlight.as_ref().and_then(|h| h.fg.clone());
         vec![
-            Property { name: "text", value: self.text.clone(), kind: PropertyKind::Text },
+            Property { name: "numbers", value: self.numbers.to_string(), kind: PropertyKind::Bool },
+            Property { name: "highlight_color", value: format_opt_color(&highlight), kind: PropertyKind::Color },
             Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
             Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
-            Property { name: "draw_char", value: self.ch.to_string(), kind: PropertyKind::Text },
             Property { name: "fg_color", value: format_opt_color(&self.style.fg), kind: PropertyKind::Color },
             Property { name: "bg_color", value: format_opt_color(&self.style.bg), kind: PropertyKind::Color },
             Property { name: "bold", value: self.style.bold.to_string(), kind: PropertyKind::Bool },
-            Property { name: "dimmed", value: self.style.dim.to_string(), kind: PropertyKind::Bool },
             Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
             Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
             Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
@@ -1037,14 +2601,17 @@ impl Editable for Header {
 
     fn set(&mut self, name: &str, value: &str) -> Result<()> {
         match name {
-            "text" => self.text = value.to_string(),
+            "numbers" => self.numbers = parse_bool(value)?,
+            // A highlight colour turns highlighting on; `none` turns it off.
+            "highlight_color" => match parse_opt_color(value)? {
+                Some(fg) => self.highlight.get_or_insert_with(Style::default).fg = Some(fg),
+                None => self.highlight = None,
+            },
             "x" => self.position.x = parse_coordinate(value)?,
             "y" => self.position.y = parse_coordinate(value)?,
-            "draw_char" => self.ch = parse_char(value)?,
             "fg_color" => self.style.fg = parse_opt_color(value)?,
             "bg_color" => self.style.bg = parse_opt_color(value)?,
             "bold" => self.style.bold = parse_bool(value)?,
-            "dimmed" => self.style.dim = parse_bool(value)?,
             "first_frame" => self.frames.start = value.parse()?,
             "last_frame" => self.frames.end = value.parse()?,
             "z_order" => self.z_order = value.parse()?,
@@ -1072,8 +2639,9 @@ impl Editable for Header {
 
     fn origin_x(&self) -> f64 { coord_val_f(&self.position.x) }
     fn origin_y(&self) -> f64 { coord_val_f(&self.position.y) }
+    // Sized by the deck's sections, which an object can't see.
     fn dim_x(&self) -> f64 { 0.0 }
-    fn dim_y(&self) -> f64 { 1.0 }
+    fn dim_y(&self) -> f64 { 0.0 }
     fn set_origin_x(&mut self, v: f64) { set_fixed(&mut self.position.x, v); }
     fn set_origin_y(&mut self, v: f64) { set_fixed(&mut self.position.y, v); }
     fn set_dim_x(&mut self, _v: f64) {}
@@ -1083,9 +2651,7 @@ impl Editable for Header {
         adjust_coordinate(&mut self.position.x, dx);
         adjust_coordinate(&mut self.position.y, dy);
     }
-    // resize_by / shrink_by: default no-op (a header has no resizable box).
 }
-
 impl Editable for Arrow {
     fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
         vec![
@@ -1310,7 +2876,7 @@ impl Editable for Table {
             let pct: f64 = value.trim().trim_end_matches('%').parse()
                 .map_err(|_| anyhow::anyhow!("Invalid percentage: {value}"))?;
             if col_idx < self.col_widths.len() {
-                self.col_widths[col_idx] = (pct / 100.0).max(0.01).min(1.0);
+                self.col_widths[col_idx] = (pct / 100.0).clamp(0.01, 1.0);
             }
             return Ok(());
         }
@@ -1488,6 +3054,132 @@ impl Editable for Art {
     // resize_by / shrink_by: default no-op (art is sized by its content).
 }
 
+impl Editable for AnsiArt {
+    fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        vec![
+            Property { name: "name", value: self.name.clone(), kind: PropertyKind::Text },
+            Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
+            Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
+            Property { name: "wrap_width", value: self.width.to_string(), kind: PropertyKind::Number },
+            Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
+            Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
+            Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "name" => self.name = value.to_string(),
+            "x" => self.position.x = parse_coordinate(value)?,
+            "y" => self.position.y = parse_coordinate(value)?,
+            "wrap_width" => self.width = value.parse::<u16>()?.max(1),
+            "first_frame" => self.frames.start = value.parse()?,
+            "last_frame" => self.frames.end = value.parse()?,
+            "z_order" => self.z_order = value.parse()?,
+            _ => bail!("Unknown property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn get_coord(&self, name: &str) -> Option<Coordinate> {
+        match name {
+            "x" => Some(self.position.x.clone()),
+            "y" => Some(self.position.y.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_coord(&mut self, name: &str, coord: Coordinate) -> Result<()> {
+        match name {
+            "x" => self.position.x = coord,
+            "y" => self.position.y = coord,
+            _ => bail!("Unknown coordinate property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn origin_x(&self) -> f64 { coord_val_f(&self.position.x) }
+    fn origin_y(&self) -> f64 { coord_val_f(&self.position.y) }
+    fn dim_x(&self) -> f64 { self.size().0 as f64 }
+    fn dim_y(&self) -> f64 { self.size().1 as f64 }
+    fn set_origin_x(&mut self, v: f64) { set_fixed(&mut self.position.x, v); }
+    fn set_origin_y(&mut self, v: f64) { set_fixed(&mut self.position.y, v); }
+    fn set_dim_x(&mut self, _v: f64) {} // sized by its content and wrap width
+    fn set_dim_y(&mut self, _v: f64) {}
+
+    fn move_by(&mut self, dx: i32, dy: i32) {
+        adjust_coordinate(&mut self.position.x, dx);
+        adjust_coordinate(&mut self.position.y, dy);
+    }
+}
+
+impl Editable for Cast {
+    fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
+        let unset = |v: Option<u16>| v.map(|v| v.to_string()).unwrap_or_default();
+        vec![
+            Property { name: "name", value: self.name.clone(), kind: PropertyKind::Text },
+            Property { name: "x", value: format_coordinate(&self.position.x), kind: PropertyKind::Coordinate },
+            Property { name: "y", value: format_coordinate(&self.position.y), kind: PropertyKind::Coordinate },
+            Property { name: "start_secs", value: self.start.to_string(), kind: PropertyKind::Number },
+            Property { name: "end_secs", value: self.end.map(|t| t.to_string()).unwrap_or_default(), kind: PropertyKind::Number },
+            Property { name: "width", value: unset(self.width), kind: PropertyKind::Number },
+            Property { name: "height", value: unset(self.height), kind: PropertyKind::Number },
+            Property { name: "first_frame", value: self.frames.start.to_string(), kind: PropertyKind::Number },
+            Property { name: "last_frame", value: self.frames.end.to_string(), kind: PropertyKind::Number },
+            Property { name: "z_order", value: self.z_order.to_string(), kind: PropertyKind::Number },
+        ]
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        let value = value.trim();
+        match name {
+            "name" => self.name = value.to_string(),
+            "x" => self.position.x = parse_coordinate(value)?,
+            "y" => self.position.y = parse_coordinate(value)?,
+            "start_secs" => self.start = value.parse::<f64>()?.max(0.0),
+            "end_secs" => self.end = if value.is_empty() { None } else { Some(value.parse::<f64>()?.max(0.0)) },
+            "width" => self.width = if value.is_empty() { None } else { Some(value.parse::<u16>()?.max(1)) },
+            "height" => self.height = if value.is_empty() { None } else { Some(value.parse::<u16>()?.max(1)) },
+            "first_frame" => self.frames.start = value.parse()?,
+            "last_frame" => self.frames.end = value.parse()?,
+            "z_order" => self.z_order = value.parse()?,
+            _ => bail!("Unknown property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn get_coord(&self, name: &str) -> Option<Coordinate> {
+        match name {
+            "x" => Some(self.position.x.clone()),
+            "y" => Some(self.position.y.clone()),
+            _ => None,
+        }
+    }
+
+    fn set_coord(&mut self, name: &str, coord: Coordinate) -> Result<()> {
+        match name {
+            "x" => self.position.x = coord,
+            "y" => self.position.y = coord,
+            _ => bail!("Unknown coordinate property: {name}"),
+        }
+        Ok(())
+    }
+
+    fn origin_x(&self) -> f64 { coord_val_f(&self.position.x) }
+    fn origin_y(&self) -> f64 { coord_val_f(&self.position.y) }
+    fn dim_x(&self) -> f64 { self.size().0 as f64 }
+    fn dim_y(&self) -> f64 { self.size().1 as f64 }
+    fn set_origin_x(&mut self, v: f64) { set_fixed(&mut self.position.x, v); }
+    fn set_origin_y(&mut self, v: f64) { set_fixed(&mut self.position.y, v); }
+    fn set_dim_x(&mut self, _v: f64) {} // sized by the recording and its crop
+    fn set_dim_y(&mut self, _v: f64) {}
+
+    fn move_by(&mut self, dx: i32, dy: i32) {
+        adjust_coordinate(&mut self.position.x, dx);
+        adjust_coordinate(&mut self.position.y, dy);
+    }
+}
+
 impl Editable for Morph {
     fn properties(&self, _ctx: &PropContext) -> Vec<Property> {
         vec![
@@ -1594,6 +3286,12 @@ pub fn get_properties(objects: &[SceneObject], object_index: usize) -> Vec<Prope
             }
         }
     }
+    // Every object type can sit on a named layer; empty means the base layer.
+    props.push(Property {
+        name: "layer",
+        value: objects[object_index].layer().unwrap_or("").to_string(),
+        kind: PropertyKind::Text,
+    });
     props
 }
 
@@ -1619,6 +3317,19 @@ pub fn common_properties(objects: &[SceneObject], members: &[usize]) -> Vec<Prop
         .collect()
 }
 
+/// Whether the members of a selection disagree on property `name` — the panel
+/// shows such a value as `(mixed)` rather than passing off the first member's
+/// as everyone's.
+pub fn is_mixed(objects: &[SceneObject], members: &[usize], name: &str) -> bool {
+    let mut values = members.iter().filter_map(|&m| {
+        get_properties(objects, m).into_iter().find(|p| p.name == name).map(|p| p.value)
+    });
+    let Some(first) = values.next() else {
+        return false;
+    };
+    values.any(|v| v != first)
+}
+
 /// Whether a property kind can be edited across a multi-object selection. The
 /// overlay-edited `Text`, the structural `GroupMember`/`TableColWidth`, and the
 /// non-editable `ReadOnly`/`Note` are excluded; everything else (coordinates,
@@ -1635,6 +3346,14 @@ fn is_bulk_editable_kind(kind: &PropertyKind) -> bool {
 }
 
 pub fn set_property(obj: &mut SceneObject, name: &str, value: &str) -> Result<()> {
+    if name == "layer" {
+        let layer = value.trim();
+        if layer.contains('\n') {
+            bail!("A layer name must be a single line");
+        }
+        obj.set_layer((!layer.is_empty()).then(|| layer.to_string()));
+        return Ok(());
+    }
     if name == "first_frame" {
         // Translate the user's 1-based slide number back to the 0-based start.
         let one_based: usize = match value.trim().parse() {
@@ -1667,6 +3386,36 @@ pub fn format_coordinate(coord: &Coordinate) -> String {
     }
 }
 
+/// A gauge reading as `12.5`, or `-5->40 (anim 1)` while an animation drives it.
+pub fn format_scalar(scalar: &Scalar) -> String {
+    match *scalar {
+        Scalar::Fixed(v) => v.to_string(),
+        Scalar::Animated { from, to, anim } => format!("{from}->{to} (anim {anim})"),
+    }
+}
+
+/// Parse [`format_scalar`]'s text back: a number, or `from->to (anim N)` to
+/// sweep the reading over animation `N`'s span.
+fn parse_scalar(s: &str) -> Result<Scalar> {
+    let s = s.trim();
+    if let Ok(v) = s.parse::<f64>() {
+        return Ok(Scalar::Fixed(v));
+    }
+    let parsed = s.split_once("->").and_then(|(from, rest)| {
+        let (to, anim) = rest.split_once("(anim")?;
+        let anim = anim.trim().strip_suffix(')')?;
+        Some(Scalar::Animated {
+            from: from.trim().parse().ok()?,
+            to: to.trim().parse().ok()?,
+            anim: anim.trim().parse().ok()?,
+        })
+    });
+    match parsed {
+        Some(scalar) => Ok(scalar),
+        None => bail!("Invalid value: {s} (use a number, or from->to (anim N))"),
+    }
+}
+
 fn parse_coordinate(s: &str) -> Result<Coordinate> {
     if let Ok(v) = s.parse::<f64>() {
         return Ok(Coordinate::Fixed(v.max(0.0)));
@@ -1761,6 +3510,37 @@ fn parse_opt_char(s: &str) -> Result<Option<char>> {
     }
 }
 
+/// The drop-shadow properties shared by `Rect` and `Label`: the on/off flag,
+/// then — while it is on — the offset and colour.
+fn shadow_properties(shadow: &Option<Shadow>) -> Vec<Property> {
+    let mut props = vec![Property { name: "shadow", value: shadow.is_some().to_string(), kind: PropertyKind::Bool }];
+    if let Some(s) = shadow {
+        props.extend([
+            Property { name: "shadow_dx", value: s.dx.to_string(), kind: PropertyKind::Number },
+            Property { name: "shadow_dy", value: s.dy.to_string(), kind: PropertyKind::Number },
+            Property { name: "shadow_color", value: format_opt_color(&s.style.fg), kind: PropertyKind::Color },
+        ]);
+    }
+    props
+}
+
+/// Set one of [`shadow_properties`]; editing the offset or colour turns the
+/// shadow on.
+fn set_shadow_property(shadow: &mut Option<Shadow>, name: &str, value: &str) -> Result<()> {
+    if name == "shadow" {
+        *shadow = if parse_bool(value)? { Some(shadow.take().unwrap_or_default()) } else { None };
+        return Ok(());
+    }
+    let s = shadow.get_or_insert_with(Shadow::default);
+    match name {
+        "shadow_dx" => s.dx = value.trim().parse()?,
+        "shadow_dy" => s.dy = value.trim().parse()?,
+        "shadow_color" => s.style.fg = parse_opt_color(value)?,
+        _ => bail!("Unknown property: {name}"),
+    }
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Coordinate / geometry helpers
 // ---------------------------------------------------------------------------
@@ -1835,6 +3615,62 @@ pub fn shrink_object(obj: &mut SceneObject, dw: i32, dh: i32) {
     as_editable_mut(obj).shrink_by(dw, dh);
 }
 
+// ---------------------------------------------------------------------------
+// Canvas resize
+// ---------------------------------------------------------------------------
+
+/// How a canvas resize treats the content already on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CanvasFit {
+    /// Leave every object where it is (anchored at the top-left corner).
+    TopLeft,
+    /// Shift everything by half the change, keeping it centred.
+    Center,
+    /// Rescale positions and sizes by the change in each axis.
+    Scale,
+}
+
+impl CanvasFit {
+    pub const ALL: [CanvasFit; 3] = [CanvasFit::TopLeft, CanvasFit::Center, CanvasFit::Scale];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CanvasFit::TopLeft => "top-left",
+            CanvasFit::Center => "center",
+            CanvasFit::Scale => "scale",
+        }
+    }
+}
+
+/// Refit `objects` to a canvas resized from `old` to `new` (width, height).
+/// Like [`move_object`], only Fixed coordinates follow; groups have no
+/// geometry of their own, so their members carry them.
+pub fn refit_objects(objects: &mut [SceneObject], old: (u16, u16), new: (u16, u16), fit: CanvasFit) {
+    match fit {
+        CanvasFit::TopLeft => {}
+        CanvasFit::Center => {
+            let dx = (new.0 as i32 - old.0 as i32) / 2;
+            let dy = (new.1 as i32 - old.1 as i32) / 2;
+            for obj in objects.iter_mut() {
+                move_object(obj, dx, dy);
+            }
+        }
+        CanvasFit::Scale => {
+            let sx = new.0 as f64 / old.0.max(1) as f64;
+            let sy = new.1 as f64 / old.1.max(1) as f64;
+            for obj in objects.iter_mut() {
+                let (ox, oy) = (object_origin_x_f(obj), object_origin_y_f(obj));
+                let (dx, dy) = (object_dim_x_f(obj), object_dim_y_f(obj));
+                set_object_origin_x_f(obj, (ox * sx).round());
+                set_object_origin_y_f(obj, (oy * sy).round());
+                // One-cell-thick things (a horizontal arrow, a rule) stay so.
+                if dx > 1.0 { set_object_dim_x_f(obj, (dx * sx).round().max(1.0)); }
+                if dy > 1.0 { set_object_dim_y_f(obj, (dy * sy).round().max(1.0)); }
+            }
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Group operations
 // ---------------------------------------------------------------------------
@@ -1864,7 +3700,7 @@ fn group_bounds_from_members(objects: &[SceneObject], members: &[usize]) -> (f64
 }
 
 /// Move all members of a group by (dx, dy).
-pub fn move_group(objects: &mut Vec<SceneObject>, group_idx: usize, dx: i32, dy: i32) {
+pub fn move_group(objects: &mut [SceneObject], group_idx: usize, dx: i32, dy: i32) {
     let members = match &objects[group_idx] {
         SceneObject::Group(g) => g.members.clone(),
         _ => return,
@@ -1876,6 +3712,101 @@ pub fn move_group(objects: &mut Vec<SceneObject>, group_idx: usize, dx: i32, dy:
     }
 }
 
+// ---------------------------------------------------------------------------
+// Align / distribute
+// ---------------------------------------------------------------------------
+
+/// Which edge or centre line [`align_objects`] lines a selection up on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    /// Horizontal centres on one column.
+    Center,
+    /// Vertical centres on one row.
+    Middle,
+}
+
+/// The axis [`distribute_objects`] spaces a selection along.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+/// Bounding box `(x, y, width, height)` of object `i` — a group's is its
+/// members' — or `None` for one with no place on the canvas (an animation, an
+/// auto-advance), which aligning leaves out.
+fn placed_bounds(objects: &[SceneObject], i: usize) -> Option<(f64, f64, f64, f64)> {
+    let obj = objects.get(i)?;
+    if let SceneObject::Group(_) = obj {
+        return Some(group_bounds(objects, i));
+    }
+    let placed = ["x", "y", "x1"].iter().any(|n| get_coord(obj, n).is_some());
+    placed.then(|| (object_origin_x_f(obj), object_origin_y_f(obj), object_dim_x_f(obj), object_dim_y_f(obj)))
+}
+
+/// Move object `i` (a group: its members) by (dx, dy).
+fn shift_placed(objects: &mut [SceneObject], i: usize, dx: i32, dy: i32) {
+    if matches!(objects[i], SceneObject::Group(_)) {
+        move_group(objects, i, dx, dy);
+    } else {
+        move_object(&mut objects[i], dx, dy);
+    }
+}
+
+/// Line `members` up on the edge or centre line `align` of their shared
+/// bounding box. Like [`move_object`], only Fixed coordinates move.
+pub fn align_objects(objects: &mut [SceneObject], members: &[usize], align: Align) {
+    let boxes: Vec<(usize, (f64, f64, f64, f64))> =
+        members.iter().filter_map(|&i| placed_bounds(objects, i).map(|b| (i, b))).collect();
+    let min = |f: fn(&(f64, f64, f64, f64)) -> f64| boxes.iter().map(|(_, b)| f(b)).fold(f64::INFINITY, f64::min);
+    let max = |f: fn(&(f64, f64, f64, f64)) -> f64| boxes.iter().map(|(_, b)| f(b)).fold(f64::NEG_INFINITY, f64::max);
+    let (left, top) = (min(|b| b.0), min(|b| b.1));
+    let (right, bottom) = (max(|b| b.0 + b.2), max(|b| b.1 + b.3));
+    for &(i, (x, y, w, h)) in &boxes {
+        let (dx, dy) = match align {
+            Align::Left => (left - x, 0.0),
+            Align::Right => (right - (x + w), 0.0),
+            Align::Center => ((left + right - w) / 2.0 - x, 0.0),
+            Align::Top => (0.0, top - y),
+            Align::Bottom => (0.0, bottom - (y + h)),
+            Align::Middle => (0.0, (top + bottom - h) / 2.0 - y),
+        };
+        shift_placed(objects, i, dx.floor() as i32, dy.floor() as i32);
+    }
+}
+
+/// Space `members` evenly along `axis`: the first and last (by position)
+/// stay put and the rest move so the gaps between neighbours are equal.
+pub fn distribute_objects(objects: &mut [SceneObject], members: &[usize], axis: Axis) {
+    let mut boxes: Vec<(usize, f64, f64)> = members
+        .iter()
+        .filter_map(|&i| placed_bounds(objects, i).map(|(x, y, w, h)| match axis {
+            Axis::Horizontal => (i, x, w),
+            Axis::Vertical => (i, y, h),
+        }))
+        .collect();
+    if boxes.len() < 3 {
+        return;
+    }
+    boxes.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let (first, last) = (boxes[0], boxes[boxes.len() - 1]);
+    let sizes: f64 = boxes.iter().map(|b| b.2).sum();
+    let gap = (last.1 + last.2 - first.1 - sizes) / (boxes.len() - 1) as f64;
+    let mut at = first.1 + first.2 + gap;
+    for &(i, start, size) in &boxes[1..boxes.len() - 1] {
+        let d = (at.round() - start) as i32;
+        match axis {
+            Axis::Horizontal => shift_placed(objects, i, d, 0),
+            Axis::Vertical => shift_placed(objects, i, 0, d),
+        }
+        at += size + gap;
+    }
+}
+
 /// Scale all members of a group so the bounding box changes by (dw, dh).
 /// Both member positions (relative to group origin) and member dimensions are scaled.
 ///
@@ -1885,7 +3816,7 @@ pub fn move_group(objects: &mut Vec<SceneObject>, group_idx: usize, dx: i32, dy:
 ///   - `anchor_top=true`   → top edge fixed,    bottom edge moves (grow/shrink from bottom)
 ///   - `anchor_top=false`  → bottom edge fixed, top edge moves    (grow/shrink from top)
 pub fn resize_group(
-    objects: &mut Vec<SceneObject>,
+    objects: &mut [SceneObject],
     group_idx: usize,
     dw: i32,
     dh: i32,
@@ -1996,6 +3927,56 @@ mod tests {
         assert_eq!(dropdown_options_for(&PropertyKind::VerticalAlign), Some(VERTICAL_ALIGN_OPTIONS));
     }
 
+    #[test]
+    fn rect_border_is_a_dropdown_that_sets_the_style() {
+        let mut o = vec![obj(
+            r#"{"type":"rect","position":{"x":{"fixed":0},"y":{"fixed":0}},"width":4,"height":3,
+                "frames":{"start":0,"end":1}}"#,
+        )];
+        let border = get_properties(&o, 0).into_iter().find(|p| p.name == "border").expect("border is listed");
+        assert_eq!((border.kind, border.value.as_str()), (PropertyKind::BorderStyle, "single"));
+        assert_eq!(dropdown_options_for(&PropertyKind::BorderStyle), Some(BORDER_STYLE_OPTIONS));
+
+        set_property(&mut o[0], "border", "rounded").unwrap();
+        assert!(matches!(&o[0], SceneObject::Rect(r) if r.border == BorderStyle::Rounded));
+        assert!(set_property(&mut o[0], "border", "wavy").is_err());
+    }
+
+    #[test]
+    fn shadow_offset_and_colour_are_listed_while_the_shadow_is_on() {
+        let mut o = vec![obj(
+            r#"{"type":"rect","position":{"x":{"fixed":0},"y":{"fixed":0}},"width":4,"height":3,
+                "frames":{"start":0,"end":1}}"#,
+        )];
+        let names = |o: &[SceneObject]| get_properties(o, 0).into_iter().map(|p| p.name).filter(|n| n.starts_with("shadow")).collect::<Vec<_>>();
+        assert_eq!(names(&o), ["shadow"]);
+
+        set_property(&mut o[0], "shadow", "true").unwrap();
+        assert_eq!(names(&o), ["shadow", "shadow_dx", "shadow_dy", "shadow_color"]);
+        set_property(&mut o[0], "shadow_dx", "2").unwrap();
+        assert!(matches!(&o[0], SceneObject::Rect(r) if r.shadow.as_ref().is_some_and(|s| s.dx == 2 && s.style.dim)));
+        assert_props_roundtrip(&mut o, 0);
+
+        set_property(&mut o[0], "shadow", "false").unwrap();
+        assert!(matches!(&o[0], SceneObject::Rect(r) if r.shadow.is_none()));
+    }
+
+    #[test]
+    fn header_gradient_ends_start_from_the_foreground_and_clear_together() {
+        let mut o = vec![obj(
+            r#"{"type":"header","text":"HI","position":{"x":{"fixed":0},"y":{"fixed":0}},
+                "style":{"fg":"blue"},"frames":{"start":0,"end":1}}"#,
+        )];
+        set_property(&mut o[0], "gradient_to", "#ff0000").unwrap();
+        let blue = Color::Named(NamedColor::Blue);
+        let red = Color::Rgb { r: 255, g: 0, b: 0 };
+        assert!(matches!(&o[0], SceneObject::Header(h) if h.gradient == Some([blue.clone(), red.clone()])));
+        assert_props_roundtrip(&mut o, 0);
+
+        set_property(&mut o[0], "gradient_from", "none").unwrap();
+        assert!(matches!(&o[0], SceneObject::Header(h) if h.gradient.is_none()));
+    }
+
     #[test]
     fn circle_properties_roundtrip() {
         let mut o = vec![obj(
@@ -2051,6 +4032,36 @@ mod tests {
         assert_eq!(x.value, "7");
     }
 
+    #[test]
+    fn is_mixed_flags_only_the_props_members_disagree_on() {
+        let objects = vec![
+            obj(r#"{"type":"label","text":"A","position":{"x":{"fixed":7},"y":{"fixed":0}},"style":{"fg":"red"},"frames":{"start":0,"end":2}}"#),
+            obj(r#"{"type":"label","text":"B","position":{"x":{"fixed":3},"y":{"fixed":0}},"style":{"fg":"red"},"frames":{"start":0,"end":2}}"#),
+        ];
+        assert!(is_mixed(&objects, &[0, 1], "x"));
+        assert!(!is_mixed(&objects, &[0, 1], "y"));
+        assert!(!is_mixed(&objects, &[0, 1], "fg_color"));
+        assert!(!is_mixed(&objects, &[0], "x"), "a single member is never mixed");
+    }
+
+    #[test]
+    fn refit_objects_centers_or_scales_fixed_geometry() {
+        let rect = || obj(r#"{"type":"rect","position":{"x":{"fixed":10},"y":{"fixed":4}},"width":20,"height":6,"frames":{"start":0,"end":1}}"#);
+        let geometry = |o: &SceneObject| (object_origin_x_f(o), object_origin_y_f(o), object_dim_x_f(o), object_dim_y_f(o));
+
+        let mut objects = vec![rect()];
+        refit_objects(&mut objects, (80, 24), (120, 30), CanvasFit::TopLeft);
+        assert_eq!(geometry(&objects[0]), (10.0, 4.0, 20.0, 6.0));
+
+        refit_objects(&mut objects, (80, 24), (120, 30), CanvasFit::Center);
+        assert_eq!(geometry(&objects[0]), (30.0, 7.0, 20.0, 6.0), "half the growth each way");
+
+        let mut objects = vec![rect(), obj(r#"{"type":"arrow","x1":{"fixed":2},"y1":{"fixed":3},"x2":{"fixed":12},"y2":{"fixed":3},"frames":{"start":0,"end":1}}"#)];
+        refit_objects(&mut objects, (80, 24), (160, 12), CanvasFit::Scale);
+        assert_eq!(geometry(&objects[0]), (20.0, 2.0, 40.0, 3.0));
+        assert_eq!(geometry(&objects[1]), (4.0, 2.0, 20.0, 1.0), "a flat arrow stays flat");
+    }
+
     #[test]
     fn hline_properties_roundtrip() {
         let mut o = vec![obj(
@@ -2219,4 +4230,38 @@ mod tests {
         assert_eq!(object_origin_x_f(&o[1]), 1.5);
         assert_eq!(object_dim_x_f(&o[1]), 4.5);
     }
+
+    #[test]
+    fn align_and_distribute_move_boxes_and_groups_by_their_bounds() {
+        let rect = |x: u16, y: u16, w: u16| {
+            obj(&format!(
+                r#"{{"type":"rect","position":{{"x":{{"fixed":{x}}},"y":{{"fixed":{y}}}}},"width":{w},"height":2,"frames":{{"start":0,"end":1}}}}"#
+            ))
+        };
+        let xs = |o: &[SceneObject]| o.iter().take(3).map(object_origin_x_f).collect::<Vec<_>>();
+        let mut o = vec![rect(2, 0, 4), rect(10, 3, 6), rect(5, 8, 2)];
+
+        align_objects(&mut o, &[0, 1, 2], Align::Right);
+        assert_eq!(xs(&o), [12.0, 10.0, 14.0], "right edges all at 16");
+        align_objects(&mut o, &[0, 1, 2], Align::Center);
+        assert_eq!(xs(&o), [11.0, 10.0, 12.0], "centres on the box's middle column");
+        align_objects(&mut o, &[0, 1], Align::Bottom);
+        assert_eq!((object_origin_y_f(&o[0]), object_origin_y_f(&o[1])), (3.0, 3.0));
+
+        // Ends stay put; the middle one moves so both gaps are equal.
+        let mut o = vec![rect(0, 0, 4), rect(5, 0, 4), rect(20, 0, 4)];
+        distribute_objects(&mut o, &[2, 1, 0], Axis::Horizontal);
+        assert_eq!(xs(&o), [0.0, 10.0, 20.0]);
+
+        // A group moves as one box, through its members; an animation is left out.
+        let mut o = vec![
+            rect(8, 0, 2),
+            rect(12, 0, 2),
+            rect(1, 5, 3),
+            obj(r#"{"type":"group","members":[0,1],"frames":{"start":0,"end":1}}"#),
+            obj(r#"{"type":"animation","id":1,"frames":{"start":0,"end":1}}"#),
+        ];
+        align_objects(&mut o, &[3, 2, 4], Align::Left);
+        assert_eq!(xs(&o), [1.0, 5.0, 1.0]);
+    }
 }
@@ -0,0 +1,202 @@
+//! Find and replace across the deck's text: label and header text and table
+//! cell content, searched in object order (a table's cells row by row).
+
+use regex_lite::Regex;
+
+use crate::engine::source::{SceneObject, SourcePresentation};
+
+/// One match: the object, the table cell it's in (`None` for a label's or
+/// header's own text), and its byte range within that text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextMatch {
+    pub object_index: usize,
+    pub cell: Option<(usize, usize)>,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A table cell as `(row, column)`; `None` for a label's or header's own text.
+type CellRef = Option<(usize, usize)>;
+
+/// The pattern for `find`: the typed text taken literally, or as a regex.
+pub fn compile(find: &str, regex: bool) -> Result<Regex, String> {
+    if find.is_empty() {
+        return Err("enter the text to find".into());
+    }
+    let pattern = if regex { find.to_string() } else { regex_lite::escape(find) };
+    Regex::new(&pattern).map_err(|e| format!("bad regex: {e}"))
+}
+
+/// Every searchable text, in search order, as `(object, cell, text)`. Objects
+/// on locked layers are left out.
+fn texts(source: &SourcePresentation) -> Vec<(usize, CellRef, &str)> {
+    let mut out = Vec::new();
+    for (i, obj) in source.objects.iter().enumerate() {
+        if source.is_locked(obj) {
+            continue;
+        }
+        match obj {
+            SceneObject::Label(l) => out.push((i, None, l.text.as_str())),
+            SceneObject::Header(h) => out.push((i, None, h.text.as_str())),
+            SceneObject::Table(t) => {
+                for (r, row) in t.cells.iter().enumerate() {
+                    for (c, cell) in row.iter().enumerate() {
+                        out.push((i, Some((r, c)), cell.content.as_str()));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+/// The first match at or after byte `offset` of the text of `object_index`
+/// (and `cell`), carrying on through the texts after it.
+pub fn find_from(
+    source: &SourcePresentation,
+    re: &Regex,
+    object_index: usize,
+    cell: CellRef,
+    offset: usize,
+) -> Option<TextMatch> {
+    for (i, c, text) in texts(source) {
+        let start = match (i, c).cmp(&(object_index, cell)) {
+            std::cmp::Ordering::Less => continue,
+            std::cmp::Ordering::Equal => offset,
+            std::cmp::Ordering::Greater => 0,
+        };
+        if start > text.len() || !text.is_char_boundary(start) {
+            continue;
+        }
+        if let Some(m) = re.find_at(text, start) {
+            return Some(TextMatch { object_index: i, cell: c, start: m.start(), end: m.end() });
+        }
+    }
+    None
+}
+
+/// Where the search carries on after skipping `m`: past its end, or past one
+/// more character for an empty match so it isn't found again.
+pub fn after(source: &SourcePresentation, m: &TextMatch) -> usize {
+    if m.end > m.start {
+        return m.end;
+    }
+    let next = text_of(source, m).and_then(|t| t[m.end..].chars().next()).map_or(1, char::len_utf8);
+    m.end + next
+}
+
+/// Number of matches in the deck.
+pub fn count(source: &SourcePresentation, re: &Regex) -> usize {
+    texts(source).iter().map(|(_, _, text)| re.find_iter(text).count()).sum()
+}
+
+fn text_of<'a>(source: &'a SourcePresentation, m: &TextMatch) -> Option<&'a str> {
+    match (source.objects.get(m.object_index)?, m.cell) {
+        (SceneObject::Label(l), None) => Some(&l.text),
+        (SceneObject::Header(h), None) => Some(&h.text),
+        (SceneObject::Table(t), Some((r, c))) => t.cells.get(r)?.get(c).map(|cell| cell.content.as_str()),
+        _ => None,
+    }
+}
+
+fn text_of_mut<'a>(source: &'a mut SourcePresentation, m: &TextMatch) -> Option<&'a mut String> {
+    match (source.objects.get_mut(m.object_index)?, m.cell) {
+        (SceneObject::Label(l), None) => Some(&mut l.text),
+        (SceneObject::Header(h), None) => Some(&mut h.text),
+        (SceneObject::Table(t), Some((r, c))) => t.cells.get_mut(r)?.get_mut(c).map(|cell| &mut cell.content),
+        _ => None,
+    }
+}
+
+/// Replace match `m` with `replacement` — with `$1`/`${name}` expanded from
+/// the match's groups when `regex` is set. Returns the byte offset the search
+/// carries on from: just past the inserted text (and, for an empty match, one
+/// character further, so the same spot doesn't match again).
+pub fn replace(source: &mut SourcePresentation, re: &Regex, m: &TextMatch, replacement: &str, regex: bool) -> usize {
+    let Some(text) = text_of_mut(source, m) else {
+        return m.end;
+    };
+    let with = match re.captures_at(text, m.start).filter(|_| regex) {
+        Some(caps) => {
+            let mut out = String::new();
+            caps.expand(replacement, &mut out);
+            out
+        }
+        None => replacement.to_string(),
+    };
+    text.replace_range(m.start..m.end, &with);
+    let resume = m.start + with.len();
+    match text[resume..].chars().next() {
+        Some(ch) if m.start == m.end => resume + ch.len_utf8(),
+        None if m.start == m.end => resume + 1,
+        _ => resume,
+    }
+}
+
+/// The object's own text (or cell) the match is in, with the match marked
+/// `[…]` — for the confirm prompt.
+pub fn context(source: &SourcePresentation, m: &TextMatch) -> String {
+    let Some(text) = text_of(source, m) else {
+        return String::new();
+    };
+    let line_start = text[..m.start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = text[m.end..].find('\n').map_or(text.len(), |i| m.end + i);
+    format!("{}[{}]{}", &text[line_start..m.start], &text[m.start..m.end], &text[m.end..line_end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deck() -> SourcePresentation {
+        let mut source: SourcePresentation = serde_json::from_str(r#"{"width":40,"height":10,"frame_count":2,"objects":[]}"#).unwrap();
+        source.objects = vec![
+            serde_json::from_str(r#"{"type":"label","text":"Acme Cloud by Acme","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":1}}"#).unwrap(),
+            serde_json::from_str(r#"{"type":"rect","position":{"x":{"fixed":0},"y":{"fixed":0}},"width":2,"height":2,"frames":{"start":0,"end":1}}"#).unwrap(),
+            serde_json::from_str(r#"{"type":"table","position":{"x":{"fixed":0},"y":{"fixed":3}},"col_widths":[1.0,1.0],"rows":1,"cells":[[{"content":"v1"},{"content":"Acme v2"}]],"frames":{"start":1,"end":2}}"#).unwrap(),
+        ];
+        source
+    }
+
+    #[test]
+    fn matches_run_through_labels_then_table_cells_and_replace_in_place() {
+        let mut source = deck();
+        let re = compile("Acme", false).unwrap();
+        assert_eq!(count(&source, &re), 3);
+
+        let first = find_from(&source, &re, 0, None, 0).unwrap();
+        assert_eq!(first, TextMatch { object_index: 0, cell: None, start: 0, end: 4 });
+        assert_eq!(context(&source, &first), "[Acme] Cloud by Acme");
+        let skip = after(&source, &first);
+        let second = find_from(&source, &re, 0, None, skip).unwrap();
+        assert_eq!((second.start, second.end), (14, 18));
+        let next = replace(&mut source, &re, &second, "Initech", false);
+        let third = find_from(&source, &re, 0, None, next).unwrap();
+        assert_eq!(third, TextMatch { object_index: 2, cell: Some((0, 1)), start: 0, end: 4 });
+        replace(&mut source, &re, &third, "$0 Corp", false);
+        match (&source.objects[0], &source.objects[2]) {
+            (SceneObject::Label(l), SceneObject::Table(t)) => {
+                assert_eq!(l.text, "Acme Cloud by Initech");
+                assert_eq!(t.cells[0][1].content, "$0 Corp v2", "literal mode doesn't expand groups");
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn regex_mode_expands_groups_and_rejects_bad_patterns() {
+        let mut source = deck();
+        let re = compile(r"v(\d)", true).unwrap();
+        let m = find_from(&source, &re, 0, None, 0).unwrap();
+        assert_eq!(m.cell, Some((0, 0)));
+        replace(&mut source, &re, &m, "version ${1}", true);
+        match &source.objects[2] {
+            SceneObject::Table(t) => assert_eq!(t.cells[0][0].content, "version 1"),
+            _ => unreachable!(),
+        }
+        assert!(compile("(", true).is_err());
+        assert!(compile("(", false).is_ok(), "literal text is escaped");
+        assert!(compile("", false).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+//! The start screen ([`Mode::Start`]) and new-presentation wizard
+//! ([`Mode::NewDeck`]), drawn as a box over the empty canvas, and the
+//! recently-opened list behind them (`~/.config/bs/recent.json`), each deck
+//! shown by its `metadata` title where it has one.
+
+use std::io;
+use std::path::PathBuf;
+
+use crossterm::{cursor, queue, style};
+
+use super::config::Theme;
+use super::panel::draw_caret_line;
+use super::state::{EditorState, Mode, RecentDeck};
+use super::ui::{self, Layout};
+
+/// How many recently opened decks are remembered.
+const MAX_RECENT: usize = 10;
+
+/// Labels of the wizard's fields, in [`Mode::NewDeck`] order.
+pub(super) const NEW_DECK_FIELDS: [&str; 4] = ["file", "width", "height", "frames"];
+
+fn recent_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".into());
+    let mut path = PathBuf::from(home);
+    path.push(".config");
+    path.push("bs");
+    path.push("recent.json");
+    path
+}
+
+/// The recently opened decks, newest first, each with its title.
+pub fn load_recent() -> Vec<RecentDeck> {
+    recent_paths().into_iter().map(|path| RecentDeck { title: deck_title(&path), path }).collect()
+}
+
+/// `metadata.title` of the source deck at `path`, if it reads and has one.
+fn deck_title(path: &str) -> Option<String> {
+    let json = std::fs::read_to_string(path).ok()?;
+    let value: serde_json::Value = serde_json::from_str(&json).ok()?;
+    Some(value.get("metadata")?.get("title")?.as_str()?.to_string())
+}
+
+/// The remembered paths, newest first, minus any that have since gone.
+fn recent_paths() -> Vec<String> {
+    std::fs::read_to_string(recent_path())
+        .ok()
+        .and_then(|json| serde_json::from_str::<Vec<String>>(&json).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|p| std::path::Path::new(p).exists())
+        .collect()
+}
+
+/// `list` with `path` moved (or added) to the front, capped at [`MAX_RECENT`].
+pub(super) fn with_recent(mut list: Vec<String>, path: String) -> Vec<String> {
+    list.retain(|p| *p != path);
+    list.insert(0, path);
+    list.truncate(MAX_RECENT);
+    list
+}
+
+/// Note that `path` was just opened. Best-effort: the list is a convenience,
+/// so a read-only config directory is not an error.
+pub fn remember(path: &str) {
+    let Ok(abs) = std::fs::canonicalize(path) else {
+        return;
+    };
+    let list = with_recent(recent_paths(), abs.to_string_lossy().into_owned());
+    let file = recent_path();
+    if let Some(dir) = file.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(&list) {
+        let _ = std::fs::write(file, json);
+    }
+}
+
+/// `path` with the home directory shortened to `~`.
+fn display_path(path: &str) -> String {
+    match std::env::var("HOME") {
+        Ok(home) if !home.is_empty() && path.starts_with(&home) => format!("~{}", &path[home.len()..]),
+        _ => path.to_string(),
+    }
+}
+
+/// One bordered row of the box: `│text…│`, `text` padded to `inner_w`.
+fn boxed_row(stdout: &mut io::Stdout, theme: &Theme, x: u16, y: u16, inner_w: usize, text: &str, reverse: bool) -> anyhow::Result<()> {
+    let text: String = text.chars().take(inner_w).collect();
+    queue!(stdout, cursor::MoveTo(x, y), style::Print("\u{2502}"))?;
+    if reverse {
+        queue!(stdout, theme.selection())?;
+    }
+    queue!(
+        stdout,
+        style::Print(format!("{text:<inner_w$}")),
+        style::SetAttribute(style::Attribute::Reset),
+        style::Print("\u{2502}"),
+    )?;
+    Ok(())
+}
+
+fn border(left: char, label: &str, right: char, inner_w: usize) -> String {
+    let label: String = label.chars().take(inner_w).collect();
+    std::iter::once(left)
+        .chain(label.chars())
+        .chain(std::iter::repeat_n('\u{2500}', inner_w.saturating_sub(label.chars().count())))
+        .chain(std::iter::once(right))
+        .collect()
+}
+
+pub fn render_start(stdout: &mut io::Stdout, layout: &Layout, state: &EditorState) -> anyhow::Result<()> {
+    let (bx, by, bw, bh) = ui::text_overlay(layout);
+    if bw < 4 || bh < 4 {
+        return Ok(());
+    }
+    let inner_w = (bw - 2) as usize;
+    let rows = (bh - 2) as usize;
+
+    let (title, hint) = match &state.mode {
+        Mode::Start { .. } => (" bs ", " Enter: open · Esc: quit "),
+        Mode::NewDeck { .. } => (" New presentation ", " Enter: create · Esc: back "),
+        _ => return Ok(()),
+    };
+    queue!(stdout, cursor::MoveTo(bx, by), style::Print(border('\u{250c}', title, '\u{2510}', inner_w)))?;
+    for r in 0..rows {
+        boxed_row(stdout, &state.config.theme, bx, by + 1 + r as u16, inner_w, "", false)?;
+    }
+    queue!(stdout, cursor::MoveTo(bx, by + bh - 1), style::Print(border('\u{2514}', hint, '\u{2518}', inner_w)))?;
+
+    match &state.mode {
+        Mode::Start { selected, recent } => {
+            boxed_row(stdout, &state.config.theme, bx, by + 1, inner_w, " New presentation\u{2026}", *selected == 0)?;
+            if rows > 2 {
+                let heading = if recent.is_empty() { " No recent presentations" } else { " Recent" };
+                queue!(
+                    stdout,
+                    cursor::MoveTo(bx + 1, by + 3),
+                    state.config.theme.dimmed(),
+                    style::Print(heading),
+                    style::SetAttribute(style::Attribute::Reset),
+                )?;
+            }
+            // Scroll the list just far enough to keep the pick on screen.
+            let room = rows.saturating_sub(3);
+            let first = selected.saturating_sub(1).saturating_sub(room.saturating_sub(1));
+            for (row, (i, deck)) in recent.iter().enumerate().skip(first).take(room).enumerate() {
+                let text = match &deck.title {
+                    Some(title) => format!("   {title} \u{2014} {}", display_path(&deck.path)),
+                    None => format!("   {}", display_path(&deck.path)),
+                };
+                boxed_row(stdout, &state.config.theme, bx, by + 4 + row as u16, inner_w, &text, *selected == i + 1)?;
+            }
+        }
+        Mode::NewDeck { selected_field, fields, cursor, .. } => {
+            for (i, (name, buf)) in NEW_DECK_FIELDS.iter().zip(fields).enumerate() {
+                if 2 * i + 1 > rows {
+                    break;
+                }
+                let y = by + 1 + 2 * i as u16;
+                let marker = if *selected_field == i { "\u{203a}" } else { " " };
+                let prefix = format!("{marker} {name:>6}: ");
+                queue!(stdout, cursor::MoveTo(bx + 1, y), style::Print(&prefix))?;
+                let vx = bx + 1 + prefix.chars().count() as u16;
+                let field_w = inner_w.saturating_sub(prefix.chars().count()).max(1);
+                if *selected_field == i {
+                    // Keep the caret in view on a long path.
+                    let cur = (*cursor).min(buf.chars().count());
+                    let off = (cur + 1).saturating_sub(field_w);
+                    let window: String = buf.chars().skip(off).collect();
+                    draw_caret_line(stdout, &state.config.theme, vx, y, &window, Some(cur - off), false, field_w)?;
+                } else {
+                    let val: String = buf.chars().take(field_w).collect();
+                    queue!(stdout, cursor::MoveTo(vx, y), style::Print(val))?;
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
@@ -1,18 +1,26 @@
-use anyhow::{Context, Result};
+use std::cell::RefCell;
+
+use anyhow::{bail, Context, Result};
 
 use crate::art_library::ArtItem;
 use crate::engine::source::{
-    AnimId, AnimSpans, Animation, AutoAdvance, Coordinate, FrameRange, SceneObject,
-    SourcePresentation,
+    AnimId, AnimSpans, Animation, AutoAdvance, Coordinate, DiffLineKind, FrameRange, Scalar,
+    SceneObject, SourcePresentation, SOURCE_FORMAT_VERSION,
 };
+use crate::validate::Diagnostic;
 
 use super::config::EditorConfig;
+use super::preview::SceneCache;
+use super::properties::{self, CanvasFit};
+use super::search::TextMatch;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ConfirmAction {
     DeleteFrame,
     /// Delete a multi-selected set of frames (0-based indices).
     DeleteFrames { frames: Vec<usize> },
+    /// Keep only the frames `lo..=hi`, deleting every frame before and after.
+    TrimFrames { lo: usize, hi: usize },
     DeleteObject { object_index: usize },
     /// Delete a multi-selected set of objects (indices into `source.objects`).
     DeleteObjects { object_indices: Vec<usize> },
@@ -80,6 +88,14 @@ pub enum MultiSelectPurpose {
     Select,
 }
 
+/// A recently opened deck, as the start screen lists it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecentDeck {
+    pub path: String,
+    /// The deck's `metadata.title`, when it has one.
+    pub title: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Mode {
     Normal,
@@ -164,6 +180,9 @@ pub enum Mode {
         /// Show the animated element only every `gap_frames`-th frame of the span
         /// (a stop-motion strobe with empty gaps between). `1` = every frame (off).
         gap_frames: usize,
+        /// The frame the session was opened on. Left/Right scrub `current_frame`
+        /// through the span; leaving the session puts it back here.
+        home_frame: usize,
     },
     /// Configuring a *converge* animation: a set of objects (`members`) each
     /// animate from wherever they sit at the span start to one shared target
@@ -260,12 +279,15 @@ pub enum Mode {
         cursor: usize,
         purpose: ArtPick,
     },
-    /// Presentation settings — currently the output frame size (width × height).
+    /// Presentation settings — currently the output frame size (width × height)
+    /// and how the content already on the canvas follows a change to it — with
+    /// a last row opening the key-binding editor.
     Settings {
-        /// 0 = width, 1 = height.
+        /// 0 = width, 1 = height, 2 = content fit, 3 = key bindings.
         selected_field: usize,
         width_buf: String,
         height_buf: String,
+        fit: CanvasFit,
         /// Text cursor within the selected field's buffer.
         cursor: usize,
     },
@@ -294,6 +316,36 @@ pub enum Mode {
         buf: String,
         cursor: usize,
     },
+    /// Typing how long the current frame shows (in seconds), stored as its
+    /// [`SourcePresentation::durations`] entry. Enter sets it (`0`/empty clears
+    /// it); Esc returns to the frame menu.
+    FrameDurationInput {
+        buf: String,
+        cursor: usize,
+    },
+    /// Typing the name of the section the current frame starts, stored as its
+    /// [`SourcePresentation::sections`] entry. Enter sets it (empty clears
+    /// it); Esc returns to the frame menu.
+    FrameSectionInput {
+        buf: String,
+        cursor: usize,
+    },
+    /// Typing the signed shift for a ripple (`+3`, `-2`): every frame range at or
+    /// after the current frame moves by it ([`ripple_shift`]). `members` limits
+    /// it to a multi-select set (`None` = every object). Esc backs out.
+    FrameRippleInput {
+        buf: String,
+        cursor: usize,
+        members: Option<Vec<usize>>,
+    },
+    /// Typing the frame range (and optional per-frame step) to stamp the
+    /// selected object across ([`stamp_across_frames`]). Esc returns to the
+    /// selected object.
+    StampFramesInput {
+        object_index: usize,
+        buf: String,
+        cursor: usize,
+    },
     /// A set of frames has been selected (0-based indices); `d` deletes them,
     /// and (for a contiguous range) `m` moves or `c` copies them as a block.
     FrameSelected {
@@ -336,6 +388,43 @@ pub enum Mode {
     PresentationMenu {
         selected: usize,
     },
+    /// The layer panel: lists `SourcePresentation::layer_names` so the
+    /// highlighted layer can be shown/hidden or locked/unlocked. `selected`
+    /// indexes that list.
+    Layers {
+        selected: usize,
+    },
+    /// The lanes view: one row per object over the whole deck, a bar across its
+    /// frame range. `selected` indexes `source.objects`; `at_end` picks which end
+    /// of its range ←/→ (or a drag) moves — the exclusive `end` rather than
+    /// `start`.
+    Lanes {
+        selected: usize,
+        at_end: bool,
+    },
+    /// The diagnostics panel: the deck's `bs validate` findings
+    /// ([`crate::validate::validate`], then [`crate::validate::lint`]) as they
+    /// stood when the panel opened. `selected` indexes `items`.
+    Diagnostics {
+        selected: usize,
+        items: Vec<Diagnostic>,
+    },
+    /// The start screen, shown when `edit` is run without a path (or on a
+    /// missing one): "New presentation…" on row 0, then the recently opened
+    /// decks. Enter opens the pick in place of this empty deck; Esc quits.
+    Start {
+        selected: usize,
+        recent: Vec<RecentDeck>,
+    },
+    /// The new-presentation wizard (row 0 of [`Mode::Start`]): file, width,
+    /// height and frame count, in that order. Enter creates the deck; Esc goes
+    /// back to the start screen, whose `recent` list is carried along.
+    NewDeck {
+        selected_field: usize,
+        fields: [String; 4],
+        cursor: usize,
+        recent: Vec<RecentDeck>,
+    },
     /// Typing a path to open another presentation as a new deck (reached from the
     /// presentations menu). Enter opens it; Esc returns to the menu.
     OpenFile {
@@ -356,6 +445,60 @@ pub enum Mode {
         selected_cells: Vec<(usize, usize)>,
         sub_state: TableCellSubState,
     },
+    /// Typing a label's text straight onto the canvas: the preview draws `buf`
+    /// in the label's place with a block caret at `cursor` (a char index).
+    /// Enter applies it as the label's `text`; Esc leaves the label as it was.
+    EditInPlace {
+        object_index: usize,
+        buf: String,
+        cursor: usize,
+    },
+    /// Picking a Coordinate property's value off the canvas: a crosshair at
+    /// canvas cell (`col`, `row`) rides the arrow keys (or the mouse), and
+    /// Enter writes the point back as the property — both `x` and `y` of a
+    /// point, or the size a `width`/`height` reaches to. Esc leaves it as it
+    /// was. Either way the panel comes back on `return_property`.
+    PickCoordinate {
+        object_index: usize,
+        return_property: usize,
+        panel_scroll: usize,
+        property_name: &'static str,
+        col: u16,
+        row: u16,
+    },
+    /// The key-binding editor: every [`KeyBindings`](super::config::KeyBindings)
+    /// entry by name, `selected` highlighted. While `capturing`, the next key
+    /// pressed becomes the highlighted action's binding.
+    KeyBindings {
+        selected: usize,
+        capturing: bool,
+    },
+    /// Typing the text to find across the deck's labels, headers and table
+    /// cells; Tab toggles `regex`. Enter moves on to the replacement.
+    FindInput {
+        buf: String,
+        cursor: usize,
+        regex: bool,
+    },
+    /// Typing what each match of `find` is replaced with. Enter steps to the
+    /// first match; Esc goes back to the search text.
+    ReplaceInput {
+        find: String,
+        regex: bool,
+        buf: String,
+        cursor: usize,
+    },
+    /// Stepping through the matches: the preview sits on match `at`'s frame
+    /// with its object highlighted, and `y` replaces it, `n` skips it, `a`
+    /// replaces it and every one after. Esc stops, keeping what's done.
+    ReplaceConfirm {
+        find: String,
+        regex: bool,
+        replace: String,
+        at: TextMatch,
+        replaced: usize,
+        skipped: usize,
+    },
 }
 
 /// A read-only snapshot of workspace-level state (the other open decks and the
@@ -398,30 +541,61 @@ pub struct EditorState {
     /// clipboard), refreshed by the `Editor` before each redraw. See
     /// [`WorkspaceView`].
     pub workspace: WorkspaceView,
+    /// While a colour or arrow head/body character dropdown is open, the value
+    /// the highlighted option stands in for on the canvas — put back on Esc.
+    pub(super) dropdown_preview: Option<String>,
+    /// The canvas preview's resolved frames, kept between redraws.
+    pub(super) scene_cache: RefCell<SceneCache>,
+    /// The file this deck was opened from, while it still holds `slides`: the
+    /// editor works on them lowered to flat objects, so the first save over it
+    /// refuses and asks for another before writing the flat deck.
+    pub(super) slides_file: Option<String>,
 }
 
 impl EditorState {
     pub fn open(path: &str) -> Result<Self> {
+        let mut slides_file = None;
         let source = if std::path::Path::new(path).exists() {
             let json =
                 std::fs::read_to_string(path).with_context(|| format!("Failed to read {path}"))?;
-            serde_json::from_str(&json).with_context(|| format!("Failed to parse {path}"))?
+            let mut source = crate::migrate::source_from_str(&json).with_context(|| format!("Failed to parse {path}"))?;
+            // The editor works on the flat model; saving writes it back lowered,
+            // once the user has confirmed it (see `save`).
+            if !source.slides.is_empty() {
+                slides_file = Some(path.to_string());
+            }
+            if let Err(e) = source.lower() {
+                bail!("invalid slide in {path}: {e}");
+            }
+            source
         } else {
             SourcePresentation {
+                format_version: SOURCE_FORMAT_VERSION,
                 width: 80,
                 height: 24,
                 frame_count: 1,
                 objects: Vec::new(),
                 links: Vec::new(),
+                background: None,
+                layers: Vec::new(),
+                components: Default::default(),
+                vars: Default::default(),
+                slides: Vec::new(),
+                lint: Default::default(),
+                auto_z: false,
+                durations: Vec::new(),
+                on_enter: Vec::new(),
+                sections: Vec::new(),
+                metadata: Default::default(),
             }
         };
 
-        Ok(EditorState {
+        let mut state = EditorState {
             source,
             file_path: path.to_string(),
             current_frame: 0,
             mode: Mode::Normal,
-            config: EditorConfig::load(),
+            config: EditorConfig::default(),
             dirty: false,
             status_message: None,
             blink_hidden: false,
@@ -429,10 +603,39 @@ impl EditorState {
             clipboard: Vec::new(),
             clipboard_sources: Vec::new(),
             workspace: WorkspaceView::default(),
-        })
+            dropdown_preview: None,
+            scene_cache: RefCell::default(),
+            slides_file,
+        };
+        state.use_config(EditorConfig::load());
+        Ok(state)
+    }
+
+    /// Edit with `config`, and the deck's project config laid over it. A
+    /// project config that can't be used is left out, with the reason in the
+    /// status line.
+    pub fn use_config(&mut self, config: EditorConfig) {
+        self.config = match config.for_deck(&self.file_path) {
+            Ok(config) => config,
+            Err(e) => {
+                self.status_message = Some(format!("⚠ {e:#}"));
+                config
+            }
+        };
     }
 
+    /// Write the deck to its file. Over a file written with `slides`, the
+    /// first save refuses — saving would replace them with the flat objects
+    /// they lower to — and the next one goes ahead.
     pub fn save(&mut self) -> Result<()> {
+        if self.slides_file.as_deref() == Some(self.file_path.as_str()) {
+            self.slides_file = None;
+            bail!(
+                "{} is written with `slides`, which saving replaces with flat objects — save again to do so, \
+                 or save as another file",
+                self.file_path
+            );
+        }
         let json = serde_json::to_string_pretty(&self.source)?;
         std::fs::write(&self.file_path, &json)
             .with_context(|| format!("Failed to write {}", self.file_path))?;
@@ -449,12 +652,15 @@ impl EditorState {
         Ok(())
     }
 
-    /// Returns indices into `self.source.objects` for objects visible on `current_frame`.
+    /// Returns indices into `self.source.objects` for the objects that can be
+    /// picked on `current_frame`: visible there, and not on a hidden or locked
+    /// layer.
     pub fn objects_on_current_frame(&self) -> Vec<usize> {
         self.source
             .objects
             .iter()
             .enumerate()
+            .filter(|(_, o)| !self.source.is_hidden(o) && !self.source.is_locked(o))
             .filter(|(i, _)| {
                 // Auto groups derive their span from their members, so go through
                 // the presentation's effective-range helper rather than the raw
@@ -481,6 +687,25 @@ pub fn scene_object_frame_range(obj: &SceneObject) -> Option<&FrameRange> {
         SceneObject::Arrow(a) => Some(&a.frames),
         SceneObject::Table(t) => Some(&t.frames),
         SceneObject::Art(a) => Some(&a.frames),
+        SceneObject::AnsiArt(a) => Some(&a.frames),
+        SceneObject::Cast(a) => Some(&a.frames),
+        SceneObject::Countdown(c) => Some(&c.frames),
+        SceneObject::Toc(c) => Some(&c.frames),
+        SceneObject::Footer(c) => Some(&c.frames),
+        SceneObject::Gauge(c) => Some(&c.frames),
+        SceneObject::Heatmap(c) => Some(&c.frames),
+        SceneObject::Diff(c) => Some(&c.frames),
+        SceneObject::FileTree(c) => Some(&c.frames),
+        SceneObject::Gantt(c) => Some(&c.frames),
+        SceneObject::PieChart(c) => Some(&c.frames),
+        SceneObject::ScatterPlot(c) => Some(&c.frames),
+        SceneObject::Marquee(c) => Some(&c.frames),
+        SceneObject::Spinner(c) => Some(&c.frames),
+        SceneObject::Connector(c) => Some(&c.frames),
+        SceneObject::Keycap(c) => Some(&c.frames),
+        SceneObject::Watermark(c) => Some(&c.frames),
+        SceneObject::BackgroundEffect(c) => Some(&c.frames),
+        SceneObject::Clock(c) => Some(&c.frames),
         SceneObject::Command(c) => Some(&c.frames),
         SceneObject::List(l) => Some(&l.frames),
         SceneObject::Loop(l) => Some(&l.frames),
@@ -488,6 +713,8 @@ pub fn scene_object_frame_range(obj: &SceneObject) -> Option<&FrameRange> {
         SceneObject::Animation(a) => Some(&a.frames),
         SceneObject::AutoAdvance(a) => Some(&a.frames),
         SceneObject::Circle(c) => Some(&c.frames),
+        SceneObject::Instance(o) => Some(&o.frames),
+        SceneObject::Include(_) => None,
     }
 }
 
@@ -503,6 +730,25 @@ pub fn scene_object_frame_range_mut(obj: &mut SceneObject) -> Option<&mut FrameR
         SceneObject::Arrow(a) => Some(&mut a.frames),
         SceneObject::Table(t) => Some(&mut t.frames),
         SceneObject::Art(a) => Some(&mut a.frames),
+        SceneObject::AnsiArt(a) => Some(&mut a.frames),
+        SceneObject::Cast(a) => Some(&mut a.frames),
+        SceneObject::Countdown(c) => Some(&mut c.frames),
+        SceneObject::Toc(c) => Some(&mut c.frames),
+        SceneObject::Footer(c) => Some(&mut c.frames),
+        SceneObject::Gauge(c) => Some(&mut c.frames),
+        SceneObject::Heatmap(c) => Some(&mut c.frames),
+        SceneObject::Diff(c) => Some(&mut c.frames),
+        SceneObject::FileTree(c) => Some(&mut c.frames),
+        SceneObject::Gantt(c) => Some(&mut c.frames),
+        SceneObject::PieChart(c) => Some(&mut c.frames),
+        SceneObject::ScatterPlot(c) => Some(&mut c.frames),
+        SceneObject::Marquee(c) => Some(&mut c.frames),
+        SceneObject::Spinner(c) => Some(&mut c.frames),
+        SceneObject::Connector(c) => Some(&mut c.frames),
+        SceneObject::Keycap(c) => Some(&mut c.frames),
+        SceneObject::Watermark(c) => Some(&mut c.frames),
+        SceneObject::BackgroundEffect(c) => Some(&mut c.frames),
+        SceneObject::Clock(c) => Some(&mut c.frames),
         SceneObject::Command(c) => Some(&mut c.frames),
         SceneObject::List(l) => Some(&mut l.frames),
         SceneObject::Loop(l) => Some(&mut l.frames),
@@ -510,6 +756,8 @@ pub fn scene_object_frame_range_mut(obj: &mut SceneObject) -> Option<&mut FrameR
         SceneObject::Animation(a) => Some(&mut a.frames),
         SceneObject::AutoAdvance(a) => Some(&mut a.frames),
         SceneObject::Circle(c) => Some(&mut c.frames),
+        SceneObject::Instance(o) => Some(&mut o.frames),
+        SceneObject::Include(_) => None,
     }
 }
 
@@ -523,6 +771,25 @@ pub fn scene_object_type_name(obj: &SceneObject) -> &'static str {
         SceneObject::Arrow(_) => "Arrow",
         SceneObject::Table(_) => "Table",
         SceneObject::Art(_) => "Art",
+        SceneObject::AnsiArt(_) => "ANSI Art",
+        SceneObject::Cast(_) => "Cast",
+        SceneObject::Countdown(_) => "Countdown",
+        SceneObject::Toc(_) => "Toc",
+        SceneObject::Footer(_) => "Footer",
+        SceneObject::Gauge(_) => "Gauge",
+        SceneObject::Heatmap(_) => "Heatmap",
+        SceneObject::Diff(_) => "Diff",
+        SceneObject::FileTree(_) => "FileTree",
+        SceneObject::Gantt(_) => "Gantt",
+        SceneObject::PieChart(_) => "PieChart",
+        SceneObject::ScatterPlot(_) => "ScatterPlot",
+        SceneObject::Marquee(_) => "Marquee",
+        SceneObject::Spinner(_) => "Spinner",
+        SceneObject::Connector(_) => "Connector",
+        SceneObject::Keycap(_) => "Keycap",
+        SceneObject::Watermark(_) => "Watermark",
+        SceneObject::BackgroundEffect(_) => "BackgroundEffect",
+        SceneObject::Clock(_) => "Clock",
         SceneObject::Command(_) => "Command",
         SceneObject::List(_) => "List",
         SceneObject::Loop(_) => "Loop",
@@ -530,75 +797,54 @@ pub fn scene_object_type_name(obj: &SceneObject) -> &'static str {
         SceneObject::Animation(_) => "Animation",
         SceneObject::AutoAdvance(_) => "AutoAdvance",
         SceneObject::Circle(_) => "Circle",
+        SceneObject::Instance(_) => "Instance",
+        SceneObject::Include(_) => "Include",
     }
 }
 
 /// Collect mutable references to all Coordinate fields of a SceneObject.
+/// Objects that draw nothing have none; their frame ranges still shift via
+/// `scene_object_frame_range_mut` during frame insert/delete.
 fn scene_object_coordinates_mut(obj: &mut SceneObject) -> Vec<&mut Coordinate> {
-    match obj {
-        SceneObject::Label(l) => vec![
-            &mut l.position.x,
-            &mut l.position.y,
-            &mut l.width,
-            &mut l.height,
-        ],
-        SceneObject::Rect(r) => vec![
-            &mut r.position.x,
-            &mut r.position.y,
-            &mut r.width,
-            &mut r.height,
-        ],
-        SceneObject::HLine(h) => vec![&mut h.y, &mut h.x_start, &mut h.x_end],
-        SceneObject::Header(h) => vec![&mut h.position.x, &mut h.position.y],
-        SceneObject::Arrow(a) => vec![&mut a.x1, &mut a.y1, &mut a.x2, &mut a.y2],
-        SceneObject::Group(_) => vec![],
-        SceneObject::Table(t) => vec![
-            &mut t.position.x,
-            &mut t.position.y,
-            &mut t.width,
-            &mut t.height,
-        ],
-        SceneObject::Art(a) => vec![&mut a.position.x, &mut a.position.y],
-        SceneObject::Command(c) => vec![
-            &mut c.position.x,
-            &mut c.position.y,
-            &mut c.width,
-            &mut c.height,
-        ],
-        SceneObject::List(l) => vec![
-            &mut l.position.x,
-            &mut l.position.y,
-            &mut l.width,
-            &mut l.height,
-        ],
-        // A loop has no coordinates (it draws nothing); its frame range still
-        // shifts via `scene_object_frame_range_mut` during frame insert/delete.
-        SceneObject::Loop(_) => vec![],
-        // A morph is sized by its art content (like Art); only its position can
-        // animate, so width/height are absent here.
-        SceneObject::Morph(m) => vec![&mut m.position.x, &mut m.position.y],
-        // An animation span has no coordinates (it draws nothing); its frame
-        // range still shifts via `scene_object_frame_range_mut`.
-        SceneObject::Animation(_) => vec![],
-        // Auto-advance has no coordinates (it draws nothing); its frame range
-        // still shifts via `scene_object_frame_range_mut`.
-        SceneObject::AutoAdvance(_) => vec![],
-        // A circle is positioned (x/y animate); its diameter is a plain number.
-        SceneObject::Circle(c) => vec![&mut c.position.x, &mut c.position.y],
-    }
+    obj.coordinates_mut()
 }
 
-/// The animation ids referenced by `obj`'s `Animated` coordinates (an object can
-/// be driven by several animations — e.g. x and y over different spans).
+/// The animation ids referenced by `obj`'s `Animated` coordinates and scalars
+/// (an object can be driven by several animations — e.g. x and y over
+/// different spans).
 pub fn referenced_anim_ids(obj: &SceneObject) -> Vec<AnimId> {
     let mut clone = obj.clone();
-    scene_object_coordinates_mut(&mut clone)
+    let mut ids: Vec<AnimId> = scene_object_coordinates_mut(&mut clone)
         .into_iter()
         .filter_map(|c| match c {
             Coordinate::Animated { anim, .. } => Some(*anim),
             _ => None,
         })
-        .collect()
+        .collect();
+    ids.extend(obj.scalars().into_iter().filter_map(|(_, s)| match s {
+        Scalar::Animated { anim, .. } => Some(*anim),
+        Scalar::Fixed(_) => None,
+    }));
+    ids
+}
+
+/// Point every `Animated` coordinate and scalar of `obj` riding an animation
+/// in `id_map` at its new id.
+fn remap_anim_ids(obj: &mut SceneObject, id_map: &std::collections::HashMap<AnimId, AnimId>) {
+    for coord in scene_object_coordinates_mut(obj) {
+        if let Coordinate::Animated { anim, .. } = coord {
+            if let Some(&new) = id_map.get(anim) {
+                *anim = new;
+            }
+        }
+    }
+    for scalar in obj.scalars_mut() {
+        if let Scalar::Animated { anim, .. } = scalar {
+            if let Some(&new) = id_map.get(anim) {
+                *anim = new;
+            }
+        }
+    }
 }
 
 /// The union of the spans of every animation driving `obj`, as an exclusive
@@ -649,6 +895,7 @@ pub fn next_anim_id(source: &SourcePresentation) -> AnimId {
 /// backgrounds remain visible, matching the range-based frame model.
 pub fn insert_blank_frame(source: &mut SourcePresentation, inserted_after: usize) {
     source.frame_count += 1;
+    open_frame_gap(source, inserted_after + 1, 1);
     // A range ending exactly at the new frame position is left alone (the
     // source frame's object does not bleed into the blank one); only ranges
     // that genuinely span past it are stretched to stay contiguous.
@@ -687,6 +934,10 @@ pub fn copy_frame(source: &mut SourcePresentation, current: usize) {
 
     // Make room for the new frame; spanning objects extend across it.
     insert_blank_frame(source, current);
+    let ms = source.frame_duration(current);
+    source.set_frame_duration(new_frame, ms);
+    let commands = source.frame_on_enter(current).to_vec();
+    source.set_frame_on_enter(new_frame, commands);
     let anims = AnimSpans::of(source);
 
     // Clone every visible object the blank insert did NOT carry onto the new
@@ -742,6 +993,7 @@ fn insert_blank_frames_at(source: &mut SourcePresentation, dest: usize, count: u
         return;
     }
     source.frame_count += count;
+    open_frame_gap(source, dest, count);
     for obj in &mut source.objects {
         // Includes `Animation` objects, whose frame range *is* their span (the
         // single source of truth); driven coordinates carry no span to shift.
@@ -756,6 +1008,88 @@ fn insert_blank_frames_at(source: &mut SourcePresentation, dest: usize, count: u
     }
 }
 
+/// Keep [`SourcePresentation::durations`], [`SourcePresentation::on_enter`]
+/// and [`SourcePresentation::sections`] aligned with `count` blank frames
+/// inserted at `at`: the new frames get no duration, commands or section of
+/// their own.
+fn open_frame_gap(source: &mut SourcePresentation, at: usize, count: usize) {
+    if at < source.durations.len() {
+        source.durations.splice(at..at, std::iter::repeat_n(None, count));
+    }
+    if at < source.on_enter.len() {
+        source.on_enter.splice(at..at, std::iter::repeat_n(Vec::new(), count));
+    }
+    if at < source.sections.len() {
+        source.sections.splice(at..at, std::iter::repeat_n(None, count));
+    }
+}
+
+/// Ripple-shift frame ranges by `delta` frames from frame `from` onward: a range
+/// boundary at or after `from` moves (a start at `from`, an end past it), so a
+/// range that crosses `from` stretches or shrinks and one after it slides whole.
+/// A backward shift clamps at `from` and never leaves a range empty.
+///
+/// `only` limits the shift to those objects — plus the animations that drive
+/// them and the members of any group among them; `None` shifts every object.
+/// Shifting everything also grows or shrinks the deck by `delta` (never below
+/// the last range's end); a partial shift only grows it as far as needed.
+/// Returns how many objects moved.
+pub fn ripple_shift(
+    source: &mut SourcePresentation,
+    from: usize,
+    delta: isize,
+    only: Option<&[usize]>,
+) -> usize {
+    let targets: Vec<usize> = match only {
+        None => (0..source.objects.len()).collect(),
+        Some(members) => {
+            let mut t = expand_selection(source, members);
+            let ids: Vec<AnimId> = t.iter().flat_map(|&i| referenced_anim_ids(&source.objects[i])).collect();
+            for (i, obj) in source.objects.iter().enumerate() {
+                if matches!(obj, SceneObject::Animation(a) if ids.contains(&a.id)) && !t.contains(&i) {
+                    t.push(i);
+                }
+            }
+            t
+        }
+    };
+    let shift = |b: usize| {
+        if delta >= 0 {
+            b + delta as usize
+        } else {
+            b.saturating_sub(delta.unsigned_abs()).max(from)
+        }
+    };
+    let mut moved = 0;
+    for &i in &targets {
+        let Some(fr) = scene_object_frame_range_mut(&mut source.objects[i]) else {
+            continue;
+        };
+        let before = (fr.start, fr.end);
+        if fr.start >= from {
+            fr.start = shift(fr.start);
+        }
+        if fr.end > from {
+            fr.end = shift(fr.end).max(fr.start + 1);
+        }
+        if (fr.start, fr.end) != before {
+            moved += 1;
+        }
+    }
+    let last_end = source
+        .objects
+        .iter()
+        .filter_map(scene_object_frame_range)
+        .map(|fr| fr.end)
+        .max()
+        .unwrap_or(0);
+    source.frame_count = match only {
+        None => source.frame_count.saturating_add_signed(delta).max(last_end).max(1),
+        Some(_) => source.frame_count.max(last_end),
+    };
+    moved
+}
+
 /// Duplicate the contiguous frame block `[lo, hi]` (inclusive, 0-based) as a new
 /// block placed immediately before (`before`) or after the `target` frame,
 /// growing the deck by `count = hi - lo + 1` frames. Returns `(new_current,
@@ -810,7 +1144,15 @@ pub fn copy_frames(
         specs.push((i, clone, new_start, new_end));
     }
 
+    let block_durations: Vec<Option<u64>> = (lo..=hi).map(|f| source.frame_duration(f)).collect();
+    let block_on_enter: Vec<Vec<String>> = (lo..=hi).map(|f| source.frame_on_enter(f).to_vec()).collect();
     insert_blank_frames_at(source, dest, count);
+    for (k, ms) in block_durations.into_iter().enumerate() {
+        source.set_frame_duration(dest + k, ms);
+    }
+    for (k, commands) in block_on_enter.into_iter().enumerate() {
+        source.set_frame_on_enter(dest + k, commands);
+    }
 
     // Append the clones with their mapped ranges, then re-point cloned groups.
     let mut index_map: std::collections::HashMap<usize, usize> = std::collections::HashMap::new();
@@ -852,14 +1194,8 @@ pub fn copy_frames(
         }
     }
     if !id_map.is_empty() {
-        for idx in first_new..source.objects.len() {
-            for coord in scene_object_coordinates_mut(&mut source.objects[idx]) {
-                if let Coordinate::Animated { anim, .. } = coord {
-                    if let Some(&new) = id_map.get(anim) {
-                        *anim = new;
-                    }
-                }
-            }
+        for obj in &mut source.objects[first_new..] {
+            remap_anim_ids(obj, &id_map);
         }
     }
 
@@ -951,7 +1287,7 @@ pub struct FrameClipboard {
     pub objects: Vec<SceneObject>,
 }
 
-/// Flatten every animated coordinate on `obj` whose animation id is **not** in
+/// Flatten every animated coordinate (and scalar) on `obj` whose animation id is **not** in
 /// `keep` to a `Fixed` value sampled at `frame`. Used by [`copy_frame_block`] so a
 /// captured object never references an animation that won't travel with it.
 fn flatten_uncaptured_anims(
@@ -966,6 +1302,11 @@ fn flatten_uncaptured_anims(
             *coord = Coordinate::Fixed(coord.evaluate(frame, anims) as f64);
         }
     }
+    for scalar in obj.scalars_mut() {
+        if matches!(scalar, Scalar::Animated { anim, .. } if !keep.contains(anim)) {
+            *scalar = Scalar::Fixed(scalar.evaluate(frame, anims));
+        }
+    }
 }
 
 /// Capture the contiguous frame block `[lo, hi]` (inclusive, 0-based) of `source`
@@ -1097,14 +1438,8 @@ pub fn paste_frame_block(
         }
     }
     if !id_map.is_empty() {
-        for idx in first_new..target.objects.len() {
-            for coord in scene_object_coordinates_mut(&mut target.objects[idx]) {
-                if let Coordinate::Animated { anim, .. } = coord {
-                    if let Some(&new) = id_map.get(anim) {
-                        *anim = new;
-                    }
-                }
-            }
+        for obj in &mut target.objects[first_new..] {
+            remap_anim_ids(obj, &id_map);
         }
     }
 
@@ -1200,13 +1535,11 @@ pub fn apply_gap(
     }
 }
 
-/// True if `obj` has at least one `Animated` coordinate — only animated elements
-/// are gap-strobed, so a non-animated object can have no strobe copies.
+/// True if `obj` has at least one `Animated` coordinate or scalar — only
+/// animated elements are gap-strobed, so a non-animated object can have no
+/// strobe copies.
 fn is_animated(obj: &SceneObject) -> bool {
-    let mut clone = obj.clone();
-    scene_object_coordinates_mut(&mut clone)
-        .into_iter()
-        .any(|c| matches!(c, Coordinate::Animated { .. }))
+    !referenced_anim_ids(obj).is_empty()
 }
 
 /// A content key that ignores only the frame range: serialize `obj` with its
@@ -1287,6 +1620,7 @@ pub fn ensure_animation(
         auto_play,
         delay_ms,
         gap_frames,
+        layer: None,
     }));
 }
 
@@ -1303,7 +1637,7 @@ fn animation_span_by_id(source: &SourcePresentation, id: AnimId) -> Option<(usiz
     })
 }
 
-/// Flatten every coordinate on `obj` driven by animation `id` back to a static
+/// Flatten every coordinate (and scalar) on `obj` driven by animation `id` back to a static
 /// `Fixed` at its `from` value (where the motion began), leaving coordinates on
 /// *other* animations alone. Then widen the object's frame range to cover
 /// `[start, end_excl)` (never shrinking) so a reverted — possibly gap-strobed —
@@ -1317,6 +1651,13 @@ fn flatten_animation(obj: &mut SceneObject, id: AnimId, start: usize, end_excl:
             }
         }
     }
+    for scalar in obj.scalars_mut() {
+        if let Scalar::Animated { from, anim, .. } = *scalar {
+            if anim == id {
+                *scalar = Scalar::Fixed(from);
+            }
+        }
+    }
     if let Some(fr) = scene_object_frame_range_mut(obj) {
         if fr.start > start {
             fr.start = start;
@@ -1387,7 +1728,7 @@ pub fn prune_orphan_animations(source: &mut SourcePresentation) {
     }
 }
 
-/// Replace every `Animated` coordinate on `obj` with a `Fixed` value sampled at
+/// Replace every `Animated` coordinate (and scalar) on `obj` with a `Fixed` value sampled at
 /// `frame` (looking the span up in `anims`). Used when pasting/snapshotting: a
 /// clone re-anchored to a single frame can't ride an animation, and an animated
 /// coordinate is un-nudgeable (the arrow-key move only adjusts `Fixed`).
@@ -1399,6 +1740,9 @@ pub fn flatten_coordinates(obj: &mut SceneObject, frame: usize, anims: &AnimSpan
             *coord = Coordinate::Fixed(coord.evaluate(frame, anims) as f64);
         }
     }
+    for scalar in obj.scalars_mut() {
+        *scalar = Scalar::Fixed(scalar.evaluate(frame, anims));
+    }
 }
 
 /// Expand a selection so every selected `Group` also pulls in its members — a
@@ -1508,6 +1852,24 @@ pub fn move_frames(
 /// span (the single source of truth), and animated coordinates carry no span of
 /// their own, so remapping object ranges is all that's needed.
 fn remap_ranges_through_pos(source: &mut SourcePresentation, pos: &[usize], n: usize) {
+    if !source.durations.is_empty() {
+        let old = std::mem::take(&mut source.durations);
+        for (f, ms) in old.into_iter().enumerate().take(n) {
+            source.set_frame_duration(pos[f], ms);
+        }
+    }
+    if !source.on_enter.is_empty() {
+        let old = std::mem::take(&mut source.on_enter);
+        for (f, commands) in old.into_iter().enumerate().take(n) {
+            source.set_frame_on_enter(pos[f], commands);
+        }
+    }
+    if !source.sections.is_empty() {
+        let old = std::mem::take(&mut source.sections);
+        for (f, name) in old.into_iter().enumerate().take(n) {
+            source.set_frame_section(pos[f], name);
+        }
+    }
     for obj in &mut source.objects {
         if let Some(fr) = scene_object_frame_range_mut(obj) {
             if fr.start < fr.end {
@@ -1559,6 +1921,75 @@ pub fn parse_frame_selection(input: &str, frame_count: usize) -> Result<Vec<usiz
     Ok(out)
 }
 
+/// Parse a stamp-across-frames spec: an inclusive 1-based frame range (`5-12`,
+/// or a single frame) and an optional per-frame step `dx,dy` (`5-12 +1,0`).
+/// Returns `(first, last, dx, dy)` with 0-based frames, the range clamped to
+/// the deck.
+pub fn parse_stamp_spec(input: &str, frame_count: usize) -> Result<(usize, usize, i32, i32), String> {
+    let mut tokens = input.split_whitespace();
+    let range = tokens.next().ok_or("enter a frame range, e.g. 5-12")?;
+    let (a, b) = range.split_once('-').unwrap_or((range, range));
+    let a: usize = a.parse().map_err(|_| format!("bad range '{range}'"))?;
+    let b: usize = b.parse().map_err(|_| format!("bad range '{range}'"))?;
+    if a == 0 || b == 0 || a > b {
+        return Err(format!("bad range '{range}'"));
+    }
+    if a > frame_count {
+        return Err(format!("the deck has {frame_count} frame(s)"));
+    }
+    let (dx, dy) = match tokens.next() {
+        Some(step) => {
+            let bad = || format!("bad step '{step}' (want dx,dy)");
+            let (x, y) = step.split_once(',').ok_or_else(bad)?;
+            let x: i32 = x.trim().trim_start_matches('+').parse().map_err(|_| bad())?;
+            let y: i32 = y.trim().trim_start_matches('+').parse().map_err(|_| bad())?;
+            (x, y)
+        }
+        None => (0, 0),
+    };
+    if let Some(extra) = tokens.next() {
+        return Err(format!("unexpected '{extra}'"));
+    }
+    Ok((a - 1, b.min(frame_count) - 1, dx, dy))
+}
+
+/// Stamp object `index` once on each frame `first..=last` (0-based), for
+/// stop-motion effects animated coordinates can't express. Each copy — a
+/// group with copies of its members — lives on its one frame only, with any
+/// animated coordinate frozen at that frame, and the copy on the `k`th frame
+/// of the run sits `k × (dx, dy)` cells from the original. Returns the
+/// number of copies made.
+pub fn stamp_across_frames(
+    source: &mut SourcePresentation,
+    index: usize,
+    first: usize,
+    last: usize,
+    dx: i32,
+    dy: i32,
+) -> Result<usize, String> {
+    let anims = AnimSpans::of(source);
+    let is_group = matches!(source.objects.get(index), Some(SceneObject::Group(_)));
+    for (k, frame) in (first..=last).enumerate() {
+        let before = source.objects.len();
+        if is_group {
+            source.clone_group(index, 0, 0)?;
+        } else {
+            let obj = source.objects.get(index).ok_or_else(|| format!("no object {index}"))?.clone();
+            source.objects.push(obj);
+        }
+        let k = k as i32;
+        for obj in &mut source.objects[before..] {
+            if let Some(fr) = scene_object_frame_range_mut(obj) {
+                fr.start = frame;
+                fr.end = frame + 1;
+            }
+            flatten_coordinates(obj, frame, &anims);
+            properties::move_object(obj, k * dx, k * dy);
+        }
+    }
+    Ok(last + 1 - first)
+}
+
 /// Delete a set of frames (0-based), highest index first so the lower indices
 /// stay valid as the deck shrinks. Always keeps at least one frame — once the
 /// deck is down to a single frame, further deletions are skipped. Returns the
@@ -1577,9 +2008,35 @@ pub fn delete_frames(source: &mut SourcePresentation, frames: &[usize]) -> usize
     removed
 }
 
+/// Crop the deck to the frames `first..=last` (0-based): every frame before
+/// and after is deleted, and the ranges of objects and animations are rebased
+/// onto the kept block. Returns the number of frames removed.
+pub fn trim_frames(source: &mut SourcePresentation, first: usize, last: usize) -> usize {
+    let outside: Vec<usize> = (0..source.frame_count).filter(|&f| f < first || f > last).collect();
+    delete_frames(source, &outside)
+}
+
 /// Adjust all frame indices after frame `deleted` has been removed.
 pub fn adjust_frames_after_delete(source: &mut SourcePresentation, deleted: usize) {
     source.frame_count -= 1;
+    if deleted < source.durations.len() {
+        source.durations.remove(deleted);
+        while let Some(None) = source.durations.last() {
+            source.durations.pop();
+        }
+    }
+    if deleted < source.on_enter.len() {
+        source.on_enter.remove(deleted);
+        while source.on_enter.last().is_some_and(Vec::is_empty) {
+            source.on_enter.pop();
+        }
+    }
+    if deleted < source.sections.len() {
+        source.sections.remove(deleted);
+        while let Some(None) = source.sections.last() {
+            source.sections.pop();
+        }
+    }
     for obj in &mut source.objects {
         if let Some(fr) = scene_object_frame_range_mut(obj) {
             if fr.start > deleted {
@@ -1698,6 +2155,20 @@ pub fn frame_auto_advance_delay(source: &SourcePresentation, frame: usize) -> Op
     })
 }
 
+/// Resize the canvas to `width`×`height`, refitting the objects on it as `fit`
+/// says. Returns the indices of the objects that now draw outside it.
+pub fn resize_canvas(source: &mut SourcePresentation, width: u16, height: u16, fit: CanvasFit) -> Vec<usize> {
+    let old = (source.width, source.height);
+    properties::refit_objects(&mut source.objects, old, (width, height), fit);
+    source.width = width;
+    source.height = height;
+    crate::engine::Engine::compile_reporting(source)
+        .1
+        .into_iter()
+        .map(|c| c.object)
+        .collect()
+}
+
 /// Set (or replace) the single-frame auto-advance marker on `frame`. Any
 /// existing single-frame marker on the frame is removed first; a `delay_ms` of
 /// `0` just removes it (toggle off). Returns `true` if a marker is now present.
@@ -1721,6 +2192,7 @@ pub fn set_frame_auto_advance(
     source.objects.push(SceneObject::AutoAdvance(AutoAdvance {
         frames: FrameRange { start: frame, end: frame + 1 },
         delay_ms,
+        layer: None,
     }));
     true
 }
@@ -1745,6 +2217,59 @@ pub fn scene_object_summary(obj: &SceneObject) -> String {
             let name = if a.name.is_empty() { "custom" } else { &a.name };
             format!("Art: {name}")
         }
+        SceneObject::AnsiArt(a) => {
+            let name = if a.name.is_empty() { "custom" } else { &a.name };
+            format!("ANSI Art: {name}")
+        }
+        SceneObject::Cast(c) => {
+            let name = if c.name.is_empty() { "custom" } else { &c.name };
+            let n = c.frames.end.saturating_sub(c.frames.start);
+            format!("Cast: {name} ({n} frames)")
+        }
+        SceneObject::Countdown(c) => {
+            let last = c.frames.end.saturating_sub(1).max(c.frames.start);
+            let auto = if c.auto_play { ", auto" } else { "" };
+            format!("Countdown: {}→{}{auto}", c.text(c.frames.start), c.text(last))
+        }
+        SceneObject::Clock(c) => format!("Clock: {}", if c.seconds { "HH:MM:SS" } else { "HH:MM" }),
+        SceneObject::Footer(f) => {
+            let text_preview: String = f.text.chars().take(15).collect();
+            format!("Footer: \"{}\"", text_preview)
+        }
+        SceneObject::BackgroundEffect(b) => format!("BackgroundEffect: {}", b.effect.as_str()),
+        SceneObject::Watermark(w) => {
+            let text_preview: String = w.text.split('\n').next().unwrap_or("").chars().take(15).collect();
+            format!("Watermark: \"{}\"", text_preview)
+        }
+        SceneObject::Gauge(g) => format!("Gauge: {}–{}", g.min.start_value(), g.max.start_value()),
+        SceneObject::Connector(c) => format!("Connector: {} points", c.points.len()),
+        SceneObject::Keycap(k) => format!("Keycap: {}", k.keys.join("+")),
+        SceneObject::Spinner(s) => {
+            let text_preview: String = s.text.chars().take(15).collect();
+            format!("Spinner: \"{}\"", text_preview)
+        }
+        SceneObject::Marquee(m) => {
+            let text_preview: String = m.text.chars().take(15).collect();
+            format!("Marquee: \"{}\"", text_preview)
+        }
+        SceneObject::ScatterPlot(p) => format!("ScatterPlot: {} points", p.points.len()),
+        SceneObject::PieChart(p) => format!("PieChart: {} segments", p.segments.len()),
+        SceneObject::Gantt(g) => format!("Gantt: {} tasks", g.tasks.len()),
+        SceneObject::FileTree(t) => format!("FileTree: {} paths", t.entries.len()),
+        SceneObject::Diff(d) => {
+            let lines: Vec<_> = d.hunks().into_iter().flatten().collect();
+            let count = |kind| lines.iter().filter(|l| l.kind == kind).count();
+            format!("Diff: +{} -{}", count(DiffLineKind::Added), count(DiffLineKind::Removed))
+        }
+        SceneObject::Heatmap(h) => {
+            let (cols, rows) = (h.values.iter().map(Vec::len).max().unwrap_or(0), h.values.len());
+            format!("Heatmap: {}×{}", cols, rows)
+        }
+        SceneObject::Toc(t) => {
+            let numbers = if t.numbers { "numbered" } else { "names" };
+            let highlight = if t.highlight.is_some() { ", highlight" } else { "" };
+            format!("Toc: {numbers}{highlight}")
+        }
         SceneObject::Command(c) => format!("Command: {}", c.command),
         SceneObject::List(l) => {
             let kind = if l.ordered { "ordered" } else { "unordered" };
@@ -1774,6 +2299,8 @@ pub fn scene_object_summary(obj: &SceneObject) -> String {
             format!("Auto-advance: {lo}-{hi} ({})", format_secs(a.delay_ms))
         }
         SceneObject::Circle(c) => format!("Circle: ⌀{} '{}'", c.diameter, c.ch),
+        SceneObject::Instance(o) => format!("Instance: {}", o.component),
+        SceneObject::Include(o) => format!("Include: {} @{}", o.path, o.at + 1),
     }
 }
 
@@ -1793,7 +2320,25 @@ mod tests {
     }
 
     fn pres(frame_count: usize, objects: Vec<SceneObject>) -> SourcePresentation {
-        SourcePresentation { width: 80, height: 24, frame_count, objects, links: Vec::new() }
+        SourcePresentation {
+            format_version: SOURCE_FORMAT_VERSION,
+            width: 80,
+            height: 24,
+            frame_count,
+            objects,
+            links: Vec::new(),
+            background: None,
+            layers: Vec::new(),
+            components: Default::default(),
+            vars: Default::default(),
+            slides: Vec::new(),
+            lint: Default::default(),
+            auto_z: false,
+            durations: Vec::new(),
+            on_enter: Vec::new(),
+            sections: Vec::new(),
+            metadata: Default::default(),
+        }
     }
 
     fn range(obj: &SceneObject) -> (usize, usize) {
@@ -2241,9 +2786,9 @@ mod tests {
             8,
             vec![
                 mk(1, 0, 9, 0, 3),
-                SceneObject::Animation(Animation { id: 1, frames: FrameRange { start: 0, end: 4 }, auto_play: true, delay_ms: 500, gap_frames: 0 }),
+                SceneObject::Animation(Animation { id: 1, frames: FrameRange { start: 0, end: 4 }, auto_play: true, delay_ms: 500, gap_frames: 0, layer: None }),
                 mk(2, 0, 9, 4, 7),
-                SceneObject::Animation(Animation { id: 2, frames: FrameRange { start: 4, end: 8 }, auto_play: true, delay_ms: 500, gap_frames: 0 }),
+                SceneObject::Animation(Animation { id: 2, frames: FrameRange { start: 4, end: 8 }, auto_play: true, delay_ms: 500, gap_frames: 0, layer: None }),
             ],
         );
         delete_frames(&mut p, &[2, 3, 4, 5]);
@@ -2376,6 +2921,85 @@ mod tests {
         assert_eq!(range(&p.objects[0]), (0, 5)); // background still spans the deck
     }
 
+    #[test]
+    fn ripple_shift_moves_every_range_from_the_frame_onward() {
+        // Background 0..8 crosses frame 3, B starts on it, A ends before it.
+        let mut p = pres(8, vec![label(0, 8), label(0, 3), label(3, 5), label(5, 8)]);
+        assert_eq!(ripple_shift(&mut p, 3, 2, None), 3);
+        let ranges: Vec<_> = p.objects.iter().map(range).collect();
+        assert_eq!(ranges, [(0, 10), (0, 3), (5, 7), (7, 10)], "two blank frames open at 3..5");
+        assert_eq!(p.frame_count, 10);
+
+        // Backwards: boundaries clamp at the frame, and no range empties.
+        assert_eq!(ripple_shift(&mut p, 3, -4, None), 3);
+        let ranges: Vec<_> = p.objects.iter().map(range).collect();
+        assert_eq!(ranges, [(0, 6), (0, 3), (3, 4), (3, 6)]);
+        assert_eq!(p.frame_count, 6);
+    }
+
+    #[test]
+    fn ripple_shift_of_a_selection_carries_its_animations_and_group_members() {
+        let mut p = pres(6, vec![animated_label(2, 4, 1, 0, 9), label(2, 4), label(2, 6), group(vec![2])]);
+        ensure_animation(&mut p, 1, 2, 4, true, 500, 0);
+        let moved = ripple_shift(&mut p, 2, 3, Some(&[0, 3]));
+        assert_eq!(moved, 3, "the label, its animation and the group's member");
+        let ranges: Vec<_> = p.objects.iter().filter_map(|o| scene_object_frame_range(o).map(|r| (r.start, r.end))).collect();
+        assert_eq!(ranges, [(5, 7), (2, 4), (5, 9), (5, 7)], "the unselected label stays put");
+        assert_eq!(p.frame_count, 9, "the deck grows to fit");
+    }
+
+    #[test]
+    fn frame_durations_follow_frames_through_insert_copy_move_and_delete() {
+        let mut p = pres(4, vec![label(0, 4)]);
+        p.set_frame_duration(1, Some(1000));
+        p.set_frame_duration(2, Some(2000));
+        insert_blank_frame(&mut p, 0);
+        assert_eq!(p.durations, [None, None, Some(1000), Some(2000)], "the blank frame has none");
+        copy_frame(&mut p, 2);
+        assert_eq!(p.durations, [None, None, Some(1000), Some(1000), Some(2000)], "a copy keeps its source's");
+        copy_frames(&mut p, 3, 4, 0, true);
+        assert_eq!(p.frame_duration(0), Some(1000));
+        assert_eq!(p.frame_duration(1), Some(2000));
+        assert_eq!(p.frame_count, 8);
+
+        let mut p = pres(3, vec![label(0, 3)]);
+        p.set_frame_duration(0, Some(500));
+        move_frame(&mut p, 0, 2, false);
+        assert_eq!(p.durations, [None, None, Some(500)]);
+        delete_frames(&mut p, &[2]);
+        assert!(p.durations.is_empty(), "deleting the only timed frame leaves no trailing entries");
+    }
+
+    #[test]
+    fn frame_on_enter_commands_follow_frames_like_durations() {
+        let demo = || vec!["make demo".to_string()];
+        let mut p = pres(3, vec![label(0, 3)]);
+        p.set_frame_on_enter(1, demo());
+        insert_blank_frame(&mut p, 0);
+        assert_eq!(p.frame_on_enter(2), demo());
+        assert!(p.frame_on_enter(1).is_empty(), "the blank frame runs nothing");
+        copy_frame(&mut p, 2);
+        assert_eq!(p.frame_on_enter(3), demo(), "a copy keeps its source's");
+        move_frame(&mut p, 3, 0, true);
+        assert_eq!(p.frame_on_enter(0), demo());
+        delete_frames(&mut p, &[0, 3]);
+        assert!(p.on_enter.is_empty(), "no trailing empty entries");
+    }
+
+    #[test]
+    fn sections_follow_frames_but_copies_start_none() {
+        let mut p = pres(3, vec![label(0, 3)]);
+        p.set_frame_section(1, Some("Demo".into()));
+        insert_blank_frame(&mut p, 0);
+        assert_eq!(p.frame_section(2), Some("Demo"));
+        copy_frame(&mut p, 2);
+        assert_eq!(p.frame_section(3), None, "a copy doesn't repeat the toc entry");
+        move_frame(&mut p, 2, 0, true);
+        assert_eq!(p.frame_section(0), Some("Demo"));
+        delete_frames(&mut p, &[0]);
+        assert!(p.sections.is_empty(), "no trailing empty entries");
+    }
+
     #[test]
     fn copy_frames_duplicates_a_block_after_target() {
         // Deck A,B,C,D; copy block [1,2] (B,C) after frame 3 → +2 frames at [4,6).
@@ -2537,6 +3161,31 @@ mod tests {
         assert!(parse_frame_selection("20-30", 10).is_err()); // all out of range
     }
 
+    #[test]
+    fn saving_over_a_slides_deck_asks_first_and_save_as_leaves_it_alone() {
+        let dir = std::env::temp_dir().join(format!("bs_slides_save_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let deck = dir.join("deck.json").to_str().unwrap().to_string();
+        let json = r#"{"width":10,"height":2,"slides":[{"objects":[{"type":"label","text":"hi","position":{"x":{"fixed":0},"y":{"fixed":0}}}]}]}"#;
+        std::fs::write(&deck, json).unwrap();
+
+        let mut state = EditorState::open(&deck).unwrap();
+        assert_eq!(state.source.objects.len(), 1, "edited lowered");
+        let err = state.save().unwrap_err().to_string();
+        assert!(err.contains("`slides`"), "{err}");
+        assert_eq!(std::fs::read_to_string(&deck).unwrap(), json, "the slides are kept");
+
+        let copy = dir.join("flat.json").to_str().unwrap().to_string();
+        let mut other = EditorState::open(&deck).unwrap();
+        other.save_as(&copy).unwrap();
+        assert_eq!(std::fs::read_to_string(&deck).unwrap(), json, "save as writes elsewhere without asking");
+
+        state.save().unwrap();
+        let saved: SourcePresentation = serde_json::from_str(&std::fs::read_to_string(&deck).unwrap()).unwrap();
+        assert!(saved.slides.is_empty() && saved.objects.len() == 1, "the second save writes the flat deck");
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn save_as_writes_the_file_and_adopts_the_path() {
         let target = std::env::temp_dir().join("bs_save_as_unit_test.json");
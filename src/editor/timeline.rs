@@ -4,6 +4,7 @@ use crossterm::{cursor, queue, style, terminal};
 
 use crate::engine::source::SceneObject;
 
+use super::config::Theme;
 use super::state::{EditorState, Mode};
 use super::ui::Layout;
 
@@ -17,13 +18,24 @@ pub fn render_timeline(
     let frame_count = state.source.frame_count;
     let current = state.current_frame;
 
-    // Row 1: the frame bar (slide range indicator) — always shown, including
-    // while typing a jump/select, where it live-previews the chosen slides.
+    // Row 1: the scrubber — the whole deck as a proportional track with the
+    // current frame's thumb; click or drag along it to move (`scrub_frame`).
     queue!(
         stdout,
         cursor::MoveTo(0, y),
         terminal::Clear(terminal::ClearType::CurrentLine),
     )?;
+    if frame_count > 0 {
+        render_scrubber(stdout, &state.config.theme, width, frame_count, current)?;
+    }
+
+    // Row 2: the frame bar (slide range indicator) — always shown, including
+    // while typing a jump/select, where it live-previews the chosen slides.
+    queue!(
+        stdout,
+        cursor::MoveTo(0, y + 1),
+        terminal::Clear(terminal::ClearType::CurrentLine),
+    )?;
 
     if frame_count == 0 {
         queue!(stdout, style::Print(" (no frames)"))?;
@@ -47,14 +59,14 @@ pub fn render_timeline(
                 .unwrap_or_default(),
             _ => Vec::new(),
         };
-        render_frame_bar(stdout, width, &segs, current, &live)?;
+        render_frame_bar(stdout, &state.config.theme, width, &segs, current, &live)?;
     }
 
-    // Row 2: Mode + status — or, while typing a frame jump/select, the input
+    // Row 3: Mode + status — or, while typing a frame jump/select, the input
     // field with its instructions sitting on the same row right behind it.
     queue!(
         stdout,
-        cursor::MoveTo(0, y + 1),
+        cursor::MoveTo(0, y + 2),
         terminal::Clear(terminal::ClearType::CurrentLine),
     )?;
 
@@ -77,6 +89,52 @@ pub fn render_timeline(
             *cursor,
             "(0 = off · Enter: set · Esc: cancel)".to_string(),
         )),
+        Mode::FrameDurationInput { buf, cursor } => Some((
+            "Frame duration (s): ",
+            buf.clone(),
+            *cursor,
+            "(0 = none · Enter: set · Esc: cancel)".to_string(),
+        )),
+        Mode::FrameSectionInput { buf, cursor } => Some((
+            "Section name: ",
+            buf.clone(),
+            *cursor,
+            "(empty = none · Enter: set · Esc: cancel)".to_string(),
+        )),
+        Mode::FindInput { buf, cursor, regex } => Some((
+            if *regex { "Find (regex): " } else { "Find: " },
+            buf.clone(),
+            *cursor,
+            "(label, header and table text · Tab: literal/regex · Enter: next · Esc: cancel)".to_string(),
+        )),
+        Mode::ReplaceInput { find, regex, buf, cursor } => Some((
+            "Replace with: ",
+            buf.clone(),
+            *cursor,
+            format!(
+                "(for '{find}'{} · Enter: step through matches · Esc: back)",
+                if *regex { ", $1 for a group" } else { "" },
+            ),
+        )),
+        Mode::StampFramesInput { buf, cursor, .. } => Some((
+            "Stamp across frames: ",
+            buf.clone(),
+            *cursor,
+            "(e.g. 5-12, or 5-12 +1,0 to step each copy · Enter: stamp · Esc: cancel)".to_string(),
+        )),
+        Mode::FrameRippleInput { buf, cursor, members } => Some((
+            "Shift ranges by: ",
+            buf.clone(),
+            *cursor,
+            format!(
+                "(frames, e.g. +3 or -2, from frame {} on · {} · Enter: shift · Esc: cancel)",
+                current + 1,
+                match members {
+                    Some(m) => format!("{} selected", m.len()),
+                    None => "all objects".to_string(),
+                },
+            ),
+        )),
         _ => None,
     };
     if let Some((prefix, buf, cursor, instructions)) = input_field {
@@ -87,7 +145,7 @@ pub fn render_timeline(
         };
         let display = format!("{prefix}{buf}   {trailing}");
         let caret = prefix.chars().count() + cursor;
-        super::panel::draw_caret_line(stdout, 0, y + 1, &display, Some(caret), false, width)?;
+        super::panel::draw_caret_line(stdout, &state.config.theme, 0, y + 2, &display, Some(caret), false, width)?;
         return Ok(());
     }
 
@@ -110,14 +168,23 @@ pub fn render_timeline(
         Mode::AddArt { .. } => "ADD ART",
         Mode::LoadArtFile { .. } => "LOAD ART",
         Mode::Settings { .. } => "SETTINGS",
+        Mode::KeyBindings { .. } => "KEY BINDINGS",
         Mode::TableAddColumn { .. } => "ADD COL",
         Mode::TableRemoveColumn { .. } => "REMOVE COL",
         Mode::TableEditCellProps { .. } => "EDIT CELLS",
+        Mode::EditInPlace { .. } => "EDIT TEXT",
+        Mode::PickCoordinate { .. } => "PICK",
         Mode::SaveAs { .. } => "SAVE AS",
         Mode::FrameMenu => "FRAME",
         Mode::FrameJump { .. } => "JUMP",
         Mode::FrameSelectInput { .. } => "SELECT FRAMES",
         Mode::FrameAutoInput { .. } => "AUTO-ADVANCE",
+        Mode::FrameDurationInput { .. } => "DURATION",
+        Mode::FrameSectionInput { .. } => "SECTION",
+        Mode::FrameRippleInput { .. } => "RIPPLE",
+        Mode::StampFramesInput { .. } => "STAMP",
+        Mode::FindInput { .. } | Mode::ReplaceInput { .. } => "FIND",
+        Mode::ReplaceConfirm { .. } => "REPLACE",
         Mode::FrameSelected { .. } => "FRAMES SELECTED",
         Mode::FrameRangePlace { copy: false, .. } => "MOVE RANGE",
         Mode::FrameRangePlace { copy: true, .. } => "COPY RANGE",
@@ -125,7 +192,12 @@ pub fn render_timeline(
         Mode::FrameOverlay { .. } => "OVERLAY FRAME",
         Mode::FramePastePlace => "PASTE FRAMES",
         Mode::PresentationMenu { .. } => "PRESENTATIONS",
+        Mode::Layers { .. } => "LAYERS",
+        Mode::Lanes { .. } => "LANES",
+        Mode::Diagnostics { .. } => "DIAGNOSTICS",
         Mode::OpenFile { .. } => "OPEN FILE",
+        Mode::Start { .. } => "START",
+        Mode::NewDeck { .. } => "NEW PRESENTATION",
     };
     let dirty_str = if state.dirty { " [modified]" } else { "" };
     // Replace newlines so a multi-line label value doesn't scroll the terminal.
@@ -134,7 +206,7 @@ pub fn render_timeline(
 
     queue!(
         stdout,
-        style::SetAttribute(style::Attribute::Dim),
+        state.config.theme.dimmed(),
         style::Print(format!(
             " {mode_str} | Frame {}/{frame_count}{dirty_str} {status}",
             current + 1,
@@ -145,6 +217,67 @@ pub fn render_timeline(
     Ok(())
 }
 
+/// Columns of the scrubber's track for a row `width` wide: one column of margin
+/// on each side.
+fn track_len(width: usize) -> usize {
+    width.saturating_sub(2)
+}
+
+/// The frame a track column stands for. Both ends are exact — the first column
+/// is frame 0 and the last is the last frame — and the rest round to nearest.
+pub(super) fn frame_at(col: usize, len: usize, frame_count: usize) -> usize {
+    if len <= 1 || frame_count <= 1 {
+        return 0;
+    }
+    let col = col.min(len - 1);
+    (col * (frame_count - 1) * 2 + (len - 1)) / (2 * (len - 1))
+}
+
+/// The track column holding `frame`'s thumb — the inverse of [`frame_at`] (exact
+/// whenever the track has at least one column per frame).
+pub(super) fn thumb_col(frame: usize, len: usize, frame_count: usize) -> usize {
+    if len <= 1 || frame_count <= 1 {
+        return 0;
+    }
+    let frame = frame.min(frame_count - 1);
+    (frame * (len - 1) * 2 + (frame_count - 1)) / (2 * (frame_count - 1))
+}
+
+/// The frame under screen column `x` of a scrubber drawn `term_width` wide.
+/// Clicks in the margins clamp to the first/last frame.
+pub(crate) fn scrub_frame(x: u16, term_width: u16, frame_count: usize) -> usize {
+    frame_at((x as usize).saturating_sub(1), track_len(term_width as usize), frame_count)
+}
+
+/// Draw the track: played frames heavy, the rest light and dim, with a thumb on
+/// the current frame.
+fn render_scrubber(
+    stdout: &mut io::Stdout,
+    theme: &Theme,
+    width: usize,
+    frame_count: usize,
+    current: usize,
+) -> anyhow::Result<()> {
+    let len = track_len(width);
+    if len == 0 {
+        return Ok(());
+    }
+    let thumb = thumb_col(current, len, frame_count);
+    let played: String = "━".repeat(thumb);
+    let rest: String = "─".repeat(len - thumb - 1);
+    queue!(
+        stdout,
+        style::Print(format!(" {played}")),
+        style::SetAttribute(style::Attribute::Bold),
+        style::Print("●"),
+        style::SetAttribute(style::Attribute::Reset),
+        theme.dimmed(),
+        style::Print(rest),
+        style::SetAttribute(style::Attribute::Reset),
+    )?;
+    Ok(())
+}
+
 /// One cell in the frame bar: a single frame, or a collapsed auto-play range
 /// `[start, end)` shown as one "lo-hi" block. Frames under an auto-play
 /// animation advance on their own, so the whole sequence reads as one unit.
@@ -220,6 +353,7 @@ fn build_segments(state: &EditorState) -> Vec<Seg> {
 
 fn render_frame_bar(
     stdout: &mut io::Stdout,
+    theme: &Theme,
     width: usize,
     segs: &[Seg],
     current: usize,
@@ -231,7 +365,7 @@ fn render_frame_bar(
     let total: usize = segs.iter().map(|s| s.label().chars().count() + 1).sum::<usize>() + 1;
     if total <= width {
         for seg in segs {
-            render_seg(stdout, seg, current, selected)?;
+            render_seg(stdout, theme, seg, current, selected)?;
         }
         return Ok(());
     }
@@ -251,7 +385,7 @@ fn render_frame_bar(
                 queue!(stdout, style::Print("... "))?;
             }
         }
-        render_seg(stdout, &segs[i], current, selected)?;
+        render_seg(stdout, theme, &segs[i], current, selected)?;
         prev = Some(i);
     }
     Ok(())
@@ -318,13 +452,13 @@ fn abbreviated_indices(segs: &[Seg], cur: usize, width: usize) -> Vec<usize> {
     pick_indices(segs.len(), cur, 1)
 }
 
-fn render_seg(stdout: &mut io::Stdout, seg: &Seg, current: usize, selected: &[usize]) -> anyhow::Result<()> {
+fn render_seg(stdout: &mut io::Stdout, theme: &Theme, seg: &Seg, current: usize, selected: &[usize]) -> anyhow::Result<()> {
     let label = seg.label();
     let highlight = seg.contains(current) || selected.iter().any(|&f| seg.contains(f));
     if highlight {
         queue!(
             stdout,
-            style::SetAttribute(style::Attribute::Reverse),
+            theme.selection(),
             style::Print(&label),
             style::SetAttribute(style::Attribute::Reset),
             style::Print(" "),
@@ -343,6 +477,28 @@ mod tests {
         (0..n).map(Seg::Single).collect()
     }
 
+    #[test]
+    fn scrubber_ends_are_exact_and_every_frame_has_its_own_column() {
+        for (len, frames) in [(78, 5), (78, 78), (10, 1), (40, 39)] {
+            assert_eq!(frame_at(0, len, frames), 0);
+            assert_eq!(frame_at(len - 1, len, frames), frames - 1);
+            for f in 0..frames {
+                assert_eq!(frame_at(thumb_col(f, len, frames), len, frames), f, "len {len}, {frames} frames");
+            }
+        }
+    }
+
+    #[test]
+    fn scrubber_columns_spread_a_long_deck_evenly() {
+        // 300 frames on a 78-column track: several frames per column, monotonic,
+        // with the margins clamping to the ends.
+        let cols: Vec<usize> = (0..78).map(|c| frame_at(c, 78, 300)).collect();
+        assert!(cols.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(frame_at(39, 78, 300), 151);
+        assert_eq!(scrub_frame(0, 80, 300), 0, "left margin");
+        assert_eq!(scrub_frame(79, 80, 300), 299, "right margin");
+    }
+
     #[test]
     fn pick_indices_shows_three_at_each_edge_plus_the_current_window() {
         // 20 segments, cursor at 10: first 3, the current ±3, and last 3.
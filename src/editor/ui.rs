@@ -5,6 +5,10 @@ use super::state::Mode;
 /// Used by both the renderer and the input handler for scroll calculations.
 pub const RIGHT_PANEL_WIDTH: u16 = 24;
 
+/// Rows under the canvas: the proportional scrubber, the frame-label bar and the
+/// mode/status line.
+pub const TIMELINE_HEIGHT: u16 = 3;
+
 pub struct Layout {
     pub right_panel_width: u16,
     pub canvas_x: u16,
@@ -21,8 +25,8 @@ pub struct Layout {
 /// every side (so a border can be drawn around it). Used when editing a
 /// `Text` property — far roomier than the ~21-column right-panel field.
 pub fn text_overlay(layout: &Layout) -> (u16, u16, u16, u16) {
-    let w = layout.canvas_width.min(64).max(12);
-    let h = layout.canvas_height.min(16).max(5);
+    let w = layout.canvas_width.clamp(12, 64);
+    let h = layout.canvas_height.clamp(5, 16);
     let x = layout.canvas_x + layout.canvas_width.saturating_sub(w) / 2;
     let y = layout.canvas_y + layout.canvas_height.saturating_sub(h) / 2;
     (x, y, w, h)
@@ -31,7 +35,7 @@ pub fn text_overlay(layout: &Layout) -> (u16, u16, u16, u16) {
 /// Centred geometry for the single-line "Save As" popup: `(x, y, w, h)` with a
 /// fixed 3-row height (top border + input line + bottom border).
 pub fn save_as_overlay(layout: &Layout) -> (u16, u16, u16, u16) {
-    let w = layout.canvas_width.min(60).max(16);
+    let w = layout.canvas_width.clamp(16, 60);
     let h = 3u16;
     let x = layout.canvas_x + layout.canvas_width.saturating_sub(w) / 2;
     let y = layout.canvas_y + layout.canvas_height.saturating_sub(h) / 2;
@@ -52,13 +56,16 @@ impl Layout {
             | Mode::AddArt { .. }
             | Mode::LoadArtFile { .. }
             | Mode::PresentationMenu { .. }
+            | Mode::Layers { .. }
+            | Mode::Diagnostics { .. }
             | Mode::OpenFile { .. }
-            | Mode::Settings { .. } => RIGHT_PANEL_WIDTH,
+            | Mode::Settings { .. }
+            | Mode::KeyBindings { .. } => RIGHT_PANEL_WIDTH,
             _ => 0,
         };
         // Fullscreen ("no bars") mode hides the menu bar and timeline, handing
         // their rows to the canvas.
-        let timeline_h: u16 = if fullscreen { 0 } else { 2 };
+        let timeline_h: u16 = if fullscreen { 0 } else { TIMELINE_HEIGHT };
         // SelectedObject has more key hints, so reserve 2 lines.
         let menu_h: u16 = if fullscreen {
             0
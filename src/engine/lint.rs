@@ -0,0 +1,214 @@
+//! Lint — suspicious but legal content, found by compiling the deck.
+//!
+//! Unlike `bs validate`'s structural checks, a lint finding doesn't make the
+//! deck wrong: a label may be meant to run off the canvas, a frame may be
+//! blank on purpose. Each rule therefore has a level the deck can set in its
+//! `lint` map (`allow` silences it, `deny` makes it an error); everything is
+//! a warning by default.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{str_width, Color, DrawOp};
+
+use super::source::{AnimSpans, SceneObject, SourcePresentation};
+use super::Engine;
+
+/// The lowest foreground/background contrast ratio (WCAG's, 1–21) a glyph
+/// may have before it counts as unreadable — the WCAG minimum for large text,
+/// which is what a projected terminal cell amounts to.
+const MIN_CONTRAST: f64 = 3.0;
+
+/// A lint rule, as named in a deck's `lint` map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintRule {
+    /// A label wider than the whole canvas: some of it is clipped wherever it
+    /// is placed.
+    WideLabel,
+    /// Two rects with a `bg` whose boxes intersect on a frame they share.
+    OverlappingRects,
+    /// A frame that draws nothing visible.
+    EmptyFrame,
+    /// Text whose colour is too close to the background behind it.
+    LowContrast,
+    /// A table with an explicit `height` its rows don't fit in (tables grow
+    /// past it rather than clip).
+    TableOverflow,
+}
+
+impl LintRule {
+    pub const ALL: &'static [LintRule] = &[
+        LintRule::WideLabel,
+        LintRule::OverlappingRects,
+        LintRule::EmptyFrame,
+        LintRule::LowContrast,
+        LintRule::TableOverflow,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LintRule::WideLabel => "wide_label",
+            LintRule::OverlappingRects => "overlapping_rects",
+            LintRule::EmptyFrame => "empty_frame",
+            LintRule::LowContrast => "low_contrast",
+            LintRule::TableOverflow => "table_overflow",
+        }
+    }
+}
+
+/// How a deck wants a rule's findings reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintLevel {
+    /// Not checked at all.
+    Allow,
+    #[default]
+    Warn,
+    Deny,
+}
+
+/// One lint finding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lint {
+    pub rule: LintRule,
+    /// The rule's level in this deck (never `Allow`).
+    pub level: LintLevel,
+    /// Index of the offending object, when the finding is about one.
+    pub object: Option<usize>,
+    /// The frames (ascending) it was found on; empty for findings that don't
+    /// depend on the frame.
+    pub frames: Vec<usize>,
+    pub message: String,
+}
+
+impl Engine {
+    /// Compile `source` and report suspicious content, ordered by rule and
+    /// then object, skipping rules the deck's `lint` map allows.
+    pub fn lint(source: &SourcePresentation) -> Vec<Lint> {
+        let level = |rule| source.lint.get(&rule).copied().unwrap_or_default();
+        let on = |rule| level(rule) != LintLevel::Allow;
+
+        // Per-frame findings, keyed by (rule, object, message).
+        let mut found: BTreeMap<(LintRule, Option<usize>, String), Vec<usize>> = BTreeMap::new();
+        let mut boxes: Vec<(usize, usize, Bounds)> = Vec::new();
+        let scenes = Self::compile_visiting(source, |frame, object, ops| {
+            if on(LintRule::LowContrast) && low_contrast(ops, source.background.as_ref()) {
+                let key = (LintRule::LowContrast, Some(object), "text is hard to read against its background".into());
+                found.entry(key).or_default().push(frame);
+            }
+            if on(LintRule::OverlappingRects) {
+                if let Some(SceneObject::Rect(r)) = source.objects.get(object) {
+                    if r.style.bg.is_some() {
+                        if let Some(b) = Bounds::around(ops) {
+                            boxes.push((frame, object, b));
+                        }
+                    }
+                }
+            }
+        });
+        for (k, &(frame, a, ref box_a)) in boxes.iter().enumerate() {
+            for &(_, b, ref box_b) in boxes[k + 1..].iter().filter(|(f, ..)| *f == frame) {
+                if box_a.intersects(box_b) {
+                    let message = format!("opaque rect overlaps the opaque rect objects[{a}]");
+                    found.entry((LintRule::OverlappingRects, Some(b), message)).or_default().push(frame);
+                }
+            }
+        }
+        if on(LintRule::EmptyFrame) {
+            for (frame, scene) in scenes.iter().enumerate() {
+                if !scene.ops.iter().any(|op| op.ch != ' ' || op.style.bg.is_some()) {
+                    found.entry((LintRule::EmptyFrame, None, "the frame draws nothing".into())).or_default().push(frame);
+                }
+            }
+        }
+
+        let mut out: Vec<Lint> = found
+            .into_iter()
+            .map(|((rule, object, message), frames)| Lint { rule, level: level(rule), object, frames, message })
+            .collect();
+        let anims = AnimSpans::of(source);
+        for (i, obj) in source.objects.iter().enumerate() {
+            let static_lint = |rule, message| Lint { rule, level: level(rule), object: Some(i), frames: Vec::new(), message };
+            match obj {
+                SceneObject::Label(l) if on(LintRule::WideLabel) => {
+                    let w = l.width.evaluate(l.frames.start, &anims) as usize;
+                    let w = if w > 0 { w } else { l.plain_text().split('\n').map(str_width).max().unwrap_or(0) };
+                    let pad = (l.padding.left + l.padding.right) as usize;
+                    let w = w + pad + if l.framed { 2 } else { 0 };
+                    if w > source.width as usize {
+                        let message = format!("label is {w} columns wide on a {}-column canvas", source.width);
+                        out.push(static_lint(LintRule::WideLabel, message));
+                    }
+                }
+                SceneObject::Table(t) if on(LintRule::TableOverflow) => {
+                    let height = t.height.evaluate(t.frames.start, &anims);
+                    let natural = t.natural_height(t.frames.start, &anims);
+                    if height > 0 && natural > height {
+                        let message = format!("table rows need {natural} lines but its height is {height}");
+                        out.push(static_lint(LintRule::TableOverflow, message));
+                    }
+                }
+                _ => {}
+            }
+        }
+        out.sort_by_key(|l| (l.rule, l.object));
+        out
+    }
+}
+
+/// Whether any glyph in `ops` has too little contrast with the background it
+/// is drawn on (its own `bg`, else the deck's). Glyphs over the terminal's own
+/// background, or in its own foreground, are never flagged: their colours are
+/// unknown.
+fn low_contrast(ops: &[DrawOp], background: Option<&Color>) -> bool {
+    ops.iter().any(|op| {
+        let (Some(fg), Some(bg)) = (op.style.fg.as_ref(), op.style.bg.as_ref().or(background)) else {
+            return false;
+        };
+        op.ch != ' ' && contrast(fg, bg) < MIN_CONTRAST
+    })
+}
+
+/// The WCAG contrast ratio between two colours, from 1 (identical) to 21.
+fn contrast(a: &Color, b: &Color) -> f64 {
+    let (la, lb) = (luminance(a), luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// WCAG relative luminance of an sRGB colour.
+fn luminance(c: &Color) -> f64 {
+    let (r, g, b) = c.rgb();
+    let lin = |v: u8| {
+        let v = v as f64 / 255.0;
+        if v <= 0.03928 { v / 12.92 } else { ((v + 0.055) / 1.055).powf(2.4) }
+    };
+    0.2126 * lin(r) + 0.7152 * lin(g) + 0.0722 * lin(b)
+}
+
+/// The cells an object covered on one frame, as an inclusive box.
+struct Bounds {
+    x0: u16,
+    y0: u16,
+    x1: u16,
+    y1: u16,
+}
+
+impl Bounds {
+    fn around(ops: &[DrawOp]) -> Option<Bounds> {
+        let first = ops.first()?;
+        let mut r = Bounds { x0: first.x, y0: first.y, x1: first.x, y1: first.y };
+        for op in ops {
+            r.x0 = r.x0.min(op.x);
+            r.y0 = r.y0.min(op.y);
+            r.x1 = r.x1.max(op.x);
+            r.y1 = r.y1.max(op.y);
+        }
+        Some(r)
+    }
+
+    fn intersects(&self, other: &Bounds) -> bool {
+        self.x0 <= other.x1 && other.x0 <= self.x1 && self.y0 <= other.y1 && other.y0 <= self.y1
+    }
+}
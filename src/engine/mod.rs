@@ -6,26 +6,83 @@
 //! The engine understands time, animation, layout, and relationships.
 //! It never deals with terminals, ANSI codes, or grids.
 
+mod lint;
 pub mod objects;
 pub mod source;
 
-use crate::types::ResolvedScene;
+pub use lint::{Lint, LintLevel, LintRule};
+
+use std::collections::BTreeMap;
+
+use crate::types::{DrawOp, ResolvedScene};
 use objects::{Resolve, ResolveCtx};
-use source::{AnimSpans, FrameRange, SourcePresentation};
+use source::{AnimSpans, FrameRange, SceneObject, SourcePresentation};
 
 pub struct Engine;
 
+/// An object that drew at least one cell outside the canvas — content the
+/// renderer silently clips.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutOfBounds {
+    /// Index of the object in `SourcePresentation::objects`.
+    pub object: usize,
+    /// The frames (ascending) on which it was clipped.
+    pub frames: Vec<usize>,
+}
+
 impl Engine {
     /// Compile a source presentation into resolved scenes, one per frame.
     pub fn compile(source: &SourcePresentation) -> Vec<ResolvedScene> {
+        Self::compile_reporting(source).0
+    }
+
+    /// Just frame `frame` of [`Engine::compile`] — for the editor's preview,
+    /// which shows one frame at a time.
+    pub fn compile_frame(source: &SourcePresentation, frame: usize) -> ResolvedScene {
+        let source = &*source.with_vars_applied();
+        let overrides = source.member_overrides();
+        let expansions = source.instance_expansions();
+        let anims = AnimSpans::of(source);
+        let sections = source.section_starts();
+        Self::resolve_frame(source, frame, &overrides, &expansions, &anims, &sections, &mut |_, _, _| {})
+    }
+
+    /// [`Engine::compile`], also reporting every object that drew outside the
+    /// canvas and on which frames (ordered by object index).
+    pub fn compile_reporting(source: &SourcePresentation) -> (Vec<ResolvedScene>, Vec<OutOfBounds>) {
+        let mut clipped: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        let scenes = Self::compile_visiting(source, |frame, object, ops| {
+            if ops.iter().any(|op| op.x >= source.width || op.y >= source.height) {
+                clipped.entry(object).or_default().push(frame);
+            }
+        });
+        let clipped = clipped
+            .into_iter()
+            .map(|(object, frames)| OutOfBounds { object, frames })
+            .collect();
+        (scenes, clipped)
+    }
+
+    /// [`Engine::compile`], handing `visit` each object's draw ops on every
+    /// frame as `(frame, object index, ops)` — how the compile report and
+    /// [`Engine::lint`] attribute what they find to objects.
+    pub(crate) fn compile_visiting(
+        source: &SourcePresentation,
+        mut visit: impl FnMut(usize, usize, &[DrawOp]),
+    ) -> Vec<ResolvedScene> {
+        // `${var}` text is drawn with the deck's `vars` substituted.
+        let source = &*source.with_vars_applied();
         // A group with an explicit range overrides its members' frame ranges;
         // compute that mapping once and reuse it for every frame.
         let overrides = source.member_overrides();
+        // Likewise every instance's component expansion.
+        let expansions = source.instance_expansions();
         // The animation-span table is the single source of truth for timing;
         // build it once and thread it into every coordinate evaluation.
         let anims = AnimSpans::of(source);
+        let sections = source.section_starts();
         (0..source.frame_count)
-            .map(|frame| Self::resolve_frame(source, frame, &overrides, &anims))
+            .map(|frame| Self::resolve_frame(source, frame, &overrides, &expansions, &anims, &sections, &mut visit))
             .collect()
     }
 
@@ -33,26 +90,53 @@ impl Engine {
         source: &SourcePresentation,
         frame: usize,
         overrides: &[Option<FrameRange>],
+        expansions: &[Option<Vec<SceneObject>>],
         anims: &AnimSpans,
+        sections: &[(usize, String)],
+        visit: &mut impl FnMut(usize, usize, &[DrawOp]),
     ) -> ResolvedScene {
         let mut ops = Vec::new();
-        let ctx = ResolveCtx { frame, canvas_width: source.width, anims };
+        // The object each op was drawn by, for stacking.
+        let mut owners = Vec::new();
+        let ctx = ResolveCtx {
+            frame,
+            canvas_width: source.width,
+            canvas_height: source.height,
+            frame_count: source.frame_count,
+            anims,
+            sections,
+        };
 
         for (i, obj) in source.objects.iter().enumerate() {
-            match overrides.get(i).and_then(|o| o.as_ref()) {
-                // Member of an explicit-range group: render on the group's range
-                // (a clone carries the substituted range through the object's own
-                // self-gating) instead of the member's own range.
-                Some(range) => {
-                    if range.contains(frame) {
-                        let mut member = obj.clone();
-                        member.set_frame_range(range.clone());
-                        member.resolve(&ctx, &mut ops);
-                    }
+            if source.is_hidden(obj) {
+                continue;
+            }
+            let before = ops.len();
+            let range = overrides.get(i).and_then(|o| o.as_ref());
+            match (obj, expansions.get(i).and_then(|e| e.as_deref())) {
+                // An instance draws its component's objects in its place, on
+                // an explicit group's range when it is a member of one.
+                (SceneObject::Instance(inst), Some(expansion)) => {
+                    inst.resolve_expansion(expansion, range.unwrap_or(&inst.frames), &ctx, &mut ops)
                 }
-                None => obj.resolve(&ctx, &mut ops),
+                _ => match range {
+                    // Member of an explicit-range group: render on the group's range
+                    // (a clone carries the substituted range through the object's own
+                    // self-gating) instead of the member's own range.
+                    Some(range) => {
+                        if range.contains(frame) {
+                            let mut member = obj.clone();
+                            member.set_frame_range(range.clone());
+                            member.resolve(&ctx, &mut ops);
+                        }
+                    }
+                    None => obj.resolve(&ctx, &mut ops),
+                },
             }
+            visit(frame, i, &ops[before..]);
+            owners.resize(ops.len(), i);
         }
+        Self::stack(&mut ops, &mut owners, source.auto_z);
 
         ResolvedScene {
             width: source.width,
@@ -60,4 +144,25 @@ impl Engine {
             ops,
         }
     }
+
+    /// Put one frame's `ops` (each drawn by object `owners[k]`) in paint order:
+    /// by `(z_order, object index)`, so equal-z objects always stack in author
+    /// order however the deck was edited. With `auto_z` the key is
+    /// `(object index, z_order)` instead, and each op's `z_order` is rewritten
+    /// to its rank so the renderer's z sort keeps that order. `owners` is
+    /// permuted along with `ops`.
+    pub(crate) fn stack(ops: &mut Vec<DrawOp>, owners: &mut Vec<usize>, auto_z: bool) {
+        let mut order: Vec<usize> = (0..ops.len()).collect();
+        if auto_z {
+            order.sort_by_key(|&k| (owners[k], ops[k].z_order));
+            for (rank, &k) in order.iter().enumerate() {
+                ops[k].z_order = rank as i32;
+            }
+        } else {
+            order.sort_by_key(|&k| (ops[k].z_order, owners[k]));
+        }
+        let mut taken: Vec<Option<DrawOp>> = ops.drain(..).map(Some).collect();
+        ops.extend(order.iter().filter_map(|&k| taken[k].take()));
+        *owners = order.iter().map(|&k| owners[k]).collect();
+    }
 }
@@ -5,7 +5,7 @@ use crate::types::{AnimationRegion, DrawOp};
 use super::super::source::{AnimId, FrameRange};
 use super::{Resolve, ResolveCtx};
 
-fn default_delay_ms() -> u64 {
+pub(crate) fn default_delay_ms() -> u64 {
     500
 }
 
@@ -53,6 +53,8 @@ pub struct Animation {
     /// records the setting so the animate menu can recover it. Ignored at runtime.
     #[serde(default, skip_serializing_if = "is_zero")]
     pub gap_frames: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
 }
 
 fn is_zero(n: &usize) -> bool {
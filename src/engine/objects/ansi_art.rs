@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+use crate::import::vt::Screen;
+use crate::types::{Cell, DrawOp};
+
+use super::super::source::{FrameRange, Position};
+use super::motion::{Entrance, Exit};
+use super::{Resolve, ResolveCtx};
+
+pub(crate) fn default_ansi_width() -> u16 {
+    80
+}
+
+/// ANSI art: text with SGR escape sequences stored inline (imported from an
+/// `.ans` file) and replayed on a terminal `width` columns wide — autowrap
+/// included, as 80-column art expects — so each cell keeps its own colours and
+/// attributes. Unstyled blanks are transparent, as in [`Art`](super::Art).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnsiArt {
+    pub position: Position,
+    /// The raw art, escape sequences included. A bare `\n` starts a new line.
+    pub ansi: String,
+    /// Name of the file it came from; display only.
+    #[serde(default)]
+    pub name: String,
+    /// Columns the art wraps at.
+    #[serde(default = "default_ansi_width")]
+    pub width: u16,
+    pub frames: FrameRange,
+    #[serde(default)]
+    pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
+}
+
+/// Whether `cell` paints anything: a glyph, or a blank with a background.
+fn is_visible(cell: &Cell) -> bool {
+    !cell.is_continuation() && (cell.ch != ' ' || cell.style.bg.is_some())
+}
+
+impl AnsiArt {
+    /// The art replayed into styled cells, cropped to the rows it reaches.
+    pub fn cells(&self) -> Vec<Vec<Cell>> {
+        let width = self.width.max(1) as usize;
+        let text = self.ansi.replace("\r\n", "\n").replace('\n', "\r\n");
+        // Enough rows that nothing scrolls off: every line, plus every wrap.
+        let rows = text.matches('\n').count() + text.chars().count() / width + 1;
+        let mut screen = Screen::new(width, rows);
+        screen.feed(&text);
+        let mut grid = screen.grid().to_vec();
+        let used = grid.iter().rposition(|row| row.iter().any(is_visible)).map_or(0, |r| r + 1);
+        grid.truncate(used);
+        grid
+    }
+
+    /// `(columns, rows)` the art covers.
+    pub fn size(&self) -> (usize, usize) {
+        let cells = self.cells();
+        let cols = cells.iter().filter_map(|row| row.iter().rposition(is_visible)).max().map_or(0, |c| c + 1);
+        (cols, cells.len())
+    }
+}
+
+impl Resolve for AnsiArt {
+    fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+        let frame = ctx.frame;
+        if !self.frames.contains(frame) {
+            return;
+        }
+        let base_x = self.position.x.evaluate(frame, ctx.anims);
+        let base_y = self.position.y.evaluate(frame, ctx.anims);
+        for (row, line) in self.cells().into_iter().enumerate() {
+            for (col, cell) in line.into_iter().enumerate() {
+                if !is_visible(&cell) {
+                    continue; // transparent
+                }
+                ops.push(DrawOp {
+                    x: base_x + col as u16,
+                    y: base_y + row as u16,
+                    ch: cell.ch,
+                    style: (*cell.style).clone(),
+                    z_order: self.z_order,
+                });
+            }
+        }
+    }
+}
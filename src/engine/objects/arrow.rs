@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use crate::types::{DrawOp, Style};
 
 use super::super::source::{Coordinate, FrameRange, deserialize_coord_compat};
+use super::motion::{Entrance, Exit};
 use super::{Resolve, ResolveCtx};
 
 fn default_true() -> bool {
@@ -77,6 +78,12 @@ pub struct Arrow {
     pub frames: FrameRange,
     #[serde(default)]
     pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
 }
 
 // ---------------------------------------------------------------------------
@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::types::{DrawOp, Style};
+use crate::types::{char_columns, DrawOp, Style};
 
 use super::super::source::{FrameRange, Position};
+use super::motion::{Entrance, Exit};
 use super::{Resolve, ResolveCtx};
 
 /// A pre-made ASCII-art object. The art text is stored inline (copied from the
@@ -24,6 +25,12 @@ pub struct Art {
     pub frames: FrameRange,
     #[serde(default)]
     pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
 }
 
 impl Resolve for Art {
@@ -37,7 +44,7 @@ impl Resolve for Art {
         let has_bg = self.style.bg.is_some();
 
         for (row, line) in self.art.split('\n').enumerate() {
-            for (col, ch) in line.chars().enumerate() {
+            for (col, ch) in char_columns(line) {
                 if ch == ' ' && !has_bg {
                     continue; // transparent
                 }
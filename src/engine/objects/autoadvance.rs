@@ -33,6 +33,8 @@ pub struct AutoAdvance {
     /// Delay before advancing to the next frame, in milliseconds.
     #[serde(default = "default_delay_ms")]
     pub delay_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
 }
 
 impl AutoAdvance {
@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{Color, DrawOp, NamedColor, Style};
+
+use super::super::source::{FrameRange, Position};
+use super::{Resolve, ResolveCtx};
+
+pub(crate) fn default_effect_z() -> i32 {
+    -2000
+}
+
+pub(crate) fn default_effect_density() -> f64 {
+    0.1
+}
+
+/// What a [`BackgroundEffect`] draws.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Effect {
+    /// Scattered stars that twinkle from frame to frame.
+    #[default]
+    Starfield,
+    /// Columns of glyphs raining down, each with a bright head and a fading trail.
+    Matrix,
+    /// Faint specks that shift every frame.
+    Noise,
+}
+
+impl Effect {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Effect::Starfield => "starfield",
+            Effect::Matrix => "matrix",
+            Effect::Noise => "noise",
+        }
+    }
+
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        match s.trim() {
+            "starfield" => Some(Effect::Starfield),
+            "matrix" => Some(Effect::Matrix),
+            "noise" => Some(Effect::Noise),
+            _ => None,
+        }
+    }
+
+    /// The colour drawn in when the style sets no `fg`.
+    fn default_fg(&self) -> Option<Color> {
+        match self {
+            Effect::Starfield => Some(Color::Named(NamedColor::White)),
+            Effect::Matrix => Some(Color::Named(NamedColor::Green)),
+            Effect::Noise => None,
+        }
+    }
+}
+
+/// Glyphs a star cycles through as it twinkles, faintest first.
+const STAR_GLYPHS: [char; 4] = ['.', '·', '+', '*'];
+
+/// Glyphs the matrix rain is drawn from.
+const RAIN_GLYPHS: &[char] = &[
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'E', 'K', 'Z', ':', '=', '+', '*', '<', '>', '|',
+];
+
+/// Glyphs of the noise effect.
+const NOISE_GLYPHS: [char; 3] = ['.', '·', ':'];
+
+/// A moving backdrop — a starfield, matrix rain or faint noise — filling a
+/// `width` × `height` area (0: to the canvas edge) across its frames.
+///
+/// Everything is derived from `seed` and the frame, so the same deck always
+/// draws the same effect; change `seed` for a different one. `density` is the
+/// share of cells lit (of columns raining, for `matrix`). It sits below
+/// everything else (`z_order` -2000 unless set), draws no blanks, and is dim
+/// except for the brightest stars and the rain's heads. It has no
+/// `enter`/`exit`: like a watermark, it stays put while slides come and go.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackgroundEffect {
+    #[serde(default)]
+    pub effect: Effect,
+    #[serde(default)]
+    pub seed: u64,
+    #[serde(default = "default_effect_density")]
+    pub density: f64,
+    pub position: Position,
+    /// Columns covered; 0 runs to the canvas' right edge.
+    #[serde(default)]
+    pub width: u16,
+    /// Rows covered; 0 runs to the canvas' bottom edge.
+    #[serde(default)]
+    pub height: u16,
+    #[serde(default)]
+    pub style: Style,
+    pub frames: FrameRange,
+    #[serde(default = "default_effect_z")]
+    pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+}
+
+/// A well-mixed hash of `seed` and three coordinates (splitmix64's finaliser).
+fn hash(seed: u64, a: u64, b: u64, c: u64) -> u64 {
+    let mut z = seed
+        .wrapping_add(a.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+        .wrapping_add(b.wrapping_mul(0xC2B2_AE3D_27D4_EB4F))
+        .wrapping_add(c.wrapping_mul(0x1656_67B1_9E37_79F9));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// `h` as a fraction in `[0, 1)`.
+fn unit(h: u64) -> f64 {
+    (h >> 11) as f64 / (1u64 << 53) as f64
+}
+
+impl BackgroundEffect {
+    /// The `(columns, rows)` covered on a `canvas_width` × `canvas_height`
+    /// canvas, with the top-left at `(x, y)`.
+    pub fn area(&self, x: u16, y: u16, canvas_width: u16, canvas_height: u16) -> (u16, u16) {
+        let w = if self.width == 0 { canvas_width.saturating_sub(x) } else { self.width };
+        let h = if self.height == 0 { canvas_height.saturating_sub(y) } else { self.height };
+        (w, h)
+    }
+
+    /// The cells lit `t` frames into the range over a `w` × `h` area, as
+    /// `(column, row, glyph, bright)`.
+    pub fn cells(&self, t: usize, w: u16, h: u16) -> Vec<(u16, u16, char, bool)> {
+        let (seed, t) = (self.seed, t as u64);
+        let density = self.density.clamp(0.0, 1.0);
+        let mut out = Vec::new();
+        match self.effect {
+            Effect::Starfield => {
+                for row in 0..h {
+                    for col in 0..w {
+                        let star = hash(seed, u64::from(col), u64::from(row), 0);
+                        if unit(star) >= density {
+                            continue;
+                        }
+                        // Each star steps through the glyphs from its own phase.
+                        let glyph = STAR_GLYPHS[((star >> 8).wrapping_add(t) % STAR_GLYPHS.len() as u64) as usize];
+                        out.push((col, row, glyph, glyph == '*'));
+                    }
+                }
+            }
+            Effect::Matrix => {
+                let h64 = u64::from(h.max(1));
+                for col in 0..w {
+                    let drop = hash(seed, u64::from(col), 0, 1);
+                    if unit(drop) >= density {
+                        continue;
+                    }
+                    // A drop falls one or two rows a frame, trailing 3 to h/2
+                    // cells, and wraps round once its trail has left the area.
+                    let speed = 1 + (drop >> 8) % 2;
+                    let trail = 3 + (drop >> 16) % (h64 / 2).max(1);
+                    let head = ((drop >> 24) + t * speed) % (h64 + trail);
+                    for k in 0..trail {
+                        let Some(row) = head.checked_sub(k).filter(|&r| r < h64) else {
+                            continue;
+                        };
+                        let g = hash(seed, u64::from(col), row, t + 2);
+                        out.push((col, row as u16, RAIN_GLYPHS[(g % RAIN_GLYPHS.len() as u64) as usize], k == 0));
+                    }
+                }
+            }
+            Effect::Noise => {
+                for row in 0..h {
+                    for col in 0..w {
+                        let speck = hash(seed, u64::from(col), u64::from(row), t + 1);
+                        if unit(speck) < density {
+                            out.push((col, row, NOISE_GLYPHS[((speck >> 8) % 3) as usize], false));
+                        }
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+impl Resolve for BackgroundEffect {
+    fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+        let frame = ctx.frame;
+        if !self.frames.contains(frame) {
+            return;
+        }
+        let x = self.position.x.evaluate(frame, ctx.anims);
+        let y = self.position.y.evaluate(frame, ctx.anims);
+        let (w, h) = self.area(x, y, ctx.canvas_width, ctx.canvas_height);
+        let fg = self.style.fg.clone().or_else(|| self.effect.default_fg());
+        let dim = Style { fg: fg.clone(), dim: true, ..self.style.clone() };
+        let bright = Style { fg, bold: true, ..self.style.clone() };
+        for (col, row, ch, lit) in self.cells(frame - self.frames.start, w, h) {
+            let style = if lit { &bright } else { &dim };
+            ops.push(DrawOp { x: x + col, y: y + row, ch, style: style.clone(), z_order: self.z_order });
+        }
+    }
+}
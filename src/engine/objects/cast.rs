@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+
+use crate::import::Recording;
+use crate::types::{char_width, Cell, DrawOp};
+
+use super::super::source::{FrameRange, Position};
+use super::motion::{Entrance, Exit};
+use super::{Resolve, ResolveCtx};
+
+/// A snippet of an asciinema v2 recording, stored inline and played across
+/// the object's frames: frame by frame the recording's screen moves from
+/// `start` (on the first frame of the range) to `end` (on the last), the way
+/// a [`Morph`](super::Morph) moves from `from` to `to`. A single-frame range
+/// shows the screen at `end`.
+///
+/// The screen is replayed at compile time, so a demo plays the same on every
+/// run and in every export. It is opaque — blanks paint too — and sized as
+/// recorded unless `width`/`height` crop it from the top-left.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cast {
+    pub position: Position,
+    /// The `.cast` file's text: a header line, then one event per line.
+    pub cast: String,
+    /// Name of the file it came from; display only.
+    #[serde(default)]
+    pub name: String,
+    /// Recording time, in seconds, shown on the first frame.
+    #[serde(default)]
+    pub start: f64,
+    /// Recording time shown on the last frame; the end of the recording when
+    /// unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<f64>,
+    /// Columns of the recording shown; all of them when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u16>,
+    /// Rows of the recording shown; all of them when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u16>,
+    pub frames: FrameRange,
+    #[serde(default)]
+    pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
+}
+
+impl Cast {
+    /// The recording time shown on `frame`, given the snippet's `end`.
+    fn time_at(&self, frame: usize, end: f64) -> f64 {
+        let span = self.frames.end.saturating_sub(self.frames.start);
+        if span <= 1 {
+            return end;
+        }
+        let local = frame.saturating_sub(self.frames.start).min(span - 1);
+        self.start + (end - self.start) * local as f64 / (span - 1) as f64
+    }
+
+    /// The cropped screen shown on `frame`, or `None` when the cast doesn't
+    /// parse (`bs validate` reports why).
+    pub fn screen_at(&self, frame: usize) -> Option<Vec<Vec<Cell>>> {
+        let recording = Recording::parse(&self.cast).ok()?;
+        let t = self.time_at(frame, self.end.unwrap_or_else(|| recording.duration()));
+        let mut grid = recording.screens_at(&[t]).pop()?;
+        let (w, h) = self.size_of(&recording);
+        grid.truncate(h);
+        for row in &mut grid {
+            row.truncate(w);
+            // A wide glyph whose right half was cropped away goes blank.
+            if let Some(last) = row.last_mut().filter(|c| char_width(c.ch) == 2) {
+                last.ch = ' ';
+            }
+        }
+        Some(grid)
+    }
+
+    /// `(columns, rows)` the object covers; nothing when the cast doesn't
+    /// parse.
+    pub fn size(&self) -> (usize, usize) {
+        Recording::parse(&self.cast).map_or((0, 0), |r| self.size_of(&r))
+    }
+
+    fn size_of(&self, recording: &Recording) -> (usize, usize) {
+        let w = self.width.map_or(recording.width, |w| w.min(recording.width));
+        let h = self.height.map_or(recording.height, |h| h.min(recording.height));
+        (w as usize, h as usize)
+    }
+}
+
+impl Resolve for Cast {
+    fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+        let frame = ctx.frame;
+        if !self.frames.contains(frame) {
+            return;
+        }
+        let Some(screen) = self.screen_at(frame) else {
+            return;
+        };
+        let base_x = self.position.x.evaluate(frame, ctx.anims);
+        let base_y = self.position.y.evaluate(frame, ctx.anims);
+        for (row, line) in screen.into_iter().enumerate() {
+            for (col, cell) in line.into_iter().enumerate() {
+                if cell.is_continuation() {
+                    continue; // painted by the wide glyph to its left
+                }
+                ops.push(DrawOp {
+                    x: base_x + col as u16,
+                    y: base_y + row as u16,
+                    ch: cell.ch,
+                    style: (*cell.style).clone(),
+                    z_order: self.z_order,
+                });
+            }
+        }
+    }
+}
@@ -3,13 +3,14 @@ use serde::{Deserialize, Serialize};
 use crate::types::{DrawOp, Style};
 
 use super::super::source::{FrameRange, Position};
+use super::motion::{Entrance, Exit};
 use super::{Resolve, ResolveCtx};
 
-fn default_diameter() -> u16 {
+pub(crate) fn default_diameter() -> u16 {
     10
 }
 
-fn default_fill_char() -> char {
+pub(crate) fn default_fill_char() -> char {
     '@'
 }
 
@@ -42,6 +43,12 @@ pub struct Circle {
     pub frames: FrameRange,
     #[serde(default)]
     pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
 }
 
 impl Circle {
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{ClockRegion, DrawOp, Style};
+
+use super::super::source::{AnimSpans, FrameRange, Position};
+use super::{Resolve, ResolveCtx};
+
+/// The time of day, as the player's clock reads it — for workshop agendas
+/// and "back at …" slides.
+///
+/// Like a [`Command`](super::Command), it can't be baked into the static
+/// frames: they carry a `--:--` placeholder (what the editor preview and
+/// every export show), and the object emits a [`ClockRegion`] sidecar (see
+/// [`Clock::region`]) the player paints the current local time from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Clock {
+    pub position: Position,
+    /// Show `HH:MM:SS` rather than `HH:MM`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub seconds: bool,
+    #[serde(default)]
+    pub style: Style,
+    pub frames: FrameRange,
+    #[serde(default)]
+    pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+}
+
+impl Clock {
+    /// Resolve this clock into its runtime sidecar spec, placed where it sits
+    /// on `frame`.
+    pub fn region(&self, frame: usize, anims: &AnimSpans) -> ClockRegion {
+        ClockRegion {
+            start_frame: self.frames.start,
+            end_frame: self.frames.end,
+            x: self.position.x.evaluate(frame, anims),
+            y: self.position.y.evaluate(frame, anims),
+            seconds: self.seconds,
+            style: self.style.clone(),
+        }
+    }
+}
+
+impl Resolve for Clock {
+    fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+        let frame = ctx.frame;
+        if !self.frames.contains(frame) {
+            return;
+        }
+        let x = self.position.x.evaluate(frame, ctx.anims);
+        let y = self.position.y.evaluate(frame, ctx.anims);
+        for (i, ch) in ClockRegion::placeholder(self.seconds).chars().enumerate() {
+            ops.push(DrawOp { x: x + i as u16, y, ch, style: self.style.clone(), z_order: self.z_order });
+        }
+    }
+}
@@ -42,6 +42,8 @@ pub struct Command {
     pub frames: FrameRange,
     #[serde(default)]
     pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
 }
 
 impl Command {
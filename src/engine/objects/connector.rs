@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{DrawOp, Style};
+
+use super::super::source::{FrameRange, Position};
+use super::motion::{Entrance, Exit};
+use super::rect::BorderStyle;
+use super::{Resolve, ResolveCtx};
+
+// Directions a cell's line leaves it in.
+const UP: u8 = 1;
+const DOWN: u8 = 2;
+const LEFT: u8 = 4;
+const RIGHT: u8 = 8;
+
+/// A line through a list of points, drawn in box-drawing characters with the
+/// right corner, tee or cross wherever segments meet — wiring for a diagram,
+/// where an [`Arrow`](super::Arrow)'s routing and head aren't wanted.
+///
+/// `points` are offsets from `position`. Consecutive points are joined
+/// straight; two that differ in both column and row are joined across first,
+/// then down or up, with an elbow. Where the line meets itself — or a branch
+/// doubles back along it — the cell gets the junction for every direction the
+/// line leaves it in, so a `T` or `+` of wires needs just one connector.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Connector {
+    pub position: Position,
+    /// `[column, row]` offsets from `position`, in order.
+    pub points: Vec<[u16; 2]>,
+    /// The box-drawing set, as for a rect's `border`.
+    #[serde(default)]
+    pub line: BorderStyle,
+    #[serde(default)]
+    pub style: Style,
+    pub frames: FrameRange,
+    #[serde(default)]
+    pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
+}
+
+/// The character for a cell the line leaves in the `dirs` directions.
+fn junction(line: BorderStyle, dirs: u8) -> char {
+    let b = line.chars();
+    // Tees (├ ┤ ┬ ┴) and the cross, in the set's own weight.
+    let [tee_right, tee_left, tee_down, tee_up, cross] = match line {
+        BorderStyle::Double => ['╠', '╣', '╦', '╩', '╬'],
+        BorderStyle::Heavy => ['┣', '┫', '┳', '┻', '╋'],
+        BorderStyle::Ascii => ['+'; 5],
+        BorderStyle::Single | BorderStyle::Rounded | BorderStyle::Dashed => ['├', '┤', '┬', '┴', '┼'],
+    };
+    match dirs {
+        d if d & (UP | DOWN) == 0 => b.horizontal,
+        d if d & (LEFT | RIGHT) == 0 => b.vertical,
+        d if d == DOWN | RIGHT => b.top_left,
+        d if d == DOWN | LEFT => b.top_right,
+        d if d == UP | RIGHT => b.bottom_left,
+        d if d == UP | LEFT => b.bottom_right,
+        d if d == UP | DOWN | RIGHT => tee_right,
+        d if d == UP | DOWN | LEFT => tee_left,
+        d if d == LEFT | RIGHT | DOWN => tee_down,
+        d if d == LEFT | RIGHT | UP => tee_up,
+        _ => cross,
+    }
+}
+
+impl Connector {
+    /// The corners the line turns at: the points, plus an elbow between any
+    /// two that differ in both column and row.
+    pub fn path(&self) -> Vec<[u16; 2]> {
+        let mut path: Vec<[u16; 2]> = Vec::new();
+        for &[x, y] in &self.points {
+            if let Some(&[px, py]) = path.last() {
+                if px != x && py != y {
+                    path.push([x, py]);
+                }
+            }
+            path.push([x, y]);
+        }
+        path
+    }
+
+    /// Every cell on the line, as `(column, row, directions)` offsets from
+    /// `position`, in first-reached order.
+    pub fn cells(&self) -> Vec<(u16, u16, u8)> {
+        let mut cells: Vec<(u16, u16, u8)> = Vec::new();
+        let mut link = |x: u16, y: u16, dir: u8| match cells.iter_mut().find(|c| c.0 == x && c.1 == y) {
+            Some(cell) => cell.2 |= dir,
+            None => cells.push((x, y, dir)),
+        };
+        let path = self.path();
+        if let [only] = path.as_slice() {
+            link(only[0], only[1], 0);
+        }
+        for pair in path.windows(2) {
+            let ([x0, y0], [x1, y1]) = (pair[0], pair[1]);
+            if x0 == x1 && y0 == y1 {
+                continue;
+            }
+            // Each step links the cell it leaves to the one it enters.
+            let (mut x, mut y) = (x0, y0);
+            while (x, y) != (x1, y1) {
+                let (nx, ny, out, back) = if x < x1 {
+                    (x + 1, y, RIGHT, LEFT)
+                } else if x > x1 {
+                    (x - 1, y, LEFT, RIGHT)
+                } else if y < y1 {
+                    (x, y + 1, DOWN, UP)
+                } else {
+                    (x, y - 1, UP, DOWN)
+                };
+                link(x, y, out);
+                link(nx, ny, back);
+                (x, y) = (nx, ny);
+            }
+        }
+        cells
+    }
+
+    /// `(columns, rows)` from `position` to the line's far corner.
+    pub fn size(&self) -> (u16, u16) {
+        let w = self.points.iter().map(|p| p[0] + 1).max().unwrap_or(0);
+        let h = self.points.iter().map(|p| p[1] + 1).max().unwrap_or(0);
+        (w, h)
+    }
+}
+
+impl Resolve for Connector {
+    fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+        let frame = ctx.frame;
+        if !self.frames.contains(frame) {
+            return;
+        }
+        let x = self.position.x.evaluate(frame, ctx.anims);
+        let y = self.position.y.evaluate(frame, ctx.anims);
+        for (col, row, dirs) in self.cells() {
+            let ch = junction(self.line, dirs);
+            ops.push(DrawOp { x: x + col, y: y + row, ch, style: self.style.clone(), z_order: self.z_order });
+        }
+    }
+}
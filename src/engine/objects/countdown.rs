@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{AutoAdvanceRegion, DrawOp, Style};
+
+use super::super::source::{FrameRange, Position};
+use super::header::{default_header_char, Header};
+use super::motion::{Entrance, Exit};
+use super::{Resolve, ResolveCtx};
+
+pub(crate) fn default_step_secs() -> u64 {
+    1
+}
+
+/// An `mm:ss` countdown for break slides and exercise timers: `from_secs` on
+/// the first frame of the range, `step_secs` less on each frame after, held at
+/// `00:00` once it runs out.
+///
+/// Each frame's value is baked into the static frames. With `auto_play` the
+/// countdown also emits an [`AutoAdvanceRegion`] (see [`Countdown::region`])
+/// so the player steps through its frames every `step_secs` on its own — the
+/// counter then keeps wall-clock time — stopping on its last frame. `big`
+/// draws the digits in the `header` block font.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Countdown {
+    pub position: Position,
+    /// Seconds shown on the first frame.
+    pub from_secs: u64,
+    /// Seconds taken off per frame.
+    #[serde(default = "default_step_secs")]
+    pub step_secs: u64,
+    /// Advance through the countdown's frames every `step_secs` at play time.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub auto_play: bool,
+    /// Draw the counter in large block letters, like a `header`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub big: bool,
+    #[serde(default)]
+    pub style: Style,
+    pub frames: FrameRange,
+    #[serde(default)]
+    pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
+}
+
+impl Countdown {
+    /// Seconds left on `frame`.
+    pub fn remaining(&self, frame: usize) -> u64 {
+        let elapsed = frame.saturating_sub(self.frames.start) as u64;
+        self.from_secs.saturating_sub(elapsed.saturating_mul(self.step_secs))
+    }
+
+    /// The counter as shown on `frame`: `mm:ss`, minutes running past 59.
+    pub fn text(&self, frame: usize) -> String {
+        let secs = self.remaining(frame);
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+
+    /// The auto-advance sidecar for an `auto_play` countdown: every frame but
+    /// the last moves on after `step_secs`. `None` without `auto_play`, or
+    /// with a single frame.
+    pub fn region(&self) -> Option<AutoAdvanceRegion> {
+        let end_frame = self.frames.end.checked_sub(1)?;
+        (self.auto_play && end_frame > self.frames.start).then(|| AutoAdvanceRegion {
+            start_frame: self.frames.start,
+            end_frame,
+            delay_ms: self.step_secs.saturating_mul(1000),
+        })
+    }
+}
+
+impl Resolve for Countdown {
+    fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+        let frame = ctx.frame;
+        if !self.frames.contains(frame) {
+            return;
+        }
+        if self.big {
+            let header = Header {
+                text: self.text(frame),
+                position: self.position.clone(),
+                style: self.style.clone(),
+                frames: self.frames.clone(),
+                z_order: self.z_order,
+                layer: None,
+                enter: None,
+                exit: None,
+                ch: default_header_char(),
+                gradient: None,
+            };
+            header.resolve(ctx, ops);
+            return;
+        }
+        let x = self.position.x.evaluate(frame, ctx.anims);
+        let y = self.position.y.evaluate(frame, ctx.anims);
+        for (i, ch) in self.text(frame).chars().enumerate() {
+            ops.push(DrawOp { x: x + i as u16, y, ch, style: self.style.clone(), z_order: self.z_order });
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{char_width, Color, DrawOp, NamedColor, Style};
+
+use super::super::source::{FrameRange, Position};
+use super::motion::{Entrance, Exit};
+use super::{Resolve, ResolveCtx};
+
+pub(crate) fn default_diff_context() -> usize {
+    3
+}
+
+/// What a line of a [`Diff`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLineKind {
+    /// Unchanged, shown for context.
+    Context,
+    Added,
+    Removed,
+    /// A hunk's `@@ -a,b +c,d @@` header.
+    Hunk,
+}
+
+/// One displayed line of a [`Diff`], without its gutter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub text: String,
+}
+
+/// A code change in unified-diff form: additions in green after a `+`
+/// gutter, deletions in red after a `-`, unchanged lines after a blank.
+///
+/// The change is given either as a unified `diff` (as `git diff` prints it;
+/// the `---`/`+++` file header is dropped) or as `before` and `after` texts,
+/// which are diffed line by line into hunks with `context` unchanged lines
+/// around each change. With `reveal`, the hunks show one more per frame from
+/// the start of `frames`; a hidden hunk keeps its rows, like a `label`'s
+/// revealed list items, so the layout doesn't jump.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Diff {
+    pub position: Position,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub before: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub after: String,
+    /// A unified diff to show instead of diffing `before` and `after`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub diff: String,
+    /// Unchanged lines kept around each change when diffing `before`/`after`.
+    #[serde(default = "default_diff_context")]
+    pub context: usize,
+    /// Show the hunks one per frame.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub reveal: bool,
+    #[serde(default)]
+    pub style: Style,
+    pub frames: FrameRange,
+    #[serde(default)]
+    pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
+}
+
+fn line(kind: DiffLineKind, text: &str) -> DiffLine {
+    DiffLine { kind, text: text.to_string() }
+}
+
+/// Split a unified diff into hunks, each led by its `@@` line. Lines before
+/// the first `@@` other than the file header form a hunk of their own.
+fn parse_unified(diff: &str) -> Vec<Vec<DiffLine>> {
+    let mut hunks: Vec<Vec<DiffLine>> = Vec::new();
+    for l in diff.lines() {
+        if l.starts_with("@@") {
+            hunks.push(vec![line(DiffLineKind::Hunk, l)]);
+            continue;
+        }
+        let header = ["diff ", "index ", "--- ", "+++ "].iter().any(|p| l.starts_with(p));
+        if (hunks.is_empty() && header) || l.starts_with('\\') {
+            continue; // file header, or "\ No newline at end of file"
+        }
+        let parsed = match l.chars().next() {
+            Some('+') => line(DiffLineKind::Added, &l[1..]),
+            Some('-') => line(DiffLineKind::Removed, &l[1..]),
+            Some(' ') => line(DiffLineKind::Context, &l[1..]),
+            _ => line(DiffLineKind::Context, l),
+        };
+        match hunks.last_mut() {
+            Some(hunk) => hunk.push(parsed),
+            None => hunks.push(vec![parsed]),
+        }
+    }
+    hunks
+}
+
+/// The line-by-line edit script turning `a` into `b` (a longest common
+/// subsequence walk), with each line's 1-based numbers in `a` and `b`.
+fn edit_script<'a>(a: &[&'a str], b: &[&'a str]) -> Vec<(DiffLineKind, &'a str, usize, usize)> {
+    let (n, m) = (a.len(), b.len());
+    // lcs[i][j]: length of the longest common subsequence of a[i..] and b[j..].
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if a[i] == b[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut script = Vec::new();
+    while i < n || j < m {
+        if i < n && j < m && a[i] == b[j] {
+            script.push((DiffLineKind::Context, a[i], i + 1, j + 1));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            script.push((DiffLineKind::Removed, a[i], i + 1, j + 1));
+            i += 1;
+        } else {
+            script.push((DiffLineKind::Added, b[j], i + 1, j + 1));
+            j += 1;
+        }
+    }
+    script
+}
+
+/// Diff `before` against `after` into hunks keeping `context` unchanged lines
+/// around each change; changes closer than twice that share a hunk.
+fn diff_texts(before: &str, after: &str, context: usize) -> Vec<Vec<DiffLine>> {
+    let (a, b): (Vec<&str>, Vec<&str>) = (before.lines().collect(), after.lines().collect());
+    let script = edit_script(&a, &b);
+    let changed: Vec<usize> = (0..script.len()).filter(|&k| script[k].0 != DiffLineKind::Context).collect();
+    let mut spans: Vec<(usize, usize)> = Vec::new();
+    for &k in &changed {
+        let (lo, hi) = (k.saturating_sub(context), k.saturating_add(context).saturating_add(1).min(script.len()));
+        match spans.last_mut() {
+            Some(span) if lo <= span.1 => span.1 = hi,
+            _ => spans.push((lo, hi)),
+        }
+    }
+    spans
+        .into_iter()
+        .map(|(lo, hi)| {
+            let lines = &script[lo..hi];
+            let old_len = lines.iter().filter(|l| l.0 != DiffLineKind::Added).count();
+            let new_len = lines.iter().filter(|l| l.0 != DiffLineKind::Removed).count();
+            // An empty side's start is the line before it, as `diff -u` writes it.
+            let start = |first: usize, len: usize| if len == 0 { first - 1 } else { first };
+            let header = format!(
+                "@@ -{},{old_len} +{},{new_len} @@",
+                start(lines[0].2, old_len),
+                start(lines[0].3, new_len)
+            );
+            std::iter::once(line(DiffLineKind::Hunk, &header))
+                .chain(lines.iter().map(|&(kind, text, _, _)| line(kind, text)))
+                .collect()
+        })
+        .collect()
+}
+
+impl Diff {
+    /// The hunks shown, from `diff` when set, else from diffing `before`
+    /// against `after`.
+    pub fn hunks(&self) -> Vec<Vec<DiffLine>> {
+        if self.diff.is_empty() {
+            diff_texts(&self.before, &self.after, self.context)
+        } else {
+            parse_unified(&self.diff)
+        }
+    }
+
+    /// `(columns, rows)` the diff covers, gutters included.
+    pub fn size(&self) -> (u16, u16) {
+        let lines: Vec<DiffLine> = self.hunks().into_iter().flatten().collect();
+        let width = lines.iter().map(|l| 2 + l.text.chars().map(char_width).sum::<usize>()).max().unwrap_or(0);
+        (width as u16, lines.len() as u16)
+    }
+}
+
+impl Resolve for Diff {
+    fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+        let frame = ctx.frame;
+        if !self.frames.contains(frame) {
+            return;
+        }
+        let x = self.position.x.evaluate(frame, ctx.anims);
+        let y = self.position.y.evaluate(frame, ctx.anims);
+        let colored = |c: NamedColor| Style { fg: Some(Color::Named(c)), ..self.style.clone() };
+        let revealed = if self.reveal { frame - self.frames.start + 1 } else { usize::MAX };
+
+        let mut row = y;
+        for (h, hunk) in self.hunks().iter().enumerate() {
+            for l in hunk {
+                if h < revealed {
+                    let (gutter, style) = match l.kind {
+                        DiffLineKind::Context => ("  ", self.style.clone()),
+                        DiffLineKind::Added => ("+ ", colored(NamedColor::Green)),
+                        DiffLineKind::Removed => ("- ", colored(NamedColor::Red)),
+                        DiffLineKind::Hunk => ("", Style { dim: true, ..self.style.clone() }),
+                    };
+                    let mut col = x;
+                    for ch in gutter.chars().chain(l.text.chars()) {
+                        ops.push(DrawOp { x: col, y: row, ch, style: style.clone(), z_order: self.z_order });
+                        col += char_width(ch) as u16;
+                    }
+                }
+                row += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{char_width, DrawOp, Style};
+
+use super::super::source::{FrameRange, Position};
+use super::motion::{Entrance, Exit};
+use super::{Resolve, ResolveCtx};
+
+fn default_true() -> bool {
+    true
+}
+
+/// One path of a [`FileTree`]. Serialized as the bare path when it has no
+/// `style` and opens on the first frame.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "TreeEntryRepr", into = "TreeEntryRepr")]
+pub struct TreeEntry {
+    /// `/`-separated; a trailing `/` marks a folder with nothing listed in it.
+    pub path: String,
+    /// Style for the entry's name; the tree's own `style` when unset.
+    pub style: Option<Style>,
+    /// Frames after the start of the tree's range before a folder's contents
+    /// show; until then it is drawn collapsed.
+    pub open_at: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum TreeEntryRepr {
+    Path(String),
+    Full {
+        path: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        style: Option<Style>,
+        #[serde(default)]
+        open_at: usize,
+    },
+}
+
+impl From<TreeEntryRepr> for TreeEntry {
+    fn from(r: TreeEntryRepr) -> Self {
+        match r {
+            TreeEntryRepr::Path(path) => TreeEntry { path, style: None, open_at: 0 },
+            TreeEntryRepr::Full { path, style, open_at } => TreeEntry { path, style, open_at },
+        }
+    }
+}
+
+impl From<TreeEntry> for TreeEntryRepr {
+    fn from(e: TreeEntry) -> Self {
+        if e.style.is_none() && e.open_at == 0 {
+            TreeEntryRepr::Path(e.path)
+        } else {
+            TreeEntryRepr::Full { path: e.path, style: e.style, open_at: e.open_at }
+        }
+    }
+}
+
+/// A folder or file in the tree built from a [`FileTree`]'s entries.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TreeNode {
+    pub name: String,
+    pub folder: bool,
+    pub style: Option<Style>,
+    pub open_at: usize,
+    pub children: Vec<TreeNode>,
+}
+
+impl TreeNode {
+    /// The child called `name`, added (as a folder) if it isn't there yet.
+    fn child(&mut self, name: &str) -> &mut TreeNode {
+        let i = match self.children.iter().position(|c| c.name == name) {
+            Some(i) => i,
+            None => {
+                self.children.push(TreeNode { name: name.to_string(), folder: true, ..TreeNode::default() });
+                self.children.len() - 1
+            }
+        };
+        &mut self.children[i]
+    }
+}
+
+/// A directory listing drawn as a tree — the layout of a repo, the files a
+/// change touches — from a flat list of paths.
+///
+/// Folders a path passes through are added as needed, and everything keeps
+/// the order it is first listed in. Folders get a `▾` (open) or `▸`
+/// (collapsed) glyph and a trailing `/`, files a `·`, unless `glyphs` is off.
+/// A folder entry's `open_at` holds its contents back for that many frames,
+/// so a tree can unfold one level per click; the rows below move down as it
+/// opens.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileTree {
+    pub position: Position,
+    pub entries: Vec<TreeEntry>,
+    /// Mark folders and files with glyphs before their names.
+    #[serde(default = "default_true")]
+    pub glyphs: bool,
+    #[serde(default)]
+    pub style: Style,
+    pub frames: FrameRange,
+    #[serde(default)]
+    pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
+}
+
+impl FileTree {
+    /// The top-level nodes of the tree the entries describe.
+    pub fn roots(&self) -> Vec<TreeNode> {
+        let mut root = TreeNode::default();
+        for entry in &self.entries {
+            let parts: Vec<&str> = entry.path.split('/').filter(|p| !p.is_empty()).collect();
+            let Some((last, parents)) = parts.split_last() else {
+                continue;
+            };
+            let mut node = &mut root;
+            for part in parents {
+                node = node.child(part);
+            }
+            let is_new = !node.children.iter().any(|c| c.name == *last);
+            let leaf = node.child(last);
+            if is_new {
+                leaf.folder = entry.path.ends_with('/');
+            }
+            leaf.style = entry.style.clone().or(leaf.style.take());
+            leaf.open_at = entry.open_at;
+        }
+        // A path listed under an entry makes that entry a folder.
+        fn settle(node: &mut TreeNode) {
+            for c in &mut node.children {
+                settle(c);
+                c.folder |= !c.children.is_empty();
+            }
+        }
+        settle(&mut root);
+        root.children
+    }
+
+    /// The tree as drawn `t` frames into its range: one `(text, node)` line
+    /// per visible entry, the text carrying the branch lines and glyph.
+    pub fn lines(&self, t: usize) -> Vec<(String, TreeNode)> {
+        fn walk(nodes: &[TreeNode], prefix: &str, root: bool, t: usize, glyphs: bool, out: &mut Vec<(String, TreeNode)>) {
+            for (i, node) in nodes.iter().enumerate() {
+                let last = i + 1 == nodes.len();
+                let branch = match (root, last) {
+                    (true, _) => "",
+                    (false, false) => "├── ",
+                    (false, true) => "└── ",
+                };
+                let open = node.folder && node.open_at <= t;
+                let glyph = match (glyphs, node.folder, open) {
+                    (false, ..) => "",
+                    (true, false, _) => "· ",
+                    (true, true, true) => "▾ ",
+                    (true, true, false) => "▸ ",
+                };
+                let slash = if node.folder { "/" } else { "" };
+                out.push((format!("{prefix}{branch}{glyph}{}{slash}", node.name), node.clone()));
+                if open {
+                    let rest = match (root, last) {
+                        (true, _) => String::new(),
+                        (false, false) => format!("{prefix}│   "),
+                        (false, true) => format!("{prefix}    "),
+                    };
+                    walk(&node.children, &rest, false, t, glyphs, out);
+                }
+            }
+        }
+        let mut out = Vec::new();
+        walk(&self.roots(), "", true, t, self.glyphs, &mut out);
+        out
+    }
+}
+
+impl Resolve for FileTree {
+    fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+        let frame = ctx.frame;
+        if !self.frames.contains(frame) {
+            return;
+        }
+        let x = self.position.x.evaluate(frame, ctx.anims);
+        let y = self.position.y.evaluate(frame, ctx.anims);
+        for (row, (text, node)) in self.lines(frame - self.frames.start).into_iter().enumerate() {
+            // Branch lines take the tree's style; the glyph and name the entry's.
+            let name_at = text.chars().count() - node.name.chars().count() - usize::from(node.folder);
+            let glyph_at = if self.glyphs { name_at - 2 } else { name_at };
+            let name_style = node.style.clone().unwrap_or_else(|| self.style.clone());
+            let mut col = x;
+            for (i, ch) in text.chars().enumerate() {
+                let style = if i < glyph_at { &self.style } else { &name_style };
+                ops.push(DrawOp { x: col, y: y + row as u16, ch, style: style.clone(), z_order: self.z_order });
+                col += char_width(ch) as u16;
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{char_width, DrawOp, Style};
+
+use super::super::source::{FrameRange, Position};
+use super::label::TextAlign;
+use super::motion::{Entrance, Exit};
+use super::{Resolve, ResolveCtx};
+
+/// One line of text repeated on every frame of its range, with the page
+/// filled in: `{frame}` becomes the 1-based slide number, `{total}` the
+/// deck's frame count and `{section}` the name of the section the frame is in
+/// (empty before the first). One footer spanning the deck replaces a
+/// hand-numbered label per frame.
+///
+/// The text is aligned within a row `width` cells wide from its position —
+/// the rest of the canvas when `width` is 0 — so `"align": "right"` pins page
+/// numbers to the right edge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Footer {
+    /// The template, e.g. `"{section} · {frame}/{total}"`.
+    pub text: String,
+    pub position: Position,
+    /// Width of the row the text is aligned in; 0 runs to the canvas edge.
+    #[serde(default)]
+    pub width: u16,
+    #[serde(default)]
+    pub align: TextAlign,
+    #[serde(default)]
+    pub style: Style,
+    pub frames: FrameRange,
+    #[serde(default)]
+    pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
+}
+
+impl Footer {
+    /// The text as shown on `frame` of a `total`-frame deck, inside `section`.
+    pub fn text_at(&self, frame: usize, total: usize, section: Option<&str>) -> String {
+        self.text
+            .replace("{frame}", &(frame + 1).to_string())
+            .replace("{total}", &total.to_string())
+            .replace("{section}", section.unwrap_or(""))
+    }
+}
+
+impl Resolve for Footer {
+    fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+        let frame = ctx.frame;
+        if !self.frames.contains(frame) {
+            return;
+        }
+        let x = self.position.x.evaluate(frame, ctx.anims);
+        let y = self.position.y.evaluate(frame, ctx.anims);
+        let section = ctx.sections.iter().rfind(|(start, _)| *start <= frame).map(|(_, name)| name.as_str());
+        let text = self.text_at(frame, ctx.frame_count, section);
+
+        let width = if self.width > 0 { self.width } else { ctx.canvas_width.saturating_sub(x) };
+        let used: usize = text.chars().map(char_width).sum();
+        let slack = (width as usize).saturating_sub(used) as u16;
+        let mut col = x + match self.align {
+            TextAlign::Left => 0,
+            TextAlign::Center => slack / 2,
+            TextAlign::Right => slack,
+        };
+        for ch in text.chars() {
+            ops.push(DrawOp { x: col, y, ch, style: self.style.clone(), z_order: self.z_order });
+            col += char_width(ch) as u16;
+        }
+    }
+}
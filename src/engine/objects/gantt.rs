@@ -0,0 +1,168 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{char_width, Color, DrawOp, NamedColor, Style};
+
+use super::super::source::{FrameRange, Position};
+use super::motion::{Entrance, Exit};
+use super::rect::BorderStyle;
+use super::{Resolve, ResolveCtx};
+
+pub(crate) fn default_gantt_width() -> u16 {
+    40
+}
+
+/// One bar of a [`Gantt`] chart.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GanttTask {
+    /// Written on the bar in black, as much as fits.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub label: String,
+    pub start: f64,
+    pub end: f64,
+    /// The row it shares with the other tasks of the same lane; a task with
+    /// no lane gets an unlabelled row to itself.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub lane: String,
+    /// The bar's colour; cyan when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
+}
+
+/// A roadmap as a Gantt chart: each task a horizontal bar from its `start`
+/// to its `end`, in a box `width` columns wide with one row per lane and the
+/// lane names down its left side.
+///
+/// `start` and `end` are in whatever unit the deck likes (weeks, sprints,
+/// days of the quarter); the chart spans `min` to `max`, by default the
+/// earliest start to the latest end. `today` draws a red marker line down the
+/// chart at that point, flagged with a `▼` on the top edge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Gantt {
+    pub position: Position,
+    pub tasks: Vec<GanttTask>,
+    /// Outer width, border included.
+    #[serde(default = "default_gantt_width")]
+    pub width: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    /// Where to draw the "today" marker.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub today: Option<f64>,
+    #[serde(default)]
+    pub border: BorderStyle,
+    #[serde(default)]
+    pub style: Style,
+    pub frames: FrameRange,
+    #[serde(default)]
+    pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
+}
+
+impl Gantt {
+    /// The rows of the chart: each lane's name (empty for a task without
+    /// one) and the indices of its tasks, lanes in first-listed order.
+    pub fn lanes(&self) -> Vec<(String, Vec<usize>)> {
+        let mut lanes: Vec<(String, Vec<usize>)> = Vec::new();
+        for (i, task) in self.tasks.iter().enumerate() {
+            match lanes.iter_mut().find(|(name, _)| !task.lane.is_empty() && *name == task.lane) {
+                Some((_, tasks)) => tasks.push(i),
+                None => lanes.push((task.lane.clone(), vec![i])),
+            }
+        }
+        lanes
+    }
+
+    /// The `(min, max)` the chart spans.
+    pub fn range(&self) -> (f64, f64) {
+        let min = self.min.unwrap_or_else(|| self.tasks.iter().map(|t| t.start).fold(f64::INFINITY, f64::min));
+        let max = self.max.unwrap_or_else(|| self.tasks.iter().map(|t| t.end).fold(f64::NEG_INFINITY, f64::max));
+        (min, max)
+    }
+
+    /// `(columns, rows)` the chart covers.
+    pub fn size(&self) -> (u16, u16) {
+        (self.width.max(2), self.lanes().len() as u16 + 2)
+    }
+
+    /// Width of the lane-name column, including a blank column either side.
+    fn lane_width(&self) -> u16 {
+        let w = self.tasks.iter().map(|t| t.lane.chars().map(char_width).sum::<usize>()).max().unwrap_or(0);
+        if w == 0 { 0 } else { w as u16 + 2 }
+    }
+}
+
+impl Resolve for Gantt {
+    fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+        let frame = ctx.frame;
+        if !self.frames.contains(frame) {
+            return;
+        }
+        let x = self.position.x.evaluate(frame, ctx.anims);
+        let y = self.position.y.evaluate(frame, ctx.anims);
+        let (w, h) = self.size();
+        let lane_width = self.lane_width();
+        let plot = w.saturating_sub(2 + lane_width);
+        let (min, max) = self.range();
+        let scale = |v: f64| {
+            let t = if max > min { ((v - min) / (max - min)).clamp(0.0, 1.0) } else { 0.0 };
+            (t * f64::from(plot)).round() as u16
+        };
+        let today = self.today.filter(|v| (min..=max).contains(v)).map(|v| scale(v).min(plot.saturating_sub(1)));
+        let marker = Style { fg: Some(Color::Named(NamedColor::Red)), ..self.style.clone() };
+        let mut put = |col: u16, row: u16, ch: char, style: &Style| {
+            ops.push(DrawOp { x: x + col, y: y + row, ch, style: style.clone(), z_order: self.z_order });
+        };
+
+        let b = self.border.chars();
+        for col in 1..w - 1 {
+            let flag = today.is_some_and(|t| col == 1 + lane_width + t);
+            let (top, style) = if flag { ('▼', &marker) } else { (b.horizontal, &self.style) };
+            put(col, 0, top, style);
+            put(col, h - 1, b.horizontal, &self.style);
+        }
+        put(0, 0, b.top_left, &self.style);
+        put(w - 1, 0, b.top_right, &self.style);
+        put(0, h - 1, b.bottom_left, &self.style);
+        put(w - 1, h - 1, b.bottom_right, &self.style);
+
+        for (r, (lane, tasks)) in self.lanes().iter().enumerate() {
+            let row = r as u16 + 1;
+            put(0, row, b.vertical, &self.style);
+            put(w - 1, row, b.vertical, &self.style);
+            let mut col = 2;
+            for ch in lane.chars() {
+                put(col, row, ch, &self.style);
+                col += char_width(ch) as u16;
+            }
+
+            // The row's plot cells: a blank, or a bar's colour and the label
+            // character written on it.
+            let mut cells: Vec<(char, Option<Color>)> = vec![(' ', None); plot as usize];
+            for &i in tasks {
+                let task = &self.tasks[i];
+                let color = task.color.clone().unwrap_or(Color::Named(NamedColor::Cyan));
+                let from = scale(task.start).min(plot.saturating_sub(1));
+                let to = scale(task.end).max(from + 1).min(plot);
+                let mut label = task.label.chars();
+                for cell in &mut cells[from as usize..to as usize] {
+                    *cell = (label.next().unwrap_or(' '), Some(color.clone()));
+                }
+            }
+            for (c, (ch, bg)) in cells.into_iter().enumerate() {
+                let col = 1 + lane_width + c as u16;
+                if today == Some(c as u16) {
+                    put(col, row, '│', &Style { bg, ..marker.clone() });
+                } else if bg.is_some() {
+                    put(col, row, ch, &Style { fg: Some(Color::Named(NamedColor::Black)), bg, ..self.style.clone() });
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{Color, DrawOp, Style};
+
+use super::super::source::{AnimSpans, FrameRange, Position, Scalar};
+use super::motion::{Entrance, Exit};
+use super::{Resolve, ResolveCtx, BRAILLE_BITS};
+
+pub(crate) fn default_gauge_max() -> Scalar {
+    Scalar::Fixed(100.0)
+}
+
+fn default_gauge_min() -> Scalar {
+    Scalar::Fixed(0.0)
+}
+
+pub(crate) fn default_gauge_size() -> u16 {
+    20
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// A colour a [`Gauge`] switches to once its value reaches `at`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Threshold {
+    pub at: f64,
+    pub color: Color,
+}
+
+/// A semicircular dial: an arc from `min` on the left to `max` on the right,
+/// lit up to `value` — for latency budgets, error rates and the like.
+///
+/// `value`, `min` and `max` are [`Scalar`]s — any number, negative or
+/// fractional — so an `animation` can sweep the reading (or rescale the dial)
+/// from one frame to another; the value is clamped to the range. The lit part takes the colour of the highest
+/// `thresholds` entry the value has reached (else the style's `fg`); the rest
+/// of the arc is drawn dim. The arc is drawn in braille dots, or in whole
+/// blocks with `blocks`, `size` columns wide (at most the canvas' width) and
+/// about a quarter as many rows tall, with the value written in the middle of
+/// its bottom row.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Gauge {
+    pub position: Position,
+    pub value: Scalar,
+    #[serde(default = "default_gauge_min")]
+    pub min: Scalar,
+    #[serde(default = "default_gauge_max")]
+    pub max: Scalar,
+    /// Width in columns.
+    #[serde(default = "default_gauge_size")]
+    pub size: u16,
+    /// Colours by value, in any order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub thresholds: Vec<Threshold>,
+    /// Draw the arc in `█` blocks rather than braille dots.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub blocks: bool,
+    /// Write the value (and `unit`) under the arc's centre.
+    #[serde(default = "default_true")]
+    pub show_value: bool,
+    /// Suffix for the shown value, e.g. `"%"` or `" ms"`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub unit: String,
+    #[serde(default)]
+    pub style: Style,
+    pub frames: FrameRange,
+    #[serde(default)]
+    pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
+}
+
+impl Gauge {
+    /// Rows the gauge covers.
+    pub fn rows(&self) -> u16 {
+        Self::rows_for(self.size.max(2))
+    }
+
+    /// Rows an arc `cols` columns wide covers.
+    fn rows_for(cols: u16) -> u16 {
+        let radius = cols - 1; // in dots: the arc spans 2·cols of them
+        radius / 4 + 1
+    }
+
+    /// How far `value` lies between `min` and `max`, clamped to 0–1.
+    pub fn fraction(value: f64, min: f64, max: f64) -> f64 {
+        let span = max - min;
+        if span <= 0.0 {
+            return 0.0;
+        }
+        ((value - min) / span).clamp(0.0, 1.0)
+    }
+
+    /// The `(value, min, max)` reading at `frame`, the value clamped to the range.
+    pub fn reading(&self, frame: usize, anims: &AnimSpans) -> (f64, f64, f64) {
+        let min = self.min.evaluate(frame, anims);
+        let max = self.max.evaluate(frame, anims);
+        let value = self.value.evaluate(frame, anims);
+        (if min <= max { value.clamp(min, max) } else { value }, min, max)
+    }
+
+    /// The colour the lit arc takes at `value`.
+    pub fn color_at(&self, value: f64) -> Option<Color> {
+        self.thresholds
+            .iter()
+            .filter(|t| value >= t.at)
+            .max_by(|a, b| a.at.total_cmp(&b.at))
+            .map(|t| t.color.clone())
+            .or_else(|| self.style.fg.clone())
+    }
+
+    /// The text written under the arc at `value`, to as many decimal places
+    /// (at most three) as the gauge's own `value` is written with — so a sweep
+    /// from 10 to 90 reads in whole numbers and one from 0.5 to 2.5 in tenths.
+    pub fn value_text(&self, value: f64) -> String {
+        let (from, to) = match self.value {
+            Scalar::Fixed(v) => (v, v),
+            Scalar::Animated { from, to, .. } => (from, to),
+        };
+        let places = |v: f64| v.to_string().split_once('.').map_or(0, |(_, f)| f.len());
+        let places = places(from).max(places(to)).min(3);
+        let scale = 10f64.powi(places as i32);
+        // `+ 0.0` turns a reading that rounds to -0 into 0.
+        let shown = (value * scale).round() / scale + 0.0;
+        format!("{shown:.places$}{}", self.unit)
+    }
+
+    /// Each cell an arc `cols` columns wide touches, as `(column, row, dot
+    /// bits, lit)`: a cell is lit when most of its dots lie at or before
+    /// `fraction` along the arc.
+    fn cells(cols: u16, fraction: f64) -> Vec<(u16, u16, u8, bool)> {
+        let rows = Self::rows_for(cols);
+        // Braille dots are square (a cell is 2×4 of them and twice as tall
+        // as wide), so the arc is a true circle in dot space.
+        let r = f64::from(cols) - 0.5;
+        let (cx, cy) = (r, f64::from(cols - 1));
+        let thickness = (r / 4.0).max(2.0);
+        let mut cells = Vec::new();
+        for row in 0..rows {
+            for col in 0..cols {
+                let (mut bits, mut dots, mut lit) = (0u8, 0, 0);
+                for (dy, row_bits) in BRAILLE_BITS.iter().enumerate() {
+                    for (dx, bit) in row_bits.iter().enumerate() {
+                        let x = f64::from(col) * 2.0 + dx as f64;
+                        let y = f64::from(row) * 4.0 + dy as f64;
+                        let (ox, oy) = (x - cx, cy - y);
+                        let dist = ox.hypot(oy);
+                        if oy < 0.0 || dist > r + 0.5 || dist < r - thickness {
+                            continue;
+                        }
+                        bits |= bit;
+                        dots += 1;
+                        // 0 on the left end of the arc, 1 on the right.
+                        if 1.0 - oy.atan2(ox) / std::f64::consts::PI <= fraction {
+                            lit += 1;
+                        }
+                    }
+                }
+                if dots > 0 {
+                    cells.push((col, row, bits, lit * 2 >= dots && fraction > 0.0));
+                }
+            }
+        }
+        cells
+    }
+}
+
+impl Resolve for Gauge {
+    fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+        let frame = ctx.frame;
+        if !self.frames.contains(frame) {
+            return;
+        }
+        let x = self.position.x.evaluate(frame, ctx.anims);
+        let y = self.position.y.evaluate(frame, ctx.anims);
+        let (value, min, max) = self.reading(frame, ctx.anims);
+        let lit_style = Style { fg: self.color_at(value), ..self.style.clone() };
+        let track_style = Style { dim: true, ..self.style.clone() };
+        // No wider than the canvas: the arc's cost grows with its area.
+        let cols = self.size.clamp(2, ctx.canvas_width.max(2));
+
+        for (col, row, bits, lit) in Self::cells(cols, Self::fraction(value, min, max)) {
+            let ch = if self.blocks {
+                if bits.count_ones() < 4 {
+                    continue;
+                }
+                '█'
+            } else {
+                char::from_u32(0x2800 + u32::from(bits)).unwrap_or(' ')
+            };
+            let style = if lit { &lit_style } else { &track_style };
+            let (x, y) = (x.saturating_add(col), y.saturating_add(row));
+            ops.push(DrawOp { x, y, ch, style: style.clone(), z_order: self.z_order });
+        }
+
+        if self.show_value {
+            let text = self.value_text(value);
+            let width = text.chars().count() as u16;
+            let left = x.saturating_add(cols.saturating_sub(width) / 2);
+            let bottom = y.saturating_add(Self::rows_for(cols) - 1);
+            for (i, ch) in text.chars().enumerate() {
+                ops.push(DrawOp {
+                    x: left.saturating_add(i as u16),
+                    y: bottom,
+                    ch,
+                    style: lit_style.clone(),
+                    z_order: self.z_order,
+                });
+            }
+        }
+    }
+}
@@ -21,6 +21,8 @@ pub struct Group {
     pub frames: Option<FrameRange>,
     #[serde(default)]
     pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
 }
 
 impl Resolve for Group {
@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::types::{DrawOp, Style};
+use crate::types::{Color, DrawOp, Style};
 
 use super::super::source::{FrameRange, Position};
+use super::motion::{Entrance, Exit};
 use super::{font, Resolve, ResolveCtx};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,18 +15,35 @@ pub struct Header {
     pub frames: FrameRange,
     #[serde(default)]
     pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
     /// Fill character used for the large glyphs (default: `█`).
     #[serde(default = "default_header_char")]
     pub ch: char,
+    /// Foreground blended from the first colour at the left glyph column to
+    /// the second at the right of the widest line, in place of `style.fg`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gradient: Option<[Color; 2]>,
 }
 
-fn default_header_char() -> char {
+pub(crate) fn default_header_char() -> char {
     '█'
 }
 
 /// Blank glyph-rows inserted between wrapped header lines.
 const LINE_GAP: u16 = 1;
 
+/// The RGB colour `t` (0–1) of the way from `from` to `to`.
+pub(crate) fn blend(from: &Color, to: &Color, t: f64) -> Color {
+    let ((r0, g0, b0), (r1, g1, b1)) = (from.rgb(), to.rgb());
+    let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    Color::Rgb { r: mix(r0, r1), g: mix(g0, g1), b: mix(b0, b1) }
+}
+
 impl Header {
     /// Split the header text into lines that each fit within `avail` glyph
     /// columns, breaking on word boundaries. A word that is wider than `avail`
@@ -50,8 +68,21 @@ impl Header {
         lines
     }
 
-    /// Render a single line of glyphs starting at `(base_x, base_y)`.
-    fn render_line(&self, text: &str, base_x: u16, base_y: u16, ops: &mut Vec<DrawOp>) {
+    /// The style of a glyph cell `col` columns right of the header's left edge,
+    /// with the `gradient` spread over `span` columns.
+    fn glyph_style(&self, col: u16, span: u16) -> Style {
+        match &self.gradient {
+            Some([from, to]) => {
+                let t = f64::from(col) / f64::from(span.saturating_sub(1).max(1));
+                Style { fg: Some(blend(from, to, t.min(1.0))), ..self.style.clone() }
+            }
+            None => self.style.clone(),
+        }
+    }
+
+    /// Render a single line of glyphs starting at `(base_x, base_y)`, its
+    /// gradient spread over `span` columns.
+    fn render_line(&self, text: &str, base_x: u16, base_y: u16, span: u16, ops: &mut Vec<DrawOp>) {
         let has_bg = self.style.bg.is_some();
         let bg_style = if has_bg {
             Style {
@@ -59,6 +90,8 @@ impl Header {
                 bg: self.style.bg.clone(),
                 bold: false,
                 dim: false,
+                underline: false,
+                link: None,
             }
         } else {
             Style::default()
@@ -75,7 +108,7 @@ impl Header {
                                 x: cursor_x + col as u16,
                                 y: base_y + row as u16,
                                 ch: self.ch,
-                                style: self.style.clone(),
+                                style: self.glyph_style(cursor_x - base_x + col as u16, span),
                                 z_order: self.z_order,
                             });
                         } else if has_bg {
@@ -122,9 +155,11 @@ impl Resolve for Header {
         // next line drops one glyph height plus a one-row gap below.
         let avail = canvas_width.saturating_sub(base_x);
         let stride = font::GLYPH_HEIGHT + LINE_GAP;
-        for (line_idx, line) in self.wrap_lines(avail).iter().enumerate() {
+        let lines = self.wrap_lines(avail);
+        let span = lines.iter().map(|l| font::text_width(l)).max().unwrap_or(0);
+        for (line_idx, line) in lines.iter().enumerate() {
             let line_y = base_y + line_idx as u16 * stride;
-            self.render_line(line, base_x, line_y, ops);
+            self.render_line(line, base_x, line_y, span, ops);
         }
     }
 }
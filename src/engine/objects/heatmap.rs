@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{char_width, Color, DrawOp, NamedColor, Style};
+
+use super::super::source::{FrameRange, Position};
+use super::header::blend;
+use super::motion::{Entrance, Exit};
+use super::{Resolve, ResolveCtx};
+
+pub(crate) fn default_heatmap_cell_width() -> u16 {
+    3
+}
+
+pub(crate) fn default_heatmap_low() -> Color {
+    Color::Named(NamedColor::Blue)
+}
+
+pub(crate) fn default_heatmap_high() -> Color {
+    Color::Named(NamedColor::Red)
+}
+
+/// A grid of numbers drawn as coloured cells — error rates by hour and day,
+/// benchmark results by input size — each cell's background blended from
+/// `low` to `high` by where its value lies between `min` and `max`.
+///
+/// `min` and `max` default to the smallest and largest value in the grid.
+/// `row_labels` go in a column to the left of the cells and `col_labels` in a
+/// row above them, each cut to the cell width; the labels take the object's
+/// `style`. Rows may be ragged: a missing value leaves its cell blank.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Heatmap {
+    pub position: Position,
+    /// The values, one inner list per row.
+    pub values: Vec<Vec<f64>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub row_labels: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub col_labels: Vec<String>,
+    /// Columns per cell.
+    #[serde(default = "default_heatmap_cell_width")]
+    pub cell_width: u16,
+    /// The value drawn fully `low`; the smallest value when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    /// The value drawn fully `high`; the largest value when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    #[serde(default = "default_heatmap_low")]
+    pub low: Color,
+    #[serde(default = "default_heatmap_high")]
+    pub high: Color,
+    #[serde(default)]
+    pub style: Style,
+    pub frames: FrameRange,
+    #[serde(default)]
+    pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
+}
+
+impl Heatmap {
+    /// The `(min, max)` the colour ramp spans.
+    pub fn range(&self) -> (f64, f64) {
+        let all = self.values.iter().flatten().copied();
+        let min = self.min.unwrap_or_else(|| all.clone().fold(f64::INFINITY, f64::min));
+        let max = self.max.unwrap_or_else(|| all.fold(f64::NEG_INFINITY, f64::max));
+        (min, max)
+    }
+
+    /// The background a cell holding `value` gets.
+    pub fn color_at(&self, value: f64) -> Color {
+        let (min, max) = self.range();
+        let t = if max > min { ((value - min) / (max - min)).clamp(0.0, 1.0) } else { 0.0 };
+        blend(&self.low, &self.high, t)
+    }
+
+    /// Width of the row-label column, including its one-column gap.
+    fn label_width(&self) -> u16 {
+        let w = self.row_labels.iter().map(|l| l.chars().map(char_width).sum::<usize>()).max().unwrap_or(0);
+        if w == 0 { 0 } else { w as u16 + 1 }
+    }
+
+    /// `(columns, rows)` the heatmap covers, labels included.
+    pub fn size(&self) -> (u16, u16) {
+        let cols = self.values.iter().map(Vec::len).max().unwrap_or(0) as u16;
+        let header = u16::from(!self.col_labels.is_empty());
+        (self.label_width() + cols * self.cell_width, header + self.values.len() as u16)
+    }
+}
+
+impl Resolve for Heatmap {
+    fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+        let frame = ctx.frame;
+        if !self.frames.contains(frame) {
+            return;
+        }
+        let x = self.position.x.evaluate(frame, ctx.anims);
+        let y = self.position.y.evaluate(frame, ctx.anims);
+        let mut text = |col: u16, row: u16, s: &str, width: u16| {
+            let mut used = 0;
+            for ch in s.chars() {
+                let w = char_width(ch) as u16;
+                if used + w > width {
+                    break;
+                }
+                ops.push(DrawOp { x: col + used, y: row, ch, style: self.style.clone(), z_order: self.z_order });
+                used += w;
+            }
+        };
+
+        let left = x + self.label_width();
+        let mut top = y;
+        if !self.col_labels.is_empty() {
+            for (i, label) in self.col_labels.iter().enumerate() {
+                text(left + i as u16 * self.cell_width, y, label, self.cell_width);
+            }
+            top += 1;
+        }
+        for (i, label) in self.row_labels.iter().enumerate().take(self.values.len()) {
+            text(x, top + i as u16, label, self.label_width().saturating_sub(1));
+        }
+
+        for (r, row) in self.values.iter().enumerate() {
+            for (c, &value) in row.iter().enumerate() {
+                let style = Style { bg: Some(self.color_at(value)), ..Style::default() };
+                for dx in 0..self.cell_width {
+                    ops.push(DrawOp {
+                        x: left + c as u16 * self.cell_width + dx,
+                        y: top + r as u16,
+                        ch: ' ',
+                        style: style.clone(),
+                        z_order: self.z_order,
+                    });
+                }
+            }
+        }
+    }
+}
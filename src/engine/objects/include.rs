@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::DrawOp;
+
+use super::super::source::FrameRange;
+use super::{Resolve, ResolveCtx};
+
+/// Pulls another source deck's objects into this one.
+///
+/// Like `Group` it draws nothing itself: when the deck is loaded
+/// ([`crate::include::resolve_includes`]) the included deck's objects are
+/// appended after this deck's own, with their frames moved so the included
+/// deck's `frames.start` lands on `at`. The engine alone (the editor preview,
+/// [`crate::pipeline::compile`] on a parsed deck) never sees them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Include {
+    /// The deck to include, relative to the including file's directory.
+    pub path: String,
+    /// Frame of this deck the included deck's first selected frame lands on.
+    #[serde(default)]
+    pub at: usize,
+    /// Which of the included deck's frames to take (end exclusive); all of
+    /// them when omitted. Objects are clipped to the selection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frames: Option<FrameRange>,
+    /// Included objects without a layer of their own are put on this one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+}
+
+impl Resolve for Include {
+    fn resolve(&self, _ctx: &ResolveCtx, _ops: &mut Vec<DrawOp>) {
+        // Includes draw nothing; the included objects are spliced into the
+        // deck when it is loaded.
+    }
+}
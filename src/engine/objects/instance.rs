@@ -0,0 +1,176 @@
+//! Reusable components: a named group of object templates in the deck's
+//! `components` section, stamped out by `instance` objects with arguments.
+//!
+//! A template is plain source JSON with `{{name}}` placeholders in its
+//! strings. A string that is exactly one placeholder (`"x": "{{x}}"`) takes the
+//! argument's JSON value as-is, so numbers stay numbers; a placeholder inside
+//! longer text (`"text": "Step {{n}}: {{title}}"`) is spliced in as text.
+//!
+//! An instance draws nothing by itself — its expansion needs the deck's
+//! components, so the engine expands every instance once per compile
+//! ([`SourcePresentation::instance_expansions`]) and resolves the expansion in
+//! its place.
+//!
+//! [`SourcePresentation::instance_expansions`]: crate::engine::source::SourcePresentation::instance_expansions
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::types::DrawOp;
+
+use super::super::source::{FrameRange, SceneObject};
+use super::{Resolve, ResolveCtx};
+
+/// One entry of `SourcePresentation::components`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Component {
+    /// Every placeholder the templates may use, with its default. A `null`
+    /// default makes the parameter required.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub params: Map<String, Value>,
+    /// Object templates: source objects, minus `frames` (an instance's range
+    /// applies to all of them), with placeholders in their strings.
+    pub objects: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Instance {
+    /// Name of the component in `SourcePresentation::components`.
+    pub component: String,
+    /// Parameter values; omitted parameters take their defaults.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub args: Map<String, Value>,
+    /// Frame range of every object in the expansion.
+    pub frames: FrameRange,
+    /// Added to the z-order of everything the expansion draws.
+    #[serde(default)]
+    pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+}
+
+impl Instance {
+    /// The component's objects with this instance's arguments substituted and
+    /// its frame range applied, or a description of why they can't be built
+    /// (unknown component or parameter, missing argument, a template that is
+    /// not a valid object).
+    pub fn expand(&self, components: &BTreeMap<String, Component>) -> Result<Vec<SceneObject>, String> {
+        let name = &self.component;
+        let component = components
+            .get(name)
+            .ok_or_else(|| format!("there is no component `{name}`"))?;
+        if let Some(arg) = self.args.keys().find(|k| !component.params.contains_key(*k)) {
+            return Err(format!("component `{name}` has no parameter `{arg}`"));
+        }
+        let mut vars = component.params.clone();
+        vars.extend(self.args.iter().map(|(k, v)| (k.clone(), v.clone())));
+        if let Some((missing, _)) = vars.iter().find(|(_, v)| v.is_null()) {
+            return Err(format!("component `{name}` needs an argument for `{missing}`"));
+        }
+
+        let frames = serde_json::json!({ "start": self.frames.start, "end": self.frames.end });
+        let mut out = Vec::with_capacity(component.objects.len());
+        for (k, template) in component.objects.iter().enumerate() {
+            let mut value = substitute(template, &vars)
+                .map_err(|p| format!("component `{name}` uses `{{{{{p}}}}}`, which is not a parameter"))?;
+            if let Value::Object(m) = &mut value {
+                m.entry("frames").or_insert_with(|| frames.clone());
+            }
+            let mut obj: SceneObject = serde_json::from_value(value)
+                .map_err(|e| format!("component `{name}` object {k} is invalid: {e}"))?;
+            if matches!(
+                obj,
+                SceneObject::Group(_)
+                    | SceneObject::Command(_)
+                    | SceneObject::Loop(_)
+                    | SceneObject::Animation(_)
+                    | SceneObject::AutoAdvance(_)
+                    | SceneObject::Instance(_)
+            ) {
+                return Err(format!(
+                    "component `{name}` object {k} is a {}; components may only hold drawable objects",
+                    obj.kind()
+                ));
+            }
+            obj.set_frame_range(self.frames.clone());
+            out.push(obj);
+        }
+        Ok(out)
+    }
+
+    /// Resolve `expansion` (this instance's [`Self::expand`]) as the instance,
+    /// on `range` — the instance's own frames, or an explicit group's.
+    pub(crate) fn resolve_expansion(
+        &self,
+        expansion: &[SceneObject],
+        range: &FrameRange,
+        ctx: &ResolveCtx,
+        ops: &mut Vec<DrawOp>,
+    ) {
+        if !range.contains(ctx.frame) {
+            return;
+        }
+        let before = ops.len();
+        let own = range.start == self.frames.start && range.end == self.frames.end;
+        for obj in expansion {
+            if own {
+                obj.resolve(ctx, ops);
+            } else {
+                let mut obj = obj.clone();
+                obj.set_frame_range(range.clone());
+                obj.resolve(ctx, ops);
+            }
+        }
+        for op in &mut ops[before..] {
+            op.z_order += self.z_order;
+        }
+    }
+}
+
+impl Resolve for Instance {
+    fn resolve(&self, _ctx: &ResolveCtx, _ops: &mut Vec<DrawOp>) {
+        // An instance draws through its expansion, which the engine builds
+        // from the deck's components (see `resolve_expansion`).
+    }
+}
+
+/// `template` with every placeholder replaced from `vars`, or the name of the
+/// first placeholder `vars` lacks.
+fn substitute(template: &Value, vars: &Map<String, Value>) -> Result<Value, String> {
+    Ok(match template {
+        Value::String(s) => substitute_str(s, vars)?,
+        Value::Array(items) => {
+            Value::Array(items.iter().map(|v| substitute(v, vars)).collect::<Result<_, _>>()?)
+        }
+        Value::Object(m) => Value::Object(
+            m.iter()
+                .map(|(k, v)| Ok((k.clone(), substitute(v, vars)?)))
+                .collect::<Result<_, String>>()?,
+        ),
+        other => other.clone(),
+    })
+}
+
+fn substitute_str(s: &str, vars: &Map<String, Value>) -> Result<Value, String> {
+    let lookup = |name: &str| vars.get(name.trim()).ok_or_else(|| name.trim().to_string());
+    if let Some(name) = s.strip_prefix("{{").and_then(|r| r.strip_suffix("}}")) {
+        if !name.contains("{{") && !name.contains("}}") {
+            return lookup(name).cloned();
+        }
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(open) = rest.find("{{") {
+        let Some(close) = rest[open + 2..].find("}}") else { break };
+        out.push_str(&rest[..open]);
+        match lookup(&rest[open + 2..open + 2 + close])? {
+            Value::String(text) => out.push_str(text),
+            other => out.push_str(&other.to_string()),
+        }
+        rest = &rest[open + 2 + close + 2..];
+    }
+    out.push_str(rest);
+    Ok(Value::String(out))
+}
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{char_columns, str_width, Color, DrawOp, Style};
+
+use super::super::source::{FrameRange, Position};
+use super::motion::{Entrance, Exit};
+use super::rect::BorderStyle;
+use super::{Resolve, ResolveCtx};
+
+pub(crate) fn default_keycap_border() -> BorderStyle {
+    BorderStyle::Rounded
+}
+
+pub(crate) fn default_keycap_separator() -> String {
+    "+".to_string()
+}
+
+/// A keyboard shortcut drawn as a row of key caps — `Ctrl`, `Shift`, `P` —
+/// for tool and TUI walkthroughs.
+///
+/// Each of `keys` gets a small box in the `border` set, one blank either side
+/// of its name, with `separator` between caps on the middle row. `compact`
+/// draws it on a single row instead, as `⟨Ctrl⟩+⟨S⟩`. With `cap` set, the
+/// keys' faces are filled in that colour, like the caps of a real keyboard.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keycap {
+    pub position: Position,
+    /// The keys of the shortcut, in the order they're pressed.
+    pub keys: Vec<String>,
+    /// Drawn between caps; empty leaves a blank.
+    #[serde(default = "default_keycap_separator")]
+    pub separator: String,
+    #[serde(default = "default_keycap_border")]
+    pub border: BorderStyle,
+    /// One row of `⟨key⟩`s instead of boxed caps.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub compact: bool,
+    /// Background of the keys' faces.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cap: Option<Color>,
+    #[serde(default)]
+    pub style: Style,
+    pub frames: FrameRange,
+    #[serde(default)]
+    pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
+}
+
+/// A key combination as written in the editor (`Ctrl+Shift+P`) split into
+/// its keys. A `+` where a key should start is the `+` key itself, so
+/// `Ctrl++` is Ctrl and plus.
+pub fn parse_keys(s: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut key = String::new();
+    for ch in s.trim().chars() {
+        if ch == '+' && !key.is_empty() {
+            keys.push(std::mem::take(&mut key).trim().to_string());
+        } else if !(ch.is_whitespace() && key.is_empty()) {
+            key.push(ch);
+        }
+    }
+    if !key.trim().is_empty() {
+        keys.push(key.trim().to_string());
+    }
+    keys
+}
+
+impl Keycap {
+    /// The separator with its surrounding blanks, as drawn between two caps.
+    fn gap(&self) -> String {
+        match (self.separator.is_empty(), self.compact) {
+            (true, _) => " ".to_string(),
+            (false, true) => self.separator.clone(),
+            (false, false) => format!(" {} ", self.separator),
+        }
+    }
+
+    /// Every cell drawn, as `(column, row, char, on a key's face)`.
+    pub fn cells(&self) -> Vec<(u16, u16, char, bool)> {
+        let mut cells = Vec::new();
+        let text = |col: u16, row: u16, s: &str, face: bool, cells: &mut Vec<(u16, u16, char, bool)>| {
+            for (c, ch) in char_columns(s) {
+                cells.push((col + c as u16, row, ch, face));
+            }
+        };
+        let b = self.border.chars();
+        let (gap, mid) = (self.gap(), u16::from(!self.compact));
+        let mut col = 0u16;
+        for (i, key) in self.keys.iter().enumerate() {
+            if i > 0 {
+                text(col, mid, &gap, false, &mut cells);
+                col += str_width(&gap) as u16;
+            }
+            let w = str_width(key) as u16;
+            if self.compact {
+                cells.push((col, 0, '⟨', false));
+                text(col + 1, 0, key, true, &mut cells);
+                cells.push((col + 1 + w, 0, '⟩', false));
+                col += w + 2;
+                continue;
+            }
+            // ╭─────╮
+            // │ key │
+            // ╰─────╯
+            let right = col + w + 3;
+            cells.push((col, 0, b.top_left, false));
+            cells.push((right, 0, b.top_right, false));
+            cells.push((col, 1, b.vertical, false));
+            cells.push((right, 1, b.vertical, false));
+            cells.push((col, 2, b.bottom_left, false));
+            cells.push((right, 2, b.bottom_right, false));
+            for c in col + 1..right {
+                cells.push((c, 0, b.horizontal, false));
+                cells.push((c, 2, b.horizontal, false));
+            }
+            text(col + 1, 1, &format!(" {key} "), true, &mut cells);
+            col = right + 1;
+        }
+        cells
+    }
+
+    /// `(columns, rows)` covered.
+    pub fn size(&self) -> (u16, u16) {
+        let keys: usize = self.keys.iter().map(|k| str_width(k) + if self.compact { 2 } else { 4 }).sum();
+        let gaps = self.keys.len().saturating_sub(1) * str_width(&self.gap());
+        ((keys + gaps) as u16, if self.compact { 1 } else { 3 })
+    }
+}
+
+impl Resolve for Keycap {
+    fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+        let frame = ctx.frame;
+        if !self.frames.contains(frame) {
+            return;
+        }
+        let x = self.position.x.evaluate(frame, ctx.anims);
+        let y = self.position.y.evaluate(frame, ctx.anims);
+        let face = Style { bg: self.cap.clone().or_else(|| self.style.bg.clone()), ..self.style.clone() };
+        for (col, row, ch, on_face) in self.cells() {
+            let style = if on_face { &face } else { &self.style };
+            ops.push(DrawOp { x: x + col, y: y + row, ch, style: style.clone(), z_order: self.z_order });
+        }
+    }
+}
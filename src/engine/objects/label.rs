@@ -1,8 +1,13 @@
+
 use serde::{Deserialize, Serialize};
 
-use crate::types::{DrawOp, Style};
+use crate::types::{char_columns, char_width, str_width, DrawOp, Style, WIDE_CONTINUATION};
 
 use super::super::source::{Coordinate, FrameRange, Position, deserialize_coord_compat};
+use super::markup;
+use super::motion::{Entrance, Exit};
+use super::rect::{deserialize_shadow, serialize_shadow, BorderStyle, Shadow};
+use super::table::caret_block_style;
 use super::{Resolve, ResolveCtx};
 
 /// Horizontal alignment of text within the label's `width`. Only meaningful when
@@ -77,24 +82,80 @@ impl VerticalAlign {
     }
 }
 
+/// Blank cells between a label's text and its frame (or the edge of its `bg`
+/// fill). Written as one number for all four sides, or as an object of sides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(from = "PaddingRepr", into = "PaddingRepr")]
+pub struct Padding {
+    pub top: u16,
+    pub right: u16,
+    pub bottom: u16,
+    pub left: u16,
+}
+
+impl Padding {
+    /// The same padding on every side.
+    pub fn uniform(n: u16) -> Self {
+        Padding { top: n, right: n, bottom: n, left: n }
+    }
+    fn is_zero(&self) -> bool {
+        *self == Padding::default()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum PaddingRepr {
+    All(u16),
+    Sides {
+        #[serde(default)]
+        top: u16,
+        #[serde(default)]
+        right: u16,
+        #[serde(default)]
+        bottom: u16,
+        #[serde(default)]
+        left: u16,
+    },
+}
+
+impl From<PaddingRepr> for Padding {
+    fn from(r: PaddingRepr) -> Self {
+        match r {
+            PaddingRepr::All(n) => Padding::uniform(n),
+            PaddingRepr::Sides { top, right, bottom, left } => Padding { top, right, bottom, left },
+        }
+    }
+}
+
+impl From<Padding> for PaddingRepr {
+    fn from(p: Padding) -> Self {
+        if p == Padding::uniform(p.top) {
+            PaddingRepr::All(p.top)
+        } else {
+            PaddingRepr::Sides { top: p.top, right: p.right, bottom: p.bottom, left: p.left }
+        }
+    }
+}
+
 /// Re-place a wrapped, width-`w` row's content according to `align`. `Left`
 /// returns the row untouched (preserving any list-continuation indent); `Center`
 /// and `Right` trim the content to its non-space span and re-seat it within `w`.
-fn align_row(row: Vec<char>, w: usize, align: TextAlign) -> Vec<char> {
+fn align_row(row: Vec<Glyph>, w: usize, align: TextAlign) -> Vec<Glyph> {
     if align == TextAlign::Left {
         return row;
     }
-    let Some(lo) = row.iter().position(|&c| c != ' ') else {
+    let Some(lo) = row.iter().position(|&(c, _)| c != ' ') else {
         return row; // blank row — nothing to align
     };
-    let hi = row.iter().rposition(|&c| c != ' ').unwrap() + 1;
+    let hi = row.iter().rposition(|&(c, _)| c != ' ').unwrap() + 1;
     let content_len = hi - lo;
     let start = match align {
         TextAlign::Center => w.saturating_sub(content_len) / 2,
         TextAlign::Right => w.saturating_sub(content_len),
         TextAlign::Left => 0,
     };
-    let mut out = vec![' '; w];
+    let mut out = vec![(' ', None); w];
     for (i, &c) in row[lo..hi].iter().enumerate() {
         if start + i < w {
             out[start + i] = c;
@@ -103,55 +164,149 @@ fn align_row(row: Vec<char>, w: usize, align: TextAlign) -> Vec<char> {
     out
 }
 
-fn draw_frame(ops: &mut Vec<DrawOp>, fx: u16, fy: u16, fw: usize, fh: usize, style: &Style, z_order: i32) {
+#[allow(clippy::too_many_arguments)]
+fn draw_frame(
+    ops: &mut Vec<DrawOp>,
+    fx: u16,
+    fy: u16,
+    fw: usize,
+    fh: usize,
+    border: BorderStyle,
+    style: &Style,
+    z_order: i32,
+) {
     if fw < 2 || fh < 2 {
         return;
     }
     let fw = fw as u16;
     let fh = fh as u16;
     let z = z_order;
+    let b = border.chars();
 
-    ops.push(DrawOp { x: fx,          y: fy,          ch: '┌', style: style.clone(), z_order: z });
-    ops.push(DrawOp { x: fx + fw - 1, y: fy,          ch: '┐', style: style.clone(), z_order: z });
-    ops.push(DrawOp { x: fx,          y: fy + fh - 1, ch: '└', style: style.clone(), z_order: z });
-    ops.push(DrawOp { x: fx + fw - 1, y: fy + fh - 1, ch: '┘', style: style.clone(), z_order: z });
+    ops.push(DrawOp { x: fx,          y: fy,          ch: b.top_left, style: style.clone(), z_order: z });
+    ops.push(DrawOp { x: fx + fw - 1, y: fy,          ch: b.top_right, style: style.clone(), z_order: z });
+    ops.push(DrawOp { x: fx,          y: fy + fh - 1, ch: b.bottom_left, style: style.clone(), z_order: z });
+    ops.push(DrawOp { x: fx + fw - 1, y: fy + fh - 1, ch: b.bottom_right, style: style.clone(), z_order: z });
     for i in 1..fw - 1 {
-        ops.push(DrawOp { x: fx + i, y: fy,          ch: '─', style: style.clone(), z_order: z });
-        ops.push(DrawOp { x: fx + i, y: fy + fh - 1, ch: '─', style: style.clone(), z_order: z });
+        ops.push(DrawOp { x: fx + i, y: fy,          ch: b.horizontal, style: style.clone(), z_order: z });
+        ops.push(DrawOp { x: fx + i, y: fy + fh - 1, ch: b.horizontal, style: style.clone(), z_order: z });
     }
     for j in 1..fh - 1 {
-        ops.push(DrawOp { x: fx,          y: fy + j, ch: '│', style: style.clone(), z_order: z });
-        ops.push(DrawOp { x: fx + fw - 1, y: fy + j, ch: '│', style: style.clone(), z_order: z });
+        ops.push(DrawOp { x: fx,          y: fy + j, ch: b.vertical, style: style.clone(), z_order: z });
+        ops.push(DrawOp { x: fx + fw - 1, y: fy + j, ch: b.vertical, style: style.clone(), z_order: z });
+    }
+}
+
+/// Spaces of indent per nesting level of a list item in label text.
+const LIST_INDENT: usize = 2;
+
+/// The glyph a `-` bullet takes at each nesting level, repeating past the last.
+const BULLETS: [char; 3] = ['-', '•', '◦'];
+
+/// A list item's marker: `- `, a [`BULLETS`] glyph, `N. ` or the
+/// auto-numbered `#. `.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ListMarker {
+    Bullet(char),
+    Number(usize),
+    Auto,
+}
+
+/// Parse a list item line into its leading indent, its marker and the width
+/// of the marker with its trailing space; `None` for any other line.
+fn list_item(line: &str) -> Option<(usize, ListMarker, usize)> {
+    let rest = line.trim_start_matches(' ');
+    let indent = line.len() - rest.len();
+    if rest.starts_with("#. ") {
+        return Some((indent, ListMarker::Auto, 3));
+    }
+    let mut chars = rest.chars();
+    let first = chars.next()?;
+    if (first == '-' || BULLETS.contains(&first)) && chars.next() == Some(' ') {
+        return Some((indent, ListMarker::Bullet(first), 2));
+    }
+    // "N. " where N is one or more ASCII digits.
+    let after_digits = rest.trim_start_matches(|c: char| c.is_ascii_digit());
+    let digits = &rest[..rest.len() - after_digits.len()];
+    if !digits.is_empty() && after_digits.starts_with(". ") {
+        return Some((indent, ListMarker::Number(digits.parse().ok()?), digits.len() + 2));
     }
+    None
+}
+
+/// Expand the list shorthand in label text, line by line. Items nest by
+/// [`LIST_INDENT`] spaces per level; a `-` bullet shows its level's
+/// [`BULLETS`] glyph, and `#.` is numbered one past the level's previous
+/// item (an explicit `N.` sets the count). A deeper level restarts its count
+/// under each parent item, and any line that is neither an item nor blank
+/// ends the list.
+fn format_list(text: &str) -> String {
+    let mut counts: Vec<usize> = Vec::new();
+    let lines: Vec<String> = text
+        .split('\n')
+        .map(|line| {
+            let Some((indent, marker, width)) = list_item(line) else {
+                if !line.trim().is_empty() {
+                    counts.clear();
+                }
+                return line.to_string();
+            };
+            let level = indent / LIST_INDENT;
+            counts.resize(level + 1, 0);
+            let body = &line[indent + marker_len(line, indent, width)..];
+            let pad = " ".repeat(indent);
+            match marker {
+                ListMarker::Auto => {
+                    counts[level] += 1;
+                    format!("{pad}{}. {body}", counts[level])
+                }
+                ListMarker::Number(n) => {
+                    counts[level] = n;
+                    line.to_string()
+                }
+                ListMarker::Bullet('-') => format!("{pad}{} {body}", BULLETS[level % BULLETS.len()]),
+                ListMarker::Bullet(_) => line.to_string(),
+            }
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// Byte length of the marker [`list_item`] measured `width` columns wide
+/// (a bullet glyph may be several bytes).
+fn marker_len(line: &str, indent: usize, width: usize) -> usize {
+    line[indent..].char_indices().nth(width).map_or(line.len() - indent, |(i, _)| i)
 }
 
 /// Returns the number of spaces to insert at the start of wrapped continuation
-/// lines for list items:
-/// - `"- text"` → 2 (aligns with the text after the bullet)
-/// - `"1. text"` → 3 (aligns with the text after the number and dot)
+/// lines for list items, so they line up with the item text:
+/// - `"- text"` → 2 (after the bullet)
+/// - `"10. text"` → 4 (after the number and dot)
+/// - `"  • text"` → 4 (a nested item's own indent counts too)
 /// - anything else → 0
 fn list_continuation_indent(line: &str) -> usize {
-    if line.starts_with("- ") {
-        return 2;
-    }
-    // Match "N. " where N is one or more ASCII digits.
-    let after_digits = line.trim_start_matches(|c: char| c.is_ascii_digit());
-    let digit_count = line.len() - after_digits.len(); // bytes == chars (ASCII digits)
-    if digit_count > 0 && after_digits.starts_with(". ") {
-        return 3;
-    }
-    0
+    list_item(line).map_or(0, |(indent, _, width)| indent + width)
 }
 
-/// Wrap a single logical text line to a grid width using word-breaking.
+/// A laid-out cell: its glyph and the index in the label's plain text of the
+/// char it shows (`None` for padding), which picks its markup style.
+type Glyph = (char, Option<usize>);
+
+/// Wrap a single logical text line, starting at char `base` of the text, to a
+/// grid width using word-breaking.
 ///
 /// Delegates to the shared [`wrap`](super::wrap) helper, supplying the
-/// list-item continuation indent. Returns one `Vec<char>` per visual row, each
-/// of length `w` (padded with spaces); empty lines return a single empty `Vec`.
-fn wrap_text_line(line: &str, w: usize) -> Vec<Vec<char>> {
+/// list-item continuation indent. Returns one row per visual row, each of
+/// length `w` (padded with spaces); empty lines return a single empty `Vec`.
+fn wrap_text_line(line: &str, base: usize, w: usize) -> Vec<Vec<Glyph>> {
     let indent = list_continuation_indent(line);
     let indexed = super::wrap::wrap_line_indexed(0, line, w, indent);
-    super::wrap::indexed_to_chars(line, indexed)
+    let chars = super::wrap::indexed_to_chars(line, indexed.clone());
+    chars
+        .into_iter()
+        .zip(indexed)
+        .map(|(chars, idx)| chars.into_iter().zip(idx).map(|(c, i)| (c, i.map(|i| base + i))).collect())
+        .collect()
 }
 
 fn default_label_width() -> Coordinate {
@@ -170,12 +325,40 @@ pub struct Label {
     pub width: Coordinate,
     #[serde(default = "default_label_height", deserialize_with = "deserialize_coord_compat")]
     pub height: Coordinate,
+    /// Hyperlink the text to this URL (see [`Style::link`]); the frame,
+    /// padding and fill stay unlinked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    /// Show the list items one per frame from the start of `frames` (see
+    /// [`format_list`]); the label's other lines show throughout.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub reveal: bool,
+    /// Read `**bold**`, `__underline__` and `[colour]…[/colour]` markup in
+    /// `text` (see [`super::markup`]). Off by default, so text written before
+    /// markup existed still shows verbatim.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub markup: bool,
     /// When `true`, draw a single-cell border (box-drawing chars) around the label's
     /// bounding box.  The border is rendered one cell outside (x-1, y-1) so the text
     /// position is preserved.  Requires `width > 0` for reliable sizing; if `width`
     /// is 0 the frame is sized from the longest text line and line count.
     #[serde(default)]
     pub framed: bool,
+    /// Character set of the frame (see [`BorderStyle`]); only drawn when `framed`.
+    #[serde(default, skip_serializing_if = "BorderStyle::is_default")]
+    pub border: BorderStyle,
+    /// Drop shadow behind the frame (see [`Shadow`]); only drawn when `framed`.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_shadow",
+        serialize_with = "serialize_shadow",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub shadow: Option<Shadow>,
+    /// Room between the text and the frame or `bg` fill, which grow to make it;
+    /// the text keeps its position.
+    #[serde(default, skip_serializing_if = "Padding::is_zero")]
+    pub padding: Padding,
     /// Optional separate style for the box border.  When `None`, the border uses
     /// the label's own `style`.  Only `fg` and `bg` are relevant for the border.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -191,10 +374,31 @@ pub struct Label {
     pub frames: FrameRange,
     #[serde(default)]
     pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
 }
 
-impl Resolve for Label {
-    fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+impl Label {
+    /// `text` as shown: with its lists formatted and its markup stripped,
+    /// unless `markup` is off.
+    pub fn plain_text(&self) -> String {
+        let listed = format_list(&self.text);
+        if self.markup { markup::parse(&listed).0 } else { listed }
+    }
+
+    /// Draw the label as the editor's in-place text editing shows it: `text`
+    /// verbatim — no list formatting or markup, every item shown — in the
+    /// label's box, with a block caret on the char at index `caret` (or on the
+    /// blank after a line's last one).
+    pub fn resolve_with_caret(&self, ctx: &ResolveCtx, caret: usize, ops: &mut Vec<DrawOp>) {
+        self.draw(ctx, Some(caret), ops);
+    }
+
+    fn draw(&self, ctx: &ResolveCtx, caret: Option<usize>, ops: &mut Vec<DrawOp>) {
         let frame = ctx.frame;
         if !self.frames.contains(frame) {
             return;
@@ -205,33 +409,67 @@ impl Resolve for Label {
         let h = self.height.evaluate(frame, ctx.anims) as usize;
 
         let has_bg = self.style.bg.is_some();
-
-        // The border sits one cell outside the text. Normally the text keeps its
-        // declared position; but at the canvas edge (base 0) there is no room
-        // outside, so the border would land *on* the text and hide it. There we
-        // shift the text in by one instead, keeping it inside the border. Away
-        // from the edge `draw_*` equals `base_*`, so positions are unchanged.
-        let (frame_x, frame_y) = (base_x.saturating_sub(1), base_y.saturating_sub(1));
-        let (draw_x, draw_y) = if self.framed {
-            (frame_x + 1, frame_y + 1)
+        let text_style = Style { link: self.link.clone().or_else(|| self.style.link.clone()), ..self.style.clone() };
+        let (text, styles): (String, Vec<Style>) = if caret.is_some() {
+            (self.text.clone(), Vec::new())
+        } else if self.markup {
+            let (plain, marks) = markup::parse(&format_list(&self.text));
+            (plain, marks.iter().map(|m| m.apply(&text_style)).collect())
         } else {
-            (base_x, base_y)
+            (format_list(&self.text), Vec::new())
+        };
+        let style_of = |idx: Option<usize>| match idx {
+            Some(i) => styles.get(i).unwrap_or(&text_style).clone(),
+            None => self.style.clone(),
+        };
+        // With `reveal`, item `n` (0-based) first shows `n` frames in; a hidden
+        // item keeps its rows so the layout doesn't jump as items appear.
+        let revealed = if self.reveal && caret.is_none() { frame - self.frames.start + 1 } else { usize::MAX };
+        let mut items = 0;
+        let mut hidden = |line: &str| {
+            list_item(line).is_some() && {
+                items += 1;
+                items > revealed
+            }
         };
 
+        // The border sits one cell (plus the padding) outside the text. Normally
+        // the text keeps its declared position; but near the canvas edge there is
+        // no room outside, so the border would land *on* the text and hide it.
+        // There we shift the text in instead, keeping it inside the border. Away
+        // from the edge `draw_*` equals `base_*`, so positions are unchanged.
+        let pad = self.padding;
+        let border = u16::from(self.framed);
+        let (frame_x, frame_y) =
+            (base_x.saturating_sub(border + pad.left), base_y.saturating_sub(border + pad.top));
+        let (draw_x, draw_y) = (frame_x + border + pad.left, frame_y + border + pad.top);
+        let (pad_w, pad_h) = ((pad.left + pad.right) as usize, (pad.top + pad.bottom) as usize);
+        // `(row, column)` of the caret from the text's top-left, when editing.
+        let mut caret_at: Option<(usize, usize)> = None;
+        let text_ops = ops.len();
+
         // Build a grid of characters when width > 0, so we can fill
         // remaining cells in the bounding box with bg-colored spaces.
         if w > 0 {
-            let mut rows: Vec<Vec<char>> = Vec::new();
+            let mut rows: Vec<Vec<Glyph>> = Vec::new();
             let mut row: usize = 0;
-            'lines: for line in self.text.split('\n') {
+            let mut line_start = 0;
+            'lines: for line in text.split('\n') {
                 if h > 0 && row >= h {
                     break;
                 }
-                for wrapped_row in wrap_text_line(line, w) {
+                let base = line_start;
+                line_start += line.chars().count() + 1;
+                if caret.is_some_and(|c| c >= base && c < line_start) {
+                    // Where the caret goes if no char places it (an empty line).
+                    caret_at = Some((row, 0));
+                }
+                let hide = hidden(line);
+                for wrapped_row in wrap_text_line(line, base, w) {
                     if h > 0 && row >= h {
                         break 'lines;
                     }
-                    rows.push(align_row(wrapped_row, w, self.align));
+                    rows.push(if hide { Vec::new() } else { align_row(wrapped_row, w, self.align) });
                     row += 1;
                 }
             }
@@ -240,37 +478,74 @@ impl Resolve for Label {
             if h > 0 {
                 let n = rows.len().min(h);
                 let pad_top = self.valign.top_pad(h, n);
-                let mut padded: Vec<Vec<char>> = vec![Vec::new(); h];
+                let mut padded: Vec<Vec<Glyph>> = vec![Vec::new(); h];
                 for (i, r) in rows.into_iter().take(n).enumerate() {
                     padded[pad_top + i] = r;
                 }
                 rows = padded;
+                caret_at = caret_at.map(|(r, c)| (r + pad_top, c));
+            }
+            if let Some(caret) = caret {
+                caret_at = glyph_cell(&rows, caret)
+                    .or_else(|| {
+                        let (r, c) = glyph_cell(&rows, caret.checked_sub(1)?)?;
+                        Some((r, c + char_width(rows[r][c].0)))
+                    })
+                    .or(caret_at);
             }
             // Emit DrawOps for all cells
             for (r, row_chars) in rows.iter().enumerate() {
                 let emit_w = if has_bg { w } else { row_chars.len() };
                 for col in 0..emit_w {
-                    let ch = row_chars.get(col).copied().unwrap_or(' ');
+                    let (ch, idx) = row_chars.get(col).copied().unwrap_or((' ', None));
                     if !has_bg && ch == ' ' && col >= row_chars.len() {
                         continue;
                     }
+                    // The rasterizer fills a wide glyph's second column.
+                    if ch == WIDE_CONTINUATION {
+                        continue;
+                    }
                     ops.push(DrawOp {
                         x: draw_x + col as u16,
                         y: draw_y + r as u16,
                         ch,
-                        style: self.style.clone(),
+                        style: style_of(idx),
                         z_order: self.z_order,
                     });
                 }
             }
+            // The padding takes the fill too.
+            if has_bg && !pad.is_zero() {
+                let (fill_x, fill_y) = (draw_x - pad.left, draw_y - pad.top);
+                for j in 0..(rows.len() + pad_h) as u16 {
+                    for i in 0..(w + pad_w) as u16 {
+                        let text_col = (pad.left..pad.left + w as u16).contains(&i);
+                        if text_col && (pad.top..pad.top + rows.len() as u16).contains(&j) {
+                            continue;
+                        }
+                        ops.push(DrawOp {
+                            x: fill_x + i,
+                            y: fill_y + j,
+                            ch: ' ',
+                            style: self.style.clone(),
+                            z_order: self.z_order,
+                        });
+                    }
+                }
+            }
             if self.framed {
                 let border_style = self.frame_style.as_ref().unwrap_or(&self.style);
+                let (fw, fh) = (w + pad_w + 2, rows.len() + pad_h + 2);
+                if let Some(shadow) = &self.shadow {
+                    shadow.draw(ops, frame_x, frame_y, fw as u16, fh as u16, self.z_order);
+                }
                 draw_frame(
                     ops,
                     frame_x,
                     frame_y,
-                    w + 2,
-                    rows.len() + 2,
+                    fw,
+                    fh,
+                    self.border,
                     border_style,
                     self.z_order,
                 );
@@ -279,37 +554,70 @@ impl Resolve for Label {
             // No wrapping (auto width) — emit chars directly, no fill. Horizontal
             // alignment has no box to act in here, but vertical alignment still
             // does when a height is set: offset the rows by the top padding.
-            let lines: Vec<&str> = self.text.split('\n').collect();
+            let lines: Vec<&str> = text.split('\n').collect();
             let visible = if h > 0 { lines.len().min(h) } else { lines.len() };
             let pad_top = if h > 0 { self.valign.top_pad(h, visible) } else { 0 };
             let mut max_len: usize = 0;
+            let mut line_start = 0;
             for (row, line) in lines.iter().take(visible).enumerate() {
-                let line_len = line.chars().count();
+                let line_len = str_width(line);
                 if line_len > max_len {
                     max_len = line_len;
                 }
-                for (col, ch) in line.chars().enumerate() {
+                let base = line_start;
+                line_start += line.chars().count() + 1;
+                if let Some(k) = caret.and_then(|c| c.checked_sub(base)).filter(|&k| base + k < line_start) {
+                    caret_at = Some((pad_top + row, line.chars().take(k).map(char_width).sum()));
+                }
+                if hidden(line) {
+                    continue;
+                }
+                for (k, (col, ch)) in char_columns(line).enumerate() {
                     ops.push(DrawOp {
                         x: draw_x + col as u16,
                         y: draw_y + (pad_top + row) as u16,
                         ch,
-                        style: self.style.clone(),
+                        style: style_of(Some(base + k)),
                         z_order: self.z_order,
                     });
                 }
             }
             if self.framed {
                 let border_style = self.frame_style.as_ref().unwrap_or(&self.style);
+                let (fw, fh) = (max_len + pad_w + 2, (if h > 0 { h } else { visible }) + pad_h + 2);
+                if let Some(shadow) = &self.shadow {
+                    shadow.draw(ops, frame_x, frame_y, fw as u16, fh as u16, self.z_order);
+                }
                 draw_frame(
                     ops,
                     frame_x,
                     frame_y,
-                    max_len + 2,
-                    (if h > 0 { h } else { visible }) + 2,
+                    fw,
+                    fh,
+                    self.border,
                     border_style,
                     self.z_order,
                 );
             }
         }
+        // The caret inverts the cell it sits on, drawn or blank.
+        if let Some((r, c)) = caret_at {
+            let (x, y) = (draw_x + c as u16, draw_y + r as u16);
+            match ops[text_ops..].iter_mut().find(|op| op.x == x && op.y == y) {
+                Some(op) => op.style = caret_block_style(&op.style),
+                None => ops.push(DrawOp { x, y, ch: ' ', style: caret_block_style(&self.style), z_order: self.z_order }),
+            }
+        }
     }
 }
+
+impl Resolve for Label {
+    fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+        self.draw(ctx, None, ops);
+    }
+}
+
+/// `(row, column)` of the glyph showing char `i` of the text, if one does.
+fn glyph_cell(rows: &[Vec<Glyph>], i: usize) -> Option<(usize, usize)> {
+    rows.iter().enumerate().find_map(|(r, row)| row.iter().position(|g| g.1 == Some(i)).map(|c| (r, c)))
+}
@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::types::{DrawOp, Style};
+use crate::types::{char_width, str_width, DrawOp, Style, WIDE_CONTINUATION};
 
 use super::super::source::{Coordinate, FrameRange, Position, deserialize_coord_compat};
+use super::motion::{Entrance, Exit};
 use super::{Resolve, ResolveCtx};
 
 fn default_list_width() -> Coordinate {
@@ -13,11 +14,11 @@ fn default_list_height() -> Coordinate {
     Coordinate::Fixed(0.0)
 }
 
-fn default_bullet() -> String {
+pub(crate) fn default_bullet() -> String {
     "-".to_string()
 }
 
-fn default_spacing() -> usize {
+pub(crate) fn default_spacing() -> usize {
     1
 }
 
@@ -54,6 +55,12 @@ pub struct List {
     pub frames: FrameRange,
     #[serde(default)]
     pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
 }
 
 impl List {
@@ -93,13 +100,20 @@ impl Resolve for List {
                 }
             }
             let marker = self.marker(i);
-            let indent = marker.chars().count();
+            let indent = str_width(&marker);
             let full = format!("{marker}{item}");
             if w > 0 {
                 let indexed = super::wrap::wrap_line_indexed(0, &full, w, indent);
                 rows.extend(super::wrap::indexed_to_chars(&full, indexed));
             } else {
-                rows.push(full.chars().collect());
+                let mut row = Vec::new();
+                for ch in full.chars() {
+                    row.push(ch);
+                    if char_width(ch) == 2 {
+                        row.push(WIDE_CONTINUATION);
+                    }
+                }
+                rows.push(row);
             }
         }
 
@@ -117,6 +131,9 @@ impl Resolve for List {
             let emit_w = if has_bg && w > 0 { w } else { row_chars.len() };
             for col in 0..emit_w {
                 let ch = row_chars.get(col).copied().unwrap_or(' ');
+                if ch == WIDE_CONTINUATION {
+                    continue;
+                }
                 ops.push(DrawOp {
                     x: base_x + col as u16,
                     y: base_y + r as u16,
@@ -39,6 +39,8 @@ pub struct Loop {
     /// restarting (`5,6,7,8,5,6,…`). On by default.
     #[serde(default = "default_true")]
     pub bounce: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
 }
 
 impl Loop {
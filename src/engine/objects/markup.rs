@@ -0,0 +1,113 @@
+//! Inline markup in label text.
+//!
+//! `**bold**`, `__underline__`, `[colour]…[/colour]` (a colour name or
+//! `#rrggbb`, nestable) and `[link=url]…[/link]` (a hyperlink) style part of a
+//! label without splitting it into several. A marker only counts when it is closed later in the text, so a
+//! stray `**` or `[note]` stays literal; `\*`, `\_`, `\[` and `\\` write the
+//! character itself. [`parse`] strips the markup, leaving the plain text the
+//! label lays out and the [`Marks`] of each of its chars.
+
+use crate::types::{Color, NamedColor, Style};
+
+/// The styling markup adds to one char, over the label's own style.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Marks {
+    pub bold: bool,
+    pub underline: bool,
+    pub fg: Option<Color>,
+    pub link: Option<String>,
+}
+
+impl Marks {
+    /// `base` with these marks applied.
+    pub fn apply(&self, base: &Style) -> Style {
+        Style {
+            fg: self.fg.clone().or_else(|| base.fg.clone()),
+            bold: base.bold || self.bold,
+            underline: base.underline || self.underline,
+            link: self.link.clone().or_else(|| base.link.clone()),
+            ..base.clone()
+        }
+    }
+}
+
+/// Strip the markup from `text`: its plain text and one [`Marks`] per char of
+/// it (newlines included).
+pub(crate) fn parse(text: &str) -> (String, Vec<Marks>) {
+    let chars: Vec<char> = text.chars().collect();
+    let at = |i: usize, s: &str| s.chars().enumerate().all(|(k, c)| chars.get(i + k) == Some(&c));
+    let later = |i: usize, s: &str| (i..chars.len()).any(|k| at(k, s));
+
+    let mut plain = String::new();
+    let mut marks = Vec::new();
+    let mut cur = Marks::default();
+    // Open colour spans, innermost last: (tag, colour).
+    let mut colours: Vec<(String, Color)> = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\\' && matches!(chars.get(i + 1), Some('*' | '_' | '[' | '\\')) {
+            plain.push(chars[i + 1]);
+            marks.push(Marks { fg: colours.last().map(|(_, c)| c.clone()), ..cur.clone() });
+            i += 2;
+            continue;
+        }
+        if let Some((marker, on)) = [("**", &mut cur.bold), ("__", &mut cur.underline)]
+            .into_iter()
+            .find(|(m, _)| at(i, m))
+        {
+            if *on || later(i + 2, marker) {
+                *on = !*on;
+                i += 2;
+                continue;
+            }
+        }
+        if c == '[' {
+            if let Some(len) = chars[i + 1..].iter().position(|&c| c == ']') {
+                let tag: String = chars[i + 1..i + 1 + len].iter().collect();
+                let end = i + len + 2;
+                if let Some(url) = tag.strip_prefix("link=") {
+                    if cur.link.is_none() && !url.is_empty() && later(end, "[/link]") {
+                        cur.link = Some(url.to_string());
+                        i = end;
+                        continue;
+                    }
+                } else if tag == "/link" {
+                    if cur.link.is_some() {
+                        cur.link = None;
+                        i = end;
+                        continue;
+                    }
+                } else if let Some(name) = tag.strip_prefix('/') {
+                    if colours.last().is_some_and(|(open, _)| open == name) {
+                        colours.pop();
+                        i = end;
+                        continue;
+                    }
+                } else if let Some(colour) = parse_colour(&tag) {
+                    if later(end, &format!("[/{tag}]")) {
+                        colours.push((tag, colour));
+                        i = end;
+                        continue;
+                    }
+                }
+            }
+        }
+        plain.push(c);
+        marks.push(Marks { fg: colours.last().map(|(_, c)| c.clone()), ..cur.clone() });
+        i += 1;
+    }
+    (plain, marks)
+}
+
+/// A colour tag: a colour name or `#rrggbb`.
+fn parse_colour(tag: &str) -> Option<Color> {
+    if let Some(hex) = tag.strip_prefix('#') {
+        let channel = |k: usize| hex.get(k..k + 2).and_then(|h| u8::from_str_radix(h, 16).ok());
+        return match (hex.len(), channel(0), channel(2), channel(4)) {
+            (6, Some(r), Some(g), Some(b)) => Some(Color::Rgb { r, g, b }),
+            _ => None,
+        };
+    }
+    serde_json::from_value::<NamedColor>(serde_json::Value::String(tag.to_string())).ok().map(Color::Named)
+}
@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{char_width, DrawOp, Style};
+
+use super::super::source::{FrameRange, Position};
+use super::motion::{Entrance, Exit};
+use super::{Resolve, ResolveCtx};
+
+pub(crate) fn default_marquee_speed() -> f64 {
+    1.0
+}
+
+pub(crate) fn default_marquee_gap() -> u16 {
+    4
+}
+
+/// One line of text scrolling sideways through a window `width` cells wide —
+/// a ticker, or a URL too long for the slide.
+///
+/// The text moves `speed` cells left per frame (negative scrolls right),
+/// counted from the start of its range, and repeats after `gap` blank cells,
+/// so it loops for as long as the object is shown. `width` 0 runs to the
+/// canvas edge. Blanks paint only when the style sets a `bg`, which then
+/// fills the whole window like a ticker tape.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Marquee {
+    pub text: String,
+    pub position: Position,
+    /// Width of the window; 0 runs to the canvas edge.
+    #[serde(default)]
+    pub width: u16,
+    /// Cells moved per frame; fractions move every few frames.
+    #[serde(default = "default_marquee_speed")]
+    pub speed: f64,
+    /// Blank cells between one pass of the text and the next.
+    #[serde(default = "default_marquee_gap")]
+    pub gap: u16,
+    #[serde(default)]
+    pub style: Style,
+    pub frames: FrameRange,
+    #[serde(default)]
+    pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
+}
+
+impl Marquee {
+    /// One pass of the text and its gap, a cell per entry: a character, `None`
+    /// for a blank, and `Some('\0')` for the second cell of a wide character.
+    fn tape(&self) -> Vec<Option<char>> {
+        let mut cells = Vec::new();
+        for ch in self.text.chars() {
+            match char_width(ch) {
+                0 => {}
+                1 => cells.push(Some(ch).filter(|&c| c != ' ')),
+                _ => cells.extend([Some(ch), Some('\0')]),
+            }
+        }
+        cells.extend(std::iter::repeat_n(None, self.gap as usize));
+        cells
+    }
+
+    /// The window's cells `t` frames into the range, `width` wide.
+    pub fn window(&self, t: usize, width: u16) -> Vec<Option<char>> {
+        let tape = self.tape();
+        if tape.is_empty() {
+            return vec![None; width as usize];
+        }
+        let len = tape.len() as i64;
+        // Reduced onto the tape first: a huge `speed` saturates the cast, and
+        // adding the column must not overflow.
+        let offset = ((t as f64 * self.speed).floor() as i64).rem_euclid(len);
+        let mut cells: Vec<Option<char>> =
+            (0..i64::from(width)).map(|c| tape[(offset + c).rem_euclid(len) as usize]).collect();
+        // A wide character cut in half at either edge is left out.
+        if cells.first() == Some(&Some('\0')) {
+            cells[0] = None;
+        }
+        if let Some(last) = cells.last_mut() {
+            if last.is_some_and(|c| char_width(c) > 1) {
+                *last = None;
+            }
+        }
+        cells
+    }
+}
+
+impl Resolve for Marquee {
+    fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+        let frame = ctx.frame;
+        if !self.frames.contains(frame) {
+            return;
+        }
+        let x = self.position.x.evaluate(frame, ctx.anims);
+        let y = self.position.y.evaluate(frame, ctx.anims);
+        let width = if self.width > 0 { self.width } else { ctx.canvas_width.saturating_sub(x) };
+        for (c, cell) in self.window(frame - self.frames.start, width).into_iter().enumerate() {
+            let ch = match cell {
+                Some('\0') => continue,
+                Some(ch) => ch,
+                None if self.style.bg.is_some() => ' ',
+                None => continue,
+            };
+            ops.push(DrawOp { x: x + c as u16, y, ch, style: self.style.clone(), z_order: self.z_order });
+        }
+    }
+}
@@ -14,7 +14,8 @@
 //! 2. **`src/engine/objects/mod.rs`** (this file) — add `mod <new>;`, a
 //!    `pub use <new>::<New>;`, and an arm to `impl Resolve for SceneObject`.
 //! 3. **`src/engine/source.rs`** — add the `SceneObject` variant, extend the
-//!    `pub use super::objects::{…}` re-export, and (only if the object emits a
+//!    `pub use super::objects::{…}` re-export, arms in `SceneObject::kind()`
+//!    and `SceneObject::coordinates()`, and (only if the object emits a
 //!    play-time sidecar, like `Command`'s `command_regions()` or `Loop`'s
 //!    `loop_regions()`) collect it there.
 //! 4. **`src/editor/properties.rs`** — `impl Editable for <New>`, plus an arm
@@ -25,59 +26,138 @@
 //! 7. **`src/editor/input.rs`** — only if the type needs special-case editing
 //!    behaviour (e.g. the `Group`/`Table`/`Art` `matches!` checks). Plain
 //!    types that edit through the `Editable` trait need nothing here.
+//! 8. **`src/builder.rs`** — optional: a constructor method on `Presentation`
+//!    and arms in `position_mut`/`style_mut`/`z_order_mut` so the shared
+//!    modifiers reach the new type.
 //!
 //! `panel.rs`, `menubar.rs`, and `preview.rs` are driven by `OBJECT_TYPES` and
 //! the generic `Editable` dispatch, so they usually need no changes.
 
 pub mod font;
 mod animation;
+mod ansi_art;
 mod arrow;
 mod autoadvance;
+mod background_effect;
 mod circle;
 mod art;
+mod cast;
+mod clock;
 mod command;
+mod connector;
+mod countdown;
+mod diff;
+mod file_tree;
+mod footer;
+mod gantt;
+mod gauge;
 mod group;
 mod header;
+mod heatmap;
 mod hline;
+mod include;
+mod instance;
+mod keycap;
 mod label;
 mod list;
 mod looping;
+mod marquee;
+mod markup;
 mod morph;
+mod pie_chart;
+pub mod motion;
 mod rect;
+mod scatter_plot;
+mod spinner;
 pub mod table;
+mod toc;
+mod watermark;
 mod wrap;
 
 pub use animation::Animation;
+pub use ansi_art::AnsiArt;
 pub use arrow::Arrow;
 pub use autoadvance::AutoAdvance;
+pub use background_effect::{BackgroundEffect, Effect};
 pub use circle::Circle;
 pub use art::Art;
+pub use cast::Cast;
+pub use clock::Clock;
 pub use command::Command;
+pub use connector::Connector;
+pub use countdown::Countdown;
+pub use diff::{Diff, DiffLine, DiffLineKind};
+pub use file_tree::{FileTree, TreeEntry, TreeNode};
+pub use footer::Footer;
+pub use gantt::{Gantt, GanttTask};
+pub use gauge::{Gauge, Threshold};
 pub use group::Group;
 pub use header::Header;
+pub use heatmap::Heatmap;
 pub use hline::HLine;
-pub use label::{Label, TextAlign, VerticalAlign};
+pub use include::Include;
+pub use instance::{Component, Instance};
+pub use keycap::{parse_keys, Keycap};
+pub use label::{Label, Padding, TextAlign, VerticalAlign};
 pub use list::List;
 pub use looping::Loop;
+pub use marquee::Marquee;
 pub use morph::{Morph, MorphMode};
-pub use rect::Rect;
+pub use pie_chart::{PieChart, PieSegment, PIE_PALETTE};
+pub use rect::{BorderStyle, BoxChars, Rect, Shadow, SHADOW_CHAR};
+pub use scatter_plot::ScatterPlot;
+pub use spinner::Spinner;
 pub use table::Table;
+pub use toc::Toc;
+pub use watermark::Watermark;
+
+// Serde field defaults, shared with `crate::builder` so code-built objects get
+// exactly the values an omitted JSON field would.
+pub(crate) use animation::default_delay_ms as default_animation_delay_ms;
+pub(crate) use ansi_art::default_ansi_width;
+pub(crate) use background_effect::{default_effect_density, default_effect_z};
+pub(crate) use circle::{default_diameter, default_fill_char};
+pub(crate) use countdown::default_step_secs;
+pub(crate) use diff::default_diff_context;
+pub(crate) use gantt::default_gantt_width;
+pub(crate) use gauge::{default_gauge_max, default_gauge_size};
+pub(crate) use header::default_header_char;
+pub(crate) use heatmap::{default_heatmap_cell_width, default_heatmap_high, default_heatmap_low};
+pub(crate) use hline::default_hline_char;
+pub(crate) use keycap::{default_keycap_border, default_keycap_separator};
+pub(crate) use list::{default_bullet, default_spacing};
+pub(crate) use marquee::{default_marquee_gap, default_marquee_speed};
+pub(crate) use pie_chart::default_pie_char;
+pub(crate) use scatter_plot::{default_plot_height, default_plot_width, default_ticks};
+pub(crate) use spinner::default_spinner_glyphs;
+pub(crate) use watermark::default_watermark_z;
 
 use crate::types::DrawOp;
 
 use super::source::{AnimSpans, SceneObject};
 
+/// Braille dot bits by `[row][column]` within a 2×4 cell, for the objects
+/// that plot in braille dots (`Gauge`, `ScatterPlot`).
+pub(crate) const BRAILLE_BITS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
 /// Everything an object needs to resolve itself for one frame.
 ///
-/// `frame` is the frame being rendered; `canvas_width` is the width (in cells)
-/// of the output frame (most objects ignore it — `Header` uses it to word-wrap
-/// its large glyphs); `anims` maps each animation id to its span, so an
+/// `frame` is the frame being rendered; `canvas_width` × `canvas_height` is the
+/// size (in cells) of the output frame (most objects ignore it — `Header` uses
+/// the width to word-wrap its large glyphs, entrance/exit presets slide to the
+/// edges); `frame_count` is the deck's length, for a `Footer`'s page
+/// numbers; `anims` maps each animation id to its span, so an
 /// animated `Coordinate` can look up its timing (the span lives on the
-/// `Animation` object, not on the coordinate).
+/// `Animation` object, not on the coordinate); `sections` is the deck's
+/// [`section_starts`](super::source::SourcePresentation::section_starts), for
+/// a `Toc` to list.
 pub struct ResolveCtx<'a> {
     pub frame: usize,
     pub canvas_width: u16,
+    pub canvas_height: u16,
+    pub frame_count: usize,
     pub anims: &'a AnimSpans,
+    pub sections: &'a [(usize, String)],
 }
 
 /// Resolve an object for a given frame into concrete `DrawOp`s.
@@ -87,6 +167,7 @@ pub trait Resolve {
 
 impl Resolve for SceneObject {
     fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+        let before = ops.len();
         match self {
             SceneObject::Label(o) => o.resolve(ctx, ops),
             SceneObject::HLine(o) => o.resolve(ctx, ops),
@@ -96,6 +177,25 @@ impl Resolve for SceneObject {
             SceneObject::Arrow(o) => o.resolve(ctx, ops),
             SceneObject::Table(o) => o.resolve(ctx, ops),
             SceneObject::Art(o) => o.resolve(ctx, ops),
+            SceneObject::AnsiArt(o) => o.resolve(ctx, ops),
+            SceneObject::Cast(o) => o.resolve(ctx, ops),
+            SceneObject::Countdown(o) => o.resolve(ctx, ops),
+            SceneObject::Toc(o) => o.resolve(ctx, ops),
+            SceneObject::Footer(o) => o.resolve(ctx, ops),
+            SceneObject::Gauge(o) => o.resolve(ctx, ops),
+            SceneObject::Heatmap(o) => o.resolve(ctx, ops),
+            SceneObject::Diff(o) => o.resolve(ctx, ops),
+            SceneObject::FileTree(o) => o.resolve(ctx, ops),
+            SceneObject::Gantt(o) => o.resolve(ctx, ops),
+            SceneObject::PieChart(o) => o.resolve(ctx, ops),
+            SceneObject::ScatterPlot(o) => o.resolve(ctx, ops),
+            SceneObject::Marquee(o) => o.resolve(ctx, ops),
+            SceneObject::Spinner(o) => o.resolve(ctx, ops),
+            SceneObject::Connector(o) => o.resolve(ctx, ops),
+            SceneObject::Keycap(o) => o.resolve(ctx, ops),
+            SceneObject::Watermark(o) => o.resolve(ctx, ops),
+            SceneObject::BackgroundEffect(o) => o.resolve(ctx, ops),
+            SceneObject::Clock(o) => o.resolve(ctx, ops),
             SceneObject::Command(o) => o.resolve(ctx, ops),
             SceneObject::List(o) => o.resolve(ctx, ops),
             SceneObject::Loop(o) => o.resolve(ctx, ops),
@@ -103,6 +203,16 @@ impl Resolve for SceneObject {
             SceneObject::Animation(o) => o.resolve(ctx, ops),
             SceneObject::AutoAdvance(o) => o.resolve(ctx, ops),
             SceneObject::Circle(o) => o.resolve(ctx, ops),
+            SceneObject::Instance(o) => o.resolve(ctx, ops),
+            SceneObject::Include(o) => o.resolve(ctx, ops),
+        }
+        let (enter, exit) = (self.enter(), self.exit());
+        if enter.is_some() || exit.is_some() {
+            if let Some(range) = self.declared_frame_range() {
+                let mut own = ops.split_off(before);
+                motion::apply(enter, exit, &range, ctx, &mut own);
+                ops.append(&mut own);
+            }
         }
     }
 }
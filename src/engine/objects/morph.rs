@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use crate::types::{DrawOp, Style};
 
 use super::super::source::{FrameRange, Position};
+use super::motion::{Entrance, Exit};
 use super::{Resolve, ResolveCtx};
 
 /// How a [`Morph`] transitions each cell from the `from` art to the `to` art as
@@ -114,6 +115,12 @@ pub struct Morph {
     pub frames: FrameRange,
     #[serde(default)]
     pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
 }
 
 impl Morph {
@@ -185,7 +192,7 @@ mod tests {
     fn render(m: &Morph, frame: usize) -> Vec<DrawOp> {
         let anims = AnimSpans::default();
         let mut ops = Vec::new();
-        m.resolve(&ResolveCtx { frame, canvas_width: 80, anims: &anims }, &mut ops);
+        m.resolve(&ResolveCtx { frame, canvas_width: 80, canvas_height: 24, frame_count: 1, anims: &anims, sections: &[] }, &mut ops);
         ops
     }
 
@@ -199,6 +206,9 @@ mod tests {
             style: Style::default(),
             frames: FrameRange { start, end },
             z_order: 0,
+            layer: None,
+            enter: None,
+            exit: None,
         }
     }
 
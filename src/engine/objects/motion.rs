@@ -0,0 +1,203 @@
+//! Entrance and exit presets: declarative motion at the start and end of an
+//! object's frame range (`"enter": "slide_from_left"`,
+//! `"exit": {"preset": "slide_down", "frames": 6}`).
+//!
+//! A preset saves authoring animated coordinates (and the `Animation` owning
+//! their span) for the common "fly in, sit, fly out" case. The object resolves
+//! at rest as usual; then, for the first `frames` frames of its range (entrance)
+//! or the last `frames` (exit), whatever it drew is offset towards the named
+//! canvas edge. Ops pushed off the canvas are dropped, so the motion never
+//! trips the out-of-bounds warning.
+//!
+//! Every motion frame shows part of the object: an entrance of `n` frames
+//! starts `n/(n+1)` of the way out and reaches rest on the frame after it; an
+//! exit mirrors that, ending `n/(n+1)` out on the object's last frame. Where a
+//! short range makes the two overlap, the entrance wins.
+
+use serde::{Deserialize, Serialize};
+
+use super::ResolveCtx;
+use crate::engine::source::FrameRange;
+use crate::types::DrawOp;
+
+/// Frames a preset takes when given by name alone.
+pub const DEFAULT_MOTION_FRAMES: usize = 4;
+
+/// Where an entrance comes in from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnterPreset {
+    SlideFromLeft,
+    SlideFromRight,
+    SlideFromTop,
+    SlideFromBottom,
+}
+
+/// Where an exit leaves to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitPreset {
+    SlideLeft,
+    SlideRight,
+    SlideUp,
+    SlideDown,
+}
+
+/// A canvas edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl EnterPreset {
+    fn edge(self) -> Edge {
+        match self {
+            EnterPreset::SlideFromLeft => Edge::Left,
+            EnterPreset::SlideFromRight => Edge::Right,
+            EnterPreset::SlideFromTop => Edge::Top,
+            EnterPreset::SlideFromBottom => Edge::Bottom,
+        }
+    }
+}
+
+impl ExitPreset {
+    fn edge(self) -> Edge {
+        match self {
+            ExitPreset::SlideLeft => Edge::Left,
+            ExitPreset::SlideRight => Edge::Right,
+            ExitPreset::SlideUp => Edge::Top,
+            ExitPreset::SlideDown => Edge::Bottom,
+        }
+    }
+}
+
+/// A preset and how many frames it runs for. Serialized as the bare preset
+/// name when `frames` is [`DEFAULT_MOTION_FRAMES`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    from = "MotionRepr<P>",
+    into = "MotionRepr<P>",
+    bound(serialize = "P: Serialize + Clone", deserialize = "P: Deserialize<'de>")
+)]
+pub struct Motion<P> {
+    pub preset: P,
+    pub frames: usize,
+}
+
+/// `enter` on an object.
+pub type Entrance = Motion<EnterPreset>;
+/// `exit` on an object.
+pub type Exit = Motion<ExitPreset>;
+
+impl<P> Motion<P> {
+    pub fn new(preset: P, frames: usize) -> Self {
+        Motion { preset, frames }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum MotionRepr<P> {
+    Name(P),
+    Full {
+        preset: P,
+        #[serde(default = "default_motion_frames")]
+        frames: usize,
+    },
+}
+
+fn default_motion_frames() -> usize {
+    DEFAULT_MOTION_FRAMES
+}
+
+impl<P> From<MotionRepr<P>> for Motion<P> {
+    fn from(r: MotionRepr<P>) -> Self {
+        match r {
+            MotionRepr::Name(preset) => Motion { preset, frames: DEFAULT_MOTION_FRAMES },
+            MotionRepr::Full { preset, frames } => Motion { preset, frames },
+        }
+    }
+}
+
+impl<P> From<Motion<P>> for MotionRepr<P> {
+    fn from(m: Motion<P>) -> Self {
+        if m.frames == DEFAULT_MOTION_FRAMES {
+            MotionRepr::Name(m.preset)
+        } else {
+            MotionRepr::Full { preset: m.preset, frames: m.frames }
+        }
+    }
+}
+
+/// Offset `ops` — everything one object drew on `ctx.frame` — for its
+/// entrance or exit within `range`, dropping ops that leave the canvas.
+pub(crate) fn apply(
+    enter: Option<&Entrance>,
+    exit: Option<&Exit>,
+    range: &FrameRange,
+    ctx: &ResolveCtx,
+    ops: &mut Vec<DrawOp>,
+) {
+    let Some((edge, fraction)) = progress(enter, exit, range, ctx.frame) else {
+        return;
+    };
+    let (Some(min_x), Some(max_x)) =
+        (ops.iter().map(|o| o.x).min(), ops.iter().map(|o| o.x).max())
+    else {
+        return;
+    };
+    let (min_y, max_y) = (
+        ops.iter().map(|o| o.y).min().unwrap_or(0),
+        ops.iter().map(|o| o.y).max().unwrap_or(0),
+    );
+    let (w, h) = (ctx.canvas_width as i32, ctx.canvas_height as i32);
+    // How far the object travels to be entirely past `edge`.
+    let distance = match edge {
+        Edge::Left => max_x as i32 + 1,
+        Edge::Right => w - min_x as i32,
+        Edge::Top => max_y as i32 + 1,
+        Edge::Bottom => h - min_y as i32,
+    };
+    let step = (distance.max(0) as f64 * fraction).round() as i32;
+    let (dx, dy) = match edge {
+        Edge::Left => (-step, 0),
+        Edge::Right => (step, 0),
+        Edge::Top => (0, -step),
+        Edge::Bottom => (0, step),
+    };
+    ops.retain_mut(|op| {
+        let (x, y) = (op.x as i32 + dx, op.y as i32 + dy);
+        if x < 0 || y < 0 || x >= w || y >= h {
+            return false;
+        }
+        (op.x, op.y) = (x as u16, y as u16);
+        true
+    });
+}
+
+/// The edge the object is moving from/to on `frame` and how far out it is
+/// (0 = at rest, towards 1 = gone), or `None` outside both motions.
+fn progress(
+    enter: Option<&Entrance>,
+    exit: Option<&Exit>,
+    range: &FrameRange,
+    frame: usize,
+) -> Option<(Edge, f64)> {
+    if !range.contains(frame) {
+        return None;
+    }
+    let since_start = frame - range.start;
+    if let Some(e) = enter.filter(|e| since_start < e.frames) {
+        let n = e.frames as f64;
+        return Some((e.preset.edge(), (n - since_start as f64) / (n + 1.0)));
+    }
+    let to_end = range.end - 1 - frame;
+    if let Some(x) = exit.filter(|x| to_end < x.frames) {
+        let n = x.frames as f64;
+        return Some((x.preset.edge(), (n - to_end as f64) / (n + 1.0)));
+    }
+    None
+}
@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{char_width, Color, DrawOp, NamedColor, Style};
+
+use super::super::source::{FrameRange, Position};
+use super::circle::{default_diameter, Circle};
+use super::motion::{Entrance, Exit};
+use super::{Resolve, ResolveCtx};
+
+fn default_true() -> bool {
+    true
+}
+
+pub(crate) fn default_pie_char() -> char {
+    '█'
+}
+
+/// Colours for segments without one of their own, in turn.
+pub const PIE_PALETTE: [NamedColor; 6] = [
+    NamedColor::Cyan,
+    NamedColor::Magenta,
+    NamedColor::Yellow,
+    NamedColor::Green,
+    NamedColor::Blue,
+    NamedColor::Red,
+];
+
+/// One slice of a [`PieChart`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PieSegment {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub label: String,
+    pub value: f64,
+    /// The slice's colour; the next [`PIE_PALETTE`] colour when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
+}
+
+/// A pie (or, with a `hole`, donut) chart: each segment a slice sized by its
+/// share of the total, clockwise from twelve o'clock, with a legend of
+/// labels and percentages to its right.
+///
+/// The disc is `diameter` rows tall (at most the canvas' height) and, like a
+/// [`Circle`], twice as many columns wide so it looks round. With `sweep`, the
+/// slices sweep in clockwise across the object's frames, the whole pie
+/// showing on the last.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PieChart {
+    pub position: Position,
+    pub segments: Vec<PieSegment>,
+    /// Height in rows.
+    #[serde(default = "default_diameter")]
+    pub diameter: u16,
+    /// The hole's share of the radius, 0 (a pie) to below 1 (a thin ring).
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub hole: f64,
+    /// Character the slices are filled with.
+    #[serde(default = "default_pie_char")]
+    pub ch: char,
+    /// List each segment's label and percentage beside the chart.
+    #[serde(default = "default_true")]
+    pub legend: bool,
+    /// Draw the slices in clockwise over the object's frames.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sweep: bool,
+    #[serde(default)]
+    pub style: Style,
+    pub frames: FrameRange,
+    #[serde(default)]
+    pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
+}
+
+fn is_zero(v: &f64) -> bool {
+    *v == 0.0
+}
+
+impl PieChart {
+    /// Each segment's share of the total (negative values count as 0).
+    pub fn shares(&self) -> Vec<f64> {
+        let total: f64 = self.segments.iter().map(|s| s.value.max(0.0)).sum();
+        self.segments.iter().map(|s| if total > 0.0 { s.value.max(0.0) / total } else { 0.0 }).collect()
+    }
+
+    /// The colour segment `i` is drawn in.
+    pub fn color(&self, i: usize) -> Color {
+        self.segments[i].color.clone().unwrap_or_else(|| Color::Named(PIE_PALETTE[i % PIE_PALETTE.len()].clone()))
+    }
+
+    /// The legend lines: the fill character, the label and the percentage.
+    pub fn legend_lines(&self) -> Vec<String> {
+        self.shares()
+            .iter()
+            .zip(&self.segments)
+            .map(|(share, s)| match s.label.as_str() {
+                "" => format!("{} {:.0}%", self.ch, share * 100.0),
+                label => format!("{} {label} {:.0}%", self.ch, share * 100.0),
+            })
+            .collect()
+    }
+
+    /// The segment covering the point `turn` (0–1) of the way clockwise
+    /// round from twelve o'clock.
+    fn segment_at(&self, turn: f64) -> Option<usize> {
+        let mut end = 0.0;
+        for (i, share) in self.shares().into_iter().enumerate() {
+            end += share;
+            if turn < end {
+                return Some(i);
+            }
+        }
+        None
+    }
+}
+
+impl Resolve for PieChart {
+    fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+        let frame = ctx.frame;
+        if !self.frames.contains(frame) {
+            return;
+        }
+        let x0 = self.position.x.evaluate(frame, ctx.anims);
+        let y0 = self.position.y.evaluate(frame, ctx.anims);
+        // No taller than the canvas: the disc's cost grows with its area.
+        let rows = self.diameter.clamp(1, ctx.canvas_height.max(1));
+        let cols = Circle::columns(rows);
+        let shown = if self.sweep {
+            let span = self.frames.end.saturating_sub(self.frames.start).max(1);
+            (frame - self.frames.start + 1) as f64 / span as f64
+        } else {
+            1.0
+        };
+
+        // The same ellipse as a `Circle`, measured in radii so the disc is round.
+        let (cx, cy) = ((cols as f64 - 1.0) / 2.0, (rows as f64 - 1.0) / 2.0);
+        let (rx, ry) = (cols as f64 / 2.0, rows as f64 / 2.0);
+        for r in 0..rows {
+            for c in 0..cols {
+                let dx = (c as f64 - cx) / rx;
+                let dy = (r as f64 - cy) / ry;
+                let dist = dx.hypot(dy);
+                if dist > 1.0 || dist < self.hole {
+                    continue;
+                }
+                let turn = dx.atan2(-dy).rem_euclid(std::f64::consts::TAU) / std::f64::consts::TAU;
+                if turn >= shown {
+                    continue;
+                }
+                if let Some(i) = self.segment_at(turn) {
+                    let style = Style { fg: Some(self.color(i)), ..self.style.clone() };
+                    let (x, y) = (x0.saturating_add(c), y0.saturating_add(r));
+                    ops.push(DrawOp { x, y, ch: self.ch, style, z_order: self.z_order });
+                }
+            }
+        }
+
+        if self.legend {
+            let top = y0.saturating_add(rows.saturating_sub(self.segments.len() as u16) / 2);
+            for (i, line) in self.legend_lines().iter().enumerate() {
+                let mut col = x0.saturating_add(cols).saturating_add(2);
+                for (k, ch) in line.chars().enumerate() {
+                    // The swatch takes the segment's colour, the text the chart's style.
+                    let fg = if k == 0 { Some(self.color(i)) } else { self.style.fg.clone() };
+                    let style = Style { fg, ..self.style.clone() };
+                    ops.push(DrawOp { x: col, y: top.saturating_add(i as u16), ch, style, z_order: self.z_order });
+                    col = col.saturating_add(char_width(ch) as u16);
+                }
+            }
+        }
+    }
+}
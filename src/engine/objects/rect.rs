@@ -1,10 +1,148 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::types::{DrawOp, Style};
+use crate::types::{char_columns, char_width, DrawOp, Style};
 
 use super::super::source::{Coordinate, FrameRange, Position, deserialize_coord_compat};
+use super::motion::{Entrance, Exit};
 use super::{Resolve, ResolveCtx};
 
+/// The box-drawing character set of a rect's border or a label's frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BorderStyle {
+    #[default]
+    Single,
+    Double,
+    Heavy,
+    /// Single lines with rounded corners.
+    Rounded,
+    /// `+`, `-` and `|`, for terminals without box-drawing glyphs.
+    Ascii,
+    /// Single corners with dashed edges.
+    Dashed,
+}
+
+/// The six characters a border is drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoxChars {
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+    pub horizontal: char,
+    pub vertical: char,
+}
+
+impl BorderStyle {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BorderStyle::Single => "single",
+            BorderStyle::Double => "double",
+            BorderStyle::Heavy => "heavy",
+            BorderStyle::Rounded => "rounded",
+            BorderStyle::Ascii => "ascii",
+            BorderStyle::Dashed => "dashed",
+        }
+    }
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        match s.trim() {
+            "single" => Some(BorderStyle::Single),
+            "double" => Some(BorderStyle::Double),
+            "heavy" => Some(BorderStyle::Heavy),
+            "rounded" => Some(BorderStyle::Rounded),
+            "ascii" => Some(BorderStyle::Ascii),
+            "dashed" => Some(BorderStyle::Dashed),
+            _ => None,
+        }
+    }
+    pub(crate) fn is_default(&self) -> bool {
+        matches!(self, BorderStyle::Single)
+    }
+
+    pub fn chars(self) -> BoxChars {
+        let [top_left, top_right, bottom_left, bottom_right, horizontal, vertical] = match self {
+            BorderStyle::Single => ['┌', '┐', '└', '┘', '─', '│'],
+            BorderStyle::Double => ['╔', '╗', '╚', '╝', '═', '║'],
+            BorderStyle::Heavy => ['┏', '┓', '┗', '┛', '━', '┃'],
+            BorderStyle::Rounded => ['╭', '╮', '╰', '╯', '─', '│'],
+            BorderStyle::Ascii => ['+', '+', '+', '+', '-', '|'],
+            BorderStyle::Dashed => ['┌', '┐', '└', '┘', '┄', '┆'],
+        };
+        BoxChars { top_left, top_right, bottom_left, bottom_right, horizontal, vertical }
+    }
+}
+
+/// A drop shadow behind a box: the box's footprint shifted `dx`/`dy` cells
+/// down-right, drawn where it sticks out from under the box. Written as
+/// `"shadow": true` for the defaults, or as an object to change them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Shadow {
+    #[serde(default = "default_shadow_offset")]
+    pub dx: u16,
+    #[serde(default = "default_shadow_offset")]
+    pub dy: u16,
+    /// Dimmed, in the terminal's own colours, by default.
+    #[serde(default = "default_shadow_style")]
+    pub style: Style,
+}
+
+/// The glyph a shadow is drawn with.
+pub const SHADOW_CHAR: char = '░';
+
+fn default_shadow_offset() -> u16 {
+    1
+}
+
+fn default_shadow_style() -> Style {
+    Style { dim: true, ..Style::default() }
+}
+
+impl Default for Shadow {
+    fn default() -> Self {
+        Shadow { dx: default_shadow_offset(), dy: default_shadow_offset(), style: default_shadow_style() }
+    }
+}
+
+impl Shadow {
+    /// Draw the shadow of the `w`×`h` box at `(x, y)`.
+    pub(crate) fn draw(&self, ops: &mut Vec<DrawOp>, x: u16, y: u16, w: u16, h: u16, z_order: i32) {
+        for j in 0..h {
+            for i in 0..w {
+                let sx = x.saturating_add(self.dx).saturating_add(i);
+                let sy = y.saturating_add(self.dy).saturating_add(j);
+                if sx < x + w && sy < y + h {
+                    continue;
+                }
+                ops.push(DrawOp { x: sx, y: sy, ch: SHADOW_CHAR, style: self.style.clone(), z_order });
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ShadowRepr {
+    On(bool),
+    Full(Shadow),
+}
+
+/// `shadow` as `true`/`false` or a [`Shadow`] object.
+pub(crate) fn deserialize_shadow<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Shadow>, D::Error> {
+    Ok(match Option::<ShadowRepr>::deserialize(d)? {
+        Some(ShadowRepr::On(true)) => Some(Shadow::default()),
+        Some(ShadowRepr::Full(shadow)) => Some(shadow),
+        Some(ShadowRepr::On(false)) | None => None,
+    })
+}
+
+/// A default shadow is written back as `true`.
+pub(crate) fn serialize_shadow<S: Serializer>(shadow: &Option<Shadow>, s: S) -> Result<S::Ok, S::Error> {
+    match shadow {
+        Some(shadow) if *shadow != Shadow::default() => shadow.serialize(s),
+        other => s.serialize_bool(other.is_some()),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Rect {
     pub position: Position,
@@ -18,7 +156,22 @@ pub struct Rect {
     #[serde(default)]
     pub z_order: i32,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "BorderStyle::is_default")]
+    pub border: BorderStyle,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_shadow",
+        serialize_with = "serialize_shadow",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub shadow: Option<Shadow>,
 }
 
 impl Resolve for Rect {
@@ -34,40 +187,46 @@ impl Resolve for Rect {
         let h = self.height.evaluate(frame, ctx.anims);
         let s = &self.style;
         let z = self.z_order;
+        let b = self.border.chars();
+
+        if let Some(shadow) = &self.shadow {
+            shadow.draw(ops, x, y, w, h, z);
+        }
 
         // Top edge
-        ops.push(DrawOp { x, y, ch: '┌', style: s.clone(), z_order: z });
+        ops.push(DrawOp { x, y, ch: b.top_left, style: s.clone(), z_order: z });
         for i in 1..w.saturating_sub(1) {
-            ops.push(DrawOp { x: x + i, y, ch: '─', style: s.clone(), z_order: z });
+            ops.push(DrawOp { x: x + i, y, ch: b.horizontal, style: s.clone(), z_order: z });
         }
         if w > 1 {
-            ops.push(DrawOp { x: x + w - 1, y, ch: '┐', style: s.clone(), z_order: z });
+            ops.push(DrawOp { x: x + w - 1, y, ch: b.top_right, style: s.clone(), z_order: z });
         }
 
         // Side edges
         for j in 1..h.saturating_sub(1) {
-            ops.push(DrawOp { x, y: y + j, ch: '│', style: s.clone(), z_order: z });
+            ops.push(DrawOp { x, y: y + j, ch: b.vertical, style: s.clone(), z_order: z });
             if w > 1 {
-                ops.push(DrawOp { x: x + w - 1, y: y + j, ch: '│', style: s.clone(), z_order: z });
+                ops.push(DrawOp { x: x + w - 1, y: y + j, ch: b.vertical, style: s.clone(), z_order: z });
             }
         }
 
         // Bottom edge
         if h > 1 {
-            ops.push(DrawOp { x, y: y + h - 1, ch: '└', style: s.clone(), z_order: z });
+            ops.push(DrawOp { x, y: y + h - 1, ch: b.bottom_left, style: s.clone(), z_order: z });
             for i in 1..w.saturating_sub(1) {
-                ops.push(DrawOp { x: x + i, y: y + h - 1, ch: '─', style: s.clone(), z_order: z });
+                ops.push(DrawOp { x: x + i, y: y + h - 1, ch: b.horizontal, style: s.clone(), z_order: z });
             }
             if w > 1 {
-                ops.push(DrawOp { x: x + w - 1, y: y + h - 1, ch: '┘', style: s.clone(), z_order: z });
+                ops.push(DrawOp { x: x + w - 1, y: y + h - 1, ch: b.bottom_right, style: s.clone(), z_order: z });
             }
         }
 
         // Title (rendered on top edge, one z-level above the rect)
         if let Some(title) = &self.title {
-            for (i, ch) in title.chars().enumerate() {
+            for (i, ch) in char_columns(title) {
                 let tx = x + 2 + i as u16;
-                if tx < x + w - 1 {
+                // Keep the whole glyph off the right corner.
+                if tx + (char_width(ch) as u16) < x + w {
                     ops.push(DrawOp {
                         x: tx,
                         y,
@@ -0,0 +1,224 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{Color, DrawOp, Style};
+
+use super::super::source::{FrameRange, Position};
+use super::motion::{Entrance, Exit};
+use super::{Resolve, ResolveCtx, BRAILLE_BITS};
+
+pub(crate) fn default_plot_width() -> u16 {
+    40
+}
+
+pub(crate) fn default_plot_height() -> u16 {
+    12
+}
+
+pub(crate) fn default_ticks() -> u16 {
+    3
+}
+
+/// A scatter plot of `(x, y)` points in braille dots — 2×4 to a cell — with
+/// labelled axes, all fitted into a `width` × `height` box.
+///
+/// The axes span `x_min`–`x_max` and `y_min`–`y_max`, by default the points'
+/// own extent. `ticks` values are marked evenly along each axis, ends
+/// included. `y_label` goes on the box's top row and `x_label` centred on its
+/// bottom one. The points take `color` (the style's `fg` when unset); the
+/// axes, ticks and labels take the style.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScatterPlot {
+    pub position: Position,
+    pub points: Vec<[f64; 2]>,
+    /// Outer width, labels included.
+    #[serde(default = "default_plot_width")]
+    pub width: u16,
+    /// Outer height, labels included.
+    #[serde(default = "default_plot_height")]
+    pub height: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x_min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x_max: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y_min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y_max: Option<f64>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub x_label: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub y_label: String,
+    /// Values marked along each axis, ends included (fewer than 2 marks none).
+    #[serde(default = "default_ticks")]
+    pub ticks: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
+    #[serde(default)]
+    pub style: Style,
+    pub frames: FrameRange,
+    #[serde(default)]
+    pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
+}
+
+/// A tick value as written: whole numbers bare, others to two places.
+pub fn format_tick(v: f64) -> String {
+    if v.fract() == 0.0 && v.abs() < 1e15 {
+        format!("{v:.0}")
+    } else {
+        let s = format!("{v:.2}");
+        s.trim_end_matches('0').trim_end_matches('.').to_string()
+    }
+}
+
+/// The `(min, max)` of `values`, each replaced by its override when set, and
+/// widened around a single value so the span is never empty.
+fn span(values: impl Iterator<Item = f64> + Clone, min: Option<f64>, max: Option<f64>) -> (f64, f64) {
+    let lo = min.unwrap_or_else(|| values.clone().fold(f64::INFINITY, f64::min));
+    let hi = max.unwrap_or_else(|| values.fold(f64::NEG_INFINITY, f64::max));
+    match (lo.is_finite(), hi.is_finite()) {
+        (true, true) if hi > lo => (lo, hi),
+        (true, true) => (lo - 1.0, lo + 1.0),
+        _ => (0.0, 1.0),
+    }
+}
+
+/// The plot's layout within its box, in cells from the top-left.
+struct Layout {
+    /// The y axis' column; the y tick labels end just left of it.
+    axis_col: u16,
+    /// The x axis' row; the x tick labels sit just below it.
+    axis_row: u16,
+    /// First row of the plot area.
+    top: u16,
+    /// Plot area size in cells, right of the y axis and above the x axis.
+    cols: u16,
+    rows: u16,
+}
+
+impl ScatterPlot {
+    /// The `(min, max)` of the x axis.
+    pub fn x_range(&self) -> (f64, f64) {
+        span(self.points.iter().map(|p| p[0]), self.x_min, self.x_max)
+    }
+
+    /// The `(min, max)` of the y axis.
+    pub fn y_range(&self) -> (f64, f64) {
+        span(self.points.iter().map(|p| p[1]), self.y_min, self.y_max)
+    }
+
+    /// The values marked along an axis spanning `(lo, hi)`.
+    fn tick_values(&self, (lo, hi): (f64, f64)) -> Vec<f64> {
+        if self.ticks < 2 {
+            return Vec::new();
+        }
+        let n = f64::from(self.ticks - 1);
+        (0..self.ticks).map(|i| lo + (hi - lo) * f64::from(i) / n).collect()
+    }
+
+    fn layout(&self) -> Layout {
+        let y_ticks = self.tick_values(self.y_range());
+        let axis_col = y_ticks.iter().map(|&v| format_tick(v).chars().count()).max().unwrap_or(0) as u16;
+        let top = u16::from(!self.y_label.is_empty());
+        let below = 1 + u16::from(!self.tick_values(self.x_range()).is_empty()) + u16::from(!self.x_label.is_empty());
+        let axis_row = self.height.saturating_sub(below).max(top + 1);
+        Layout {
+            axis_col,
+            axis_row,
+            top,
+            cols: self.width.saturating_sub(axis_col + 1).max(1),
+            rows: axis_row - top,
+        }
+    }
+
+    /// The plot cells holding points, as `(column, row, dot bits)` from the
+    /// plot area's top-left.
+    fn cells(&self, layout: &Layout) -> Vec<(u16, u16, u8)> {
+        let ((x0, x1), (y0, y1)) = (self.x_range(), self.y_range());
+        let (dots_x, dots_y) = (f64::from(layout.cols * 2 - 1), f64::from(layout.rows * 4 - 1));
+        let mut cells: Vec<(u16, u16, u8)> = Vec::new();
+        for &[x, y] in &self.points {
+            if !(x0..=x1).contains(&x) || !(y0..=y1).contains(&y) {
+                continue;
+            }
+            let dx = ((x - x0) / (x1 - x0) * dots_x).round() as u16;
+            let dy = ((y1 - y) / (y1 - y0) * dots_y).round() as u16;
+            let (col, row) = (dx / 2, dy / 4);
+            let bit = BRAILLE_BITS[(dy % 4) as usize][(dx % 2) as usize];
+            match cells.iter_mut().find(|c| c.0 == col && c.1 == row) {
+                Some(cell) => cell.2 |= bit,
+                None => cells.push((col, row, bit)),
+            }
+        }
+        cells
+    }
+}
+
+impl Resolve for ScatterPlot {
+    fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+        let frame = ctx.frame;
+        if !self.frames.contains(frame) {
+            return;
+        }
+        let x = self.position.x.evaluate(frame, ctx.anims);
+        let y = self.position.y.evaluate(frame, ctx.anims);
+        let layout = self.layout();
+        let (w, h) = (self.width, self.height.max(layout.axis_row + 1));
+        let mut put = |col: u16, row: u16, ch: char, style: &Style| {
+            if col < w && row < h {
+                ops.push(DrawOp { x: x + col, y: y + row, ch, style: style.clone(), z_order: self.z_order });
+            }
+        };
+        let text = |col: u16, row: u16, s: &str, put: &mut dyn FnMut(u16, u16, char, &Style)| {
+            for (i, ch) in s.chars().enumerate() {
+                put(col + i as u16, row, ch, &self.style);
+            }
+        };
+
+        // Axes, with the tick marks and their values.
+        let Layout { axis_col, axis_row, top, cols, rows } = layout;
+        for row in top..axis_row {
+            put(axis_col, row, '│', &self.style);
+        }
+        put(axis_col, axis_row, '└', &self.style);
+        for col in axis_col + 1..axis_col + 1 + cols {
+            put(col, axis_row, '─', &self.style);
+        }
+        let y_ticks = self.tick_values(self.y_range());
+        for (i, v) in y_ticks.iter().enumerate() {
+            let row = axis_row - 1 - ((rows - 1) as usize * i / (y_ticks.len() - 1)) as u16;
+            let label = format_tick(*v);
+            put(axis_col, row, '┤', &self.style);
+            text(axis_col - label.chars().count() as u16, row, &label, &mut put);
+        }
+        let x_ticks = self.tick_values(self.x_range());
+        for (i, v) in x_ticks.iter().enumerate() {
+            let col = axis_col + 1 + ((cols - 1) as usize * i / (x_ticks.len() - 1)) as u16;
+            let label = format_tick(*v);
+            put(col, axis_row, '┬', &self.style);
+            // Centred under the mark, but kept inside the box.
+            let len = label.chars().count() as u16;
+            let start = col.saturating_sub(len / 2).min(w.saturating_sub(len));
+            text(start, axis_row + 1, &label, &mut put);
+        }
+        if !self.y_label.is_empty() {
+            text(0, 0, &self.y_label, &mut put);
+        }
+        if !self.x_label.is_empty() {
+            let len = self.x_label.chars().count() as u16;
+            let start = axis_col + 1 + cols.saturating_sub(len) / 2;
+            text(start, h - 1, &self.x_label, &mut put);
+        }
+
+        let dot_style = Style { fg: self.color.clone().or_else(|| self.style.fg.clone()), ..self.style.clone() };
+        for (col, row, bits) in self.cells(&layout) {
+            let ch = char::from_u32(0x2800 + u32::from(bits)).unwrap_or(' ');
+            put(axis_col + 1 + col, top + row, ch, &dot_style);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{char_width, DrawOp, Style};
+
+use super::super::source::{FrameRange, Position};
+use super::motion::{Entrance, Exit};
+use super::{Resolve, ResolveCtx};
+
+pub(crate) fn default_spinner_glyphs() -> String {
+    "⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏".to_string()
+}
+
+/// A loading spinner for storyboarding a CLI: one glyph of `glyphs` per
+/// frame, in turn, from the start of its range, with optional `text` after
+/// it (`⠹ Installing dependencies…`).
+///
+/// `glyphs` is any sequence of characters — `⠋⠙⠹…`, `|/-\`, `◐◓◑◒`. With
+/// `done` set, the last frame shows that glyph instead (`✔`), so the step
+/// visibly finishes before the next slide.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Spinner {
+    pub position: Position,
+    /// The frames of the animation, one character each.
+    #[serde(default = "default_spinner_glyphs")]
+    pub glyphs: String,
+    /// Written one blank after the glyph.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub text: String,
+    /// Shown in place of the spinner on the last frame of the range.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub done: Option<char>,
+    #[serde(default)]
+    pub style: Style,
+    pub frames: FrameRange,
+    #[serde(default)]
+    pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
+}
+
+impl Spinner {
+    /// The glyph shown `t` frames into a range of `len` frames, if any.
+    pub fn glyph_at(&self, t: usize, len: usize) -> Option<char> {
+        if let Some(done) = self.done.filter(|_| t + 1 == len) {
+            return Some(done);
+        }
+        let glyphs: Vec<char> = self.glyphs.chars().collect();
+        (!glyphs.is_empty()).then(|| glyphs[t % glyphs.len()])
+    }
+
+    /// Columns from the glyph to the text: past the widest glyph (`done`
+    /// included) and one blank, so the text doesn't shift as the glyphs turn.
+    pub fn text_offset(&self) -> u16 {
+        let widest = self.glyphs.chars().chain(self.done).map(char_width).max().unwrap_or(1);
+        widest.max(1) as u16 + 1
+    }
+}
+
+impl Resolve for Spinner {
+    fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+        let frame = ctx.frame;
+        if !self.frames.contains(frame) {
+            return;
+        }
+        let x = self.position.x.evaluate(frame, ctx.anims);
+        let y = self.position.y.evaluate(frame, ctx.anims);
+        let len = self.frames.end.saturating_sub(self.frames.start);
+        if let Some(glyph) = self.glyph_at(frame - self.frames.start, len) {
+            ops.push(DrawOp { x, y, ch: glyph, style: self.style.clone(), z_order: self.z_order });
+        }
+        let mut col = x + self.text_offset();
+        for ch in self.text.chars() {
+            ops.push(DrawOp { x: col, y, ch, style: self.style.clone(), z_order: self.z_order });
+            col += char_width(ch) as u16;
+        }
+    }
+}
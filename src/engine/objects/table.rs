@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::types::{Color, DrawOp, NamedColor, Style};
+use crate::types::{Color, DrawOp, NamedColor, Style, WIDE_CONTINUATION};
 
 use super::super::source::{AnimSpans, Coordinate, FrameRange, Position, deserialize_coord_compat};
+use super::motion::{Entrance, Exit};
 use super::{Resolve, ResolveCtx};
 
 // ---------------------------------------------------------------------------
@@ -51,12 +52,14 @@ fn wrap_cell_content_indexed(content: &str, w: usize) -> Vec<Vec<Option<usize>>>
 }
 
 /// Style for the block cursor: the highlighted character's colors inverted.
-fn caret_block_style(st: &Style) -> Style {
+pub(super) fn caret_block_style(st: &Style) -> Style {
     Style {
         fg: st.bg.clone().or(Some(Color::Named(NamedColor::Black))),
         bg: st.fg.clone().or(Some(Color::Named(NamedColor::White))),
         bold: st.bold,
         dim: false,
+        underline: false,
+        link: None,
     }
 }
 
@@ -127,6 +130,12 @@ pub struct Table {
     pub frames: FrameRange,
     #[serde(default)]
     pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
 }
 
 impl Table {
@@ -212,6 +221,7 @@ impl Table {
 
     /// Returns the pixel y range (inclusive start, exclusive end) of row `row_idx`
     /// including content only (no border row), evaluated at `frame`.
+    #[allow(clippy::needless_range_loop)]
     pub fn row_pixel_range(&self, frame: usize, anims: &AnimSpans, row_idx: usize) -> Option<(u16, u16)> {
         let total_w = self.width.evaluate(frame, anims) as usize;
         let (cws, _) = self.layout(total_w);
@@ -275,6 +285,7 @@ impl Table {
     /// surplus is distributed across rows (top to bottom) so the table fills
     /// the requested height. Rows whose content is taller than the budget are
     /// never clipped — an explicit height only pads, it never shrinks.
+    #[allow(clippy::needless_range_loop)]
     fn row_heights(&self, frame: usize, anims: &AnimSpans, col_content_widths: &[usize]) -> Vec<usize> {
         let nrows = self.rows;
         let mut heights = vec![1usize; nrows];
@@ -321,7 +332,8 @@ impl Table {
     /// - `cursor_cell`:      current navigation cursor in cell-props mode.
     /// - `blink_hidden`:     suppress cursor highlight during blink frame.
     /// - `editing_caret`:    `(row, col, char_index)` of the cell being text-edited;
-    ///                       that character is drawn inverted (the block cursor).
+    ///   that character is drawn inverted (the block cursor).
+    #[allow(clippy::too_many_arguments)]
     pub fn resolve_with_editor_overlay(
         &self,
         frame: usize,
@@ -354,6 +366,7 @@ impl Table {
         }
     }
 
+    #[allow(clippy::needless_range_loop)]
     fn draw_cursor_cell(&self, frame: usize, anims: &AnimSpans, row: usize, col: usize, ops: &mut Vec<DrawOp>) {
         if !self.borders {
             return;
@@ -386,6 +399,8 @@ impl Table {
             bg: None,
             bold: true,
             dim: false,
+            underline: false,
+            link: None,
         };
         let z = self.z_order + 100;
 
@@ -434,6 +449,7 @@ impl Table {
         }
     }
 
+    #[allow(clippy::too_many_arguments, clippy::needless_range_loop)]
     fn resolve_internal(
         &self,
         frame: usize,
@@ -493,6 +509,8 @@ impl Table {
                     bg: base.bg.clone(),
                     bold: is_header || base.bold,
                     dim: false,
+                    underline: base.underline,
+                    link: base.link.clone(),
                 }
             } else if cell_mode {
                 // In cell mode all non-selected cells are dimmed white
@@ -501,6 +519,8 @@ impl Table {
                     bg: None,
                     bold: false,
                     dim: true,
+                    underline: false,
+                    link: None,
                 }
             } else if is_header {
                 Style {
@@ -508,6 +528,8 @@ impl Table {
                     bg: base.bg.clone(),
                     bold: true,
                     dim: base.dim,
+                    underline: base.underline,
+                    link: base.link.clone(),
                 }
             } else {
                 base.clone()
@@ -526,6 +548,8 @@ impl Table {
                     bg: None,
                     bold: false,
                     dim: true,
+                    underline: false,
+                    link: None,
                 }
             } else if in_highlighted {
                 Style {
@@ -533,6 +557,8 @@ impl Table {
                     bg: None,
                     bold: false,
                     dim: false,
+                    underline: false,
+                    link: None,
                 }
             } else {
                 self.style.clone()
@@ -640,6 +666,11 @@ impl Table {
                     for (line_idx, row_src) in idx_grid.iter().enumerate() {
                         let ly = ry + line_idx as u16;
                         for (xi, src) in row_src.iter().enumerate() {
+                            // Second column of a wide glyph: the rasterizer
+                            // fills it from the first.
+                            if xi > 0 && src.is_some() && row_src[xi - 1] == *src {
+                                continue;
+                            }
                             let is_caret = *src == Some(caret);
                             let ch = src.map(|i| chars[i]).unwrap_or(' ');
                             if is_caret {
@@ -681,7 +712,7 @@ impl Table {
                 for (line_idx, row_chars) in wrapped.iter().enumerate() {
                     let ly = ry + line_idx as u16;
                     for (xi, &ch) in row_chars.iter().enumerate() {
-                        if ch != ' ' || st.bg.is_some() {
+                        if ch != WIDE_CONTINUATION && (ch != ' ' || st.bg.is_some()) {
                             ops.push(DrawOp {
                                 x: cx + xi as u16,
                                 y: ly,
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{char_width, DrawOp, Style};
+
+use super::super::source::{FrameRange, Position};
+use super::motion::{Entrance, Exit};
+use super::{Resolve, ResolveCtx};
+
+fn default_true() -> bool {
+    true
+}
+
+/// A table of contents: the deck's named sections (see
+/// [`SourcePresentation::sections`](super::super::source::SourcePresentation::sections)),
+/// one per line, each after the slide number it starts on.
+///
+/// The list is built at compile time from the sections themselves, so an
+/// agenda slide never drifts from the deck it describes. With `highlight`
+/// set, the section the frame being drawn belongs to is drawn in that style
+/// — a toc repeated on every section's title slide marks where the talk is.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Toc {
+    pub position: Position,
+    /// Put each section's slide number before its name.
+    #[serde(default = "default_true")]
+    pub numbers: bool,
+    /// Style for the current section's entry; drawn like the rest when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<Style>,
+    #[serde(default)]
+    pub style: Style,
+    pub frames: FrameRange,
+    #[serde(default)]
+    pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enter: Option<Entrance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Exit>,
+}
+
+impl Toc {
+    /// The entries for `sections` (as `(first frame, name)`), one per line:
+    /// the name, after its 1-based slide number right-aligned when `numbers`
+    /// is set.
+    pub fn lines(&self, sections: &[(usize, String)]) -> Vec<String> {
+        let digits = sections.last().map_or(1, |(frame, _)| (frame + 1).to_string().len());
+        sections
+            .iter()
+            .map(|(frame, name)| {
+                if self.numbers {
+                    format!("{:>digits$}  {name}", frame + 1)
+                } else {
+                    name.clone()
+                }
+            })
+            .collect()
+    }
+}
+
+impl Resolve for Toc {
+    fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+        let frame = ctx.frame;
+        if !self.frames.contains(frame) {
+            return;
+        }
+        let x = self.position.x.evaluate(frame, ctx.anims);
+        let y = self.position.y.evaluate(frame, ctx.anims);
+        let current = ctx.sections.iter().rposition(|(start, _)| *start <= frame);
+        for (row, line) in self.lines(ctx.sections).into_iter().enumerate() {
+            let style = match &self.highlight {
+                Some(highlight) if current == Some(row) => highlight,
+                _ => &self.style,
+            };
+            let mut col = x;
+            for ch in line.chars() {
+                ops.push(DrawOp { x: col, y: y + row as u16, ch, style: style.clone(), z_order: self.z_order });
+                col += char_width(ch) as u16;
+            }
+        }
+    }
+}
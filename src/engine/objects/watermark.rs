@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{char_width, DrawOp, Style};
+
+use super::super::source::{FrameRange, Position};
+use super::font;
+use super::header::{default_header_char, Header};
+use super::{Resolve, ResolveCtx};
+
+pub(crate) fn default_watermark_z() -> i32 {
+    -1000
+}
+
+/// Background text behind everything else — a logo, "DRAFT", the event name
+/// — kept as one object across the frames it marks rather than a copy per
+/// slide.
+///
+/// It is always drawn dim (on top of its own `style`), blanks don't paint,
+/// and it sits far down the stack (`z_order` -1000 unless set), so whatever
+/// a slide draws covers it. `big` draws it in the `header` block font. It
+/// has no `enter`/`exit`: it stays put while slides come and go.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Watermark {
+    /// The text; one line per `\n`.
+    pub text: String,
+    pub position: Position,
+    /// Draw the text in large block letters, word-wrapped like a `header`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub big: bool,
+    #[serde(default)]
+    pub style: Style,
+    pub frames: FrameRange,
+    #[serde(default = "default_watermark_z")]
+    pub z_order: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+}
+
+impl Watermark {
+    /// `(columns, rows)` the text covers.
+    pub fn size(&self) -> (u16, u16) {
+        if self.big {
+            // Unwrapped; a header only wraps at the canvas edge.
+            let words: Vec<&str> = self.text.split_whitespace().collect();
+            return (font::text_width(&words.join(" ")), font::GLYPH_HEIGHT);
+        }
+        let lines = self.text.split('\n');
+        let w = lines.clone().map(|l| l.chars().map(char_width).sum::<usize>()).max().unwrap_or(0);
+        (w as u16, lines.count() as u16)
+    }
+}
+
+impl Resolve for Watermark {
+    fn resolve(&self, ctx: &ResolveCtx, ops: &mut Vec<DrawOp>) {
+        let frame = ctx.frame;
+        if !self.frames.contains(frame) {
+            return;
+        }
+        let style = Style { dim: true, ..self.style.clone() };
+        if self.big {
+            let header = Header {
+                text: self.text.clone(),
+                position: self.position.clone(),
+                style,
+                frames: self.frames.clone(),
+                z_order: self.z_order,
+                layer: None,
+                enter: None,
+                exit: None,
+                ch: default_header_char(),
+                gradient: None,
+            };
+            header.resolve(ctx, ops);
+            return;
+        }
+        let x = self.position.x.evaluate(frame, ctx.anims);
+        let y = self.position.y.evaluate(frame, ctx.anims);
+        for (row, line) in self.text.split('\n').enumerate() {
+            let mut col = x;
+            for ch in line.chars() {
+                if ch != ' ' {
+                    ops.push(DrawOp { x: col, y: y + row as u16, ch, style: style.clone(), z_order: self.z_order });
+                }
+                col += char_width(ch) as u16;
+            }
+        }
+    }
+}
@@ -11,6 +11,12 @@
 //! indices. Deriving both from one function means the visible glyphs and their
 //! source indices can never drift apart — and `Label` and `Table` can no longer
 //! grow divergent copies of the wrap algorithm.
+//!
+//! Widths are display columns: a double-width glyph fills two slots, both
+//! carrying its index, and [`indexed_to_chars`] turns the second into
+//! [`WIDE_CONTINUATION`] so callers can skip it when emitting draw ops.
+
+use crate::types::{char_width, WIDE_CONTINUATION};
 
 /// Wrap one logical line to `w` cells wide.
 ///
@@ -35,34 +41,47 @@ pub fn wrap_line_indexed(
     let mut pos = 0usize;
     let mut first = true;
 
+    // Lay `chars[pos..pos + n]` out from column `col0`, clipping at `w`.
+    let place = |pos: usize, n: usize, col0: usize| {
+        let mut row = vec![None; w];
+        let mut col = col0;
+        for (i, &ch) in chars.iter().enumerate().skip(pos).take(n) {
+            for slot in row.iter_mut().skip(col).take(char_width(ch)) {
+                *slot = Some(base + i);
+            }
+            col += char_width(ch);
+        }
+        row
+    };
+
     while pos < chars.len() {
         let col0 = if first { 0 } else { indent.min(w.saturating_sub(1)) };
         first = false;
         let avail = w - col0;
 
-        let remaining = &chars[pos..];
-        if remaining.len() <= avail {
+        // How many chars fit in `avail` columns. At least one, so a wide glyph
+        // in a one-column box is clipped rather than looping forever.
+        let mut fit = 0;
+        let mut used = 0;
+        while pos + fit < chars.len() && used + char_width(chars[pos + fit]) <= avail {
+            used += char_width(chars[pos + fit]);
+            fit += 1;
+        }
+        if pos + fit == chars.len() {
             // Everything fits on this row.
-            let mut row = vec![None; w];
-            for i in 0..remaining.len() {
-                row[col0 + i] = Some(base + pos + i);
-            }
-            rows.push(row);
+            rows.push(place(pos, fit, col0));
             break;
         }
+        let fit = fit.max(1);
 
         // Find the last space within the available width for a soft break.
-        let chunk = &remaining[..avail];
+        let chunk = &chars[pos..pos + fit];
         let (row_len, advance) = match chunk.iter().rposition(|&c| c == ' ') {
             Some(sp) => (sp, sp + 1), // break before the space, skip the space
-            None => (avail, avail),   // hard break
+            None => (fit, fit),       // hard break
         };
 
-        let mut row = vec![None; w];
-        for i in 0..row_len {
-            row[col0 + i] = Some(base + pos + i);
-        }
-        rows.push(row);
+        rows.push(place(pos, row_len, col0));
         pos += advance;
 
         // Skip any additional leading spaces so the next row starts on a word.
@@ -78,14 +97,24 @@ pub fn wrap_line_indexed(
 }
 
 /// Map an indexed wrap of a single line back to glyph rows, substituting `' '`
-/// for padding cells. Only valid when the indices point into `line` (i.e. the
-/// wrap was produced with `base == 0` for this same line).
+/// for padding cells and [`WIDE_CONTINUATION`] for the second slot of a wide
+/// glyph. Only valid when the indices point into `line` (i.e. the wrap was
+/// produced with `base == 0` for this same line).
 pub fn indexed_to_chars(line: &str, grid: Vec<Vec<Option<usize>>>) -> Vec<Vec<char>> {
     let chars: Vec<char> = line.chars().collect();
     grid.into_iter()
         .map(|row| {
+            let mut prev = None;
             row.into_iter()
-                .map(|idx| idx.map(|i| chars[i]).unwrap_or(' '))
+                .map(|idx| {
+                    let ch = match idx {
+                        Some(i) if prev == Some(i) => WIDE_CONTINUATION,
+                        Some(i) => chars[i],
+                        None => ' ',
+                    };
+                    prev = idx;
+                    ch
+                })
                 .collect()
         })
         .collect()
@@ -113,4 +142,15 @@ mod tests {
         assert_eq!(chars[1], vec![' ', 'c']);
         assert_eq!(chars[2], vec![' ', 'd']);
     }
+
+    #[test]
+    fn wide_glyphs_take_two_columns_and_wrap_as_a_unit() {
+        // Width 5 holds two wide glyphs (4 columns); the third would need
+        // columns 4–5, so it wraps whole rather than splitting.
+        let grid = wrap_line_indexed(0, "日本語", 5, 0);
+        assert_eq!(grid[0], vec![Some(0), Some(0), Some(1), Some(1), None]);
+        assert_eq!(grid[1][..2], [Some(2), Some(2)]);
+        let chars = indexed_to_chars("日本語", grid);
+        assert_eq!(chars[0], vec!['日', WIDE_CONTINUATION, '本', WIDE_CONTINUATION, ' ']);
+    }
 }
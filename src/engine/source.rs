@@ -3,21 +3,50 @@
 //! These types define *what exists* and *how it behaves*, not how it is drawn.
 //! The engine reads these and resolves them into concrete `DrawOp`s per frame.
 
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 // Re-export object types so they remain accessible via `engine::source::*`.
 pub use super::objects::{
-    Animation, Arrow, Art, AutoAdvance, Circle, Command, Group, HLine, Header, Label, List, Loop,
-    Morph, MorphMode, Rect, Table, TextAlign, VerticalAlign,
+    Animation, AnsiArt, Arrow, Art, AutoAdvance, BackgroundEffect, BorderStyle, Cast, Circle, Clock,
+    Command, Component, Connector, Countdown, Diff, DiffLine, DiffLineKind, Effect, FileTree,
+    Footer, Gantt, GanttTask, Gauge, Group, HLine, Header, Heatmap, Include, Instance, Keycap,
+    Label, List, Loop, Marquee, Morph, MorphMode, Padding, PieChart, PieSegment, Rect, ScatterPlot,
+    Shadow, Spinner, Table, TextAlign, Threshold, Toc, TreeEntry, TreeNode, VerticalAlign,
+    Watermark,
 };
+pub use super::objects::motion::{EnterPreset, Entrance, Exit, ExitPreset, Motion};
+
+use super::lint::{LintLevel, LintRule};
 
-use crate::types::{AnimationRegion, AutoAdvanceRegion, CommandRegion, LoopRegion};
+use crate::types::{
+    AnimationRegion, AutoAdvanceRegion, ClockRegion, Color, CommandRegion, LoopRegion, Marker,
+    Metadata, TerminalContract,
+};
+
+/// The source `format_version` this build reads and writes. Older documents
+/// are upgraded as they are read ([`crate::migrate::source_from_str`]).
+pub const SOURCE_FORMAT_VERSION: u32 = 2;
+
+fn current_format_version() -> u32 {
+    SOURCE_FORMAT_VERSION
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SourcePresentation {
+    /// Schema version of the document. In memory always
+    /// [`SOURCE_FORMAT_VERSION`]: the loaders migrate older files first.
+    #[serde(default = "current_format_version")]
+    pub format_version: u32,
     pub width: u16,
     pub height: u16,
+    /// Omitted (or `0`), it is derived from the objects and slides when the
+    /// deck is loaded ([`Self::lower`]).
+    #[serde(default)]
     pub frame_count: usize,
+    #[serde(default)]
     pub objects: Vec<SceneObject>,
     /// Groups of object indices that are **linked**: a non-placement property
     /// edit (text, colour, art, …) on any member propagates to the others, while
@@ -25,6 +54,106 @@ pub struct SourcePresentation {
     /// maintained through object deletion like `Group.members`.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub links: Vec<Vec<usize>>,
+    /// Deck-wide background colour behind every cell that sets no `bg`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<Color>,
+    /// The layer registry: per-layer visibility and editor lock, keyed by the
+    /// name objects give in their `layer` field. A layer objects name but the
+    /// registry omits is visible and unlocked; objects with no `layer` are on
+    /// the base layer, which is always both.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub layers: Vec<Layer>,
+    /// Reusable object templates by name, stamped out by `instance` objects
+    /// (see [`Instance`]).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub components: BTreeMap<String, Component>,
+    /// Values for `${name}` in label, header, footer and table text, substituted at
+    /// compile time (`bs compile --set name=value` overrides them). A deck
+    /// without `vars` is left exactly as written.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub vars: BTreeMap<String, String>,
+    /// Slide-and-build authoring: slides laid end to end from frame 0, each
+    /// one frame per build step, lowered into `objects` and `frame_count` when
+    /// the deck is loaded ([`Self::lower_slides`]).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub slides: Vec<Slide>,
+    /// Per-rule levels for [`crate::engine::Engine::lint`]; a rule the map
+    /// leaves out is a warning.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub lint: BTreeMap<LintRule, LintLevel>,
+    /// Stack by author order alone: a later object draws above an earlier one
+    /// whatever their `z_order`, which then only orders an object's own parts.
+    /// Off, objects stack by `z_order`, equal ones in author order.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub auto_z: bool,
+    /// How long each frame shows, in milliseconds, indexed by frame. `null` —
+    /// or a list shorter than the deck — leaves a frame to the default pace of
+    /// whatever plays or exports it. Compiled onto
+    /// [`PlayablePresentation::durations`](crate::types::PlayablePresentation::durations).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub durations: Vec<Option<u64>>,
+    /// Shell commands to run when each frame is shown, indexed by frame like
+    /// [`Self::durations`]. The player only runs them under `play
+    /// --allow-exec`. Compiled onto
+    /// [`PlayablePresentation::on_enter`](crate::types::PlayablePresentation::on_enter).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub on_enter: Vec<Vec<String>>,
+    /// Section names, indexed by frame like [`Self::durations`]: a named
+    /// frame starts a section that runs until the next one. Listed by `toc`
+    /// objects, and compiled onto
+    /// [`PlayablePresentation::markers`](crate::types::PlayablePresentation::markers).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sections: Vec<Option<String>>,
+    /// Title, author, date and description, carried onto the compiled deck for
+    /// `info`, the player's status bar and the exporters.
+    #[serde(default, skip_serializing_if = "Metadata::is_empty")]
+    pub metadata: Metadata,
+}
+
+/// One entry of [`SourcePresentation::slides`]. Its objects are source object
+/// JSON without `frames`: `objects` show on every frame of the slide, and each
+/// `builds` step adds its objects one frame later than the step before, to
+/// stay until the slide ends. An object that does give `frames` counts them
+/// from the slide's first frame (e.g. an animation's span), and a slide runs
+/// long enough to hold it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Slide {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub objects: Vec<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub builds: Vec<Vec<serde_json::Value>>,
+    /// Start a section of this name on the slide's first frame
+    /// ([`SourcePresentation::sections`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+}
+
+/// One entry of [`SourcePresentation::layers`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Layer {
+    pub name: String,
+    /// Hidden layers are skipped when compiling: their objects draw nothing
+    /// and their commands never run (e.g. a speaker-notes layer).
+    #[serde(default = "default_true")]
+    pub visible: bool,
+    /// Locked layers stay visible but the editor won't select their objects.
+    #[serde(default)]
+    pub locked: bool,
+    /// Overlay layers are left out of the compiled frames and kept as
+    /// prepared [`Overlay`](crate::types::Overlay)s the presenter shows with
+    /// a number key — the first overlay layer is `1`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub overlay: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Layer {
+    pub fn new(name: impl Into<String>) -> Self {
+        Layer { name: name.into(), visible: true, locked: false, overlay: false }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,6 +167,25 @@ pub enum SceneObject {
     Arrow(Arrow),
     Table(Table),
     Art(Art),
+    AnsiArt(AnsiArt),
+    Cast(Cast),
+    Countdown(Countdown),
+    Toc(Toc),
+    Footer(Footer),
+    Gauge(Gauge),
+    Heatmap(Heatmap),
+    Diff(Diff),
+    FileTree(FileTree),
+    Gantt(Gantt),
+    PieChart(PieChart),
+    ScatterPlot(ScatterPlot),
+    Marquee(Marquee),
+    Spinner(Spinner),
+    Connector(Connector),
+    Keycap(Keycap),
+    Watermark(Watermark),
+    BackgroundEffect(BackgroundEffect),
+    Clock(Clock),
     Command(Command),
     List(List),
     Loop(Loop),
@@ -45,6 +193,8 @@ pub enum SceneObject {
     Animation(Animation),
     AutoAdvance(AutoAdvance),
     Circle(Circle),
+    Instance(Instance),
+    Include(Include),
 }
 
 impl SceneObject {
@@ -61,6 +211,25 @@ impl SceneObject {
             SceneObject::Arrow(a) => Some(a.frames.clone()),
             SceneObject::Table(t) => Some(t.frames.clone()),
             SceneObject::Art(a) => Some(a.frames.clone()),
+            SceneObject::AnsiArt(a) => Some(a.frames.clone()),
+            SceneObject::Cast(a) => Some(a.frames.clone()),
+            SceneObject::Countdown(a) => Some(a.frames.clone()),
+            SceneObject::Toc(a) => Some(a.frames.clone()),
+            SceneObject::Footer(a) => Some(a.frames.clone()),
+            SceneObject::Gauge(a) => Some(a.frames.clone()),
+            SceneObject::Heatmap(a) => Some(a.frames.clone()),
+            SceneObject::Diff(a) => Some(a.frames.clone()),
+            SceneObject::FileTree(a) => Some(a.frames.clone()),
+            SceneObject::Gantt(a) => Some(a.frames.clone()),
+            SceneObject::PieChart(a) => Some(a.frames.clone()),
+            SceneObject::ScatterPlot(a) => Some(a.frames.clone()),
+            SceneObject::Marquee(a) => Some(a.frames.clone()),
+            SceneObject::Spinner(a) => Some(a.frames.clone()),
+            SceneObject::Connector(a) => Some(a.frames.clone()),
+            SceneObject::Keycap(a) => Some(a.frames.clone()),
+            SceneObject::Watermark(a) => Some(a.frames.clone()),
+            SceneObject::BackgroundEffect(a) => Some(a.frames.clone()),
+            SceneObject::Clock(c) => Some(c.frames.clone()),
             SceneObject::Command(c) => Some(c.frames.clone()),
             SceneObject::List(l) => Some(l.frames.clone()),
             SceneObject::Loop(l) => Some(l.frames.clone()),
@@ -68,6 +237,201 @@ impl SceneObject {
             SceneObject::Animation(a) => Some(a.frames.clone()),
             SceneObject::AutoAdvance(a) => Some(a.frames.clone()),
             SceneObject::Circle(c) => Some(c.frames.clone()),
+            SceneObject::Instance(o) => Some(o.frames.clone()),
+            SceneObject::Include(_) => None,
+        }
+    }
+
+    /// The object's `type` tag as it appears in the source JSON (`"label"`,
+    /// `"h_line"`, …) — for diagnostics that point back at the authored file.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SceneObject::Label(_) => "label",
+            SceneObject::HLine(_) => "h_line",
+            SceneObject::Rect(_) => "rect",
+            SceneObject::Header(_) => "header",
+            SceneObject::Group(_) => "group",
+            SceneObject::Arrow(_) => "arrow",
+            SceneObject::Table(_) => "table",
+            SceneObject::Art(_) => "art",
+            SceneObject::AnsiArt(_) => "ansi_art",
+            SceneObject::Cast(_) => "cast",
+            SceneObject::Countdown(_) => "countdown",
+            SceneObject::Toc(_) => "toc",
+            SceneObject::Footer(_) => "footer",
+            SceneObject::Gauge(_) => "gauge",
+            SceneObject::Heatmap(_) => "heatmap",
+            SceneObject::Diff(_) => "diff",
+            SceneObject::FileTree(_) => "file_tree",
+            SceneObject::Gantt(_) => "gantt",
+            SceneObject::PieChart(_) => "pie_chart",
+            SceneObject::ScatterPlot(_) => "scatter_plot",
+            SceneObject::Marquee(_) => "marquee",
+            SceneObject::Spinner(_) => "spinner",
+            SceneObject::Connector(_) => "connector",
+            SceneObject::Keycap(_) => "keycap",
+            SceneObject::Watermark(_) => "watermark",
+            SceneObject::BackgroundEffect(_) => "background_effect",
+            SceneObject::Clock(_) => "clock",
+            SceneObject::Command(_) => "command",
+            SceneObject::List(_) => "list",
+            SceneObject::Loop(_) => "loop",
+            SceneObject::Morph(_) => "morph",
+            SceneObject::Animation(_) => "animation",
+            SceneObject::AutoAdvance(_) => "auto_advance",
+            SceneObject::Circle(_) => "circle",
+            SceneObject::Instance(_) => "instance",
+            SceneObject::Include(_) => "include",
+        }
+    }
+
+    /// Every `Coordinate` field of the object, paired with its JSON path
+    /// relative to the object (`"position.x"`, `"width"`, …). Objects that draw
+    /// nothing (`Group`, `Loop`, `Animation`, `AutoAdvance`) have none.
+    pub fn coordinates(&self) -> Vec<(&'static str, &Coordinate)> {
+        match self {
+            SceneObject::Label(l) => vec![
+                ("position.x", &l.position.x),
+                ("position.y", &l.position.y),
+                ("width", &l.width),
+                ("height", &l.height),
+            ],
+            SceneObject::Rect(r) => vec![
+                ("position.x", &r.position.x),
+                ("position.y", &r.position.y),
+                ("width", &r.width),
+                ("height", &r.height),
+            ],
+            SceneObject::HLine(h) => vec![("y", &h.y), ("x_start", &h.x_start), ("x_end", &h.x_end)],
+            SceneObject::Header(h) => vec![("position.x", &h.position.x), ("position.y", &h.position.y)],
+            SceneObject::Arrow(a) => vec![("x1", &a.x1), ("y1", &a.y1), ("x2", &a.x2), ("y2", &a.y2)],
+            SceneObject::Table(t) => vec![
+                ("position.x", &t.position.x),
+                ("position.y", &t.position.y),
+                ("width", &t.width),
+                ("height", &t.height),
+            ],
+            SceneObject::Art(a) => vec![("position.x", &a.position.x), ("position.y", &a.position.y)],
+            SceneObject::AnsiArt(a) => vec![("position.x", &a.position.x), ("position.y", &a.position.y)],
+            SceneObject::Cast(a) => vec![("position.x", &a.position.x), ("position.y", &a.position.y)],
+            SceneObject::Countdown(a) => vec![("position.x", &a.position.x), ("position.y", &a.position.y)],
+            SceneObject::Toc(a) => vec![("position.x", &a.position.x), ("position.y", &a.position.y)],
+            SceneObject::Footer(a) => vec![("position.x", &a.position.x), ("position.y", &a.position.y)],
+            SceneObject::Gauge(a) => vec![("position.x", &a.position.x), ("position.y", &a.position.y)],
+            SceneObject::Heatmap(a) => vec![("position.x", &a.position.x), ("position.y", &a.position.y)],
+            SceneObject::Diff(a) => vec![("position.x", &a.position.x), ("position.y", &a.position.y)],
+            SceneObject::FileTree(a) => vec![("position.x", &a.position.x), ("position.y", &a.position.y)],
+            SceneObject::Gantt(a) => vec![("position.x", &a.position.x), ("position.y", &a.position.y)],
+            SceneObject::PieChart(a) => vec![("position.x", &a.position.x), ("position.y", &a.position.y)],
+            SceneObject::ScatterPlot(a) => vec![("position.x", &a.position.x), ("position.y", &a.position.y)],
+            SceneObject::Marquee(a) => vec![("position.x", &a.position.x), ("position.y", &a.position.y)],
+            SceneObject::Spinner(a) => vec![("position.x", &a.position.x), ("position.y", &a.position.y)],
+            SceneObject::Connector(a) => vec![("position.x", &a.position.x), ("position.y", &a.position.y)],
+            SceneObject::Keycap(a) => vec![("position.x", &a.position.x), ("position.y", &a.position.y)],
+            SceneObject::Watermark(a) => vec![("position.x", &a.position.x), ("position.y", &a.position.y)],
+            SceneObject::BackgroundEffect(a) => vec![("position.x", &a.position.x), ("position.y", &a.position.y)],
+            SceneObject::Clock(c) => vec![("position.x", &c.position.x), ("position.y", &c.position.y)],
+            SceneObject::Command(c) => vec![
+                ("position.x", &c.position.x),
+                ("position.y", &c.position.y),
+                ("width", &c.width),
+                ("height", &c.height),
+            ],
+            SceneObject::List(l) => vec![
+                ("position.x", &l.position.x),
+                ("position.y", &l.position.y),
+                ("width", &l.width),
+                ("height", &l.height),
+            ],
+            SceneObject::Morph(m) => vec![("position.x", &m.position.x), ("position.y", &m.position.y)],
+            SceneObject::Circle(c) => vec![("position.x", &c.position.x), ("position.y", &c.position.y)],
+            SceneObject::Group(_)
+            | SceneObject::Loop(_)
+            | SceneObject::Animation(_)
+            | SceneObject::AutoAdvance(_)
+            | SceneObject::Instance(_)
+            | SceneObject::Include(_) => vec![],
+        }
+    }
+
+    /// Mutable [`Self::coordinates`], without the paths.
+    pub fn coordinates_mut(&mut self) -> Vec<&mut Coordinate> {
+        match self {
+            SceneObject::Label(l) => vec![&mut l.position.x, &mut l.position.y, &mut l.width, &mut l.height],
+            SceneObject::Rect(r) => vec![&mut r.position.x, &mut r.position.y, &mut r.width, &mut r.height],
+            SceneObject::HLine(h) => vec![&mut h.y, &mut h.x_start, &mut h.x_end],
+            SceneObject::Header(h) => vec![&mut h.position.x, &mut h.position.y],
+            SceneObject::Arrow(a) => vec![&mut a.x1, &mut a.y1, &mut a.x2, &mut a.y2],
+            SceneObject::Table(t) => vec![&mut t.position.x, &mut t.position.y, &mut t.width, &mut t.height],
+            SceneObject::Art(a) => vec![&mut a.position.x, &mut a.position.y],
+            SceneObject::AnsiArt(a) => vec![&mut a.position.x, &mut a.position.y],
+            SceneObject::Cast(a) => vec![&mut a.position.x, &mut a.position.y],
+            SceneObject::Countdown(a) => vec![&mut a.position.x, &mut a.position.y],
+            SceneObject::Toc(a) => vec![&mut a.position.x, &mut a.position.y],
+            SceneObject::Footer(a) => vec![&mut a.position.x, &mut a.position.y],
+            SceneObject::Gauge(a) => vec![&mut a.position.x, &mut a.position.y],
+            SceneObject::Heatmap(a) => vec![&mut a.position.x, &mut a.position.y],
+            SceneObject::Diff(a) => vec![&mut a.position.x, &mut a.position.y],
+            SceneObject::FileTree(a) => vec![&mut a.position.x, &mut a.position.y],
+            SceneObject::Gantt(a) => vec![&mut a.position.x, &mut a.position.y],
+            SceneObject::PieChart(a) => vec![&mut a.position.x, &mut a.position.y],
+            SceneObject::ScatterPlot(a) => vec![&mut a.position.x, &mut a.position.y],
+            SceneObject::Marquee(a) => vec![&mut a.position.x, &mut a.position.y],
+            SceneObject::Spinner(a) => vec![&mut a.position.x, &mut a.position.y],
+            SceneObject::Connector(a) => vec![&mut a.position.x, &mut a.position.y],
+            SceneObject::Keycap(a) => vec![&mut a.position.x, &mut a.position.y],
+            SceneObject::Watermark(a) => vec![&mut a.position.x, &mut a.position.y],
+            SceneObject::BackgroundEffect(a) => vec![&mut a.position.x, &mut a.position.y],
+            SceneObject::Clock(c) => vec![&mut c.position.x, &mut c.position.y],
+            SceneObject::Command(c) => vec![&mut c.position.x, &mut c.position.y, &mut c.width, &mut c.height],
+            SceneObject::List(l) => vec![&mut l.position.x, &mut l.position.y, &mut l.width, &mut l.height],
+            SceneObject::Morph(m) => vec![&mut m.position.x, &mut m.position.y],
+            SceneObject::Circle(c) => vec![&mut c.position.x, &mut c.position.y],
+            SceneObject::Group(_)
+            | SceneObject::Loop(_)
+            | SceneObject::Animation(_)
+            | SceneObject::AutoAdvance(_)
+            | SceneObject::Instance(_)
+            | SceneObject::Include(_) => vec![],
+        }
+    }
+
+    /// Every [`Scalar`] field of the object with its JSON path — the readings
+    /// an animation can drive besides [`Self::coordinates`]. Only `Gauge` has any.
+    pub fn scalars(&self) -> Vec<(&'static str, &Scalar)> {
+        match self {
+            SceneObject::Gauge(g) => vec![("value", &g.value), ("min", &g.min), ("max", &g.max)],
+            _ => vec![],
+        }
+    }
+
+    /// Mutable [`Self::scalars`], without the paths.
+    pub fn scalars_mut(&mut self) -> Vec<&mut Scalar> {
+        match self {
+            SceneObject::Gauge(g) => vec![&mut g.value, &mut g.min, &mut g.max],
+            _ => vec![],
+        }
+    }
+
+    /// Shift the object's placement by (`dx`, `dy`) cells: every x / y
+    /// coordinate but not its size, an animated one along its whole path.
+    /// Values stop at 0.
+    pub fn translate(&mut self, dx: i32, dy: i32) {
+        let shifts: Vec<i32> = self
+            .coordinates()
+            .iter()
+            .map(|(path, _)| match path.rsplit('.').next().unwrap_or(path).chars().next() {
+                Some('x') => dx,
+                Some('y') => dy,
+                _ => 0,
+            })
+            .collect();
+        let shift = |v: u16, d: i32| (i32::from(v) + d).clamp(0, i32::from(u16::MAX)) as u16;
+        for (coord, d) in self.coordinates_mut().into_iter().zip(shifts) {
+            match coord {
+                Coordinate::Fixed(v) => *v = (*v + f64::from(d)).max(0.0),
+                Coordinate::Animated { from, to, .. } => (*from, *to) = (shift(*from, d), shift(*to, d)),
+            }
         }
     }
 
@@ -83,6 +447,25 @@ impl SceneObject {
             SceneObject::Arrow(a) => a.frames = r,
             SceneObject::Table(t) => t.frames = r,
             SceneObject::Art(a) => a.frames = r,
+            SceneObject::AnsiArt(a) => a.frames = r,
+            SceneObject::Cast(a) => a.frames = r,
+            SceneObject::Countdown(a) => a.frames = r,
+            SceneObject::Toc(a) => a.frames = r,
+            SceneObject::Footer(a) => a.frames = r,
+            SceneObject::Gauge(a) => a.frames = r,
+            SceneObject::Heatmap(a) => a.frames = r,
+            SceneObject::Diff(a) => a.frames = r,
+            SceneObject::FileTree(a) => a.frames = r,
+            SceneObject::Gantt(a) => a.frames = r,
+            SceneObject::PieChart(a) => a.frames = r,
+            SceneObject::ScatterPlot(a) => a.frames = r,
+            SceneObject::Marquee(a) => a.frames = r,
+            SceneObject::Spinner(a) => a.frames = r,
+            SceneObject::Connector(a) => a.frames = r,
+            SceneObject::Keycap(a) => a.frames = r,
+            SceneObject::Watermark(a) => a.frames = r,
+            SceneObject::BackgroundEffect(a) => a.frames = r,
+            SceneObject::Clock(c) => c.frames = r,
             SceneObject::Command(c) => c.frames = r,
             SceneObject::List(l) => l.frames = r,
             SceneObject::Loop(l) => l.frames = r,
@@ -90,11 +473,445 @@ impl SceneObject {
             SceneObject::Animation(a) => a.frames = r,
             SceneObject::AutoAdvance(a) => a.frames = r,
             SceneObject::Circle(c) => c.frames = r,
+            SceneObject::Instance(o) => o.frames = r,
+            // An include's frames are the included deck's; it has no range of its own.
+            SceneObject::Include(_) => {}
         }
     }
+
+    /// The named layer the object is on; `None` for the base layer.
+    pub fn layer(&self) -> Option<&str> {
+        match self {
+            SceneObject::Label(o) => o.layer.as_deref(),
+            SceneObject::HLine(o) => o.layer.as_deref(),
+            SceneObject::Rect(o) => o.layer.as_deref(),
+            SceneObject::Header(o) => o.layer.as_deref(),
+            SceneObject::Group(o) => o.layer.as_deref(),
+            SceneObject::Arrow(o) => o.layer.as_deref(),
+            SceneObject::Table(o) => o.layer.as_deref(),
+            SceneObject::Art(o) => o.layer.as_deref(),
+            SceneObject::AnsiArt(o) => o.layer.as_deref(),
+            SceneObject::Cast(o) => o.layer.as_deref(),
+            SceneObject::Countdown(o) => o.layer.as_deref(),
+            SceneObject::Toc(o) => o.layer.as_deref(),
+            SceneObject::Footer(o) => o.layer.as_deref(),
+            SceneObject::Gauge(o) => o.layer.as_deref(),
+            SceneObject::Heatmap(o) => o.layer.as_deref(),
+            SceneObject::Diff(o) => o.layer.as_deref(),
+            SceneObject::FileTree(o) => o.layer.as_deref(),
+            SceneObject::Gantt(o) => o.layer.as_deref(),
+            SceneObject::PieChart(o) => o.layer.as_deref(),
+            SceneObject::ScatterPlot(o) => o.layer.as_deref(),
+            SceneObject::Marquee(o) => o.layer.as_deref(),
+            SceneObject::Spinner(o) => o.layer.as_deref(),
+            SceneObject::Connector(o) => o.layer.as_deref(),
+            SceneObject::Keycap(o) => o.layer.as_deref(),
+            SceneObject::Watermark(o) => o.layer.as_deref(),
+            SceneObject::BackgroundEffect(o) => o.layer.as_deref(),
+            SceneObject::Clock(o) => o.layer.as_deref(),
+            SceneObject::Command(o) => o.layer.as_deref(),
+            SceneObject::List(o) => o.layer.as_deref(),
+            SceneObject::Loop(o) => o.layer.as_deref(),
+            SceneObject::Morph(o) => o.layer.as_deref(),
+            SceneObject::Animation(o) => o.layer.as_deref(),
+            SceneObject::AutoAdvance(o) => o.layer.as_deref(),
+            SceneObject::Circle(o) => o.layer.as_deref(),
+            SceneObject::Instance(o) => o.layer.as_deref(),
+            SceneObject::Include(o) => o.layer.as_deref(),
+        }
+    }
+
+    /// The object's entrance preset, if any (only drawable objects other
+    /// than `Command`, `Clock`, `Watermark` and `BackgroundEffect` take one).
+    pub fn enter(&self) -> Option<&Entrance> {
+        match self {
+            SceneObject::Label(o) => o.enter.as_ref(),
+            SceneObject::HLine(o) => o.enter.as_ref(),
+            SceneObject::Rect(o) => o.enter.as_ref(),
+            SceneObject::Header(o) => o.enter.as_ref(),
+            SceneObject::Arrow(o) => o.enter.as_ref(),
+            SceneObject::Table(o) => o.enter.as_ref(),
+            SceneObject::Art(o) => o.enter.as_ref(),
+            SceneObject::AnsiArt(o) => o.enter.as_ref(),
+            SceneObject::Cast(o) => o.enter.as_ref(),
+            SceneObject::Countdown(o) => o.enter.as_ref(),
+            SceneObject::Toc(o) => o.enter.as_ref(),
+            SceneObject::Footer(o) => o.enter.as_ref(),
+            SceneObject::Gauge(o) => o.enter.as_ref(),
+            SceneObject::Heatmap(o) => o.enter.as_ref(),
+            SceneObject::Diff(o) => o.enter.as_ref(),
+            SceneObject::FileTree(o) => o.enter.as_ref(),
+            SceneObject::Gantt(o) => o.enter.as_ref(),
+            SceneObject::PieChart(o) => o.enter.as_ref(),
+            SceneObject::ScatterPlot(o) => o.enter.as_ref(),
+            SceneObject::Marquee(o) => o.enter.as_ref(),
+            SceneObject::Spinner(o) => o.enter.as_ref(),
+            SceneObject::Connector(o) => o.enter.as_ref(),
+            SceneObject::Keycap(o) => o.enter.as_ref(),
+            SceneObject::List(o) => o.enter.as_ref(),
+            SceneObject::Morph(o) => o.enter.as_ref(),
+            SceneObject::Circle(o) => o.enter.as_ref(),
+            SceneObject::Group(_)
+            | SceneObject::Clock(_)
+            | SceneObject::Watermark(_)
+            | SceneObject::BackgroundEffect(_)
+            | SceneObject::Command(_)
+            | SceneObject::Loop(_)
+            | SceneObject::Animation(_)
+            | SceneObject::AutoAdvance(_)
+            | SceneObject::Instance(_)
+            | SceneObject::Include(_) => None,
+        }
+    }
+
+    /// The object's exit preset, if any (see [`Self::enter`]).
+    pub fn exit(&self) -> Option<&Exit> {
+        match self {
+            SceneObject::Label(o) => o.exit.as_ref(),
+            SceneObject::HLine(o) => o.exit.as_ref(),
+            SceneObject::Rect(o) => o.exit.as_ref(),
+            SceneObject::Header(o) => o.exit.as_ref(),
+            SceneObject::Arrow(o) => o.exit.as_ref(),
+            SceneObject::Table(o) => o.exit.as_ref(),
+            SceneObject::Art(o) => o.exit.as_ref(),
+            SceneObject::AnsiArt(o) => o.exit.as_ref(),
+            SceneObject::Cast(o) => o.exit.as_ref(),
+            SceneObject::Countdown(o) => o.exit.as_ref(),
+            SceneObject::Toc(o) => o.exit.as_ref(),
+            SceneObject::Footer(o) => o.exit.as_ref(),
+            SceneObject::Gauge(o) => o.exit.as_ref(),
+            SceneObject::Heatmap(o) => o.exit.as_ref(),
+            SceneObject::Diff(o) => o.exit.as_ref(),
+            SceneObject::FileTree(o) => o.exit.as_ref(),
+            SceneObject::Gantt(o) => o.exit.as_ref(),
+            SceneObject::PieChart(o) => o.exit.as_ref(),
+            SceneObject::ScatterPlot(o) => o.exit.as_ref(),
+            SceneObject::Marquee(o) => o.exit.as_ref(),
+            SceneObject::Spinner(o) => o.exit.as_ref(),
+            SceneObject::Connector(o) => o.exit.as_ref(),
+            SceneObject::Keycap(o) => o.exit.as_ref(),
+            SceneObject::List(o) => o.exit.as_ref(),
+            SceneObject::Morph(o) => o.exit.as_ref(),
+            SceneObject::Circle(o) => o.exit.as_ref(),
+            SceneObject::Group(_)
+            | SceneObject::Clock(_)
+            | SceneObject::Watermark(_)
+            | SceneObject::BackgroundEffect(_)
+            | SceneObject::Command(_)
+            | SceneObject::Loop(_)
+            | SceneObject::Animation(_)
+            | SceneObject::AutoAdvance(_)
+            | SceneObject::Instance(_)
+            | SceneObject::Include(_) => None,
+        }
+    }
+
+    /// Set the entrance preset; a no-op on objects that take none.
+    pub fn set_enter(&mut self, enter: Option<Entrance>) {
+        if let Some((slot, _)) = self.motion_slots() {
+            *slot = enter;
+        }
+    }
+
+    /// Set the exit preset; a no-op on objects that take none.
+    pub fn set_exit(&mut self, exit: Option<Exit>) {
+        if let Some((_, slot)) = self.motion_slots() {
+            *slot = exit;
+        }
+    }
+
+    fn motion_slots(&mut self) -> Option<(&mut Option<Entrance>, &mut Option<Exit>)> {
+        match self {
+            SceneObject::Label(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::HLine(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::Rect(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::Header(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::Arrow(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::Table(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::Art(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::AnsiArt(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::Cast(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::Countdown(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::Toc(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::Footer(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::Gauge(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::Heatmap(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::Diff(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::FileTree(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::Gantt(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::PieChart(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::ScatterPlot(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::Marquee(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::Spinner(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::Connector(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::Keycap(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::List(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::Morph(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::Circle(o) => Some((&mut o.enter, &mut o.exit)),
+            SceneObject::Group(_)
+            | SceneObject::Clock(_)
+            | SceneObject::Watermark(_)
+            | SceneObject::BackgroundEffect(_)
+            | SceneObject::Command(_)
+            | SceneObject::Loop(_)
+            | SceneObject::Animation(_)
+            | SceneObject::AutoAdvance(_)
+            | SceneObject::Instance(_)
+            | SceneObject::Include(_) => None,
+        }
+    }
+
+    /// The object's interpolated text (label, header, footer, marquee and spinner `text`, table cell
+    /// `content`), paired with its JSON path relative to the object.
+    pub fn texts(&self) -> Vec<(String, &str)> {
+        match self {
+            SceneObject::Label(l) => vec![("text".into(), l.text.as_str())],
+            SceneObject::Header(h) => vec![("text".into(), h.text.as_str())],
+            SceneObject::Footer(f) => vec![("text".into(), f.text.as_str())],
+            SceneObject::Marquee(m) => vec![("text".into(), m.text.as_str())],
+            SceneObject::Spinner(s) => vec![("text".into(), s.text.as_str())],
+            SceneObject::Table(t) => t
+                .cells
+                .iter()
+                .enumerate()
+                .flat_map(|(r, row)| {
+                    row.iter()
+                        .enumerate()
+                        .map(move |(c, cell)| (format!("cells[{r}][{c}].content"), cell.content.as_str()))
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    fn texts_mut(&mut self) -> Vec<&mut String> {
+        match self {
+            SceneObject::Label(l) => vec![&mut l.text],
+            SceneObject::Header(h) => vec![&mut h.text],
+            SceneObject::Footer(f) => vec![&mut f.text],
+            SceneObject::Marquee(m) => vec![&mut m.text],
+            SceneObject::Spinner(s) => vec![&mut s.text],
+            SceneObject::Table(t) => t.cells.iter_mut().flatten().map(|c| &mut c.content).collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Move the object to layer `layer` (`None` = the base layer).
+    pub fn set_layer(&mut self, layer: Option<String>) {
+        let slot = match self {
+            SceneObject::Label(o) => &mut o.layer,
+            SceneObject::HLine(o) => &mut o.layer,
+            SceneObject::Rect(o) => &mut o.layer,
+            SceneObject::Header(o) => &mut o.layer,
+            SceneObject::Group(o) => &mut o.layer,
+            SceneObject::Arrow(o) => &mut o.layer,
+            SceneObject::Table(o) => &mut o.layer,
+            SceneObject::Art(o) => &mut o.layer,
+            SceneObject::AnsiArt(o) => &mut o.layer,
+            SceneObject::Cast(o) => &mut o.layer,
+            SceneObject::Countdown(o) => &mut o.layer,
+            SceneObject::Toc(o) => &mut o.layer,
+            SceneObject::Footer(o) => &mut o.layer,
+            SceneObject::Gauge(o) => &mut o.layer,
+            SceneObject::Heatmap(o) => &mut o.layer,
+            SceneObject::Diff(o) => &mut o.layer,
+            SceneObject::FileTree(o) => &mut o.layer,
+            SceneObject::Gantt(o) => &mut o.layer,
+            SceneObject::PieChart(o) => &mut o.layer,
+            SceneObject::ScatterPlot(o) => &mut o.layer,
+            SceneObject::Marquee(o) => &mut o.layer,
+            SceneObject::Spinner(o) => &mut o.layer,
+            SceneObject::Connector(o) => &mut o.layer,
+            SceneObject::Keycap(o) => &mut o.layer,
+            SceneObject::Watermark(o) => &mut o.layer,
+            SceneObject::BackgroundEffect(o) => &mut o.layer,
+            SceneObject::Clock(o) => &mut o.layer,
+            SceneObject::Command(o) => &mut o.layer,
+            SceneObject::List(o) => &mut o.layer,
+            SceneObject::Loop(o) => &mut o.layer,
+            SceneObject::Morph(o) => &mut o.layer,
+            SceneObject::Animation(o) => &mut o.layer,
+            SceneObject::AutoAdvance(o) => &mut o.layer,
+            SceneObject::Circle(o) => &mut o.layer,
+            SceneObject::Instance(o) => &mut o.layer,
+            SceneObject::Include(o) => &mut o.layer,
+        };
+        *slot = layer;
+    }
 }
 
 impl SourcePresentation {
+    /// Every layer name in use: the registry's, in order, then any that only
+    /// objects mention, in object order.
+    pub fn layer_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.layers.iter().map(|l| l.name.clone()).collect();
+        for name in self.objects.iter().filter_map(SceneObject::layer) {
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+        names
+    }
+
+    /// The registry entry for `name`, created (visible, unlocked) if missing.
+    pub fn layer_entry(&mut self, name: &str) -> &mut Layer {
+        let i = match self.layers.iter().position(|l| l.name == name) {
+            Some(i) => i,
+            None => {
+                self.layers.push(Layer::new(name));
+                self.layers.len() - 1
+            }
+        };
+        &mut self.layers[i]
+    }
+
+    /// The text of the objects on layer `layer`, one entry per frame: what
+    /// each frame shows there, objects in author order and separated by a
+    /// blank line, with `vars` applied and label markup stripped. Read with
+    /// the layer hidden or not — it is how `bs play --tmux-notes` gets a
+    /// `notes` layer's speaker notes.
+    pub fn layer_text(&self, layer: &str) -> Vec<String> {
+        let deck = self.with_vars_applied();
+        let overrides = deck.member_overrides();
+        let mut out = vec![String::new(); self.frame_count];
+        for (i, obj) in deck.objects.iter().enumerate() {
+            if obj.layer() != Some(layer) {
+                continue;
+            }
+            let text = match obj {
+                SceneObject::Label(l) => l.plain_text(),
+                SceneObject::List(l) => l.text.clone(),
+                _ => obj.texts().into_iter().map(|(_, t)| t).collect::<Vec<_>>().join("\n"),
+            };
+            if text.trim().is_empty() {
+                continue;
+            }
+            let range = overrides[i].clone().unwrap_or_else(|| deck.effective_frame_range(i));
+            for notes in out.iter_mut().take(range.end).skip(range.start) {
+                if !notes.is_empty() {
+                    notes.push_str("\n\n");
+                }
+                notes.push_str(text.trim_end());
+            }
+        }
+        out
+    }
+
+    /// Whether `obj` is on a hidden layer.
+    pub fn is_hidden(&self, obj: &SceneObject) -> bool {
+        obj.layer()
+            .is_some_and(|name| self.layers.iter().any(|l| l.name == name && !l.visible))
+    }
+
+    /// The visible overlay layers, in registry order: the one at index `n` is
+    /// toggled by key `n + 1` at play time.
+    pub fn overlay_layers(&self) -> Vec<&str> {
+        self.layers.iter().filter(|l| l.overlay && l.visible).map(|l| l.name.as_str()).collect()
+    }
+
+    /// A copy with every overlay layer hidden but `shown` — with `None`, the
+    /// deck the frames are compiled from.
+    pub fn with_overlay(&self, shown: Option<&str>) -> SourcePresentation {
+        let mut deck = self.clone();
+        for layer in deck.layers.iter_mut().filter(|l| l.overlay) {
+            layer.visible = layer.visible && shown == Some(layer.name.as_str());
+        }
+        deck
+    }
+
+    /// Whether `obj` is on a locked layer.
+    pub fn is_locked(&self, obj: &SceneObject) -> bool {
+        obj.layer()
+            .is_some_and(|name| self.layers.iter().any(|l| l.name == name && l.locked))
+    }
+
+    /// `text` with every `${name}` that `vars` defines replaced by its value
+    /// and `$${` unescaped to a literal `${`. Unknown names stay as written.
+    pub fn interpolate(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(at) = rest.find("${") {
+            let (before, after) = (&rest[..at], &rest[at + 2..]);
+            if let Some(literal) = before.strip_suffix('$') {
+                out.push_str(literal);
+                out.push_str("${");
+                rest = after;
+                continue;
+            }
+            out.push_str(before);
+            match after.find('}').and_then(|end| Some((end, self.vars.get(after[..end].trim())?))) {
+                Some((end, value)) => {
+                    out.push_str(value);
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    out.push_str("${");
+                    rest = after;
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+
+    /// Every `${name}` the objects' text uses that `vars` doesn't define, as
+    /// `(object index, path within the object, name)` — empty for a deck
+    /// without `vars`.
+    pub fn undefined_vars(&self) -> Vec<(usize, String, String)> {
+        if self.vars.is_empty() {
+            return vec![];
+        }
+        let mut out = Vec::new();
+        for (i, obj) in self.objects.iter().enumerate() {
+            for (path, text) in obj.texts() {
+                let mut rest = text;
+                while let Some(at) = rest.find("${") {
+                    let escaped = rest[..at].ends_with('$');
+                    rest = &rest[at + 2..];
+                    let Some(end) = rest.find('}') else { break };
+                    let name = rest[..end].trim();
+                    if !escaped && !self.vars.contains_key(name) {
+                        out.push((i, path.clone(), name.to_string()));
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// The deck with `vars` interpolated into its objects' text
+    /// ([`Self::interpolate`]); borrowed unchanged when it has no `vars`.
+    pub fn with_vars_applied(&self) -> Cow<'_, SourcePresentation> {
+        if self.vars.is_empty() {
+            return Cow::Borrowed(self);
+        }
+        let mut out = self.clone();
+        for obj in &mut out.objects {
+            self.interpolate_object(obj);
+        }
+        Cow::Owned(out)
+    }
+
+    fn interpolate_object(&self, obj: &mut SceneObject) {
+        if self.vars.is_empty() {
+            return;
+        }
+        for text in obj.texts_mut() {
+            if text.contains("${") {
+                *text = self.interpolate(text);
+            }
+        }
+    }
+
+    /// The canvas this deck compiles onto.
+    pub fn contract(&self) -> TerminalContract {
+        TerminalContract {
+            width: self.width,
+            height: self.height,
+            background: self.background.clone(),
+        }
+    }
+
     /// Effective frame range of object `i` — the range used to decide where it
     /// is visible. For most objects this is their declared range; for an *auto*
     /// group it is the union of its members' declared ranges. An empty/auto
@@ -149,13 +966,15 @@ impl SourcePresentation {
         out
     }
 
-    /// Collect the runtime command specs from all `Command` objects, evaluated
-    /// at each command's first active frame. These travel as a sidecar on the
-    /// `PlayablePresentation` because they cannot be baked into static frames.
+    /// Collect the runtime command specs from all `Command` objects not on a
+    /// hidden layer, evaluated at each command's first active frame. These
+    /// travel as a sidecar on the `PlayablePresentation` because they cannot be
+    /// baked into static frames.
     pub fn command_regions(&self) -> Vec<CommandRegion> {
         let anims = AnimSpans::of(self);
         self.objects
             .iter()
+            .filter(|obj| !self.is_hidden(obj))
             .filter_map(|obj| match obj {
                 SceneObject::Command(c) => Some(c.region(c.frames.start, &anims)),
                 _ => None,
@@ -163,6 +982,21 @@ impl SourcePresentation {
             .collect()
     }
 
+    /// Collect the runtime clock specs from all `Clock` objects not on a
+    /// hidden layer, placed at each clock's first frame — like the command
+    /// specs, the time of day can't be baked into the static frames.
+    pub fn clock_regions(&self) -> Vec<ClockRegion> {
+        let anims = AnimSpans::of(self);
+        self.objects
+            .iter()
+            .filter(|obj| !self.is_hidden(obj))
+            .filter_map(|obj| match obj {
+                SceneObject::Clock(c) => Some(c.region(c.frames.start, &anims)),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Collect the runtime loop specs from all `Loop` objects. Like the command
     /// specs, these travel as a sidecar on the `PlayablePresentation` because a
     /// loop is a play-time navigation behavior, not something bakeable into the
@@ -177,6 +1011,41 @@ impl SourcePresentation {
             .collect()
     }
 
+    /// Deep-copy the group at `index` — its members, and those of any group
+    /// among them — onto the end of `objects`, moved by (`dx`, `dy`) cells
+    /// ([`SceneObject::translate`]). The copies keep their frames, styles and
+    /// author order; they ride the same animations but join no link family.
+    /// Returns the new group's index.
+    pub fn clone_group(&mut self, index: usize, dx: i32, dy: i32) -> Result<usize, String> {
+        if !matches!(self.objects.get(index), Some(SceneObject::Group(_))) {
+            return Err(format!("object {index} is not a group"));
+        }
+        let mut tree = vec![index];
+        let mut next = 0;
+        while let Some(&i) = tree.get(next) {
+            if let SceneObject::Group(g) = &self.objects[i] {
+                for &m in &g.members {
+                    if m < self.objects.len() && !tree.contains(&m) {
+                        tree.push(m);
+                    }
+                }
+            }
+            next += 1;
+        }
+        tree.sort_unstable();
+        let base = self.objects.len();
+        let copy_of: BTreeMap<usize, usize> = tree.iter().enumerate().map(|(k, &i)| (i, base + k)).collect();
+        for &i in &tree {
+            let mut obj = self.objects[i].clone();
+            if let SceneObject::Group(g) = &mut obj {
+                g.members = g.members.iter().filter_map(|m| copy_of.get(m).copied()).collect();
+            }
+            obj.translate(dx, dy);
+            self.objects.push(obj);
+        }
+        Ok(copy_of[&index])
+    }
+
     /// Other objects linked to object `index` (its link family minus itself).
     /// Empty when the object is in no link group.
     pub fn link_siblings(&self, index: usize) -> Vec<usize> {
@@ -201,20 +1070,214 @@ impl SourcePresentation {
             .collect()
     }
 
-    /// Collect the runtime auto-advance specs from all `AutoAdvance` objects.
-    /// Like loops and animations, these travel as a sidecar on the
-    /// `PlayablePresentation` — auto-advance is a play-time navigation behavior
-    /// that draws nothing into the static frames.
+    /// Collect the runtime auto-advance specs from all `AutoAdvance` objects,
+    /// and from `auto_play` countdowns ([`Countdown::region`]). Like loops and
+    /// animations, these travel as a sidecar on the `PlayablePresentation` —
+    /// auto-advance is a play-time navigation behavior that draws nothing into
+    /// the static frames.
     pub fn auto_advance_regions(&self) -> Vec<AutoAdvanceRegion> {
         self.objects
             .iter()
             .filter_map(|obj| match obj {
                 SceneObject::AutoAdvance(a) => Some(a.region()),
+                SceneObject::Countdown(c) => c.region(),
                 _ => None,
             })
             .collect()
     }
 
+    /// `frame`'s duration in milliseconds, if [`Self::durations`] gives one.
+    pub fn frame_duration(&self, frame: usize) -> Option<u64> {
+        self.durations.get(frame).copied().flatten()
+    }
+
+    /// Set (or, with `None`, clear) `frame`'s duration. The list stays no
+    /// longer than its last set entry.
+    pub fn set_frame_duration(&mut self, frame: usize, ms: Option<u64>) {
+        if frame >= self.durations.len() {
+            if ms.is_none() {
+                return;
+            }
+            self.durations.resize(frame + 1, None);
+        }
+        self.durations[frame] = ms;
+        while let Some(None) = self.durations.last() {
+            self.durations.pop();
+        }
+    }
+
+    /// The per-frame durations as the compiled deck carries them: one entry
+    /// per frame, or none at all when no frame has one.
+    pub fn frame_durations(&self) -> Vec<Option<u64>> {
+        if self.durations.iter().take(self.frame_count).all(Option::is_none) {
+            return Vec::new();
+        }
+        (0..self.frame_count).map(|f| self.frame_duration(f)).collect()
+    }
+
+    /// The commands `frame` runs when shown, if [`Self::on_enter`] gives any.
+    pub fn frame_on_enter(&self, frame: usize) -> &[String] {
+        self.on_enter.get(frame).map_or(&[], Vec::as_slice)
+    }
+
+    /// Set (or, with an empty list, clear) `frame`'s enter commands. The list
+    /// stays no longer than its last set entry.
+    pub fn set_frame_on_enter(&mut self, frame: usize, commands: Vec<String>) {
+        if frame >= self.on_enter.len() {
+            if commands.is_empty() {
+                return;
+            }
+            self.on_enter.resize(frame + 1, Vec::new());
+        }
+        self.on_enter[frame] = commands;
+        while self.on_enter.last().is_some_and(Vec::is_empty) {
+            self.on_enter.pop();
+        }
+    }
+
+    /// The enter commands as the compiled deck carries them: one entry per
+    /// frame, or none at all when no frame has any.
+    pub fn frames_on_enter(&self) -> Vec<Vec<String>> {
+        if self.on_enter.iter().take(self.frame_count).all(Vec::is_empty) {
+            return Vec::new();
+        }
+        (0..self.frame_count).map(|f| self.frame_on_enter(f).to_vec()).collect()
+    }
+
+    /// `frame`'s section name, if it starts one.
+    pub fn frame_section(&self, frame: usize) -> Option<&str> {
+        self.sections.get(frame).and_then(Option::as_deref)
+    }
+
+    /// Name (or, with `None`, unname) the section `frame` starts. The list
+    /// stays no longer than its last set entry.
+    pub fn set_frame_section(&mut self, frame: usize, name: Option<String>) {
+        if frame >= self.sections.len() {
+            if name.is_none() {
+                return;
+            }
+            self.sections.resize(frame + 1, None);
+        }
+        self.sections[frame] = name;
+        while let Some(None) = self.sections.last() {
+            self.sections.pop();
+        }
+    }
+
+    /// The deck's sections in order, as `(first frame, name)`; sections past
+    /// the last frame are left out.
+    pub fn section_starts(&self) -> Vec<(usize, String)> {
+        (0..self.frame_count)
+            .filter_map(|f| self.frame_section(f).map(|name| (f, name.to_string())))
+            .collect()
+    }
+
+    /// The sections as the compiled deck's markers.
+    pub fn section_markers(&self) -> Vec<Marker> {
+        self.section_starts()
+            .into_iter()
+            .map(|(frame_index, label)| Marker { frame_index, label })
+            .collect()
+    }
+
+    /// Lower the authoring conveniences into the flat model the engine draws:
+    /// [`Self::lower_slides`], then — when `frame_count` was omitted — derive
+    /// it from the objects ([`Self::derived_frame_count`]).
+    pub fn lower(&mut self) -> Result<(), String> {
+        let derive = self.frame_count == 0;
+        self.lower_slides()?;
+        if derive {
+            self.frame_count = self.frame_count.max(self.derived_frame_count());
+        }
+        Ok(())
+    }
+
+    /// The frame count the objects need: the largest `frames.end` of any of
+    /// them (0 for a deck without ranged objects).
+    pub fn derived_frame_count(&self) -> usize {
+        self.objects.iter().filter_map(|o| o.declared_frame_range()).map(|r| r.end).max().unwrap_or(0)
+    }
+
+    /// Lower `slides` into the flat model: each slide's objects are appended to
+    /// `objects` with global frame ranges, and `frame_count` grows to cover the
+    /// last slide. Fails, naming the offending `slides[…]` path, on an object
+    /// that isn't valid source JSON or is a `group` or `include` (whose object
+    /// indices and frames a slide can't express); `slides` is left empty.
+    pub fn lower_slides(&mut self) -> Result<(), String> {
+        let mut start = 0;
+        for (s, slide) in std::mem::take(&mut self.slides).into_iter().enumerate() {
+            if let Some(name) = &slide.section {
+                self.set_frame_section(start, Some(name.clone()));
+            }
+            // (object, build step, frames relative to the slide if given)
+            let mut parsed = Vec::new();
+            let steps = std::iter::once(&slide.objects).chain(&slide.builds);
+            for (step, templates) in steps.enumerate() {
+                for (k, template) in templates.iter().enumerate() {
+                    let path = match step {
+                        0 => format!("slides[{s}].objects[{k}]"),
+                        _ => format!("slides[{s}].builds[{}][{k}]", step - 1),
+                    };
+                    let mut value = template.clone();
+                    let explicit = value.get("frames").is_some();
+                    if let serde_json::Value::Object(m) = &mut value {
+                        m.entry("frames").or_insert_with(|| serde_json::json!({ "start": 0, "end": 1 }));
+                    }
+                    let obj: SceneObject =
+                        serde_json::from_value(value).map_err(|e| format!("{path}: {e}"))?;
+                    if matches!(obj, SceneObject::Group(_) | SceneObject::Include(_)) {
+                        return Err(format!("{path}: a slide can't hold a {}", obj.kind()));
+                    }
+                    let own = if explicit { obj.declared_frame_range() } else { None };
+                    parsed.push((obj, step, own));
+                }
+            }
+            let len = parsed
+                .iter()
+                .filter_map(|(_, _, own)| own.as_ref().map(|r| r.end))
+                .fold(slide.builds.len() + 1, usize::max);
+            for (mut obj, step, own) in parsed {
+                let r = own.unwrap_or(FrameRange { start: step, end: len });
+                obj.set_frame_range(FrameRange { start: start + r.start, end: start + r.end });
+                self.objects.push(obj);
+            }
+            start += len;
+        }
+        self.frame_count = self.frame_count.max(start);
+        Ok(())
+    }
+
+    /// Every object's component expansion, by object index: `Some` for an
+    /// `instance` (empty when it can't be expanded — see
+    /// [`Self::validate_instances`]), `None` for everything else.
+    pub fn instance_expansions(&self) -> Vec<Option<Vec<SceneObject>>> {
+        self.objects
+            .iter()
+            .map(|obj| match obj {
+                SceneObject::Instance(inst) => {
+                    let mut expansion = inst.expand(&self.components).unwrap_or_default();
+                    expansion.iter_mut().for_each(|o| self.interpolate_object(o));
+                    Some(expansion)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Check that every `instance` expands: its component exists, its
+    /// arguments match the component's parameters and the substituted
+    /// templates are valid objects. Returns the first failure, prefixed with
+    /// the instance's object index. Like [`Self::validate_loops`], a hard gate
+    /// at compile time.
+    pub fn validate_instances(&self) -> Result<(), String> {
+        for (i, obj) in self.objects.iter().enumerate() {
+            if let SceneObject::Instance(inst) = obj {
+                inst.expand(&self.components).map_err(|e| format!("object {i}: {e}"))?;
+            }
+        }
+        Ok(())
+    }
+
     /// Validate every `Loop` object's range: each must be non-empty, fit within
     /// the deck, **disjoint** from every other loop (loops may neither overlap
     /// nor nest), and must not **bisect an animation** — a loop replays whole
@@ -290,7 +1353,7 @@ pub struct Position {
 /// the coordinate, since they are per-object.)
 pub type AnimId = u32;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Coordinate {
     /// Fixed value stored as f64 so group-scaling can use fractional precision.
@@ -415,6 +1478,107 @@ impl Coordinate {
     }
 }
 
+/// An animatable reading that is not a place on the canvas — a gauge's
+/// `value`, `min` and `max`. Unlike a [`Coordinate`] it may be negative or
+/// fractional and is never floored. Written as a bare number when fixed, or
+/// `{"animated": {"from", "to", "anim"}}` to sweep it over an animation's span.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scalar {
+    Fixed(f64),
+    /// Linear motion from `from` to `to` over the span of the [`Animation`]
+    /// with id `anim`, as for [`Coordinate::Animated`].
+    Animated { from: f64, to: f64, anim: AnimId },
+}
+
+/// `Scalar`'s object form, also accepted for a fixed value (`{"fixed": 5}`)
+/// so a field that used to hold a [`Coordinate`] still loads.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ScalarObject {
+    Fixed(f64),
+    Animated { from: f64, to: f64, anim: AnimId },
+}
+
+impl Serialize for Scalar {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        match *self {
+            Scalar::Fixed(v) => s.serialize_f64(v),
+            Scalar::Animated { from, to, anim } => ScalarObject::Animated { from, to, anim }.serialize(s),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Scalar {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Scalar, D::Error> {
+        use serde::de::{Error, MapAccess, Visitor};
+        use std::fmt;
+
+        struct ScalarVisitor;
+
+        impl<'de> Visitor<'de> for ScalarVisitor {
+            type Value = Scalar;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a number or an animated value")
+            }
+
+            fn visit_u64<E: Error>(self, v: u64) -> Result<Scalar, E> {
+                Ok(Scalar::Fixed(v as f64))
+            }
+
+            fn visit_i64<E: Error>(self, v: i64) -> Result<Scalar, E> {
+                Ok(Scalar::Fixed(v as f64))
+            }
+
+            fn visit_f64<E: Error>(self, v: f64) -> Result<Scalar, E> {
+                Ok(Scalar::Fixed(v))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Scalar, A::Error> {
+                Ok(match ScalarObject::deserialize(serde::de::value::MapAccessDeserializer::new(map))? {
+                    ScalarObject::Fixed(v) => Scalar::Fixed(v),
+                    ScalarObject::Animated { from, to, anim } => Scalar::Animated { from, to, anim },
+                })
+            }
+        }
+
+        d.deserialize_any(ScalarVisitor)
+    }
+}
+
+impl Scalar {
+    /// The value at `frame`: fixed as given, animated interpolated linearly
+    /// over its animation's span (held at `from` when the animation is missing).
+    pub fn evaluate(&self, frame: usize, anims: &AnimSpans) -> f64 {
+        match *self {
+            Scalar::Fixed(v) => v,
+            Scalar::Animated { from, to, anim } => {
+                let Some(span) = anims.span(anim) else {
+                    return from;
+                };
+                let start = span.start;
+                let end_frame = span.end.saturating_sub(1);
+                if frame <= start {
+                    return from;
+                }
+                if frame >= end_frame {
+                    return to;
+                }
+                let progress = (frame - start) as f64 / (end_frame - start) as f64;
+                from + (to - from) * progress
+            }
+        }
+    }
+
+    /// The value independent of animation timing — `Animated` at its `from`.
+    pub fn start_value(&self) -> f64 {
+        match *self {
+            Scalar::Fixed(v) => v,
+            Scalar::Animated { from, .. } => from,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FrameRange {
     pub start: usize,
@@ -0,0 +1,131 @@
+//! asciinema v2 cast export.
+//!
+//! A header line (`{"version":2,"width":…,"height":…}`) followed by one output
+//! event per frame at `i / fps` seconds. The first frame clears the screen and
+//! paints every row; later frames only repaint the cells their diff changed, so
+//! the cast stays about as small as the compiled deck.
+//!
+//! [`CastRecorder`] writes the same events live, for `bs play --record`: each
+//! frame the presenter shows, stamped with the time since recording began.
+
+use std::io::{self, Write};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use serde_json::json;
+
+use super::ExportOptions;
+use crate::renderer::ansi::{osc8, row_to_ansi, sgr, RESET};
+use crate::renderer::Renderer;
+use crate::types::{char_width, Cell, CellChange, Frame, PlayablePresentation, TerminalContract};
+
+/// Render `p` as an asciinema v2 cast.
+pub fn to_cast(p: &PlayablePresentation, opts: &ExportOptions) -> String {
+    let header = json!({
+        "version": 2,
+        "width": p.contract.width,
+        "height": p.contract.height,
+    });
+    let mut out = format!("{header}\n");
+    for (i, frame) in p.frames.iter().enumerate() {
+        let data = match frame {
+            Frame::Full { cells } => full_to_ansi(cells),
+            Frame::Diff { changes } => diff_to_ansi(changes),
+        };
+        let t = i as f64 * opts.frame_secs();
+        out += &json!([round_ms(t), "o", data]).to_string();
+        out.push('\n');
+    }
+    out
+}
+
+/// A cast written as a talk is given: one event per frame shown, at the real
+/// time it was shown. Every event is flushed as it is written, so a talk cut
+/// short still leaves a playable cast; dropping the recorder writes a last,
+/// empty event so the replay holds the final frame as long as it was up.
+pub struct CastRecorder<W: Write> {
+    out: W,
+    start: Instant,
+    prev: Option<Vec<Vec<Cell>>>,
+}
+
+impl<W: Write> CastRecorder<W> {
+    /// Write the header for a `contract`-sized cast to `out`.
+    pub fn new(mut out: W, contract: &TerminalContract) -> io::Result<Self> {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        let header = json!({
+            "version": 2,
+            "width": contract.width,
+            "height": contract.height,
+            "timestamp": timestamp,
+        });
+        writeln!(out, "{header}")?;
+        out.flush()?;
+        Ok(CastRecorder { out, start: Instant::now(), prev: None })
+    }
+
+    /// Record `grid` as shown now.
+    pub fn record(&mut self, grid: &[Vec<Cell>]) -> io::Result<()> {
+        self.record_at(self.start.elapsed().as_secs_f64(), grid)
+    }
+
+    /// Record `grid` as shown `secs` after recording began: in full the first
+    /// time, then as its diff from the grid recorded before.
+    pub fn record_at(&mut self, secs: f64, grid: &[Vec<Cell>]) -> io::Result<()> {
+        let data = match &self.prev {
+            Some(prev) => diff_to_ansi(&Renderer::diff(prev, grid)),
+            None => full_to_ansi(grid),
+        };
+        self.prev = Some(grid.to_vec());
+        self.event(secs, &data)
+    }
+
+    fn event(&mut self, secs: f64, data: &str) -> io::Result<()> {
+        writeln!(self.out, "{}", json!([round_ms(secs), "o", data]))?;
+        self.out.flush()
+    }
+}
+
+impl<W: Write> Drop for CastRecorder<W> {
+    fn drop(&mut self) {
+        let _ = self.event(self.start.elapsed().as_secs_f64(), "");
+    }
+}
+
+/// Clear the screen and paint every row.
+fn full_to_ansi(cells: &[Vec<Cell>]) -> String {
+    let mut s = String::from("\x1b[2J");
+    for (y, row) in cells.iter().enumerate() {
+        s += &format!("\x1b[{};1H", y + 1);
+        s += &row_to_ansi(row);
+    }
+    s
+}
+
+/// Timestamps to the millisecond, so `0.1 * 3` is written as `0.3`.
+fn round_ms(t: f64) -> f64 {
+    (t * 1000.0).round() / 1000.0
+}
+
+/// Cursor-addressed repaint of just the changed cells. Horizontally adjacent
+/// changes share one cursor move; each cell selects its own style (and link).
+fn diff_to_ansi(changes: &[CellChange]) -> String {
+    let mut sorted: Vec<&CellChange> = changes.iter().collect();
+    sorted.sort_by_key(|c| (c.y, c.x));
+    let mut out = String::new();
+    let mut next: Option<(u16, u16)> = None;
+    for c in sorted.into_iter().filter(|c| !c.cell.is_continuation()) {
+        if next != Some((c.x, c.y)) {
+            out += &format!("\x1b[{};{}H", c.y + 1, c.x + 1);
+        }
+        out += &sgr(&c.cell.style);
+        match &c.cell.style.link {
+            Some(url) => out += &format!("{}{}{}", osc8(Some(url)), c.cell.ch, osc8(None)),
+            None => out.push(c.cell.ch),
+        }
+        next = Some((c.x + char_width(c.cell.ch) as u16, c.y));
+    }
+    if !out.is_empty() {
+        out += RESET;
+    }
+    out
+}
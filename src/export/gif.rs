@@ -0,0 +1,103 @@
+//! Animated GIF export.
+//!
+//! Glyphs come from the built-in `font8x8` bitmaps (ASCII, Latin-1, box
+//! drawing, block elements, Greek), each 8×8 bitmap row doubled to give the
+//! 1:2 cell of a real terminal; `--scale` multiplies the cell size. Colours are
+//! quantized to the xterm 256-colour palette, which is also the GIF's global
+//! palette. Characters outside the font render as blanks.
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use font8x8::{UnicodeFonts, BASIC_FONTS, BLOCK_FONTS, BOX_FONTS, GREEK_FONTS, LATIN_FONTS};
+
+use super::{for_each_grid, style_rgb, ExportOptions};
+use crate::types::{nearest_xterm, xterm_palette, Cell, PlayablePresentation};
+
+/// Cell size in pixels at `--scale 1`.
+const CELL_W: usize = 8;
+const CELL_H: usize = 16;
+
+/// Nearest-palette-entry lookup, memoized per colour.
+struct Quantizer {
+    cache: HashMap<(u8, u8, u8), u8>,
+}
+
+impl Quantizer {
+    fn new() -> Self {
+        Quantizer { cache: HashMap::new() }
+    }
+
+    fn index(&mut self, c: (u8, u8, u8)) -> u8 {
+        *self.cache.entry(c).or_insert_with(|| nearest_xterm(c, 0..256))
+    }
+}
+
+/// The 8×8 bitmap for `ch` (bit 0 = leftmost pixel), shared with the PDF font.
+pub(super) fn glyph(ch: char) -> Option<[u8; 8]> {
+    BASIC_FONTS
+        .get(ch)
+        .or_else(|| LATIN_FONTS.get(ch))
+        .or_else(|| BOX_FONTS.get(ch))
+        .or_else(|| BLOCK_FONTS.get(ch))
+        .or_else(|| GREEK_FONTS.get(ch))
+}
+
+/// Rasterize one frame into palette indices (`width × height` pixels).
+fn rasterize(grid: &[Vec<Cell>], scale: usize, q: &mut Quantizer) -> Vec<u8> {
+    let (cw, ch) = (CELL_W * scale, CELL_H * scale);
+    let cols = grid.first().map_or(0, |r| r.len());
+    let width = cols * cw;
+    let mut pixels = vec![0u8; width * grid.len() * ch];
+    for (y, row) in grid.iter().enumerate() {
+        for (x, cell) in row.iter().enumerate() {
+            let (fg, bg) = style_rgb(&cell.style);
+            let (fg, bg) = (q.index(fg), q.index(bg));
+            let bits = glyph(cell.ch).unwrap_or([0; 8]);
+            for py in 0..ch {
+                let mut line = bits[py / (2 * scale)];
+                if cell.style.bold {
+                    // Smear one pixel right for a heavier stroke.
+                    line |= line << 1;
+                }
+                if cell.style.underline && py >= ch - scale {
+                    // The bottom pixel row is the underline.
+                    line = 0xff;
+                }
+                let base = (y * ch + py) * width + x * cw;
+                for px in 0..cw {
+                    let on = line & (1 << (px / scale)) != 0;
+                    pixels[base + px] = if on { fg } else { bg };
+                }
+            }
+        }
+    }
+    pixels
+}
+
+/// Render `p` as an animated, infinitely-looping GIF.
+pub fn to_gif(p: &PlayablePresentation, opts: &ExportOptions) -> Result<Vec<u8>> {
+    let scale = (opts.scale.round() as usize).max(1);
+    let width = p.contract.width as usize * CELL_W * scale;
+    let height = p.contract.height as usize * CELL_H * scale;
+    let (w, h) = (
+        u16::try_from(width).context("GIF too wide; lower --scale")?,
+        u16::try_from(height).context("GIF too tall; lower --scale")?,
+    );
+    let mut q = Quantizer::new();
+    let palette: Vec<u8> = xterm_palette().iter().flat_map(|&(r, g, b)| [r, g, b]).collect();
+    let delay = (100.0 * opts.frame_secs()).round().clamp(1.0, u16::MAX as f64) as u16;
+
+    let mut bytes = Vec::new();
+    {
+        let mut encoder = ::gif::Encoder::new(&mut bytes, w, h, &palette)?;
+        encoder.set_repeat(::gif::Repeat::Infinite)?;
+        for_each_grid(p, |_, grid| {
+            let mut frame = ::gif::Frame::from_indexed_pixels(w, h, rasterize(grid, scale, &mut q), None);
+            frame.delay = delay;
+            encoder.write_frame(&frame)?;
+            Ok(())
+        })?;
+    }
+    Ok(bytes)
+}
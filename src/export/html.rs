@@ -0,0 +1,116 @@
+//! Self-contained HTML export.
+//!
+//! Every frame is pre-rendered into its own hidden `<pre>` of styled `<span>`s;
+//! a few lines of inline script show one at a time. ←/→ (or PageUp/PageDown)
+//! step, Home/End jump, Space toggles autoplay at `--fps`. No external assets,
+//! so the file can be mailed around or dropped on any static host. The deck's
+//! `metadata` becomes the page's `<title>` and `author`/`date`/`description`
+//! `<meta>` tags.
+
+use super::{escape_xml, for_each_grid, hex, style_runs, style_rgb, ExportOptions, DEFAULT_BG, DEFAULT_FG};
+use crate::types::{Cell, Metadata, PlayablePresentation, Style};
+
+const SCRIPT: &str = r#"(function () {
+  var frames = document.querySelectorAll("pre.frame");
+  var cur = 0, timer = null;
+  function show(i) {
+    cur = Math.max(0, Math.min(frames.length - 1, i));
+    for (var k = 0; k < frames.length; k++) frames[k].hidden = k !== cur;
+  }
+  function toggle() {
+    if (timer) { clearInterval(timer); timer = null; return; }
+    timer = setInterval(function () { show(cur + 1 < frames.length ? cur + 1 : 0); }, DELAY);
+  }
+  document.addEventListener("keydown", function (e) {
+    switch (e.key) {
+      case "ArrowRight": case "PageDown": show(cur + 1); break;
+      case "ArrowLeft": case "PageUp": show(cur - 1); break;
+      case "Home": show(0); break;
+      case "End": show(frames.length - 1); break;
+      case " ": toggle(); break;
+      default: return;
+    }
+    e.preventDefault();
+  });
+  show(0);
+})();"#;
+
+/// Render `p` as a standalone HTML page.
+pub fn to_html(p: &PlayablePresentation, opts: &ExportOptions) -> String {
+    let size = 16.0 * opts.scale;
+    let mut frames = String::new();
+    for_each_grid(p, |i, grid| {
+        frames += &format!("<pre class=\"frame\"{}>", if i == 0 { "" } else { " hidden" });
+        frames += &grid_to_spans(grid);
+        frames += "</pre>\n";
+        Ok(())
+    })
+    .expect("collecting frames is infallible");
+    let delay_ms = (opts.frame_secs() * 1000.0).round() as u64;
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n{head}<style>\n\
+         body {{ margin: 0; background: {bg}; color: {fg}; }}\n\
+         pre.frame {{ margin: 0; font-family: {font}; font-size: {size:.1}px; line-height: 1.2; }}\n\
+         </style>\n</head>\n<body>\n{frames}<script>\n{script}\n</script>\n</body>\n</html>\n",
+        head = head(&p.metadata),
+        bg = hex(DEFAULT_BG),
+        fg = hex(DEFAULT_FG),
+        font = escape_xml(&opts.font),
+        script = SCRIPT.replace("DELAY", &delay_ms.to_string()),
+    )
+}
+
+/// The page's `<title>` (the deck's, else a generic one) and a `<meta>` per
+/// other metadata field.
+fn head(metadata: &Metadata) -> String {
+    let title = metadata.title.as_deref().unwrap_or("bs presentation");
+    let mut head = format!("<title>{}</title>\n", escape_xml(title));
+    for (name, value) in [("author", &metadata.author), ("date", &metadata.date), ("description", &metadata.description)] {
+        if let Some(value) = value {
+            head += &format!("<meta name=\"{name}\" content=\"{}\">\n", escape_xml(value));
+        }
+    }
+    head
+}
+
+/// One frame as `<pre>` content: unstyled runs as plain text, styled runs
+/// wrapped in a `<span>` with inline colours and linked runs in an `<a>`.
+fn grid_to_spans(grid: &[Vec<Cell>]) -> String {
+    let mut out = String::new();
+    for (y, row) in grid.iter().enumerate() {
+        if y > 0 {
+            out.push('\n');
+        }
+        for (_, style, text) in style_runs(row) {
+            if let Some(url) = &style.link {
+                out += &format!("<a href=\"{}\">", escape_xml(url));
+            }
+            let unlinked = Style { link: None, ..style.clone() };
+            if unlinked.is_default() {
+                out += &escape_xml(&text);
+            } else {
+                out += &styled_span(&unlinked, &text);
+            }
+            if style.link.is_some() {
+                out += "</a>";
+            }
+        }
+    }
+    out
+}
+
+/// A styled run as a `<span>` with inline colours.
+fn styled_span(style: &Style, text: &str) -> String {
+    let (fg, bg) = style_rgb(style);
+    let mut css = format!("color:{}", hex(fg));
+    if style.bg.is_some() {
+        css += &format!(";background:{}", hex(bg));
+    }
+    if style.bold {
+        css += ";font-weight:bold";
+    }
+    if style.underline {
+        css += ";text-decoration:underline";
+    }
+    format!("<span style=\"{css}\">{}</span>", escape_xml(text))
+}
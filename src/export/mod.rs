@@ -0,0 +1,323 @@
+//! Exporters — `bs export <format> <input> <output>`.
+//!
+//! One dispatcher for every non-terminal output of a deck, instead of a pile of
+//! flags on `compile`. Each format lives in its own module and works on the
+//! compiled `PlayablePresentation` (the input may be a source or a compiled
+//! file; see [`pipeline::load_playable`](crate::pipeline::load_playable)), so an
+//! export always shows exactly what `bs play` would.
+//!
+//! All formats share one [`ExportOptions`]: `--fps` (frame timing for the
+//! time-based formats), `--font` (CSS font family for SVG/HTML) and `--scale`
+//! (font-size multiplier for SVG/HTML, integer pixel scale for GIF). Options a
+//! format has no use for are ignored (PDF, with its fixed page, takes none). `--show-layer` / `--hide-layer` override
+//! the source deck's layer visibility for this export only (e.g. hide a
+//! `notes` layer from the audience copy).
+
+mod cast;
+mod gif;
+mod html;
+mod pdf;
+mod svg;
+mod text;
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+
+use crate::pipeline::{self, write_output, STDIO};
+use crate::types::{Cell, Frame, PlayablePresentation, Style};
+
+pub use cast::{to_cast, CastRecorder};
+pub use self::gif::to_gif;
+pub use html::to_html;
+pub use pdf::to_pdf;
+pub use svg::to_svg;
+pub use text::{to_text, wants_ansi};
+
+/// The output formats `bs export` knows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// asciinema v2 cast — replays the deck at `fps` in any cast player.
+    Cast,
+    /// Animated GIF rendered with a built-in 8×8 bitmap font.
+    Gif,
+    /// One SVG image per frame (`out-001.svg`, …; a single-frame deck writes
+    /// exactly `out.svg`).
+    Svg,
+    /// A self-contained HTML page with keyboard navigation and autoplay.
+    Html,
+    /// One A4-landscape page per frame, text in an embedded bitmap font.
+    Pdf,
+    /// One text file per frame, numbered like SVG: plain `.txt`, or ANSI
+    /// colours when the output ends in `.ans`.
+    Text,
+}
+
+impl ExportFormat {
+    pub const ALL: &'static [ExportFormat] =
+        &[ExportFormat::Cast, ExportFormat::Gif, ExportFormat::Svg, ExportFormat::Html, ExportFormat::Pdf, ExportFormat::Text];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ExportFormat::Cast => "cast",
+            ExportFormat::Gif => "gif",
+            ExportFormat::Svg => "svg",
+            ExportFormat::Html => "html",
+            ExportFormat::Pdf => "pdf",
+            ExportFormat::Text => "text",
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match ExportFormat::ALL.iter().find(|f| f.as_str() == s) {
+            Some(f) => Ok(*f),
+            None => {
+                let known: Vec<&str> = ExportFormat::ALL.iter().map(|f| f.as_str()).collect();
+                bail!("unknown export format `{s}` (expected one of: {})", known.join(", "))
+            }
+        }
+    }
+}
+
+/// Options shared by every exporter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportOptions {
+    /// Frames per second for time-based formats (cast timestamps, GIF delay,
+    /// HTML autoplay).
+    pub fps: f64,
+    /// CSS font family for SVG/HTML.
+    pub font: String,
+    /// Font-size multiplier (SVG/HTML) or pixel scale (GIF, rounded).
+    pub scale: f64,
+    /// Layers to include even if the deck hides them.
+    pub show_layers: Vec<String>,
+    /// Layers to leave out even if the deck shows them.
+    pub hide_layers: Vec<String>,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        ExportOptions {
+            fps: 1.0,
+            font: "monospace".into(),
+            scale: 1.0,
+            show_layers: Vec::new(),
+            hide_layers: Vec::new(),
+        }
+    }
+}
+
+impl ExportOptions {
+    /// Seconds each frame stays on screen.
+    pub fn frame_secs(&self) -> f64 {
+        1.0 / self.fps
+    }
+}
+
+/// Export the deck at `input` (source or compiled) to `output` in `format`.
+/// Either may be `-` for stdin/stdout (except a multi-frame SVG export, which
+/// needs a path to number).
+pub fn export_file(format: ExportFormat, input: &str, output: &str, opts: &ExportOptions) -> Result<()> {
+    let p = load_with_layers(input, opts)?;
+    let written = match format {
+        ExportFormat::Cast => {
+            write_output(output, to_cast(&p, opts))?;
+            1
+        }
+        ExportFormat::Html => {
+            write_output(output, to_html(&p, opts))?;
+            1
+        }
+        ExportFormat::Gif => {
+            write_output(output, to_gif(&p, opts)?)?;
+            1
+        }
+        ExportFormat::Pdf => {
+            write_output(output, to_pdf(&p)?)?;
+            1
+        }
+        ExportFormat::Svg | ExportFormat::Text if output == STDIO && p.frames.len() > 1 => {
+            bail!("{format} writes one file per frame; give an output path, not `-`, for a multi-frame deck")
+        }
+        ExportFormat::Svg => {
+            let paths = frame_paths(Path::new(output), p.frames.len());
+            let mut n = 0;
+            for_each_grid(&p, |i, grid| {
+                write_output(&paths[i].to_string_lossy(), to_svg(grid, opts))?;
+                n += 1;
+                Ok(())
+            })?;
+            n
+        }
+        ExportFormat::Text => {
+            let ansi = wants_ansi(Path::new(output));
+            let paths = frame_paths(Path::new(output), p.frames.len());
+            for_each_grid(&p, |i, grid| write_output(&paths[i].to_string_lossy(), to_text(grid, ansi)))?;
+            paths.len()
+        }
+    };
+    eprintln!(
+        "Exported {} frames from {input} -> {output} ({format}{})",
+        p.frames.len(),
+        if written > 1 { format!(", {written} files") } else { String::new() },
+    );
+    Ok(())
+}
+
+/// Load `input` for export. With layer overrides the deck must be a source
+/// (a compiled file has its layers baked in): the overrides are applied to its
+/// registry and it is compiled afresh. Exports never show overlays unless
+/// `--show-layer` names them.
+fn load_with_layers(input: &str, opts: &ExportOptions) -> Result<PlayablePresentation> {
+    if opts.show_layers.is_empty() && opts.hide_layers.is_empty() {
+        return pipeline::load_playable(input);
+    }
+    let bytes = pipeline::read_input_bytes(input)?;
+    let value: Option<serde_json::Value> = (!pipeline::is_binary(&bytes))
+        .then(|| serde_json::from_slice(&bytes))
+        .transpose()
+        .with_context(|| format!("Failed to parse {input}"))?;
+    let Some(value) = value.filter(|v| v.get("contract").is_none()) else {
+        bail!("{input} is already compiled; --show-layer / --hide-layer need the source deck");
+    };
+    let mut source = crate::migrate::source_from_value(value)
+        .with_context(|| format!("Failed to parse {input} as a source presentation"))?;
+    pipeline::prepare_source(&mut source, input)?;
+    // A shown overlay layer is baked into the frames like any other.
+    for name in &opts.show_layers {
+        let layer = source.layer_entry(name);
+        layer.visible = true;
+        layer.overlay = false;
+    }
+    for name in &opts.hide_layers {
+        source.layer_entry(name).visible = false;
+    }
+    pipeline::compile(&source).with_context(|| format!("Failed to compile {input}"))
+}
+
+/// Output paths for a one-file-per-frame format: `out.svg` → `out-001.svg`,
+/// `out-002.svg`, … (zero-padded to at least three digits). A single-frame deck
+/// writes exactly `output`.
+pub fn frame_paths(output: &Path, count: usize) -> Vec<PathBuf> {
+    if count == 1 {
+        return vec![output.to_path_buf()];
+    }
+    let digits = count.to_string().len().max(3);
+    let stem = output.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let ext = output.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+    (1..=count)
+        .map(|i| output.with_file_name(format!("{stem}-{i:0digits$}{ext}")))
+        .collect()
+}
+
+/// Visit every frame's full grid in order, replaying the diffs incrementally
+/// (one pass, unlike calling `grid_at` per frame).
+pub fn for_each_grid(
+    p: &PlayablePresentation,
+    mut f: impl FnMut(usize, &[Vec<Cell>]) -> Result<()>,
+) -> Result<()> {
+    let mut replay = GridReplay::new(p);
+    let mut i = 0;
+    while let Some(grid) = replay.next_grid() {
+        f(i, grid)?;
+        i += 1;
+    }
+    Ok(())
+}
+
+/// A deck's frames replayed one at a time — [`for_each_grid`] for walking
+/// several decks side by side.
+pub struct GridReplay<'a> {
+    frames: std::slice::Iter<'a, Frame>,
+    grid: Vec<Vec<Cell>>,
+}
+
+impl<'a> GridReplay<'a> {
+    pub fn new(p: &'a PlayablePresentation) -> Self {
+        GridReplay { frames: p.frames.iter(), grid: p.grid_at(0) }
+    }
+
+    /// The next frame's full grid; `None` past the last frame.
+    pub fn next_grid(&mut self) -> Option<&[Vec<Cell>]> {
+        match self.frames.next()? {
+            Frame::Full { cells } => self.grid = cells.clone(),
+            Frame::Diff { changes } => {
+                for c in changes {
+                    let (x, y) = (c.x as usize, c.y as usize);
+                    if y < self.grid.len() && x < self.grid[y].len() {
+                        self.grid[y][x] = c.cell.clone();
+                    }
+                }
+            }
+        }
+        Some(&self.grid)
+    }
+}
+
+/// Foreground for cells with no `fg` in the graphical formats (a terminal's
+/// default text colour is unknowable, so pick a neutral light grey).
+pub const DEFAULT_FG: (u8, u8, u8) = (208, 208, 208);
+/// Background for cells with no `bg` in the graphical formats.
+pub const DEFAULT_BG: (u8, u8, u8) = (0, 0, 0);
+
+/// Concrete `(fg, bg)` RGB for a cell style in the graphical formats; `dim`
+/// halves the foreground's distance to the background.
+pub fn style_rgb(style: &Style) -> ((u8, u8, u8), (u8, u8, u8)) {
+    let bg = style.bg.as_ref().map_or(DEFAULT_BG, |c| c.rgb());
+    let mut fg = style.fg.as_ref().map_or(DEFAULT_FG, |c| c.rgb());
+    if style.dim {
+        let mix = |a: u8, b: u8| ((a as u16 + b as u16) / 2) as u8;
+        fg = (mix(fg.0, bg.0), mix(fg.1, bg.1), mix(fg.2, bg.2));
+    }
+    (fg, bg)
+}
+
+/// `#rrggbb` for an RGB triple.
+pub fn hex(c: (u8, u8, u8)) -> String {
+    format!("#{:02x}{:02x}{:02x}", c.0, c.1, c.2)
+}
+
+/// Escape text for XML/HTML content and attribute values.
+pub fn escape_xml(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Split a row into maximal runs of identically-styled cells:
+/// `(start column, style, text)`. Continuation cells add no text (the wide
+/// glyph before them spans both columns).
+pub fn style_runs(row: &[Cell]) -> Vec<(usize, &Style, String)> {
+    let mut runs: Vec<(usize, &Style, String)> = Vec::new();
+    for (x, cell) in row.iter().enumerate() {
+        if cell.is_continuation() {
+            continue;
+        }
+        match runs.last_mut() {
+            Some((_, style, text)) if std::ptr::eq(*style, &*cell.style) => text.push(cell.ch),
+            _ => runs.push((x, &*cell.style, cell.ch.to_string())),
+        }
+    }
+    runs
+}
@@ -0,0 +1,328 @@
+//! PDF export — one fixed-size page per frame.
+//!
+//! Every page is A4 landscape with the frame scaled to fit and centred on the
+//! default background. Text is set in an embedded Type 3 font built from the
+//! same `font8x8` bitmaps as the GIF export (each set pixel a filled square,
+//! each bitmap row doubled for the 1:2 cell), so the file needs no system font
+//! and prints the same everywhere; a `ToUnicode` map keeps it searchable.
+//! Characters outside the font render as blanks. Streams are left
+//! uncompressed — there is no deflate encoder in the tree. The deck's
+//! `metadata` fills the document information dictionary.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use super::gif::glyph;
+use super::{for_each_grid, style_rgb, DEFAULT_BG};
+use crate::types::{Cell, Metadata, PlayablePresentation, StyleRef};
+
+/// Page size in points (A4 landscape).
+pub const PAGE_W: f64 = 842.0;
+pub const PAGE_H: f64 = 595.0;
+/// Blank border kept around the frame.
+const MARGIN: f64 = 36.0;
+
+/// A single-byte Type 3 font holds this many glyphs; a deck drawing with
+/// more distinct characters spreads them over several fonts.
+const CODES_PER_FONT: usize = 256;
+
+/// Where a frame lands on the page: cell size and the grid's top-left corner.
+struct Geometry {
+    cell_w: f64,
+    cell_h: f64,
+    left: f64,
+    top: f64,
+}
+
+/// The embedded glyphs, numbered in first-use order: glyph `i` is code
+/// `i % 256` of font `i / 256`.
+#[derive(Default)]
+struct Glyphs {
+    chars: Vec<char>,
+    index: HashMap<char, usize>,
+}
+
+impl Glyphs {
+    /// `(font, code)` for `ch`, embedding it on first use; `None` when the
+    /// bitmap font lacks it.
+    fn code(&mut self, ch: char) -> Option<(usize, u8)> {
+        let i = match self.index.get(&ch) {
+            Some(&i) => i,
+            None => {
+                glyph(ch)?;
+                self.chars.push(ch);
+                self.index.insert(ch, self.chars.len() - 1);
+                self.chars.len() - 1
+            }
+        };
+        Some((i / CODES_PER_FONT, (i % CODES_PER_FONT) as u8))
+    }
+}
+
+/// A PDF number: two decimals, trailing zeros dropped.
+fn num(v: f64) -> String {
+    let s = format!("{v:.2}");
+    s.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// `r g b` operands (0–1) for an RGB triple.
+fn rgb(c: (u8, u8, u8)) -> String {
+    let f = |v: u8| num(v as f64 / 255.0);
+    format!("{} {} {}", f(c.0), f(c.1), f(c.2))
+}
+
+/// One frame's content stream: background, cell backgrounds, text runs (set
+/// once more, nudged right, when bold) and underlines.
+fn page_content(grid: &[Vec<Cell>], g: &Geometry, glyphs: &mut Glyphs) -> String {
+    let mut s = format!("{} rg 0 0 {} {} re f\n", rgb(DEFAULT_BG), num(PAGE_W), num(PAGE_H));
+    for (y, row) in grid.iter().enumerate() {
+        let base = g.top - (y + 1) as f64 * g.cell_h;
+        let x_at = |x: usize| g.left + x as f64 * g.cell_w;
+
+        // Backgrounds, merged across equal neighbours.
+        let mut x = 0;
+        while x < row.len() {
+            if row[x].style.bg.is_none() {
+                x += 1;
+                continue;
+            }
+            let bg = style_rgb(&row[x].style).1;
+            let start = x;
+            while x < row.len() && row[x].style.bg.is_some() && style_rgb(&row[x].style).1 == bg {
+                x += 1;
+            }
+            let w = (x - start) as f64 * g.cell_w;
+            let _ = writeln!(s, "{} rg {} {} {} {} re f", rgb(bg), num(x_at(start)), num(base), num(w), num(g.cell_h));
+        }
+
+        // Text: runs of one style and one font, broken at blanks, continuation
+        // cells and characters the font lacks.
+        let mut run: Option<(usize, usize, StyleRef, String)> = None;
+        let flush = |run: &mut Option<(usize, usize, StyleRef, String)>, s: &mut String| {
+            let Some((start, font, style, hex)) = run.take() else { return };
+            let fg = rgb(style_rgb(&style).0);
+            let passes: &[f64] = if style.bold { &[0.0, 0.125] } else { &[0.0] };
+            for dx in passes {
+                let _ = writeln!(
+                    s,
+                    "BT /F{font} {} Tf {fg} rg {} {} Td <{hex}> Tj ET",
+                    num(g.cell_h),
+                    num(x_at(start) + dx * g.cell_w),
+                    num(base),
+                );
+            }
+        };
+        for (x, cell) in row.iter().enumerate() {
+            let code = (!cell.is_continuation() && cell.ch != ' ').then(|| glyphs.code(cell.ch)).flatten();
+            let Some((font, code)) = code else {
+                flush(&mut run, &mut s);
+                continue;
+            };
+            match &mut run {
+                Some((_, f, style, hex)) if *f == font && *style == cell.style => {
+                    let _ = write!(hex, "{code:02x}");
+                }
+                _ => {
+                    flush(&mut run, &mut s);
+                    run = Some((x, font, cell.style.clone(), format!("{code:02x}")));
+                }
+            }
+        }
+        flush(&mut run, &mut s);
+
+        // Underlines: the bottom pixel row of the cell, as in the GIF.
+        for (x, cell) in row.iter().enumerate() {
+            if cell.style.underline && !cell.is_continuation() {
+                let _ = writeln!(
+                    s,
+                    "{} rg {} {} {} {} re f",
+                    rgb(style_rgb(&cell.style).0),
+                    num(x_at(x)),
+                    num(base),
+                    num(g.cell_w),
+                    num(g.cell_h / 16.0),
+                );
+            }
+        }
+    }
+    s
+}
+
+/// A glyph procedure: the bitmap's set pixels as filled rectangles in an
+/// 8×16 glyph space (rows doubled), horizontal runs merged.
+fn glyph_proc(bits: [u8; 8]) -> String {
+    let mut s = String::from("8 0 0 0 8 16 d1\n");
+    let mut any = false;
+    for (r, line) in bits.iter().enumerate() {
+        let y = 14 - 2 * r;
+        let mut px = 0;
+        while px < 8 {
+            if line & (1 << px) == 0 {
+                px += 1;
+                continue;
+            }
+            let start = px;
+            while px < 8 && line & (1 << px) != 0 {
+                px += 1;
+            }
+            let _ = writeln!(s, "{start} {y} {} 2 re", px - start);
+            any = true;
+        }
+    }
+    if any {
+        s.push_str("f\n");
+    }
+    s
+}
+
+/// A `ToUnicode` CMap for one font's codes, so viewers can search and copy.
+fn to_unicode(chars: &[char]) -> String {
+    let mut s = String::from(
+        "/CIDInit /ProcSet findresource begin\n12 dict begin\nbegincmap\n\
+         /CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def\n\
+         /CMapName /Adobe-Identity-UCS def\n/CMapType 2 def\n\
+         1 begincodespacerange\n<00> <FF>\nendcodespacerange\n",
+    );
+    // At most 100 entries per bfchar block.
+    for (block, chunk) in chars.chunks(100).enumerate() {
+        let _ = writeln!(s, "{} beginbfchar", chunk.len());
+        for (i, ch) in chunk.iter().enumerate() {
+            let mut units = [0u16; 2];
+            let hex: String = ch.encode_utf16(&mut units).iter().map(|u| format!("{u:04X}")).collect();
+            let _ = writeln!(s, "<{:02X}> <{hex}>", block * 100 + i);
+        }
+        s.push_str("endbfchar\n");
+    }
+    s.push_str("endcmap\nCMapName currentdict /CMap defineresource pop\nend\nend\n");
+    s
+}
+
+/// The document information dictionary for the deck's `metadata`, if it sets
+/// anything: `/Title`, `/Author`, the description as `/Subject`, and a date
+/// written `YYYY[-MM[-DD]]` as `/CreationDate` (other dates are free text a
+/// PDF date can't hold).
+fn info_dict(metadata: &Metadata) -> Option<String> {
+    let mut entries = String::new();
+    for (key, value) in [("Title", &metadata.title), ("Author", &metadata.author), ("Subject", &metadata.description)] {
+        if let Some(value) = value {
+            let _ = write!(entries, "/{key} {} ", text_string(value));
+        }
+    }
+    if let Some(date) = metadata.date.as_deref().and_then(pdf_date) {
+        let _ = write!(entries, "/CreationDate ({date}) ");
+    }
+    (!entries.is_empty()).then(|| format!("<< {entries}>>"))
+}
+
+/// A PDF text string: UTF-16BE behind a byte-order mark, hex-encoded, so any
+/// character survives without escaping.
+fn text_string(s: &str) -> String {
+    let mut hex = String::from("<FEFF");
+    for unit in s.encode_utf16() {
+        let _ = write!(hex, "{unit:04X}");
+    }
+    hex.push('>');
+    hex
+}
+
+/// `D:YYYYMMDD` (or a shorter prefix) for a `YYYY`, `YYYY-MM` or `YYYY-MM-DD`
+/// date.
+fn pdf_date(date: &str) -> Option<String> {
+    let parts: Vec<&str> = date.trim().split('-').collect();
+    let well_formed = parts.len() <= 3
+        && parts.iter().zip([4, 2, 2]).all(|(p, w)| p.len() == w && p.bytes().all(|b| b.is_ascii_digit()));
+    well_formed.then(|| format!("D:{}", parts.concat()))
+}
+
+fn stream(data: &str) -> String {
+    format!("<< /Length {} >>\nstream\n{data}\nendstream", data.len())
+}
+
+/// Render `p` as a PDF document with one A4-landscape page per frame.
+pub fn to_pdf(p: &PlayablePresentation) -> Result<Vec<u8>> {
+    let (cols, rows) = (p.contract.width.max(1) as f64, p.contract.height.max(1) as f64);
+    let cell_w = ((PAGE_W - 2.0 * MARGIN) / cols).min((PAGE_H - 2.0 * MARGIN) / (2.0 * rows));
+    let g = Geometry {
+        cell_w,
+        cell_h: 2.0 * cell_w,
+        left: (PAGE_W - cols * cell_w) / 2.0,
+        top: (PAGE_H + rows * 2.0 * cell_w) / 2.0,
+    };
+    let mut glyphs = Glyphs::default();
+    let mut contents = Vec::new();
+    for_each_grid(p, |_, grid| {
+        contents.push(page_content(grid, &g, &mut glyphs));
+        Ok(())
+    })?;
+
+    // Objects 1–3 are the catalog, page tree and shared resources; then a
+    // page and its content per frame; then each font's dictionary, ToUnicode
+    // map and glyph procedures; last, the document information, if any.
+    let first_page = 4;
+    let font_chunks: Vec<&[char]> = glyphs.chars.chunks(CODES_PER_FONT).collect();
+    let mut objects: Vec<String> = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".into(),
+        format!(
+            "<< /Type /Pages /Count {} /Kids [{}] >>",
+            contents.len(),
+            (0..contents.len()).map(|i| format!("{} 0 R", first_page + 2 * i)).collect::<Vec<_>>().join(" ")
+        ),
+        String::new(), // resources, once the font objects are numbered
+    ];
+    for (i, content) in contents.iter().enumerate() {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources 3 0 R /Contents {} 0 R >>",
+            num(PAGE_W),
+            num(PAGE_H),
+            first_page + 2 * i + 1
+        ));
+        objects.push(stream(content));
+    }
+    let mut font_refs = String::new();
+    for (f, chars) in font_chunks.iter().enumerate() {
+        let font_obj = objects.len() + 1;
+        let procs: String =
+            (0..chars.len()).map(|c| format!("/g{c} {} 0 R", font_obj + 2 + c)).collect::<Vec<_>>().join(" ");
+        let names: String = (0..chars.len()).map(|c| format!("/g{c}")).collect::<Vec<_>>().join(" ");
+        objects.push(format!(
+            "<< /Type /Font /Subtype /Type3 /FontBBox [0 0 8 16] /FontMatrix [0.0625 0 0 0.0625 0 0] \
+             /CharProcs << {procs} >> /Encoding << /Type /Encoding /Differences [0 {names}] >> \
+             /FirstChar 0 /LastChar {} /Widths [{}] /Resources << >> /ToUnicode {} 0 R >>",
+            chars.len() - 1,
+            vec!["8"; chars.len()].join(" "),
+            font_obj + 1
+        ));
+        objects.push(stream(&to_unicode(chars)));
+        for &ch in *chars {
+            objects.push(stream(&glyph_proc(glyph(ch).unwrap_or([0; 8]))));
+        }
+        let _ = write!(font_refs, "/F{f} {font_obj} 0 R ");
+    }
+    objects[2] = format!("<< /Font << {font_refs}>> >>");
+    let info = info_dict(&p.metadata).map(|dict| {
+        objects.push(dict);
+        format!(" /Info {} 0 R", objects.len())
+    });
+
+    let mut out: Vec<u8> = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, body) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.extend_from_slice(format!("{} 0 obj\n{body}\nendobj\n", i + 1).as_bytes());
+    }
+    let xref = out.len();
+    let mut tail = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for off in offsets {
+        let _ = writeln!(tail, "{off:010} 00000 n ");
+    }
+    let _ = write!(
+        tail,
+        "trailer\n<< /Size {} /Root 1 0 R{} >>\nstartxref\n{xref}\n%%EOF\n",
+        objects.len() + 1,
+        info.unwrap_or_default()
+    );
+    out.extend_from_slice(tail.as_bytes());
+    Ok(out)
+}
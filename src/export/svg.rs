@@ -0,0 +1,58 @@
+//! SVG export — one image per frame.
+//!
+//! Cells are laid out on a fixed grid (0.6em wide, 1.2em tall): each maximal
+//! run of identically-styled cells becomes one background `<rect>` (when it has
+//! a background) and one `<text>` pinned to the run's width with `textLength`,
+//! so columns line up whatever monospace font the viewer substitutes.
+
+use super::{escape_xml, hex, style_runs, style_rgb, ExportOptions, DEFAULT_BG};
+use crate::types::Cell;
+
+/// Base font size in pixels at `--scale 1`.
+pub const FONT_SIZE: f64 = 16.0;
+
+/// Render one frame's grid as a standalone SVG document.
+pub fn to_svg(grid: &[Vec<Cell>], opts: &ExportOptions) -> String {
+    let size = FONT_SIZE * opts.scale;
+    let (cw, ch) = (size * 0.6, size * 1.2);
+    let cols = grid.first().map_or(0, |r| r.len());
+    let (w, h) = (cols as f64 * cw, grid.len() as f64 * ch);
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w:.1}\" height=\"{h:.1}\" viewBox=\"0 0 {w:.1} {h:.1}\">\n"
+    );
+    out += &format!(
+        "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+        hex(DEFAULT_BG)
+    );
+    out += &format!(
+        "<g font-family=\"{}\" font-size=\"{size:.1}\" xml:space=\"preserve\">\n",
+        escape_xml(&opts.font)
+    );
+    for (y, row) in grid.iter().enumerate() {
+        let top = y as f64 * ch;
+        for (x, style, text) in style_runs(row) {
+            let left = x as f64 * cw;
+            let len = text.chars().count() as f64 * cw;
+            let (fg, bg) = style_rgb(style);
+            if style.bg.is_some() {
+                out += &format!(
+                    "<rect x=\"{left:.1}\" y=\"{top:.1}\" width=\"{len:.1}\" height=\"{ch:.1}\" fill=\"{}\"/>\n",
+                    hex(bg)
+                );
+            }
+            if text.trim().is_empty() {
+                continue;
+            }
+            let weight = if style.bold { " font-weight=\"bold\"" } else { "" };
+            let decoration = if style.underline { " text-decoration=\"underline\"" } else { "" };
+            out += &format!(
+                "<text x=\"{left:.1}\" y=\"{:.1}\" textLength=\"{len:.1}\" lengthAdjust=\"spacingAndGlyphs\" fill=\"{}\"{weight}{decoration}>{}</text>\n",
+                top + size,
+                hex(fg),
+                escape_xml(&text)
+            );
+        }
+    }
+    out += "</g>\n</svg>\n";
+    out
+}
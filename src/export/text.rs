@@ -0,0 +1,26 @@
+//! Text export — one file per frame, either plain characters (`.txt`, trailing
+//! blanks trimmed so the files diff and grep cleanly) or, for an `.ans` output,
+//! the same ANSI escapes `bs frame --ansi` prints, ready to `cat` in a terminal.
+
+use std::path::Path;
+
+use crate::renderer::ansi::{grid_to_ansi, grid_to_plain};
+use crate::types::Cell;
+
+/// Whether `output` asks for coloured text (an `.ans` extension).
+pub fn wants_ansi(output: &Path) -> bool {
+    output.extension().is_some_and(|e| e.eq_ignore_ascii_case("ans"))
+}
+
+/// Render one frame's grid as text, newline-terminated. Plain text drops
+/// trailing spaces from each row; ANSI keeps full rows, since a trailing
+/// blank may carry a background colour.
+pub fn to_text(grid: &[Vec<Cell>], ansi: bool) -> String {
+    let mut out = if ansi {
+        grid_to_ansi(grid)
+    } else {
+        grid_to_plain(grid).lines().map(str::trim_end).collect::<Vec<_>>().join("\n")
+    };
+    out.push('\n');
+    out
+}
@@ -0,0 +1,242 @@
+//! C ABI over the compile/render pipeline (feature `ffi`).
+//!
+//! For non-Rust toolchains — Python via `ctypes`/`cffi`, CI plugins — that
+//! want the engine without shelling out to `bs`. The declarations are in
+//! `include/bs.h`; the library is the crate's `cdylib`.
+//!
+//! Conventions:
+//! - Strings in are NUL-terminated UTF-8. Strings out are allocated here and
+//!   must be released with [`bs_string_free`].
+//! - Failures return `NULL` / `false`; [`bs_last_error`] then describes the
+//!   most recent failure on the calling thread.
+//! - A [`BsDeck`] is an opaque handle from [`bs_deck_load`], released with
+//!   [`bs_deck_free`]. It is not thread-safe; use one handle per thread.
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::pipeline;
+use crate::renderer::Renderer;
+use crate::types::{Cell, Color, PlayablePresentation};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_error(e: anyhow::Error) {
+    let msg = CString::new(format!("{e:#}").replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(msg));
+}
+
+/// Run `f`, recording its error (if any) for [`bs_last_error`].
+fn guard<T>(f: impl FnOnce() -> Result<T>) -> Option<T> {
+    f().map_err(set_error).ok()
+}
+
+/// Borrow a C string argument as `&str`.
+///
+/// # Safety
+/// `s` must be null or point to a NUL-terminated string that stays valid for
+/// the returned lifetime.
+unsafe fn arg<'a>(s: *const c_char, name: &str) -> Result<&'a str> {
+    if s.is_null() {
+        return Err(anyhow!("{name} is NULL"));
+    }
+    // SAFETY: non-null, and NUL-terminated per the caller's contract.
+    unsafe { CStr::from_ptr(s) }.to_str().with_context(|| format!("{name} is not UTF-8"))
+}
+
+fn out_string(s: String) -> Result<*mut c_char> {
+    Ok(CString::new(s).context("output contains a NUL byte")?.into_raw())
+}
+
+/// The last error message on this thread, or `NULL` if nothing has failed.
+/// The pointer stays valid until the next failing call on this thread; do not
+/// free it.
+#[unsafe(no_mangle)]
+pub extern "C" fn bs_last_error() -> *const c_char {
+    LAST_ERROR.with(|slot| slot.borrow().as_ref().map_or(ptr::null(), |s| s.as_ptr()))
+}
+
+/// Release a string returned by this library. `NULL` is ignored.
+///
+/// # Safety
+/// `s` must be null or a string returned by this library and not yet freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bs_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        // SAFETY: allocated by `CString::into_raw` in `out_string`.
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+/// Compile source JSON to compiled (playable) JSON, as `bs compile` does.
+/// Returns `NULL` on error.
+///
+/// # Safety
+/// `source_json` must be null or a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bs_compile(source_json: *const c_char) -> *mut c_char {
+    guard(|| {
+        // SAFETY: forwarded caller contract.
+        let json = unsafe { arg(source_json, "source_json") }?;
+        let deck = pipeline::parse_deck(json, "source")?;
+        out_string(serde_json::to_string(&deck.playable)?)
+    })
+    .unwrap_or(ptr::null_mut())
+}
+
+/// A loaded deck plus the grid of the last frame queried, so reading every
+/// cell of one frame replays the deck once.
+pub struct BsDeck {
+    presentation: PlayablePresentation,
+    cache: Option<(usize, Vec<Vec<Cell>>)>,
+}
+
+impl BsDeck {
+    fn grid(&mut self, frame: usize) -> &[Vec<Cell>] {
+        let stale = self.cache.as_ref().is_none_or(|(f, _)| *f != frame);
+        if stale {
+            self.cache = Some((frame, self.presentation.grid_at(frame)));
+        }
+        &self.cache.as_ref().unwrap().1
+    }
+}
+
+/// Load a deck from source or compiled JSON. Returns `NULL` on error.
+///
+/// # Safety
+/// `json` must be null or a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bs_deck_load(json: *const c_char) -> *mut BsDeck {
+    guard(|| {
+        // SAFETY: forwarded caller contract.
+        let json = unsafe { arg(json, "json") }?;
+        let presentation = pipeline::parse_deck(json, "deck")?.playable;
+        Ok(Box::into_raw(Box::new(BsDeck { presentation, cache: None })))
+    })
+    .unwrap_or(ptr::null_mut())
+}
+
+/// Release a deck. `NULL` is ignored.
+///
+/// # Safety
+/// `deck` must be null or a handle from [`bs_deck_load`] not yet freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bs_deck_free(deck: *mut BsDeck) {
+    if !deck.is_null() {
+        // SAFETY: allocated by `Box::into_raw` in `bs_deck_load`.
+        drop(unsafe { Box::from_raw(deck) });
+    }
+}
+
+/// Canvas width in cells.
+///
+/// # Safety
+/// `deck` must be a live handle from [`bs_deck_load`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bs_deck_width(deck: *const BsDeck) -> u32 {
+    // SAFETY: caller contract.
+    unsafe { &*deck }.presentation.contract.width.into()
+}
+
+/// Canvas height in cells.
+///
+/// # Safety
+/// `deck` must be a live handle from [`bs_deck_load`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bs_deck_height(deck: *const BsDeck) -> u32 {
+    // SAFETY: caller contract.
+    unsafe { &*deck }.presentation.contract.height.into()
+}
+
+/// Number of frames.
+///
+/// # Safety
+/// `deck` must be a live handle from [`bs_deck_load`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bs_deck_frame_count(deck: *const BsDeck) -> usize {
+    // SAFETY: caller contract.
+    unsafe { &*deck }.presentation.frames.len()
+}
+
+/// One cell of a frame. Colours are `0xRRGGBB` (named colours resolved to
+/// xterm values), or `-1` for the terminal default.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BsCell {
+    /// Unicode scalar value of the character; `0` for the right half of a
+    /// double-width character in the cell to its left.
+    pub ch: u32,
+    pub fg: i32,
+    pub bg: i32,
+    pub bold: bool,
+    pub dim: bool,
+    pub underline: bool,
+}
+
+fn color_value(c: &Option<Color>) -> i32 {
+    c.as_ref().map_or(-1, |c| {
+        let (r, g, b) = c.rgb();
+        i32::from(r) << 16 | i32::from(g) << 8 | i32::from(b)
+    })
+}
+
+/// Read the cell at (`x`, `y`) of `frame` (0-based, clamped to the last
+/// frame) into `out`. Returns `false`, leaving `out` untouched, when the
+/// position is outside the canvas.
+///
+/// # Safety
+/// `deck` must be a live handle from [`bs_deck_load`]; `out` must be valid
+/// for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bs_deck_cell(
+    deck: *mut BsDeck,
+    frame: usize,
+    x: u32,
+    y: u32,
+    out: *mut BsCell,
+) -> bool {
+    // SAFETY: caller contract.
+    let deck = unsafe { &mut *deck };
+    let Some(cell) = deck.grid(frame).get(y as usize).and_then(|row| row.get(x as usize)) else {
+        set_error(anyhow!("cell ({x}, {y}) is outside the canvas"));
+        return false;
+    };
+    let cell = BsCell {
+        ch: cell.ch.into(),
+        fg: color_value(&cell.style.fg),
+        bg: color_value(&cell.style.bg),
+        bold: cell.style.bold,
+        dim: cell.style.dim,
+        underline: cell.style.underline,
+    };
+    // SAFETY: caller contract.
+    unsafe { out.write(cell) };
+    true
+}
+
+/// Frame `frame` (0-based, clamped) as text, rows separated by `\n`: ANSI
+/// styled when `ansi`, plain characters otherwise. Free with
+/// [`bs_string_free`].
+///
+/// # Safety
+/// `deck` must be a live handle from [`bs_deck_load`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bs_deck_frame_text(
+    deck: *const BsDeck,
+    frame: usize,
+    ansi: bool,
+) -> *mut c_char {
+    // SAFETY: caller contract.
+    let p = &unsafe { &*deck }.presentation;
+    let text = if ansi {
+        Renderer::frame_to_ansi(p, frame)
+    } else {
+        Renderer::frame_to_plain_text(p, frame)
+    };
+    guard(|| out_string(text)).unwrap_or(ptr::null_mut())
+}
@@ -0,0 +1,40 @@
+//! ANSI art files (`.ans`) for [`AnsiArt`](crate::engine::source::AnsiArt)
+//! objects: UTF-8 when the file is valid UTF-8, else the IBM code page 437
+//! that scene art is drawn in, cut at the `SUB` (`0x1a`) before any SAUCE
+//! metadata record.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// Code page 437, bytes `0x80..=0xff`.
+const CP437_HIGH: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ',
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»',
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐',
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧',
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀',
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{a0}',
+];
+
+/// Decode an ANSI art file's bytes to text.
+pub fn decode(bytes: &[u8]) -> String {
+    let bytes = bytes.split(|&b| b == 0x1a).next().unwrap_or_default();
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes.iter().map(|&b| if b < 0x80 { b as char } else { CP437_HIGH[b as usize - 0x80] }).collect(),
+    }
+}
+
+/// Read `path` as ANSI art: `Some(text)` for an `.ans` file or one carrying
+/// escape sequences, `None` for plain text (which loads as ordinary `Art`).
+/// A single trailing newline is trimmed, as for library art.
+pub fn load_file(path: &Path) -> Result<Option<String>> {
+    let bytes = fs::read(path).with_context(|| format!("Failed to read art file {}", path.display()))?;
+    let text = decode(&bytes);
+    let ansi = path.extension().is_some_and(|e| e.eq_ignore_ascii_case("ans")) || text.contains('\x1b');
+    Ok(ansi.then(|| text.strip_suffix('\n').unwrap_or(&text).to_string()))
+}
@@ -0,0 +1,119 @@
+//! asciinema v2 cast import.
+//!
+//! The recording's output events are replayed on a [`Screen`] the size of the
+//! header's `width × height`, and the screen is sampled every `--every`
+//! seconds — or, with `--markers`, at each marker (`m` event, added while
+//! recording with asciinema's marker hotkey) — plus once at the end. Runs of
+//! identical samples collapse into one frame, and each frame's duration is the
+//! recording time it stayed on screen (the last frame has none).
+//!
+//! [`Recording`] is the parsed cast, also replayed by `Cast` objects to embed
+//! a snippet of one in a slide.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use super::vt::Screen;
+use super::ImportOptions;
+use crate::renderer::{Renderer, DEFAULT_KEYFRAME_INTERVAL};
+use crate::types::{Cell, PlayablePresentation, TerminalContract};
+
+#[derive(Deserialize)]
+struct Header {
+    version: u32,
+    width: u16,
+    height: u16,
+}
+
+/// A parsed asciinema v2 cast: the terminal size and its events in order.
+pub struct Recording {
+    pub width: u16,
+    pub height: u16,
+    /// `(time, code, data)`: `o` is output, `m` a marker.
+    events: Vec<(f64, String, String)>,
+}
+
+impl Recording {
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut lines = text.lines().enumerate().filter(|(_, l)| !l.trim().is_empty());
+        let Some((_, first)) = lines.next() else {
+            bail!("empty cast: no header line");
+        };
+        let header: Header = serde_json::from_str(first).context("bad cast header")?;
+        if header.version != 2 {
+            bail!("only asciinema v2 casts can be read (this one is v{})", header.version);
+        }
+        let mut events: Vec<(f64, String, String)> = Vec::new();
+        for (i, line) in lines {
+            let event = serde_json::from_str(line).with_context(|| format!("bad cast event on line {}", i + 1))?;
+            events.push(event);
+        }
+        Ok(Recording { width: header.width, height: header.height, events })
+    }
+
+    /// Seconds from the start to the last event.
+    pub fn duration(&self) -> f64 {
+        self.events.last().map_or(0.0, |e| e.0)
+    }
+
+    /// The screen after every output event up to `times` (ascending), one
+    /// grid per time, replaying the recording once.
+    pub fn screens_at(&self, times: &[f64]) -> Vec<Vec<Vec<Cell>>> {
+        let mut screen = Screen::new(self.width as usize, self.height as usize);
+        let mut events = self.events.iter().filter(|e| e.1 == "o").peekable();
+        times
+            .iter()
+            .map(|&t| {
+                while let Some((_, _, data)) = events.next_if(|e| e.0 <= t) {
+                    screen.feed(data);
+                }
+                screen.grid().to_vec()
+            })
+            .collect()
+    }
+}
+
+/// Parse a v2 cast and sample it into a compiled deck.
+pub fn from_cast(text: &str, opts: &ImportOptions) -> Result<PlayablePresentation> {
+    let Recording { width, height, events } = Recording::parse(text)?;
+
+    let mut screen = Screen::new(width as usize, height as usize);
+    let mut samples: Vec<(f64, Vec<Vec<Cell>>)> = Vec::new();
+    let mut next = opts.every;
+    for (t, code, data) in &events {
+        if opts.markers {
+            if code == "m" {
+                samples.push((*t, screen.grid().to_vec()));
+            }
+        } else {
+            while *t > next {
+                samples.push((next, screen.grid().to_vec()));
+                next += opts.every;
+            }
+        }
+        if code == "o" {
+            screen.feed(data);
+        }
+    }
+    let end = events.last().map_or(0.0, |e| e.0);
+    samples.push((end, screen.grid().to_vec()));
+
+    // Collapse repeats, carrying each run's start time.
+    let mut frames: Vec<(f64, Vec<Vec<Cell>>)> = Vec::new();
+    for (t, grid) in samples {
+        if frames.last().is_none_or(|(_, prev)| *prev != grid) {
+            frames.push((t, grid));
+        }
+    }
+    let mut durations: Vec<Option<u64>> = (0..frames.len())
+        .map(|i| frames.get(i + 1).map(|next| ((next.0 - frames[i].0) * 1000.0).round().max(0.0) as u64))
+        .collect();
+    if durations.iter().all(Option::is_none) {
+        durations.clear();
+    }
+
+    let contract = TerminalContract { width, height, background: None };
+    let mut p = Renderer::from_grids(frames.into_iter().map(|(_, grid)| grid), contract, DEFAULT_KEYFRAME_INTERVAL);
+    p.durations = durations;
+    Ok(p)
+}
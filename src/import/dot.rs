@@ -0,0 +1,731 @@
+//! Graphviz DOT import.
+//!
+//! Reads the common subset of DOT — `graph`/`digraph` bodies with node, edge
+//! (`->`/`--` chains, `{ a b }` endpoints), attribute and `subgraph`
+//! statements, quoted IDs and comments — and lays it out in layers: each
+//! node's rank is its longest path from a root (cycles are broken where a
+//! depth-first walk meets them), and each layer is ordered by the mean place
+//! of its neighbours so edges cross less. Layers run top to bottom, or left to
+//! right under `rankdir=LR`, and are spread over the `--width × --height`
+//! canvas with at least [`MIN_GAP`] cells between layers. Every node becomes a
+//! `rect` with a centred `label`, grouped so they move as one; every edge
+//! becomes an `arrow` (headless in an undirected `graph`), with its `label`, if
+//! any, beside the middle. An edge spanning several layers gets a virtual node
+//! in each layer it passes, so it runs down a lane of its own between the boxes
+//! as a `connector` capped with the arrowhead. Only `label` and `rankdir`
+//! attributes are read; ports, styles and shapes are ignored.
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+
+use super::ImportOptions;
+use crate::builder::Presentation;
+use crate::engine::source::{Group, SceneObject, SourcePresentation, TextAlign};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Id(String),
+    Edge,
+    Sym(char),
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut line_start = true;
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => {
+                line_start = true;
+                continue;
+            }
+            c if c.is_whitespace() => continue,
+            // `#` lines are C-preprocessor output.
+            '#' if line_start => {
+                while chars.next_if(|&c| c != '\n').is_some() {}
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                while chars.next_if(|&c| c != '\n').is_some() {}
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                loop {
+                    match chars.next() {
+                        Some('/') if prev == '*' => break,
+                        Some(c) => prev = c,
+                        None => bail!("unterminated /* comment"),
+                    }
+                }
+            }
+            '-' if matches!(chars.peek(), Some('>') | Some('-')) => {
+                chars.next();
+                tokens.push(Token::Edge);
+            }
+            '"' => {
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('"') => s.push('"'),
+                            Some('\n') => {} // line continuation
+                            Some(c) => {
+                                s.push('\\');
+                                s.push(c);
+                            }
+                            None => bail!("unterminated string"),
+                        },
+                        Some(c) => s.push(c),
+                        None => bail!("unterminated string"),
+                    }
+                }
+                // `"a" + "b"` concatenates.
+                match tokens.last_mut() {
+                    Some(Token::Sym('+')) => {
+                        tokens.pop();
+                        if let Some(Token::Id(prev)) = tokens.last_mut() {
+                            prev.push_str(&s);
+                        }
+                    }
+                    _ => tokens.push(Token::Id(s)),
+                }
+            }
+            '<' => {
+                // An HTML label: keep its text, drop the tags.
+                let (mut depth, mut in_tag, mut s) = (1, false, String::new());
+                while depth > 0 {
+                    match chars.next() {
+                        Some('<') => {
+                            depth += 1;
+                            in_tag = true;
+                        }
+                        Some('>') => {
+                            depth -= 1;
+                            in_tag = false;
+                        }
+                        Some(c) if !in_tag => s.push(c),
+                        Some(_) => {}
+                        None => bail!("unterminated <html> ID"),
+                    }
+                }
+                tokens.push(Token::Id(s.trim().to_string()));
+            }
+            '{' | '}' | '[' | ']' | ';' | ',' | '=' | ':' | '+' => tokens.push(Token::Sym(c)),
+            c if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' || !c.is_ascii() => {
+                let mut s = c.to_string();
+                while let Some(c) = chars.next_if(|&c| c.is_alphanumeric() || c == '_' || c == '.' || !c.is_ascii()) {
+                    s.push(c);
+                }
+                tokens.push(Token::Id(s));
+            }
+            c => bail!("unexpected character `{c}`"),
+        }
+        line_start = false;
+    }
+    Ok(tokens)
+}
+
+struct Node {
+    id: String,
+    label: Option<String>,
+}
+
+struct Edge {
+    from: usize,
+    to: usize,
+    label: Option<String>,
+}
+
+#[derive(Default)]
+struct Graph {
+    directed: bool,
+    left_right: bool,
+    nodes: Vec<Node>,
+    index: HashMap<String, usize>,
+    edges: Vec<Edge>,
+}
+
+impl Graph {
+    fn node(&mut self, id: &str) -> usize {
+        if let Some(&i) = self.index.get(id) {
+            return i;
+        }
+        self.nodes.push(Node { id: id.to_string(), label: None });
+        self.index.insert(id.to_string(), self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+fn is_keyword(tok: Option<&Token>, word: &str) -> bool {
+    matches!(tok, Some(Token::Id(s)) if s.eq_ignore_ascii_case(word))
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let tok = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek() == Some(&Token::Sym(c));
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        match self.next() {
+            Some(Token::Sym(s)) if s == c => Ok(()),
+            Some(tok) => bail!("expected `{c}`, found {}", describe(&tok)),
+            None => bail!("expected `{c}`, found the end of the file"),
+        }
+    }
+
+    fn id(&mut self) -> Result<String> {
+        match self.next() {
+            Some(Token::Id(s)) => Ok(s),
+            Some(tok) => bail!("expected an ID, found {}", describe(&tok)),
+            None => bail!("expected an ID, found the end of the file"),
+        }
+    }
+
+    fn graph(&mut self) -> Result<Graph> {
+        let mut g = Graph::default();
+        if is_keyword(self.peek(), "strict") {
+            self.pos += 1;
+        }
+        if is_keyword(self.peek(), "digraph") {
+            g.directed = true;
+        } else if !is_keyword(self.peek(), "graph") {
+            bail!("a DOT file starts with `graph` or `digraph`");
+        }
+        self.pos += 1;
+        if matches!(self.peek(), Some(Token::Id(_))) {
+            self.pos += 1;
+        }
+        self.expect('{')?;
+        self.stmts(&mut g)?;
+        Ok(g)
+    }
+
+    /// Statements up to and including the closing `}`; returns the nodes they
+    /// mention, for a subgraph used as an edge endpoint.
+    fn stmts(&mut self, g: &mut Graph) -> Result<Vec<usize>> {
+        let mut mentioned = Vec::new();
+        while !self.eat('}') {
+            if self.peek().is_none() {
+                bail!("missing `}}` at the end of the file");
+            }
+            if self.eat(';') || self.eat(',') {
+                continue;
+            }
+            if is_keyword(self.peek(), "graph") || is_keyword(self.peek(), "node") || is_keyword(self.peek(), "edge") {
+                let graph = is_keyword(self.peek(), "graph");
+                self.pos += 1;
+                let attrs = self.attrs()?;
+                if graph {
+                    apply_graph_attrs(g, &attrs);
+                }
+                continue;
+            }
+            if let (Some(Token::Id(_)), Some(Token::Sym('='))) = (self.peek(), self.tokens.get(self.pos + 1)) {
+                let key = self.id()?;
+                self.pos += 1;
+                let value = self.id()?;
+                apply_graph_attrs(g, &[(key, value)]);
+                continue;
+            }
+            let mut tail = self.endpoint(g)?;
+            mentioned.extend(&tail);
+            let mut chain = Vec::new();
+            while self.peek() == Some(&Token::Edge) {
+                self.pos += 1;
+                let head = self.endpoint(g)?;
+                mentioned.extend(&head);
+                chain.push((tail, head.clone()));
+                tail = head;
+            }
+            let attrs = self.attrs()?;
+            let label = attrs.iter().rev().find(|(k, _)| k == "label").map(|(_, v)| v.clone());
+            if chain.is_empty() {
+                if let (Some(label), [node]) = (label, tail.as_slice()) {
+                    g.nodes[*node].label = Some(label);
+                }
+                continue;
+            }
+            for (from, to) in chain {
+                for &f in &from {
+                    for &t in &to {
+                        g.edges.push(Edge { from: f, to: t, label: label.clone() });
+                    }
+                }
+            }
+        }
+        Ok(mentioned)
+    }
+
+    /// A node ID (its port dropped) or a subgraph.
+    fn endpoint(&mut self, g: &mut Graph) -> Result<Vec<usize>> {
+        if is_keyword(self.peek(), "subgraph") {
+            self.pos += 1;
+            if matches!(self.peek(), Some(Token::Id(_))) {
+                self.pos += 1;
+            }
+            self.expect('{')?;
+            return self.stmts(g);
+        }
+        if self.eat('{') {
+            return self.stmts(g);
+        }
+        let id = self.id()?;
+        while self.eat(':') {
+            self.id()?;
+        }
+        Ok(vec![g.node(&id)])
+    }
+
+    /// Any `[k=v, …]` lists, concatenated.
+    fn attrs(&mut self) -> Result<Vec<(String, String)>> {
+        let mut attrs = Vec::new();
+        while self.eat('[') {
+            while !self.eat(']') {
+                if self.eat(';') || self.eat(',') {
+                    continue;
+                }
+                let key = self.id()?;
+                let value = if self.eat('=') { self.id()? } else { "true".to_string() };
+                attrs.push((key, value));
+            }
+        }
+        Ok(attrs)
+    }
+}
+
+fn describe(tok: &Token) -> String {
+    match tok {
+        Token::Id(s) => format!("`{s}`"),
+        Token::Edge => "an edge operator".to_string(),
+        Token::Sym(c) => format!("`{c}`"),
+    }
+}
+
+fn apply_graph_attrs(g: &mut Graph, attrs: &[(String, String)]) {
+    for (k, v) in attrs {
+        if k == "rankdir" {
+            g.left_right = v.eq_ignore_ascii_case("LR") || v.eq_ignore_ascii_case("RL");
+        }
+    }
+}
+
+/// A DOT label's lines: `\n`, `\l` and `\r` break lines, and `\N` stands for
+/// the node's ID.
+fn label_lines(label: &str, id: &str) -> Vec<String> {
+    label
+        .replace("\\N", id)
+        .replace("\\l", "\n")
+        .replace("\\r", "\n")
+        .replace("\\n", "\n")
+        .trim_end_matches('\n')
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+/// Cut `line` to `max` columns, ending it with `…` if anything was dropped.
+fn truncate(line: &str, max: usize) -> String {
+    if line.chars().count() <= max {
+        return line.to_string();
+    }
+    let mut s: String = line.chars().take(max.saturating_sub(1)).collect();
+    s.push('…');
+    s
+}
+
+/// Each node's layer: the longest path to it from a root, over the edges
+/// left once every cycle-closing one is reversed.
+fn ranks(g: &Graph) -> Vec<usize> {
+    let n = g.nodes.len();
+    let mut out: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for e in g.edges.iter().filter(|e| e.from != e.to) {
+        out[e.from].push(e.to);
+    }
+    // Depth-first from each node in file order; an edge back into the walk's
+    // own path closes a cycle and is reversed.
+    let mut state = vec![0u8; n]; // 0 unseen, 1 on the path, 2 done
+    let mut dag: Vec<(usize, usize)> = Vec::new();
+    for root in 0..n {
+        if state[root] != 0 {
+            continue;
+        }
+        let mut stack = vec![(root, 0)];
+        state[root] = 1;
+        while let Some(&mut (v, ref mut i)) = stack.last_mut() {
+            if let Some(&w) = out[v].get(*i) {
+                *i += 1;
+                match state[w] {
+                    1 => dag.push((w, v)),
+                    2 => dag.push((v, w)),
+                    _ => {
+                        dag.push((v, w));
+                        state[w] = 1;
+                        stack.push((w, 0));
+                    }
+                }
+            } else {
+                state[v] = 2;
+                stack.pop();
+            }
+        }
+    }
+    // Longest path, relaxed in topological order.
+    let mut indegree = vec![0; n];
+    for &(_, w) in &dag {
+        indegree[w] += 1;
+    }
+    let mut rank = vec![0; n];
+    let mut ready: Vec<usize> = (0..n).filter(|&v| indegree[v] == 0).rev().collect();
+    while let Some(v) = ready.pop() {
+        for &(a, w) in &dag {
+            if a == v {
+                rank[w] = rank[w].max(rank[v] + 1);
+                indegree[w] -= 1;
+                if indegree[w] == 0 {
+                    ready.push(w);
+                }
+            }
+        }
+    }
+    rank
+}
+
+/// Nodes per layer, ordered to cut crossings: a few sweeps down and up, each
+/// sorting a layer by the mean position of its neighbours in the layer before.
+/// `edges` join nodes in adjacent layers — long edges come as chains through
+/// virtual nodes.
+fn order_layers(edges: &[(usize, usize)], rank: &[usize]) -> Vec<Vec<usize>> {
+    let layer_count = rank.iter().max().map_or(0, |r| r + 1);
+    let mut layers: Vec<Vec<usize>> = vec![Vec::new(); layer_count];
+    for (v, &r) in rank.iter().enumerate() {
+        layers[r].push(v);
+    }
+    let mut place = vec![0.0; rank.len()];
+    for layer in &layers {
+        for (i, &v) in layer.iter().enumerate() {
+            place[v] = (i as f64 + 0.5) / layer.len() as f64;
+        }
+    }
+    for sweep in 0..4 {
+        let down = sweep % 2 == 0;
+        let order: Vec<usize> = if down { (1..layer_count).collect() } else { (0..layer_count.saturating_sub(1)).rev().collect() };
+        for r in order {
+            let adjacent = if down { r - 1 } else { r + 1 };
+            let key = |v: usize| {
+                let neighbours: Vec<f64> = edges
+                    .iter()
+                    .filter_map(|&(from, to)| match (from == v, to == v) {
+                        (true, false) if rank[to] == adjacent => Some(place[to]),
+                        (false, true) if rank[from] == adjacent => Some(place[from]),
+                        _ => None,
+                    })
+                    .collect();
+                if neighbours.is_empty() {
+                    place[v]
+                } else {
+                    neighbours.iter().sum::<f64>() / neighbours.len() as f64
+                }
+            };
+            let mut keyed: Vec<(f64, usize)> = layers[r].iter().map(|&v| (key(v), v)).collect();
+            keyed.sort_by(|a, b| a.0.total_cmp(&b.0));
+            layers[r] = keyed.into_iter().map(|(_, v)| v).collect();
+            for (i, &v) in layers[r].iter().enumerate() {
+                place[v] = (i as f64 + 0.5) / layers[r].len() as f64;
+            }
+        }
+    }
+    layers
+}
+
+/// Fewest cells between two layers: room for an arrow's body and its head.
+const MIN_GAP: usize = 2;
+
+/// Cells across a virtual node's lane: the line, with a blank either side.
+const LANE: usize = 3;
+
+/// A node's box along the layer axis (`main`) and across it (`cross`).
+#[derive(Clone, Copy, Default)]
+struct Placed {
+    main: usize,
+    main_len: usize,
+    cross: usize,
+    cross_len: usize,
+}
+
+impl Placed {
+    fn main_mid(&self) -> usize {
+        self.main + self.main_len / 2
+    }
+
+    fn cross_mid(&self) -> usize {
+        self.cross + self.cross_len / 2
+    }
+}
+
+/// Lay out `len`-long items along `span` cells with equal gaps before, between
+/// and after them — at least `min_gap` between, the margins giving way first;
+/// returns each item's start.
+fn spread(lens: &[usize], span: usize, min_gap: usize) -> Vec<usize> {
+    let free = span.saturating_sub(lens.iter().sum());
+    let gap = (free / (lens.len() + 1)).max(min_gap);
+    let mut at = free.saturating_sub(gap * lens.len().saturating_sub(1)) / 2;
+    lens.iter()
+        .map(|len| {
+            let start = at;
+            at += len + gap;
+            start
+        })
+        .collect()
+}
+
+/// Parse a DOT graph and lay it out as an editable source deck.
+pub fn from_dot(text: &str, opts: &ImportOptions) -> Result<SourcePresentation> {
+    let mut parser = Parser { tokens: tokenize(text)?, pos: 0 };
+    let g = parser.graph()?;
+    if parser.peek().is_some() {
+        bail!("unexpected text after the graph's closing `}}`");
+    }
+    let (width, height) = (opts.width as usize, opts.height as usize);
+    let n = g.nodes.len();
+    let mut rank = ranks(&g);
+
+    // Edges between adjacent layers as they are; longer ones as chains
+    // through a virtual node (numbered from `n`) in each layer they pass.
+    let mut links = Vec::new();
+    let mut virtuals: Vec<Vec<usize>> = vec![Vec::new(); g.edges.len()];
+    for (i, e) in g.edges.iter().enumerate() {
+        let (ra, rb) = (rank[e.from], rank[e.to]);
+        let mut prev = e.from;
+        if ra.abs_diff(rb) > 1 {
+            let passed: Vec<usize> = if rb > ra { (ra + 1..rb).collect() } else { (rb + 1..ra).rev().collect() };
+            for r in passed {
+                rank.push(r);
+                let v = rank.len() - 1;
+                virtuals[i].push(v);
+                links.push((prev, v));
+                prev = v;
+            }
+        }
+        links.push((prev, e.to));
+    }
+    let layers = order_layers(&links, &rank);
+
+    // Box sizes, labels cut so a layer (across TB) or the layers (across LR)
+    // fit the width.
+    let per_box = if g.left_right {
+        width.saturating_sub(MIN_GAP * layers.len().saturating_sub(1)) / layers.len().max(1)
+    } else {
+        layers
+            .iter()
+            .map(|l| {
+                let lanes = l.iter().filter(|&&v| v >= n).count();
+                width.saturating_sub(LANE * lanes) / (l.len() - lanes).max(1)
+            })
+            .min()
+            .unwrap_or(width)
+    };
+    let max_text = per_box.saturating_sub(4).max(1);
+    let lines: Vec<Vec<String>> = g
+        .nodes
+        .iter()
+        .map(|n| {
+            let label = n.label.as_deref().unwrap_or(&n.id);
+            let lines = label_lines(label, &n.id);
+            let lines = if lines.is_empty() { vec![String::new()] } else { lines };
+            lines.iter().map(|l| truncate(l, max_text)).collect()
+        })
+        .collect();
+    let size = |v: usize| {
+        let w = lines[v].iter().map(|l| l.chars().count()).max().unwrap_or(0) + 4;
+        (w, lines[v].len() + 2)
+    };
+    // (along the layer axis, across it); a virtual node is a lane through
+    // its layer.
+    let lens = |v: usize| match v {
+        v if v >= n => (0, LANE),
+        v if g.left_right => size(v),
+        v => (size(v).1, size(v).0),
+    };
+    let (main_span, cross_span) = if g.left_right { (width, height) } else { (height, width) };
+
+    let thickness: Vec<usize> = layers.iter().map(|l| l.iter().map(|&v| lens(v).0).max().unwrap_or(0)).collect();
+    let needed = thickness.iter().sum::<usize>() + MIN_GAP * layers.len().saturating_sub(1);
+    if needed > main_span {
+        let (flag, unit) = if g.left_right { ("--width", "columns") } else { ("--height", "rows") };
+        bail!("the graph's {} layers need {needed} {unit}, but {flag} is {main_span}", layers.len());
+    }
+    let starts = spread(&thickness, main_span, MIN_GAP);
+    let mut placed = vec![Placed::default(); rank.len()];
+    for ((layer, &main), &thick) in layers.iter().zip(&starts).zip(&thickness) {
+        let cross_lens: Vec<usize> = layer.iter().map(|&v| lens(v).1).collect();
+        for (&v, cross) in layer.iter().zip(spread(&cross_lens, cross_span, 0)) {
+            let (m, c) = if v >= n { (thick, LANE) } else { lens(v) };
+            // Centre thinner boxes in their layer.
+            let slack = thick - m;
+            placed[v] = Placed { main: main + slack / 2, main_len: m, cross, cross_len: c };
+        }
+    }
+    let xy = |main: usize, cross: usize| -> (u16, u16) {
+        let (x, y) = if g.left_right { (main, cross) } else { (cross, main) };
+        (x.min(u16::MAX as usize) as u16, y.min(u16::MAX as usize) as u16)
+    };
+
+    let mut deck = Presentation::new(opts.width, opts.height);
+    for (v, p) in placed[..n].iter().enumerate() {
+        let (x, y) = xy(p.main, p.cross);
+        let (w, h) = size(v);
+        deck = deck
+            .rect(w as u16, h as u16)
+            .at(x, y)
+            .label(lines[v].join("\n"))
+            .at(x + 1, y + 1)
+            .size(w as u16 - 2, h as u16 - 2)
+            .align(TextAlign::Center);
+    }
+    // Whether an arrow between two (main, cross) points runs along the layer
+    // axis first — `Arrow` goes horizontal first when |dx| >= |dy|.
+    let main_first = |s: (usize, usize), e: (usize, usize)| {
+        let (dm, dc) = (s.0.abs_diff(e.0), s.1.abs_diff(e.1));
+        if g.left_right { dm >= dc } else { dm > dc }
+    };
+    for (e, lane) in g.edges.iter().zip(&virtuals).filter(|(e, _)| e.from != e.to) {
+        let (a, b) = (placed[e.from], placed[e.to]);
+        let (ra, rb) = (rank[e.from], rank[e.to]);
+        // A pair of opposite edges keeps one cell apart.
+        let paired = g.edges.iter().any(|o| o.from == e.to && o.to == e.from);
+        let nudge = |c: usize| match (paired, rb > ra) {
+            (false, _) => c,
+            (true, true) => c.saturating_sub(1),
+            (true, false) => c + 1,
+        };
+        // The arrow's ends, and the leg along which its label sits.
+        let (start, end, leg) = if !lane.is_empty() {
+            // Through the lanes of the layers between: along each lane, and
+            // across in the middle of the gap after each layer (short of the
+            // far side, so the head has a cell), capped with the head.
+            let down = rb > ra;
+            let past = |r: usize| {
+                if down {
+                    (starts[r] + thickness[r] + starts[r + 1] - 1) / 2
+                } else {
+                    (starts[r - 1] + thickness[r - 1] + starts[r]) / 2
+                }
+            };
+            // Leave and enter on the side nearest the lanes, clear of the
+            // arrows at the boxes' middles where there's room.
+            let near = |p: Placed, c: usize| c.clamp(p.cross + 1, (p.cross + p.cross_len).saturating_sub(2).max(p.cross + 1));
+            let from = if down { a.main + a.main_len } else { a.main.saturating_sub(1) };
+            let mut points = vec![(from, near(a, placed[lane[0]].cross_mid()))];
+            let mut r = ra;
+            for &v in lane {
+                let (turn, c) = (past(r), placed[v].cross_mid());
+                points.extend([(turn, points[points.len() - 1].1), (turn, c)]);
+                r = rank[v];
+            }
+            let last = placed[lane[lane.len() - 1]].cross_mid();
+            let end = (if down { b.main.saturating_sub(1) } else { b.main + b.main_len }, near(b, last));
+            let turn = (past(r), end.1);
+            points.extend([(turn.0, points[points.len() - 1].1), turn, end]);
+            let points: Vec<(u16, u16)> = points.into_iter().map(|(m, c)| xy(m, c)).collect();
+            // Beneath the arrows, whose heads it may pass.
+            deck = deck.connector(&points).z(-1);
+            if g.directed {
+                let head = match (g.left_right, down) {
+                    (false, true) => '▼',
+                    (false, false) => '▲',
+                    (true, true) => '▶',
+                    (true, false) => '◀',
+                };
+                let (x, y) = xy(end.0, end.1);
+                deck = deck.label(head.to_string()).at(x, y).z(1);
+            }
+            (turn, end, (turn, end))
+        } else if ra == rb {
+            // Within a layer: straight across, between the facing sides.
+            let (from, to) = if b.cross > a.cross {
+                (a.cross + a.cross_len, b.cross.saturating_sub(1))
+            } else {
+                (a.cross.saturating_sub(1), b.cross + b.cross_len)
+            };
+            let (start, end) = ((a.main_mid(), from), (b.main_mid(), to));
+            (start, end, (start, end))
+        } else {
+            // Across layers: leave and enter through the sides facing along
+            // the layer axis.
+            let from = if rb > ra { a.main + a.main_len } else { a.main.saturating_sub(1) };
+            let to = if rb > ra { b.main.saturating_sub(1) } else { b.main + b.main_len };
+            let (lo, hi) = (a.cross.max(b.cross), (a.cross + a.cross_len).min(b.cross + b.cross_len));
+            if lo + 2 < hi {
+                // The boxes overlap across the layers: a straight arrow.
+                let c = nudge((lo + hi - 1) / 2).clamp(lo + 1, hi - 2);
+                let (start, end) = ((from, c), (to, c));
+                (start, end, (start, end))
+            } else {
+                let start = (from, nudge(a.cross_mid()));
+                let end = (to, nudge(b.cross_mid()));
+                if !main_first(start, end) {
+                    // Across first, then along the layer axis into the face.
+                    (start, end, ((start.0, end.1), end))
+                } else {
+                    // Along the layer axis first, then into the near end.
+                    let side = if b.cross > a.cross { b.cross.saturating_sub(1) } else { b.cross + b.cross_len };
+                    let end = (b.main_mid(), side);
+                    (start, end, (start, (end.0, start.1)))
+                }
+            }
+        };
+        if lane.is_empty() {
+            let (x1, y1) = xy(start.0, start.1);
+            let (x2, y2) = xy(end.0, end.1);
+            deck = deck.arrow(x1, y1, x2, y2);
+        }
+        if let Some(label) = &e.label {
+            let text = label_lines(label, "").join(" ");
+            let ((lx1, ly1), (lx2, ly2)) = (xy(leg.0 .0, leg.0 .1), xy(leg.1 .0, leg.1 .1));
+            // Beside a vertical leg, above a horizontal one.
+            let (x, y) = if lx1 == lx2 {
+                (lx1 + 1, ly1.midpoint(ly2))
+            } else {
+                (lx1.midpoint(lx2).saturating_sub(text.chars().count() as u16 / 2), ly1.saturating_sub(1))
+            };
+            deck = deck.label(text).at(x, y).z(1);
+        }
+    }
+
+    let mut source = deck.build();
+    for obj in &mut source.objects {
+        if let SceneObject::Arrow(a) = obj {
+            a.head = g.directed;
+        }
+    }
+    for v in 0..g.nodes.len() {
+        source.objects.push(SceneObject::Group(Group {
+            members: vec![2 * v, 2 * v + 1],
+            frames: None,
+            z_order: 0,
+            layer: None,
+        }));
+    }
+    Ok(source)
+}
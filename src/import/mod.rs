@@ -0,0 +1,102 @@
+//! Importers — `bs import <format> <input> <output>`.
+//!
+//! The counterpart of [`export`](crate::export): each format lives in its own
+//! module and turns a foreign file into a deck `bs` can play — a compiled deck
+//! for recordings, an editable source deck for diagrams. `vt.rs` is the
+//! terminal emulator shared by the formats that carry raw terminal output
+//! (and by `AnsiArt` objects); `ansi.rs` reads `.ans` art files for them, and
+//! `cast.rs`'s [`Recording`] is replayed by `Cast` objects too.
+//!
+//! Options a format has no use for are ignored.
+
+pub mod ansi;
+mod cast;
+mod dot;
+pub mod vt;
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{bail, Result};
+
+use crate::pipeline::{read_input, write_output, CompiledFormat};
+
+pub use cast::{from_cast, Recording};
+pub use dot::from_dot;
+
+/// The input formats `bs import` knows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// An asciinema v2 recording, sampled into a compiled deck.
+    Cast,
+    /// A Graphviz DOT graph, laid out as an editable source deck.
+    Dot,
+}
+
+impl ImportFormat {
+    pub const ALL: &'static [ImportFormat] = &[ImportFormat::Cast, ImportFormat::Dot];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ImportFormat::Cast => "cast",
+            ImportFormat::Dot => "dot",
+        }
+    }
+}
+
+impl fmt::Display for ImportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ImportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match ImportFormat::ALL.iter().find(|f| f.as_str() == s) {
+            Some(f) => Ok(*f),
+            None => {
+                let known: Vec<&str> = ImportFormat::ALL.iter().map(|f| f.as_str()).collect();
+                bail!("unknown import format `{s}` (expected one of: {})", known.join(", "))
+            }
+        }
+    }
+}
+
+/// Options shared by every importer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportOptions {
+    /// Seconds of recording between sampled frames (cast).
+    pub every: f64,
+    /// Sample at the recording's markers instead of by time (cast).
+    pub markers: bool,
+    /// Canvas the layout fills (dot).
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        ImportOptions { every: 1.0, markers: false, width: 80, height: 24 }
+    }
+}
+
+/// Import `input` (or stdin for `-`) as `format`, writing the deck to
+/// `output` (or stdout).
+pub fn import_file(format: ImportFormat, input: &str, output: &str, opts: &ImportOptions) -> Result<()> {
+    let text = read_input(input)?;
+    let (bytes, what) = match format {
+        ImportFormat::Cast => {
+            let p = from_cast(&text, opts)?;
+            (CompiledFormat::Json.encode(&p)?, format!("{} frames", p.frames.len()))
+        }
+        ImportFormat::Dot => {
+            let source = from_dot(&text, opts)?;
+            (serde_json::to_string_pretty(&source)?.into_bytes(), format!("{} objects", source.objects.len()))
+        }
+    };
+    write_output(output, bytes)?;
+    eprintln!("Imported {what} from {input} -> {output} ({format})");
+    Ok(())
+}
@@ -0,0 +1,454 @@
+//! A small VT100/xterm screen: enough of the escape-sequence repertoire to
+//! replay recorded terminal output into cell grids — printing with autowrap,
+//! cursor movement, erasing, insert/delete, scroll regions, the alternate
+//! screen, SGR colours and attributes, and OSC 8 links. Other sequences are
+//! parsed and dropped, so they never leak into the grid as text.
+
+use std::mem;
+
+use crate::types::{char_width, xterm_palette, Cell, Color, NamedColor, Style};
+
+/// Where the parser is within an escape sequence.
+enum State {
+    Ground,
+    Escape,
+    /// `ESC (`-style charset designation: the next char is its argument.
+    Charset,
+    Csi(String),
+    Osc(String),
+    /// An `ESC` inside an OSC string — the start of its `ESC \` terminator.
+    OscEscape(String),
+}
+
+/// The graphic rendition set by SGR. `reverse` has no [`Style`] counterpart,
+/// so it is applied as swapped colours when a cell is written.
+#[derive(Clone, Default)]
+struct Pen {
+    style: Style,
+    reverse: bool,
+}
+
+impl Pen {
+    fn cell_style(&self) -> Style {
+        let mut style = self.style.clone();
+        if self.reverse {
+            let fg = style.bg.take().unwrap_or(Color::Named(NamedColor::Black));
+            style.bg = Some(style.fg.take().unwrap_or(Color::Named(NamedColor::White)));
+            style.fg = Some(fg);
+        }
+        style
+    }
+}
+
+pub struct Screen {
+    width: usize,
+    height: usize,
+    grid: Vec<Vec<Cell>>,
+    /// The main screen while the alternate one is shown.
+    main: Option<Vec<Vec<Cell>>>,
+    x: usize,
+    y: usize,
+    saved_cursor: (usize, usize),
+    /// Set after printing in the last column: the next printable wraps first.
+    pending_wrap: bool,
+    /// The scroll region, inclusive rows.
+    top: usize,
+    bottom: usize,
+    pen: Pen,
+    state: State,
+}
+
+/// The colour a 256-colour palette index selects: the matching named colour
+/// where one exists (so the player emits the same index), else its RGB.
+pub fn palette_color(index: u8) -> Color {
+    let named = match index {
+        0 => NamedColor::Black,
+        9 => NamedColor::Red,
+        10 => NamedColor::Green,
+        11 => NamedColor::Yellow,
+        12 => NamedColor::Blue,
+        13 => NamedColor::Magenta,
+        14 => NamedColor::Cyan,
+        15 => NamedColor::White,
+        _ => return Color::from(xterm_palette()[index as usize]),
+    };
+    Color::Named(named)
+}
+
+/// `38`/`48` extended colour from its arguments (`5;n` or `2;r;g;b`, the
+/// colon form's colour-space slot already dropped). Returns the colour and how
+/// many arguments it used.
+fn extended_color(args: &[&str]) -> (Option<Color>, usize) {
+    let num = |i: usize| args.get(i).and_then(|a| a.parse::<u8>().ok());
+    match args.first().copied() {
+        Some("5") => (num(1).map(palette_color), 2),
+        Some("2") => match (num(1), num(2), num(3)) {
+            (Some(r), Some(g), Some(b)) => (Some(Color::Rgb { r, g, b }), 4),
+            _ => (None, 4),
+        },
+        _ => (None, 1),
+    }
+}
+
+impl Screen {
+    /// A blank `width × height` screen (each at least 1).
+    pub fn new(width: usize, height: usize) -> Self {
+        let (width, height) = (width.max(1), height.max(1));
+        Screen {
+            width,
+            height,
+            grid: vec![vec![Cell::default(); width]; height],
+            main: None,
+            x: 0,
+            y: 0,
+            saved_cursor: (0, 0),
+            pending_wrap: false,
+            top: 0,
+            bottom: height - 1,
+            pen: Pen::default(),
+            state: State::Ground,
+        }
+    }
+
+    /// The cells currently on screen.
+    pub fn grid(&self) -> &[Vec<Cell>] {
+        &self.grid
+    }
+
+    /// The cursor position, `(column, row)`.
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.x, self.y)
+    }
+
+    /// Interpret `text` as terminal output.
+    pub fn feed(&mut self, text: &str) {
+        for ch in text.chars() {
+            self.step(ch);
+        }
+    }
+
+    fn step(&mut self, ch: char) {
+        match mem::replace(&mut self.state, State::Ground) {
+            State::Ground => self.ground(ch),
+            State::Escape => self.escape(ch),
+            State::Charset => {}
+            State::Csi(mut params) => {
+                if ('\x40'..='\x7e').contains(&ch) {
+                    self.csi(&params, ch);
+                } else if ch == '\x1b' {
+                    self.state = State::Escape;
+                } else {
+                    params.push(ch);
+                    self.state = State::Csi(params);
+                }
+            }
+            State::Osc(mut s) => match ch {
+                '\x07' => self.osc(&s),
+                '\x1b' => self.state = State::OscEscape(s),
+                _ => {
+                    s.push(ch);
+                    self.state = State::Osc(s);
+                }
+            },
+            State::OscEscape(s) => {
+                self.osc(&s);
+                if ch != '\\' {
+                    self.step(ch);
+                }
+            }
+        }
+    }
+
+    fn ground(&mut self, ch: char) {
+        match ch {
+            '\x1b' => self.state = State::Escape,
+            '\r' => {
+                self.x = 0;
+                self.pending_wrap = false;
+            }
+            '\n' | '\x0b' | '\x0c' => {
+                self.pending_wrap = false;
+                self.linefeed();
+            }
+            '\x08' => {
+                self.x = self.x.saturating_sub(1);
+                self.pending_wrap = false;
+            }
+            '\t' => {
+                self.x = ((self.x / 8 + 1) * 8).min(self.width - 1);
+                self.pending_wrap = false;
+            }
+            c if c.is_control() => {}
+            c => self.put(c),
+        }
+    }
+
+    fn escape(&mut self, ch: char) {
+        match ch {
+            '[' => self.state = State::Csi(String::new()),
+            ']' => self.state = State::Osc(String::new()),
+            '(' | ')' | '*' | '+' => self.state = State::Charset,
+            '7' => self.saved_cursor = (self.x, self.y),
+            '8' => self.restore_cursor(),
+            'D' => self.linefeed(),
+            'E' => {
+                self.x = 0;
+                self.linefeed();
+            }
+            'M' => {
+                if self.y == self.top {
+                    self.scroll_down(1);
+                } else {
+                    self.y = self.y.saturating_sub(1);
+                }
+            }
+            'c' => *self = Screen::new(self.width, self.height),
+            _ => {}
+        }
+        self.pending_wrap = false;
+    }
+
+    fn put(&mut self, ch: char) {
+        let w = char_width(ch).min(self.width);
+        if self.pending_wrap || self.x + w > self.width {
+            self.x = 0;
+            self.linefeed();
+        }
+        self.pending_wrap = false;
+        let style = self.pen.cell_style();
+        if w == 2 {
+            self.grid[self.y][self.x + 1] = Cell::continuation(style.clone());
+        }
+        self.grid[self.y][self.x] = Cell { ch, style: style.into() };
+        if self.x + w >= self.width {
+            self.x = self.width - 1;
+            self.pending_wrap = true;
+        } else {
+            self.x += w;
+        }
+    }
+
+    /// An erased cell: blank, keeping the pen's background.
+    fn blank(&self) -> Cell {
+        Cell { ch: ' ', style: Style { bg: self.pen.cell_style().bg, ..Style::default() }.into() }
+    }
+
+    fn linefeed(&mut self) {
+        if self.y == self.bottom {
+            self.scroll_up(1);
+        } else if self.y + 1 < self.height {
+            self.y += 1;
+        }
+    }
+
+    fn scroll_up(&mut self, n: usize) {
+        for _ in 0..n.min(self.bottom - self.top + 1) {
+            self.grid.remove(self.top);
+            self.grid.insert(self.bottom, vec![self.blank(); self.width]);
+        }
+    }
+
+    fn scroll_down(&mut self, n: usize) {
+        for _ in 0..n.min(self.bottom - self.top + 1) {
+            self.grid.remove(self.bottom);
+            self.grid.insert(self.top, vec![self.blank(); self.width]);
+        }
+    }
+
+    fn restore_cursor(&mut self) {
+        (self.x, self.y) = self.saved_cursor;
+        self.x = self.x.min(self.width - 1);
+        self.y = self.y.min(self.height - 1);
+    }
+
+    fn erase(&mut self, y: usize, xs: std::ops::Range<usize>) {
+        let blank = self.blank();
+        for x in xs.start.min(self.width)..xs.end.min(self.width) {
+            self.grid[y][x] = blank.clone();
+        }
+    }
+
+    fn csi(&mut self, params: &str, action: char) {
+        let private = params.starts_with(['?', '>', '=']);
+        let body = params.trim_start_matches(['?', '>', '=']);
+        let args: Vec<usize> = body.split(';').map(|a| a.split(':').next().unwrap_or("").parse().unwrap_or(0)).collect();
+        // The i-th argument, with 0 or missing meaning `default`.
+        let arg = |i: usize, default: usize| match args.get(i) {
+            Some(&n) if n > 0 => n,
+            _ => default,
+        };
+        let n = arg(0, 1);
+        let (w, h) = (self.width, self.height);
+        self.pending_wrap = false;
+        match action {
+            'A' => self.y = self.y.saturating_sub(n),
+            'B' | 'e' => self.y = (self.y + n).min(h - 1),
+            'C' | 'a' => self.x = (self.x + n).min(w - 1),
+            'D' => self.x = self.x.saturating_sub(n),
+            'E' => {
+                self.y = (self.y + n).min(h - 1);
+                self.x = 0;
+            }
+            'F' => {
+                self.y = self.y.saturating_sub(n);
+                self.x = 0;
+            }
+            'G' | '`' => self.x = (n - 1).min(w - 1),
+            'd' => self.y = (n - 1).min(h - 1),
+            'H' | 'f' => {
+                self.y = (arg(0, 1) - 1).min(h - 1);
+                self.x = (arg(1, 1) - 1).min(w - 1);
+            }
+            'J' => {
+                let (x, y) = (self.x, self.y);
+                let rows = match args.first().copied().unwrap_or(0) {
+                    0 => {
+                        self.erase(y, x..w);
+                        y + 1..h
+                    }
+                    1 => {
+                        self.erase(y, 0..x + 1);
+                        0..y
+                    }
+                    _ => 0..h,
+                };
+                for row in rows {
+                    self.erase(row, 0..w);
+                }
+            }
+            'K' => {
+                let (x, y) = (self.x, self.y);
+                match args.first().copied().unwrap_or(0) {
+                    0 => self.erase(y, x..w),
+                    1 => self.erase(y, 0..x + 1),
+                    _ => self.erase(y, 0..w),
+                }
+            }
+            'X' => {
+                let (x, y) = (self.x, self.y);
+                self.erase(y, x..x + n);
+            }
+            '@' => {
+                let blank = self.blank();
+                let row = &mut self.grid[self.y];
+                for _ in 0..n.min(w - self.x) {
+                    row.pop();
+                    row.insert(self.x, blank.clone());
+                }
+            }
+            'P' => {
+                let blank = self.blank();
+                let row = &mut self.grid[self.y];
+                for _ in 0..n.min(w - self.x) {
+                    row.remove(self.x);
+                    row.push(blank.clone());
+                }
+            }
+            'L' | 'M' if (self.top..=self.bottom).contains(&self.y) => {
+                let top = mem::replace(&mut self.top, self.y);
+                if action == 'L' {
+                    self.scroll_down(n);
+                } else {
+                    self.scroll_up(n);
+                }
+                self.top = top;
+                self.x = 0;
+            }
+            'S' => self.scroll_up(n),
+            'T' if !private => self.scroll_down(n),
+            'r' if !private => {
+                let (top, bottom) = (arg(0, 1) - 1, arg(1, h).min(h) - 1);
+                if top < bottom {
+                    (self.top, self.bottom) = (top, bottom);
+                    (self.x, self.y) = (0, 0);
+                }
+            }
+            's' if !private => self.saved_cursor = (self.x, self.y),
+            'u' if !private => self.restore_cursor(),
+            'm' if !private => self.sgr(body),
+            'h' | 'l' if private => {
+                for mode in &args {
+                    if matches!(mode, 47 | 1047 | 1049) {
+                        self.alternate_screen(action == 'h', *mode == 1049);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn alternate_screen(&mut self, on: bool, save_cursor: bool) {
+        if on && self.main.is_none() {
+            if save_cursor {
+                self.saved_cursor = (self.x, self.y);
+            }
+            let blank = vec![vec![self.blank(); self.width]; self.height];
+            self.main = Some(mem::replace(&mut self.grid, blank));
+        } else if !on {
+            if let Some(main) = self.main.take() {
+                self.grid = main;
+                if save_cursor {
+                    self.restore_cursor();
+                }
+            }
+        }
+    }
+
+    /// Apply an SGR parameter list. `38`/`48` take their colour either from
+    /// the following `;` arguments or from `:` sub-arguments.
+    fn sgr(&mut self, params: &str) {
+        let groups: Vec<&str> = params.split(';').collect();
+        let mut i = 0;
+        while i < groups.len() {
+            let sub: Vec<&str> = groups[i].split(':').collect();
+            let style = &mut self.pen.style;
+            match sub[0].parse::<u8>().unwrap_or(0) {
+                0 => self.pen = Pen::default(),
+                1 => style.bold = true,
+                2 => style.dim = true,
+                22 => (style.bold, style.dim) = (false, false),
+                4 => style.underline = true,
+                24 => style.underline = false,
+                7 => self.pen.reverse = true,
+                27 => self.pen.reverse = false,
+                c @ 30..=37 => style.fg = Some(palette_color(c - 30)),
+                c @ 90..=97 => style.fg = Some(palette_color(c - 90 + 8)),
+                39 => style.fg = None,
+                c @ 40..=47 => style.bg = Some(palette_color(c - 40)),
+                c @ 100..=107 => style.bg = Some(palette_color(c - 100 + 8)),
+                49 => style.bg = None,
+                c @ (38 | 48) => {
+                    let color = if sub.len() > 1 {
+                        // `38:2::r:g:b` carries a colour-space slot; `38:2:r:g:b` does not.
+                        let mut args = sub[1..].to_vec();
+                        if args[0] == "2" && args.len() > 4 {
+                            args.remove(1);
+                        }
+                        extended_color(&args).0
+                    } else {
+                        let (color, used) = extended_color(&groups[i + 1..]);
+                        i += used;
+                        color
+                    };
+                    if c == 38 {
+                        style.fg = color;
+                    } else {
+                        style.bg = color;
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+
+    /// OSC 8 hyperlinks (`8;params;url`, an empty url closing the link); other
+    /// OSC strings (titles, palette changes) are dropped.
+    fn osc(&mut self, s: &str) {
+        let mut parts = s.splitn(3, ';');
+        if parts.next() == Some("8") {
+            let url = parts.nth(1).unwrap_or("");
+            self.pen.style.link = (!url.is_empty()).then(|| url.to_string());
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! Include resolution — splicing the decks named by `include` objects into
+//! the deck that includes them.
+//!
+//! Shared intros and outros live in their own source files; a deck pulls them
+//! in with `{"type": "include", "path": "intro.json", "at": 0}`. Resolution
+//! happens whenever a source deck is loaded ([`crate::pipeline::parse_source`],
+//! [`crate::pipeline::parse_deck`]): the included deck is read
+//! relative to the including file, its own includes are resolved first, and
+//! its objects are **appended** after this deck's (so this deck's group
+//! members and links keep their indices). The `include` object stays behind as
+//! a marker that draws nothing, and the source file on disk is never touched.
+//! JSON handed over in memory (the C and wasm APIs) has no file of its own, so
+//! its includes are relative to the working directory — on wasm, they fail.
+//!
+//! Appending renumbers what the included objects reference: group members and
+//! link families are offset to their new indices, and animation ids are moved
+//! past this deck's so the two decks' animations never collide.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use crate::engine::source::{
+    AnimId, Coordinate, FrameRange, Include, Scalar, SceneObject, SourcePresentation,
+};
+
+/// How deep includes may nest before resolution gives up.
+const MAX_DEPTH: usize = 16;
+
+/// Resolve every `include` in `source`, reading included decks relative to
+/// `dir`. Fails on a missing or unparsable file, an include cycle, or a
+/// `frames` selection outside the included deck.
+pub fn resolve_includes(source: &mut SourcePresentation, dir: &Path) -> Result<()> {
+    resolve(source, dir, &mut Vec::new())
+}
+
+fn resolve(source: &mut SourcePresentation, dir: &Path, stack: &mut Vec<PathBuf>) -> Result<()> {
+    let includes: Vec<Include> = source
+        .objects
+        .iter()
+        .filter_map(|o| match o {
+            SceneObject::Include(inc) => Some(inc.clone()),
+            _ => None,
+        })
+        .collect();
+    for inc in includes {
+        let path = dir.join(&inc.path);
+        let key = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+        if stack.contains(&key) {
+            bail!("include cycle through {}", path.display());
+        }
+        if stack.len() >= MAX_DEPTH {
+            bail!("includes nest more than {MAX_DEPTH} deep at {}", path.display());
+        }
+        let json = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read included deck {}", path.display()))?;
+        let mut deck = crate::migrate::source_from_str(&json)
+            .with_context(|| format!("Failed to parse included deck {}", path.display()))?;
+        if let Err(e) = deck.lower() {
+            bail!("invalid slide in included deck {}: {e}", path.display());
+        }
+        stack.push(key);
+        resolve(&mut deck, path.parent().unwrap_or(Path::new(".")), stack)?;
+        stack.pop();
+        splice(source, deck, &inc).with_context(|| format!("Failed to include {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Append `deck`'s objects to `source` as `inc` places them.
+fn splice(source: &mut SourcePresentation, deck: SourcePresentation, inc: &Include) -> Result<()> {
+    let sel = inc.frames.clone().unwrap_or(FrameRange { start: 0, end: deck.frame_count });
+    if sel.start >= sel.end || sel.end > deck.frame_count {
+        bail!(
+            "frames {}..{} are not within its {}-frame deck",
+            sel.start,
+            sel.end,
+            deck.frame_count
+        );
+    }
+    // A range clipped to the selection and moved so `sel.start` lands on `at`;
+    // `None` when nothing of it is selected.
+    let place = |r: &FrameRange| {
+        let (start, end) = (r.start.max(sel.start), r.end.min(sel.end));
+        (start < end).then(|| FrameRange { start: start - sel.start + inc.at, end: end - sel.start + inc.at })
+    };
+
+    let mut next_id = source.objects.iter().filter_map(anim_id).max().map_or(1, |m| m + 1);
+    let ids: HashMap<AnimId, AnimId> = deck
+        .objects
+        .iter()
+        .filter_map(anim_id)
+        .map(|old| {
+            next_id += 1;
+            (old, next_id - 1)
+        })
+        .collect();
+
+    let base = source.objects.len();
+    let mut index = vec![None; deck.objects.len()];
+    let mut kept = Vec::new();
+    for (k, mut obj) in deck.objects.into_iter().enumerate() {
+        if matches!(obj, SceneObject::Include(_)) {
+            continue;
+        }
+        if let Some(r) = obj.declared_frame_range() {
+            let Some(r) = place(&r) else { continue };
+            obj.set_frame_range(r);
+        }
+        if let SceneObject::Animation(a) = &mut obj {
+            a.id = ids[&a.id];
+        }
+        for coord in obj.coordinates_mut() {
+            if let Coordinate::Animated { anim, .. } = coord {
+                *anim = ids.get(anim).copied().unwrap_or(*anim);
+            }
+        }
+        for scalar in obj.scalars_mut() {
+            if let Scalar::Animated { anim, .. } = scalar {
+                *anim = ids.get(anim).copied().unwrap_or(*anim);
+            }
+        }
+        if obj.layer().is_none() && inc.layer.is_some() {
+            obj.set_layer(inc.layer.clone());
+        }
+        index[k] = Some(base + kept.len());
+        kept.push(obj);
+    }
+    let renumber = |members: &[usize]| -> Vec<usize> {
+        members.iter().filter_map(|&m| index.get(m).copied().flatten()).collect()
+    };
+    for obj in &mut kept {
+        if let SceneObject::Group(g) = obj {
+            g.members = renumber(&g.members);
+        }
+    }
+    source.links.extend(deck.links.iter().map(|fam| renumber(fam)).filter(|fam| fam.len() > 1));
+    source.objects.extend(kept);
+    source.frame_count = source.frame_count.max(inc.at + sel.end - sel.start);
+
+    // The including deck's own definitions win over the included deck's.
+    for (name, value) in deck.vars {
+        source.vars.entry(name).or_insert(value);
+    }
+    for (name, component) in deck.components {
+        source.components.entry(name).or_insert(component);
+    }
+    for layer in deck.layers {
+        if !source.layers.iter().any(|l| l.name == layer.name) {
+            source.layers.push(layer);
+        }
+    }
+    Ok(())
+}
+
+fn anim_id(obj: &SceneObject) -> Option<AnimId> {
+    match obj {
+        SceneObject::Animation(a) => Some(a.id),
+        _ => None,
+    }
+}
@@ -0,0 +1,188 @@
+//! Deck statistics (`bs info`).
+//!
+//! Answers "how big is this deck, and why?" for either a source or a compiled
+//! file: contract size, frame count, what the objects are, how large the
+//! compiled output is, and how much each frame's diff weighs. A slide that
+//! rewrites the whole screen every frame shows up as a huge `max` diff long
+//! before it shows up as a sluggish player in front of an audience. The deck's
+//! `metadata` (title, author, date, description) heads the report.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::engine::source::SourcePresentation;
+use crate::types::{Frame, Marker, Metadata, PlayablePresentation};
+
+/// Which kind of file the stats were gathered from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeckKind {
+    Source,
+    Compiled,
+}
+
+/// Everything `bs info` prints.
+#[derive(Debug, Clone)]
+pub struct Stats {
+    pub kind: DeckKind,
+    pub width: u16,
+    pub height: u16,
+    pub frame_count: usize,
+    /// Object count per source `type` tag (empty for a compiled file — the
+    /// objects are gone by then).
+    pub objects_by_type: BTreeMap<&'static str, usize>,
+    /// Size in bytes of the compiled JSON (as `bs compile` writes it).
+    pub compiled_bytes: usize,
+    /// Number of `Frame::Full` entries (normally just the first).
+    pub full_frames: usize,
+    /// Mean changed cells per diff frame (0.0 when there are no diffs).
+    pub avg_diff_cells: f64,
+    /// Largest diff as `(frame index, changed cells)`, if any diff exists.
+    pub max_diff: Option<(usize, usize)>,
+    pub markers: Vec<Marker>,
+    pub metadata: Metadata,
+}
+
+impl Stats {
+    /// Total number of source objects (0 for a compiled file).
+    pub fn object_count(&self) -> usize {
+        self.objects_by_type.values().sum()
+    }
+}
+
+/// Stats for a compiled presentation whose serialized form is `compiled_bytes`
+/// long.
+pub fn playable_stats(p: &PlayablePresentation, compiled_bytes: usize) -> Stats {
+    let mut full_frames = 0;
+    let mut diff_total = 0usize;
+    let mut diff_frames = 0usize;
+    let mut max_diff: Option<(usize, usize)> = None;
+    for (i, frame) in p.frames.iter().enumerate() {
+        match frame {
+            Frame::Full { .. } => full_frames += 1,
+            Frame::Diff { changes } => {
+                diff_frames += 1;
+                diff_total += changes.len();
+                if max_diff.is_none_or(|(_, n)| changes.len() > n) {
+                    max_diff = Some((i, changes.len()));
+                }
+            }
+        }
+    }
+    Stats {
+        kind: DeckKind::Compiled,
+        width: p.contract.width,
+        height: p.contract.height,
+        frame_count: p.frames.len(),
+        objects_by_type: BTreeMap::new(),
+        compiled_bytes,
+        full_frames,
+        avg_diff_cells: if diff_frames == 0 { 0.0 } else { diff_total as f64 / diff_frames as f64 },
+        max_diff,
+        markers: p.markers.clone(),
+        metadata: p.metadata.clone(),
+    }
+}
+
+/// Stats for a source presentation: its own object census plus the stats of
+/// the deck it compiles to.
+pub fn source_stats(source: &SourcePresentation) -> Result<Stats> {
+    let compiled = crate::pipeline::compile(source)?;
+    let bytes = serde_json::to_string_pretty(&compiled)?.len();
+    let mut stats = playable_stats(&compiled, bytes);
+    stats.kind = DeckKind::Source;
+    for obj in &source.objects {
+        *stats.objects_by_type.entry(obj.kind()).or_default() += 1;
+    }
+    Ok(stats)
+}
+
+/// Human-readable byte size (`812 B`, `4.2 KiB`, `3.1 MiB`).
+fn human_bytes(n: usize) -> String {
+    const KIB: f64 = 1024.0;
+    let f = n as f64;
+    if f < KIB {
+        format!("{n} B")
+    } else if f < KIB * KIB {
+        format!("{:.1} KiB", f / KIB)
+    } else {
+        format!("{:.1} MiB", f / (KIB * KIB))
+    }
+}
+
+/// Render `stats` as the indented report `bs info` prints.
+pub fn format_stats(name: &str, stats: &Stats) -> String {
+    let kind = match stats.kind {
+        DeckKind::Source => "source",
+        DeckKind::Compiled => "compiled",
+    };
+    let mut out = format!("{name} ({kind})\n");
+    let Metadata { title, author, date, description } = &stats.metadata;
+    for (label, value) in [("title:", title), ("author:", author), ("date:", date), ("about:", description)] {
+        if let Some(value) = value {
+            out += &format!("  {label:<12}{value}\n");
+        }
+    }
+    out += &format!("  canvas:     {}x{}\n", stats.width, stats.height);
+    out += &format!("  frames:     {}\n", stats.frame_count);
+    if stats.kind == DeckKind::Source {
+        let by_type: Vec<String> =
+            stats.objects_by_type.iter().map(|(t, n)| format!("{t} {n}")).collect();
+        if by_type.is_empty() {
+            out += "  objects:    0\n";
+        } else {
+            out += &format!("  objects:    {} ({})\n", stats.object_count(), by_type.join(", "));
+        }
+    }
+    out += &format!("  compiled:   {}\n", human_bytes(stats.compiled_bytes));
+    match stats.max_diff {
+        Some((frame, n)) => {
+            out += &format!(
+                "  diff cells: avg {:.1}, max {n} (frame {})\n",
+                stats.avg_diff_cells,
+                frame + 1
+            );
+        }
+        None => out += "  diff cells: none (single frame)\n",
+    }
+    if stats.full_frames > 1 {
+        out += &format!("  full frames: {}\n", stats.full_frames);
+    }
+    if stats.markers.is_empty() {
+        out += "  markers:    none\n";
+    } else {
+        out += "  markers:\n";
+        for m in &stats.markers {
+            out += &format!("    frame {:>4}  {}\n", m.frame_index + 1, m.label);
+        }
+    }
+    out
+}
+
+/// Print stats for the file at `path`, detecting whether it is a source
+/// (`objects`) or a compiled (`contract` + `frames`, in any compiled format)
+/// presentation.
+pub fn info_file(path: &str) -> Result<()> {
+    let bytes = crate::pipeline::read_input_bytes(path)?;
+    if crate::pipeline::is_binary(&bytes) {
+        let p = crate::pipeline::parse_playable(&bytes, path)?;
+        print!("{}", format_stats(path, &playable_stats(&p, bytes.len())));
+        return Ok(());
+    }
+    let json = String::from_utf8(bytes).with_context(|| format!("{path} is not UTF-8 JSON"))?;
+    let value: Value =
+        serde_json::from_str(&json).with_context(|| format!("Failed to parse {path}"))?;
+    let stats = if value.get("contract").is_some() {
+        let p: PlayablePresentation = serde_json::from_value(value)
+            .with_context(|| format!("Failed to parse {path} as a compiled presentation"))?;
+        playable_stats(&p, json.len())
+    } else {
+        let mut s = crate::migrate::source_from_value(value)
+            .with_context(|| format!("Failed to parse {path} as a source presentation"))?;
+        crate::pipeline::prepare_source(&mut s, path)?;
+        source_stats(&s).with_context(|| format!("Failed to compile {path}"))?
+    };
+    print!("{}", format_stats(path, &stats));
+    Ok(())
+}
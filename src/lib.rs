@@ -1,8 +1,29 @@
 pub mod art_library;
+#[cfg(feature = "terminal")]
 pub mod editor;
+pub mod builder;
+mod compact;
+pub mod diff;
 pub mod engine;
+pub mod export;
+pub mod import;
+pub mod include;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod info;
+#[cfg(feature = "terminal")]
 pub mod menubar;
 pub mod migrate;
+pub mod msgpack;
+pub mod pipeline;
+#[cfg(feature = "terminal")]
 pub mod player;
 pub mod renderer;
+pub mod serve;
+pub mod stream;
 pub mod types;
+pub mod validate;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "ratatui")]
+pub mod widget;
@@ -1,82 +1,268 @@
-use std::{fs, process};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::process;
 
 use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
 
 use bs::{
-    editor::Editor,
-    engine::{source::SourcePresentation, Engine},
-    player::Player,
-    renderer::Renderer,
-    types::{PlayablePresentation, TerminalContract},
+    editor::{config::EditorConfig, Editor},
+    export::{CastRecorder, ExportFormat, ExportOptions},
+    import::{ImportFormat, ImportOptions},
+    pipeline::{self, CompiledFormat},
+    player::{ColorDepth, EnterHooks, Player, TmuxNotes},
+    renderer::{Renderer, DEFAULT_KEYFRAME_INTERVAL},
+    serve::Server,
+    stream::{self, FrameStream},
+    types::Cell,
 };
 
 fn main() {
-    if let Err(e) = run() {
+    if let Err(e) = run(Cli::parse()) {
         eprintln!("Error: {e:#}");
         process::exit(1);
     }
 }
 
-const COMPILE_USAGE: &str = "bs compile <source.json> <output.json>";
-const PLAY_USAGE: &str = "bs play <presentation.json>";
-const EDIT_USAGE: &str = "bs edit <source.json> [more.json ...]";
-const MIGRATE_USAGE: &str = "bs migrate <source.json>   (upgrades the file in place; writes <source.json>.bak)";
+/// bs — terminal-native presentation engine.
+///
+/// Any deck path may be `-` to read stdin (and `compile`/`export` outputs `-`
+/// to write stdout).
+#[derive(Parser)]
+#[command(name = "bs", version)]
+struct Cli {
+    /// Don't emit colours or text attributes (also set by a non-empty NO_COLOR).
+    #[arg(long, global = true)]
+    no_color: bool,
 
-fn run() -> Result<()> {
-    let mut args = std::env::args().skip(1);
+    /// Editor config file to use instead of ~/.config/bs/editor.json.
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
 
-    match args.next().as_deref() {
-        Some("compile") => {
-            let source_path = args.next().context(COMPILE_USAGE)?;
-            let output_path = args.next().context(COMPILE_USAGE)?;
-            compile(&source_path, &output_path)
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Compile a source deck into a playable presentation.
+    Compile {
+        source: String,
+        output: String,
+        /// Write a full keyframe every N frames so seeking replays at most N
+        /// diffs (0: only the first frame).
+        #[arg(long, value_name = "N", default_value_t = DEFAULT_KEYFRAME_INTERVAL)]
+        keyframe_interval: usize,
+        /// Output encoding: json, the smaller, faster-loading msgpack, or
+        /// stream, which `play` starts at once and reads as it goes.
+        #[arg(long, default_value_t = CompiledFormat::Json, value_parser = parse_compiled_format)]
+        format: CompiledFormat,
+        /// Set `${KEY}` in the deck's text, overriding its `vars` (repeatable).
+        #[arg(long = "set", value_name = "KEY=VALUE", value_parser = parse_var)]
+        vars: Vec<(String, String)>,
+    },
+    /// Play a compiled presentation in the terminal (a stream file is read
+    /// frame by frame as it is shown).
+    Play {
+        presentation: String,
+        /// No colours: keep bold/dim, show highlights as bold and dark text
+        /// as dim — for restricted terminals and serial consoles.
+        #[arg(long)]
+        monochrome: bool,
+        /// Keep tmux pane PANE showing each frame's speaker notes and the
+        /// next frame (the deck must then be a source to have notes).
+        #[arg(long, value_name = "PANE")]
+        tmux_notes: Option<String>,
+        /// The layer holding the speaker notes.
+        #[arg(long, value_name = "LAYER", default_value = "notes", requires = "tmux_notes")]
+        notes_layer: String,
+        /// Record the talk as given to an asciinema cast: every frame shown,
+        /// at the time it was shown.
+        #[arg(long, value_name = "FILE")]
+        record: Option<PathBuf>,
+        /// Run the deck's `on_enter` shell commands as their frames are
+        /// shown.
+        #[arg(long)]
+        allow_exec: bool,
+        /// Append the output of `on_enter` commands to FILE instead of
+        /// dropping it.
+        #[arg(long, value_name = "FILE", requires = "allow_exec")]
+        exec_log: Option<PathBuf>,
+    },
+    /// Present a deck here while browsers follow along: serves an xterm.js
+    /// page that shows whatever frame this terminal is on.
+    Serve {
+        deck: String,
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+        /// Also let terminals follow along with `telnet host PORT` (front it
+        /// with an sshd `ForceCommand` for ssh).
+        #[arg(long, value_name = "PORT")]
+        telnet: Option<u16>,
+        /// Address to listen on; `0.0.0.0` lets other machines watch.
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+        /// No colours in this terminal (the browsers keep theirs).
+        #[arg(long)]
+        monochrome: bool,
+    },
+    /// Open one or more source decks in the interactive editor. Without a
+    /// path, or on one that does not exist yet, it opens on a start screen of
+    /// recent decks and a new-presentation wizard.
+    Edit {
+        sources: Vec<String>,
+    },
+    /// Check a source deck for semantic problems (ranges, animations, members, …).
+    Validate { source: String },
+    /// Print statistics for a source or compiled deck.
+    Info { deck: String },
+    /// Print one frame (numbered from 1) to stdout.
+    Frame {
+        deck: String,
+        n: usize,
+        /// Keep colours as ANSI escape codes (the default unless --no-color).
+        #[arg(long, conflicts_with = "plain")]
+        ansi: bool,
+        /// Characters only, no escape codes.
+        #[arg(long)]
+        plain: bool,
+    },
+    /// Compare two decks: objects added/removed/moved and differing frames.
+    Diff {
+        a: String,
+        b: String,
+        /// Also print each differing frame side by side.
+        #[arg(long)]
+        side_by_side: bool,
+    },
+    /// Export a deck as cast, gif, svg, html, pdf or text (.txt, or .ans for colour).
+    Export {
+        #[arg(value_parser = parse_format)]
+        format: ExportFormat,
+        input: String,
+        output: String,
+        /// Frames per second (cast timing, GIF delay, HTML autoplay).
+        #[arg(long, default_value_t = 1.0, value_parser = parse_positive)]
+        fps: f64,
+        /// CSS font family (SVG/HTML).
+        #[arg(long, default_value = "monospace")]
+        font: String,
+        /// Font-size multiplier (SVG/HTML) or pixel scale (GIF).
+        #[arg(long, default_value_t = 1.0, value_parser = parse_positive)]
+        scale: f64,
+        /// Include a layer the deck hides (repeatable; source decks only).
+        #[arg(long = "show-layer", value_name = "LAYER")]
+        show_layers: Vec<String>,
+        /// Leave out a layer, e.g. speaker notes (repeatable; source decks only).
+        #[arg(long = "hide-layer", value_name = "LAYER")]
+        hide_layers: Vec<String>,
+    },
+    /// Import a recording as a compiled deck (cast: an asciinema v2 file) or a
+    /// diagram as a source deck (dot: a Graphviz graph, laid out in layers).
+    Import {
+        #[arg(value_parser = parse_import_format)]
+        format: ImportFormat,
+        input: String,
+        output: String,
+        /// Sample a frame every SECS of the recording.
+        #[arg(long, value_name = "SECS", default_value_t = 1.0, value_parser = parse_positive)]
+        every: f64,
+        /// Sample a frame at each marker instead of by time.
+        #[arg(long, conflicts_with = "every")]
+        markers: bool,
+        /// Canvas width the diagram is laid out in.
+        #[arg(long, default_value_t = 80)]
+        width: u16,
+        /// Canvas height the diagram is laid out in.
+        #[arg(long, default_value_t = 24)]
+        height: u16,
+    },
+    /// Upgrade an old-format source file in place (writes <source>.bak).
+    Migrate { source: String },
+    /// Cut a source deck down to frames FIRST..=LAST (numbered from 1),
+    /// rebasing object ranges and animations onto them.
+    Trim {
+        source: String,
+        output: String,
+        first: usize,
+        last: usize,
+    },
+}
+
+fn parse_format(s: &str) -> Result<ExportFormat, String> {
+    s.parse().map_err(|e: anyhow::Error| e.to_string())
+}
+
+fn parse_import_format(s: &str) -> Result<ImportFormat, String> {
+    s.parse().map_err(|e: anyhow::Error| e.to_string())
+}
+
+fn parse_compiled_format(s: &str) -> Result<CompiledFormat, String> {
+    s.parse().map_err(|e: anyhow::Error| e.to_string())
+}
+
+fn parse_var(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => Ok((key.trim().to_string(), value.to_string())),
+        _ => Err(format!("expected KEY=VALUE, got `{s}`")),
+    }
+}
+
+fn parse_positive(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(n) if n > 0.0 && n.is_finite() => Ok(n),
+        _ => Err(format!("must be a positive number, got `{s}`")),
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let color = !cli.no_color && std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty());
+    match cli.command {
+        Command::Compile { source, output, keyframe_interval, format, vars } => {
+            compile(&source, &output, keyframe_interval, format, vars)
         }
-        Some("play") => {
-            let path = args.next().context(PLAY_USAGE)?;
-            play(&path)
+        Command::Play { presentation, monochrome, tmux_notes, notes_layer, record, allow_exec, exec_log } => {
+            let notes = tmux_notes.as_deref().map(|pane| (pane, notes_layer.as_str()));
+            let exec = allow_exec.then_some(exec_log.as_deref());
+            play(&presentation, color, monochrome, notes, record.as_deref(), exec)
         }
-        Some("edit") => {
-            let paths: Vec<String> = args.collect();
-            if paths.is_empty() {
-                bail!(EDIT_USAGE);
-            }
-            edit(&paths)
+        Command::Serve { deck, port, telnet, host, monochrome } => serve(&deck, &host, port, telnet, color, monochrome),
+        Command::Edit { sources } => edit(&sources, cli.config),
+        Command::Validate { source } => validate(&source, cli.config),
+        Command::Info { deck } => bs::info::info_file(&deck),
+        Command::Frame { deck, n, ansi, plain } => frame(&deck, n, ansi || (color && !plain)),
+        Command::Diff { a, b, side_by_side } => bs::diff::diff_files(&a, &b, side_by_side),
+        Command::Export { format, input, output, fps, font, scale, show_layers, hide_layers } => {
+            let opts = ExportOptions { fps, font, scale, show_layers, hide_layers };
+            bs::export::export_file(format, &input, &output, &opts)
         }
-        Some("migrate") => {
-            let path = args.next().context(MIGRATE_USAGE)?;
-            bs::migrate::migrate_file(&path)
+        Command::Import { format, input, output, every, markers, width, height } => {
+            let opts = ImportOptions { every, markers, width, height };
+            bs::import::import_file(format, &input, &output, &opts)
         }
-        _ => bail!(
-            "bs — terminal-native presentation engine\n\nUsage:\n  {COMPILE_USAGE}\n  {PLAY_USAGE}\n  {EDIT_USAGE}\n  {MIGRATE_USAGE}"
-        ),
+        Command::Migrate { source } => bs::migrate::migrate_file(&source),
+        Command::Trim { source, output, first, last } => trim(&source, &output, first, last),
     }
 }
 
-fn compile(source_path: &str, output_path: &str) -> Result<()> {
-    let source_json =
-        fs::read_to_string(source_path).with_context(|| format!("Failed to read {source_path}"))?;
-    let source: SourcePresentation = serde_json::from_str(&source_json)
-        .with_context(|| format!("Failed to parse {source_path}"))?;
-
-    // Hard gate: loop ranges must be well-formed and non-overlapping.
-    if let Err(e) = source.validate_loops() {
-        bail!("Invalid loops in {source_path}: {e}");
-    }
+fn compile(
+    source_path: &str,
+    output_path: &str,
+    keyframe_interval: usize,
+    format: CompiledFormat,
+    vars: Vec<(String, String)>,
+) -> Result<()> {
+    let source_json = pipeline::read_input(source_path)?;
+    let mut source = pipeline::parse_source(&source_json, source_path)?;
+    source.vars.extend(vars);
 
-    let scenes = Engine::compile(&source);
-    let contract = TerminalContract {
-        width: source.width,
-        height: source.height,
-    };
-    let mut presentation = Renderer::render(&scenes, contract);
-    presentation.commands = source.command_regions();
-    presentation.loops = source.loop_regions();
-    presentation.animations = source.animation_regions();
-    presentation.auto_advances = source.auto_advance_regions();
+    let compiled = pipeline::compile_checked(&source, keyframe_interval)
+        .with_context(|| format!("Failed to compile {source_path}"))?;
+    bs::validate::print_diagnostics(source_path, &source_json, &compiled.warnings);
+    let presentation = compiled.presentation;
 
-    let output_json = serde_json::to_string_pretty(&presentation)?;
-    fs::write(output_path, &output_json)
-        .with_context(|| format!("Failed to write {output_path}"))?;
+    pipeline::write_output(output_path, format.encode(&presentation)?)?;
 
     eprintln!(
         "Compiled {} frames from {} -> {}",
@@ -88,16 +274,140 @@ fn compile(source_path: &str, output_path: &str) -> Result<()> {
     Ok(())
 }
 
-fn edit(paths: &[String]) -> Result<()> {
-    let mut editor = Editor::open_many(paths)?;
+fn frame(path: &str, n: usize, ansi: bool) -> Result<()> {
+    let presentation = pipeline::load_playable(path)?;
+    let count = presentation.frames.len();
+    if n == 0 || n > count {
+        bail!("{path} has {count} frames; frame {n} is out of range (frames are numbered from 1)");
+    }
+    let text = if ansi {
+        Renderer::frame_to_ansi(&presentation, n - 1)
+    } else {
+        Renderer::frame_to_plain_text(&presentation, n - 1)
+    };
+    pipeline::write_output(pipeline::STDIO, text + "\n")
+}
+
+fn trim(source_path: &str, output_path: &str, first: usize, last: usize) -> Result<()> {
+    let json = pipeline::read_input(source_path)?;
+    let mut source = bs::migrate::source_from_str(&json).with_context(|| format!("Failed to parse {source_path}"))?;
+    // Work on the flat model, as the editor does; includes stay references.
+    if let Err(e) = source.lower() {
+        bail!("invalid slide in {source_path}: {e}");
+    }
+    let count = source.frame_count;
+    if first == 0 || first > last || last > count {
+        bail!("{source_path} has {count} frames; {first}..={last} is not a range of them (frames are numbered from 1)");
+    }
+    let removed = bs::editor::state::trim_frames(&mut source, first - 1, last - 1);
+    pipeline::write_output(output_path, serde_json::to_string_pretty(&source)?)?;
+    eprintln!("Trimmed {removed} frames from {source_path} -> {output_path} ({} left)", source.frame_count);
+    Ok(())
+}
+
+fn serve(path: &str, host: &str, port: u16, telnet: Option<u16>, color: bool, monochrome: bool) -> Result<()> {
+    let presentation = pipeline::load_playable(path)?;
+    let server = Server::bind((host, port), &presentation.contract)?;
+    eprintln!("Serving {path} at http://{}/", server.local_addr());
+    if let Some(port) = telnet {
+        let addr = server.listen_telnet((host, port))?;
+        eprintln!("Terminals can follow with `telnet {} {}`", addr.ip(), addr.port());
+    }
+
+    let mut player = Player::new(presentation.clone()).with_color(color);
+    if monochrome {
+        player = player.with_color_depth(ColorDepth::Monochrome);
+    }
+    player.on_frame_change(move |frame| server.show(&presentation.grid_at(frame)));
+    player.play()
+}
+
+/// Validate with the lint levels the editor would use for this deck: the
+/// user's config with any project config above the deck laid over it.
+fn validate(path: &str, config: Option<PathBuf>) -> Result<()> {
+    let config = match config {
+        Some(file) => EditorConfig::load_file(&file)?,
+        None => EditorConfig::load(),
+    };
+    bs::validate::validate_file(path, &config.for_deck(path)?.lint)
+}
+
+fn edit(paths: &[String], config: Option<PathBuf>) -> Result<()> {
+    let mut editor = match paths {
+        [] => Editor::start(None)?,
+        [path] if !std::path::Path::new(path).exists() => Editor::start(Some(path))?,
+        _ => Editor::open_many(paths)?,
+    };
+    if let Some(path) = config {
+        editor = editor.with_config(EditorConfig::load_file(&path)?);
+    }
     editor.run()
 }
 
-fn play(path: &str) -> Result<()> {
-    let json = fs::read_to_string(path).with_context(|| format!("Failed to read {path}"))?;
-    let presentation: PlayablePresentation =
-        serde_json::from_str(&json).with_context(|| format!("Failed to parse {path}"))?;
+/// `notes` is the tmux pane and notes layer for `--tmux-notes`.
+fn play(
+    path: &str,
+    color: bool,
+    monochrome: bool,
+    notes: Option<(&str, &str)>,
+    record: Option<&Path>,
+    exec: Option<Option<&Path>>,
+) -> Result<()> {
+    // A stream file is played off disk, so even a huge deck starts at once.
+    // Notes live in the source, so with a notes pane a source deck is
+    // compiled here rather than required up front.
+    let streamed = notes.is_none() && path != pipeline::STDIO && stream::is_stream_file(path);
+    let (mut player, presentation, source) = if streamed {
+        (Player::streamed(FrameStream::open(path)?)?, None, None)
+    } else if notes.is_some() {
+        let deck = pipeline::load_deck(path)?;
+        (Player::new(deck.playable.clone()), Some(deck.playable), deck.source)
+    } else {
+        let bytes = pipeline::read_input_bytes(path)?;
+        let presentation = pipeline::parse_playable(&bytes, path)?;
+        (Player::new(presentation.clone()), Some(presentation), None)
+    };
 
-    let mut player = Player::new(presentation);
+    player = player.with_color(color);
+    if monochrome {
+        player = player.with_color_depth(ColorDepth::Monochrome);
+    }
+    if let (Some((pane, layer)), Some(presentation)) = (notes, &presentation) {
+        let text = match &source {
+            Some(source) => source.layer_text(layer),
+            None => {
+                eprintln!("{path} is compiled, so it has no speaker notes; showing the next frame only");
+                Vec::new()
+            }
+        };
+        let tmux = TmuxNotes::new(pane, presentation.clone(), text)?;
+        player.on_frame_change(move |frame| tmux.show(frame));
+    }
+    if !player.enter_commands().is_empty() {
+        match exec {
+            Some(log) => {
+                let mut hooks = EnterHooks::new(player.enter_commands().to_vec(), log)?;
+                player.on_frame_change(move |frame| hooks.run(frame));
+            }
+            None => eprintln!("{path} has on_enter commands; pass --allow-exec to run them"),
+        }
+    }
+    if let Some(record) = record {
+        // A streamed deck is replayed for the recorder from its own handle.
+        let grid_at: Box<dyn Fn(usize) -> Result<Vec<Vec<Cell>>>> = match presentation {
+            Some(presentation) => Box::new(move |frame| Ok(presentation.grid_at(frame))),
+            None => {
+                let stream = FrameStream::open(path)?;
+                Box::new(move |frame| stream.grid_at(frame))
+            }
+        };
+        let file = File::create(record).with_context(|| format!("Failed to create {}", record.display()))?;
+        let mut recorder = CastRecorder::new(BufWriter::new(file), player.contract())?;
+        player.on_frame_change(move |frame| {
+            if let Ok(grid) = grid_at(frame) {
+                let _ = recorder.record(&grid);
+            }
+        });
+    }
     player.play()
 }
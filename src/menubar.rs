@@ -1,17 +1,26 @@
-use std::io;
+use std::io::Write;
 
 use crossterm::{queue, style};
 
 /// Print a menu item string, bolding any text inside `[...]` brackets.
 /// Text outside brackets is printed dim.
-pub fn print_menu_item(stdout: &mut io::Stdout, item: &str) -> anyhow::Result<()> {
+pub fn print_menu_item(stdout: &mut impl Write, item: &str) -> anyhow::Result<()> {
+    let mut dim = style::ContentStyle::new();
+    dim.attributes.set(style::Attribute::Dim);
+    print_menu_item_styled(stdout, item, dim)
+}
+
+/// [`print_menu_item`] with the text outside brackets in `text`; the bracketed
+/// keys are bold over `text`'s colours.
+pub fn print_menu_item_styled(stdout: &mut impl Write, item: &str, text: style::ContentStyle) -> anyhow::Result<()> {
+    let key = style::ContentStyle { attributes: style::Attribute::Bold.into(), ..text };
     let mut rest = item;
     while !rest.is_empty() {
         if let Some(open) = rest.find('[') {
             if open > 0 {
                 queue!(
                     stdout,
-                    style::SetAttribute(style::Attribute::Dim),
+                    style::SetStyle(text),
                     style::Print(&rest[..open]),
                     style::SetAttribute(style::Attribute::Reset),
                 )?;
@@ -20,7 +29,7 @@ pub fn print_menu_item(stdout: &mut io::Stdout, item: &str) -> anyhow::Result<()
             if let Some(close) = rest.find(']') {
                 queue!(
                     stdout,
-                    style::SetAttribute(style::Attribute::Bold),
+                    style::SetStyle(key),
                     style::Print(&rest[..=close]),
                     style::SetAttribute(style::Attribute::Reset),
                 )?;
@@ -32,7 +41,7 @@ pub fn print_menu_item(stdout: &mut io::Stdout, item: &str) -> anyhow::Result<()
         } else {
             queue!(
                 stdout,
-                style::SetAttribute(style::Attribute::Dim),
+                style::SetStyle(text),
                 style::Print(rest),
                 style::SetAttribute(style::Attribute::Reset),
             )?;
@@ -1,4 +1,20 @@
-//! One-shot migration of old-format presentation JSON to the current format.
+//! Source format versions, and the migrations between them.
+//!
+//! A source deck records the schema it was written in as `format_version`
+//! ([`SOURCE_FORMAT_VERSION`] for everything this build saves; a file without
+//! the field predates it and is version 1). Every loader reads through
+//! [`source_from_str`] / [`source_from_value`], which runs the migrations from
+//! the file's version up to the current one on the raw JSON before parsing —
+//! so old decks keep opening — and refuses a version newer than this build
+//! knows with an error saying so, rather than a confusing parse failure.
+//! `bs migrate` runs the same upgrade and writes the result back.
+//!
+//! Compiled presentations carry their own `format_version`
+//! ([`COMPILED_FORMAT_VERSION`](crate::types::COMPILED_FORMAT_VERSION)),
+//! checked as they are decoded; the one compiled schema change so far
+//! (run-length frames) is read in place by the decoder.
+//!
+//! ## Version 1 → 2: animation ids
 //!
 //! The animation model changed to a single source of truth: an animation's span
 //! lives only on the `Animation` object, and animated coordinates reference it by
@@ -23,10 +39,18 @@
 use std::collections::BTreeMap;
 use std::fs;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use serde_json::{json, Value};
 
-use crate::engine::source::SourcePresentation;
+use crate::engine::source::{SourcePresentation, SOURCE_FORMAT_VERSION};
+
+/// The version of a source with no `format_version`: anything saved before
+/// the field existed.
+pub const UNVERSIONED_SOURCE: u32 = 1;
+
+/// `SOURCE_MIGRATIONS[i]` upgrades a source from version `i + 1` to `i + 2`.
+const SOURCE_MIGRATIONS: [fn(&mut Value) -> Result<Report>; SOURCE_FORMAT_VERSION as usize - 1] =
+    [migrate_value];
 
 /// Outcome of a migration, for reporting.
 #[derive(Debug, Default, PartialEq, Eq)]
@@ -44,6 +68,51 @@ impl Report {
     pub fn unchanged(&self) -> bool {
         self.coords_linked == 0 && self.ids_assigned == 0 && self.synthesized == 0
     }
+
+    fn absorb(&mut self, other: Report) {
+        self.coords_linked += other.coords_linked;
+        self.ids_assigned += other.ids_assigned;
+        self.synthesized += other.synthesized;
+    }
+}
+
+/// Parse source JSON written in any supported `format_version`.
+pub fn source_from_str(json: &str) -> Result<SourcePresentation> {
+    source_from_value(serde_json::from_str(json)?)
+}
+
+/// [`source_from_str`] over JSON already parsed.
+pub fn source_from_value(mut value: Value) -> Result<SourcePresentation> {
+    upgrade_source(&mut value)?;
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Bring the source document `root` up to [`SOURCE_FORMAT_VERSION`] in place,
+/// stamping it with that version. Returns the version it was written in and
+/// what the migrations changed; fails on a version this build doesn't know.
+pub fn upgrade_source(root: &mut Value) -> Result<(u32, Report)> {
+    let Some(doc) = root.as_object_mut() else {
+        bail!("not a presentation (expected a JSON object)");
+    };
+    let version = match doc.get("format_version") {
+        None => UNVERSIONED_SOURCE,
+        Some(v) => match v.as_u64().and_then(|v| u32::try_from(v).ok()) {
+            Some(v) if v >= 1 => v,
+            _ => bail!("format_version must be a positive whole number, got {v}"),
+        },
+    };
+    if version > SOURCE_FORMAT_VERSION {
+        bail!(
+            "this deck is format_version {version}, newer than this bs understands \
+             ({SOURCE_FORMAT_VERSION}); upgrade bs to open it"
+        );
+    }
+    doc.insert("format_version".into(), json!(SOURCE_FORMAT_VERSION));
+    let mut report = Report::default();
+    for step in &SOURCE_MIGRATIONS[version as usize - 1..] {
+        report.absorb(step(root)?);
+    }
+    Ok((version, report))
 }
 
 /// Migrate `path` **in place**, saving the original to `<path>.bak` first. A
@@ -54,10 +123,10 @@ pub fn migrate_file(path: &str) -> Result<()> {
     let mut json: Value = serde_json::from_str(&original)
         .with_context(|| format!("Failed to parse {path} as JSON"))?;
 
-    let report = migrate_value(&mut json)
+    let (version, report) = upgrade_source(&mut json)
         .with_context(|| format!("Failed to migrate {path}"))?;
 
-    if report.unchanged() {
+    if version == SOURCE_FORMAT_VERSION {
         eprintln!("{path} is already in the current format — nothing to do.");
         return Ok(());
     }
@@ -76,7 +145,7 @@ pub fn migrate_file(path: &str) -> Result<()> {
     fs::write(path, &migrated).with_context(|| format!("Failed to write {path}"))?;
 
     eprintln!(
-        "Migrated {path} (backup: {backup}) — linked {} coordinate{}, assigned {} id{}, synthesized {} animation{}.",
+        "Migrated {path} from format_version {version} (backup: {backup}) — linked {} coordinate{}, assigned {} id{}, synthesized {} animation{}.",
         report.coords_linked, plural(report.coords_linked),
         report.ids_assigned, plural(report.ids_assigned),
         report.synthesized, plural(report.synthesized),
@@ -91,10 +160,10 @@ fn plural(n: usize) -> &'static str {
 /// Transform a parsed presentation `Value` in place from the old animation format
 /// to the current one. Pure (no I/O) so it can be unit-tested.
 pub fn migrate_value(root: &mut Value) -> Result<Report> {
-    let objects = root
-        .get_mut("objects")
-        .and_then(Value::as_array_mut)
-        .context("not a presentation (no `objects` array)")?;
+    let Some(objects) = root.get_mut("objects").and_then(Value::as_array_mut) else {
+        // A deck of `slides` alone postdates the old model: nothing to do.
+        return Ok(Report::default());
+    };
 
     // Pass 1: give every animation object an id and record span → id.
     let mut span_to_id: BTreeMap<(i64, i64), u64> = BTreeMap::new();
@@ -311,6 +380,38 @@ mod tests {
         assert_eq!(v, before, "value untouched");
     }
 
+    #[test]
+    fn unversioned_sources_are_upgraded_and_stamped() {
+        let old = r#"{ "width": 4, "height": 1, "frame_count": 2, "objects": [
+            { "type": "label", "text": "X",
+              "position": { "x": { "animated": { "from": 0, "to": 3, "start_frame": 0, "end_frame": 1 } },
+                            "y": { "fixed": 0 } },
+              "frames": { "start": 0, "end": 2 } }
+        ] }"#;
+        let mut v = val(old);
+        let (version, report) = upgrade_source(&mut v).unwrap();
+        assert_eq!((version, report.coords_linked, report.synthesized), (1, 1, 1));
+        assert_eq!(v["format_version"], json!(SOURCE_FORMAT_VERSION));
+        let source = source_from_str(old).unwrap();
+        assert_eq!(source.format_version, SOURCE_FORMAT_VERSION);
+
+        // A current document passes through with no migration run.
+        let mut current = serde_json::to_value(&source).unwrap();
+        let (version, report) = upgrade_source(&mut current).unwrap();
+        assert_eq!(version, SOURCE_FORMAT_VERSION);
+        assert!(report.unchanged());
+    }
+
+    #[test]
+    fn newer_or_malformed_versions_are_refused() {
+        let newer = format!(r#"{{"format_version": {}, "width": 4, "height": 1, "objects": []}}"#, SOURCE_FORMAT_VERSION + 1);
+        let err = source_from_str(&newer).unwrap_err().to_string();
+        assert!(err.contains("newer than this bs understands"), "{err}");
+        let err = source_from_str(r#"{"format_version": "2", "width": 4, "height": 1, "objects": []}"#).unwrap_err();
+        assert!(err.to_string().contains("positive whole number"));
+        assert!(source_from_str(r#"{"format_version": 0, "width": 4, "height": 1, "objects": []}"#).is_err());
+    }
+
     #[test]
     fn synthesized_ids_avoid_existing_ones() {
         // An existing animation has id 7; a synthesized one must not collide.
@@ -0,0 +1,236 @@
+//! MessagePack form of a compiled [`PlayablePresentation`]
+//! (`bs compile --format msgpack`).
+//!
+//! The binary file carries exactly the same document as the JSON one — the
+//! [compact](crate::compact) wire shape with its style table and run-length
+//! frames — just encoded as MessagePack instead of text: about half the size of
+//! even minified JSON (a fraction of the pretty-printed default) and no
+//! number/string scanning at startup. Encoding goes through
+//! [`serde_json::Value`], so the two formats can't drift apart.
+//!
+//! A compiled document is always a map, so a file whose first byte is a
+//! MessagePack map marker (`0x80..=0x8f`, `0xde`, `0xdf`) is binary; JSON
+//! starts with `{` or whitespace. [`is_msgpack`] is what `play` and the other
+//! readers use to autodetect.
+
+use anyhow::{bail, Context, Result};
+use serde_json::{Map, Number, Value};
+
+use crate::types::PlayablePresentation;
+
+/// Deepest nesting [`decode`] accepts; compiled decks nest four or five levels.
+const MAX_DEPTH: usize = 64;
+
+/// True when `bytes` starts like a MessagePack map (i.e. is not JSON text).
+pub fn is_msgpack(bytes: &[u8]) -> bool {
+    matches!(bytes.first(), Some(0x80..=0x8f | 0xde | 0xdf))
+}
+
+/// Encode a compiled presentation as MessagePack.
+pub fn to_msgpack(presentation: &PlayablePresentation) -> Result<Vec<u8>> {
+    let value = serde_json::to_value(presentation)?;
+    let mut out = Vec::new();
+    encode(&value, &mut out);
+    Ok(out)
+}
+
+/// Decode a compiled presentation written by [`to_msgpack`].
+pub fn from_msgpack(bytes: &[u8]) -> Result<PlayablePresentation> {
+    let value = decode(bytes)?;
+    serde_json::from_value(value).context("not a compiled presentation")
+}
+
+/// Append the MessagePack encoding of `value` to `out`.
+pub fn encode(value: &Value, out: &mut Vec<u8>) {
+    match value {
+        Value::Null => out.push(0xc0),
+        Value::Bool(b) => out.push(if *b { 0xc3 } else { 0xc2 }),
+        Value::Number(n) => {
+            if let Some(u) = n.as_u64() {
+                encode_uint(u, out);
+            } else if let Some(i) = n.as_i64() {
+                encode_int(i, out);
+            } else {
+                out.push(0xcb);
+                out.extend_from_slice(&n.as_f64().unwrap_or(0.0).to_be_bytes());
+            }
+        }
+        Value::String(s) => {
+            encode_len(s.len(), 0xa0, 32, [0xd9, 0xda, 0xdb], out);
+            out.extend_from_slice(s.as_bytes());
+        }
+        Value::Array(items) => {
+            encode_len(items.len(), 0x90, 16, [0, 0xdc, 0xdd], out);
+            for item in items {
+                encode(item, out);
+            }
+        }
+        Value::Object(map) => {
+            encode_len(map.len(), 0x80, 16, [0, 0xde, 0xdf], out);
+            for (k, v) in map {
+                encode(&Value::String(k.clone()), out);
+                encode(v, out);
+            }
+        }
+    }
+}
+
+fn encode_uint(u: u64, out: &mut Vec<u8>) {
+    if u < 0x80 {
+        out.push(u as u8);
+    } else if let Ok(b) = u8::try_from(u) {
+        out.extend_from_slice(&[0xcc, b]);
+    } else if let Ok(h) = u16::try_from(u) {
+        out.push(0xcd);
+        out.extend_from_slice(&h.to_be_bytes());
+    } else if let Ok(w) = u32::try_from(u) {
+        out.push(0xce);
+        out.extend_from_slice(&w.to_be_bytes());
+    } else {
+        out.push(0xcf);
+        out.extend_from_slice(&u.to_be_bytes());
+    }
+}
+
+/// Negative integers only (non-negative ones go through [`encode_uint`]).
+fn encode_int(i: i64, out: &mut Vec<u8>) {
+    if i >= -32 {
+        out.push(i as i8 as u8);
+    } else if let Ok(b) = i8::try_from(i) {
+        out.extend_from_slice(&[0xd0, b as u8]);
+    } else if let Ok(h) = i16::try_from(i) {
+        out.push(0xd1);
+        out.extend_from_slice(&h.to_be_bytes());
+    } else if let Ok(w) = i32::try_from(i) {
+        out.push(0xd2);
+        out.extend_from_slice(&w.to_be_bytes());
+    } else {
+        out.push(0xd3);
+        out.extend_from_slice(&i.to_be_bytes());
+    }
+}
+
+/// A length header: the `fix` form (`fix | len`) below `fix_max`, otherwise
+/// the 8/16/32-bit marker from `wide` (a 0 marker means no 8-bit form).
+fn encode_len(len: usize, fix: u8, fix_max: usize, wide: [u8; 3], out: &mut Vec<u8>) {
+    if len < fix_max {
+        out.push(fix | len as u8);
+    } else if wide[0] != 0 && len <= u8::MAX as usize {
+        out.extend_from_slice(&[wide[0], len as u8]);
+    } else if len <= u16::MAX as usize {
+        out.push(wide[1]);
+        out.extend_from_slice(&(len as u16).to_be_bytes());
+    } else {
+        out.push(wide[2]);
+        out.extend_from_slice(&(len as u32).to_be_bytes());
+    }
+}
+
+/// Decode one MessagePack value spanning all of `bytes`.
+pub fn decode(bytes: &[u8]) -> Result<Value> {
+    let mut reader = Reader { bytes, pos: 0 };
+    let value = reader.value(0)?;
+    if reader.pos != bytes.len() {
+        bail!("{} trailing bytes after the MessagePack value", bytes.len() - reader.pos);
+    }
+    Ok(value)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, n: usize) -> Result<&[u8]> {
+        let Some(slice) = self.bytes.get(self.pos..).and_then(|rest| rest.get(..n)) else {
+            bail!("truncated MessagePack at byte {}", self.pos);
+        };
+        self.pos += n;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.take(N)?.try_into().expect("take returns N bytes"))
+    }
+
+    fn len(&mut self, width: usize) -> Result<usize> {
+        Ok(match width {
+            1 => self.array::<1>()?[0] as usize,
+            2 => u16::from_be_bytes(self.array()?) as usize,
+            _ => u32::from_be_bytes(self.array()?) as usize,
+        })
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value> {
+        if depth > MAX_DEPTH {
+            bail!("MessagePack nested deeper than {MAX_DEPTH} levels");
+        }
+        let marker = self.array::<1>()?[0];
+        Ok(match marker {
+            0x00..=0x7f => Value::from(marker),
+            0xe0..=0xff => Value::from(marker as i8),
+            0x80..=0x8f => self.map((marker & 0x0f) as usize, depth)?,
+            0x90..=0x9f => self.list((marker & 0x0f) as usize, depth)?,
+            0xa0..=0xbf => self.string((marker & 0x1f) as usize)?,
+            0xc0 => Value::Null,
+            0xc2 => Value::Bool(false),
+            0xc3 => Value::Bool(true),
+            0xca => float(f32::from_be_bytes(self.array()?) as f64),
+            0xcb => float(f64::from_be_bytes(self.array()?)),
+            0xcc => Value::from(u8::from_be_bytes(self.array()?)),
+            0xcd => Value::from(u16::from_be_bytes(self.array()?)),
+            0xce => Value::from(u32::from_be_bytes(self.array()?)),
+            0xcf => Value::from(u64::from_be_bytes(self.array()?)),
+            0xd0 => Value::from(i8::from_be_bytes(self.array()?)),
+            0xd1 => Value::from(i16::from_be_bytes(self.array()?)),
+            0xd2 => Value::from(i32::from_be_bytes(self.array()?)),
+            0xd3 => Value::from(i64::from_be_bytes(self.array()?)),
+            0xd9..=0xdb => {
+                let len = self.len(1 << (marker - 0xd9))?;
+                self.string(len)?
+            }
+            0xdc | 0xdd => {
+                let len = self.len(2 << (marker - 0xdc))?;
+                self.list(len, depth)?
+            }
+            0xde | 0xdf => {
+                let len = self.len(2 << (marker - 0xde))?;
+                self.map(len, depth)?
+            }
+            _ => bail!("unsupported MessagePack type 0x{marker:02x} at byte {}", self.pos - 1),
+        })
+    }
+
+    fn string(&mut self, len: usize) -> Result<Value> {
+        let at = self.pos;
+        let s = std::str::from_utf8(self.take(len)?)
+            .with_context(|| format!("invalid UTF-8 in MessagePack string at byte {at}"))?;
+        Ok(Value::String(s.to_string()))
+    }
+
+    fn list(&mut self, len: usize, depth: usize) -> Result<Value> {
+        // Every element is at least one byte, so a bogus length can't
+        // over-allocate past the input.
+        let mut items = Vec::with_capacity(len.min(self.bytes.len() - self.pos));
+        for _ in 0..len {
+            items.push(self.value(depth + 1)?);
+        }
+        Ok(Value::Array(items))
+    }
+
+    fn map(&mut self, len: usize, depth: usize) -> Result<Value> {
+        let mut map = Map::new();
+        for _ in 0..len {
+            let Value::String(key) = self.value(depth + 1)? else {
+                bail!("non-string MessagePack map key before byte {}", self.pos);
+            };
+            map.insert(key, self.value(depth + 1)?);
+        }
+        Ok(Value::Object(map))
+    }
+}
+
+fn float(f: f64) -> Value {
+    Number::from_f64(f).map_or(Value::Null, Value::Number)
+}
@@ -0,0 +1,300 @@
+//! The full source → playable pipeline in one call.
+//!
+//! `Engine::compile` and `Renderer::render` stay separate stages (the editor
+//! preview drives them individually), but everything that turns a source file
+//! into a playable deck — the CLI's `compile`, `info`, `export`, `diff` — goes
+//! through [`compile`] so the loop gate and the play-time sidecars can't be forgotten
+//! by one caller and not another.
+
+use std::fmt;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+
+use crate::engine::{source::SourcePresentation, Engine};
+use crate::include;
+use crate::migrate;
+use crate::msgpack;
+use crate::renderer::{Renderer, DEFAULT_KEYFRAME_INTERVAL};
+use crate::stream;
+use crate::types::{Overlay, PlayablePresentation};
+use crate::validate::{self, Diagnostic};
+
+/// Compile `source` into a playable presentation: resolve every frame, render
+/// and diff the grids, and attach the play-time sidecars (commands, loops,
+/// animations, auto-advances, overlays).
+///
+/// Fails only on the hard gates: loop ranges must be well-formed and disjoint
+/// (`SourcePresentation::validate_loops`), and every `instance` must expand
+/// (`SourcePresentation::validate_instances`).
+pub fn compile(source: &SourcePresentation) -> Result<PlayablePresentation> {
+    compile_with_keyframes(source, DEFAULT_KEYFRAME_INTERVAL)
+}
+
+/// [`compile`] with a keyframe every `interval` frames (see
+/// [`Renderer::render_with_keyframes`]).
+pub fn compile_with_keyframes(
+    source: &SourcePresentation,
+    interval: usize,
+) -> Result<PlayablePresentation> {
+    Ok(compile_checked(source, interval)?.presentation)
+}
+
+/// A compiled deck plus the non-fatal findings from compiling it.
+#[derive(Debug, Clone)]
+pub struct Compiled {
+    pub presentation: PlayablePresentation,
+    /// Undefined `${name}` text ([`validate::undefined_vars`]), objects cut
+    /// off by `frame_count` ([`validate::past_the_end`]) and objects the
+    /// renderer clipped at the canvas edge, and on which frames
+    /// ([`validate::out_of_bounds`]), in object order.
+    pub warnings: Vec<Diagnostic>,
+}
+
+/// [`compile_with_keyframes`], keeping the compile warnings `bs compile` prints.
+pub fn compile_checked(source: &SourcePresentation, interval: usize) -> Result<Compiled> {
+    // Decks built in memory reach here unlowered.
+    let lowered;
+    let source = if source.slides.is_empty() && source.frame_count != 0 {
+        source
+    } else {
+        let mut s = source.clone();
+        if let Err(e) = s.lower() {
+            bail!("invalid slide: {e}");
+        }
+        lowered = s;
+        &lowered
+    };
+    if let Err(e) = source.validate_loops() {
+        bail!("invalid loops: {e}");
+    }
+    if let Err(e) = source.validate_instances() {
+        bail!("invalid instance: {e}");
+    }
+
+    // Overlay layers stay out of the frames, each kept as the cells it
+    // changes on them.
+    let overlay_names = source.overlay_layers();
+    let base = (!overlay_names.is_empty()).then(|| source.with_overlay(None));
+    let frames_source = base.as_ref().unwrap_or(source);
+    let (scenes, clipped) = Engine::compile_reporting(frames_source);
+    let mut presentation = Renderer::render_with_keyframes(&scenes, source.contract(), interval);
+    presentation.overlays = overlay_names
+        .into_iter()
+        .map(|name| Overlay {
+            name: name.to_string(),
+            frames: Renderer::overlay_frames(
+                &scenes,
+                &Engine::compile(&source.with_overlay(Some(name))),
+                &presentation.contract,
+            ),
+        })
+        .collect();
+    presentation.commands = frames_source.command_regions();
+    presentation.clocks = frames_source.clock_regions();
+    presentation.loops = source.loop_regions();
+    presentation.animations = source.animation_regions();
+    presentation.auto_advances = source.auto_advance_regions();
+    presentation.durations = source.frame_durations();
+    presentation.on_enter = source.frames_on_enter();
+    presentation.markers = source.section_markers();
+    presentation.metadata = source.metadata.clone();
+    let mut warnings = validate::undefined_vars(source);
+    warnings.extend(validate::past_the_end(source));
+    warnings.extend(validate::out_of_bounds(source, &clipped));
+    warnings.sort_by_key(|d| d.object);
+    Ok(Compiled { presentation, warnings })
+}
+
+/// The encodings `bs compile --format` can write a compiled deck in. Readers
+/// autodetect them ([`parse_playable`], [`load_deck`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompiledFormat {
+    /// Pretty-printed JSON — diffable and easy to inspect with `jq`.
+    #[default]
+    Json,
+    /// MessagePack ([`crate::msgpack`]) — several times smaller and faster to load.
+    Msgpack,
+    /// Seekable frame records ([`crate::stream`]) — `play` starts at once and
+    /// reads frames as it shows them, however long the deck.
+    Stream,
+}
+
+impl CompiledFormat {
+    pub const ALL: &'static [CompiledFormat] = &[CompiledFormat::Json, CompiledFormat::Msgpack, CompiledFormat::Stream];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CompiledFormat::Json => "json",
+            CompiledFormat::Msgpack => "msgpack",
+            CompiledFormat::Stream => "stream",
+        }
+    }
+
+    /// Serialize `presentation` in this format.
+    pub fn encode(self, presentation: &PlayablePresentation) -> Result<Vec<u8>> {
+        match self {
+            CompiledFormat::Json => Ok(serde_json::to_string_pretty(presentation)?.into_bytes()),
+            CompiledFormat::Msgpack => msgpack::to_msgpack(presentation),
+            CompiledFormat::Stream => stream::to_stream(presentation),
+        }
+    }
+}
+
+impl fmt::Display for CompiledFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CompiledFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match CompiledFormat::ALL.iter().find(|f| f.as_str() == s) {
+            Some(f) => Ok(*f),
+            None => {
+                let known: Vec<&str> = CompiledFormat::ALL.iter().map(|f| f.as_str()).collect();
+                bail!("unknown compiled format `{s}` (expected one of: {})", known.join(", "))
+            }
+        }
+    }
+}
+
+/// Parse a compiled presentation in any [`CompiledFormat`]; `path` only
+/// labels errors.
+pub fn parse_playable(bytes: &[u8], path: &str) -> Result<PlayablePresentation> {
+    if stream::is_stream(bytes) {
+        return stream::from_stream(bytes).with_context(|| format!("Failed to parse {path}"));
+    }
+    if msgpack::is_msgpack(bytes) {
+        return msgpack::from_msgpack(bytes).with_context(|| format!("Failed to parse {path}"));
+    }
+    let json = std::str::from_utf8(bytes).with_context(|| format!("{path} is not UTF-8 JSON"))?;
+    serde_json::from_str(json).with_context(|| format!("Failed to parse {path}"))
+}
+
+/// True when `bytes` is one of the binary [`CompiledFormat`]s rather than
+/// JSON text.
+pub fn is_binary(bytes: &[u8]) -> bool {
+    msgpack::is_msgpack(bytes) || stream::is_stream(bytes)
+}
+
+/// A deck loaded from disk: the compiled presentation, plus the source it was
+/// compiled from when the file was a source.
+#[derive(Debug, Clone)]
+pub struct LoadedDeck {
+    pub source: Option<SourcePresentation>,
+    pub playable: PlayablePresentation,
+}
+
+/// Load the deck at `path` for playback-side tooling (exporters, inspectors):
+/// a compiled presentation (one with a `contract`, in either
+/// [`CompiledFormat`]) is parsed as-is, a source presentation is compiled
+/// first — so every such tool accepts either.
+pub fn load_playable(path: &str) -> Result<PlayablePresentation> {
+    Ok(load_deck(path)?.playable)
+}
+
+/// Like [`load_playable`], but keeps the source around for tools that also
+/// look at the objects (`diff`).
+pub fn load_deck(path: &str) -> Result<LoadedDeck> {
+    let bytes = read_input_bytes(path)?;
+    if is_binary(&bytes) {
+        return Ok(LoadedDeck { source: None, playable: parse_playable(&bytes, path)? });
+    }
+    let json = String::from_utf8(bytes).with_context(|| format!("{path} is not UTF-8 JSON"))?;
+    parse_deck(&json, path)
+}
+
+/// [`load_deck`] over JSON already in memory; `path` labels errors and
+/// locates a source's includes.
+pub fn parse_deck(json: &str, path: &str) -> Result<LoadedDeck> {
+    let value: Value =
+        serde_json::from_str(json).with_context(|| format!("Failed to parse {path}"))?;
+    if value.get("contract").is_some() {
+        let playable = serde_json::from_value(value)
+            .with_context(|| format!("Failed to parse {path} as a compiled presentation"))?;
+        Ok(LoadedDeck { source: None, playable })
+    } else {
+        let mut source = migrate::source_from_value(value)
+            .with_context(|| format!("Failed to parse {path} as a source presentation"))?;
+        prepare_source(&mut source, path)?;
+        let playable = compile(&source).with_context(|| format!("Failed to compile {path}"))?;
+        Ok(LoadedDeck { source: Some(source), playable })
+    }
+}
+
+/// Parse the source deck `json` read from `path`, lowering its slides and
+/// resolving its includes ([`prepare_source`]).
+pub fn parse_source(json: &str, path: &str) -> Result<SourcePresentation> {
+    let mut source = migrate::source_from_str(json).with_context(|| format!("Failed to parse {path}"))?;
+    prepare_source(&mut source, path)?;
+    Ok(source)
+}
+
+/// What every load of a source deck from `path` does after parsing it: lower
+/// its `slides` and derive an omitted `frame_count`
+/// ([`SourcePresentation::lower`]), then resolve its
+/// includes relative to the file ([`include::resolve_includes`]).
+pub(crate) fn prepare_source(source: &mut SourcePresentation, path: &str) -> Result<()> {
+    if let Err(e) = source.lower() {
+        bail!("invalid slide in {path}: {e}");
+    }
+    include::resolve_includes(source, &include_dir(path))
+}
+
+/// The directory `path`'s includes are relative to: its parent, or the
+/// working directory for stdin.
+fn include_dir(path: &str) -> PathBuf {
+    match Path::new(path).parent() {
+        Some(dir) if path != STDIO => dir.to_path_buf(),
+        _ => PathBuf::new(),
+    }
+}
+
+/// The path argument that means stdin (as an input) or stdout (as an output),
+/// so the CLI composes with `curl`, `jq` and build pipelines.
+pub const STDIO: &str = "-";
+
+/// Read the whole of `path`, or stdin when `path` is [`STDIO`].
+pub fn read_input(path: &str) -> Result<String> {
+    if path == STDIO {
+        let mut s = String::new();
+        io::stdin().read_to_string(&mut s).context("Failed to read stdin")?;
+        Ok(s)
+    } else {
+        fs::read_to_string(path).with_context(|| format!("Failed to read {path}"))
+    }
+}
+
+/// [`read_input`] without the UTF-8 requirement, for files that may be binary
+/// compiled decks.
+pub fn read_input_bytes(path: &str) -> Result<Vec<u8>> {
+    if path == STDIO {
+        let mut bytes = Vec::new();
+        io::stdin().read_to_end(&mut bytes).context("Failed to read stdin")?;
+        Ok(bytes)
+    } else {
+        fs::read(path).with_context(|| format!("Failed to read {path}"))
+    }
+}
+
+/// Write `contents` to `path`, or to stdout when `path` is [`STDIO`]; a
+/// stdout whose reader has gone away is not an error.
+pub fn write_output(path: &str, contents: impl AsRef<[u8]>) -> Result<()> {
+    if path == STDIO {
+        let mut out = io::stdout().lock();
+        match out.write_all(contents.as_ref()).and_then(|_| out.flush()) {
+            // The reader stopped early (`| head`): there's no one left to write to.
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+            written => written.context("Failed to write stdout"),
+        }
+    } else {
+        fs::write(path, contents).with_context(|| format!("Failed to write {path}"))
+    }
+}
@@ -0,0 +1,156 @@
+//! Terminal colour capability.
+//!
+//! Decks may use arbitrary `Color::Rgb` values, but a terminal without
+//! 24-bit support mangles `38;2;r;g;b` sequences — usually into the wrong
+//! palette entry, sometimes into stray attributes. The player therefore works
+//! out how many colours the terminal advertises ([`ColorDepth::detect`]) and
+//! quantizes RGB to the nearest xterm palette entry when it has to.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use crossterm::style;
+
+use super::to_ct_color;
+use crate::types::{nearest_xterm, Color, Style};
+
+/// terminfo's `colors` is the 14th numeric capability.
+const TERMINFO_COLORS: usize = 13;
+
+/// How many colours the terminal can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    /// 24-bit RGB is passed through as-is.
+    TrueColor,
+    /// RGB is quantized to the 6×6×6 cube and grey ramp of the 256-colour palette.
+    Ansi256,
+    /// RGB is quantized to the 16 system colours.
+    Ansi16,
+    /// No colours at all (`play --monochrome`): see [`monochrome_style`].
+    Monochrome,
+}
+
+impl ColorDepth {
+    /// Detect the current terminal's depth from `COLORTERM`, then the
+    /// terminfo entry for `TERM`, then the `TERM` name itself.
+    pub fn detect() -> Self {
+        let colorterm = env::var("COLORTERM").ok();
+        let term = env::var("TERM").ok();
+        let colors = term.as_deref().and_then(terminfo_colors);
+        Self::from_env(colorterm.as_deref(), term.as_deref(), colors)
+    }
+
+    /// The decision behind [`Self::detect`], given the `COLORTERM` and `TERM`
+    /// values and terminfo's `colors` for `TERM` (when an entry was found).
+    pub fn from_env(colorterm: Option<&str>, term: Option<&str>, terminfo_colors: Option<u32>) -> Self {
+        if matches!(colorterm, Some("truecolor" | "24bit")) {
+            return ColorDepth::TrueColor;
+        }
+        match terminfo_colors {
+            Some(n) if n >= 1 << 24 => ColorDepth::TrueColor,
+            Some(n) if n >= 256 => ColorDepth::Ansi256,
+            Some(_) => ColorDepth::Ansi16,
+            None => match term {
+                Some(t) if t.ends_with("-direct") => ColorDepth::TrueColor,
+                Some(t) if t.contains("256color") => ColorDepth::Ansi256,
+                _ => ColorDepth::Ansi16,
+            },
+        }
+    }
+
+    /// The crossterm colour to emit for `c` at this depth. Named colours are
+    /// palette entries already and pass through unchanged; monochrome is
+    /// always the terminal default.
+    pub fn ct_color(self, c: &Color) -> style::Color {
+        match (self, c) {
+            (ColorDepth::Monochrome, _) => style::Color::Reset,
+            (ColorDepth::Ansi256, Color::Rgb { r, g, b }) => {
+                style::Color::AnsiValue(nearest_xterm((*r, *g, *b), 16..256))
+            }
+            (ColorDepth::Ansi16, Color::Rgb { r, g, b }) => {
+                style::Color::AnsiValue(nearest_xterm((*r, *g, *b), 0..16))
+            }
+            _ => to_ct_color(c),
+        }
+    }
+}
+
+/// Foregrounds darker than this (perceived brightness, 0–1) render dim in
+/// monochrome — black, greys and navy read as de-emphasized text.
+const DARK_FG: f64 = 0.3;
+
+/// `s` without colours, keeping its meaning in attributes: bold, dim and
+/// underline stay, a cell highlighted with a `bg` other than the deck's
+/// `background` turns bold, and a dark foreground turns dim.
+pub fn monochrome_style(s: &Style, background: Option<&Color>) -> style::ContentStyle {
+    let highlighted = s.bg.is_some() && s.bg.as_ref() != background;
+    let dark = s.fg.as_ref().is_some_and(|c| brightness(c.rgb()) < DARK_FG);
+    let mut cs = style::ContentStyle::default();
+    if s.bold || highlighted {
+        cs.attributes.set(style::Attribute::Bold);
+    }
+    if s.dim || (dark && !s.bold && !highlighted) {
+        cs.attributes.set(style::Attribute::Dim);
+    }
+    if s.underline {
+        cs.attributes.set(style::Attribute::Underlined);
+    }
+    cs
+}
+
+/// Perceived brightness of an RGB colour in 0–1 (the HSP model).
+fn brightness((r, g, b): (u8, u8, u8)) -> f64 {
+    let c = |v: u8, w: f64| w * (v as f64 / 255.0).powi(2);
+    (c(r, 0.299) + c(g, 0.587) + c(b, 0.114)).sqrt()
+}
+
+/// terminfo's `colors` for `term`, looked up in the usual database
+/// locations (`$TERMINFO`, `~/.terminfo`, `$TERMINFO_DIRS`, the system dirs).
+fn terminfo_colors(term: &str) -> Option<u32> {
+    let first = term.chars().next()?;
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    dirs.extend(env::var_os("TERMINFO").map(PathBuf::from));
+    dirs.extend(env::var_os("HOME").map(|h| PathBuf::from(h).join(".terminfo")));
+    if let Some(list) = env::var_os("TERMINFO_DIRS") {
+        dirs.extend(env::split_paths(&list).filter(|p| !p.as_os_str().is_empty()));
+    }
+    for sys in ["/etc/terminfo", "/lib/terminfo", "/usr/share/terminfo", "/usr/lib/terminfo"] {
+        dirs.push(PathBuf::from(sys));
+    }
+    // Linux buckets entries by first letter, macOS by its hex code.
+    let buckets = [first.to_string(), format!("{:x}", first as u32)];
+    dirs.iter()
+        .flat_map(|d| buckets.iter().map(move |b| d.join(b).join(term)))
+        .find_map(|path| fs::read(path).ok())
+        .and_then(|bytes| parse_terminfo_colors(&bytes))
+}
+
+/// Read `colors` out of a compiled terminfo entry (legacy 16-bit or ncurses
+/// 6.1's 32-bit number format). `None` when absent or malformed.
+pub fn parse_terminfo_colors(bytes: &[u8]) -> Option<u32> {
+    let short = |i: usize| -> Option<usize> {
+        let b = bytes.get(i * 2..i * 2 + 2)?;
+        Some(u16::from_le_bytes([b[0], b[1]]) as usize)
+    };
+    let width = match short(0)? {
+        0o432 => 2,
+        0o1036 => 4,
+        _ => return None,
+    };
+    let (names, bools, nums) = (short(1)?, short(2)?, short(3)?);
+    if nums <= TERMINFO_COLORS {
+        return None;
+    }
+    // Numbers start on an even offset after the header, names and booleans.
+    let mut at = 12 + names + bools;
+    at += at % 2;
+    let at = at + TERMINFO_COLORS * width;
+    let raw = bytes.get(at..at + width)?;
+    let value = if width == 2 {
+        i16::from_le_bytes([raw[0], raw[1]]) as i32
+    } else {
+        i32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]])
+    };
+    u32::try_from(value).ok()
+}
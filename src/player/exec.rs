@@ -0,0 +1,65 @@
+//! Frame enter commands (`bs play --allow-exec`).
+//!
+//! A deck's per-frame `on_enter` commands are handed to `sh -c` as the frame
+//! is shown — starting a demo service right at the demo slide, say. They run
+//! in the background with no stdin, their output dropped or appended to a
+//! log, and outlive the frame (and the player) that started them.
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+
+use anyhow::{Context, Result};
+
+/// Runs each frame's enter commands as it is shown.
+pub struct EnterHooks {
+    /// Commands by frame; frames past the end have none.
+    commands: Vec<Vec<String>>,
+    /// Where command output goes; `None` drops it.
+    log: Option<File>,
+    /// Commands started and not yet known to have exited, reaped as more
+    /// start.
+    running: Vec<Child>,
+}
+
+impl EnterHooks {
+    /// Get ready to run `commands`, appending their output to `log` if given.
+    pub fn new(commands: Vec<Vec<String>>, log: Option<&Path>) -> Result<Self> {
+        let log = log
+            .map(|path| {
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .with_context(|| format!("Failed to open {}", path.display()))
+            })
+            .transpose()?;
+        Ok(EnterHooks { commands, log, running: Vec::new() })
+    }
+
+    /// Start `frame`'s commands. One that fails to start is noted in the log
+    /// and the rest still run.
+    pub fn run(&mut self, frame: usize) {
+        self.running.retain_mut(|child| matches!(child.try_wait(), Ok(None)));
+        for command in self.commands.get(frame).into_iter().flatten() {
+            if let Some(log) = &mut self.log {
+                let _ = writeln!(log, "# frame {}: {command}", frame + 1);
+            }
+            let output = || match &self.log {
+                Some(log) => log.try_clone().map_or_else(|_| Stdio::null(), Stdio::from),
+                None => Stdio::null(),
+            };
+            let started =
+                Command::new("sh").arg("-c").arg(command).stdin(Stdio::null()).stdout(output()).stderr(output()).spawn();
+            match started {
+                Ok(child) => self.running.push(child),
+                Err(e) => {
+                    if let Some(log) = &mut self.log {
+                        let _ = writeln!(log, "# failed to start: {e}");
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,191 @@
+//! Live annotation (`d` in the player): strokes and boxes inked over the frame
+//! on screen, for circling things while taking questions.
+//!
+//! Ink is held apart from the frame grid and only painted over it, so the
+//! deck itself is never touched: it is wiped when the frame changes (or on
+//! `c`), and the grid hosts read back stays the frame as compiled.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use crate::types::{Cell, Color, NamedColor, Style, StyleRef, WIDE_CONTINUATION};
+
+/// What a stroke leaves in each cell it crosses.
+const STROKE: char = '█';
+
+/// Annotations over the current frame, and the pen that draws them.
+#[derive(Debug, Default)]
+pub(super) struct Ink {
+    /// Inked cells by `(y, x)` canvas position, so a row is one range.
+    cells: BTreeMap<(u16, u16), char>,
+    /// The pen's cell while draw mode is on; `None` when it is off.
+    pen: Option<(u16, u16)>,
+    /// The first corner of a box being marked out.
+    anchor: Option<(u16, u16)>,
+}
+
+/// A canvas rectangle as `(x, y, w, h)`.
+pub(super) type Area = (u16, u16, u16, u16);
+
+impl Ink {
+    pub fn drawing(&self) -> bool {
+        self.pen.is_some()
+    }
+
+    pub fn pen(&self) -> Option<(u16, u16)> {
+        self.pen
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Enter draw mode with the pen in the middle of a `width`×`height`
+    /// canvas, or leave it. Leaving keeps what was drawn.
+    pub fn toggle(&mut self, width: u16, height: u16) {
+        self.pen = match self.pen {
+            Some(_) => None,
+            None => Some((width / 2, height / 2)),
+        };
+        self.anchor = None;
+    }
+
+    /// Wipe every stroke and any half-marked box; the pen stays where it is.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.anchor = None;
+    }
+
+    /// Move the pen to `(x, y)`, clamped to a `width`×`height` canvas, inking
+    /// a straight line from where it was when `ink` is set. Returns the area
+    /// to repaint; nothing happens outside draw mode.
+    pub fn pen_to(&mut self, x: u16, y: u16, ink: bool, width: u16, height: u16) -> Option<Area> {
+        let from = self.pen?;
+        let to = (x.min(width.saturating_sub(1)), y.min(height.saturating_sub(1)));
+        if ink {
+            for (x, y) in line(from, to) {
+                self.cells.insert((y, x), STROKE);
+            }
+        }
+        self.pen = Some(to);
+        Some(spanning(from, to))
+    }
+
+    /// Mark a box corner at the pen: the first call anchors it, the second
+    /// inks the outline between the anchor and the pen. Returns the area to
+    /// repaint.
+    pub fn mark_box(&mut self) -> Option<Area> {
+        let pen = self.pen?;
+        let Some(anchor) = self.anchor.take() else {
+            self.anchor = Some(pen);
+            return Some(spanning(pen, pen));
+        };
+        let (x0, x1) = (anchor.0.min(pen.0), anchor.0.max(pen.0));
+        let (y0, y1) = (anchor.1.min(pen.1), anchor.1.max(pen.1));
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                if let Some(ch) = box_char(x, y, (x0, y0), (x1, y1)) {
+                    self.cells.insert((y, x), ch);
+                }
+            }
+        }
+        Some(spanning(anchor, pen))
+    }
+
+    /// Canvas row `y` as shown: `row` with any ink, and the pen and box
+    /// anchor highlighted, painted over it. A wide glyph half covered by ink
+    /// gives up its other half as a blank.
+    pub fn overlay<'a>(&self, y: u16, row: &'a [Cell]) -> Cow<'a, [Cell]> {
+        let marks: Vec<u16> = [self.pen, self.anchor].into_iter().flatten().filter(|m| m.1 == y).map(|m| m.0).collect();
+        let inked = self.cells.range((y, 0)..=(y, u16::MAX));
+        if marks.is_empty() && inked.clone().next().is_none() {
+            return Cow::Borrowed(row);
+        }
+        let mut shown = row.to_vec();
+        for (&(_, x), &ch) in inked {
+            let x = x as usize;
+            if x >= shown.len() {
+                continue;
+            }
+            if row[x].is_continuation() && x > 0 && !self.cells.contains_key(&(y, x as u16 - 1)) {
+                shown[x - 1].ch = ' ';
+            }
+            if row.get(x + 1).is_some_and(Cell::is_continuation) {
+                shown[x + 1].ch = ' ';
+            }
+            shown[x] = Cell { ch, style: ink_style() };
+        }
+        let width = shown.len();
+        for x in marks.into_iter().map(usize::from).filter(|&x| x < width) {
+            let ch = if shown[x].ch == WIDE_CONTINUATION { ' ' } else { shown[x].ch };
+            shown[x] = Cell { ch, style: pen_style() };
+        }
+        Cow::Owned(shown)
+    }
+}
+
+/// The cells of a straight line from `a` to `b`, both included.
+fn line(a: (u16, u16), b: (u16, u16)) -> Vec<(u16, u16)> {
+    let (mut x, mut y) = (i32::from(a.0), i32::from(a.1));
+    let (x1, y1) = (i32::from(b.0), i32::from(b.1));
+    let (dx, dy) = ((x1 - x).abs(), -(y1 - y).abs());
+    let (sx, sy) = ((x1 - x).signum(), (y1 - y).signum());
+    let mut err = dx + dy;
+    let mut cells = Vec::new();
+    loop {
+        cells.push((x as u16, y as u16));
+        if (x, y) == (x1, y1) {
+            return cells;
+        }
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+    }
+}
+
+/// The outline character of a box from `top_left` to `bottom_right` at
+/// `(x, y)`, or `None` inside it. A box one cell high or wide is a line.
+fn box_char(x: u16, y: u16, top_left: (u16, u16), bottom_right: (u16, u16)) -> Option<char> {
+    let (left, right) = (x == top_left.0, x == bottom_right.0);
+    let (top, bottom) = (y == top_left.1, y == bottom_right.1);
+    Some(match (left || right, top || bottom) {
+        _ if top_left.1 == bottom_right.1 => '─',
+        _ if top_left.0 == bottom_right.0 => '│',
+        (true, true) => match (top, left) {
+            (true, true) => '┌',
+            (true, false) => '┐',
+            (false, true) => '└',
+            (false, false) => '┘',
+        },
+        (false, true) => '─',
+        (true, false) => '│',
+        (false, false) => return None,
+    })
+}
+
+/// The area spanning two cells, widened by a column each side so the halves
+/// of a wide glyph the ink split are repainted too.
+fn spanning(a: (u16, u16), b: (u16, u16)) -> Area {
+    let x0 = a.0.min(b.0).saturating_sub(1);
+    let y0 = a.1.min(b.1);
+    (x0, y0, a.0.max(b.0) + 2 - x0, a.1.max(b.1) + 1 - y0)
+}
+
+fn ink_style() -> StyleRef {
+    Style { fg: Some(Color::Named(NamedColor::Yellow)), bold: true, ..Style::default() }.into()
+}
+
+fn pen_style() -> StyleRef {
+    Style {
+        fg: Some(Color::Named(NamedColor::Black)),
+        bg: Some(Color::Named(NamedColor::Yellow)),
+        ..Style::default()
+    }
+    .into()
+}
@@ -4,18 +4,33 @@
 //! The player does not interpret semantics or perform rendering decisions;
 //! it treats the presentation as an immutable, authoritative visual script.
 
+use std::borrow::Cow;
 use std::io::{self, Read, Write};
 use std::process::{Child, Command as ProcCommand, Stdio};
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use anyhow::{bail, Result};
 use crossterm::{cursor, event, execute, queue, style, terminal};
 
+mod color;
+mod exec;
+mod ink;
+mod tmux;
+
+pub use color::{monochrome_style, parse_terminfo_colors, ColorDepth};
+pub use exec::EnterHooks;
+pub use tmux::{notes_page, TmuxNotes};
+
+use ink::{Area, Ink};
+
 use crate::menubar::print_menu_item;
+use crate::renderer::ansi::osc8;
+use crate::stream::FrameStream;
 use crate::types::{
-    Cell, Color, CommandRegion, Frame, LoopRegion, NamedColor, PlayablePresentation, Style,
+    char_width, Cell, CellChange, ClockRegion, Color, CommandRegion, Frame, LoopRegion, NamedColor,
+    PlayablePresentation, Style, TerminalContract,
 };
 
 /// Rows reserved above the canvas for the menu bar (when not in fullscreen).
@@ -24,6 +39,65 @@ const CANVAS_OFFSET: u16 = 1;
 /// Frames moved per Shift+arrow jump during playback navigation.
 const FRAMES_PER_JUMP: usize = 10;
 
+/// Event-poll interval while a command runs, so its output streams in.
+const COMMAND_POLL: Duration = Duration::from_millis(30);
+
+/// Event-poll interval while idle.
+const IDLE_POLL: Duration = Duration::from_millis(200);
+
+/// One playback action — the player's key bindings, for hosts driving it with
+/// [`Player::step`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerInput {
+    /// `→` / Space / Enter: next frame; breaks out of a loop or skips an
+    /// auto-play animation.
+    Next,
+    /// `←`: previous frame; breaks out of a loop or skips an auto-play
+    /// animation backwards.
+    Prev,
+    /// Shift+`→`: ten frames forward (clamped).
+    JumpForward,
+    /// Shift+`←`: ten frames back (clamped).
+    JumpBack,
+    /// Home.
+    First,
+    /// End.
+    Last,
+    /// Go to a 0-based frame (clamped to the last).
+    GoTo(usize),
+    /// `f`: toggle the menu and status bars.
+    ToggleFullscreen,
+    /// `d`: start or stop drawing on the frame (what was drawn stays until
+    /// the frame changes).
+    ToggleDraw,
+    /// While drawing: move the pen to canvas cell `(x, y)` (clamped), inking a
+    /// line there when `ink` is set — arrow keys and mouse drags.
+    Pen { x: u16, y: u16, ink: bool },
+    /// While drawing, `b`: anchor a box corner at the pen, or ink the box from
+    /// the anchor to the pen.
+    MarkBox,
+    /// `c`: wipe what was drawn on this frame.
+    ClearInk,
+    /// `1`–`9`: show or hide the deck's overlay at this 0-based index on the
+    /// current frame (hidden again when the frame changes).
+    ToggleOverlay(usize),
+    /// `b` / `w`: black out or white out the screen. While it is blank, any
+    /// input but `Quit` and `Tick` only brings the frame back.
+    Blank(Blank),
+    /// `q`: stop playback.
+    Quit,
+    /// No input: service a running command and fire any elapsed loop or
+    /// auto-advance timer.
+    Tick,
+}
+
+/// What [`PlayerInput::Blank`] fills the screen with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Blank {
+    Black,
+    White,
+}
+
 /// A binary currently executing for the active frame. The child runs with piped
 /// stdio (it can never touch the real terminal) and is read on background
 /// threads, so the event loop stays responsive — arrow keys kill it and move on.
@@ -51,7 +125,10 @@ struct LoopPlay {
 }
 
 pub struct Player {
+    /// The deck; for a streamed deck, everything but its frames.
     presentation: PlayablePresentation,
+    /// Where a streamed deck's frames are read from, as they are shown.
+    stream: Option<FrameStream>,
     current_frame: usize,
     grid: Vec<Vec<Cell>>,
     fullscreen: bool,
@@ -61,29 +138,160 @@ pub struct Player {
     /// the current frame boundary (and no loop is driving). `None` = wait for a
     /// keypress. Loops, when active, drive advancement instead (see `loop_play`).
     auto_deadline: Option<Instant>,
+    /// Paint cell styles (`false` for `--no-color`: characters only).
+    color: bool,
+    /// What RGB colours are downgraded to; `None` until [`Self::play`]
+    /// detects it (or [`Self::with_color_depth`] sets it), meaning truecolor.
+    color_depth: Option<ColorDepth>,
+    /// Whether linked cells are wrapped in OSC 8 hyperlinks; `None` until
+    /// [`Self::play`] detects it (or [`Self::with_hyperlinks`] sets it),
+    /// meaning yes.
+    hyperlinks: Option<bool>,
+    /// Whether frame 0 has been started (see `start`).
+    started: bool,
+    /// Live annotations over the current frame, and the draw-mode pen.
+    ink: Ink,
+    /// Indices of the overlays shown on the current frame, in the order they
+    /// were turned on (later ones paint over earlier ones).
+    overlays_on: Vec<usize>,
+    /// What the screen is blanked with, if it is: nothing else is painted,
+    /// and the timers hold, until it comes back.
+    blank: Option<Blank>,
+    /// Callbacks run with the new frame index each time the shown frame
+    /// changes (see [`Self::on_frame_change`]).
+    frame_hooks: Vec<Box<dyn FnMut(usize)>>,
+    /// Seconds east of UTC the clocks show; `None` until the first clock is
+    /// painted (or [`Self::with_utc_offset`] sets it), meaning local time.
+    utc_offset: Option<i64>,
 }
 
 impl Player {
     pub fn new(presentation: PlayablePresentation) -> Self {
-        let w = presentation.contract.width as usize;
-        let h = presentation.contract.height as usize;
         Self {
+            grid: presentation.grid_at(0),
             presentation,
+            stream: None,
             current_frame: 0,
-            grid: vec![vec![Cell::default(); w]; h],
             fullscreen: false,
             running: None,
             loop_play: None,
             auto_deadline: None,
+            color: true,
+            color_depth: None,
+            hyperlinks: None,
+            started: false,
+            ink: Ink::default(),
+            overlays_on: Vec::new(),
+            blank: None,
+            frame_hooks: Vec::new(),
+            utc_offset: None,
+        }
+    }
+
+    /// Play a [stream file](crate::stream) straight off disk: only the frame
+    /// on screen is held in memory, and frames are decoded as they are shown.
+    pub fn streamed(stream: FrameStream) -> Result<Self> {
+        let grid = stream.grid_at(0)?;
+        let mut player = Player::new(stream.skeleton().clone());
+        player.grid = grid;
+        player.stream = Some(stream);
+        Ok(player)
+    }
+
+    /// Enable or disable colours and attributes (`bs --no-color play`).
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Emit colours at `depth` instead of detecting the terminal's on
+    /// [`Self::play`] — for hosts painting with [`Self::render_to`] into
+    /// something other than the controlling terminal.
+    pub fn with_color_depth(mut self, depth: ColorDepth) -> Self {
+        self.color_depth = Some(depth);
+        self
+    }
+
+    /// Emit OSC 8 hyperlinks for linked cells (or not) instead of detecting
+    /// the terminal's support on [`Self::play`].
+    pub fn with_hyperlinks(mut self, hyperlinks: bool) -> Self {
+        self.hyperlinks = Some(hyperlinks);
+        self
+    }
+
+    /// Show the deck's clocks `secs` east of UTC instead of in local time.
+    pub fn with_utc_offset(mut self, secs: i64) -> Self {
+        self.utc_offset = Some(secs);
+        self
+    }
+
+    /// Run `hook` with the 0-based frame index whenever a different frame is
+    /// shown — on start (frame 0), navigation, loop steps and auto-advance —
+    /// after it has been painted. Hooks run in registration order on the
+    /// player's thread; to hand frames to another thread, send them down a
+    /// channel from the hook.
+    pub fn on_frame_change(&mut self, hook: impl FnMut(usize) + 'static) {
+        self.frame_hooks.push(Box::new(hook));
+    }
+
+    fn notify_frame(&mut self) {
+        let frame = self.current_frame;
+        for hook in &mut self.frame_hooks {
+            hook(frame);
         }
     }
 
+    /// The crossterm style to paint `s` with, honouring [`Self::with_color`]
+    /// and the colour depth.
+    fn content_style(&self, s: &Style) -> style::ContentStyle {
+        if !self.color {
+            return style::ContentStyle::default();
+        }
+        match self.color_depth.unwrap_or(ColorDepth::TrueColor) {
+            ColorDepth::Monochrome => {
+                monochrome_style(s, self.presentation.contract.background.as_ref())
+            }
+            depth => to_content_style_at(s, depth),
+        }
+    }
+
+    /// Print one cell in its style; see [`Self::print_cells`].
+    fn print_cell(&self, stdout: &mut impl Write, cell: &Cell) -> Result<()> {
+        self.print_cells(stdout, std::iter::once(cell))
+    }
+
+    /// Print `cells` left to right from the cursor. Each run of cells that
+    /// paint alike goes out as one styled write, so its SGR is sent once
+    /// rather than per cell, inside an OSC 8 hyperlink when it is linked and
+    /// links are on (not with `--no-color`, which prints characters only).
+    fn print_cells<'a>(&self, stdout: &mut impl Write, cells: impl IntoIterator<Item = &'a Cell>) -> Result<()> {
+        let mut run = String::new();
+        let mut run_style: Option<(style::ContentStyle, Option<&str>)> = None;
+        for cell in cells {
+            let link = cell.style.link.as_deref().filter(|_| self.color && self.hyperlinks != Some(false));
+            let paint = (self.content_style(&cell.style), link);
+            if run_style.as_ref() != Some(&paint) {
+                if let Some((cs, link)) = run_style.replace(paint) {
+                    print_run(stdout, cs, link, &run)?;
+                    run.clear();
+                }
+            }
+            run.push(cell.ch);
+        }
+        if let Some((cs, link)) = run_style {
+            print_run(stdout, cs, link, &run)?;
+        }
+        Ok(())
+    }
+
     /// Play the presentation in the terminal.
     ///
     /// Sets up the terminal, enters the event loop, and restores the terminal
     /// on exit (even on error).
     pub fn play(&mut self) -> Result<()> {
         let (term_w, term_h) = terminal::size()?;
+        self.color_depth.get_or_insert_with(ColorDepth::detect);
+        self.hyperlinks.get_or_insert_with(|| hyperlinks_supported(std::env::var("TERM").ok().as_deref()));
         let need_w = self.presentation.contract.width;
         let need_h = self.presentation.contract.height;
         // +2: one row for menu bar, one row for status bar
@@ -109,23 +317,122 @@ impl Player {
         let result = self.run_loop(&mut stdout);
 
         // Always restore terminal state.
-        let _ = execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = execute!(stdout, event::DisableMouseCapture, cursor::Show, terminal::LeaveAlternateScreen);
         let _ = terminal::disable_raw_mode();
 
         result
     }
 
+    // -----------------------------------------------------------------------
+    // Host-driven playback
+    // -----------------------------------------------------------------------
+
+    /// Apply one input without touching the terminal — for hosts that run
+    /// their own event loop. The first call also starts frame 0 (its command,
+    /// loop or auto-advance timer). Returns `Ok(false)` once the input quits
+    /// playback (any running command is killed). Paint the result with
+    /// [`Self::render_to`]; send [`PlayerInput::Tick`] by [`Self::next_deadline`]
+    /// to keep commands, loops and auto-advance moving.
+    pub fn step(&mut self, input: PlayerInput) -> Result<bool> {
+        let mut sink = io::sink();
+        if !self.started {
+            self.start(&mut sink)?;
+        }
+        self.handle(input, &mut sink)
+    }
+
+    /// Paint the whole player screen — menu bar, canvas and status bar, or the
+    /// bare canvas in fullscreen, or the blank — to `out` as crossterm escape
+    /// sequences.
+    /// Cursor positions are absolute, as when playing in a terminal.
+    pub fn render_to(&self, out: &mut impl Write) -> Result<()> {
+        self.redraw_all(out)
+    }
+
+    /// When the host should next send [`PlayerInput::Tick`]: the loop or
+    /// auto-advance deadline, a short poll while a command runs, or when a
+    /// clock on the frame next changes. `None` when playback is waiting for
+    /// input. Timers hold while drawing or blanked.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        let command = self.running.as_ref().map(|_| Instant::now() + COMMAND_POLL);
+        let timers = [self.loop_play.as_ref().map(|lp| lp.deadline), self.auto_deadline];
+        [command, self.next_clock_tick()]
+            .into_iter()
+            .chain(timers.into_iter().filter(|_| !self.timers_held()))
+            .flatten()
+            .min()
+    }
+
+    /// The frame on screen (0-based).
+    pub fn current_frame(&self) -> usize {
+        self.current_frame
+    }
+
+    pub fn frame_count(&self) -> usize {
+        match &self.stream {
+            Some(stream) => stream.len(),
+            None => self.presentation.frames.len(),
+        }
+    }
+
+    /// The canvas size and colours the deck was compiled for.
+    pub fn contract(&self) -> &TerminalContract {
+        &self.presentation.contract
+    }
+
+    /// The deck's per-frame enter commands (see [`EnterHooks`]); empty when
+    /// no frame has any.
+    pub fn enter_commands(&self) -> &[Vec<String>] {
+        &self.presentation.on_enter
+    }
+
+    pub fn is_fullscreen(&self) -> bool {
+        self.fullscreen
+    }
+
+    /// The canvas as painted, including any command output overlay — for hosts
+    /// that draw it themselves. Annotations are not part of it; see
+    /// [`Self::annotated_grid`].
+    pub fn grid(&self) -> &[Vec<Cell>] {
+        &self.grid
+    }
+
+    /// Whether draw mode is on.
+    pub fn is_drawing(&self) -> bool {
+        self.ink.drawing()
+    }
+
+    /// What the screen is blanked with, if it is.
+    pub fn blanked(&self) -> Option<Blank> {
+        self.blank
+    }
+
+    /// [`Self::grid`] with the overlays turned on and the live annotations,
+    /// and the pen while drawing, painted over it — what the audience sees.
+    pub fn annotated_grid(&self) -> Vec<Vec<Cell>> {
+        (0..self.grid.len()).map(|y| self.shown_row(y).into_owned()).collect()
+    }
+
     // -----------------------------------------------------------------------
     // Event loop
     // -----------------------------------------------------------------------
 
-    fn run_loop(&mut self, stdout: &mut io::Stdout) -> Result<()> {
-        self.apply_frame(0)?;
+    /// Paint frame 0 and its clocks, start its command, and arm its loop /
+    /// auto-advance timer.
+    fn start(&mut self, stdout: &mut impl Write) -> Result<()> {
+        self.started = true;
         self.redraw_all(stdout)?;
+        self.paint_clocks(stdout)?;
         self.maybe_start_command(stdout)?;
         // Frame 0 may itself sit inside a loop, or under an auto-play animation.
         self.arm_loop(None);
         self.schedule_auto();
+        self.notify_frame();
+        Ok(())
+    }
+
+    fn run_loop(&mut self, stdout: &mut io::Stdout) -> Result<()> {
+        self.start(stdout)?;
 
         loop {
             // Drive any running command: drain output, repaint, finalize on exit.
@@ -136,133 +443,295 @@ impl Player {
             // Poll briefly while a command runs so output streams in; otherwise
             // wait longer (the loop is idle until the next keypress). While a
             // loop auto-plays, never wait past its next-frame deadline.
-            let mut poll = if self.running.is_some() {
-                Duration::from_millis(30)
-            } else {
-                Duration::from_millis(200)
-            };
-            if let Some(lp) = &self.loop_play {
-                poll = poll.min(lp.deadline.saturating_duration_since(Instant::now()));
-            }
-            if let Some(dl) = self.auto_deadline {
-                poll = poll.min(dl.saturating_duration_since(Instant::now()));
-            }
+            let poll = self
+                .next_deadline()
+                .map_or(IDLE_POLL, |dl| dl.saturating_duration_since(Instant::now()).min(IDLE_POLL));
 
             if !event::poll(poll)? {
-                // No key arrived — advance on whichever timer elapsed. A loop, if
-                // active, drives playback; otherwise an auto-play animation does.
-                let now = Instant::now();
-                if self.loop_play.as_ref().is_some_and(|lp| now >= lp.deadline) {
-                    self.loop_tick(stdout)?;
-                } else if self.auto_deadline.is_some_and(|dl| now >= dl) {
-                    self.auto_tick(stdout)?;
+                // No key arrived — advance on whichever timer elapsed.
+                let before = self.current_frame;
+                self.paint_clocks(stdout)?;
+                self.advance_timers(stdout)?;
+                if self.current_frame != before {
+                    self.notify_frame();
                 }
                 continue;
             }
 
-            match event::read()? {
+            let input = match event::read()? {
                 event::Event::Key(key) => {
                     use event::KeyCode::*;
-                    match key.code {
-                        // Quit also stops any running binary.
-                        Char('q') => {
-                            self.kill_running();
-                            break;
-                        }
-                        // Esc leaves fullscreen first; otherwise it quits.
-                        Esc => {
-                            if self.fullscreen {
-                                self.fullscreen = false;
-                                self.redraw_all(stdout)?;
-                            } else {
-                                self.kill_running();
+                    let shift = key.modifiers.contains(event::KeyModifiers::SHIFT);
+                    // Any key but `q` brings a blanked screen back.
+                    if let Some(blank) = self.blank.filter(|_| key.code != Char('q')) {
+                        self.handle(PlayerInput::Blank(blank), stdout)?;
+                        continue;
+                    }
+                    // While drawing, arrows move the pen — inking, or with
+                    // Shift just moving.
+                    if let Some((x, y)) = self.ink.pen() {
+                        let to = match key.code {
+                            Left => Some((x.saturating_sub(1), y)),
+                            Right => Some((x.saturating_add(1), y)),
+                            Up => Some((x, y.saturating_sub(1))),
+                            Down => Some((x, y.saturating_add(1))),
+                            _ => None,
+                        };
+                        if let Some((x, y)) = to {
+                            if !self.handle(PlayerInput::Pen { x, y, ink: !shift }, stdout)? {
                                 break;
                             }
+                            continue;
                         }
-                        // Shift+→ / Shift+← jump FRAMES_PER_JUMP frames at once
-                        // (clamped), tearing down any loop — a quick coarse scrub.
-                        Right if key.modifiers.contains(event::KeyModifiers::SHIFT) => {
-                            self.stop_loop();
-                            let last = self.presentation.frames.len().saturating_sub(1);
-                            let target = (self.current_frame + FRAMES_PER_JUMP).min(last);
-                            self.nav_to(target, stdout)?;
-                            self.arm_loop(None);
-                        }
-                        Left if key.modifiers.contains(event::KeyModifiers::SHIFT) => {
-                            self.stop_loop();
-                            let target = self.current_frame.saturating_sub(FRAMES_PER_JUMP);
-                            self.nav_to(target, stdout)?;
-                            self.arm_loop(None);
-                        }
-                        // Navigation always interrupts a running binary and
-                        // moves on — a slow command (or a loop) can never trap
-                        // the deck. Inside a loop, → breaks out to the first
-                        // frame after it and ← to the first frame before it.
-                        // On an auto-play animation (no loop), an arrow skips the
-                        // whole animation: → to the first frame past the last-
-                        // ending overlapping span, ← to the slide before the
-                        // earliest-starting one.
-                        Right | Char(' ') | Enter => {
-                            if let Some(lp) = self.loop_play.take() {
-                                let last = self.presentation.frames.len().saturating_sub(1);
-                                let target = lp.region.end_frame.min(last);
-                                self.nav_to(target, stdout)?;
-                                self.arm_loop(Some(span(&lp.region)));
-                            } else if let Some((_, hi)) = self.animation_cluster(self.current_frame) {
-                                let last = self.presentation.frames.len().saturating_sub(1);
-                                self.nav_to(hi.min(last), stdout)?;
-                                self.arm_loop(None);
-                            } else {
-                                self.nav_forward(stdout)?;
-                                self.arm_loop(None);
-                            }
-                        }
-                        Left => {
-                            if let Some(lp) = self.loop_play.take() {
-                                let target = lp.region.start_frame.saturating_sub(1);
-                                self.nav_to(target, stdout)?;
-                                self.arm_loop(Some(span(&lp.region)));
-                            } else if let Some((lo, _)) = self.animation_cluster(self.current_frame) {
-                                self.nav_to(lo.saturating_sub(1), stdout)?;
-                                self.arm_loop(None);
-                            } else {
-                                self.nav_back(stdout)?;
-                                self.arm_loop(None);
-                            }
-                        }
-                        Home => {
-                            self.stop_loop();
-                            self.nav_to(0, stdout)?;
-                            self.arm_loop(None);
-                        }
-                        End => {
-                            self.stop_loop();
-                            let last = self.presentation.frames.len().saturating_sub(1);
-                            self.nav_to(last, stdout)?;
-                            self.arm_loop(None);
+                    }
+                    match key.code {
+                        Char('q') => PlayerInput::Quit,
+                        // Esc leaves draw mode, then fullscreen; otherwise it quits.
+                        Esc if self.ink.drawing() => PlayerInput::ToggleDraw,
+                        Esc if self.fullscreen => PlayerInput::ToggleFullscreen,
+                        Esc => PlayerInput::Quit,
+                        Right if shift => PlayerInput::JumpForward,
+                        Left if shift => PlayerInput::JumpBack,
+                        Right | Char(' ') | Enter => PlayerInput::Next,
+                        Left => PlayerInput::Prev,
+                        Home => PlayerInput::First,
+                        End => PlayerInput::Last,
+                        Char('f') => PlayerInput::ToggleFullscreen,
+                        Char('d') => PlayerInput::ToggleDraw,
+                        Char('b') if self.ink.drawing() => PlayerInput::MarkBox,
+                        Char('b') => PlayerInput::Blank(Blank::Black),
+                        Char('w') => PlayerInput::Blank(Blank::White),
+                        Char('c') => PlayerInput::ClearInk,
+                        Char(n @ '1'..='9') => PlayerInput::ToggleOverlay(n as usize - '1' as usize),
+                        _ => continue,
+                    }
+                }
+                // The mouse is only captured while drawing: the left button
+                // inks strokes, the right one drags out a box.
+                event::Event::Mouse(mouse) => {
+                    use event::{MouseButton, MouseEventKind::*};
+                    let Some(y) = mouse.row.checked_sub(self.canvas_offset()) else {
+                        continue;
+                    };
+                    let (x, moved) = (mouse.column, PlayerInput::Pen { x: mouse.column, y, ink: false });
+                    match mouse.kind {
+                        Down(MouseButton::Left) => {
+                            self.handle(moved, stdout)?;
+                            PlayerInput::Pen { x, y, ink: true }
                         }
-                        // Toggle "no bars" fullscreen: hide the menu/status bars
-                        // and give the canvas the whole screen.
-                        Char('f') => {
-                            self.fullscreen = !self.fullscreen;
-                            self.redraw_all(stdout)?;
+                        Drag(MouseButton::Left) => PlayerInput::Pen { x, y, ink: true },
+                        Down(MouseButton::Right) | Up(MouseButton::Right) => {
+                            self.handle(moved, stdout)?;
+                            PlayerInput::MarkBox
                         }
-                        _ => {}
+                        Drag(MouseButton::Right) => moved,
+                        _ => continue,
                     }
-                    // After any navigation, re-arm the auto-play timer for the
-                    // frame we landed on (disarmed while a loop drives playback).
-                    self.schedule_auto();
                 }
                 event::Event::Resize(_, _) => {
                     self.redraw_all(stdout)?;
+                    continue;
+                }
+                _ => continue,
+            };
+            if !self.handle(input, stdout)? {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply one input, painting incrementally to `stdout` and notifying the
+    /// frame hooks if it moved. `Ok(false)` once the input quits playback.
+    fn handle(&mut self, input: PlayerInput, stdout: &mut impl Write) -> Result<bool> {
+        let before = self.current_frame;
+        let running = self.apply_input(input, stdout)?;
+        if self.current_frame != before {
+            self.notify_frame();
+        }
+        Ok(running)
+    }
+
+    fn apply_input(&mut self, input: PlayerInput, stdout: &mut impl Write) -> Result<bool> {
+        let last = self.frame_count().saturating_sub(1);
+        // A blanked screen comes back on the next input, which does nothing
+        // else.
+        if let Some(blank) = self.blank {
+            if !matches!(input, PlayerInput::Quit | PlayerInput::Tick | PlayerInput::Blank(_)) {
+                return self.apply_input(PlayerInput::Blank(blank), stdout);
+            }
+        }
+        match input {
+            // Quit also stops any running binary.
+            PlayerInput::Quit => {
+                self.kill_running();
+                return Ok(false);
+            }
+            PlayerInput::Tick => {
+                if self.running.is_some() {
+                    self.service_command(stdout)?;
+                }
+                self.paint_clocks(stdout)?;
+                self.advance_timers(stdout)?;
+                return Ok(true);
+            }
+            // Toggle "no bars" fullscreen: hide the menu/status bars and give
+            // the canvas the whole screen.
+            PlayerInput::ToggleFullscreen => {
+                self.fullscreen = !self.fullscreen;
+                self.redraw_all(stdout)?;
+                return Ok(true);
+            }
+            // The timers hold while drawing and start afresh once it stops.
+            PlayerInput::ToggleDraw => {
+                let contract = &self.presentation.contract;
+                self.ink.toggle(contract.width, contract.height);
+                if self.ink.drawing() {
+                    queue!(stdout, event::EnableMouseCapture)?;
+                } else {
+                    queue!(stdout, event::DisableMouseCapture)?;
+                    self.restart_timers();
+                }
+                self.render_full(stdout)?;
+                self.render_status(stdout)?;
+                return Ok(true);
+            }
+            PlayerInput::Blank(blank) => {
+                if self.blank.take().is_none() {
+                    self.blank = Some(blank);
+                } else {
+                    self.restart_timers();
+                }
+                self.redraw_all(stdout)?;
+                return Ok(true);
+            }
+            PlayerInput::Pen { x, y, ink } => {
+                let contract = &self.presentation.contract;
+                if let Some(area) = self.ink.pen_to(x, y, ink, contract.width, contract.height) {
+                    self.render_area(stdout, area)?;
+                }
+                return Ok(true);
+            }
+            PlayerInput::MarkBox => {
+                if let Some(area) = self.ink.mark_box() {
+                    self.render_area(stdout, area)?;
+                }
+                return Ok(true);
+            }
+            PlayerInput::ClearInk => {
+                self.ink.clear();
+                self.render_full(stdout)?;
+                return Ok(true);
+            }
+            // Only an overlay with something on this frame toggles.
+            PlayerInput::ToggleOverlay(n) => {
+                let here = self.presentation.overlays.get(n).and_then(|o| o.changes_at(self.current_frame));
+                if here.is_some() {
+                    match self.overlays_on.iter().position(|&on| on == n) {
+                        Some(i) => {
+                            self.overlays_on.remove(i);
+                        }
+                        None => self.overlays_on.push(n),
+                    }
+                    self.render_full(stdout)?;
+                    self.render_status(stdout)?;
+                }
+                return Ok(true);
+            }
+            // Jumps tear down any loop — a quick coarse scrub.
+            PlayerInput::JumpForward => {
+                self.stop_loop();
+                self.nav_to((self.current_frame + FRAMES_PER_JUMP).min(last), stdout)?;
+                self.arm_loop(None);
+            }
+            PlayerInput::JumpBack => {
+                self.stop_loop();
+                self.nav_to(self.current_frame.saturating_sub(FRAMES_PER_JUMP), stdout)?;
+                self.arm_loop(None);
+            }
+            PlayerInput::First => {
+                self.stop_loop();
+                self.nav_to(0, stdout)?;
+                self.arm_loop(None);
+            }
+            PlayerInput::Last => {
+                self.stop_loop();
+                self.nav_to(last, stdout)?;
+                self.arm_loop(None);
+            }
+            PlayerInput::GoTo(frame) => {
+                self.stop_loop();
+                self.nav_to(frame.min(last), stdout)?;
+                self.arm_loop(None);
+            }
+            // Navigation always interrupts a running binary and moves on — a
+            // slow command (or a loop) can never trap the deck. Inside a loop,
+            // Next breaks out to the first frame after it and Prev to the first
+            // frame before it. On an auto-play animation (no loop), a step skips
+            // the whole animation: Next to the first frame past the last-ending
+            // overlapping span, Prev to the slide before the earliest-starting
+            // one.
+            PlayerInput::Next => {
+                if let Some(lp) = self.loop_play.take() {
+                    self.nav_to(lp.region.end_frame.min(last), stdout)?;
+                    self.arm_loop(Some(span(&lp.region)));
+                } else if let Some((_, hi)) = self.animation_cluster(self.current_frame) {
+                    self.nav_to(hi.min(last), stdout)?;
+                    self.arm_loop(None);
+                } else {
+                    self.nav_forward(stdout)?;
+                    self.arm_loop(None);
+                }
+            }
+            PlayerInput::Prev => {
+                if let Some(lp) = self.loop_play.take() {
+                    self.nav_to(lp.region.start_frame.saturating_sub(1), stdout)?;
+                    self.arm_loop(Some(span(&lp.region)));
+                } else if let Some((lo, _)) = self.animation_cluster(self.current_frame) {
+                    self.nav_to(lo.saturating_sub(1), stdout)?;
+                    self.arm_loop(None);
+                } else {
+                    self.nav_back(stdout)?;
+                    self.arm_loop(None);
                 }
-                _ => {}
             }
         }
+        // After any navigation, re-arm the auto-play timer for the frame we
+        // landed on (disarmed while a loop drives playback).
+        self.schedule_auto();
+        Ok(true)
+    }
 
+    /// Advance on whichever timer elapsed. A loop, if active, drives playback;
+    /// otherwise an auto-play animation or auto-advance marker does.
+    fn advance_timers(&mut self, stdout: &mut impl Write) -> Result<()> {
+        if self.timers_held() {
+            return Ok(());
+        }
+        let now = Instant::now();
+        if self.loop_play.as_ref().is_some_and(|lp| now >= lp.deadline) {
+            self.loop_tick(stdout)?;
+        } else if self.auto_deadline.is_some_and(|dl| now >= dl) {
+            self.auto_tick(stdout)?;
+        }
         Ok(())
     }
 
+    /// Drawing and a blank screen hold the loop and auto-advance timers (a
+    /// frame change would wipe the ink, or go unseen).
+    fn timers_held(&self) -> bool {
+        self.ink.drawing() || self.blank.is_some()
+    }
+
+    /// Start the loop and auto-advance timers afresh on the current frame,
+    /// once they are no longer held.
+    fn restart_timers(&mut self) {
+        self.stop_loop();
+        self.arm_loop(None);
+        self.schedule_auto();
+    }
+
     // -----------------------------------------------------------------------
     // Loop playback
     // -----------------------------------------------------------------------
@@ -382,7 +851,7 @@ impl Player {
     /// advance to (the last frame never auto-advances). `None` when no region
     /// covers the frame.
     fn frame_auto_advance_delay(&self, frame: usize) -> Option<u64> {
-        if frame + 1 >= self.presentation.frames.len() {
+        if frame + 1 >= self.frame_count() {
             return None;
         }
         self.presentation
@@ -423,8 +892,8 @@ impl Player {
     /// Advance one frame because an auto-play animation's delay elapsed (no loop
     /// active). Re-arms the loop (in case we stepped into one) and the animation
     /// timer for the new frame.
-    fn auto_tick(&mut self, stdout: &mut io::Stdout) -> Result<()> {
-        let last = self.presentation.frames.len().saturating_sub(1);
+    fn auto_tick(&mut self, stdout: &mut impl Write) -> Result<()> {
+        let last = self.frame_count().saturating_sub(1);
         if self.current_frame < last {
             self.nav_forward(stdout)?;
             self.arm_loop(None);
@@ -434,7 +903,7 @@ impl Player {
     }
 
     /// Advance the active loop by one frame (called when its delay elapses).
-    fn loop_tick(&mut self, stdout: &mut io::Stdout) -> Result<()> {
+    fn loop_tick(&mut self, stdout: &mut impl Write) -> Result<()> {
         let Some(lp) = self.loop_play.as_ref() else {
             return Ok(());
         };
@@ -452,7 +921,7 @@ impl Player {
         // deck just past the loop (an infinite loop, count 0, never gets here).
         if region.count != 0 && iterations >= region.count {
             self.stop_loop();
-            let last = self.presentation.frames.len().saturating_sub(1);
+            let last = self.frame_count().saturating_sub(1);
             let target = region.end_frame.min(last);
             self.nav_to(target, stdout)?;
             self.arm_loop(Some(span(&region)));
@@ -463,10 +932,12 @@ impl Player {
         // (bounce / restart), so rebuild the grid from scratch rather than
         // diffing — same path as `nav_back`.
         self.kill_running();
+        self.clear_marks();
         self.current_frame = next;
         self.rebuild_grid(next)?;
         self.render_full(stdout)?;
         self.render_status(stdout)?;
+        self.paint_clocks(stdout)?;
         self.maybe_start_command(stdout)?;
         // The next wait crosses `next`→ in `next_forward`; let an auto-play
         // animation covering that boundary set the pace, else the loop's delay.
@@ -485,15 +956,22 @@ impl Player {
     // Navigation
     // -----------------------------------------------------------------------
 
-    fn nav_forward(&mut self, stdout: &mut io::Stdout) -> Result<()> {
-        let last = self.presentation.frames.len().saturating_sub(1);
+    fn nav_forward(&mut self, stdout: &mut impl Write) -> Result<()> {
+        let last = self.frame_count().saturating_sub(1);
         if self.current_frame >= last {
             return Ok(());
         }
-        // If the current frame had a command (live or finished), its output is
-        // overlaid on the grid — rebuild from scratch rather than diffing.
-        let overlaid = self.running.is_some() || self.region_for(self.current_frame).is_some();
+        // If the current frame had a command (live or finished) or a clock,
+        // its output is overlaid on the grid, and ink or the pen over it —
+        // rebuild from scratch rather than diffing.
+        let overlaid = self.running.is_some()
+            || self.region_for(self.current_frame).is_some()
+            || self.clocks_on(self.current_frame).next().is_some()
+            || !self.ink.is_empty()
+            || self.ink.drawing()
+            || !self.overlays_on.is_empty();
         self.kill_running();
+        self.clear_marks();
         self.current_frame += 1;
         if overlaid {
             self.rebuild_grid(self.current_frame)?;
@@ -503,45 +981,64 @@ impl Player {
             self.render_diff(stdout, self.current_frame)?;
         }
         self.render_status(stdout)?;
+        self.paint_clocks(stdout)?;
         self.maybe_start_command(stdout)
     }
 
-    fn nav_back(&mut self, stdout: &mut io::Stdout) -> Result<()> {
+    fn nav_back(&mut self, stdout: &mut impl Write) -> Result<()> {
         if self.current_frame == 0 {
             return Ok(());
         }
         self.kill_running();
+        self.clear_marks();
         self.current_frame -= 1;
         self.rebuild_grid(self.current_frame)?;
         self.render_full(stdout)?;
         self.render_status(stdout)?;
+        self.paint_clocks(stdout)?;
         self.maybe_start_command(stdout)
     }
 
-    fn nav_to(&mut self, target: usize, stdout: &mut io::Stdout) -> Result<()> {
+    fn nav_to(&mut self, target: usize, stdout: &mut impl Write) -> Result<()> {
         self.kill_running();
+        self.clear_marks();
         self.current_frame = target;
         self.rebuild_grid(target)?;
         self.render_full(stdout)?;
         self.render_status(stdout)?;
+        self.paint_clocks(stdout)?;
         self.maybe_start_command(stdout)
     }
 
+    /// Wipe the ink and hide the overlays, as leaving a frame does.
+    fn clear_marks(&mut self) {
+        self.ink.clear();
+        self.overlays_on.clear();
+    }
+
     // -----------------------------------------------------------------------
     // Grid management
     // -----------------------------------------------------------------------
 
+    /// Frame `index`, borrowed from memory or read from the stream.
+    fn frame(&self, index: usize) -> Result<Cow<'_, Frame>> {
+        match &self.stream {
+            Some(stream) => Ok(Cow::Owned(stream.frame(index)?)),
+            None => Ok(Cow::Borrowed(&self.presentation.frames[index])),
+        }
+    }
+
     fn apply_frame(&mut self, index: usize) -> Result<()> {
-        match &self.presentation.frames[index] {
+        match self.frame(index)?.into_owned() {
             Frame::Full { cells } => {
-                self.grid = cells.clone();
+                self.grid = cells;
             }
             Frame::Diff { changes } => {
                 for change in changes {
                     let x = change.x as usize;
                     let y = change.y as usize;
                     if y < self.grid.len() && x < self.grid[0].len() {
-                        self.grid[y][x] = change.cell.clone();
+                        self.grid[y][x] = change.cell;
                     }
                 }
             }
@@ -549,10 +1046,30 @@ impl Player {
         Ok(())
     }
 
+    /// Canvas row `y` as shown: the grid with the overlays turned on, then
+    /// the ink, painted over it.
+    fn shown_row(&self, y: usize) -> Cow<'_, [Cell]> {
+        let mut row = Cow::Borrowed(&self.grid[y][..]);
+        let width = row.len();
+        for &n in &self.overlays_on {
+            let changes = self.presentation.overlays[n].changes_at(self.current_frame).unwrap_or_default();
+            for c in changes.iter().filter(|c| usize::from(c.y) == y && usize::from(c.x) < width) {
+                row.to_mut()[usize::from(c.x)] = c.cell.clone();
+            }
+        }
+        match self.ink.overlay(y as u16, &row) {
+            Cow::Borrowed(_) => row,
+            Cow::Owned(inked) => Cow::Owned(inked),
+        }
+    }
+
     fn rebuild_grid(&mut self, target: usize) -> Result<()> {
-        // Full replay shares one implementation with the editor preview and the
-        // test harness (see `PlayablePresentation::grid_at`).
-        self.grid = self.presentation.grid_at(target);
+        // Replay from the nearest keyframe shares one implementation with the
+        // editor preview and the test harness (see `PlayablePresentation::grid_at`).
+        self.grid = match &self.stream {
+            Some(stream) => stream.grid_at(target)?,
+            None => self.presentation.grid_at(target),
+        };
         Ok(())
     }
 
@@ -573,7 +1090,10 @@ impl Player {
     /// Clear and repaint everything for the current fullscreen state: the menu
     /// and status bars are drawn only when not in fullscreen (`render_status`
     /// self-guards on `fullscreen`; `render_menubar` is gated here).
-    fn redraw_all(&self, stdout: &mut io::Stdout) -> Result<()> {
+    fn redraw_all(&self, stdout: &mut impl Write) -> Result<()> {
+        if let Some(blank) = self.blank {
+            return self.render_blank(stdout, blank);
+        }
         queue!(stdout, terminal::Clear(terminal::ClearType::All))?;
         if !self.fullscreen {
             self.render_menubar(stdout)?;
@@ -583,8 +1103,34 @@ impl Player {
         Ok(())
     }
 
-    fn render_menubar(&self, stdout: &mut io::Stdout) -> Result<()> {
-        let items: &[&str] = &[
+    /// Fill the whole screen with `blank` — with `--no-color`, black is a
+    /// plain clear and white reverse video.
+    fn render_blank(&self, stdout: &mut impl Write, blank: Blank) -> Result<()> {
+        let contract = &self.presentation.contract;
+        let (w, h) = terminal::size().unwrap_or((contract.width, contract.height + 2));
+        let mut cs = style::ContentStyle::default();
+        match (blank, self.color) {
+            (Blank::Black, true) => cs.background_color = Some(style::Color::Black),
+            (Blank::White, true) => cs.background_color = Some(style::Color::White),
+            (Blank::Black, false) => {}
+            (Blank::White, false) => cs.attributes.set(style::Attribute::Reverse),
+        }
+        let row = " ".repeat(usize::from(w));
+        queue!(stdout, terminal::Clear(terminal::ClearType::All))?;
+        for y in 0..h {
+            queue!(stdout, cursor::MoveTo(0, y), style::PrintStyledContent(style::StyledContent::new(cs, &row)))?;
+        }
+        stdout.flush()?;
+        Ok(())
+    }
+
+    fn render_menubar(&self, stdout: &mut impl Write) -> Result<()> {
+        let overlays = match self.presentation.overlays.len() {
+            0 => None,
+            1 => Some("[1] overlay"),
+            _ => Some("[1-9] overlays"),
+        };
+        let items = [
             "[←] prev",
             "[→][Space] next",
             "[⇧←][⇧→] ±10",
@@ -592,7 +1138,11 @@ impl Player {
             "[End] last",
             "[q][Esc] quit",
             "[f]ull",
-        ];
+            "[d]raw",
+            "[b][w] blank",
+        ]
+        .into_iter()
+        .chain(overlays);
 
         queue!(
             stdout,
@@ -600,7 +1150,7 @@ impl Player {
             terminal::Clear(terminal::ClearType::CurrentLine),
             style::Print(" "),
         )?;
-        for (i, item) in items.iter().enumerate() {
+        for (i, item) in items.enumerate() {
             if i > 0 {
                 queue!(stdout, style::Print("  "))?;
             }
@@ -610,33 +1160,43 @@ impl Player {
         Ok(())
     }
 
-    fn render_full(&self, stdout: &mut io::Stdout) -> Result<()> {
+    fn render_full(&self, stdout: &mut impl Write) -> Result<()> {
+        if self.blank.is_some() {
+            return Ok(());
+        }
         let offset = self.canvas_offset();
-        for (y, row) in self.grid.iter().enumerate() {
+        for y in 0..self.grid.len() {
             queue!(stdout, cursor::MoveTo(0, y as u16 + offset))?;
-            for cell in row {
-                let cs = to_content_style(&cell.style);
-                queue!(
-                    stdout,
-                    style::PrintStyledContent(style::StyledContent::new(cs, cell.ch))
-                )?;
-            }
+            // The wide glyph before a continuation cell already advanced the
+            // cursor over it.
+            let row = self.shown_row(y);
+            self.print_cells(stdout, row.iter().filter(|c| !c.is_continuation()))?;
         }
         stdout.flush()?;
         Ok(())
     }
 
-    fn render_diff(&self, stdout: &mut io::Stdout, frame_index: usize) -> Result<()> {
-        match &self.presentation.frames[frame_index] {
+    fn render_diff(&self, stdout: &mut impl Write, frame_index: usize) -> Result<()> {
+        if self.blank.is_some() {
+            return Ok(());
+        }
+        match &*self.frame(frame_index)? {
             Frame::Diff { changes } => {
-                for change in changes {
-                    let cs = to_content_style(&change.cell.style);
-                    queue!(
-                        stdout,
-                        cursor::MoveTo(change.x, change.y + self.canvas_offset()),
-                        style::PrintStyledContent(style::StyledContent::new(cs, change.cell.ch)),
-                    )?;
+                let mut changes: Vec<&CellChange> = changes.iter().filter(|c| !c.cell.is_continuation()).collect();
+                changes.sort_by_key(|c| (c.y, c.x));
+                // Each span of changes the cursor runs straight through (a
+                // wide glyph advancing it two) is printed with one move.
+                let mut span: Vec<&Cell> = Vec::new();
+                let mut next = None;
+                for c in changes {
+                    if next != Some((c.x, c.y)) {
+                        self.print_cells(stdout, span.drain(..))?;
+                        queue!(stdout, cursor::MoveTo(c.x, c.y + self.canvas_offset()))?;
+                    }
+                    span.push(&c.cell);
+                    next = Some((c.x + char_width(c.cell.ch) as u16, c.y));
                 }
+                self.print_cells(stdout, span)?;
                 stdout.flush()?;
             }
             Frame::Full { .. } => {
@@ -647,37 +1207,95 @@ impl Player {
         Ok(())
     }
 
-    fn render_status(&self, stdout: &mut io::Stdout) -> Result<()> {
+    fn render_status(&self, stdout: &mut impl Write) -> Result<()> {
         // Fullscreen ("no bars") owns the whole screen — no footer at all. Guard
         // here so every caller (navigation, loop steps, full repaint) honours it.
-        if self.fullscreen {
+        if self.fullscreen || self.blank.is_some() {
             return Ok(());
         }
         let status_y = self.presentation.contract.height + self.canvas_offset();
-        let (_, term_h) = terminal::size()?;
-        if status_y >= term_h {
-            return Ok(()); // No room for status bar.
+        // No room for the status bar (an off-terminal writer always has room).
+        if terminal::size().is_ok_and(|(_, term_h)| status_y >= term_h) {
+            return Ok(());
         }
 
-        let total = self.presentation.frames.len();
-        let status = format!(
-            " Frame {}/{} | \u{2190}\u{2192}: navigate | q: quit ",
-            self.current_frame + 1,
-            total,
-        );
+        let total = self.frame_count();
+        let title = match &self.presentation.metadata.title {
+            Some(title) => format!(" {title} |"),
+            None => String::new(),
+        };
+        let hints = if self.ink.drawing() {
+            "DRAW \u{2190}\u{2191}\u{2192}\u{2193}: ink | \u{21e7}+arrows: move | b: box | c: clear | d: done"
+        } else {
+            "\u{2190}\u{2192}: navigate | q: quit"
+        };
+        let shown: Vec<&str> = self.overlays_on.iter().map(|&n| self.presentation.overlays[n].name.as_str()).collect();
+        let shown = if shown.is_empty() { String::new() } else { format!(" + {}", shown.join(", ")) };
+        let status = format!("{title} Frame {}/{}{shown} | {hints} ", self.current_frame + 1, total);
+        // A long title is cut at the screen edge rather than wrapping.
+        let width = terminal::size().map_or(usize::MAX, |(w, _)| usize::from(w));
+        let status: String = status.chars().take(width).collect();
 
         let mut cs = style::ContentStyle::default();
         cs.attributes.set(style::Attribute::Dim);
 
+        // Clear the rest of the row: the previous status may have been longer.
         queue!(
             stdout,
             cursor::MoveTo(0, status_y),
             style::PrintStyledContent(style::StyledContent::new(cs, status)),
+            terminal::Clear(terminal::ClearType::UntilNewLine),
         )?;
         stdout.flush()?;
         Ok(())
     }
 
+    // -----------------------------------------------------------------------
+    // Clocks
+    // -----------------------------------------------------------------------
+
+    /// The clocks shown on `frame`.
+    fn clocks_on(&self, frame: usize) -> impl Iterator<Item = &ClockRegion> {
+        self.presentation.clocks.iter().filter(move |c| frame >= c.start_frame && frame < c.end_frame)
+    }
+
+    /// When a clock on the current frame next changes: the next second, or
+    /// minute when none shows seconds.
+    fn next_clock_tick(&self) -> Option<Instant> {
+        let period: u64 = self.clocks_on(self.current_frame).map(|c| if c.seconds { 1 } else { 60 }).min()?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let past = Duration::from_secs(now.as_secs() % period) + Duration::from_nanos(now.subsec_nanos().into());
+        Some(Instant::now() + Duration::from_secs(period).saturating_sub(past))
+    }
+
+    /// Paint the current time into the current frame's clocks where it isn't
+    /// shown already.
+    fn paint_clocks(&mut self, stdout: &mut impl Write) -> Result<()> {
+        let clocks: Vec<ClockRegion> = self.clocks_on(self.current_frame).cloned().collect();
+        if clocks.is_empty() {
+            return Ok(());
+        }
+        let offset = *self.utc_offset.get_or_insert_with(local_utc_offset);
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        for clock in clocks {
+            let Some(row) = self.grid.get_mut(usize::from(clock.y)) else {
+                continue;
+            };
+            let text = clock.text(now, offset);
+            let cells = row.iter_mut().skip(usize::from(clock.x));
+            let mut changed = false;
+            for (cell, ch) in cells.zip(text.chars()) {
+                let painted = Cell { ch, style: (&clock.style).into() };
+                changed |= *cell != painted;
+                *cell = painted;
+            }
+            if changed {
+                self.render_region(stdout, clock.x, clock.y, text.len() as u16, 1)?;
+            }
+        }
+        Ok(())
+    }
+
     // -----------------------------------------------------------------------
     // Command execution
     // -----------------------------------------------------------------------
@@ -692,7 +1310,7 @@ impl Player {
     }
 
     /// Start the command for the current frame, if there is one.
-    fn maybe_start_command(&mut self, stdout: &mut io::Stdout) -> Result<()> {
+    fn maybe_start_command(&mut self, stdout: &mut impl Write) -> Result<()> {
         let Some(region) = self.region_for(self.current_frame) else {
             return Ok(());
         };
@@ -701,7 +1319,7 @@ impl Player {
 
     /// Spawn the binary with piped stdio and background readers. A spawn failure
     /// is rendered as an error in the box rather than crashing the player.
-    fn start_command(&mut self, region: CommandRegion, stdout: &mut io::Stdout) -> Result<()> {
+    fn start_command(&mut self, region: CommandRegion, stdout: &mut impl Write) -> Result<()> {
         let timeout = region.timeout_secs.map(Duration::from_secs);
 
         let mut cmd = ProcCommand::new(&region.command);
@@ -749,7 +1367,7 @@ impl Player {
 
     /// Service the running command: drain output, repaint, and on exit (or
     /// timeout) finalize with a ✓ / ✗ indicator. Called once per loop tick.
-    fn service_command(&mut self, stdout: &mut io::Stdout) -> Result<()> {
+    fn service_command(&mut self, stdout: &mut impl Write) -> Result<()> {
         enum Outcome {
             Running,
             Done(bool, Vec<u8>),
@@ -811,7 +1429,7 @@ impl Player {
     /// (drawing a ✓ / ✗ on the top edge).
     fn paint_command(
         &mut self,
-        stdout: &mut io::Stdout,
+        stdout: &mut impl Write,
         region: &CommandRegion,
         status: Option<bool>,
         out: &[u8],
@@ -835,7 +1453,7 @@ impl Player {
                 }
                 self.grid[gy][gx] = Cell {
                     ch,
-                    style: region.style.clone(),
+                    style: (&region.style).into(),
                 };
             }
         }
@@ -855,7 +1473,8 @@ impl Player {
                         fg: Some(Color::Named(color)),
                         bold: true,
                         ..Style::default()
-                    },
+                    }
+                    .into(),
                 };
             }
         }
@@ -867,27 +1486,38 @@ impl Player {
         Ok(())
     }
 
-    /// Render a rectangular slice of the grid to the terminal.
-    fn render_region(&self, stdout: &mut io::Stdout, x: u16, y: u16, w: u16, h: u16) -> Result<()> {
+    /// Render an [`Area`] of the canvas, as [`Self::render_region`].
+    fn render_area(&self, stdout: &mut impl Write, (x, y, w, h): Area) -> Result<()> {
+        self.render_region(stdout, x, y, w, h)
+    }
+
+    /// Render a rectangular slice of the grid, with any overlays and ink over
+    /// it, to the terminal.
+    fn render_region(&self, stdout: &mut impl Write, x: u16, y: u16, w: u16, h: u16) -> Result<()> {
+        if self.blank.is_some() {
+            return Ok(());
+        }
         let gh = self.grid.len();
         for row in 0..h {
             let gy = (y + row) as usize;
             if gy >= gh {
                 break;
             }
-            let gw = self.grid[gy].len();
+            let shown = self.shown_row(gy);
+            let gw = shown.len();
             queue!(stdout, cursor::MoveTo(x, y + row + self.canvas_offset()))?;
             for col in 0..w {
                 let gx = (x + col) as usize;
                 if gx >= gw {
                     break;
                 }
-                let cell = &self.grid[gy][gx];
-                let cs = to_content_style(&cell.style);
-                queue!(
-                    stdout,
-                    style::PrintStyledContent(style::StyledContent::new(cs, cell.ch))
-                )?;
+                let cell = &shown[gx];
+                if cell.is_continuation() {
+                    // Nothing to print; re-sync in case the region began mid-glyph.
+                    queue!(stdout, cursor::MoveTo(x + col + 1, y + row + self.canvas_offset()))?;
+                    continue;
+                }
+                self.print_cell(stdout, cell)?;
             }
         }
         stdout.flush()?;
@@ -895,6 +1525,18 @@ impl Player {
     }
 }
 
+/// Print `text` in `cs`, inside an OSC 8 hyperlink to `link` if there is one.
+fn print_run(stdout: &mut impl Write, cs: style::ContentStyle, link: Option<&str>, text: &str) -> Result<()> {
+    if let Some(url) = link {
+        queue!(stdout, style::Print(osc8(Some(url))))?;
+    }
+    queue!(stdout, style::PrintStyledContent(style::StyledContent::new(cs, text)))?;
+    if link.is_some() {
+        queue!(stdout, style::Print(osc8(None)))?;
+    }
+    Ok(())
+}
+
 /// The `(start, end)` span of a loop region, used as an identity key when
 /// suppressing an immediate re-arm after breaking out.
 fn span(r: &LoopRegion) -> (usize, usize) {
@@ -946,6 +1588,27 @@ fn loop_next(
     (next, next_forward, next == start)
 }
 
+/// Seconds east of UTC the local clock runs, as `date +%z` reports it; UTC
+/// where that can't be run.
+fn local_utc_offset() -> i64 {
+    let out = ProcCommand::new("date").arg("+%z").stdin(Stdio::null()).stderr(Stdio::null()).output();
+    out.ok().and_then(|o| parse_utc_offset(String::from_utf8_lossy(&o.stdout).trim())).unwrap_or(0)
+}
+
+/// `+hhmm` / `-hhmm` as seconds east of UTC.
+fn parse_utc_offset(s: &str) -> Option<i64> {
+    let (sign, digits) = match s.split_at_checked(1)? {
+        ("+", d) => (1, d),
+        ("-", d) => (-1, d),
+        _ => return None,
+    };
+    if digits.len() != 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let (h, m): (i64, i64) = (digits[..2].parse().ok()?, digits[2..].parse().ok()?);
+    Some(sign * (h * 3600 + m * 60))
+}
+
 /// Read a child pipe to EOF on a background thread, forwarding chunks. The
 /// thread exits on EOF, read error, or when the receiver is dropped.
 fn spawn_reader<R: Read + Send + 'static>(mut r: R, tx: Sender<Vec<u8>>) {
@@ -977,7 +1640,7 @@ pub fn layout_output(bytes: &[u8], w: usize, h: usize) -> Vec<String> {
             let mut s: String = line.chars().take(w).collect();
             let len = s.chars().count();
             if len < w {
-                s.extend(std::iter::repeat(' ').take(w - len));
+                s.extend(std::iter::repeat_n(' ', w - len));
             }
             s
         })
@@ -1018,13 +1681,28 @@ fn strip_ansi(bytes: &[u8]) -> String {
 // Style conversion
 // ---------------------------------------------------------------------------
 
+/// Whether a terminal named `term` can be sent OSC 8 hyperlinks. Most
+/// terminals either support them or ignore the sequence; the exceptions are
+/// the bare ones that print it as text.
+pub fn hyperlinks_supported(term: Option<&str>) -> bool {
+    !matches!(term, None | Some("" | "dumb" | "linux" | "vt100" | "vt220"))
+}
+
 pub fn to_content_style(s: &Style) -> style::ContentStyle {
+    to_content_style_at(s, ColorDepth::TrueColor)
+}
+
+/// [`to_content_style`] with RGB colours downgraded to `depth`.
+pub fn to_content_style_at(s: &Style, depth: ColorDepth) -> style::ContentStyle {
+    if depth == ColorDepth::Monochrome {
+        return monochrome_style(s, None);
+    }
     let mut cs = style::ContentStyle::default();
     if let Some(fg) = &s.fg {
-        cs.foreground_color = Some(to_ct_color(fg));
+        cs.foreground_color = Some(depth.ct_color(fg));
     }
     if let Some(bg) = &s.bg {
-        cs.background_color = Some(to_ct_color(bg));
+        cs.background_color = Some(depth.ct_color(bg));
     }
     if s.bold {
         cs.attributes.set(style::Attribute::Bold);
@@ -1032,6 +1710,9 @@ pub fn to_content_style(s: &Style) -> style::ContentStyle {
     if s.dim {
         cs.attributes.set(style::Attribute::Dim);
     }
+    if s.underline {
+        cs.attributes.set(style::Attribute::Underlined);
+    }
     cs
 }
 
@@ -1057,21 +1738,27 @@ pub fn to_ct_color(c: &Color) -> style::Color {
 
 #[cfg(test)]
 mod tests {
-    use super::{loop_next, Player};
+    use super::{loop_next, parse_utc_offset, ColorDepth, Player};
     use crate::types::{
-        AnimationRegion, AutoAdvanceRegion, Cell, Frame, PlayablePresentation, TerminalContract,
+        AnimationRegion, AutoAdvanceRegion, Cell, CellChange, Color, Frame, NamedColor,
+        PlayablePresentation, Style, TerminalContract,
     };
 
     /// A player over `frames` blank frames carrying the given animation regions.
     fn player_with(frames: usize, animations: Vec<AnimationRegion>) -> Player {
         let pres = PlayablePresentation {
-            contract: TerminalContract { width: 1, height: 1 },
+            contract: TerminalContract { width: 1, height: 1, background: None },
             frames: (0..frames).map(|_| Frame::Full { cells: vec![vec![Cell::default()]] }).collect(),
             markers: Vec::new(),
             commands: Vec::new(),
+            clocks: Vec::new(),
             loops: Vec::new(),
             animations,
             auto_advances: Vec::new(),
+            durations: Vec::new(),
+            on_enter: Vec::new(),
+            metadata: Default::default(),
+            overlays: Vec::new(),
         };
         Player::new(pres)
     }
@@ -1079,13 +1766,18 @@ mod tests {
     /// A player over `frames` blank frames carrying the given auto-advance regions.
     fn player_with_auto(frames: usize, auto_advances: Vec<AutoAdvanceRegion>) -> Player {
         let pres = PlayablePresentation {
-            contract: TerminalContract { width: 1, height: 1 },
+            contract: TerminalContract { width: 1, height: 1, background: None },
             frames: (0..frames).map(|_| Frame::Full { cells: vec![vec![Cell::default()]] }).collect(),
             markers: Vec::new(),
             commands: Vec::new(),
+            clocks: Vec::new(),
             loops: Vec::new(),
             animations: Vec::new(),
             auto_advances,
+            durations: Vec::new(),
+            on_enter: Vec::new(),
+            metadata: Default::default(),
+            overlays: Vec::new(),
         };
         Player::new(pres)
     }
@@ -1125,13 +1817,18 @@ mod tests {
         // Frame 1: a 5s per-frame marker and a 3s auto-play animation both cover
         // the forward boundary; the faster one wins.
         let pres = PlayablePresentation {
-            contract: TerminalContract { width: 1, height: 1 },
+            contract: TerminalContract { width: 1, height: 1, background: None },
             frames: (0..5).map(|_| Frame::Full { cells: vec![vec![Cell::default()]] }).collect(),
             markers: Vec::new(),
             commands: Vec::new(),
+            clocks: Vec::new(),
             loops: Vec::new(),
             animations: vec![anim(0, 3, 3000)],
             auto_advances: vec![aa(0, 4, 5000)],
+            durations: Vec::new(),
+            on_enter: Vec::new(),
+            metadata: Default::default(),
+            overlays: Vec::new(),
         };
         let p = Player::new(pres);
         assert_eq!(p.effective_auto_delay(1), Some(3000)); // min(5000, 3000)
@@ -1280,4 +1977,52 @@ mod tests {
         assert_eq!(seq, vec![5, 6, 5, 6, 5]);
         assert_eq!(iters, 2);
     }
+
+    #[test]
+    fn cells_that_paint_alike_go_out_as_one_write() {
+        let red = Style { fg: Some(Color::Named(NamedColor::Red)), ..Style::default() };
+        let cell = |ch, style: &Style| Cell { ch, style: style.into() };
+        let change = |x, ch| CellChange { x, y: 0, cell: cell(ch, &red) };
+        let pres = PlayablePresentation {
+            contract: TerminalContract { width: 4, height: 1, background: None },
+            frames: vec![
+                Frame::Full { cells: vec![vec![cell('a', &red), cell('b', &red), cell('c', &Style::default()), cell('d', &red)]] },
+                Frame::Diff { changes: vec![change(3, 'z'), change(0, 'x'), change(1, 'y')] },
+            ],
+            markers: Vec::new(),
+            commands: Vec::new(),
+            clocks: Vec::new(),
+            loops: Vec::new(),
+            animations: Vec::new(),
+            auto_advances: Vec::new(),
+            durations: Vec::new(),
+            on_enter: Vec::new(),
+            metadata: Default::default(),
+            overlays: Vec::new(),
+        };
+        let p = Player::new(pres).with_color_depth(ColorDepth::TrueColor);
+        let paint = |diff: bool| {
+            let mut out = Vec::new();
+            if diff { p.render_diff(&mut out, 1) } else { p.render_full(&mut out) }.unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        let full = paint(false);
+        assert!(full.contains("ab"));
+        assert_eq!(full.matches("38;5;9m").count(), 2, "one SGR per run, not per cell");
+        // The diff is painted in order, with one cursor move per span.
+        let diff = paint(true);
+        assert_eq!(diff.matches("\x1b[2;").count(), 2);
+        assert!(diff.find("\x1b[2;1H").unwrap() < diff.find("\x1b[2;4H").unwrap());
+        assert!(diff.contains("xy"));
+    }
+
+    #[test]
+    fn utc_offsets_parse_as_date_prints_them() {
+        assert_eq!(parse_utc_offset("+0000"), Some(0));
+        assert_eq!(parse_utc_offset("+0530"), Some(5 * 3600 + 30 * 60));
+        assert_eq!(parse_utc_offset("-0800"), Some(-8 * 3600));
+        assert_eq!(parse_utc_offset("UTC"), None);
+        assert_eq!(parse_utc_offset("+5"), None);
+    }
 }
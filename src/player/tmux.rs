@@ -0,0 +1,71 @@
+//! Presenter notes in a tmux pane (`bs play --tmux-notes PANE`).
+//!
+//! The pane's terminal is looked up once with `tmux display-message` and
+//! repainted directly on every frame change — the frame number, that frame's
+//! speaker notes and a plain-text preview of the next frame — so any pane
+//! works, typically one left running `cat` or an idle shell beside the
+//! audience's.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+use crate::renderer::ansi::grid_to_plain;
+use crate::types::{Cell, PlayablePresentation};
+
+/// A tmux pane kept showing the notes for the current frame.
+pub struct TmuxNotes {
+    tty: PathBuf,
+    presentation: PlayablePresentation,
+    /// Speaker notes by frame; frames past the end have none.
+    notes: Vec<String>,
+}
+
+impl TmuxNotes {
+    /// Find `pane` (any tmux target: `%3`, `:1.1`, `notes`…) and get ready to
+    /// paint it.
+    pub fn new(pane: &str, presentation: PlayablePresentation, notes: Vec<String>) -> Result<Self> {
+        let out = Command::new("tmux")
+            .args(["display-message", "-p", "-t", pane, "#{pane_tty}"])
+            .output()
+            .context("Failed to run tmux")?;
+        let tty = String::from_utf8_lossy(&out.stdout).trim().to_string();
+        if !out.status.success() || tty.is_empty() {
+            bail!("no tmux pane `{pane}`: {}", String::from_utf8_lossy(&out.stderr).trim());
+        }
+        Ok(TmuxNotes { tty: tty.into(), presentation, notes })
+    }
+
+    /// Repaint the pane for `frame`. A pane that has gone away is left be.
+    pub fn show(&self, frame: usize) {
+        let next = (frame + 1 < self.presentation.frames.len()).then(|| self.presentation.grid_at(frame + 1));
+        let notes = self.notes.get(frame).map_or("", String::as_str);
+        let page = notes_page(frame, self.presentation.frames.len(), notes, next.as_deref());
+        if let Ok(mut tty) = OpenOptions::new().write(true).open(&self.tty) {
+            let _ = tty.write_all(format!("\x1b[H\x1b[2J{}", page.replace('\n', "\r\n")).as_bytes());
+        }
+    }
+}
+
+/// The notes pane's text for `frame` of `count`: a heading, `notes`, and the
+/// next frame as plain text (trailing blanks trimmed), if there is one.
+pub fn notes_page(frame: usize, count: usize, notes: &str, next: Option<&[Vec<Cell>]>) -> String {
+    let mut page = format!("Frame {}/{count}\n\n", frame + 1);
+    page.push_str(if notes.is_empty() { "(no notes)" } else { notes });
+    page.push_str("\n\n");
+    match next {
+        Some(grid) => {
+            page.push_str("Next:\n");
+            let plain = grid_to_plain(grid);
+            let lines: Vec<&str> = plain.lines().map(str::trim_end).collect();
+            let used = lines.iter().rposition(|l| !l.is_empty()).map_or(0, |i| i + 1);
+            page.push_str(&lines[..used].join("\n"));
+        }
+        None => page.push_str("(last frame)"),
+    }
+    page.push('\n');
+    page
+}
@@ -0,0 +1,91 @@
+//! ANSI escape-sequence encoding of styled cells.
+//!
+//! The player talks to the terminal through crossterm; everything that has to
+//! produce the same bytes *without* a terminal (cast export, text dumps) goes
+//! through these helpers instead (`grid_to_plain` is the escape-free twin).
+//! Named colours use the same palette indices crossterm emits
+//! ([`NamedColor::ansi_index`](crate::types::NamedColor::ansi_index)), so an
+//! exported frame looks exactly like the played one.
+
+use crate::types::{Cell, Color, Style, StyleRef};
+
+/// SGR sequence selecting `style` from a clean slate: always starts with a
+/// reset (`0`), so it can be emitted without knowing the previous style.
+pub fn sgr(style: &Style) -> String {
+    let mut params = vec!["0".to_string()];
+    if style.bold {
+        params.push("1".into());
+    }
+    if style.dim {
+        params.push("2".into());
+    }
+    if style.underline {
+        params.push("4".into());
+    }
+    if let Some(fg) = &style.fg {
+        params.push(color_params(38, fg));
+    }
+    if let Some(bg) = &style.bg {
+        params.push(color_params(48, bg));
+    }
+    format!("\x1b[{}m", params.join(";"))
+}
+
+/// `38;5;n` / `38;2;r;g;b` (or the `48` background forms) for `color`.
+fn color_params(base: u8, color: &Color) -> String {
+    match color {
+        Color::Named(n) => format!("{base};5;{}", n.ansi_index()),
+        Color::Rgb { r, g, b } => format!("{base};2;{r};{g};{b}"),
+    }
+}
+
+/// The SGR reset sequence.
+pub const RESET: &str = "\x1b[0m";
+
+/// OSC 8 sequence starting a hyperlink to `url`, or ending the open one for
+/// `None`. Control characters are dropped from the URL so it cannot end the
+/// sequence early.
+pub fn osc8(url: Option<&str>) -> String {
+    let url: String = url.unwrap_or_default().chars().filter(|c| !c.is_control()).collect();
+    format!("\x1b]8;;{url}\x1b\\")
+}
+
+/// Encode one row of cells, emitting an SGR sequence only where the style
+/// changes and resetting at the end if anything was styled; linked runs are
+/// wrapped in [`osc8`]. Continuation cells are skipped: the wide glyph before
+/// them already covers the column.
+pub fn row_to_ansi(row: &[Cell]) -> String {
+    let mut out = String::new();
+    let mut current = StyleRef::default();
+    for cell in row.iter().filter(|c| !c.is_continuation()) {
+        if cell.style != current {
+            if cell.style.link != current.link {
+                out.push_str(&osc8(cell.style.link.as_deref()));
+            }
+            out.push_str(&sgr(&cell.style));
+            current = cell.style.clone();
+        }
+        out.push(cell.ch);
+    }
+    if current.link.is_some() {
+        out.push_str(&osc8(None));
+    }
+    if !current.is_default() {
+        out.push_str(RESET);
+    }
+    out
+}
+
+/// Encode a whole grid as newline-separated rows (no cursor movement), ready to
+/// print at the current cursor position.
+pub fn grid_to_ansi(grid: &[Vec<Cell>]) -> String {
+    grid.iter().map(|row| row_to_ansi(row)).collect::<Vec<_>>().join("\n")
+}
+
+/// A grid's characters only, as newline-separated rows — no escape codes.
+pub fn grid_to_plain(grid: &[Vec<Cell>]) -> String {
+    grid.iter()
+        .map(|row| row.iter().filter(|c| !c.is_continuation()).map(|c| c.ch).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
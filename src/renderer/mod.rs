@@ -5,30 +5,65 @@
 //!
 //! The renderer is pure and stateless. Given the same input, it always
 //! produces the same output. It knows nothing about time, animation,
-//! or presentation semantics.
+//! or presentation semantics. `frame_to_ansi` / `frame_to_plain_text` turn a
+//! compiled frame back into text for headless consumers.
 
-use crate::types::{Cell, CellChange, Frame, PlayablePresentation, ResolvedScene, TerminalContract};
+pub mod ansi;
+
+use std::collections::HashMap;
+
+use crate::types::{
+    char_width, Cell, CellChange, Frame, PlayablePresentation, ResolvedScene, Style, StyleRef,
+    TerminalContract,
+};
+
+/// Frames between full keyframes in [`Renderer::render`]'s output, bounding how
+/// many diffs a seek has to replay (`PlayablePresentation::grid_at`).
+pub const DEFAULT_KEYFRAME_INTERVAL: usize = 50;
 
 pub struct Renderer;
 
 impl Renderer {
-    /// Render a sequence of resolved scenes into a playable presentation.
-    ///
-    /// The first frame is always a full frame. Subsequent frames are diffs
-    /// against the previous frame.
+    /// Render a sequence of resolved scenes into a playable presentation, with
+    /// a keyframe every [`DEFAULT_KEYFRAME_INTERVAL`] frames.
     pub fn render(scenes: &[ResolvedScene], contract: TerminalContract) -> PlayablePresentation {
-        let mut frames = Vec::with_capacity(scenes.len());
+        Self::render_with_keyframes(scenes, contract, DEFAULT_KEYFRAME_INTERVAL)
+    }
+
+    /// Render with a full frame (keyframe) at every multiple of `interval`;
+    /// the frames in between are diffs against the previous frame. The first
+    /// frame is always full; `interval == 0` makes it the only one.
+    pub fn render_with_keyframes(
+        scenes: &[ResolvedScene],
+        contract: TerminalContract,
+        interval: usize,
+    ) -> PlayablePresentation {
+        let canvas = contract.clone();
+        let grids = scenes.iter().map(move |scene| Self::rasterize(scene, &canvas));
+        Self::from_grids(grids, contract, interval)
+    }
+
+    /// Encode already-rasterized grids (one per frame, each `contract`-sized)
+    /// as a playable presentation, keyframed as [`Self::render_with_keyframes`].
+    /// For frames that come from somewhere other than the engine, such as a
+    /// replayed terminal recording.
+    pub fn from_grids(
+        grids: impl IntoIterator<Item = Vec<Vec<Cell>>>,
+        contract: TerminalContract,
+        interval: usize,
+    ) -> PlayablePresentation {
+        let mut frames = Vec::new();
         let mut prev_grid: Option<Vec<Vec<Cell>>> = None;
 
-        for scene in scenes {
-            let grid = Self::rasterize(scene, &contract);
+        for (i, grid) in grids.into_iter().enumerate() {
+            let keyframe = interval > 0 && i % interval == 0;
             let frame = match &prev_grid {
-                None => Frame::Full {
-                    cells: grid.clone(),
-                },
-                Some(prev) => Frame::Diff {
+                Some(prev) if !keyframe => Frame::Diff {
                     changes: Self::diff(prev, &grid),
                 },
+                _ => Frame::Full {
+                    cells: grid.clone(),
+                },
             };
             frames.push(frame);
             prev_grid = Some(grid);
@@ -39,40 +74,107 @@ impl Renderer {
             frames,
             markers: Vec::new(),
             commands: Vec::new(),
+            clocks: Vec::new(),
             loops: Vec::new(),
             animations: Vec::new(),
             auto_advances: Vec::new(),
+            durations: Vec::new(),
+            on_enter: Vec::new(),
+            metadata: Default::default(),
+            overlays: Vec::new(),
         }
     }
 
+    /// The cells each frame of `overlaid` changes over the same frame of
+    /// `base`: `(frame, changes)` for every frame with any, for an
+    /// [`Overlay`](crate::types::Overlay).
+    pub fn overlay_frames(
+        base: &[ResolvedScene],
+        overlaid: &[ResolvedScene],
+        contract: &TerminalContract,
+    ) -> Vec<(usize, Vec<CellChange>)> {
+        base.iter()
+            .zip(overlaid)
+            .enumerate()
+            .map(|(i, (b, o))| (i, Self::diff(&Self::rasterize(b, contract), &Self::rasterize(o, contract))))
+            .filter(|(_, changes)| !changes.is_empty())
+            .collect()
+    }
+
     /// Rasterize a resolved scene onto a fixed-size cell grid.
     ///
     /// Draw operations are sorted by z-order so that higher z values
-    /// paint over lower ones.
+    /// paint over lower ones. The sort is stable: equal-z ops keep the engine's
+    /// paint order, which is author order ([`crate::engine::Engine::compile`]). A double-width glyph takes its cell plus a
+    /// [`Cell::continuation`] to the right; one that would hang off the right
+    /// edge, or whose other half was later painted over, becomes a space.
+    /// The contract's `background` fills empty cells and any cell without a
+    /// `bg`, so the deck looks the same whatever the terminal theme.
     fn rasterize(scene: &ResolvedScene, contract: &TerminalContract) -> Vec<Vec<Cell>> {
         let w = contract.width as usize;
         let h = contract.height as usize;
-        let mut grid = vec![vec![Cell::default(); w]; h];
+        let mut grid = vec![vec![contract.blank_cell(); w]; h];
 
         let mut ops: Vec<_> = scene.ops.iter().collect();
         ops.sort_by_key(|op| op.z_order);
 
+        // Interned once per style per frame, not once per op.
+        let mut interned: HashMap<&Style, StyleRef> = HashMap::new();
         for op in ops {
             let x = op.x as usize;
             let y = op.y as usize;
             if x < w && y < h {
-                grid[y][x] = Cell {
-                    ch: op.ch,
-                    style: op.style.clone(),
-                };
+                let style = interned.entry(&op.style).or_insert_with(|| StyleRef::from(&op.style)).clone();
+                grid[y][x] = Cell { ch: op.ch, style: style.clone() };
+                if char_width(op.ch) == 2 && x + 1 < w {
+                    grid[y][x + 1] = Cell::continuation(style);
+                }
             }
         }
 
+        // Each style without a `bg` maps to one with the deck's.
+        let mut backed: HashMap<StyleRef, StyleRef> = HashMap::new();
+        for row in &mut grid {
+            Self::repair_wide(row);
+            if let Some(bg) = &contract.background {
+                for cell in row.iter_mut().filter(|c| c.style.bg.is_none()) {
+                    let style = backed
+                        .entry(cell.style.clone())
+                        .or_insert_with(|| Style { bg: Some(bg.clone()), ..Style::clone(&cell.style) }.into());
+                    cell.style = style.clone();
+                }
+            }
+        }
         grid
     }
 
+    /// Blank any half of a double-width glyph whose partner is gone.
+    fn repair_wide(row: &mut [Cell]) {
+        for x in 0..row.len() {
+            let broken = if row[x].is_continuation() {
+                x == 0 || char_width(row[x - 1].ch) != 2
+            } else {
+                char_width(row[x].ch) == 2 && !row.get(x + 1).is_some_and(Cell::is_continuation)
+            };
+            if broken {
+                row[x].ch = ' ';
+            }
+        }
+    }
+
+    /// Frame `n` (0-based, clamped to the last frame) as ANSI-styled text, one
+    /// line per row — the final content, no terminal required.
+    pub fn frame_to_ansi(presentation: &PlayablePresentation, n: usize) -> String {
+        ansi::grid_to_ansi(&presentation.grid_at(n))
+    }
+
+    /// Frame `n` (0-based, clamped) as plain characters, one line per row.
+    pub fn frame_to_plain_text(presentation: &PlayablePresentation, n: usize) -> String {
+        ansi::grid_to_plain(&presentation.grid_at(n))
+    }
+
     /// Compute a cell-level diff between two grids.
-    fn diff(prev: &[Vec<Cell>], next: &[Vec<Cell>]) -> Vec<CellChange> {
+    pub(crate) fn diff(prev: &[Vec<Cell>], next: &[Vec<Cell>]) -> Vec<CellChange> {
         let mut changes = Vec::new();
         for (y, (prev_row, next_row)) in prev.iter().zip(next.iter()).enumerate() {
             for (x, (prev_cell, next_cell)) in prev_row.iter().zip(next_row.iter()).enumerate() {
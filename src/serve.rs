@@ -0,0 +1,316 @@
+//! Presentation server — `bs serve <deck> --port 8080 [--telnet 2323]`.
+//!
+//! The presenter plays the deck in their own terminal as usual; every frame
+//! they show is broadcast to the audience as the same ANSI bytes a cast
+//! export carries: to browsers over a WebSocket, painted by an
+//! [xterm.js](https://xtermjs.org) page the server hands out at `/`, and with
+//! `--telnet` to terminals connected by `telnet` (or `nc`; put the port
+//! behind an sshd `ForceCommand nc localhost 2323` for `ssh talk.example.com`).
+//! Viewers are read-only: what they send is never read. One that connects
+//! late is sent the current frame straight away, and one that stops keeping
+//! up is dropped.
+//!
+//! Each viewer has a writer thread of its own, fed through a short bounded
+//! queue; showing a frame only queues it, so the presenter never waits on a
+//! viewer's socket. A viewer whose queue is full has fallen [`BACKLOG`]
+//! frames behind and is disconnected.
+//!
+//! Only what that needs of HTTP/1.1, RFC 6455 and telnet is spoken — one
+//! `GET` per connection, unmasked text frames out, the two options that stop
+//! a telnet client echoing typed keys — on `std::net` with a thread per
+//! connection, so the server adds no dependencies.
+
+use std::io::{Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+use crate::renderer::ansi::grid_to_ansi;
+use crate::types::{Cell, TerminalContract};
+
+/// The xterm.js release the page loads.
+const XTERM: &str = "https://cdn.jsdelivr.net/npm/@xterm/xterm@5.5.0";
+
+/// How long a frame may take to reach a viewer before it is dropped.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Frames queued for a viewer before it counts as lagging and is dropped.
+const BACKLOG: usize = 8;
+
+/// Largest request head read before a connection is given up on.
+const MAX_REQUEST: usize = 16 * 1024;
+
+/// Sent to a telnet viewer on connecting: `IAC WILL ECHO` and `IAC WILL
+/// SUPPRESS-GO-AHEAD`, so the client neither echoes keys nor buffers lines,
+/// then the cursor hidden.
+pub const TELNET_GREETING: &[u8] = b"\xff\xfb\x01\xff\xfb\x03\x1b[?25l";
+
+/// Bytes queued for a viewer's writer thread.
+type Outbox = SyncSender<Arc<[u8]>>;
+
+#[derive(Default)]
+struct Shared {
+    /// The latest frame's message, for viewers that connect later.
+    current: String,
+    /// Browsers, sent WebSocket frames.
+    clients: Vec<Outbox>,
+    /// Telnet viewers, sent the raw message.
+    terminals: Vec<Outbox>,
+}
+
+/// Start `stream`'s writer thread, queued `first` to send, and return its
+/// queue. The thread ends — closing the connection — when a write fails or
+/// times out, or once the queue has been dropped and drained.
+fn writer(mut stream: TcpStream, first: &[&[u8]]) -> Outbox {
+    let (outbox, queue) = mpsc::sync_channel::<Arc<[u8]>>(BACKLOG);
+    for bytes in first.iter().filter(|b| !b.is_empty()) {
+        let _ = outbox.try_send(Arc::from(*bytes));
+    }
+    thread::spawn(move || {
+        let ready = stream.set_write_timeout(Some(WRITE_TIMEOUT)).is_ok() && stream.set_nodelay(true).is_ok();
+        if ready {
+            for bytes in queue {
+                if stream.write_all(&bytes).is_err() {
+                    break;
+                }
+            }
+        }
+        let _ = stream.shutdown(Shutdown::Both);
+    });
+    outbox
+}
+
+/// Queue `bytes` for every viewer in `viewers`, dropping any that is gone or
+/// has fallen a full [`BACKLOG`] behind.
+fn broadcast(viewers: &mut Vec<Outbox>, bytes: &Arc<[u8]>) {
+    viewers.retain(|outbox| outbox.try_send(Arc::clone(bytes)).is_ok());
+}
+
+/// A running server; [`Server::show`] broadcasts a frame.
+pub struct Server {
+    addr: SocketAddr,
+    shared: Arc<Mutex<Shared>>,
+}
+
+impl Server {
+    /// Listen on `addr` and start accepting browsers in the background; the
+    /// page's terminal is sized to `contract`.
+    pub fn bind(addr: impl ToSocketAddrs, contract: &TerminalContract) -> Result<Self> {
+        let listener = TcpListener::bind(addr).context("Failed to start the server")?;
+        let addr = listener.local_addr()?;
+        let shared = Arc::new(Mutex::new(Shared::default()));
+        let page = page(contract.width, contract.height);
+        let accepting = Arc::clone(&shared);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let (shared, page) = (Arc::clone(&accepting), page.clone());
+                thread::spawn(move || {
+                    let _ = serve_connection(stream, &shared, &page);
+                });
+            }
+        });
+        Ok(Server { addr, shared })
+    }
+
+    /// The address actually bound (the port, when it was asked for as `0`).
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Also accept telnet viewers on `addr`; returns the address bound.
+    pub fn listen_telnet(&self, addr: impl ToSocketAddrs) -> Result<SocketAddr> {
+        let listener = TcpListener::bind(addr).context("Failed to start the telnet server")?;
+        let bound = listener.local_addr()?;
+        let shared = Arc::clone(&self.shared);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let mut shared = shared.lock().unwrap_or_else(|e| e.into_inner());
+                let outbox = writer(stream, &[TELNET_GREETING, shared.current.as_bytes()]);
+                shared.terminals.push(outbox);
+            }
+        });
+        Ok(bound)
+    }
+
+    /// Paint `grid` for every connected viewer. Only queues the frame, so it
+    /// returns at once however slow a viewer's connection is.
+    pub fn show(&self, grid: &[Vec<Cell>]) {
+        let message = frame_message(grid);
+        let (frame, raw): (Arc<[u8]>, Arc<[u8]>) = (ws_text_frame(&message).into(), message.as_bytes().into());
+        let mut shared = self.shared.lock().unwrap_or_else(|e| e.into_inner());
+        broadcast(&mut shared.clients, &frame);
+        broadcast(&mut shared.terminals, &raw);
+        shared.current = message;
+    }
+}
+
+/// The bytes that repaint a viewer's terminal with `grid`.
+pub fn frame_message(grid: &[Vec<Cell>]) -> String {
+    format!("\x1b[H\x1b[2J{}", grid_to_ansi(grid).replace('\n', "\r\n"))
+}
+
+/// Answer one connection: the page, the WebSocket upgrade, or a 404.
+fn serve_connection(mut stream: TcpStream, shared: &Mutex<Shared>, page: &str) -> Result<()> {
+    let head = read_head(&mut stream)?;
+    let mut lines = head.lines();
+    let path = lines.next().and_then(|l| l.split_whitespace().nth(1)).unwrap_or("/");
+    let key = lines.find_map(|l| {
+        let (name, value) = l.split_once(':')?;
+        name.trim().eq_ignore_ascii_case("sec-websocket-key").then(|| value.trim().to_string())
+    });
+    match (path, key) {
+        ("/ws", Some(key)) => {
+            let reply = format!(
+                "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+                accept_key(&key)
+            );
+            // The reply and the current frame go out first in the queue,
+            // registered under the lock, so an upgraded browser misses no
+            // frame.
+            let mut shared = shared.lock().unwrap_or_else(|e| e.into_inner());
+            let current = if shared.current.is_empty() { Vec::new() } else { ws_text_frame(&shared.current) };
+            let outbox = writer(stream, &[reply.as_bytes(), &current]);
+            shared.clients.push(outbox);
+        }
+        ("/", _) | ("/index.html", _) => respond(&mut stream, "200 OK", "text/html; charset=utf-8", page)?,
+        _ => respond(&mut stream, "404 Not Found", "text/plain", "not found\n")?,
+    }
+    Ok(())
+}
+
+fn read_head(stream: &mut TcpStream) -> Result<String> {
+    stream.set_read_timeout(Some(WRITE_TIMEOUT))?;
+    let mut head = Vec::new();
+    let mut buf = [0; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut buf)?;
+        if n == 0 || head.len() > MAX_REQUEST {
+            anyhow::bail!("incomplete request");
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+    Ok(String::from_utf8_lossy(&head).into_owned())
+}
+
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) -> Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    Ok(())
+}
+
+/// The viewer page: an xterm.js terminal `width × height`, repainted by every
+/// message and reconnecting if the server goes away.
+fn page(width: u16, height: u16) -> String {
+    format!(
+        r#"<!doctype html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>bs</title>
+<link rel="stylesheet" href="{XTERM}/css/xterm.css">
+<script src="{XTERM}/lib/xterm.js"></script>
+<style>html, body {{ margin: 0; height: 100%; background: #000; display: flex; align-items: center; justify-content: center; }}</style>
+</head>
+<body>
+<div id="screen"></div>
+<script>
+const term = new Terminal({{ cols: {width}, rows: {height}, disableStdin: true, cursorBlink: false }});
+term.open(document.getElementById("screen"));
+term.write("\x1b[?25l");
+function connect() {{
+  const ws = new WebSocket((location.protocol === "https:" ? "wss://" : "ws://") + location.host + "/ws");
+  ws.onmessage = (e) => term.write(e.data);
+  ws.onclose = () => setTimeout(connect, 1000);
+}}
+connect();
+</script>
+</body>
+</html>
+"#
+    )
+}
+
+/// An unmasked, final WebSocket text frame carrying `text`.
+pub fn ws_text_frame(text: &str) -> Vec<u8> {
+    let len = text.len();
+    let mut frame = vec![0x81];
+    match len {
+        0..=125 => frame.push(len as u8),
+        126..=0xffff => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        _ => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(text.as_bytes());
+    frame
+}
+
+/// `Sec-WebSocket-Accept` for a client's `Sec-WebSocket-Key` (RFC 6455 §4.2.2).
+pub fn accept_key(key: &str) -> String {
+    base64(&sha1(format!("{key}258EAFA5-E914-47DA-95CA-C5AB0DC85B11").as_bytes()))
+}
+
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+    let mut msg = data.to_vec();
+    msg.push(0x80);
+    while msg.len() % 64 != 56 {
+        msg.push(0);
+    }
+    msg.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+    for block in msg.chunks(64) {
+        let mut w = [0u32; 80];
+        for (i, word) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, &wi) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let t = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(k).wrapping_add(wi);
+            (e, d, c, b, a) = (d, c, b.rotate_left(30), a, t);
+        }
+        for (h, v) in h.iter_mut().zip([a, b, c, d, e]) {
+            *h = h.wrapping_add(v);
+        }
+    }
+    let mut out = [0; 20];
+    for (chunk, v) in out.chunks_mut(4).zip(h) {
+        chunk.copy_from_slice(&v.to_be_bytes());
+    }
+    out
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::new();
+    for chunk in data.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
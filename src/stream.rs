@@ -0,0 +1,202 @@
+//! Seekable form of a compiled [`PlayablePresentation`]
+//! (`bs compile --format stream`), which `bs play` shows without reading it
+//! whole.
+//!
+//! JSON and MessagePack are single documents: nothing can be shown until all of
+//! it has been parsed, and every frame is then held in memory. A stream file
+//! instead stores each frame as its own [MessagePack](crate::msgpack) record
+//! and puts everything else at the end:
+//!
+//! ```text
+//! "BSSTREAM" 0x01            magic and container version
+//! frame 0 … frame n-1        compact frames, one MessagePack record each
+//! header                     MessagePack map: the compact document minus
+//!                            its frames, plus "index": [[offset, length, full], …]
+//! header offset              u64, little-endian — the file's last 8 bytes
+//! ```
+//!
+//! Records use the [compact](crate::compact) run-length shape under the one
+//! style table in the header, so a stream file is about as small as a
+//! MessagePack one. Opening it reads the trailer and header only; a
+//! [`FrameStream`] then decodes a frame when asked for it, so playback starts
+//! at once and memory stays bounded by the frames on screen, whatever the
+//! length of the deck. Seeking replays from the nearest keyframe, whose
+//! position the index records.
+
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{BufReader, Cursor, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::compact::{self, StyleTable, Wire};
+use crate::msgpack;
+use crate::types::{Cell, Frame, PlayablePresentation, StyleRef};
+
+/// The first bytes of every stream file.
+pub const MAGIC: &[u8; 8] = b"BSSTREAM";
+
+/// Container version written after [`MAGIC`].
+const VERSION: u8 = 1;
+
+/// `[offset, length, full]` of one frame record.
+type IndexEntry = (u64, u64, bool);
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    #[serde(flatten)]
+    wire: Wire,
+    index: Vec<IndexEntry>,
+}
+
+/// True when `bytes` starts with the stream [`MAGIC`].
+pub fn is_stream(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+/// True when the file at `path` is a stream file (false when it can't be read;
+/// the caller's own open reports that).
+pub fn is_stream_file(path: impl AsRef<Path>) -> bool {
+    let mut magic = [0; MAGIC.len()];
+    File::open(path).and_then(|mut f| f.read_exact(&mut magic)).is_ok() && is_stream(&magic)
+}
+
+/// Encode a compiled presentation as a stream file.
+pub fn to_stream(presentation: &PlayablePresentation) -> Result<Vec<u8>> {
+    let mut out = MAGIC.to_vec();
+    out.push(VERSION);
+    let mut table = StyleTable::default();
+    let mut index = Vec::with_capacity(presentation.frames.len());
+    for frame in &presentation.frames {
+        let start = out.len();
+        let record = serde_json::to_value(compact::encode_frame(frame, &mut table))?;
+        msgpack::encode(&record, &mut out);
+        index.push((start as u64, (out.len() - start) as u64, matches!(frame, Frame::Full { .. })));
+    }
+    let header_at = out.len() as u64;
+    let skeleton = PlayablePresentation { frames: Vec::new(), ..presentation.clone() };
+    let header = Header { wire: Wire::without_frames(skeleton, table), index };
+    msgpack::encode(&serde_json::to_value(&header)?, &mut out);
+    out.extend_from_slice(&header_at.to_le_bytes());
+    Ok(out)
+}
+
+/// Decode a whole stream file held in memory, for the tools that want every
+/// frame (exporters, inspectors).
+pub fn from_stream(bytes: &[u8]) -> Result<PlayablePresentation> {
+    let stream = FrameStream::new(Cursor::new(bytes))?;
+    let frames = (0..stream.len()).map(|i| stream.frame(i)).collect::<Result<_>>()?;
+    Ok(PlayablePresentation { frames, ..stream.skeleton.clone() })
+}
+
+/// An open stream file: the header in memory, frames read on demand.
+pub struct FrameStream<R = BufReader<File>> {
+    reader: RefCell<R>,
+    /// The presentation with no frames: contract and sidecars.
+    skeleton: PlayablePresentation,
+    styles: Vec<StyleRef>,
+    index: Vec<IndexEntry>,
+}
+
+impl FrameStream {
+    /// Open the stream file at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        FrameStream::new(BufReader::new(file)).with_context(|| format!("Failed to open {}", path.display()))
+    }
+}
+
+impl<R: Read + Seek> FrameStream<R> {
+    /// Read the magic, trailer and header from `reader`.
+    pub fn new(mut reader: R) -> Result<Self> {
+        let mut magic = [0; MAGIC.len() + 1];
+        reader.read_exact(&mut magic).context("not a stream file")?;
+        if !is_stream(&magic) {
+            bail!("not a stream file");
+        }
+        if magic[MAGIC.len()] != VERSION {
+            bail!("stream container version {} is not supported (expected {VERSION})", magic[MAGIC.len()]);
+        }
+        let end = reader.seek(SeekFrom::End(-8)).context("stream file is truncated")?;
+        let mut trailer = [0; 8];
+        reader.read_exact(&mut trailer)?;
+        let header_at = u64::from_le_bytes(trailer);
+        if header_at < magic.len() as u64 || header_at > end {
+            bail!("stream file is truncated");
+        }
+        let header = read_record(&mut reader, header_at, end - header_at)?;
+        let header: Header = serde_json::from_value(header).context("not a compiled presentation")?;
+        header.wire.check_version().map_err(anyhow::Error::msg)?;
+        if let Some((at, len, _)) = header.index.iter().find(|(at, len, _)| at.checked_add(*len).is_none_or(|e| e > header_at)) {
+            bail!("frame record at {at} ({len} bytes) runs past the frames");
+        }
+        let (skeleton, styles) = header.wire.into_skeleton().map_err(anyhow::Error::msg)?;
+        Ok(FrameStream { reader: RefCell::new(reader), skeleton, styles, index: header.index })
+    }
+
+    /// Number of frames.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// The presentation minus its frames — contract, markers, commands, loops
+    /// and the rest.
+    pub fn skeleton(&self) -> &PlayablePresentation {
+        &self.skeleton
+    }
+
+    /// Read and decode frame `n`.
+    pub fn frame(&self, n: usize) -> Result<Frame> {
+        let Some(&(at, len, _)) = self.index.get(n) else {
+            bail!("frame {n} out of range ({} frames)", self.len());
+        };
+        let record = read_record(&mut *self.reader.borrow_mut(), at, len)?;
+        let record = serde_json::from_value(record).with_context(|| format!("frame {n} is malformed"))?;
+        compact::decode_frame(record, &self.styles, &self.skeleton.contract, n).map_err(anyhow::Error::msg)
+    }
+
+    /// The full grid shown at frame `n` (clamped to the last), replayed from
+    /// the nearest keyframe at or before it — the streamed
+    /// [`PlayablePresentation::grid_at`].
+    pub fn grid_at(&self, n: usize) -> Result<Vec<Vec<Cell>>> {
+        let contract = &self.skeleton.contract;
+        let mut grid = vec![vec![contract.blank_cell(); contract.width as usize]; contract.height as usize];
+        if self.index.is_empty() {
+            return Ok(grid);
+        }
+        let last = n.min(self.index.len() - 1);
+        let start = self.index[..=last].iter().rposition(|&(_, _, full)| full).unwrap_or(0);
+        for i in start..=last {
+            match self.frame(i)? {
+                Frame::Full { cells } => grid = cells,
+                Frame::Diff { changes } => {
+                    for c in changes {
+                        let (x, y) = (c.x as usize, c.y as usize);
+                        if y < grid.len() && x < grid[0].len() {
+                            grid[y][x] = c.cell;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(grid)
+    }
+}
+
+/// Decode the `len`-byte MessagePack record at `at`.
+fn read_record(reader: &mut (impl Read + Seek), at: u64, len: u64) -> Result<serde_json::Value> {
+    reader.seek(SeekFrom::Start(at))?;
+    let mut bytes = Vec::with_capacity(len as usize);
+    reader.take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        bail!("stream file is truncated");
+    }
+    msgpack::decode(&bytes)
+}
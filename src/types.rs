@@ -4,20 +4,26 @@
 //! - Engine → Renderer (in-memory): `ResolvedScene` containing `DrawOp`s
 //! - Renderer → Player (file): `PlayablePresentation` containing `Frame`s
 
-use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, Range};
+use std::sync::{Arc, LazyLock, Mutex, OnceLock, Weak};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 // ---------------------------------------------------------------------------
 // Shared style primitives
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum Color {
     Named(NamedColor),
     Rgb { r: u8, g: u8, b: u8 },
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum NamedColor {
     Black,
@@ -30,11 +36,117 @@ pub enum NamedColor {
     White,
 }
 
+impl NamedColor {
+    /// Index into the 256-colour palette that the player emits for this name.
+    /// crossterm maps every name except black to the *bright* half of the
+    /// 16-colour palette, so exporters use the same indices to match playback.
+    pub fn ansi_index(&self) -> u8 {
+        match self {
+            NamedColor::Black => 0,
+            NamedColor::Red => 9,
+            NamedColor::Green => 10,
+            NamedColor::Yellow => 11,
+            NamedColor::Blue => 12,
+            NamedColor::Magenta => 13,
+            NamedColor::Cyan => 14,
+            NamedColor::White => 15,
+        }
+    }
+
+    /// The colour as RGB, using xterm's default values for [`Self::ansi_index`].
+    pub fn rgb(&self) -> (u8, u8, u8) {
+        match self {
+            NamedColor::Black => (0, 0, 0),
+            NamedColor::Red => (255, 0, 0),
+            NamedColor::Green => (0, 255, 0),
+            NamedColor::Yellow => (255, 255, 0),
+            NamedColor::Blue => (92, 92, 255),
+            NamedColor::Magenta => (255, 0, 255),
+            NamedColor::Cyan => (0, 255, 255),
+            NamedColor::White => (255, 255, 255),
+        }
+    }
+}
+
+impl From<NamedColor> for Color {
+    fn from(n: NamedColor) -> Self {
+        Color::Named(n)
+    }
+}
+
+impl From<(u8, u8, u8)> for Color {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        Color::Rgb { r, g, b }
+    }
+}
+
+impl Color {
+    /// The colour as RGB (named colours via [`NamedColor::rgb`]). For
+    /// non-terminal outputs (SVG/HTML/GIF) that need concrete values.
+    pub fn rgb(&self) -> (u8, u8, u8) {
+        match self {
+            Color::Named(n) => n.rgb(),
+            Color::Rgb { r, g, b } => (*r, *g, *b),
+        }
+    }
+}
+
+/// The xterm 256-colour palette as RGB: the 16 system colours, the 6×6×6
+/// cube, then the 24-step grey ramp.
+pub fn xterm_palette() -> &'static [(u8, u8, u8)] {
+    static PALETTE: OnceLock<Vec<(u8, u8, u8)>> = OnceLock::new();
+    PALETTE.get_or_init(|| {
+        const BASE: [(u8, u8, u8); 16] = [
+            (0, 0, 0),
+            (205, 0, 0),
+            (0, 205, 0),
+            (205, 205, 0),
+            (0, 0, 238),
+            (205, 0, 205),
+            (0, 205, 205),
+            (229, 229, 229),
+            (127, 127, 127),
+            (255, 0, 0),
+            (0, 255, 0),
+            (255, 255, 0),
+            (92, 92, 255),
+            (255, 0, 255),
+            (0, 255, 255),
+            (255, 255, 255),
+        ];
+        const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+        let mut pal = BASE.to_vec();
+        for r in LEVELS {
+            for g in LEVELS {
+                for b in LEVELS {
+                    pal.push((r, g, b));
+                }
+            }
+        }
+        for i in 0..24u8 {
+            let v = 8 + 10 * i;
+            pal.push((v, v, v));
+        }
+        pal
+    })
+}
+
+/// Index of the [`xterm_palette`] entry within `range` nearest to `c`
+/// (squared RGB distance; ties go to the lower index).
+pub fn nearest_xterm(c: (u8, u8, u8), range: Range<usize>) -> u8 {
+    let palette = xterm_palette();
+    let dist = |p: (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        d(p.0, c.0) + d(p.1, c.1) + d(p.2, c.2)
+    };
+    range.min_by_key(|&i| dist(palette[i])).unwrap_or(0) as u8
+}
+
 fn is_false(b: &bool) -> bool {
     !*b
 }
 
-#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Style {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub fg: Option<Color>,
@@ -44,11 +156,127 @@ pub struct Style {
     pub bold: bool,
     #[serde(default, skip_serializing_if = "is_false")]
     pub dim: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub underline: bool,
+    /// Hyperlink target: the player wraps the cell in an OSC 8 link to it
+    /// where the terminal supports them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
 }
 
 impl Style {
     pub fn is_default(&self) -> bool {
-        self.fg.is_none() && self.bg.is_none() && !self.bold && !self.dim
+        self.fg.is_none() && self.bg.is_none() && !self.bold && !self.dim && !self.underline && self.link.is_none()
+    }
+}
+
+/// A shared, interned [`Style`] — what a [`Cell`] holds.
+///
+/// A deck uses a handful of distinct styles across thousands of cells, so each
+/// is stored once and cells share it: cloning a cell bumps a count, and
+/// comparing two (as frame diffs do for every cell) compares pointers. Reads
+/// go through `Deref`, so `cell.style.fg` works as before; to change a cell's
+/// style, build the new `Style` and intern that. A style is freed with the
+/// last cell holding it.
+#[derive(Clone)]
+pub struct StyleRef(Arc<Style>);
+
+/// The live interned styles. Entries whose style has been freed are swept out
+/// once the table has doubled since the last sweep, so a long session that
+/// loads deck after deck doesn't keep every style it ever saw.
+#[derive(Default)]
+struct Interner {
+    styles: HashMap<Style, Weak<Style>>,
+    swept_at: usize,
+}
+
+static INTERNED: LazyLock<Mutex<Interner>> = LazyLock::new(Default::default);
+
+impl StyleRef {
+    pub fn new(style: Style) -> Self {
+        // Blank cells are most of every grid; they skip the lock.
+        static DEFAULT: OnceLock<StyleRef> = OnceLock::new();
+        if style.is_default() {
+            return DEFAULT.get_or_init(|| StyleRef(Arc::new(Style::default()))).clone();
+        }
+        Self::intern(style)
+    }
+
+    fn intern(style: Style) -> Self {
+        let mut interned = INTERNED.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(live) = interned.styles.get(&style).and_then(Weak::upgrade) {
+            return StyleRef(live);
+        }
+        if interned.styles.len() >= 2 * interned.swept_at.max(32) {
+            interned.styles.retain(|_, weak| weak.strong_count() > 0);
+            interned.swept_at = interned.styles.len();
+        }
+        let shared = Arc::new(style.clone());
+        interned.styles.insert(style, Arc::downgrade(&shared));
+        StyleRef(shared)
+    }
+
+    fn is_default(&self) -> bool {
+        self.0.is_default()
+    }
+}
+
+impl Default for StyleRef {
+    fn default() -> Self {
+        StyleRef::new(Style::default())
+    }
+}
+
+impl Deref for StyleRef {
+    type Target = Style;
+
+    fn deref(&self) -> &Style {
+        &self.0
+    }
+}
+
+impl From<Style> for StyleRef {
+    fn from(style: Style) -> Self {
+        StyleRef::new(style)
+    }
+}
+
+impl From<&Style> for StyleRef {
+    fn from(style: &Style) -> Self {
+        StyleRef::new(style.clone())
+    }
+}
+
+/// Equal styles are interned once, so the pointers decide.
+impl PartialEq for StyleRef {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for StyleRef {}
+
+impl Hash for StyleRef {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::ptr::hash(Arc::as_ptr(&self.0), state);
+    }
+}
+
+impl fmt::Debug for StyleRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Serialize for StyleRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StyleRef {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Style::deserialize(deserializer).map(StyleRef::new)
     }
 }
 
@@ -69,6 +297,7 @@ pub struct DrawOp {
 pub struct ResolvedScene {
     pub width: u16,
     pub height: u16,
+    /// In paint order: by `z_order`, equal ones in author (object) order.
     pub ops: Vec<DrawOp>,
 }
 
@@ -80,24 +309,71 @@ pub struct ResolvedScene {
 pub struct TerminalContract {
     pub width: u16,
     pub height: u16,
+    /// Deck-wide background: fills empty cells and sits behind any cell that
+    /// sets no `bg` of its own. `None` leaves the terminal's default showing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<Color>,
+}
+
+impl TerminalContract {
+    /// An empty cell on this canvas.
+    pub fn blank_cell(&self) -> Cell {
+        Cell { ch: ' ', style: Style { bg: self.background.clone(), ..Style::default() }.into() }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Cell {
     pub ch: char,
-    #[serde(default, skip_serializing_if = "Style::is_default")]
-    pub style: Style,
+    #[serde(default, skip_serializing_if = "StyleRef::is_default")]
+    pub style: StyleRef,
 }
 
 impl Default for Cell {
     fn default() -> Self {
         Cell {
             ch: ' ',
-            style: Style::default(),
+            style: StyleRef::default(),
         }
     }
 }
 
+/// The `ch` of the cell to the right of a double-width glyph. The terminal
+/// draws the glyph across both columns, so this cell paints nothing itself;
+/// every output skips it.
+pub const WIDE_CONTINUATION: char = '\0';
+
+impl Cell {
+    /// The right-hand half of a double-width glyph drawn with `style`.
+    pub fn continuation(style: impl Into<StyleRef>) -> Self {
+        Cell { ch: WIDE_CONTINUATION, style: style.into() }
+    }
+
+    pub fn is_continuation(&self) -> bool {
+        self.ch == WIDE_CONTINUATION
+    }
+}
+
+/// Columns `ch` takes in a terminal: 2 for wide glyphs (CJK, most emoji),
+/// otherwise 1 — zero-width and control characters still get their own cell.
+pub fn char_width(ch: char) -> usize {
+    if unicode_width::UnicodeWidthChar::width(ch) == Some(2) { 2 } else { 1 }
+}
+
+/// Display width of `s` in columns (see [`char_width`]).
+pub fn str_width(s: &str) -> usize {
+    s.chars().map(char_width).sum()
+}
+
+/// Each char of `s` with the column it starts at.
+pub fn char_columns(s: &str) -> impl Iterator<Item = (usize, char)> + '_ {
+    s.chars().scan(0, |col, ch| {
+        let at = *col;
+        *col += char_width(ch);
+        Some((at, ch))
+    })
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CellChange {
     pub x: u16,
@@ -154,6 +430,40 @@ pub struct CommandRegion {
     pub style: Style,
 }
 
+/// A runtime clock — the sidecar spec for a `Clock` object.
+///
+/// The time of day is only known at play time, so the compiled frames carry a
+/// `--:--` placeholder where the clock sits and the player paints the current
+/// local time over it at `(x, y)` while a frame in `[start_frame, end_frame)`
+/// is shown, keeping it up to date.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClockRegion {
+    pub start_frame: usize,
+    pub end_frame: usize,
+    pub x: u16,
+    pub y: u16,
+    /// Show `HH:MM:SS` rather than `HH:MM`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub seconds: bool,
+    #[serde(default, skip_serializing_if = "Style::is_default")]
+    pub style: Style,
+}
+
+impl ClockRegion {
+    /// What the clock shows `unix_secs` seconds after the epoch, `offset_secs`
+    /// east of UTC.
+    pub fn text(&self, unix_secs: u64, offset_secs: i64) -> String {
+        let secs = (unix_secs as i64 + offset_secs).rem_euclid(86_400);
+        let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+        if self.seconds { format!("{h:02}:{m:02}:{s:02}") } else { format!("{h:02}:{m:02}") }
+    }
+
+    /// The compile-time stand-in for [`Self::text`], the same width.
+    pub fn placeholder(seconds: bool) -> &'static str {
+        if seconds { "--:--:--" } else { "--:--" }
+    }
+}
+
 /// A runtime loop region — the sidecar spec for a `Loop` object.
 ///
 /// Like a `Command`, a `Loop` is a play-time behavior that cannot be baked into
@@ -220,25 +530,87 @@ pub struct AutoAdvanceRegion {
     pub delay_ms: u64,
 }
 
+/// A prepared overlay — the sidecar for an overlay layer (`"overlay": true`
+/// in the source's `layers`).
+///
+/// The layer's objects are not baked into the frames. Instead, for each frame
+/// they would change, the compiler keeps the cells they change, and the
+/// `Player` paints those over the frame while the presenter has the overlay
+/// toggled on (key `n` for the n-th overlay), until the frame changes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Overlay {
+    /// The layer's name.
+    pub name: String,
+    /// `(frame, changes)` for every frame the overlay changes, by frame.
+    pub frames: Vec<(usize, Vec<CellChange>)>,
+}
+
+impl Overlay {
+    /// The cells the overlay changes on `frame`, if any.
+    pub fn changes_at(&self, frame: usize) -> Option<&[CellChange]> {
+        let i = self.frames.binary_search_by_key(&frame, |(f, _)| *f).ok()?;
+        Some(&self.frames[i].1)
+    }
+}
+
+/// What a deck says about itself — the `metadata` block of both the source
+/// and the compiled file. Every field is optional free text; `date` is shown
+/// as written.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Metadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl Metadata {
+    pub fn is_empty(&self) -> bool {
+        *self == Metadata::default()
+    }
+}
+
+/// The compiled `format_version` this build reads and writes; a compiled file
+/// without one predates the field and is version 1.
+pub const COMPILED_FORMAT_VERSION: u32 = 1;
+
+/// A compiled deck. Serialized compactly — a style table plus run-length
+/// encoded frames (see `compact.rs`) — and decoded back into plain frames on
+/// load. The serialized form is stamped with [`COMPILED_FORMAT_VERSION`], and
+/// a file stamped newer is refused.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "crate::compact::Wire", try_from = "crate::compact::Wire")]
 pub struct PlayablePresentation {
     pub contract: TerminalContract,
     pub frames: Vec<Frame>,
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub markers: Vec<Marker>,
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub commands: Vec<CommandRegion>,
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub clocks: Vec<ClockRegion>,
     pub loops: Vec<LoopRegion>,
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub animations: Vec<AnimationRegion>,
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub auto_advances: Vec<AutoAdvanceRegion>,
+    /// How long each frame shows, in milliseconds — one entry per frame, `None`
+    /// where the source set none; empty when no frame has a duration. Copied
+    /// from [`SourcePresentation::durations`](crate::engine::source::SourcePresentation::durations).
+    pub durations: Vec<Option<u64>>,
+    /// Shell commands to run as each frame is shown — one entry per frame;
+    /// empty when no frame has any. Copied from
+    /// [`SourcePresentation::on_enter`](crate::engine::source::SourcePresentation::on_enter).
+    pub on_enter: Vec<Vec<String>>,
+    /// Copied from [`SourcePresentation::metadata`](crate::engine::source::SourcePresentation::metadata).
+    pub metadata: Metadata,
+    /// One per overlay layer, in the order their keys number them.
+    pub overlays: Vec<Overlay>,
 }
 
 impl PlayablePresentation {
     /// Reconstruct the full cell grid visible at `frame` by replaying the
-    /// initial `Frame::Full` plus every `Frame::Diff` up to and including it.
+    /// nearest `Frame::Full` (keyframe) at or before it plus every `Frame::Diff`
+    /// after that, up to and including `frame`.
     ///
     /// This is the single source of truth for "what does frame N look like":
     /// the player, the editor preview, and the test harness all go through it,
@@ -249,12 +621,16 @@ impl PlayablePresentation {
     pub fn grid_at(&self, frame: usize) -> Vec<Vec<Cell>> {
         let w = self.contract.width as usize;
         let h = self.contract.height as usize;
-        let mut grid = vec![vec![Cell::default(); w]; h];
+        let mut grid = vec![vec![self.contract.blank_cell(); w]; h];
         if self.frames.is_empty() {
             return grid;
         }
         let last = frame.min(self.frames.len() - 1);
-        for f in &self.frames[..=last] {
+        let start = self.frames[..=last]
+            .iter()
+            .rposition(|f| matches!(f, Frame::Full { .. }))
+            .unwrap_or(0);
+        for f in &self.frames[start..=last] {
             match f {
                 Frame::Full { cells } => grid = cells.clone(),
                 Frame::Diff { changes } => {
@@ -271,3 +647,27 @@ impl PlayablePresentation {
         grid
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn linked(i: usize) -> Style {
+        Style { link: Some(format!("swept-{i}")), ..Style::default() }
+    }
+
+    #[test]
+    fn dropped_styles_are_freed_and_swept_from_the_interner() {
+        let kept = StyleRef::new(linked(0));
+        let weak = Arc::downgrade(&StyleRef::new(linked(1)).0);
+        assert!(weak.upgrade().is_none(), "the last cell's handle frees its style");
+        for i in 2..10_000 {
+            drop(StyleRef::new(linked(i)));
+        }
+        let interned = INTERNED.lock().unwrap_or_else(|e| e.into_inner());
+        let ours = interned.styles.keys().filter(|s| s.link.as_deref().is_some_and(|l| l.starts_with("swept-"))).count();
+        assert!(ours < 1_000, "{ours} entries left behind");
+        drop(interned);
+        assert_eq!(StyleRef::new(linked(0)), kept, "live styles are still shared");
+    }
+}
@@ -0,0 +1,616 @@
+//! Semantic validation of a source presentation (`bs validate`).
+//!
+//! A file can parse cleanly and still be wrong: an object whose range runs past
+//! the deck silently loses its tail, a coordinate whose animation was deleted
+//! freezes at `from`, a group pointing at a removed object does nothing. None of
+//! that is a hard error for the engine (it degrades gracefully), so it goes
+//! unnoticed until the talk. This pass finds those mistakes up front.
+//!
+//! Every finding carries a JSON path into the authored file (`objects[3].frames`)
+//! and — when validating a file — the line its object starts on, so the message
+//! reads like a compiler diagnostic (`deck.json:42: error: …`).
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use anyhow::{bail, Context, Result};
+
+use crate::engine::objects::{Resolve, ResolveCtx};
+use crate::engine::source::{AnimSpans, Coordinate, Scalar, SceneObject, SourcePresentation};
+use crate::engine::{Engine, LintLevel, LintRule, OutOfBounds};
+use crate::import::Recording;
+
+/// How serious a finding is. Errors fail `bs validate`; warnings are printed
+/// but still exit successfully.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        })
+    }
+}
+
+/// One validation finding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Index of the offending object, when the finding is about one.
+    pub object: Option<usize>,
+    /// The first frame a frame-dependent finding (a lint) was seen on.
+    pub frame: Option<usize>,
+    /// JSON path of the offending value (`objects[3].members[1]`).
+    pub path: String,
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn error(object: Option<usize>, path: String, message: String) -> Self {
+        Diagnostic { severity: Severity::Error, object, frame: None, path, message }
+    }
+
+    fn warning(object: Option<usize>, path: String, message: String) -> Self {
+        Diagnostic { severity: Severity::Warning, object, frame: None, path, message }
+    }
+}
+
+/// How far a table's `col_widths` may stray from summing to 1.0 before it is
+/// flagged (the layout absorbs rounding into the last column either way).
+const COL_WIDTH_TOLERANCE: f64 = 0.01;
+
+/// Run every semantic check over `source`, in object order.
+pub fn validate(source: &SourcePresentation) -> Vec<Diagnostic> {
+    let mut out = Vec::new();
+    if source.width == 0 || source.height == 0 {
+        out.push(Diagnostic::error(
+            None,
+            "width".into(),
+            format!("the canvas is {}x{} — both dimensions must be non-zero", source.width, source.height),
+        ));
+    }
+    if source.frame_count == 0 {
+        out.push(Diagnostic::error(None, "frame_count".into(), "the deck has no frames".into()));
+    }
+
+    let anims = AnimSpans::of(source);
+    let sections = source.section_starts();
+    let undefined = undefined_vars(source);
+    for (i, obj) in source.objects.iter().enumerate() {
+        let at = |field: &str| format!("objects[{i}].{field}");
+        let what = obj.kind();
+
+        if let Some(r) = obj.declared_frame_range() {
+            if r.start >= r.end {
+                out.push(Diagnostic::error(
+                    Some(i),
+                    at("frames"),
+                    format!("{what} has an empty frame range ({}..{})", r.start, r.end),
+                ));
+            } else if r.end > source.frame_count {
+                out.push(Diagnostic::error(
+                    Some(i),
+                    at("frames"),
+                    format!(
+                        "{what} frames {}..{} extend past the {}-frame deck",
+                        r.start, r.end, source.frame_count
+                    ),
+                ));
+            }
+            let motion = obj.enter().map_or(0, |e| e.frames) + obj.exit().map_or(0, |x| x.frames);
+            if r.start < r.end && motion > r.end - r.start {
+                out.push(Diagnostic::warning(
+                    Some(i),
+                    at(if obj.enter().is_some() { "enter" } else { "exit" }),
+                    format!(
+                        "{what} spends {motion} frames entering/exiting but is only shown for {}",
+                        r.end - r.start
+                    ),
+                ));
+            }
+        }
+
+        let driven = obj.coordinates().into_iter().filter_map(|(field, c)| match c {
+            Coordinate::Animated { anim, .. } => Some((field, *anim)),
+            Coordinate::Fixed(_) => None,
+        });
+        let driven = driven.chain(obj.scalars().into_iter().filter_map(|(field, s)| match s {
+            Scalar::Animated { anim, .. } => Some((field, *anim)),
+            Scalar::Fixed(_) => None,
+        }));
+        for (field, anim) in driven {
+            match anims.span(anim) {
+                None => out.push(Diagnostic::error(
+                    Some(i),
+                    at(field),
+                    format!("{what} is animated by animation {anim}, which does not exist"),
+                )),
+                Some(span) if span.start >= span.end => out.push(Diagnostic::error(
+                    Some(i),
+                    at(field),
+                    format!(
+                        "{what} is animated by animation {anim}, whose span {}..{} is empty",
+                        span.start, span.end
+                    ),
+                )),
+                Some(_) => {}
+            }
+        }
+
+        match obj {
+            SceneObject::Group(g) => {
+                for (k, &m) in g.members.iter().enumerate() {
+                    if m >= source.objects.len() {
+                        out.push(Diagnostic::error(
+                            Some(i),
+                            at(&format!("members[{k}]")),
+                            format!("group member {m} is out of bounds ({} objects)", source.objects.len()),
+                        ));
+                    } else if m == i {
+                        out.push(Diagnostic::error(
+                            Some(i),
+                            at(&format!("members[{k}]")),
+                            "a group cannot contain itself".into(),
+                        ));
+                    }
+                }
+            }
+            SceneObject::Table(t) => {
+                let sum: f64 = t.col_widths.iter().sum();
+                if t.col_widths.is_empty() {
+                    out.push(Diagnostic::error(Some(i), at("col_widths"), "table has no columns".into()));
+                } else if (sum - 1.0).abs() > COL_WIDTH_TOLERANCE {
+                    out.push(Diagnostic::warning(
+                        Some(i),
+                        at("col_widths"),
+                        format!("table column widths sum to {sum:.3}, expected ~1.0"),
+                    ));
+                }
+            }
+            SceneObject::Toc(_) if sections.is_empty() => out.push(Diagnostic::warning(
+                Some(i),
+                format!("objects[{i}]"),
+                "toc has no sections to list (name frames in the deck's `sections`)".into(),
+            )),
+            SceneObject::PieChart(p) if p.segments.iter().all(|s| s.value <= 0.0) => out.push(Diagnostic::warning(
+                Some(i),
+                at("segments"),
+                "pie chart has no positive values to draw".into(),
+            )),
+            SceneObject::BackgroundEffect(b) if !(b.density > 0.0 && b.density <= 1.0) => out.push(Diagnostic::warning(
+                Some(i),
+                at("density"),
+                format!("background effect density {} is outside 0–1 (0 draws nothing)", b.density),
+            )),
+            SceneObject::Keycap(k) if k.keys.iter().all(|key| key.trim().is_empty()) => out.push(Diagnostic::warning(
+                Some(i),
+                at("keys"),
+                "keycap has no keys".into(),
+            )),
+            SceneObject::Connector(c) if c.points.len() < 2 => out.push(Diagnostic::warning(
+                Some(i),
+                at("points"),
+                "connector has fewer than two points, so it draws no line".into(),
+            )),
+            SceneObject::Spinner(s) if s.glyphs.chars().count() < 2 => out.push(Diagnostic::warning(
+                Some(i),
+                at("glyphs"),
+                "spinner has fewer than two glyphs, so it never turns".into(),
+            )),
+            SceneObject::Marquee(m) if !m.speed.is_finite() => out.push(Diagnostic::error(
+                Some(i),
+                at("speed"),
+                format!("marquee speed {} is not a finite number", m.speed),
+            )),
+            SceneObject::Marquee(m) if m.speed == 0.0 => out.push(Diagnostic::warning(
+                Some(i),
+                at("speed"),
+                "marquee speed is 0, so its text never moves".into(),
+            )),
+            SceneObject::ScatterPlot(p) => {
+                let ((x0, x1), (y0, y1)) = (p.x_range(), p.y_range());
+                let outside =
+                    p.points.iter().filter(|[x, y]| !(x0..=x1).contains(x) || !(y0..=y1).contains(y)).count();
+                if p.points.is_empty() {
+                    out.push(Diagnostic::warning(Some(i), at("points"), "scatter plot has no points".into()));
+                } else if outside > 0 {
+                    out.push(Diagnostic::warning(
+                        Some(i),
+                        at("points"),
+                        format!("{outside} of {} points lie outside the plot's axes and are not drawn", p.points.len()),
+                    ));
+                }
+            }
+            SceneObject::Gantt(g) => {
+                for (k, t) in g.tasks.iter().enumerate() {
+                    if t.end < t.start {
+                        out.push(Diagnostic::error(
+                            Some(i),
+                            at(&format!("tasks[{k}]")),
+                            format!("gantt task ends ({}) before it starts ({})", t.end, t.start),
+                        ));
+                    }
+                }
+            }
+            SceneObject::Diff(d) => {
+                let (hunks, frames) = (d.hunks().len(), d.frames.end.saturating_sub(d.frames.start));
+                if hunks == 0 {
+                    out.push(Diagnostic::warning(Some(i), format!("objects[{i}]"), "diff shows no changes".into()));
+                } else if d.reveal && hunks > frames {
+                    out.push(Diagnostic::warning(
+                        Some(i),
+                        at("frames"),
+                        format!("diff reveals {hunks} hunks over only {frames} frames"),
+                    ));
+                }
+            }
+            SceneObject::Heatmap(h) => {
+                let cols = h.values.iter().map(Vec::len).max().unwrap_or(0);
+                if cols == 0 {
+                    out.push(Diagnostic::error(Some(i), at("values"), "heatmap has no values".into()));
+                }
+                if h.row_labels.len() > h.values.len() {
+                    out.push(Diagnostic::warning(
+                        Some(i),
+                        at("row_labels"),
+                        format!("heatmap has {} row labels for {} rows", h.row_labels.len(), h.values.len()),
+                    ));
+                }
+                if h.col_labels.len() > cols {
+                    out.push(Diagnostic::warning(
+                        Some(i),
+                        at("col_labels"),
+                        format!("heatmap has {} column labels for {cols} columns", h.col_labels.len()),
+                    ));
+                }
+            }
+            SceneObject::Cast(c) => match Recording::parse(&c.cast).map(|r| r.duration()) {
+                Err(e) => out.push(Diagnostic::error(Some(i), at("cast"), format!("cast does not parse: {e:#}"))),
+                Ok(duration) => {
+                    let end = c.end.unwrap_or(duration);
+                    if c.start > end {
+                        out.push(Diagnostic::error(
+                            Some(i),
+                            at("start"),
+                            format!("cast starts at {}s, after it ends at {end}s", c.start),
+                        ));
+                    }
+                }
+            },
+            SceneObject::Instance(inst) => {
+                if let Err(e) = inst.expand(&source.components) {
+                    let field = if source.components.contains_key(&inst.component) { "args" } else { "component" };
+                    out.push(Diagnostic::error(Some(i), at(field), e));
+                }
+            }
+            _ => {}
+        }
+
+        out.extend(undefined.iter().filter(|d| d.object == Some(i)).cloned());
+
+        if draws_only_outside(source, obj, &anims, &sections) {
+            out.push(Diagnostic::warning(
+                Some(i),
+                format!("objects[{i}]"),
+                format!("{what} lies entirely outside the {}x{} canvas", source.width, source.height),
+            ));
+        }
+    }
+
+    if let Err(e) = source.validate_loops() {
+        out.push(Diagnostic::error(None, "objects".into(), e));
+    }
+    out
+}
+
+/// [`Engine::lint`]'s findings as diagnostics, which `bs validate` prints
+/// after [`validate`]'s: a `deny` rule's are errors, the rest warnings. Each names its rule, so it can be set in the deck's
+/// `lint` map; findings about no object sit at that map's entry.
+pub fn lint(source: &SourcePresentation) -> Vec<Diagnostic> {
+    Engine::lint(source)
+        .into_iter()
+        .map(|l| {
+            let rule = l.rule.as_str();
+            let path = match l.object {
+                Some(i) => format!("objects[{i}]"),
+                None => format!("lint.{rule}"),
+            };
+            let on = if l.frames.is_empty() { String::new() } else { format!(" on frames {}", frame_ranges(&l.frames)) };
+            Diagnostic {
+                severity: if l.level == LintLevel::Deny { Severity::Error } else { Severity::Warning },
+                object: l.object,
+                frame: l.frames.first().copied(),
+                path,
+                message: format!("{}{on} ({rule})", l.message),
+            }
+        })
+        .collect()
+}
+
+/// True when `obj` draws something on at least one frame but never a single
+/// cell inside the canvas — it is invisible for its whole life.
+fn draws_only_outside(
+    source: &SourcePresentation,
+    obj: &SceneObject,
+    anims: &AnimSpans,
+    sections: &[(usize, String)],
+) -> bool {
+    let Some(range) = obj.declared_frame_range() else {
+        return false;
+    };
+    let mut drew = false;
+    let mut ops = Vec::new();
+    for frame in range.start..range.end.min(source.frame_count) {
+        ops.clear();
+        let ctx = ResolveCtx {
+            frame,
+            canvas_width: source.width,
+            canvas_height: source.height,
+            frame_count: source.frame_count,
+            anims,
+            sections,
+        };
+        obj.resolve(&ctx, &mut ops);
+        if ops.iter().any(|op| op.x < source.width && op.y < source.height) {
+            return false;
+        }
+        drew |= !ops.is_empty();
+    }
+    drew
+}
+
+/// Warnings for `${name}` text that the deck's `vars` don't define (drawn
+/// as written). Also printed by `bs compile`.
+pub fn undefined_vars(source: &SourcePresentation) -> Vec<Diagnostic> {
+    source
+        .undefined_vars()
+        .into_iter()
+        .map(|(i, path, name)| {
+            Diagnostic::warning(
+                Some(i),
+                format!("objects[{i}].{path}"),
+                format!("`${{{name}}}` is not defined in `vars`"),
+            )
+        })
+        .collect()
+}
+
+/// Compile-time warnings for objects whose frames run past `frame_count` —
+/// the frames beyond it are silently dropped, usually because the field
+/// drifted from the objects. ([`validate`] reports these as errors.)
+pub fn past_the_end(source: &SourcePresentation) -> Vec<Diagnostic> {
+    source
+        .objects
+        .iter()
+        .enumerate()
+        .filter_map(|(i, obj)| {
+            let r = obj.declared_frame_range()?;
+            (r.start < r.end && r.end > source.frame_count).then(|| {
+                Diagnostic::warning(
+                    Some(i),
+                    format!("objects[{i}].frames"),
+                    format!(
+                        "{} frames {}..{} run past the {}-frame deck and are cut off (omit `frame_count` to derive it)",
+                        obj.kind(),
+                        r.start,
+                        r.end,
+                        source.frame_count
+                    ),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Compile-time warnings for objects the renderer clipped (see
+/// [`Engine::compile_reporting`](crate::engine::Engine::compile_reporting)),
+/// listing the affected frames as half-open ranges (`frames 3..5, 9..10`).
+pub fn out_of_bounds(source: &SourcePresentation, clipped: &[OutOfBounds]) -> Vec<Diagnostic> {
+    clipped
+        .iter()
+        .filter_map(|c| {
+            let obj = source.objects.get(c.object)?;
+            Some(Diagnostic::warning(
+                Some(c.object),
+                format!("objects[{}]", c.object),
+                format!(
+                    "{} draws outside the {}x{} canvas on frames {}",
+                    obj.kind(),
+                    source.width,
+                    source.height,
+                    frame_ranges(&c.frames),
+                ),
+            ))
+        })
+        .collect()
+}
+
+/// Collapse ascending frame indices into comma-separated half-open ranges.
+fn frame_ranges(frames: &[usize]) -> String {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &f in frames {
+        match ranges.last_mut() {
+            Some((_, end)) if *end == f => *end = f + 1,
+            _ => ranges.push((f, f + 1)),
+        }
+    }
+    ranges.iter().map(|(s, e)| format!("{s}..{e}")).collect::<Vec<_>>().join(", ")
+}
+
+/// Print `diagnostics` to stderr as `path:line: severity: json-path: message`,
+/// taking object lines from the authored `json`.
+pub fn print_diagnostics(path: &str, json: &str, diagnostics: &[Diagnostic]) {
+    let lines = object_lines(json);
+    for d in diagnostics {
+        match d.object.and_then(|i| lines.get(i)) {
+            Some(line) => eprintln!("{path}:{line}: {}: {}: {}", d.severity, d.path, d.message),
+            None => eprintln!("{path}: {}: {}: {}", d.severity, d.path, d.message),
+        }
+    }
+}
+
+/// The 1-based line on which each element of the top-level `objects` array
+/// starts in `json`, in order. Used to point diagnostics at the authored text;
+/// returns whatever it found so far on malformed input (callers only use it
+/// after the file already parsed).
+pub fn object_lines(json: &str) -> Vec<usize> {
+    let mut scan = Scanner { bytes: json.as_bytes(), pos: 0, line: 1 };
+    let mut lines = Vec::new();
+    scan.skip_ws();
+    if scan.peek() != Some(b'{') {
+        return lines;
+    }
+    scan.pos += 1;
+    loop {
+        scan.skip_ws();
+        if scan.peek() != Some(b'"') {
+            return lines;
+        }
+        let key = scan.string();
+        scan.skip_ws();
+        if scan.peek() != Some(b':') {
+            return lines;
+        }
+        scan.pos += 1;
+        scan.skip_ws();
+        if key == "objects" && scan.peek() == Some(b'[') {
+            scan.pos += 1;
+            loop {
+                scan.skip_ws();
+                match scan.peek() {
+                    Some(b']') | None => break,
+                    Some(b',') => scan.pos += 1,
+                    Some(_) => {
+                        lines.push(scan.line);
+                        scan.value();
+                    }
+                }
+            }
+            return lines;
+        }
+        scan.value();
+        scan.skip_ws();
+        match scan.peek() {
+            Some(b',') => scan.pos += 1,
+            _ => return lines,
+        }
+    }
+}
+
+/// Just enough of a JSON tokenizer to skip values while counting lines.
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    line: usize,
+}
+
+impl Scanner<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Option<u8> {
+        let b = self.peek()?;
+        self.pos += 1;
+        if b == b'\n' {
+            self.line += 1;
+        }
+        Some(b)
+    }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\r' | b'\n')) {
+            self.bump();
+        }
+    }
+
+    /// Consume a string literal (cursor on the opening quote); returns its raw
+    /// contents (escapes left as-is — only used to compare plain keys).
+    fn string(&mut self) -> String {
+        self.bump();
+        let start = self.pos;
+        while let Some(b) = self.bump() {
+            match b {
+                b'\\' => {
+                    self.bump();
+                }
+                b'"' => return String::from_utf8_lossy(&self.bytes[start..self.pos - 1]).into_owned(),
+                _ => {}
+            }
+        }
+        String::new()
+    }
+
+    /// Consume one value of any kind.
+    fn value(&mut self) {
+        match self.peek() {
+            Some(b'"') => {
+                self.string();
+            }
+            Some(b'{' | b'[') => {
+                let mut depth = 0usize;
+                while let Some(b) = self.peek() {
+                    match b {
+                        b'"' => {
+                            self.string();
+                            continue;
+                        }
+                        b'{' | b'[' => depth += 1,
+                        b'}' | b']' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                self.bump();
+                                return;
+                            }
+                        }
+                        _ => {}
+                    }
+                    self.bump();
+                }
+            }
+            _ => {
+                while let Some(b) = self.peek() {
+                    if matches!(b, b',' | b'}' | b']' | b' ' | b'\t' | b'\r' | b'\n') {
+                        break;
+                    }
+                    self.bump();
+                }
+            }
+        }
+    }
+}
+
+/// Validate the source file at `path`, printing each finding as
+/// `path:line: severity: objects[i].field: message`. Fails when the file
+/// doesn't parse or any finding is an error. `levels` — the user's and
+/// project's lint config — apply under the deck's own.
+pub fn validate_file(path: &str, levels: &BTreeMap<LintRule, LintLevel>) -> Result<()> {
+    let json = crate::pipeline::read_input(path)?;
+    let mut source = crate::migrate::source_from_str(&json).with_context(|| format!("Failed to parse {path}"))?;
+    for (rule, level) in levels {
+        source.lint.entry(*rule).or_insert(*level);
+    }
+
+    let diagnostics = match source.lower() {
+        Ok(()) => {
+            let mut diagnostics = validate(&source);
+            diagnostics.extend(lint(&source));
+            diagnostics
+        }
+        Err(e) => vec![Diagnostic::error(None, "slides".into(), e)],
+    };
+    print_diagnostics(path, &json, &diagnostics);
+
+    let errors = diagnostics.iter().filter(|d| d.severity == Severity::Error).count();
+    let warnings = diagnostics.len() - errors;
+    if errors > 0 {
+        bail!("{path}: {errors} error(s), {warnings} warning(s)");
+    }
+    eprintln!("{path}: OK ({warnings} warning(s))");
+    Ok(())
+}
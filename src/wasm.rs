@@ -0,0 +1,111 @@
+//! JavaScript bindings for browser playback (feature `wasm`).
+//!
+//! Build with `wasm-pack build --no-default-features --features wasm`. The
+//! bindings cover the terminal-free half of the pipeline: [`compile`] turns
+//! source JSON into compiled JSON, and a [`Deck`] (from either form) hands out
+//! frames as text. [`Deck::screen`] and [`Frames`] produce escape sequences
+//! ready for a web terminal emulator such as xterm.js — cursor home, then the
+//! rows joined with `\r\n` — so writing one frame after another replays the
+//! deck. Timing, loops and commands stay with the page.
+
+use wasm_bindgen::prelude::*;
+
+use crate::pipeline;
+use crate::renderer::ansi::grid_to_ansi;
+use crate::renderer::Renderer;
+use crate::types::PlayablePresentation;
+
+fn js_error(e: anyhow::Error) -> JsError {
+    JsError::new(&format!("{e:#}"))
+}
+
+/// Compile source JSON into compiled (playable) JSON.
+#[wasm_bindgen]
+pub fn compile(source_json: &str) -> Result<String, JsError> {
+    let deck = pipeline::parse_deck(source_json, "source").map_err(js_error)?;
+    serde_json::to_string(&deck.playable).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// A compiled deck, loaded from source or compiled JSON.
+#[wasm_bindgen]
+pub struct Deck {
+    presentation: PlayablePresentation,
+}
+
+#[wasm_bindgen]
+impl Deck {
+    #[wasm_bindgen(constructor)]
+    pub fn new(json: &str) -> Result<Deck, JsError> {
+        let deck = pipeline::parse_deck(json, "deck").map_err(js_error)?;
+        Ok(Deck { presentation: deck.playable })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn width(&self) -> u16 {
+        self.presentation.contract.width
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn height(&self) -> u16 {
+        self.presentation.contract.height
+    }
+
+    #[wasm_bindgen(getter, js_name = frameCount)]
+    pub fn frame_count(&self) -> usize {
+        self.presentation.frames.len()
+    }
+
+    /// Frame `n` (0-based, clamped) as ANSI-styled rows joined with `\n`.
+    #[wasm_bindgen(js_name = frameAnsi)]
+    pub fn frame_ansi(&self, n: usize) -> String {
+        Renderer::frame_to_ansi(&self.presentation, n)
+    }
+
+    /// Frame `n` (0-based, clamped) as plain rows joined with `\n`.
+    #[wasm_bindgen(js_name = frameText)]
+    pub fn frame_text(&self, n: usize) -> String {
+        Renderer::frame_to_plain_text(&self.presentation, n)
+    }
+
+    /// Frame `n` as a full-screen repaint for a web terminal.
+    pub fn screen(&self, n: usize) -> String {
+        screen(&grid_to_ansi(&self.presentation.grid_at(n)))
+    }
+
+    /// Iterate the frames in order as web-terminal repaints.
+    pub fn frames(&self) -> Frames {
+        Frames { presentation: self.presentation.clone(), next: 0 }
+    }
+}
+
+/// Frame iterator over a [`Deck`]: `next()` returns the next frame's repaint,
+/// or `undefined` past the last frame.
+#[wasm_bindgen]
+pub struct Frames {
+    presentation: PlayablePresentation,
+    next: usize,
+}
+
+#[wasm_bindgen]
+impl Frames {
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<String> {
+        if self.next >= self.presentation.frames.len() {
+            return None;
+        }
+        let out = screen(&Renderer::frame_to_ansi(&self.presentation, self.next));
+        self.next += 1;
+        Some(out)
+    }
+
+    /// Index of the frame the next call to `next()` returns.
+    #[wasm_bindgen(getter)]
+    pub fn position(&self) -> usize {
+        self.next
+    }
+}
+
+/// Cursor home, then the rows with terminal line endings.
+fn screen(ansi: &str) -> String {
+    format!("\x1b[H{}", ansi.replace('\n', "\r\n"))
+}
@@ -0,0 +1,153 @@
+//! ratatui widget for embedding playback in another TUI (feature `ratatui`).
+//!
+//! [`PresentationWidget`] paints one frame of a compiled deck into a ratatui
+//! `Buffer`, top-left aligned in the given area and clipped to it. As a plain
+//! `Widget` it draws the frame it was built with; as a `StatefulWidget` it draws
+//! [`PresentationState::frame`] and caches the reconstructed grid, so stepping
+//! forward one frame at a time applies a single diff instead of replaying the
+//! whole deck. Colours use the same palette indices the player emits.
+//!
+//! Play-time behaviours (commands, loops, auto-advance) stay with the host: the
+//! widget only shows frames.
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color as RColor, Modifier, Style as RStyle};
+use ratatui::widgets::{StatefulWidget, Widget};
+
+use crate::types::{Cell, Color, Frame, PlayablePresentation, Style};
+
+/// A frame of `presentation`, ready to render.
+#[derive(Debug, Clone, Copy)]
+pub struct PresentationWidget<'a> {
+    presentation: &'a PlayablePresentation,
+    frame: usize,
+}
+
+impl<'a> PresentationWidget<'a> {
+    /// Show frame 0 (stateless use; see [`Self::frame`]).
+    pub fn new(presentation: &'a PlayablePresentation) -> Self {
+        PresentationWidget { presentation, frame: 0 }
+    }
+
+    /// Show frame `frame` (0-based, clamped to the last frame) when rendered
+    /// as a plain `Widget`. Ignored by the stateful render.
+    pub fn frame(mut self, frame: usize) -> Self {
+        self.frame = frame;
+        self
+    }
+}
+
+/// Playback position for the stateful widget, plus the cached grid of the
+/// last rendered frame.
+#[derive(Debug, Clone, Default)]
+pub struct PresentationState {
+    /// Frame to render (0-based; clamped at render time).
+    pub frame: usize,
+    cache: Option<(usize, Vec<Vec<Cell>>)>,
+}
+
+impl PresentationState {
+    pub fn new(frame: usize) -> Self {
+        PresentationState { frame, cache: None }
+    }
+
+    /// Advance one frame (clamped when rendered).
+    pub fn next(&mut self) {
+        self.frame = self.frame.saturating_add(1);
+    }
+
+    /// Go back one frame.
+    pub fn prev(&mut self) {
+        self.frame = self.frame.saturating_sub(1);
+    }
+
+    /// The grid at `frame`, reusing the cache when it holds this frame or the
+    /// one before it.
+    fn grid(&mut self, p: &PlayablePresentation, frame: usize) -> &[Vec<Cell>] {
+        let grid = match self.cache.take() {
+            Some((f, grid)) if f == frame => grid,
+            Some((f, mut grid)) if f + 1 == frame => {
+                match &p.frames[frame] {
+                    Frame::Full { cells } => grid = cells.clone(),
+                    Frame::Diff { changes } => {
+                        for c in changes {
+                            let (x, y) = (c.x as usize, c.y as usize);
+                            if y < grid.len() && x < grid[y].len() {
+                                grid[y][x] = c.cell.clone();
+                            }
+                        }
+                    }
+                }
+                grid
+            }
+            _ => p.grid_at(frame),
+        };
+        &self.cache.insert((frame, grid)).1
+    }
+}
+
+fn last_frame(p: &PlayablePresentation) -> usize {
+    p.frames.len().saturating_sub(1)
+}
+
+impl Widget for PresentationWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let grid = self.presentation.grid_at(self.frame.min(last_frame(self.presentation)));
+        paint(&grid, area, buf);
+    }
+}
+
+impl StatefulWidget for PresentationWidget<'_> {
+    type State = PresentationState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut PresentationState) {
+        if self.presentation.frames.is_empty() {
+            return;
+        }
+        state.frame = state.frame.min(last_frame(self.presentation));
+        let frame = state.frame;
+        paint(state.grid(self.presentation, frame), area, buf);
+    }
+}
+
+fn paint(grid: &[Vec<Cell>], area: Rect, buf: &mut Buffer) {
+    for (y, row) in grid.iter().enumerate().take(area.height as usize) {
+        for (x, cell) in row.iter().enumerate().take(area.width as usize) {
+            if let Some(target) = buf.cell_mut((area.x + x as u16, area.y + y as u16)) {
+                target.reset();
+                if cell.is_continuation() {
+                    // ratatui skips the cell after a wide symbol when drawing.
+                    continue;
+                }
+                target.set_char(cell.ch).set_style(to_ratatui_style(&cell.style));
+            }
+        }
+    }
+}
+
+/// A cell style as a ratatui style (named colours → the player's palette
+/// indices).
+pub fn to_ratatui_style(s: &Style) -> RStyle {
+    let color = |c: &Color| match c {
+        Color::Named(n) => RColor::Indexed(n.ansi_index()),
+        Color::Rgb { r, g, b } => RColor::Rgb(*r, *g, *b),
+    };
+    let mut out = RStyle::default();
+    if let Some(fg) = &s.fg {
+        out = out.fg(color(fg));
+    }
+    if let Some(bg) = &s.bg {
+        out = out.bg(color(bg));
+    }
+    if s.bold {
+        out = out.add_modifier(Modifier::BOLD);
+    }
+    if s.dim {
+        out = out.add_modifier(Modifier::DIM);
+    }
+    if s.underline {
+        out = out.add_modifier(Modifier::UNDERLINED);
+    }
+    out
+}
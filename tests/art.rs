@@ -1,7 +1,10 @@
-//! Tests for the `art` object — pre-made ASCII art rendered verbatim.
+//! Tests for the `art` object — pre-made ASCII art rendered verbatim — and
+//! `ansi_art`, which keeps the colours of its escape sequences.
 
 mod common;
 
+use bs::import::ansi::decode;
+use bs::types::{xterm_palette, Color};
 use common::{char_at, render_json};
 
 #[test]
@@ -64,3 +67,57 @@ fn art_spaces_are_transparent() {
     assert_eq!(char_at(&p, 0, 0, 0), 'X', "transparent space lets the label show through");
     assert_eq!(char_at(&p, 0, 1, 0), 'Y');
 }
+
+#[test]
+fn ansi_art_keeps_each_cells_colours_and_leaves_blanks_transparent() {
+    let p = render_json(
+        r#"{
+            "width": 5, "height": 2, "frame_count": 1,
+            "objects": [
+                { "type": "label", "text": "....",
+                  "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                  "frames": { "start": 0, "end": 1 } },
+                { "type": "ansi_art",
+                  "position": { "x": { "fixed": 1 }, "y": { "fixed": 0 } },
+                  "ansi": "\u001b[1;31mA\u001b[0m \u001b[44m \u001b[0m\nB",
+                  "frames": { "start": 0, "end": 1 }, "z_order": 1 }
+            ]
+        }"#,
+    );
+
+    let grid = p.grid_at(0);
+    assert_eq!(grid[0][1].ch, 'A');
+    assert_eq!(grid[0][1].style.fg, Some(Color::from(xterm_palette()[1])));
+    assert!(grid[0][1].style.bold);
+    assert_eq!(grid[0][2].ch, '.', "an unstyled blank shows what is underneath");
+    assert_eq!(grid[0][3].style.bg, Some(Color::from(xterm_palette()[4])));
+    assert_eq!(grid[1][1].ch, 'B');
+    assert!(grid[1][1].style.is_default(), "the reset carries across lines");
+}
+
+#[test]
+fn ansi_art_wraps_at_its_width() {
+    let p = render_json(
+        r#"{
+            "width": 4, "height": 3, "frame_count": 1,
+            "objects": [
+                { "type": "ansi_art",
+                  "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                  "ansi": "abcde", "width": 3,
+                  "frames": { "start": 0, "end": 1 } }
+            ]
+        }"#,
+    );
+
+    assert_eq!(char_at(&p, 0, 2, 0), 'c');
+    assert_eq!(char_at(&p, 0, 3, 0), ' ');
+    assert_eq!(char_at(&p, 0, 0, 1), 'd');
+    assert_eq!(char_at(&p, 0, 1, 1), 'e');
+}
+
+#[test]
+fn ansi_files_decode_as_cp437_and_stop_at_the_sauce_record() {
+    let bytes = b"\x1b[32m\xdb\xdb\xb0\x1b[0m\n\x1aSAUCE00title";
+    assert_eq!(decode(bytes), "\x1b[32m██░\x1b[0m\n");
+    assert_eq!(decode("já".as_bytes()), "já", "valid UTF-8 is kept as is");
+}
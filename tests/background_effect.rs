@@ -0,0 +1,72 @@
+//! Tests for the `background_effect` object — seeded starfield, matrix rain
+//! and noise drawn dim beneath a slide.
+
+mod common;
+
+use bs::engine::source::{BackgroundEffect, Effect};
+use common::{char_at, frame_lines, object, render_json};
+use serde_json::json;
+
+fn effect(extra: serde_json::Value) -> serde_json::Value {
+    object(json!({ "type": "background_effect", "effect": "starfield", "seed": 7, "density": 0.2,
+                   "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                   "frames": { "start": 0, "end": 3 } }), extra)
+}
+
+fn deck(objects: &[serde_json::Value]) -> String {
+    common::deck(30, 8, 3, objects).to_string()
+}
+
+#[test]
+fn the_same_seed_draws_the_same_effect() {
+    for kind in ["starfield", "matrix", "noise"] {
+        let a = render_json(&deck(&[effect(json!({ "effect": kind }))]));
+        let b = render_json(&deck(&[effect(json!({ "effect": kind }))]));
+        let other = render_json(&deck(&[effect(json!({ "effect": kind, "seed": 8 }))]));
+        for f in 0..3 {
+            assert_eq!(frame_lines(&a, f), frame_lines(&b, f), "{kind} frame {f}");
+        }
+        assert_ne!(frame_lines(&a, 1), frame_lines(&other, 1), "{kind}: another seed differs");
+        assert_ne!(frame_lines(&a, 0), frame_lines(&a, 1), "{kind}: it moves between frames");
+    }
+}
+
+#[test]
+fn it_fills_the_canvas_by_default_and_its_area_when_sized() {
+    let full = render_json(&deck(&[effect(json!({ "density": 1.0 }))]));
+    assert!(frame_lines(&full, 0).iter().all(|l| l.chars().all(|c| c != ' ')), "density 1 lights every cell");
+    let boxed = render_json(&deck(&[effect(json!({ "density": 1.0, "position": { "x": { "fixed": 2 }, "y": { "fixed": 1 } },
+                                                   "width": 3, "height": 2 }))]));
+    let lines = frame_lines(&boxed, 0);
+    assert_eq!(lines[0].trim_end(), "");
+    assert_eq!(lines[1].chars().filter(|&c| c != ' ').count(), 3);
+    assert_eq!(lines[2].chars().filter(|&c| c != ' ').count(), 3);
+    assert_eq!(lines[3].trim_end(), "");
+    let empty = render_json(&deck(&[effect(json!({ "density": 0.0 }))]));
+    assert!(frame_lines(&empty, 0).iter().all(|l| l.trim().is_empty()));
+}
+
+#[test]
+fn it_sits_under_the_slide_drawn_dim() {
+    let label = json!({ "type": "label", "text": "TITLE", "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                        "frames": { "start": 0, "end": 3 } });
+    let p = render_json(&deck(&[label, effect(json!({ "effect": "noise", "density": 1.0 }))]));
+    assert_eq!(&frame_lines(&p, 0)[0][..5], "TITLE");
+    let cell = &p.grid_at(0)[4][10];
+    assert!(cell.style.dim, "noise is dim");
+    assert_ne!(char_at(&p, 0, 10, 4), ' ');
+}
+
+#[test]
+fn matrix_drops_have_one_bright_head_per_column() {
+    let e: BackgroundEffect = serde_json::from_value(effect(json!({ "effect": "matrix", "density": 0.5 }))).unwrap();
+    assert_eq!(e.effect, Effect::Matrix);
+    for t in 0..6 {
+        let cells = e.cells(t, 30, 8);
+        assert!(!cells.is_empty());
+        for col in 0..30 {
+            let heads = cells.iter().filter(|c| c.0 == col && c.3).count();
+            assert!(heads <= 1, "column {col} at {t} has {heads} heads");
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! `builder::Presentation`: the fluent chain produces the same objects the JSON
+//! format would (defaults, frame placement, derived frame count) and renders.
+
+mod common;
+
+use bs::builder::Presentation;
+use bs::engine::source::{Coordinate, SceneObject};
+use bs::types::{Color, NamedColor};
+
+#[test]
+fn objects_land_on_the_current_frame_unless_a_range_is_given() {
+    let s = Presentation::new(20, 5)
+        .label("a")
+        .next_frame()
+        .label("b")
+        .frame(4)
+        .rect(3, 3).appears(1..3)
+        .build();
+    let ranges: Vec<_> =
+        s.objects.iter().map(|o| o.declared_frame_range().map(|r| (r.start, r.end))).collect();
+    assert_eq!(ranges, vec![Some((0, 1)), Some((1, 2)), Some((1, 3))]);
+    // Derived: just past the current frame (4) — later than any range end.
+    assert_eq!(s.frame_count, 5);
+    assert_eq!(Presentation::new(20, 5).label("a").frames(9).build().frame_count, 9);
+}
+
+#[test]
+fn modifiers_apply_to_the_last_object_only() {
+    let s = Presentation::new(20, 5)
+        .label("a").at(3, 2).fg(NamedColor::Red).bold().z(4)
+        .label("b")
+        .title("ignored on a label")
+        .build();
+    let SceneObject::Label(a) = &s.objects[0] else { panic!("expected a label") };
+    assert_eq!(a.position.x, Coordinate::Fixed(3.0));
+    assert_eq!(a.style.fg, Some(Color::Named(NamedColor::Red)));
+    assert!(a.style.bold);
+    assert_eq!(a.z_order, 4);
+    let SceneObject::Label(b) = &s.objects[1] else { panic!("expected a label") };
+    assert!(b.style.is_default());
+}
+
+#[test]
+fn move_to_adds_an_animation_and_widens_the_range() {
+    let s = Presentation::new(20, 5).label("*").at(0, 1).move_to(10, 1, 2..6).build();
+    assert_eq!(s.objects.len(), 2);
+    let SceneObject::Animation(anim) = &s.objects[0] else { panic!("expected the animation first") };
+    let SceneObject::Label(l) = &s.objects[1] else { panic!("expected the label last") };
+    assert_eq!(l.position.x, Coordinate::Animated { from: 0, to: 10, anim: anim.id });
+    assert_eq!(l.position.y, Coordinate::Fixed(1.0));
+    assert_eq!((l.frames.start, l.frames.end), (0, 6));
+    assert_eq!(s.frame_count, 6);
+}
+
+#[test]
+fn a_built_deck_round_trips_through_json_and_renders() {
+    let s = Presentation::new(12, 3)
+        .label("hi").at(1, 0)
+        .hline(4).at(2, 2).ch('=')
+        .build();
+    let json = serde_json::to_string(&s).unwrap();
+    let p = common::render_json(&json);
+    assert_eq!(common::frame_lines(&p, 0), vec![" hi         ", "            ", "  ====      "]);
+}
@@ -0,0 +1,50 @@
+//! Tests for the `cast` object — a snippet of an asciinema recording replayed
+//! at compile time and played across the object's frame range.
+
+mod common;
+
+use common::{frame_lines, render_json};
+use serde_json::json;
+
+/// `$ ls` at 0.5s, its output on the next line at 1s, more at 2s.
+const CAST: &str = "{\"version\": 2, \"width\": 6, \"height\": 2}\n\
+    [0.5, \"o\", \"$ ls\"]\n\
+    [1.0, \"o\", \"\\r\\na b\"]\n\
+    [2.0, \"o\", \" c\"]\n";
+
+fn deck(frame_count: usize, cast: serde_json::Value) -> String {
+    json!({
+        "width": 8, "height": 2, "frame_count": frame_count,
+        "objects": [
+            { "type": "label", "text": "########",
+              "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+              "frames": { "start": 0, "end": frame_count } },
+            cast,
+        ]
+    })
+    .to_string()
+}
+
+#[test]
+fn cast_plays_from_start_to_end_across_its_frames_over_what_is_underneath() {
+    let p = render_json(&deck(
+        3,
+        json!({ "type": "cast", "cast": CAST, "start": 0.5,
+                "position": { "x": { "fixed": 1 }, "y": { "fixed": 0 } },
+                "frames": { "start": 0, "end": 3 }, "z_order": 1 }),
+    ));
+    assert_eq!(frame_lines(&p, 0), ["#$ ls  #", "        "], "blanks paint over the label");
+    assert_eq!(frame_lines(&p, 1), ["#$ ls  #", " a b    "], "1.25s: halfway");
+    assert_eq!(frame_lines(&p, 2), ["#$ ls  #", " a b c  "], "the end of the recording");
+}
+
+#[test]
+fn cast_is_cropped_and_a_single_frame_shows_the_end_of_the_snippet() {
+    let p = render_json(&deck(
+        1,
+        json!({ "type": "cast", "cast": CAST, "end": 1.0, "width": 3, "height": 1,
+                "position": { "x": { "fixed": 0 }, "y": { "fixed": 1 } },
+                "frames": { "start": 0, "end": 1 } }),
+    ));
+    assert_eq!(frame_lines(&p, 0), ["########", "$ l     "]);
+}
@@ -0,0 +1,227 @@
+//! The `bs` command line (clap): per-subcommand help, typo suggestions, flag
+//! validation, compile warnings, and the global `--no-color` flag.
+
+#![cfg(feature = "terminal")]
+
+use std::process::{Command, Output, Stdio};
+
+fn bs(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_bs")).args(args).env_remove("NO_COLOR").output().unwrap()
+}
+
+fn stderr(out: &Output) -> String {
+    String::from_utf8_lossy(&out.stderr).into_owned()
+}
+
+#[test]
+fn every_subcommand_has_help() {
+    for sub in ["compile", "play", "edit", "validate", "info", "frame", "diff", "export", "import", "migrate", "trim", "serve"] {
+        let out = bs(&[sub, "--help"]);
+        assert!(out.status.success(), "{sub} --help failed");
+        assert!(String::from_utf8_lossy(&out.stdout).contains("Usage: bs "), "{sub} --help");
+    }
+}
+
+#[test]
+fn a_mistyped_subcommand_gets_a_suggestion() {
+    let out = bs(&["compiel", "a.json", "b.json"]);
+    assert!(!out.status.success());
+    assert!(stderr(&out).contains("similar subcommand exists: 'compile'"));
+}
+
+#[test]
+fn export_rejects_unknown_formats_and_non_positive_fps() {
+    let out = bs(&["export", "pptx", "in.json", "out"]);
+    assert!(!out.status.success());
+    assert!(stderr(&out).contains("unknown export format `pptx`"));
+    let out = bs(&["export", "gif", "in.json", "out.gif", "--fps", "0"]);
+    assert!(!out.status.success());
+    assert!(stderr(&out).contains("must be a positive number"));
+}
+
+#[test]
+fn no_color_makes_frame_dumps_plain() {
+    let dir = std::env::temp_dir().join(format!("bs-cli-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("deck.json");
+    std::fs::write(
+        &path,
+        r#"{"width":2,"height":1,"frame_count":1,"objects":[
+            {"type":"label","text":"hi","position":{"x":{"fixed":0},"y":{"fixed":0}},"style":{"fg":"red"},"frames":{"start":0,"end":1}}
+        ]}"#,
+    )
+    .unwrap();
+    let path = path.to_str().unwrap();
+    let colored = bs(&["frame", path, "1"]);
+    assert!(String::from_utf8_lossy(&colored.stdout).contains('\x1b'));
+    let plain = bs(&["--no-color", "frame", path, "1"]);
+    assert_eq!(String::from_utf8_lossy(&plain.stdout), "hi\n");
+    // An explicit --ansi still wins.
+    let forced = bs(&["frame", path, "1", "--ansi", "--no-color"]);
+    assert!(String::from_utf8_lossy(&forced.stdout).contains('\x1b'));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn a_frame_dump_into_a_closed_pipe_exits_cleanly() {
+    let dir = std::env::temp_dir().join(format!("bs-cli-pipe-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("deck.json");
+    // Bigger than a pipe's buffer, so the write is still going when the reader leaves.
+    std::fs::write(&path, r#"{"width":1000,"height":100,"frame_count":1,"objects":[]}"#).unwrap();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_bs"))
+        .args(["frame", path.to_str().unwrap(), "1", "--plain"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    drop(child.stdout.take());
+    let out = child.wait_with_output().unwrap();
+    assert!(out.status.success(), "{}", stderr(&out));
+    assert_eq!(stderr(&out), "");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn compile_writes_keyframes_at_the_requested_interval() {
+    let dir = std::env::temp_dir().join(format!("bs-cli-kf-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("deck.json");
+    std::fs::write(&path, r#"{"width":2,"height":1,"frame_count":5,"objects":[]}"#).unwrap();
+    let out = bs(&["compile", path.to_str().unwrap(), "-", "--keyframe-interval", "2"]);
+    assert!(out.status.success(), "{}", stderr(&out));
+    let compiled: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    let types: Vec<&str> =
+        compiled["frames"].as_array().unwrap().iter().map(|f| f["type"].as_str().unwrap()).collect();
+    assert_eq!(types, ["full", "diff", "full", "diff", "full"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn compile_prints_clipping_warnings_at_the_object_line() {
+    let dir = std::env::temp_dir().join(format!("bs-cli-clip-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("deck.json");
+    std::fs::write(
+        &path,
+        r#"{"width":2,"height":1,"frame_count":1,"objects":[
+            {"type":"label","text":"hello","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":1}}
+        ]}"#,
+    )
+    .unwrap();
+    let path = path.to_str().unwrap();
+    let out = bs(&["compile", path, "-"]);
+    assert!(out.status.success(), "clipping only warns");
+    assert!(stderr(&out).contains(&format!(
+        "{path}:2: warning: objects[0]: label draws outside the 2x1 canvas on frames 0..1"
+    )));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn compile_can_write_binary_formats_that_readers_autodetect() {
+    let dir = std::env::temp_dir().join(format!("bs-cli-mp-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (src, bin) = (dir.join("deck.json"), dir.join("deck.bin"));
+    std::fs::write(
+        &src,
+        r#"{"width":2,"height":1,"frame_count":1,"objects":[
+            {"type":"label","text":"hi","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":1}}
+        ]}"#,
+    )
+    .unwrap();
+    let (src, bin) = (src.to_str().unwrap(), bin.to_str().unwrap());
+    for format in ["msgpack", "stream"] {
+        let out = bs(&["compile", src, bin, "--format", format]);
+        assert!(out.status.success(), "{}", stderr(&out));
+        let plain = bs(&["frame", bin, "1", "--plain"]);
+        assert_eq!(String::from_utf8_lossy(&plain.stdout), "hi\n", "{format}");
+    }
+    let out = bs(&["compile", src, bin, "--format", "bincode"]);
+    assert!(stderr(&out).contains("unknown compiled format `bincode`"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn compile_set_overrides_the_decks_vars() {
+    let dir = std::env::temp_dir().join(format!("bs-cli-vars-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (src, out_path) = (dir.join("deck.json"), dir.join("out.json"));
+    std::fs::write(
+        &src,
+        r#"{"width":8,"height":1,"frame_count":1,"vars":{"v":"1.0"},"objects":[
+            {"type":"label","text":"v${v}","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":1}}
+        ]}"#,
+    )
+    .unwrap();
+    let (src, out_path) = (src.to_str().unwrap(), out_path.to_str().unwrap());
+    let out = bs(&["compile", src, out_path, "--set", "v=2.1", "--set", "event=Conf"]);
+    assert!(out.status.success(), "{}", stderr(&out));
+    let plain = bs(&["frame", out_path, "1", "--plain"]);
+    assert_eq!(String::from_utf8_lossy(&plain.stdout), "v2.1    \n");
+    let out = bs(&["compile", src, out_path, "--set", "v"]);
+    assert!(stderr(&out).contains("expected KEY=VALUE, got `v`"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn trim_keeps_a_frame_range_and_rebases_objects_and_animations() {
+    let dir = std::env::temp_dir().join(format!("bs-cli-trim-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("deck.json");
+    std::fs::write(
+        &path,
+        r#"{"width":4,"height":1,"frame_count":10,"objects":[
+            {"type":"label","text":"bg","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":10}},
+            {"type":"label","text":"intro","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":2}},
+            {"type":"label","text":"m","position":{"x":{"animated":{"from":0,"to":3,"anim":1}},"y":{"fixed":0}},"frames":{"start":4,"end":7}},
+            {"type":"animation","id":1,"frames":{"start":4,"end":7},"auto_play":true,"delay_ms":100}
+        ]}"#,
+    )
+    .unwrap();
+    let path = path.to_str().unwrap();
+
+    let out = bs(&["trim", path, "-", "4", "8"]);
+    assert!(out.status.success(), "{}", stderr(&out));
+    assert!(stderr(&out).contains("Trimmed 5 frames"), "{}", stderr(&out));
+    let trimmed: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(trimmed["frame_count"], 5);
+    let ranges: Vec<(u64, u64)> = trimmed["objects"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|o| (o["frames"]["start"].as_u64().unwrap(), o["frames"]["end"].as_u64().unwrap()))
+        .collect();
+    assert_eq!(ranges, [(0, 5), (1, 4), (1, 4)], "the intro is gone; the rest start 3 frames earlier");
+
+    let out = bs(&["trim", path, "-", "0", "3"]);
+    assert!(stderr(&out).contains("frames are numbered from 1"), "{}", stderr(&out));
+    let out = bs(&["trim", path, "-", "4", "11"]);
+    assert!(!out.status.success());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn validate_applies_the_project_config_s_lint_levels() {
+    let dir = std::env::temp_dir().join(format!("bs-cli-lint-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("talk")).unwrap();
+    let path = dir.join("talk").join("deck.json");
+    std::fs::write(&path, r#"{"width":4,"height":1,"frame_count":1,"objects":[]}"#).unwrap();
+    let validate = || {
+        // A home of its own, so the user's editor config stays out of it.
+        Command::new(env!("CARGO_BIN_EXE_bs")).args(["validate", path.to_str().unwrap()]).env("HOME", &dir).output().unwrap()
+    };
+    let out = validate();
+    assert!(out.status.success(), "an empty frame only warns by default: {}", stderr(&out));
+    assert!(stderr(&out).contains("(empty_frame)"));
+
+    std::fs::write(dir.join(".bs.json"), r#"{"lint":{"empty_frame":"deny"}}"#).unwrap();
+    let out = validate();
+    assert!(!out.status.success());
+    assert!(stderr(&out).contains("error: lint.empty_frame"), "{}", stderr(&out));
+
+    std::fs::write(&path, r#"{"width":4,"height":1,"frame_count":1,"objects":[],"lint":{"empty_frame":"allow"}}"#).unwrap();
+    let out = validate();
+    assert!(out.status.success() && !stderr(&out).contains("empty_frame"), "the deck's own level wins: {}", stderr(&out));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,38 @@
+//! Tests for the `clock` object — a placeholder in the compiled frames and
+//! the `ClockRegion` sidecar the player paints the time of day from.
+
+mod common;
+
+use bs::types::{ClockRegion, Style};
+use common::frame_lines;
+
+#[test]
+fn clock_compiles_to_a_placeholder_and_a_sidecar() {
+    let source = serde_json::from_str(
+        r#"{"width":12,"height":2,"frame_count":3,"objects":[
+            {"type":"clock","position":{"x":{"fixed":2},"y":{"fixed":1}},"frames":{"start":1,"end":3}},
+            {"type":"clock","seconds":true,"layer":"notes",
+             "position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":3}}
+        ],"layers":[{"name":"notes","visible":false}]}"#,
+    )
+    .unwrap();
+    let p = bs::pipeline::compile(&source).unwrap();
+    assert_eq!(frame_lines(&p, 0), ["            ", "            "]);
+    assert_eq!(frame_lines(&p, 1), ["            ", "  --:--     "]);
+    assert_eq!(
+        p.clocks,
+        [ClockRegion { start_frame: 1, end_frame: 3, x: 2, y: 1, seconds: false, style: Style::default() }],
+        "a clock on a hidden layer has none"
+    );
+}
+
+#[test]
+fn clock_text_is_the_time_of_day_at_the_offset() {
+    let clock = |seconds| ClockRegion { start_frame: 0, end_frame: 1, x: 0, y: 0, seconds, style: Style::default() };
+    // 2024-01-01T23:59:30Z.
+    let t = 1_704_153_570;
+    assert_eq!(clock(false).text(t, 0), "23:59");
+    assert_eq!(clock(true).text(t, 0), "23:59:30");
+    assert_eq!(clock(true).text(t, 3600), "00:59:30", "past midnight");
+    assert_eq!(clock(false).text(t, -5 * 3600 - 1800), "18:29");
+}
@@ -0,0 +1,97 @@
+//! Terminal colour capability: `ColorDepth` detection from `COLORTERM`,
+//! terminfo's `colors` and the `TERM` name, the compiled-terminfo reader, and
+//! the player quantizing RGB to the palette when truecolor is unavailable.
+
+#![cfg(feature = "terminal")]
+
+mod common;
+
+use bs::player::{parse_terminfo_colors, ColorDepth, Player};
+
+#[test]
+fn depth_prefers_colorterm_then_terminfo_then_the_term_name() {
+    let d = ColorDepth::from_env;
+    assert_eq!(d(Some("truecolor"), Some("xterm"), Some(8)), ColorDepth::TrueColor);
+    assert_eq!(d(Some("24bit"), None, None), ColorDepth::TrueColor);
+    assert_eq!(d(None, Some("xterm-256color"), Some(256)), ColorDepth::Ansi256);
+    assert_eq!(d(None, Some("xterm-direct"), Some(1 << 24)), ColorDepth::TrueColor);
+    assert_eq!(d(Some("yes"), Some("xterm-256color"), Some(8)), ColorDepth::Ansi16, "terminfo wins over the name");
+    assert_eq!(d(None, Some("foot-direct"), None), ColorDepth::TrueColor);
+    assert_eq!(d(None, Some("screen-256color"), None), ColorDepth::Ansi256);
+    assert_eq!(d(None, Some("vt100"), None), ColorDepth::Ansi16);
+    assert_eq!(d(None, None, None), ColorDepth::Ansi16);
+}
+
+/// A compiled terminfo entry with `numbers` in the given width (2 = legacy,
+/// 4 = ncurses' 32-bit format); an odd-length names section forces padding.
+fn terminfo(width: usize, numbers: &[i32]) -> Vec<u8> {
+    let magic: u16 = if width == 2 { 0o432 } else { 0o1036 };
+    let names = b"x|test\0";
+    let bools = [1u8, 0];
+    let mut out = Vec::new();
+    for v in [magic, names.len() as u16, bools.len() as u16, numbers.len() as u16, 0, 0] {
+        out.extend_from_slice(&v.to_le_bytes());
+    }
+    out.extend_from_slice(names);
+    out.extend_from_slice(&bools);
+    out.push(0); // pad to an even offset
+    for &n in numbers {
+        out.extend_from_slice(&n.to_le_bytes()[..width]);
+    }
+    out
+}
+
+#[test]
+fn terminfo_colors_is_read_from_both_number_formats() {
+    let mut nums = vec![-1; 15];
+    nums[13] = 256;
+    assert_eq!(parse_terminfo_colors(&terminfo(2, &nums)), Some(256));
+    nums[13] = 1 << 24;
+    assert_eq!(parse_terminfo_colors(&terminfo(4, &nums)), Some(1 << 24));
+    nums[13] = -1;
+    assert_eq!(parse_terminfo_colors(&terminfo(2, &nums)), None, "absent");
+    assert_eq!(parse_terminfo_colors(&terminfo(2, &[80, 0, 24])), None, "too few numbers");
+    assert_eq!(parse_terminfo_colors(b"not terminfo"), None);
+}
+
+#[test]
+fn the_player_quantizes_rgb_to_the_detected_depth() {
+    let deck = common::render_json(
+        r#"{"width":1,"height":1,"frame_count":1,"objects":[
+            {"type":"label","text":"x","position":{"x":{"fixed":0},"y":{"fixed":0}},"style":{"fg":{"r":255,"g":128,"b":0}},"frames":{"start":0,"end":1}}
+        ]}"#,
+    );
+    let screen = |depth| {
+        let mut out = Vec::new();
+        Player::new(deck.clone()).with_color_depth(depth).render_to(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    };
+    assert!(screen(ColorDepth::TrueColor).contains("38;2;255;128;0m"));
+    // Cube entry (5, 2, 0); the system colours are never picked at 256.
+    assert!(screen(ColorDepth::Ansi256).contains("38;5;208m"));
+    assert!(screen(ColorDepth::Ansi16).contains("38;5;3m"));
+    assert!(!screen(ColorDepth::Ansi16).contains("38;2;"));
+}
+
+#[test]
+fn monochrome_keeps_meaning_in_bold_and_dim_only() {
+    use bs::player::monochrome_style;
+    use bs::types::{Color, NamedColor, Style};
+    use crossterm::style::Attribute;
+
+    let attrs = |s: Style, background: Option<&Color>| {
+        let cs = monochrome_style(&s, background);
+        assert_eq!((cs.foreground_color, cs.background_color), (None, None));
+        (cs.attributes.has(Attribute::Bold), cs.attributes.has(Attribute::Dim))
+    };
+    let fg = |c: Color| Style { fg: Some(c), ..Style::default() };
+    let navy = Color::Rgb { r: 0, g: 0, b: 90 };
+    let red = Color::Named(NamedColor::Red);
+
+    assert_eq!(attrs(fg(red.clone()), None), (false, false), "bright colours read as plain text");
+    assert_eq!(attrs(fg(navy.clone()), None), (false, true), "dark ones as dim");
+    assert_eq!(attrs(Style { bold: true, ..fg(navy) }, None), (true, false));
+    let highlight = Style { bg: Some(red.clone()), ..Style::default() };
+    assert_eq!(attrs(highlight.clone(), None), (true, false), "a highlight turns bold");
+    assert_eq!(attrs(highlight, Some(&red)), (false, false), "but not the deck background");
+}
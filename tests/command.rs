@@ -10,7 +10,9 @@ mod common;
 use common::{char_at, render_json};
 
 use bs::engine::source::SourcePresentation;
+#[cfg(feature = "terminal")]
 use bs::player::layout_output;
+#[cfg(feature = "terminal")]
 use std::process::Command as ProcCommand;
 
 /// The command under test, per the request: `echo "<a fairly long line>"`.
@@ -103,6 +105,7 @@ fn border_can_be_disabled_for_a_frameless_region() {
     assert_eq!((r.status_x, r.status_y), (5 + 40 - 1, 3));
 }
 
+#[cfg(feature = "terminal")]
 #[test]
 fn command_output_renders_clipped_into_region() {
     let source: SourcePresentation = serde_json::from_str(&source_json()).unwrap();
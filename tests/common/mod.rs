@@ -11,7 +11,8 @@
 
 use bs::engine::{source::SourcePresentation, Engine};
 use bs::renderer::Renderer;
-use bs::types::{PlayablePresentation, TerminalContract};
+use bs::types::PlayablePresentation;
+use serde_json::{json, Value};
 
 /// Parse a source presentation from JSON and run it through the real pipeline.
 ///
@@ -20,11 +21,7 @@ pub fn render_json(json: &str) -> PlayablePresentation {
     let source: SourcePresentation =
         serde_json::from_str(json).expect("test source JSON should parse");
     let scenes = Engine::compile(&source);
-    let contract = TerminalContract {
-        width: source.width,
-        height: source.height,
-    };
-    Renderer::render(&scenes, contract)
+    Renderer::render(&scenes, source.contract())
 }
 
 /// Reconstruct the visible character grid at `frame_index` by replaying the
@@ -43,3 +40,18 @@ pub fn frame_lines(p: &PlayablePresentation, frame_index: usize) -> Vec<String>
 pub fn char_at(p: &PlayablePresentation, frame_index: usize, x: usize, y: usize) -> char {
     frame_lines(p, frame_index)[y].chars().nth(x).unwrap()
 }
+
+/// An object fixture: `base` (a test file's defaults for its object type) with
+/// the test's `overrides` laid over its top-level keys.
+pub fn object(mut base: Value, overrides: Value) -> Value {
+    let overrides = overrides.as_object().expect("overrides should be a JSON object").clone();
+    base.as_object_mut().expect("a fixture should be a JSON object").extend(overrides);
+    base
+}
+
+/// A `width` × `height` deck of `frame_count` frames holding `objects`, as a
+/// JSON value so a test can lay more deck fields (e.g. `vars`) over it with
+/// [`object`] before rendering its `to_string()`.
+pub fn deck(width: u16, height: u16, frame_count: usize, objects: &[Value]) -> Value {
+    json!({ "width": width, "height": height, "frame_count": frame_count, "objects": objects })
+}
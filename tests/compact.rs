@@ -0,0 +1,178 @@
+//! The compiled file's compact form: a style table plus run-length encoded
+//! full and diff frames, decoded back to identical grids on load, with the
+//! older verbatim `cells` / `changes` form still accepted, and the same
+//! document in MessagePack (`bs compile --format msgpack`) and as a seekable
+//! stream file (`--format stream`).
+
+mod common;
+
+use bs::types::PlayablePresentation;
+
+/// 4x2, 2 frames: a red "==" on frame 0, joined by a red "==" on row 1 in
+/// frame 1.
+const DECK: &str = r#"{"width":4,"height":2,"frame_count":2,"objects":[
+    {"type":"label","text":"==","position":{"x":{"fixed":0},"y":{"fixed":0}},"style":{"fg":"red"},"frames":{"start":0,"end":2}},
+    {"type":"label","text":"==","position":{"x":{"fixed":1},"y":{"fixed":1}},"style":{"fg":"red"},"frames":{"start":1,"end":2}}
+]}"#;
+
+#[test]
+fn frames_serialize_as_style_indexed_runs() {
+    let p = common::render_json(DECK);
+    let json = serde_json::to_value(&p).unwrap();
+    assert_eq!(json["styles"], serde_json::json!([{"fg": "red"}, {}]));
+    assert_eq!(json["frames"][0], serde_json::json!({"type": "full", "runs": [["=", 0, 2], [" ", 1, 6]]}));
+    assert_eq!(json["frames"][1], serde_json::json!({"type": "diff", "runs": [[1, 1, "=", 0, 2]]}));
+}
+
+#[test]
+fn compact_json_round_trips_to_the_same_grids() {
+    let p = common::render_json(DECK);
+    let back: PlayablePresentation = serde_json::from_str(&serde_json::to_string(&p).unwrap()).unwrap();
+    for f in 0..2 {
+        assert_eq!(back.grid_at(f), p.grid_at(f));
+    }
+}
+
+#[test]
+fn verbatim_frames_still_load() {
+    let old = r#"{"contract":{"width":2,"height":1},"frames":[
+        {"type":"full","cells":[[{"ch":"a","style":{"bold":true}},{"ch":" "}]]},
+        {"type":"diff","changes":[{"x":1,"y":0,"cell":{"ch":"b"}}]}
+    ]}"#;
+    let p: PlayablePresentation = serde_json::from_str(old).unwrap();
+    assert_eq!(common::frame_lines(&p, 1), vec!["ab"]);
+    assert!(p.grid_at(1)[0][0].style.bold);
+}
+
+#[test]
+fn malformed_runs_are_rejected() {
+    let bad_style = r#"{"contract":{"width":1,"height":1},"frames":[{"type":"full","runs":[["a",3,1]]}]}"#;
+    let err = serde_json::from_str::<PlayablePresentation>(bad_style).unwrap_err();
+    assert!(err.to_string().contains("style index 3"));
+    let short = r#"{"contract":{"width":2,"height":1},"styles":[{}],"frames":[{"type":"full","runs":[["a",0,1]]}]}"#;
+    let err = serde_json::from_str::<PlayablePresentation>(short).unwrap_err();
+    assert!(err.to_string().contains("runs cover 1 cells, canvas is 2x1"));
+}
+
+#[test]
+fn oversized_run_counts_are_rejected_before_they_are_expanded() {
+    let huge = format!(r#"{{"contract":{{"width":2,"height":1}},"styles":[{{}}],"frames":[{{"type":"full","runs":[["a",0,1],["b",0,{}]]}}]}}"#, usize::MAX);
+    let err = serde_json::from_str::<PlayablePresentation>(&huge).unwrap_err();
+    assert!(err.to_string().contains("runs cover more than the 2x1 canvas"), "{err}");
+    let long = r#"{"contract":{"width":2,"height":1},"styles":[{}],"frames":[
+        {"type":"full","runs":[["a",0,2]]},
+        {"type":"diff","runs":[[1,0,"b",0,4000000000]]}
+    ]}"#;
+    let err = serde_json::from_str::<PlayablePresentation>(long).unwrap_err();
+    assert!(err.to_string().contains("diff run past column 65535"), "{err}");
+}
+
+#[test]
+fn msgpack_round_trips_and_is_told_apart_from_json() {
+    let p = common::render_json(DECK);
+    let bytes = bs::msgpack::to_msgpack(&p).unwrap();
+    let json = serde_json::to_vec(&p).unwrap();
+    assert!(bs::msgpack::is_msgpack(&bytes) && !bs::msgpack::is_msgpack(&json));
+    assert!(bytes.len() < json.len());
+    for input in [&bytes, &json] {
+        let back = bs::pipeline::parse_playable(input, "deck").unwrap();
+        for f in 0..2 {
+            assert_eq!(back.grid_at(f), p.grid_at(f));
+        }
+    }
+}
+
+#[test]
+fn msgpack_values_use_the_smallest_spec_encoding() {
+    let value = serde_json::json!({"a": [1, -1, 300, "x", null, true, -200]});
+    let mut bytes = Vec::new();
+    bs::msgpack::encode(&value, &mut bytes);
+    assert_eq!(
+        bytes,
+        [0x81, 0xa1, b'a', 0x97, 0x01, 0xff, 0xcd, 0x01, 0x2c, 0xa1, b'x', 0xc0, 0xc3, 0xd1, 0xff, 0x38]
+    );
+    assert_eq!(bs::msgpack::decode(&bytes).unwrap(), value);
+    let err = bs::msgpack::decode(&bytes[..bytes.len() - 1]).unwrap_err();
+    assert!(err.to_string().contains("truncated"));
+}
+
+#[test]
+fn stream_files_seek_frames_from_the_nearest_keyframe() {
+    let source: bs::engine::source::SourcePresentation = serde_json::from_str(DECK).unwrap();
+    let mut p = bs::pipeline::compile_with_keyframes(&source, 0).unwrap();
+    // Six frames: the two drawn ones again, with a keyframe at frame 4.
+    p.frames.extend(p.frames.clone());
+    p.frames.extend(p.frames[2..4].to_vec());
+    let bytes = bs::stream::to_stream(&p).unwrap();
+    assert!(bs::stream::is_stream(&bytes) && bs::pipeline::is_binary(&bytes));
+    assert!(!bs::msgpack::is_msgpack(&bytes));
+
+    let stream = bs::stream::FrameStream::new(std::io::Cursor::new(&bytes)).unwrap();
+    assert_eq!(stream.len(), 6);
+    assert_eq!(stream.skeleton().contract, p.contract);
+    assert!(stream.skeleton().frames.is_empty());
+    for f in [5, 0, 3, 1, 4, 2, 99] {
+        assert_eq!(stream.grid_at(f).unwrap(), p.grid_at(f), "frame {f}");
+    }
+    let back = bs::pipeline::parse_playable(&bytes, "deck").unwrap();
+    assert_eq!(back.frames.len(), 6);
+    assert!(stream.frame(6).unwrap_err().to_string().contains("out of range"));
+
+    let cut = &bytes[..bytes.len() - 3];
+    assert!(bs::stream::FrameStream::new(std::io::Cursor::new(cut)).is_err());
+    let mut newer = bytes.clone();
+    newer[bs::stream::MAGIC.len()] = 9;
+    let err = bs::stream::FrameStream::new(std::io::Cursor::new(&newer)).err().unwrap();
+    assert!(err.to_string().contains("version 9"));
+}
+
+#[test]
+fn stream_index_entries_past_the_frames_are_rejected() {
+    let source: bs::engine::source::SourcePresentation = serde_json::from_str(DECK).unwrap();
+    let bytes = bs::stream::to_stream(&bs::pipeline::compile(&source).unwrap()).unwrap();
+    let header_at = u64::from_le_bytes(bytes[bytes.len() - 8..].try_into().unwrap()) as usize;
+    let header = bs::msgpack::decode(&bytes[header_at..bytes.len() - 8]).unwrap();
+    // An offset that overflows when its length is added, and one that merely
+    // runs into the header.
+    for at in [u64::MAX, header_at as u64 - 1] {
+        let mut header = header.clone();
+        header["index"][0][0] = at.into();
+        let mut out = bytes[..header_at].to_vec();
+        bs::msgpack::encode(&header, &mut out);
+        out.extend_from_slice(&(header_at as u64).to_le_bytes());
+        let err = bs::stream::FrameStream::new(std::io::Cursor::new(&out)).err().unwrap();
+        assert!(err.to_string().contains("runs past the frames"), "{err}");
+    }
+}
+
+#[test]
+fn compiled_files_are_stamped_and_newer_ones_refused() {
+    let p = common::render_json(DECK);
+    let mut json = serde_json::to_value(&p).unwrap();
+    assert_eq!(json["format_version"], bs::types::COMPILED_FORMAT_VERSION);
+    json.as_object_mut().unwrap().remove("format_version");
+    assert!(serde_json::from_value::<PlayablePresentation>(json.clone()).is_ok(), "unversioned files still load");
+
+    json["format_version"] = (bs::types::COMPILED_FORMAT_VERSION + 1).into();
+    let err = serde_json::from_value::<PlayablePresentation>(json.clone()).unwrap_err();
+    assert!(err.to_string().contains("newer than this bs understands"), "{err}");
+    let mut bytes = Vec::new();
+    bs::msgpack::encode(&json, &mut bytes);
+    assert!(bs::pipeline::parse_playable(&bytes, "deck").is_err());
+}
+
+#[test]
+fn metadata_is_compiled_through_and_left_out_when_empty() {
+    let source: bs::engine::source::SourcePresentation = serde_json::from_str(
+        r#"{"width":2,"height":1,"frame_count":1,"objects":[],"metadata":{"title":"Talk","date":"2024"}}"#,
+    )
+    .unwrap();
+    let p = bs::pipeline::compile(&source).unwrap();
+    let json = serde_json::to_value(&p).unwrap();
+    assert_eq!(json["metadata"], serde_json::json!({"title": "Talk", "date": "2024"}));
+    let back = bs::stream::from_stream(&bs::stream::to_stream(&p).unwrap()).unwrap();
+    assert_eq!(back.metadata, p.metadata);
+
+    let json = serde_json::to_value(common::render_json(DECK)).unwrap();
+    assert!(json.get("metadata").is_none());
+}
@@ -0,0 +1,85 @@
+//! Reusable components: `instance` objects stamp out a component's object
+//! templates with their arguments substituted, on the instance's frames.
+
+mod common;
+
+use bs::engine::source::{SceneObject, SourcePresentation};
+use bs::engine::Engine;
+use bs::pipeline;
+use common::{frame_lines, render_json};
+
+/// A "node" component — a title over a subtitle at `(x, y)` — and `objects`.
+fn deck(objects: &str) -> String {
+    format!(
+        r#"{{"width":12,"height":3,"frame_count":2,
+            "components":{{"node":{{
+                "params":{{"title":null,"subtitle":"-","x":0,"y":0}},
+                "objects":[
+                    {{"type":"label","text":"[{{{{title}}}}]","position":{{"x":{{"fixed":"{{{{x}}}}"}},"y":{{"fixed":"{{{{y}}}}"}}}}}},
+                    {{"type":"label","text":"{{{{ subtitle }}}}","position":{{"x":{{"fixed":"{{{{x}}}}"}},"y":{{"fixed":1}}}}}}
+                ]
+            }}}},
+            "objects":[{objects}]}}"#
+    )
+}
+
+#[test]
+fn instances_stamp_out_the_component_with_their_arguments() {
+    let p = render_json(&deck(
+        r#"{"type":"instance","component":"node","args":{"title":"A","subtitle":"one"},"frames":{"start":0,"end":2}},
+           {"type":"instance","component":"node","args":{"title":"B","x":6},"frames":{"start":1,"end":2}}"#,
+    ));
+    assert_eq!(frame_lines(&p, 0), ["[A]         ", "one         ", "            "]);
+    // The second instance keeps the `subtitle` default and only shows on its own frames.
+    assert_eq!(frame_lines(&p, 1), ["[A]   [B]   ", "one   -     ", "            "]);
+}
+
+#[test]
+fn a_placeholder_inside_text_is_spliced_in_as_text() {
+    let json = r#"{"width":10,"height":1,"frame_count":1,
+        "components":{"step":{"params":{"n":null},"objects":[
+            {"type":"label","text":"Step {{n}}/{{n}}","position":{"x":{"fixed":0},"y":{"fixed":0}}}
+        ]}},
+        "objects":[{"type":"instance","component":"step","args":{"n":3},"frames":{"start":0,"end":1}}]}"#;
+    assert_eq!(frame_lines(&render_json(json), 0), ["Step 3/3  "]);
+}
+
+#[test]
+fn a_broken_instance_fails_compilation_and_draws_nothing() {
+    let cases = [
+        (r#""component":"nod","args":{"title":"A"}"#, "there is no component `nod`"),
+        (r#""component":"node","args":{}"#, "needs an argument for `title`"),
+        (r#""component":"node","args":{"title":"A","colour":"red"}"#, "has no parameter `colour`"),
+    ];
+    for (fields, message) in cases {
+        let json = deck(&format!(r#"{{"type":"instance",{fields},"frames":{{"start":0,"end":2}}}}"#));
+        let source: SourcePresentation = serde_json::from_str(&json).unwrap();
+        let err = pipeline::compile(&source).unwrap_err().to_string();
+        assert!(err.contains(message), "{err}");
+        assert!(Engine::compile(&source).iter().all(|scene| scene.ops.is_empty()));
+    }
+
+    let json = r#"{"width":4,"height":1,"frame_count":1,
+        "components":{"c":{"objects":[{"type":"label","text":"{{oops}}","position":{"x":{"fixed":0},"y":{"fixed":0}}}]}},
+        "objects":[{"type":"instance","component":"c","frames":{"start":0,"end":1}}]}"#;
+    let source: SourcePresentation = serde_json::from_str(json).unwrap();
+    let err = pipeline::compile(&source).unwrap_err().to_string();
+    assert!(err.contains("uses `{{oops}}`, which is not a parameter"), "{err}");
+}
+
+#[test]
+fn instances_follow_an_explicit_group_range_and_round_trip() {
+    let json = deck(
+        r#"{"type":"instance","component":"node","args":{"title":"A"},"frames":{"start":0,"end":1}},
+           {"type":"group","members":[0],"frames":{"start":1,"end":2}}"#,
+    );
+    let p = render_json(&json);
+    assert_eq!(frame_lines(&p, 0)[0], "            ");
+    assert_eq!(frame_lines(&p, 1)[0], "[A]         ");
+
+    let source: SourcePresentation = serde_json::from_str(&json).unwrap();
+    let again: SourcePresentation =
+        serde_json::from_str(&serde_json::to_string(&source).unwrap()).unwrap();
+    assert!(again.components.contains_key("node"));
+    assert!(matches!(&again.objects[0], SceneObject::Instance(i) if i.component == "node"));
+}
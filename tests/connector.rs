@@ -0,0 +1,50 @@
+//! Tests for the `connector` object — a polyline drawn in box-drawing
+//! characters, with elbows, tees and crosses where its segments meet.
+
+mod common;
+
+use common::{frame_lines, render_json};
+use serde_json::json;
+
+fn rows(points: serde_json::Value, line: &str) -> Vec<String> {
+    let connector = json!({ "type": "connector", "points": points, "line": line,
+                            "position": { "x": { "fixed": 1 }, "y": { "fixed": 1 } },
+                            "frames": { "start": 0, "end": 1 } });
+    let p = render_json(&common::deck(9, 5, 1, &[connector]).to_string());
+    frame_lines(&p, 0).iter().map(|l| l.trim_end().to_string()).collect()
+}
+
+#[test]
+fn points_are_joined_with_corners_at_each_turn() {
+    let r = rows(json!([[0, 0], [4, 0], [4, 2], [6, 2]]), "single");
+    assert_eq!(r[1], " ────┐");
+    assert_eq!(r[2], "     │");
+    assert_eq!(r[3], "     └──");
+}
+
+#[test]
+fn a_diagonal_pair_is_routed_across_then_down() {
+    let r = rows(json!([[0, 0], [3, 2]]), "rounded");
+    assert_eq!(r[1], " ───╮");
+    assert_eq!(r[2], "    │");
+    assert_eq!(r[3], "    │");
+}
+
+#[test]
+fn branches_meet_in_tees_and_crosses() {
+    // Across, back to the middle, then down through the line and up again.
+    let r = rows(json!([[0, 1], [6, 1], [3, 1], [3, 0], [3, 3]]), "single");
+    assert_eq!(r[1], "    │");
+    assert_eq!(r[2], " ───┼───");
+    assert_eq!(r[4], "    │");
+    let r = rows(json!([[0, 0], [6, 0], [3, 0], [3, 2]]), "single");
+    assert_eq!(r[1], " ───┬───");
+}
+
+#[test]
+fn line_picks_the_box_drawing_set() {
+    let points = json!([[0, 0], [2, 0], [2, 1], [2, 0], [4, 0]]);
+    assert_eq!(rows(points.clone(), "double")[1], " ══╦══");
+    assert_eq!(rows(points.clone(), "heavy")[1], " ━━┳━━");
+    assert_eq!(rows(points, "ascii")[1], " --+--");
+}
@@ -0,0 +1,53 @@
+//! Tests for the `countdown` object — an `mm:ss` counter baked into each of
+//! its frames, and the auto-advance sidecar an `auto_play` countdown adds.
+
+mod common;
+
+use common::{frame_lines, render_json};
+use serde_json::json;
+
+fn deck(frame_count: usize, countdown: serde_json::Value) -> String {
+    common::deck(30, 5, frame_count, &[countdown]).to_string()
+}
+
+#[test]
+fn countdown_takes_a_step_off_each_frame_and_holds_at_zero() {
+    let p = render_json(&deck(
+        4,
+        json!({ "type": "countdown", "from_secs": 65, "step_secs": 30,
+                "position": { "x": { "fixed": 1 }, "y": { "fixed": 0 } },
+                "frames": { "start": 0, "end": 4 } }),
+    ));
+    let first_line = |f| frame_lines(&p, f)[0].trim_end().to_string();
+    assert_eq!(first_line(0), " 01:05");
+    assert_eq!(first_line(1), " 00:35");
+    assert_eq!(first_line(2), " 00:05");
+    assert_eq!(first_line(3), " 00:00", "held at zero");
+}
+
+#[test]
+fn big_countdown_draws_in_the_header_font() {
+    let p = render_json(&deck(
+        1,
+        json!({ "type": "countdown", "from_secs": 0, "big": true,
+                "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                "frames": { "start": 0, "end": 1 } }),
+    ));
+    let lines = frame_lines(&p, 0);
+    assert_eq!(lines[0].trim_end(), " ███   ███     ███   ███");
+    assert_eq!(lines[1].trim_end(), "█   █ █   █ █ █   █ █   █");
+}
+
+#[test]
+fn auto_play_countdown_advances_every_step_and_stops_on_its_last_frame() {
+    let source = serde_json::from_str(&deck(
+        5,
+        json!({ "type": "countdown", "from_secs": 120, "step_secs": 60, "auto_play": true,
+                "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                "frames": { "start": 1, "end": 4 } }),
+    ))
+    .unwrap();
+    let p = bs::pipeline::compile(&source).unwrap();
+    let regions: Vec<_> = p.auto_advances.iter().map(|r| (r.start_frame, r.end_frame, r.delay_ms)).collect();
+    assert_eq!(regions, [(1, 3, 60_000)], "frame 3 (00:00) is left for the presenter");
+}
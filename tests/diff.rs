@@ -0,0 +1,91 @@
+//! `bs diff`: object pairing (unchanged / moved / added / removed) between two
+//! sources, per-frame cell differences between compiled decks, and the
+//! side-by-side frame render.
+
+mod common;
+
+use bs::diff::{diff_decks, diff_objects, format_diff, side_by_side, FrameDiff, ObjectChange};
+use bs::engine::source::SourcePresentation;
+
+fn source(json: &str) -> SourcePresentation {
+    serde_json::from_str(json).expect("source JSON should parse")
+}
+
+/// A 6x2, 2-frame deck holding the given object JSON snippets.
+fn deck_json(objects: &[&str]) -> String {
+    format!(r#"{{"width":6,"height":2,"frame_count":2,"objects":[{}]}}"#, objects.join(","))
+}
+
+fn label(text: &str, x: u16, start: usize) -> String {
+    format!(
+        r#"{{"type":"label","text":"{text}","position":{{"x":{{"fixed":{x}}},"y":{{"fixed":0}}}},"frames":{{"start":{start},"end":2}}}}"#
+    )
+}
+
+#[test]
+fn identical_decks_have_no_differences() {
+    let json = deck_json(&[&label("hi", 0, 0)]);
+    let (s, p) = (source(&json), common::render_json(&json));
+    let d = diff_decks(&p, &p, Some((&s, &s)));
+    assert!(d.is_empty());
+    assert_eq!(format_diff(&d), "no differences\n");
+}
+
+#[test]
+fn objects_are_paired_as_moved_removed_and_added() {
+    let a = source(&deck_json(&[&label("hi", 0, 0), &label("gone", 0, 0), &label("same", 0, 1)]));
+    let b = source(&deck_json(&[&label("same", 0, 1), &label("hi", 3, 0), &label("new", 0, 0)]));
+    assert_eq!(
+        diff_objects(&a, &b),
+        vec![
+            ObjectChange::Removed { index: 1, kind: "label" },
+            ObjectChange::Added { index: 2, kind: "label" },
+            ObjectChange::Moved {
+                from: 0,
+                to: 1,
+                kind: "label",
+                changes: vec![("position.x", "0".into(), "3".into())],
+            },
+        ]
+    );
+}
+
+#[test]
+fn frame_differences_count_changed_cells_per_frame() {
+    // Same text, but the second deck only shows it from frame 1 on.
+    let a = common::render_json(&deck_json(&[&label("hi", 0, 0)]));
+    let b = common::render_json(&deck_json(&[&label("hi", 0, 1)]));
+    let d = diff_decks(&a, &b, None);
+    assert_eq!(d.objects, None);
+    assert_eq!(d.frames, vec![FrameDiff { frame: 0, cells: 2 }]);
+    assert!(format_diff(&d).contains("frames: 1 of 2 differ"));
+}
+
+#[test]
+fn differing_canvas_sizes_count_cells_outside_the_overlap() {
+    let a = common::render_json(&deck_json(&[]));
+    let b = common::render_json(r#"{"width":7,"height":2,"frame_count":2,"objects":[]}"#);
+    let d = diff_decks(&a, &b, None);
+    assert_eq!(d.canvas, Some(((6, 2), (7, 2))));
+    assert_eq!(d.frames[0].cells, 2);
+}
+
+#[test]
+fn side_by_side_marks_differing_rows() {
+    let a = common::render_json(&deck_json(&[&label("hi", 0, 0)]));
+    let b = common::render_json(&deck_json(&[&label("ho", 0, 0)]));
+    let out = side_by_side(&a.grid_at(0), &b.grid_at(0));
+    assert_eq!(out, "* hi     │ ho    \n         │       \n");
+}
+
+#[test]
+fn frame_differences_replay_diff_frames_and_count_a_longer_deck_s_extra_frames() {
+    // Four frames of `a` against three of `b`: the label moves in `a` only
+    // from frame 2, and `a`'s last frame has no counterpart.
+    let moving = r#"{"type":"label","text":"hi","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":2}},
+        {"type":"label","text":"hi","position":{"x":{"fixed":3},"y":{"fixed":0}},"frames":{"start":2,"end":4}}"#;
+    let a = common::render_json(&format!(r#"{{"width":6,"height":2,"frame_count":4,"objects":[{moving}]}}"#));
+    let b = common::render_json(&format!(r#"{{"width":6,"height":2,"frame_count":3,"objects":[{}]}}"#, label("hi", 0, 0).replace(r#""end":2"#, r#""end":3"#)));
+    let d = diff_decks(&a, &b, None);
+    assert_eq!(d.frames, vec![FrameDiff { frame: 2, cells: 4 }, FrameDiff { frame: 3, cells: 12 }]);
+}
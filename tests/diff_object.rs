@@ -0,0 +1,65 @@
+//! Tests for the `diff` object — a unified diff of `before`/`after` texts (or
+//! a given diff) with `+`/`-` gutters in green/red, revealed hunk by hunk.
+
+mod common;
+
+use bs::engine::source::{Diff, DiffLineKind};
+use bs::types::{Color, NamedColor};
+use common::{frame_lines, object, render_json};
+use serde_json::json;
+
+fn diff(extra: serde_json::Value) -> serde_json::Value {
+    object(json!({ "type": "diff", "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                   "frames": { "start": 0, "end": 2 } }), extra)
+}
+
+fn deck(diff: serde_json::Value) -> String {
+    common::deck(24, 12, 2, &[diff]).to_string()
+}
+
+#[test]
+fn diff_of_before_and_after_marks_added_and_removed_lines() {
+    let p = render_json(&deck(diff(json!({ "before": "a\nb\nc", "after": "a\nB\nc\nd" }))));
+    let lines: Vec<String> = frame_lines(&p, 0).iter().map(|l| l.trim_end().to_string()).collect();
+    assert_eq!(lines[..6], ["@@ -1,3 +1,4 @@", "  a", "- b", "+ B", "  c", "+ d"]);
+    assert_eq!(p.grid_at(0)[2][0].style.fg, Some(Color::Named(NamedColor::Red)));
+    assert_eq!(p.grid_at(0)[3][2].style.fg, Some(Color::Named(NamedColor::Green)));
+    assert!(p.grid_at(0)[0][0].style.dim, "hunk headers are dim");
+    assert_eq!(p.grid_at(0)[1][2].style.fg, None);
+}
+
+#[test]
+fn distant_changes_split_into_hunks_with_context() {
+    let before: String = (1..=12).map(|n| format!("{n}\n")).collect();
+    let after = before.replace("2\n", "two\n").replace("11\n", "eleven\n");
+    let d: Diff = serde_json::from_value(diff(json!({ "before": before, "after": after, "context": 1 }))).unwrap();
+    let hunks = d.hunks();
+    assert_eq!(hunks.len(), 2);
+    assert_eq!(hunks[0][0].text, "@@ -1,3 +1,3 @@");
+    assert_eq!(hunks[1][0].text, "@@ -10,3 +10,3 @@");
+    let d = Diff { context: 4, ..d };
+    assert_eq!(d.hunks().len(), 1, "overlapping context merges the hunks");
+    let d = Diff { context: usize::MAX, ..d };
+    assert_eq!(d.hunks()[0][0].text, "@@ -1,12 +1,12 @@", "a huge context keeps the whole file");
+}
+
+#[test]
+fn a_unified_diff_is_shown_without_its_file_header() {
+    let unified = "--- a/x.rs\n+++ b/x.rs\n@@ -1,2 +1,2 @@\n fn main() {\n-    old();\n+    new();\n\\ No newline at end of file\n";
+    let d: Diff = serde_json::from_value(diff(json!({ "diff": unified }))).unwrap();
+    let kinds: Vec<DiffLineKind> = d.hunks().concat().iter().map(|l| l.kind).collect();
+    use DiffLineKind::*;
+    assert_eq!(kinds, [Hunk, Context, Removed, Added]);
+    assert_eq!(d.hunks()[0][3].text, "    new();");
+}
+
+#[test]
+fn reveal_shows_one_more_hunk_per_frame_keeping_the_layout() {
+    let unified = "@@ -1 +1 @@\n-a\n+b\n@@ -9 +9 @@\n-y\n+z\n";
+    let p = render_json(&deck(diff(json!({ "diff": unified, "reveal": true }))));
+    let (first, second) = (frame_lines(&p, 0), frame_lines(&p, 1));
+    assert_eq!(first[2].trim_end(), "+ b");
+    assert!(first[3].trim().is_empty() && first[5].trim().is_empty(), "the second hunk is still hidden");
+    assert_eq!(second[3].trim_end(), "@@ -9 +9 @@");
+    assert_eq!(second[5].trim_end(), "+ z");
+}
@@ -0,0 +1,177 @@
+//! `bs export`: the shared option parsing and per-frame output paths, and the
+//! cast / SVG / HTML / GIF / PDF / text encoders over a compiled deck.
+
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use bs::export::{
+    export_file, frame_paths, to_cast, CastRecorder, to_gif, to_html, to_pdf, to_svg, to_text, ExportFormat, ExportOptions,
+};
+
+/// 6x2 deck, 2 frames: a red "hi" on both frames, "<b>" only on frame 1.
+const DECK: &str = r#"{"width":6,"height":2,"frame_count":2,"objects":[
+    {"type":"label","text":"hi","position":{"x":{"fixed":0},"y":{"fixed":0}},"style":{"fg":"red"},"frames":{"start":0,"end":2}},
+    {"type":"label","text":"<b>","position":{"x":{"fixed":0},"y":{"fixed":1}},"frames":{"start":1,"end":2}}
+]}"#;
+
+#[test]
+fn formats_parse_by_name_and_options_default_sensibly() {
+    assert_eq!("svg".parse::<ExportFormat>().unwrap(), ExportFormat::Svg);
+    assert!("pptx".parse::<ExportFormat>().is_err());
+    let opts = ExportOptions::default();
+    assert_eq!((opts.fps, opts.font.as_str(), opts.scale), (1.0, "monospace", 1.0));
+    assert_eq!(ExportOptions { fps: 4.0, ..opts }.frame_secs(), 0.25);
+}
+
+#[test]
+fn frame_paths_number_multi_frame_outputs_only() {
+    assert_eq!(frame_paths(Path::new("out/deck.svg"), 1), vec![PathBuf::from("out/deck.svg")]);
+    assert_eq!(
+        frame_paths(Path::new("out/deck.svg"), 2),
+        vec![PathBuf::from("out/deck-001.svg"), PathBuf::from("out/deck-002.svg")]
+    );
+    assert_eq!(frame_paths(Path::new("d.svg"), 1200)[1199], PathBuf::from("d-1200.svg"));
+}
+
+#[test]
+fn cast_has_a_v2_header_and_one_timed_event_per_frame() {
+    let p = common::render_json(DECK);
+    let cast = to_cast(&p, &ExportOptions { fps: 4.0, ..Default::default() });
+    let lines: Vec<serde_json::Value> =
+        cast.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+    assert_eq!(lines[0], serde_json::json!({"version": 2, "width": 6, "height": 2}));
+    assert_eq!(lines.len(), 3);
+    assert_eq!((lines[1][0].as_f64(), lines[2][0].as_f64()), (Some(0.0), Some(0.25)));
+    // Frame 0 paints the red label with the same palette index the player uses.
+    assert!(lines[1][2].as_str().unwrap().contains("\x1b[0;38;5;9mhi"));
+    // Frame 1 only repaints its diff, starting at the second row.
+    let diff = lines[2][2].as_str().unwrap();
+    assert!(diff.starts_with("\x1b[2;1H"));
+    assert!(!diff.contains("hi"));
+}
+
+#[test]
+fn recorded_cast_stamps_frames_as_shown_and_holds_the_last() {
+    let p = common::render_json(DECK);
+    let mut out = Vec::new();
+    {
+        let mut recorder = CastRecorder::new(&mut out, &p.contract).unwrap();
+        recorder.record_at(0.0, &p.grid_at(0)).unwrap();
+        recorder.record_at(12.5, &p.grid_at(1)).unwrap();
+        recorder.record_at(14.0, &p.grid_at(0)).unwrap();
+    }
+    let parse = |cast: &str| -> Vec<serde_json::Value> {
+        cast.lines().map(|l| serde_json::from_str(l).unwrap()).collect()
+    };
+    let lines = parse(&String::from_utf8(out).unwrap());
+    assert_eq!((lines[0]["version"].as_u64(), lines[0]["width"].as_u64()), (Some(2), Some(6)));
+    assert!(lines[0]["timestamp"].as_u64().unwrap() > 0);
+    let times: Vec<f64> = lines[1..4].iter().map(|l| l[0].as_f64().unwrap()).collect();
+    assert_eq!(times, [0.0, 12.5, 14.0]);
+    // Painted as an export would be: in full, then diffs — going back too.
+    let exported = parse(&to_cast(&p, &ExportOptions::default()));
+    assert_eq!((&lines[1][2], &lines[2][2]), (&exported[1][2], &exported[2][2]));
+    assert!(lines[3][2].as_str().unwrap().starts_with("\x1b[2;1H"));
+    // Dropping the recorder closes the cast with an empty event.
+    assert_eq!((lines.len(), lines[4][2].as_str()), (5, Some("")));
+}
+
+#[test]
+fn svg_and_html_escape_text_and_carry_colors() {
+    let p = common::render_json(DECK);
+    let opts = ExportOptions { font: "Iosevka".into(), ..Default::default() };
+
+    let svg = to_svg(&p.grid_at(1), &opts);
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.contains("font-family=\"Iosevka\""));
+    assert!(svg.contains("fill=\"#ff0000\">hi</text>"));
+    assert!(svg.contains("&lt;b&gt;"));
+
+    let html = to_html(&p, &opts);
+    assert_eq!(html.matches("<pre class=\"frame\"").count(), 2);
+    assert!(html.contains("<span style=\"color:#ff0000\">hi</span>"));
+    assert!(html.contains("&lt;b&gt;"));
+    assert!(html.contains("}, 1000);"));
+}
+
+#[test]
+fn html_and_pdf_carry_the_deck_metadata() {
+    let mut p = common::render_json(DECK);
+    assert!(to_html(&p, &ExportOptions::default()).contains("<title>bs presentation</title>"));
+    assert!(!String::from_utf8_lossy(&to_pdf(&p).unwrap()).contains("/Info"));
+
+    p.metadata.title = Some("A <b> talk".into());
+    p.metadata.author = Some("Ada".into());
+    p.metadata.date = Some("2024-05-01".into());
+    let html = to_html(&p, &ExportOptions::default());
+    assert!(html.contains("<title>A &lt;b&gt; talk</title>"));
+    assert!(html.contains("<meta name=\"author\" content=\"Ada\">"));
+    assert!(html.contains("<meta name=\"date\" content=\"2024-05-01\">"));
+
+    let pdf = String::from_utf8_lossy(&to_pdf(&p).unwrap()).into_owned();
+    assert!(pdf.contains("/Author <FEFF004100640061> "));
+    assert!(pdf.contains("/CreationDate (D:20240501) "));
+    let info = pdf.split("/Info ").nth(1).unwrap();
+    let n: usize = info.split(' ').next().unwrap().parse().unwrap();
+    assert!(pdf.contains(&format!("\n{n} 0 obj\n<< /Title <FEFF")));
+}
+
+#[test]
+fn gif_is_an_animated_gif_at_the_cell_size() {
+    let p = common::render_json(DECK);
+    let bytes = to_gif(&p, &ExportOptions { scale: 2.0, ..Default::default() }).unwrap();
+    assert_eq!(&bytes[..6], b"GIF89a");
+    // Logical screen: 6 cols × 8px × 2, 2 rows × 16px × 2.
+    assert_eq!(u16::from_le_bytes([bytes[6], bytes[7]]), 96);
+    assert_eq!(u16::from_le_bytes([bytes[8], bytes[9]]), 64);
+    // One image descriptor (0x2C after a graphic control block) per frame.
+    assert_eq!(bytes.windows(2).filter(|w| w == &[0x00, 0x2C]).count(), 2);
+}
+
+#[test]
+fn pdf_has_a_page_per_frame_an_embedded_font_and_a_valid_xref() {
+    let p = common::render_json(DECK);
+    let bytes = to_pdf(&p).unwrap();
+    let pdf = String::from_utf8_lossy(&bytes);
+    assert!(pdf.starts_with("%PDF-1.4\n") && pdf.ends_with("%%EOF\n"));
+    assert!(pdf.contains("/Type /Pages /Count 2 "));
+    assert_eq!(pdf.matches("/MediaBox [0 0 842 595]").count(), 2);
+    // One Type 3 font holding h, i, <, b, > in first-use order, mapped back to
+    // Unicode; the red label is set in the red the other exporters use.
+    assert!(pdf.contains("/Subtype /Type3"));
+    assert!(pdf.contains("/LastChar 4 "));
+    assert!(pdf.contains("<00> <0068>\n<01> <0069>"));
+    assert!(pdf.contains("1 0 0 rg 36 297.5 Td <0001> Tj"));
+    // Every xref entry points at its object's header (byte offsets, so work on
+    // the raw bytes: the binary comment line isn't UTF-8).
+    let xref = bytes.windows(6).rposition(|w| w == b"\nxref\n").unwrap() + 1;
+    let table = std::str::from_utf8(&bytes[xref..]).unwrap();
+    for (n, line) in table.lines().skip(3).take_while(|l| l.ends_with(" n ")).enumerate() {
+        let offset: usize = line[..10].parse().unwrap();
+        assert!(bytes[offset..].starts_with(format!("{} 0 obj\n", n + 1).as_bytes()), "object {}", n + 1);
+    }
+    let startxref: usize = table.lines().rev().nth(1).unwrap().parse().unwrap();
+    assert_eq!(startxref, xref);
+}
+
+#[test]
+fn text_is_plain_with_trimmed_rows_or_ansi_for_an_ans_output() {
+    let p = common::render_json(DECK);
+    assert_eq!(to_text(&p.grid_at(1), false), "hi\n<b>\n");
+    let ansi = to_text(&p.grid_at(0), true);
+    assert!(ansi.contains("\x1b[0;38;5;9mhi"));
+
+    let dir = std::env::temp_dir().join(format!("bs-export-text-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let deck = dir.join("deck.json");
+    std::fs::write(&deck, DECK).unwrap();
+    let (deck, opts) = (deck.to_str().unwrap(), ExportOptions::default());
+    export_file(ExportFormat::Text, deck, dir.join("out.txt").to_str().unwrap(), &opts).unwrap();
+    assert_eq!(std::fs::read_to_string(dir.join("out-001.txt")).unwrap(), "hi\n\n");
+    assert_eq!(std::fs::read_to_string(dir.join("out-002.txt")).unwrap(), "hi\n<b>\n");
+    export_file(ExportFormat::Text, deck, dir.join("out.ans").to_str().unwrap(), &opts).unwrap();
+    assert!(std::fs::read_to_string(dir.join("out-002.ans")).unwrap().contains('\x1b'));
+    assert!(export_file(ExportFormat::Text, deck, "-", &opts).is_err(), "two frames need a path");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
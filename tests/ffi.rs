@@ -0,0 +1,53 @@
+//! The `ffi` feature's C ABI, called from Rust: compile to JSON, load a deck,
+//! read cells and frame text, and the NULL/false + `bs_last_error` failure
+//! convention.
+
+#![cfg(feature = "ffi")]
+
+use std::ffi::{CStr, CString};
+
+use bs::ffi::*;
+
+/// 3x1, 2 frames: a bold red "a" on frame 0 only.
+const DECK: &str = r#"{"width":3,"height":1,"frame_count":2,"objects":[
+    {"type":"label","text":"a","position":{"x":{"fixed":0},"y":{"fixed":0}},"style":{"fg":"red","bold":true},"frames":{"start":0,"end":1}}
+]}"#;
+
+/// Take ownership of a returned string.
+fn take(s: *mut std::ffi::c_char) -> String {
+    assert!(!s.is_null());
+    let out = unsafe { CStr::from_ptr(s) }.to_str().unwrap().to_string();
+    unsafe { bs_string_free(s) };
+    out
+}
+
+#[test]
+fn compile_returns_compiled_json_and_reports_errors() {
+    let src = CString::new(DECK).unwrap();
+    let compiled = take(unsafe { bs_compile(src.as_ptr()) });
+    assert!(compiled.contains("\"contract\""));
+
+    let bad = CString::new("{").unwrap();
+    assert!(unsafe { bs_compile(bad.as_ptr()) }.is_null());
+    let err = unsafe { CStr::from_ptr(bs_last_error()) }.to_str().unwrap();
+    assert!(err.contains("Failed to parse source"));
+}
+
+#[test]
+fn deck_exposes_size_cells_and_text() {
+    let src = CString::new(DECK).unwrap();
+    let deck = unsafe { bs_deck_load(src.as_ptr()) };
+    assert!(!deck.is_null());
+    unsafe {
+        assert_eq!((bs_deck_width(deck), bs_deck_height(deck), bs_deck_frame_count(deck)), (3, 1, 2));
+        let mut cell = BsCell { ch: 0, fg: 0, bg: 0, bold: false, dim: false, underline: false };
+        assert!(bs_deck_cell(deck, 0, 0, 0, &mut cell));
+        assert_eq!(cell, BsCell { ch: 'a' as u32, fg: 0xff0000, bg: -1, bold: true, dim: false, underline: false });
+        assert!(bs_deck_cell(deck, 1, 0, 0, &mut cell));
+        assert_eq!((cell.ch, cell.fg), (' ' as u32, -1));
+        assert!(!bs_deck_cell(deck, 0, 3, 0, &mut cell));
+        assert_eq!(take(bs_deck_frame_text(deck, 0, false)), "a  ");
+        assert!(take(bs_deck_frame_text(deck, 0, true)).starts_with("\x1b[0;1;38;5;9ma"));
+        bs_deck_free(deck);
+    }
+}
@@ -0,0 +1,59 @@
+//! Tests for the `file_tree` object — a directory tree built from a flat list
+//! of paths, with glyphs, per-entry styles and folders opening frame by frame.
+
+mod common;
+
+use bs::engine::source::{FileTree, TreeEntry};
+use bs::types::{Color, NamedColor};
+use common::{frame_lines, render_json};
+use serde_json::json;
+
+fn tree(entries: serde_json::Value) -> serde_json::Value {
+    json!({ "type": "file_tree", "entries": entries, "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+            "frames": { "start": 0, "end": 2 } })
+}
+
+fn deck(tree: serde_json::Value) -> String {
+    common::deck(30, 8, 2, &[tree]).to_string()
+}
+
+fn trimmed(lines: Vec<String>) -> Vec<String> {
+    lines.into_iter().map(|l| l.trim_end().to_string()).filter(|l| !l.is_empty()).collect()
+}
+
+#[test]
+fn paths_build_a_tree_in_first_listed_order() {
+    let p = render_json(&deck(tree(json!(["src/main.rs", "src/engine/mod.rs", "README.md", "src/lib.rs", "docs/"]))));
+    assert_eq!(
+        trimmed(frame_lines(&p, 0)),
+        ["▾ src/", "├── · main.rs", "├── ▾ engine/", "│   └── · mod.rs", "└── · lib.rs", "· README.md", "▾ docs/"]
+    );
+}
+
+#[test]
+fn a_folder_opens_after_its_open_at_frames() {
+    let p = render_json(&deck(tree(json!([{ "path": "src/", "open_at": 1 }, "src/a.rs", "b.rs"]))));
+    assert_eq!(trimmed(frame_lines(&p, 0)), ["▸ src/", "· b.rs"]);
+    assert_eq!(trimmed(frame_lines(&p, 1)), ["▾ src/", "└── · a.rs", "· b.rs"]);
+}
+
+#[test]
+fn an_entry_style_colours_its_glyph_and_name_but_not_the_branches() {
+    let p = render_json(&deck(tree(json!(["src/a.rs", { "path": "src/b.rs", "style": { "fg": "green" } }]))));
+    let fg = |row: usize, col: usize| p.grid_at(0)[row][col].style.fg.clone();
+    assert_eq!(fg(2, 0), None, "branch");
+    assert_eq!(fg(2, 4), Some(Color::Named(NamedColor::Green)), "glyph");
+    assert_eq!(fg(2, 6), Some(Color::Named(NamedColor::Green)), "name");
+    assert_eq!(fg(1, 6), None);
+}
+
+#[test]
+fn entries_serialize_as_bare_paths_unless_styled_or_delayed() {
+    let t: FileTree = serde_json::from_value(tree(json!(["a/", { "path": "a/b", "open_at": 0 }, { "path": "c", "open_at": 2 }])))
+        .unwrap();
+    assert_eq!(t.entries[1], TreeEntry { path: "a/b".into(), style: None, open_at: 0 });
+    let back = serde_json::to_value(&t).unwrap();
+    assert_eq!(back["entries"], json!(["a/", "a/b", { "path": "c", "open_at": 2 }]));
+    let glyphless = FileTree { glyphs: false, ..t };
+    assert_eq!(glyphless.lines(0).iter().map(|(l, _)| l.as_str()).collect::<Vec<_>>(), ["a/", "└── b", "c"]);
+}
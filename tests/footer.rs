@@ -0,0 +1,65 @@
+//! Tests for the `footer` object — one templated line on every frame of its
+//! range, with the slide number, frame count and current section filled in.
+
+mod common;
+
+use bs::builder::Presentation;
+use bs::engine::source::TextAlign;
+use common::{frame_lines, render_json};
+use serde_json::json;
+
+#[test]
+fn footer_fills_in_the_page_and_section_on_every_frame() {
+    let p = render_json(
+        &json!({
+            "width": 24, "height": 2, "frame_count": 3,
+            "sections": [null, "Demo"],
+            "objects": [{ "type": "footer", "text": "[{section}] {frame}/{total}",
+                          "position": { "x": { "fixed": 0 }, "y": { "fixed": 1 } },
+                          "frames": { "start": 0, "end": 3 } }],
+        })
+        .to_string(),
+    );
+    let footer = |f| frame_lines(&p, f)[1].trim_end().to_string();
+    assert_eq!(footer(0), "[] 1/3", "no section before the first");
+    assert_eq!(footer(1), "[Demo] 2/3");
+    assert_eq!(footer(2), "[Demo] 3/3");
+}
+
+#[test]
+fn footer_aligns_within_its_row() {
+    let p = Presentation::new(12, 1)
+        .frames(2)
+        .footer("{frame}/{total}")
+        .at(2, 0)
+        .align(TextAlign::Right)
+        .appears(0..2)
+        .compile()
+        .unwrap();
+    assert_eq!(frame_lines(&p, 1)[0], "         2/2", "the row runs to the canvas edge");
+
+    let p = render_json(
+        &json!({
+            "width": 12, "height": 1, "frame_count": 1,
+            "objects": [{ "type": "footer", "text": "ab", "width": 6, "align": "center",
+                          "position": { "x": { "fixed": 1 }, "y": { "fixed": 0 } },
+                          "frames": { "start": 0, "end": 1 } }],
+        })
+        .to_string(),
+    );
+    assert_eq!(frame_lines(&p, 0)[0].trim_end(), "   ab");
+}
+
+#[test]
+fn footer_text_takes_deck_vars() {
+    let p = render_json(
+        &json!({
+            "width": 20, "height": 1, "frame_count": 1, "vars": { "event": "RustConf" },
+            "objects": [{ "type": "footer", "text": "${event} {frame}",
+                          "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                          "frames": { "start": 0, "end": 1 } }],
+        })
+        .to_string(),
+    );
+    assert_eq!(frame_lines(&p, 0)[0].trim_end(), "RustConf 1");
+}
@@ -0,0 +1,54 @@
+//! Single-frame text dumps: `renderer::ansi` grid encoders, the headless
+//! `Renderer::frame_to_*` API, and `bs frame`.
+
+mod common;
+
+#[cfg(feature = "terminal")]
+use std::process::Command;
+
+use bs::renderer::ansi::{grid_to_ansi, grid_to_plain};
+use bs::renderer::Renderer;
+
+/// 4x2, 2 frames: a red "hi" on frame 1 only, "ok" on the second row throughout.
+const DECK: &str = r#"{"width":4,"height":2,"frame_count":2,"objects":[
+    {"type":"label","text":"hi","position":{"x":{"fixed":0},"y":{"fixed":0}},"style":{"fg":"red"},"frames":{"start":0,"end":1}},
+    {"type":"label","text":"ok","position":{"x":{"fixed":1},"y":{"fixed":1}},"frames":{"start":0,"end":2}}
+]}"#;
+
+#[test]
+fn plain_dump_is_characters_only() {
+    let p = common::render_json(DECK);
+    assert_eq!(grid_to_plain(&p.grid_at(0)), "hi  \n ok ");
+    assert_eq!(grid_to_plain(&p.grid_at(1)), "    \n ok ");
+}
+
+#[test]
+fn ansi_dump_styles_runs_and_resets() {
+    let p = common::render_json(DECK);
+    assert_eq!(grid_to_ansi(&p.grid_at(0)), "\x1b[0;38;5;9mhi\x1b[0m  \n ok ");
+}
+
+#[test]
+fn renderer_dumps_a_frame_headlessly_and_clamps_past_the_end() {
+    let p = common::render_json(DECK);
+    assert_eq!(Renderer::frame_to_ansi(&p, 0), grid_to_ansi(&p.grid_at(0)));
+    assert_eq!(Renderer::frame_to_plain_text(&p, 0), "hi  \n ok ");
+    assert_eq!(Renderer::frame_to_plain_text(&p, 9), "    \n ok ");
+}
+
+#[cfg(feature = "terminal")]
+#[test]
+fn frame_subcommand_prints_the_requested_frame() {
+    let dir = std::env::temp_dir().join(format!("bs-frame-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("deck.json");
+    std::fs::write(&path, DECK).unwrap();
+    let run = |args: &[&str]| Command::new(env!("CARGO_BIN_EXE_bs")).arg("frame").arg(&path).args(args).output().unwrap();
+
+    let out = run(&["1", "--plain"]);
+    assert!(out.status.success());
+    assert_eq!(String::from_utf8(out.stdout).unwrap(), "hi  \n ok \n");
+    assert!(!run(&["3"]).status.success());
+    assert!(!run(&["0"]).status.success());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
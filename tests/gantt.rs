@@ -0,0 +1,69 @@
+//! Tests for the `gantt` object — task bars in lanes inside a bordered box,
+//! scaled over the tasks' range, with an optional "today" marker.
+
+mod common;
+
+use bs::engine::source::Gantt;
+use bs::types::{Color, NamedColor};
+use common::{frame_lines, object, render_json};
+use serde_json::json;
+
+fn gantt(extra: serde_json::Value) -> serde_json::Value {
+    object(json!({ "type": "gantt", "width": 24,
+                   "tasks": [
+                       { "label": "design", "start": 0, "end": 5, "lane": "UX" },
+                       { "label": "build", "start": 5, "end": 20, "lane": "Eng", "color": "green" },
+                       { "label": "polish", "start": 15, "end": 20, "lane": "UX" },
+                   ],
+                   "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                   "frames": { "start": 0, "end": 1 } }), extra)
+}
+
+fn deck(gantt: serde_json::Value) -> String {
+    common::deck(30, 6, 1, &[gantt]).to_string()
+}
+
+#[test]
+fn tasks_share_their_lane_row_inside_the_box() {
+    let p = render_json(&deck(gantt(json!({}))));
+    let lines = frame_lines(&p, 0);
+    assert_eq!(lines[0].trim_end(), "┌──────────────────────┐");
+    assert_eq!(lines[1].trim_end(), "│ UX  desi         poli│");
+    assert_eq!(lines[2].trim_end(), "│ Eng     build        │");
+    assert_eq!(lines[3].trim_end(), "└──────────────────────┘");
+}
+
+#[test]
+fn bars_take_their_task_colour_as_background() {
+    let p = render_json(&deck(gantt(json!({}))));
+    let cell = |row: usize, col: usize| p.grid_at(0)[row][col].style.clone();
+    assert_eq!(cell(1, 6).bg, Some(Color::Named(NamedColor::Cyan)), "default bar colour");
+    assert_eq!(cell(1, 6).fg, Some(Color::Named(NamedColor::Black)), "labels are black on the bar");
+    assert_eq!(cell(2, 18).bg, Some(Color::Named(NamedColor::Green)));
+    assert_eq!(cell(1, 12).bg, None, "the gap between a lane's tasks");
+}
+
+#[test]
+fn today_marks_the_top_edge_and_runs_down_the_chart() {
+    let p = render_json(&deck(gantt(json!({ "today": 10 }))));
+    let lines = frame_lines(&p, 0);
+    let col = lines[0].chars().position(|c| c == '▼').expect("marker on the top edge");
+    assert_eq!(col, 15);
+    assert_eq!(lines[1].chars().nth(col), Some('│'));
+    assert_eq!(p.grid_at(0)[2][col].style.fg, Some(Color::Named(NamedColor::Red)));
+    assert_eq!(p.grid_at(0)[2][col].style.bg, Some(Color::Named(NamedColor::Green)), "keeps the bar under it");
+    let p = render_json(&deck(gantt(json!({ "today": 99 }))));
+    assert!(!frame_lines(&p, 0)[0].contains('▼'), "a today outside the range isn't drawn");
+}
+
+#[test]
+fn range_defaults_to_the_tasks_and_unlaned_tasks_get_their_own_rows() {
+    let g: Gantt = serde_json::from_value(gantt(json!({ "max": 40 }))).unwrap();
+    assert_eq!(g.range(), (0.0, 40.0));
+    assert_eq!(g.lanes().iter().map(|(l, t)| (l.as_str(), t.len())).collect::<Vec<_>>(), [("UX", 2), ("Eng", 1)]);
+    let g: Gantt = serde_json::from_value(json!({ "type": "gantt", "tasks": [{ "start": 0, "end": 1 }, { "start": 1, "end": 2 }],
+        "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } }, "frames": { "start": 0, "end": 1 } }))
+    .unwrap();
+    assert_eq!(g.lanes().len(), 2);
+    assert_eq!(g.size(), (40, 4));
+}
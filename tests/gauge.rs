@@ -0,0 +1,96 @@
+//! Tests for the `gauge` object — a semicircular dial lit up to its value,
+//! coloured by threshold, with the value written under it and animatable.
+
+mod common;
+
+use bs::engine::source::{AnimSpans, Gauge, Scalar, Threshold};
+use bs::types::{Color, NamedColor};
+use common::{frame_lines, object, render_json};
+use serde_json::json;
+
+fn deck(gauge: serde_json::Value) -> String {
+    let animation = json!({ "type": "animation", "id": 1, "frames": { "start": 0, "end": 3 } });
+    common::deck(20, 5, 3, &[animation, gauge]).to_string()
+}
+
+fn gauge(extra: serde_json::Value) -> serde_json::Value {
+    object(json!({ "type": "gauge", "value": { "animated": { "from": 10, "to": 90, "anim": 1 } },
+                   "unit": "%", "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                   "frames": { "start": 0, "end": 3 } }), extra)
+}
+
+#[test]
+fn gauge_writes_its_animated_value_under_the_arc() {
+    let p = render_json(&deck(gauge(json!({}))));
+    let bottom = |f| frame_lines(&p, f)[4].clone();
+    assert!(bottom(0).contains(" 10% "), "{:?}", bottom(0));
+    assert!(bottom(1).contains(" 50% "));
+    assert!(bottom(2).contains(" 90% "));
+    assert!(frame_lines(&p, 0)[0].chars().any(|c| ('\u{2801}'..='\u{28ff}').contains(&c)), "braille arc");
+}
+
+#[test]
+fn gauge_lights_the_arc_up_to_its_value_in_the_threshold_colour() {
+    let p = render_json(&deck(gauge(json!({ "thresholds": [
+        { "at": 50, "color": "yellow" }, { "at": 80, "color": "red" },
+    ] }))));
+    // The arc's left end is always lit; its right end only near the top of the range.
+    let (left, right) = (|f| p.grid_at(f)[4][1].style.clone(), |f| p.grid_at(f)[4][18].style.clone());
+    assert!(!left(0).dim && left(0).fg.is_none(), "below every threshold: the style's own colour");
+    assert!(right(0).dim, "the unlit track is dim");
+    assert_eq!(left(1).fg, Some(Color::Named(NamedColor::Yellow)));
+    assert_eq!(left(2).fg, Some(Color::Named(NamedColor::Red)));
+}
+
+#[test]
+fn block_gauge_draws_only_whole_blocks() {
+    let p = render_json(&deck(gauge(json!({ "blocks": true, "show_value": false }))));
+    let drawn: String = frame_lines(&p, 0).concat().chars().filter(|&c| c != ' ').collect();
+    assert!(!drawn.is_empty() && drawn.chars().all(|c| c == '█'), "{drawn:?}");
+}
+
+#[test]
+fn gauge_colour_and_fraction_follow_thresholds_and_range() {
+    let g: Gauge = serde_json::from_value(json!({ "type": "gauge", "value": { "fixed": 0 }, "min": 100, "max": 200,
+        "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } }, "frames": { "start": 0, "end": 1 } }))
+    .unwrap();
+    let f = |v| Gauge::fraction(v, 100.0, 200.0);
+    assert_eq!((f(50.0), f(150.0), f(900.0)), (0.0, 0.5, 1.0));
+    assert_eq!(g.reading(0, &AnimSpans::default()), (100.0, 100.0, 200.0), "the value clamps to the range");
+    let g = Gauge {
+        thresholds: vec![
+            Threshold { at: 180.0, color: Color::Named(NamedColor::Red) },
+            Threshold { at: 120.0, color: Color::Named(NamedColor::Yellow) },
+        ],
+        ..g
+    };
+    assert_eq!(g.color_at(110.0), None);
+    assert_eq!(g.color_at(150.0), Some(Color::Named(NamedColor::Yellow)));
+    assert_eq!(g.color_at(190.0), Some(Color::Named(NamedColor::Red)), "thresholds in any order");
+}
+
+#[test]
+fn gauge_reads_negative_and_fractional_values_and_clamps_them_to_its_range() {
+    let p = render_json(&deck(gauge(json!({ "min": -20, "max": 40, "unit": "°",
+        "value": { "animated": { "from": -5.5, "to": 12.5, "anim": 1 } } }))));
+    let bottom = |f| frame_lines(&p, f)[4].clone();
+    assert!(bottom(0).contains(" -5.5° "), "{:?}", bottom(0));
+    assert!(bottom(1).contains(" 3.5° "), "{:?}", bottom(1));
+    assert!(bottom(2).contains(" 12.5° "), "{:?}", bottom(2));
+    assert!(!p.grid_at(0)[4][1].style.dim, "a reading above a negative min lights the arc");
+
+    let p = render_json(&deck(gauge(json!({ "min": -20, "max": 40, "value": -30 }))));
+    assert!(frame_lines(&p, 0)[4].contains(" -20% "), "below min: shown at min");
+
+    let g: Gauge = serde_json::from_value(gauge(json!({ "min": -20, "max": 40.5, "value": { "fixed": 7 } }))).unwrap();
+    assert_eq!((g.value, g.min, g.max), (Scalar::Fixed(7.0), Scalar::Fixed(-20.0), Scalar::Fixed(40.5)));
+    assert_eq!(serde_json::to_value(&g).unwrap()["min"], json!(-20.0), "a fixed reading saves as a bare number");
+}
+
+#[test]
+fn a_gauge_wider_than_the_canvas_is_drawn_at_the_canvas_width() {
+    let p = render_json(&deck(gauge(json!({ "size": 65535 }))));
+    let lines = frame_lines(&p, 0);
+    assert!(lines[0].chars().any(|c| ('\u{2801}'..='\u{28ff}').contains(&c)), "{lines:?}");
+    assert!(lines[4].contains("10%"), "the value still sits under the middle");
+}
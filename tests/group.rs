@@ -2,6 +2,7 @@
 //! own; its members are ordinary objects that render independently.
 
 mod common;
+use bs::engine::source::{Coordinate, SceneObject, SourcePresentation};
 use common::{char_at, frame_lines, render_json};
 
 #[test]
@@ -88,3 +89,42 @@ fn explicit_group_range_widens_member_frames() {
     assert_eq!(char_at(&p, 0, 0, 0), 'M');
     assert_eq!(char_at(&p, 1, 0, 0), 'M', "group range widens the member's visibility");
 }
+
+#[test]
+fn clone_group_deep_copies_members_and_nested_groups_with_an_offset() {
+    let mut src: SourcePresentation = serde_json::from_str(
+        r#"{
+            "width": 12, "height": 4, "frame_count": 2,
+            "objects": [
+                { "type": "label", "text": "A",
+                  "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                  "frames": { "start": 0, "end": 2 } },
+                { "type": "h_line", "y": { "fixed": 1 }, "x_start": { "fixed": 0 }, "x_end": { "fixed": 2 },
+                  "frames": { "start": 0, "end": 2 } },
+                { "type": "group", "members": [1] },
+                { "type": "animation", "id": 1, "frames": { "start": 0, "end": 2 } },
+                { "type": "label", "text": "m",
+                  "position": { "x": { "animated": { "from": 0, "to": 2, "anim": 1 } }, "y": { "fixed": 2 } },
+                  "frames": { "start": 0, "end": 2 } },
+                { "type": "group", "members": [0, 2, 4] }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(src.clone_group(5, 6, 1), Ok(10));
+    assert_eq!(src.objects.len(), 11);
+    // The copies follow in author order: A, the hline, its group, the mover, the outer group.
+    assert!(matches!(&src.objects[8], SceneObject::Group(g) if g.members == [7]));
+    assert!(matches!(&src.objects[10], SceneObject::Group(g) if g.members == [6, 8, 9]));
+    assert!(matches!(&src.objects[7], SceneObject::HLine(h)
+        if h.x_end == Coordinate::Fixed(8.0) && h.y == Coordinate::Fixed(2.0)));
+    assert!(matches!(&src.objects[9], SceneObject::Label(l)
+        if l.position.x == Coordinate::Animated { from: 6, to: 8, anim: 1 }));
+
+    let p = render_json(&serde_json::to_string(&src).unwrap());
+    assert_eq!(frame_lines(&p, 0), ["A           ", "──    A     ", "m     ──    ", "      m     "]);
+    assert_eq!(char_at(&p, 1, 8, 3), 'm', "the mover's copy rides the animation, offset");
+
+    assert!(src.clone_group(0, 1, 1).is_err(), "only a group clones");
+}
@@ -5,6 +5,7 @@
 //! `["###", " # ", " # ", " # ", "###"]` (3 wide, 5 tall).
 
 mod common;
+use bs::types::{Color, Frame};
 use common::{char_at, render_json};
 
 #[test]
@@ -88,3 +89,25 @@ fn text_word_wraps_when_too_wide_for_the_canvas() {
     assert_eq!(char_at(&p, 0, 0, 6), '█', "B top-left on wrapped line");
     assert_eq!(char_at(&p, 0, 1, 6), '█', "B top bar on wrapped line");
 }
+
+#[test]
+fn gradient_blends_the_foreground_across_the_glyph_columns() {
+    // "II" at x=2 spans columns 2..9; the gradient runs black → red over them.
+    let p = render_json(
+        r#"{
+            "width": 10, "height": 5, "frame_count": 1,
+            "objects": [
+                { "type": "header", "text": "II", "style": { "fg": "green", "bold": true },
+                  "gradient": [{ "r": 0, "g": 0, "b": 0 }, "red"],
+                  "position": { "x": { "fixed": 2 }, "y": { "fixed": 0 } },
+                  "frames": { "start": 0, "end": 1 } }
+            ]
+        }"#,
+    );
+    let Frame::Full { cells } = &p.frames[0] else { panic!("frame 0 must be Full") };
+    let fg = |x: usize| cells[0][x].style.fg.clone();
+    assert_eq!(fg(2), Some(Color::Rgb { r: 0, g: 0, b: 0 }));
+    assert_eq!(fg(6), Some(Color::Rgb { r: 170, g: 0, b: 0 }));
+    assert_eq!(fg(8), Some(Color::Rgb { r: 255, g: 0, b: 0 }));
+    assert!(cells[0][8].style.bold, "the rest of the style is kept");
+}
@@ -0,0 +1,49 @@
+//! Tests for the `heatmap` object — a grid of values drawn as cells whose
+//! background blends from `low` to `high`, with optional row/column labels.
+
+mod common;
+
+use bs::engine::source::Heatmap;
+use bs::types::Color;
+use common::{frame_lines, object, render_json};
+use serde_json::json;
+
+fn deck(heatmap: serde_json::Value) -> String {
+    common::deck(20, 4, 1, &[heatmap]).to_string()
+}
+
+fn heatmap(extra: serde_json::Value) -> serde_json::Value {
+    object(json!({ "type": "heatmap", "values": [[0, 5, 10], [10, 5]],
+                   "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                   "frames": { "start": 0, "end": 1 } }), extra)
+}
+
+#[test]
+fn heatmap_cells_blend_from_low_to_high_across_the_data_range() {
+    let p = render_json(&deck(heatmap(json!({ "low": { "r": 0, "g": 0, "b": 200 }, "high": { "r": 200, "g": 0, "b": 0 } }))));
+    let bg = |row: usize, col: usize| p.grid_at(0)[row][col].style.bg.clone();
+    assert_eq!(bg(0, 0), Some(Color::Rgb { r: 0, g: 0, b: 200 }));
+    assert_eq!(bg(0, 2), Some(Color::Rgb { r: 0, g: 0, b: 200 }), "cells are three columns wide");
+    assert_eq!(bg(0, 3), Some(Color::Rgb { r: 100, g: 0, b: 100 }));
+    assert_eq!(bg(0, 6), Some(Color::Rgb { r: 200, g: 0, b: 0 }));
+    assert_eq!(bg(1, 6), None, "a ragged row leaves its missing cell blank");
+}
+
+#[test]
+fn heatmap_labels_sit_left_of_and_above_the_cells() {
+    let p = render_json(&deck(heatmap(json!({ "row_labels": ["mon", "tuesday"], "col_labels": ["1am", "2am", "3am"] }))));
+    let lines = frame_lines(&p, 0);
+    assert_eq!(lines[0].trim_end(), "        1am2am3am");
+    assert!(lines[1].starts_with("mon     "), "{:?}", lines[1]);
+    assert!(lines[2].starts_with("tuesday "));
+    assert!(p.grid_at(0)[1][8].style.bg.is_some(), "cells start after the widest label and a gap");
+}
+
+#[test]
+fn heatmap_range_defaults_to_the_data_and_clamps_outside_it() {
+    let h: Heatmap = serde_json::from_value(heatmap(json!({ "min": 2, "low": "blue", "high": "red" }))).unwrap();
+    assert_eq!(h.range(), (2.0, 10.0));
+    assert_eq!(h.color_at(0.0), h.color_at(2.0), "values below min take the low colour");
+    assert_eq!(h.color_at(99.0), Color::Rgb { r: 255, g: 0, b: 0 });
+    assert_eq!(h.size(), (9, 2));
+}
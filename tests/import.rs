@@ -0,0 +1,193 @@
+//! `bs import`: the VT screen that replays terminal output (printing, wrap and
+//! scroll, cursor addressing, erasing, SGR colours, the alternate screen) and
+//! the asciinema cast importer's time and marker sampling, and the DOT
+//! importer's layered layout.
+
+mod common;
+
+use bs::import::vt::Screen;
+use bs::engine::source::SceneObject;
+use bs::import::{from_cast, from_dot, ImportFormat, ImportOptions};
+use bs::types::{Color, NamedColor};
+
+fn lines(screen: &Screen) -> Vec<String> {
+    screen.grid().iter().map(|row| row.iter().map(|c| c.ch).collect()).collect()
+}
+
+#[test]
+fn screen_wraps_scrolls_and_addresses_the_cursor() {
+    let mut s = Screen::new(4, 2);
+    s.feed("abcdef\r\ngh");
+    assert_eq!(lines(&s), ["ef  ", "gh  "], "wrapped at 4 columns, then scrolled");
+    s.feed("\x1b[1;3HX\x1b[2;1H\x1b[K");
+    assert_eq!(lines(&s), ["efX ", "    "]);
+    s.feed("\x1b[2J\x1b[H\tZ");
+    assert_eq!(lines(&s), ["   Z", "    "], "tab stops clamp to the last column");
+}
+
+#[test]
+fn screen_applies_sgr_colours_and_the_alternate_screen() {
+    let mut s = Screen::new(6, 1);
+    s.feed("\x1b[1;31ma\x1b[0;38;5;12mb\x1b[38;2;1;2;3;48;5;0mc\x1b[0m\x1b[7md");
+    let row = &s.grid()[0];
+    assert!(row[0].style.bold);
+    assert_eq!(row[0].style.fg, Some(Color::from(bs::types::xterm_palette()[1])));
+    assert_eq!(row[1].style.fg, Some(Color::Named(NamedColor::Blue)));
+    assert_eq!(row[2].style.fg, Some(Color::Rgb { r: 1, g: 2, b: 3 }));
+    assert_eq!(row[2].style.bg, Some(Color::Named(NamedColor::Black)));
+    assert_eq!(row[3].style.bg, Some(Color::Named(NamedColor::White)), "reverse video");
+
+    s.feed("\x1b[?1049h\x1b[Hvim\x1b]0;title\x07");
+    assert_eq!(lines(&s), ["vim   "]);
+    s.feed("\x1b[?1049l");
+    assert_eq!(lines(&s), ["abcd  "], "the main screen comes back untouched");
+}
+
+const CAST: &str = r#"{"version": 2, "width": 5, "height": 1}
+[0.2, "o", "$ "]
+[1.5, "o", "ls"]
+[1.6, "m", ""]
+[3.7, "o", "\r\nok"]
+"#;
+
+#[test]
+fn cast_is_sampled_by_time_with_repeats_collapsed() {
+    assert_eq!("cast".parse::<ImportFormat>().unwrap(), ImportFormat::Cast);
+    let p = from_cast(CAST, &ImportOptions::default()).unwrap();
+    // Samples at 1s, 2s, 3s and the end: "$ ", "$ ls" twice (collapsed), "ok".
+    let frames: Vec<_> = (0..p.frames.len()).map(|f| common::frame_lines(&p, f)).collect();
+    assert_eq!(frames, [vec!["$    "], vec!["$ ls "], vec!["ok   "]]);
+    assert_eq!(p.durations, [Some(1000), Some(1700), None]);
+}
+
+#[test]
+fn cast_is_sampled_at_markers_and_rejects_other_versions() {
+    let p = from_cast(CAST, &ImportOptions { markers: true, ..Default::default() }).unwrap();
+    assert_eq!(p.frames.len(), 2);
+    assert_eq!(common::frame_lines(&p, 0), ["$ ls "]);
+    assert_eq!(p.durations, [Some(2100), None]);
+
+    let err = from_cast("{\"version\": 1, \"width\": 5, \"height\": 1}\n", &ImportOptions::default()).unwrap_err();
+    assert!(err.to_string().contains("only asciinema v2"));
+    assert!(from_cast("{\"version\": 2, \"width\": 5, \"height\": 1}\n[1, \"o\"\n", &ImportOptions::default()).is_err());
+}
+
+fn dot_opts() -> ImportOptions {
+    ImportOptions { width: 40, height: 16, ..Default::default() }
+}
+
+/// Each rect's top-left, in object order.
+fn rect_corners(objects: &[SceneObject]) -> Vec<(u16, u16)> {
+    objects
+        .iter()
+        .filter_map(|o| match o {
+            SceneObject::Rect(r) => Some((r.position.x.start_value(), r.position.y.start_value())),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn dot_graph_is_laid_out_in_layers_of_boxes_and_arrows() {
+    assert_eq!("dot".parse::<ImportFormat>().unwrap(), ImportFormat::Dot);
+    let dot = r#"
+        // a comment
+        digraph G {
+            node [shape=box];
+            a [label="Start"];
+            a -> { b c };
+            c -> d [label="go"];
+            d -> a; /* closes a cycle */
+        }
+    "#;
+    let source = from_dot(dot, &dot_opts()).unwrap();
+    assert_eq!((source.width, source.height, source.frame_count), (40, 16, 1));
+
+    let corners = rect_corners(&source.objects);
+    assert_eq!(corners.len(), 4);
+    let (a, b, c, d) = (corners[0], corners[1], corners[2], corners[3]);
+    assert!(a.1 < b.1 && b.1 == c.1 && c.1 < d.1, "one layer per rank, top to bottom: {corners:?}");
+    assert!(b.0 < c.0, "siblings side by side in file order");
+    let arrows = source.objects.iter().filter(|o| matches!(o, SceneObject::Arrow(a) if a.head)).count();
+    assert_eq!(arrows, 3);
+    let long = source.objects.iter().filter(|o| matches!(o, SceneObject::Connector(_))).count();
+    assert_eq!(long, 1, "d -> a spans two layers");
+    let groups = source.objects.iter().filter(|o| matches!(o, SceneObject::Group(g) if g.members.len() == 2)).count();
+    assert_eq!(groups, 4, "each node's box and text move together");
+
+    let p = bs::pipeline::compile(&source).unwrap();
+    let text = common::frame_lines(&p, 0).join("\n");
+    assert!(text.contains("│ Start │"), "{text}");
+    assert!(text.contains("go"), "{text}");
+}
+
+#[test]
+fn dot_rankdir_lr_runs_layers_across_and_graphs_are_headless() {
+    let source = from_dot("graph { rankdir=LR; x -- y -- z }", &dot_opts()).unwrap();
+    let corners = rect_corners(&source.objects);
+    assert!(corners[0].0 < corners[1].0 && corners[1].0 < corners[2].0, "{corners:?}");
+    assert!(corners.iter().all(|c| c.1 == corners[0].1));
+    assert!(source.objects.iter().all(|o| !matches!(o, SceneObject::Arrow(a) if a.head)));
+
+    let err = from_dot("digraph { a -> }", &dot_opts()).unwrap_err();
+    assert!(err.to_string().contains("expected an ID"), "{err}");
+    assert!(from_dot("flowchart { a }", &dot_opts()).is_err());
+}
+
+/// Every rect's `(x, y, width, height)`, in object order.
+fn rect_boxes(objects: &[SceneObject]) -> Vec<(usize, usize, usize, usize)> {
+    objects
+        .iter()
+        .filter_map(|o| match o {
+            SceneObject::Rect(r) => Some((
+                r.position.x.start_value() as usize,
+                r.position.y.start_value() as usize,
+                r.width.start_value() as usize,
+                r.height.start_value() as usize,
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn dot_long_edges_pass_between_the_boxes_in_their_way() {
+    let source = from_dot("digraph { a -> b; b -> c; a -> c }", &dot_opts()).unwrap();
+    let boxes = rect_boxes(&source.objects);
+    let p = bs::pipeline::compile(&source).unwrap();
+    let lines = common::frame_lines(&p, 0);
+    let (x, y, w, h) = boxes[1];
+    let inside: Vec<String> = lines[y + 1..y + h - 1].iter().map(|l| l.chars().skip(x + 1).take(w - 2).collect()).collect();
+    assert_eq!(inside, [" b "], "nothing crosses box b:\n{}", lines.join("\n"));
+    // a -> c runs its own lane down past b and ends in a head on c's top.
+    assert_eq!(source.objects.iter().filter(|o| matches!(o, SceneObject::Connector(_))).count(), 1);
+    let (cx, cy, cw, _) = boxes[2];
+    let above_c: String = lines[cy - 1].chars().skip(cx).take(cw).collect();
+    assert_eq!(above_c.matches('▼').count(), 2, "{}", lines.join("\n"));
+}
+
+#[test]
+fn dot_layers_keep_a_gap_or_the_import_fails() {
+    let source = from_dot("digraph { a -> b -> c }", &dot_opts()).unwrap();
+    let boxes = rect_boxes(&source.objects);
+    for pair in boxes.windows(2) {
+        let (above, below) = (pair[0], pair[1]);
+        assert!(below.1 >= above.1 + above.3 + 2, "{boxes:?}");
+    }
+    // Every arrow ends between the boxes, never inside one.
+    for o in &source.objects {
+        if let SceneObject::Arrow(a) = o {
+            let ends = [(a.x1.start_value(), a.y1.start_value()), (a.x2.start_value(), a.y2.start_value())];
+            for (x, y) in ends.map(|(x, y)| (x as usize, y as usize)) {
+                assert!(!boxes.iter().any(|b| (b.0..b.0 + b.2).contains(&x) && (b.1..b.1 + b.3).contains(&y)), "({x}, {y}) in {boxes:?}");
+            }
+        }
+    }
+
+    // Three 3-row layers and two gaps don't fit in 10 rows.
+    let tight = ImportOptions { height: 10, ..dot_opts() };
+    let err = from_dot("digraph { a -> b -> c }", &tight).unwrap_err();
+    assert!(err.to_string().contains("need 13 rows, but --height is 10"), "{err}");
+    let err = from_dot("digraph { rankdir=LR; a -> b -> c }", &ImportOptions { width: 16, ..dot_opts() }).unwrap_err();
+    assert!(err.to_string().contains("--width is 16"), "{err}");
+}
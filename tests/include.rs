@@ -0,0 +1,124 @@
+//! `include` objects: another source deck's objects spliced in when a deck is
+//! loaded from a file — frame placement and selection, renumbered members and
+//! animation ids, nested includes relative to their file, and failures.
+
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use bs::engine::source::SceneObject;
+use bs::pipeline;
+use common::frame_lines;
+
+/// A fresh scratch directory for one test.
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("bs-include-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn write(path: &Path, json: &str) -> String {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, json).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+fn label(text: &str, x: u16, start: usize, end: usize) -> String {
+    format!(
+        r#"{{"type":"label","text":"{text}","position":{{"x":{{"fixed":{x}}},"y":{{"fixed":0}}}},"frames":{{"start":{start},"end":{end}}}}}"#
+    )
+}
+
+/// A 6x1 deck of `frame_count` frames holding `objects`.
+fn deck(frame_count: usize, objects: &[String]) -> String {
+    format!(r#"{{"width":6,"height":1,"frame_count":{frame_count},"objects":[{}]}}"#, objects.join(","))
+}
+
+#[test]
+fn included_frames_land_at_the_requested_frame_and_extend_the_deck() {
+    let dir = scratch("at");
+    write(&dir.join("intro.json"), &deck(2, &[label("I0", 0, 0, 1), label("I1", 0, 1, 2)]));
+    let main = write(
+        &dir.join("main.json"),
+        &deck(2, &[label("M", 4, 0, 2), r#"{"type":"include","path":"intro.json","at":1}"#.into()]),
+    );
+
+    let loaded = pipeline::load_deck(&main).unwrap();
+    assert_eq!(loaded.source.unwrap().objects.len(), 4, "marker kept, included objects appended");
+    let p = loaded.playable;
+    assert_eq!(p.frames.len(), 3, "the intro's last frame lands past the deck's own");
+    assert_eq!(frame_lines(&p, 0), ["    M "]);
+    assert_eq!(frame_lines(&p, 1), ["I0  M "]);
+    assert_eq!(frame_lines(&p, 2), ["I1    "]);
+}
+
+#[test]
+fn a_frame_selection_clips_and_references_are_renumbered() {
+    let dir = scratch("select");
+    let animated = r#"{"type":"label","text":"A","position":{"x":{"animated":{"from":0,"to":4,"anim":1}},"y":{"fixed":0}},"frames":{"start":0,"end":3}}"#;
+    write(
+        &dir.join("parts.json"),
+        &deck(3, &[
+            label("X", 5, 0, 1),
+            animated.into(),
+            r#"{"type":"animation","id":1,"frames":{"start":0,"end":3}}"#.into(),
+            r#"{"type":"group","members":[0,1]}"#.into(),
+        ]),
+    );
+    let main = write(
+        &dir.join("main.json"),
+        &deck(1, &[
+            r#"{"type":"animation","id":1,"frames":{"start":0,"end":1}}"#.into(),
+            r#"{"type":"include","path":"parts.json","frames":{"start":1,"end":3}}"#.into(),
+        ]),
+    );
+
+    let source = pipeline::load_deck(&main).unwrap().source.unwrap();
+    // "X" is only on the unselected frame 0 and is dropped.
+    assert_eq!(source.objects.len(), 5);
+    let SceneObject::Animation(a) = &source.objects[3] else { panic!("{:?}", source.objects[3]) };
+    assert_eq!((a.id, a.frames.start, a.frames.end), (2, 0, 2));
+    assert!(matches!(&source.objects[4], SceneObject::Group(g) if g.members == [2]));
+
+    let p = pipeline::compile(&source).unwrap();
+    assert_eq!(frame_lines(&p, 0), ["A     "], "the clipped span replays the whole motion");
+    assert_eq!(frame_lines(&p, 1), ["    A "]);
+}
+
+#[test]
+fn nested_includes_are_relative_to_their_own_file() {
+    let dir = scratch("nested");
+    write(&dir.join("shared/logo.json"), &deck(1, &[label("L", 1, 0, 1)]));
+    write(
+        &dir.join("shared/intro.json"),
+        &deck(1, &[r#"{"type":"include","path":"logo.json"}"#.into(), label("I", 3, 0, 1)]),
+    );
+    let main = write(
+        &dir.join("main.json"),
+        &deck(1, &[r#"{"type":"include","path":"shared/intro.json","layer":"intro"}"#.into()]),
+    );
+    let source = pipeline::load_deck(&main).unwrap().source.unwrap();
+    assert!(source.objects[1..].iter().all(|o| o.layer() == Some("intro")));
+    assert_eq!(frame_lines(&pipeline::compile(&source).unwrap(), 0), [" L I  "]);
+}
+
+#[test]
+fn cycles_missing_files_and_bad_selections_fail_loading() {
+    let dir = scratch("errors");
+    let include = |path: &str, extra: &str| format!(r#"{{"type":"include","path":"{path}"{extra}}}"#);
+    write(&dir.join("a.json"), &deck(1, &[include("b.json", "")]));
+    write(&dir.join("b.json"), &deck(1, &[include("a.json", "")]));
+    let err = format!("{:#}", pipeline::load_deck(dir.join("a.json").to_str().unwrap()).unwrap_err());
+    assert!(err.contains("include cycle"), "{err}");
+
+    let missing = write(&dir.join("missing.json"), &deck(1, &[include("nope.json", "")]));
+    let err = format!("{:#}", pipeline::load_deck(&missing).unwrap_err());
+    assert!(err.contains("Failed to read included deck"), "{err}");
+
+    write(&dir.join("one.json"), &deck(1, &[label("1", 0, 0, 1)]));
+    let past = write(&dir.join("past.json"), &deck(1, &[include("one.json", r#","frames":{"start":0,"end":5}"#)]));
+    let err = format!("{:#}", pipeline::load_deck(&past).unwrap_err());
+    assert!(err.contains("frames 0..5 are not within its 1-frame deck"), "{err}");
+}
@@ -0,0 +1,68 @@
+//! `bs info`: the deck statistics gathered from a source (object census +
+//! compiled size + diff weight) and from an already-compiled presentation.
+
+mod common;
+
+use bs::engine::source::SourcePresentation;
+use bs::info::{format_stats, playable_stats, source_stats, DeckKind};
+
+fn source(json: &str) -> SourcePresentation {
+    serde_json::from_str(json).expect("source JSON should parse")
+}
+
+/// 10x2 deck, 3 frames: a label on every frame, a second label only on frame 1
+/// (so frame 1 diffs in 2 cells and frame 2 diffs them back out), plus a rect.
+const DECK: &str = r#"{"width":10,"height":2,"frame_count":3,"objects":[
+    {"type":"label","text":"a","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":3}},
+    {"type":"label","text":"bc","position":{"x":{"fixed":0},"y":{"fixed":1}},"frames":{"start":1,"end":2}},
+    {"type":"rect","position":{"x":{"fixed":5},"y":{"fixed":0}},"width":2,"height":2,"frames":{"start":0,"end":3}}
+]}"#;
+
+#[test]
+fn source_stats_count_objects_by_type_and_measure_diffs() {
+    let stats = source_stats(&source(DECK)).unwrap();
+    assert_eq!(stats.kind, DeckKind::Source);
+    assert_eq!((stats.width, stats.height, stats.frame_count), (10, 2, 3));
+    assert_eq!(stats.object_count(), 3);
+    assert_eq!(stats.objects_by_type.get("label"), Some(&2));
+    assert_eq!(stats.objects_by_type.get("rect"), Some(&1));
+    assert_eq!(stats.full_frames, 1);
+    assert_eq!(stats.max_diff, Some((1, 2)));
+    assert!((stats.avg_diff_cells - 2.0).abs() < 1e-9);
+    assert!(stats.compiled_bytes > 0);
+}
+
+#[test]
+fn playable_stats_have_no_object_census() {
+    let p = common::render_json(DECK);
+    let stats = playable_stats(&p, 1234);
+    assert_eq!(stats.kind, DeckKind::Compiled);
+    assert_eq!(stats.object_count(), 0);
+    assert_eq!(stats.compiled_bytes, 1234);
+    assert_eq!(stats.frame_count, 3);
+}
+
+#[test]
+fn a_single_frame_deck_reports_no_diffs() {
+    let s = source(r#"{"width":4,"height":1,"frame_count":1,"objects":[]}"#);
+    let stats = source_stats(&s).unwrap();
+    assert_eq!(stats.max_diff, None);
+    let text = format_stats("deck.json", &stats);
+    assert!(text.contains("diff cells: none"));
+    assert!(text.contains("objects:    0"));
+}
+
+#[test]
+fn metadata_is_listed_under_the_header() {
+    let s = source(
+        r#"{"width":4,"height":1,"frame_count":1,"objects":[],
+            "metadata":{"title":"Talk","author":"Ada","date":"2024-05-01"}}"#,
+    );
+    let stats = source_stats(&s).unwrap();
+    assert_eq!(stats.metadata.title.as_deref(), Some("Talk"));
+    let text = format_stats("deck.json", &stats);
+    assert!(text.contains("title:      Talk\n"));
+    assert!(text.contains("author:     Ada\n"));
+    assert!(text.contains("date:       2024-05-01\n"));
+    assert!(!text.contains("about:"));
+}
@@ -0,0 +1,59 @@
+//! Tests for the `keycap` object — a keyboard shortcut drawn as boxed key
+//! caps, or on one row with `compact`.
+
+mod common;
+
+use bs::engine::objects::parse_keys;
+use bs::types::{Color, NamedColor, PlayablePresentation};
+use common::{frame_lines, object, render_json};
+use serde_json::json;
+
+fn keycap(extra: serde_json::Value) -> serde_json::Value {
+    object(json!({ "type": "keycap", "keys": ["Ctrl", "S"],
+                   "position": { "x": { "fixed": 1 }, "y": { "fixed": 0 } },
+                   "frames": { "start": 0, "end": 1 } }), extra)
+}
+
+fn render(keycap: serde_json::Value) -> PlayablePresentation {
+    render_json(&common::deck(24, 3, 1, &[keycap]).to_string())
+}
+
+fn rows(keycap: serde_json::Value) -> Vec<String> {
+    frame_lines(&render(keycap), 0).iter().map(|l| l.trim_end().to_string()).collect()
+}
+
+#[test]
+fn each_key_gets_a_rounded_cap_with_the_separator_between() {
+    assert_eq!(rows(keycap(json!({}))), [" ╭──────╮   ╭───╮", " │ Ctrl │ + │ S │", " ╰──────╯   ╰───╯"]);
+}
+
+#[test]
+fn border_and_separator_are_configurable() {
+    let r = rows(keycap(json!({ "border": "ascii", "separator": "" })));
+    assert_eq!(r, [" +------+ +---+", " | Ctrl | | S |", " +------+ +---+"]);
+}
+
+#[test]
+fn compact_draws_one_row() {
+    let r = rows(keycap(json!({ "compact": true, "keys": ["Ctrl", "Alt", "Del"] })));
+    assert_eq!(r, [" ⟨Ctrl⟩+⟨Alt⟩+⟨Del⟩", "", ""]);
+}
+
+#[test]
+fn cap_fills_the_faces_only() {
+    let p = render(keycap(json!({ "cap": "white", "style": { "fg": "black" } })));
+    let grid = p.grid_at(0);
+    let white = Some(Color::Named(NamedColor::White));
+    assert_eq!(grid[1][2].style.bg, white);
+    assert_eq!(grid[1][7].style.bg, white);
+    assert_eq!(grid[1][1].style.bg, None);
+    assert_eq!(grid[0][3].style.bg, None);
+}
+
+#[test]
+fn a_plus_where_a_key_starts_is_the_plus_key() {
+    assert_eq!(parse_keys("Ctrl+Shift+P"), ["Ctrl", "Shift", "P"]);
+    assert_eq!(parse_keys("Ctrl + +"), ["Ctrl", "+"]);
+    assert_eq!(parse_keys("+"), ["+"]);
+    assert_eq!(parse_keys(""), Vec::<String>::new());
+}
@@ -1,10 +1,11 @@
 //! `Label` object features beyond plain placement: the optional `framed`
-//! border (and its separate `frame_style`), background fill across the bounding
+//! border (its `border` character set, `padding` and separate `frame_style`), background fill across the bounding
 //! box, height clipping/padding, and multi-line word wrapping.
 
 mod common;
+use bs::engine::source::Label;
 use bs::types::{Color, Frame, NamedColor};
-use common::{char_at, render_json};
+use common::{char_at, frame_lines, render_json};
 
 /// The style of cell (x, y) on the first (full) frame.
 fn cell_fg(p: &bs::types::PlayablePresentation, x: usize, y: usize) -> Option<Color> {
@@ -41,6 +42,73 @@ fn framed_label_draws_a_border_one_cell_outside_the_text() {
     assert_eq!(char_at(&p, 0, 3, 2), 'i');
 }
 
+#[test]
+fn framed_label_uses_its_border_character_set() {
+    let p = render_json(
+        r#"{
+            "width": 4, "height": 3, "frame_count": 1,
+            "objects": [
+                { "type": "label", "text": "Hi", "framed": true, "border": "rounded",
+                  "position": { "x": { "fixed": 1 }, "y": { "fixed": 1 } },
+                  "frames": { "start": 0, "end": 1 } }
+            ]
+        }"#,
+    );
+    assert_eq!(common::frame_lines(&p, 0), ["╭──╮", "│Hi│", "╰──╯"]);
+}
+
+#[test]
+fn framed_label_casts_a_shadow_behind_its_frame() {
+    let label = |framed: bool| {
+        format!(
+            r#"{{"width":5,"height":4,"frame_count":1,"objects":[
+                {{"type":"label","text":"Hi","framed":{framed},"shadow":true,
+                  "position":{{"x":{{"fixed":1}},"y":{{"fixed":1}}}},"frames":{{"start":0,"end":1}}}}]}}"#
+        )
+    };
+    assert_eq!(common::frame_lines(&render_json(&label(true)), 0), ["┌──┐ ", "│Hi│░", "└──┘░", " ░░░░"]);
+    assert_eq!(common::frame_lines(&render_json(&label(false)), 0)[3], "     ", "no frame, no shadow");
+}
+
+#[test]
+fn padding_grows_the_frame_and_fill_around_the_text() {
+    let label = |extra: &str| {
+        let json = format!(
+            r#"{{"width":8,"height":5,"frame_count":1,"objects":[
+                {{"type":"label","text":"Hi","framed":true,{extra}
+                  "position":{{"x":{{"fixed":3}},"y":{{"fixed":2}}}},"frames":{{"start":0,"end":1}}}}]}}"#
+        );
+        render_json(&json)
+    };
+    let p = label(r#""padding":{"left":1,"right":2,"top":1},"#);
+    assert_eq!(common::frame_lines(&p, 0), [" ┌─────┐", " │     │", " │ Hi  │", " └─────┘", "        "]);
+
+    // Uniform padding, a bg filling it, and too little room at the canvas edge.
+    let p = render_json(
+        r#"{"width":6,"height":5,"frame_count":1,"objects":[
+            {"type":"label","text":"Hi","framed":true,"padding":1,"style":{"bg":"blue"},"width":2,
+             "position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":1}}]}"#,
+    );
+    assert_eq!(common::frame_lines(&p, 0), ["┌────┐", "│    │", "│ Hi │", "│    │", "└────┘"]);
+    assert_eq!(cell_bg(&p, 1, 1), Some(Color::Named(NamedColor::Blue)), "the padding is filled");
+    assert_eq!(cell_bg(&p, 4, 3), Some(Color::Named(NamedColor::Blue)));
+}
+
+#[test]
+fn uniform_padding_is_written_as_one_number() {
+    let written = |padding: &str| {
+        let json = format!(
+            r#"{{"text":"Hi","padding":{padding},"position":{{"x":{{"fixed":0}},"y":{{"fixed":0}}}},"frames":{{"start":0,"end":1}}}}"#
+        );
+        let label: Label = serde_json::from_str(&json).unwrap();
+        serde_json::to_value(&label).unwrap().get("padding").cloned()
+    };
+    assert_eq!(written("2"), Some(serde_json::json!(2)));
+    assert_eq!(written(r#"{"top":1,"right":1,"bottom":1,"left":1}"#), Some(serde_json::json!(1)));
+    assert_eq!(written(r#"{"left":3}"#), Some(serde_json::json!({"top":0,"right":0,"bottom":0,"left":3})));
+    assert_eq!(written("0"), None);
+}
+
 #[test]
 fn framed_label_at_the_origin_keeps_its_text_visible_inside_the_border() {
     // A framed label at (0,0) (the default new-label position): there's no room
@@ -220,3 +288,91 @@ fn width_wraps_text_across_multiple_rows() {
     assert_eq!(char_at(&p, 0, 2, 1), 'o', "end of 'two'");
     assert_eq!(char_at(&p, 0, 4, 2), 'e', "end of 'three'");
 }
+
+#[test]
+fn list_items_are_auto_numbered_and_nested_bullets_change_glyph() {
+    let p = render_json(
+        r##"{
+            "width": 12, "height": 8, "frame_count": 1,
+            "objects": [
+                { "type": "label", "text": "#. a\n  - b\n    - c\n  #. d\n  #. e\n#. f\nnote\n#. g",
+                  "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                  "frames": { "start": 0, "end": 1 } }
+            ]
+        }"##,
+    );
+    let lines: Vec<String> = frame_lines(&p, 0).iter().map(|l| l.trim_end().to_string()).collect();
+    assert_eq!(lines, ["1. a", "  • b", "    ◦ c", "  1. d", "  2. e", "2. f", "note", "1. g"]);
+}
+
+#[test]
+fn wrapped_nested_items_continue_under_their_text() {
+    let p = render_json(
+        r#"{
+            "width": 12, "height": 5, "frame_count": 1,
+            "objects": [
+                { "type": "label", "text": "  - one two\n9. x\n10. three four",
+                  "width": { "fixed": 10 },
+                  "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                  "frames": { "start": 0, "end": 1 } }
+            ]
+        }"#,
+    );
+    let lines: Vec<String> = frame_lines(&p, 0).iter().map(|l| l.trim_end().to_string()).collect();
+    assert_eq!(lines, ["  • one", "    two", "9. x", "10. three", "    four"]);
+}
+
+#[test]
+fn reveal_shows_one_list_item_per_frame() {
+    let label = r#"{ "type": "label", "text": "Plan\n- a\n  - b\n- c", "reveal": true,
+                     "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                     "frames": { "start": 1, "end": 5 } }"#;
+    let p = render_json(&format!(
+        r#"{{ "width": 6, "height": 4, "frame_count": 5, "objects": [{label}] }}"#
+    ));
+    let lines = |f| frame_lines(&p, f).iter().map(|l| l.trim_end().to_string()).collect::<Vec<_>>();
+    assert_eq!(lines(1), ["Plan", "- a", "", ""]);
+    assert_eq!(lines(2), ["Plan", "- a", "  • b", ""]);
+    assert_eq!(lines(3), ["Plan", "- a", "  • b", "- c"]);
+    assert_eq!(lines(4), lines(3));
+
+    let l: Label = serde_json::from_str(label).unwrap();
+    assert!(serde_json::to_string(&l).unwrap().contains(r#""reveal":true"#));
+    let plain: Label = serde_json::from_str(&label.replace(r#""reveal": true,"#, "")).unwrap();
+    assert!(!plain.reveal && !serde_json::to_string(&plain).unwrap().contains("reveal"));
+}
+
+#[test]
+fn caret_draws_the_text_verbatim_with_the_caret_cell_inverted() {
+    use bs::engine::objects::ResolveCtx;
+    use bs::engine::source::AnimSpans;
+    let l: Label = serde_json::from_str(
+        r#"{ "type": "label", "text": "- *a*\nb", "markup": true, "width": 6,
+             "position": { "x": { "fixed": 1 }, "y": { "fixed": 1 } },
+             "frames": { "start": 0, "end": 1 } }"#,
+    )
+    .unwrap();
+    let anims = AnimSpans::default();
+    let ctx = ResolveCtx { frame: 0, canvas_width: 10, canvas_height: 4, frame_count: 1, anims: &anims, sections: &[] };
+    let draw = |caret| {
+        let mut ops = Vec::new();
+        l.resolve_with_caret(&ctx, caret, &mut ops);
+        ops
+    };
+    let at = |ops: &[bs::types::DrawOp], x, y| ops.iter().find(|op| op.x == x && op.y == y).cloned();
+
+    // No bullet and no markup: the marks are there to be edited.
+    let ops = draw(2);
+    let row: String = (1..6).map(|x| at(&ops, x, 1).map_or(' ', |op| op.ch)).collect();
+    assert_eq!(row, "- *a*");
+    let caret = at(&ops, 3, 1).unwrap();
+    assert_eq!(caret.ch, '*');
+    assert!(caret.style.bg.is_some());
+    assert!(at(&ops, 4, 1).unwrap().style.bg.is_none());
+
+    // Past a line's last char, the caret is a blank cell after it.
+    let ops = draw(7);
+    let end = at(&ops, 2, 2).unwrap();
+    assert_eq!(end.ch, ' ');
+    assert!(end.style.bg.is_some());
+}
@@ -0,0 +1,147 @@
+//! Named layers: the `layer` field on objects, the deck's layer registry
+//! (hidden layers draw nothing and run no commands), the builder modifiers,
+//! per-export `--show-layer` / `--hide-layer` overrides, and overlay layers
+//! compiled to prepared overlays instead of frames.
+
+mod common;
+
+use bs::builder::Presentation;
+use bs::engine::source::{Layer, SourcePresentation};
+use bs::export::{export_file, ExportFormat, ExportOptions};
+use common::{frame_lines, render_json};
+
+/// "A" on the base layer, "B" on `notes`, "C" on `extra` (never registered),
+/// plus an off-canvas command on `notes`; `layers` is spliced in as the registry.
+fn deck(layers: &str) -> String {
+    format!(
+        r#"{{"width":3,"height":1,"frame_count":1,"layers":{layers},"objects":[
+            {{"type":"label","text":"A","position":{{"x":{{"fixed":0}},"y":{{"fixed":0}}}},"frames":{{"start":0,"end":1}}}},
+            {{"type":"label","text":"B","layer":"notes","position":{{"x":{{"fixed":1}},"y":{{"fixed":0}}}},"frames":{{"start":0,"end":1}}}},
+            {{"type":"label","text":"C","layer":"extra","position":{{"x":{{"fixed":2}},"y":{{"fixed":0}}}},"frames":{{"start":0,"end":1}}}},
+            {{"type":"command","layer":"notes","command":"echo","position":{{"x":{{"fixed":0}},"y":{{"fixed":5}}}},"width":1,"height":1,"frames":{{"start":0,"end":1}}}}
+        ]}}"#
+    )
+}
+
+#[test]
+fn hidden_layers_draw_nothing_and_unregistered_layers_show() {
+    let shown = render_json(&deck("[]"));
+    assert_eq!(frame_lines(&shown, 0), ["ABC"]);
+    let hidden = render_json(&deck(r#"[{"name":"notes","visible":false}]"#));
+    assert_eq!(frame_lines(&hidden, 0), ["A C"]);
+}
+
+#[test]
+fn commands_on_hidden_layers_are_dropped() {
+    let source: SourcePresentation = serde_json::from_str(&deck("[]")).unwrap();
+    assert_eq!(source.command_regions().len(), 1);
+    let source: SourcePresentation =
+        serde_json::from_str(&deck(r#"[{"name":"notes","visible":false}]"#)).unwrap();
+    assert!(source.command_regions().is_empty());
+}
+
+#[test]
+fn layer_names_list_the_registry_then_object_only_layers() {
+    let mut source: SourcePresentation =
+        serde_json::from_str(&deck(r#"[{"name":"bg"},{"name":"notes","locked":true}]"#)).unwrap();
+    assert_eq!(source.layer_names(), ["bg", "notes", "extra"]);
+    assert_eq!(source.layers[0], Layer::new("bg"), "visible and unlocked by default");
+    assert!(source.is_locked(&source.objects[1]));
+
+    // Touching an unregistered layer registers it.
+    source.layer_entry("extra").visible = false;
+    assert!(source.is_hidden(&source.objects[2]));
+    assert_eq!(source.layers.len(), 3);
+}
+
+#[test]
+fn the_builder_places_objects_on_layers() {
+    let p = Presentation::new(2, 1)
+        .hide_layer("notes")
+        .label("a")
+        .label("b").at(1, 0).layer("notes")
+        .compile()
+        .unwrap();
+    assert_eq!(frame_lines(&p, 0), ["a "]);
+}
+
+#[test]
+fn export_overrides_layer_visibility_for_source_decks_only() {
+    let dir = std::env::temp_dir().join(format!("bs-layers-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("deck.json");
+    std::fs::write(&input, deck(r#"[{"name":"notes","visible":false}]"#)).unwrap();
+    let (input, out) = (input.to_str().unwrap(), dir.join("out.svg"));
+    let svg = |opts: &ExportOptions| {
+        export_file(ExportFormat::Svg, input, out.to_str().unwrap(), opts).unwrap();
+        std::fs::read_to_string(&out).unwrap()
+    };
+
+    let show = ExportOptions { show_layers: vec!["notes".into()], ..Default::default() };
+    assert!(svg(&show).contains(">ABC</text>"));
+    let hide = ExportOptions { hide_layers: vec!["extra".into()], ..Default::default() };
+    assert!(svg(&hide).contains(">A  </text>"));
+
+    // A compiled deck has its layers baked in.
+    let compiled = dir.join("compiled.json");
+    let playable = bs::pipeline::compile(&serde_json::from_str(&deck("[]")).unwrap()).unwrap();
+    std::fs::write(&compiled, serde_json::to_string(&playable).unwrap()).unwrap();
+    let err = export_file(ExportFormat::Svg, compiled.to_str().unwrap(), out.to_str().unwrap(), &hide)
+        .unwrap_err();
+    assert!(err.to_string().contains("need the source deck"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn overlay_layers_compile_to_prepared_overlays_not_frames() {
+    let source: SourcePresentation =
+        serde_json::from_str(&deck(r#"[{"name":"notes","overlay":true}]"#)).unwrap();
+    assert_eq!(source.overlay_layers(), ["notes"]);
+    let p = bs::pipeline::compile(&source).unwrap();
+    assert_eq!(frame_lines(&p, 0), ["A C"]);
+    assert!(p.commands.is_empty(), "an overlay's commands never run");
+    assert_eq!(p.overlays.len(), 1);
+    assert_eq!(p.overlays[0].name, "notes");
+    let changes = p.overlays[0].changes_at(0).unwrap();
+    assert_eq!((changes.len(), changes[0].x, changes[0].cell.ch), (1, 1, 'B'));
+    assert!(p.overlays[0].changes_at(1).is_none());
+
+    // They survive every compiled format.
+    let json: bs::types::PlayablePresentation = serde_json::from_str(&serde_json::to_string(&p).unwrap()).unwrap();
+    assert_eq!(json.overlays, p.overlays);
+    let streamed = bs::stream::from_stream(&bs::stream::to_stream(&p).unwrap()).unwrap();
+    assert_eq!(streamed.overlays, p.overlays);
+
+    // A hidden overlay layer is dropped entirely.
+    let hidden: SourcePresentation =
+        serde_json::from_str(&deck(r#"[{"name":"notes","overlay":true,"visible":false}]"#)).unwrap();
+    assert!(bs::pipeline::compile(&hidden).unwrap().overlays.is_empty());
+
+    // Exports leave overlays out unless --show-layer bakes one in.
+    let dir = std::env::temp_dir().join(format!("bs-overlays-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (input, out) = (dir.join("deck.json"), dir.join("out.svg"));
+    std::fs::write(&input, deck(r#"[{"name":"notes","overlay":true}]"#)).unwrap();
+    let svg = |opts: &ExportOptions| {
+        export_file(ExportFormat::Svg, input.to_str().unwrap(), out.to_str().unwrap(), opts).unwrap();
+        std::fs::read_to_string(&out).unwrap()
+    };
+    assert!(svg(&ExportOptions::default()).contains(">A C</text>"));
+    assert!(svg(&ExportOptions { show_layers: vec!["notes".into()], ..Default::default() }).contains(">ABC</text>"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn layer_text_collects_each_frames_notes() {
+    let source: SourcePresentation = serde_json::from_str(
+        r#"{"width":4,"height":1,"frame_count":3,"vars":{"who":"Ada"},
+            "layers":[{"name":"notes","visible":false}],"objects":[
+            {"type":"label","text":"Thank **${who}**","markup":true,"layer":"notes","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":2}},
+            {"type":"label","text":"On stage","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":3}},
+            {"type":"list","text":"demo\nQ&A","layer":"notes","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":1,"end":2}}
+        ]}"#,
+    )
+    .unwrap();
+    assert_eq!(source.layer_text("notes"), ["Thank Ada", "Thank Ada\n\ndemo\nQ&A", ""]);
+    assert_eq!(source.layer_text("missing"), ["", "", ""]);
+}
@@ -0,0 +1,114 @@
+//! `Engine::lint`: the suspicious-content rules (wide labels, overlapping
+//! opaque rects, empty frames, low contrast, overflowing tables), the deck's
+//! per-rule levels, and the findings as `bs validate` diagnostics.
+
+use bs::engine::source::SourcePresentation;
+use bs::engine::{Engine, LintLevel, LintRule};
+use bs::validate::{lint, Severity};
+
+/// A 20x6, `frame_count`-frame deck holding the given object JSON snippets,
+/// plus any extra top-level fields.
+fn deck(frame_count: usize, extra: &str, objects: &[&str]) -> SourcePresentation {
+    serde_json::from_str(&format!(
+        r#"{{"width":20,"height":6,"frame_count":{frame_count},{extra}"objects":[{}]}}"#,
+        objects.join(",")
+    ))
+    .expect("source JSON should parse")
+}
+
+fn label(text: &str, style: &str, start: usize, end: usize) -> String {
+    format!(
+        r#"{{"type":"label","text":"{text}","style":{{{style}}},"position":{{"x":{{"fixed":0}},"y":{{"fixed":0}}}},"frames":{{"start":{start},"end":{end}}}}}"#
+    )
+}
+
+fn rect(x: u16, bg: bool) -> String {
+    let style = if bg { r#"{"bg":"blue"}"# } else { "{}" };
+    format!(
+        r#"{{"type":"rect","style":{style},"position":{{"x":{{"fixed":{x}}},"y":{{"fixed":1}}}},"width":6,"height":3,"frames":{{"start":0,"end":2}}}}"#
+    )
+}
+
+fn found(s: &SourcePresentation) -> Vec<(LintRule, Option<usize>, Vec<usize>)> {
+    Engine::lint(s).into_iter().map(|l| (l.rule, l.object, l.frames)).collect()
+}
+
+#[test]
+fn a_tidy_deck_has_no_lints() {
+    let s = deck(1, "", &[&label("hello", "", 0, 1), &rect(0, true), &rect(10, true)]);
+    assert_eq!(found(&s), []);
+}
+
+#[test]
+fn wide_labels_empty_frames_and_overlapping_opaque_rects_are_found() {
+    let s = deck(
+        3,
+        "",
+        &[
+            &label("a label longer than the canvas", "", 0, 1),
+            &rect(0, true),
+            &rect(4, true),
+            &rect(2, false),
+        ],
+    );
+    assert_eq!(
+        found(&s),
+        [
+            (LintRule::WideLabel, Some(0), vec![]),
+            (LintRule::OverlappingRects, Some(2), vec![0, 1]),
+            (LintRule::EmptyFrame, None, vec![2]),
+        ],
+        "a rect without a bg may overlap freely"
+    );
+}
+
+#[test]
+fn low_contrast_text_is_judged_against_its_own_or_the_decks_background() {
+    let s = deck(
+        3,
+        r#""background":"white","#,
+        &[
+            &label("pale", r#""fg":"yellow""#, 0, 1),
+            &label("fine", r#""fg":"black""#, 1, 2),
+            &label("same", r#""fg":"blue","bg":{"r":80,"g":80,"b":240}"#, 2, 3),
+        ],
+    );
+    assert_eq!(
+        found(&s),
+        [(LintRule::LowContrast, Some(0), vec![0]), (LintRule::LowContrast, Some(2), vec![2])]
+    );
+    assert_eq!(found(&deck(1, "", &[&label("pale", r#""fg":"yellow""#, 0, 1)])), [], "unknown terminal background");
+}
+
+#[test]
+fn a_table_taller_than_its_height_overflows() {
+    let table = |height: u16| {
+        format!(
+            r#"{{"type":"table","position":{{"x":{{"fixed":0}},"y":{{"fixed":0}}}},"width":20,"height":{height},"col_widths":[1.0],"rows":3,"cells":[[{{"content":"a"}}],[{{"content":"b"}}],[{{"content":"c"}}]],"frames":{{"start":0,"end":1}}}}"#
+        )
+    };
+    assert_eq!(found(&deck(1, "", &[&table(4)])), [(LintRule::TableOverflow, Some(0), vec![])]);
+    assert_eq!(found(&deck(1, "", &[&table(0)])), [], "an automatic height always fits");
+}
+
+#[test]
+fn the_decks_lint_levels_silence_or_escalate_rules_as_diagnostics() {
+    let objects = [label("a label longer than the canvas", "", 0, 1)];
+    let objects: Vec<&str> = objects.iter().map(String::as_str).collect();
+
+    let warned = lint(&deck(2, "", &objects));
+    let found: Vec<_> = warned.iter().map(|d| (d.severity, d.path.as_str(), d.message.as_str())).collect();
+    assert_eq!(
+        found,
+        [
+            (Severity::Warning, "objects[0]", "label is 30 columns wide on a 20-column canvas (wide_label)"),
+            (Severity::Warning, "lint.empty_frame", "the frame draws nothing on frames 1..2 (empty_frame)"),
+        ]
+    );
+    assert_eq!(warned[1].frame, Some(1));
+
+    let tuned = deck(2, r#""lint":{"empty_frame":"allow","wide_label":"deny"},"#, &objects);
+    assert_eq!(tuned.lint[&LintRule::EmptyFrame], LintLevel::Allow);
+    let found: Vec<_> = lint(&tuned).into_iter().map(|d| (d.severity, d.path)).collect();
+    assert_eq!(found, [(Severity::Error, "objects[0]".to_string())]);
+}
@@ -0,0 +1,116 @@
+//! Inline markup in label text: `**bold**`, `__underline__`,
+//! `[colour]…[/colour]` and `[link=url]…[/link]` spans styling single chars,
+//! through wrapping and alignment, and the text that stays literal; and the
+//! hyperlinks they (or a label's `link`) become in the ANSI and HTML output.
+
+mod common;
+
+use bs::export::{to_html, ExportOptions};
+use bs::renderer::ansi;
+use bs::types::{Color, NamedColor, PlayablePresentation, Style};
+use common::{frame_lines, render_json};
+
+/// A white label with `markup` on (or `extra`'s setting of it).
+fn label(text: &str, extra: &str) -> PlayablePresentation {
+    let markup = if extra.contains(r#""markup""#) { "" } else { r#""markup":true,"# };
+    render_json(&format!(
+        r#"{{"width":12,"height":2,"frame_count":1,"objects":[
+            {{"type":"label","text":"{text}",{markup}{extra}"style":{{"fg":"white"}},
+              "position":{{"x":{{"fixed":0}},"y":{{"fixed":0}}}},"frames":{{"start":0,"end":1}}}}]}}"#
+    ))
+}
+
+fn style(p: &PlayablePresentation, x: usize, y: usize) -> Style {
+    (*p.grid_at(0)[y][x].style).clone()
+}
+
+const WHITE: Option<Color> = Some(Color::Named(NamedColor::White));
+const RED: Option<Color> = Some(Color::Named(NamedColor::Red));
+
+#[test]
+fn spans_style_their_chars_and_the_markers_vanish() {
+    let p = label("a **b** __c__ [red]d[/red]", "");
+    assert_eq!(frame_lines(&p, 0)[0], "a b c d     ");
+    assert_eq!(style(&p, 0, 0), Style { fg: WHITE, ..Style::default() });
+    assert_eq!(style(&p, 2, 0), Style { fg: WHITE, bold: true, ..Style::default() });
+    assert_eq!(style(&p, 4, 0), Style { fg: WHITE, underline: true, ..Style::default() });
+    assert_eq!(style(&p, 6, 0), Style { fg: RED, ..Style::default() });
+
+    let p = label("[#ff8000]o **[blue]b[/blue]**[/#ff8000]", "");
+    assert_eq!(frame_lines(&p, 0)[0], "o b         ");
+    assert_eq!(style(&p, 0, 0).fg, Some(Color::Rgb { r: 255, g: 128, b: 0 }));
+    assert_eq!(style(&p, 1, 0).fg, Some(Color::Rgb { r: 255, g: 128, b: 0 }), "spaces inside a span");
+    assert_eq!(style(&p, 2, 0), Style { fg: Some(Color::Named(NamedColor::Blue)), bold: true, ..Style::default() });
+}
+
+#[test]
+fn styles_follow_their_chars_through_wrapping_and_alignment() {
+    let p = label("one __two__ three", r#""width":{"fixed":8},"align":"right","#);
+    assert_eq!(frame_lines(&p, 0), [" one two    ", "   three    "]);
+    assert!(style(&p, 5, 0).underline && style(&p, 7, 0).underline);
+    assert!(!style(&p, 4, 0).underline, "the space before the span");
+    assert!(!style(&p, 3, 1).underline);
+}
+
+#[test]
+fn unclosed_markers_unknown_tags_and_escapes_stay_literal() {
+    let p = label(r"2**3 [note] \\*\\*x\\*\\*", "");
+    assert_eq!(frame_lines(&p, 0)[0], "2**3 [note] ");
+    assert!(!style(&p, 1, 0).bold);
+
+    let p = label(r"\\*\\*x\\*\\* [red]", "");
+    assert_eq!(frame_lines(&p, 0)[0], "**x** [red] ");
+
+    let p = label("**not bold**", r#""markup":false,"#);
+    assert_eq!(frame_lines(&p, 0)[0], "**not bold**");
+    assert!(!style(&p, 2, 0).bold);
+}
+
+#[test]
+fn markup_is_off_unless_the_label_asks_for_it() {
+    let p = render_json(
+        r#"{"width":12,"height":1,"frame_count":1,"objects":[
+            {"type":"label","text":"__init__ a\\b","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":1}}]}"#,
+    );
+    assert_eq!(frame_lines(&p, 0)[0], "__init__ a\\b", "text from before markup existed is unchanged");
+    assert!(!style(&p, 2, 0).underline);
+}
+
+#[test]
+fn underline_reaches_the_terminal() {
+    let p = label("__u__", "");
+    assert_eq!(ansi::sgr(&style(&p, 0, 0)), "\x1b[0;4;38;5;15m");
+}
+
+#[test]
+fn link_spans_and_a_labels_link_mark_only_the_text_cells() {
+    let p = label("[link=https://a.example]go[/link] x", "");
+    assert_eq!(frame_lines(&p, 0)[0], "go x        ");
+    assert_eq!(style(&p, 0, 0).link.as_deref(), Some("https://a.example"));
+    assert_eq!(style(&p, 1, 0).link.as_deref(), Some("https://a.example"));
+    assert_eq!(style(&p, 3, 0).link, None);
+
+    let p = render_json(
+        r#"{"width":6,"height":3,"frame_count":1,"objects":[
+            {"type":"label","text":"ab","link":"https://b.example","framed":true,
+             "position":{"x":{"fixed":1},"y":{"fixed":1}},"frames":{"start":0,"end":1}}]}"#,
+    );
+    assert_eq!(style(&p, 1, 1).link.as_deref(), Some("https://b.example"));
+    assert_eq!(style(&p, 0, 0).link, None, "the frame stays unlinked");
+}
+
+#[test]
+fn linked_cells_are_wrapped_in_hyperlinks_on_output() {
+    let p = label("[link=https://a.example]go[/link] x", "");
+    let row = ansi::row_to_ansi(&p.grid_at(0)[0]);
+    let open = ansi::osc8(Some("https://a.example"));
+    let close = ansi::osc8(None);
+    assert_eq!(open, "\x1b]8;;https://a.example\x1b\\");
+    assert!(row.contains(&format!("{open}\x1b[0;38;5;15mgo{close}")), "{row:?}");
+    assert_eq!(row.matches("\x1b]8;;").count(), 2);
+    assert_eq!(ansi::osc8(Some("https://x\x1b\\y")), "\x1b]8;;https://x\\y\x1b\\");
+
+    let html = to_html(&p, &ExportOptions::default());
+    assert!(html.contains(r#"<a href="https://a.example"><span style="color:"#), "{html}");
+    assert!(html.contains(">go</span></a>"));
+}
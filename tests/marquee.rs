@@ -0,0 +1,77 @@
+//! Tests for the `marquee` object — one line of text scrolling through a
+//! fixed-width window, looping after a gap.
+
+mod common;
+
+use bs::engine::source::{Marquee, SceneObject, SourcePresentation};
+use bs::types::{Color, NamedColor};
+use bs::validate::{validate, Severity};
+use common::{frame_lines, object, render_json};
+use serde_json::json;
+
+fn marquee(extra: serde_json::Value) -> serde_json::Value {
+    object(json!({ "type": "marquee", "text": "abc", "width": 5, "gap": 2,
+                   "position": { "x": { "fixed": 1 }, "y": { "fixed": 0 } },
+                   "frames": { "start": 0, "end": 6 } }), extra)
+}
+
+fn deck(objects: &[serde_json::Value]) -> String {
+    object(common::deck(8, 1, 6, objects), json!({ "vars": { "host": "bs.example" } })).to_string()
+}
+
+fn rows(objects: &[serde_json::Value]) -> Vec<String> {
+    let p = render_json(&deck(objects));
+    (0..6).map(|f| frame_lines(&p, f)[0].clone()).collect()
+}
+
+#[test]
+fn text_scrolls_left_and_loops_after_the_gap() {
+    assert_eq!(rows(&[marquee(json!({}))]), [" abc    ", " bc  a  ", " c  ab  ", "   abc  ", "  abc   ", " abc    "]);
+}
+
+#[test]
+fn negative_speed_scrolls_right_and_fractions_move_every_few_frames() {
+    let right = rows(&[marquee(json!({ "speed": -1 }))]);
+    assert_eq!(&right[..2], [" abc    ", "  abc   "]);
+    let slow = rows(&[marquee(json!({ "speed": 0.5 }))]);
+    assert_eq!(&slow[..4], [" abc    ", " abc    ", " bc  a  ", " bc  a  "]);
+}
+
+#[test]
+fn blanks_paint_only_with_a_background() {
+    let under = json!({ "type": "label", "text": "xxxxxxxx", "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                        "frames": { "start": 0, "end": 6 }, "z_order": -1 });
+    assert_eq!(rows(&[under.clone(), marquee(json!({}))])[0], "xabcxxxx");
+    let p = render_json(&deck(&[under, marquee(json!({ "style": { "bg": "blue" } }))]));
+    assert_eq!(frame_lines(&p, 0)[0], "xabc  xx", "the whole window is filled");
+    assert_eq!(p.grid_at(0)[0][5].style.bg, Some(Color::Named(NamedColor::Blue)));
+}
+
+#[test]
+fn width_zero_runs_to_the_canvas_edge_and_vars_are_filled_in() {
+    let r = rows(&[marquee(json!({ "text": "${host}", "width": 0, "gap": 1 }))]);
+    assert_eq!(r[0], " bs.exam");
+    assert_eq!(r[3], " example");
+}
+
+#[test]
+fn a_wide_character_cut_by_the_window_edge_is_left_out() {
+    // "日" takes two cells; at frame 1 its second half starts the window.
+    let r = rows(&[marquee(json!({ "text": "日ab", "width": 3, "gap": 0 }))]);
+    assert_eq!(r[0].replace('\0', "").trim_end(), " 日a");
+    assert_eq!(r[1].trim_end(), "  ab", "half a wide character isn't drawn");
+}
+
+#[test]
+fn a_huge_speed_still_scrolls_and_a_non_finite_one_is_rejected() {
+    let m: Marquee = serde_json::from_value(marquee(json!({ "speed": 1e300 }))).unwrap();
+    assert_eq!(m.window(3, 5).len(), 5);
+    let mut source: SourcePresentation =
+        serde_json::from_str(&deck(&[marquee(json!({}))])).unwrap();
+    if let SceneObject::Marquee(m) = &mut source.objects[0] {
+        m.speed = f64::INFINITY;
+    }
+    let errors: Vec<_> = validate(&source).into_iter().filter(|d| d.severity == Severity::Error).collect();
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert_eq!(errors[0].path, "objects[0].speed");
+}
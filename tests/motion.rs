@@ -0,0 +1,64 @@
+//! Entrance/exit presets: objects slide in from / out to a canvas edge over
+//! the first / last frames of their range, the bare-name shorthand, and the
+//! builder modifiers.
+
+mod common;
+
+use bs::builder::Presentation;
+use bs::engine::source::{EnterPreset, ExitPreset, Motion, SceneObject, SourcePresentation};
+use common::{frame_lines, render_json};
+
+/// "AB" resting at `(x, y)` on frames 0..4 of a `w`x`h` deck, with `motion`
+/// spliced into the label.
+fn deck(w: u16, h: u16, x: u16, y: u16, motion: &str) -> String {
+    format!(
+        r#"{{"width":{w},"height":{h},"frame_count":4,"objects":[
+            {{"type":"label","text":"AB",{motion},"position":{{"x":{{"fixed":{x}}},"y":{{"fixed":{y}}}}},"frames":{{"start":0,"end":4}}}}
+        ]}}"#
+    )
+}
+
+#[test]
+fn an_entrance_slides_in_from_the_edge_and_comes_to_rest() {
+    let p = render_json(&deck(8, 1, 4, 0, r#""enter":{"preset":"slide_from_left","frames":2}"#));
+    // Six cells to clear the left edge: 2/3 of the way out, then 1/3, then at rest.
+    assert_eq!(frame_lines(&p, 0), ["AB      "]);
+    assert_eq!(frame_lines(&p, 1), ["  AB    "]);
+    assert_eq!(frame_lines(&p, 2), ["    AB  "]);
+    assert_eq!(frame_lines(&p, 3), ["    AB  "]);
+
+    let p = render_json(&deck(8, 1, 0, 0, r#""enter":{"preset":"slide_from_right","frames":1}"#));
+    assert_eq!(frame_lines(&p, 0), ["    AB  "], "halfway in from the right");
+}
+
+#[test]
+fn an_exit_leaves_over_the_last_frames() {
+    let p = render_json(&deck(2, 3, 0, 0, r#""exit":{"preset":"slide_down","frames":1}"#));
+    assert_eq!(frame_lines(&p, 2), ["AB", "  ", "  "]);
+    assert_eq!(frame_lines(&p, 3), ["  ", "  ", "AB"]);
+}
+
+#[test]
+fn a_bare_preset_name_takes_the_default_frame_count() {
+    let source: SourcePresentation =
+        serde_json::from_str(&deck(8, 1, 0, 0, r#""enter":"slide_from_top","exit":{"preset":"slide_up","frames":2}"#))
+            .unwrap();
+    assert_eq!(source.objects[0].enter(), Some(&Motion::new(EnterPreset::SlideFromTop, 4)));
+    let json = serde_json::to_value(&source.objects[0]).unwrap();
+    assert_eq!(json["enter"], "slide_from_top");
+    assert_eq!(json["exit"], serde_json::json!({"preset": "slide_up", "frames": 2}));
+}
+
+#[test]
+fn motion_off_the_canvas_is_not_a_clipping_warning() {
+    let source = Presentation::new(6, 1)
+        .label("hi").at(2, 0).appears(0..4)
+        .enter(EnterPreset::SlideFromRight, 2)
+        .exit(ExitPreset::SlideLeft, 2)
+        .build();
+    assert!(matches!(&source.objects[0], SceneObject::Label(l) if l.exit.is_some()));
+    let compiled = bs::pipeline::compile_checked(&source, 50).unwrap();
+    assert!(compiled.warnings.is_empty(), "{:?}", compiled.warnings);
+    assert_eq!(frame_lines(&compiled.presentation, 0), ["     h"]);
+    assert_eq!(frame_lines(&compiled.presentation, 3), ["i     "]);
+}
@@ -0,0 +1,79 @@
+//! Tests for the `pie_chart` object — slices sized by share, clockwise from
+//! twelve o'clock, with a legend, an optional hole and a sweep-in.
+
+mod common;
+
+use bs::engine::source::PieChart;
+use bs::types::{Color, NamedColor};
+use common::{frame_lines, object, render_json};
+use serde_json::json;
+
+fn pie(extra: serde_json::Value) -> serde_json::Value {
+    object(json!({ "type": "pie_chart", "diameter": 8, "ch": "#",
+                   "segments": [
+                       { "label": "rust", "value": 2 },
+                       { "label": "go", "value": 1, "color": "yellow" },
+                       { "value": 1 },
+                   ],
+                   "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                   "frames": { "start": 0, "end": 2 } }), extra)
+}
+
+fn deck(pie: serde_json::Value) -> String {
+    common::deck(40, 8, 2, &[pie]).to_string()
+}
+
+#[test]
+fn slices_run_clockwise_from_twelve_in_their_colours() {
+    let p = render_json(&deck(pie(json!({}))));
+    let fg = |row: usize, col: usize| p.grid_at(0)[row][col].style.fg.clone();
+    // The right half is the first segment (half the total), cyan by default.
+    assert_eq!(fg(2, 12), Some(Color::Named(NamedColor::Cyan)));
+    assert_eq!(fg(5, 12), Some(Color::Named(NamedColor::Cyan)));
+    // Then the bottom-left quarter, then the top-left.
+    assert_eq!(fg(5, 3), Some(Color::Named(NamedColor::Yellow)));
+    assert_eq!(fg(2, 3), Some(Color::Named(NamedColor::Yellow)), "third segment takes the palette's third colour");
+}
+
+#[test]
+fn the_legend_lists_labels_and_percentages_beside_the_chart() {
+    let p = render_json(&deck(pie(json!({}))));
+    let lines = frame_lines(&p, 0);
+    assert_eq!(lines[2].chars().skip(18).collect::<String>().trim_end(), "# rust 50%");
+    assert_eq!(lines[3].chars().skip(18).collect::<String>().trim_end(), "# go 25%");
+    assert_eq!(lines[4].chars().skip(18).collect::<String>().trim_end(), "# 25%", "an unlabelled segment");
+    assert_eq!(p.grid_at(0)[3][18].style.fg, Some(Color::Named(NamedColor::Yellow)), "the swatch");
+    assert_eq!(p.grid_at(0)[3][20].style.fg, None, "the text");
+    let p = render_json(&deck(pie(json!({ "legend": false }))));
+    assert!(frame_lines(&p, 0).iter().all(|l| l.chars().skip(17).all(|c| c == ' ')));
+}
+
+#[test]
+fn a_hole_makes_a_donut() {
+    let p = render_json(&deck(pie(json!({ "hole": 0.5 }))));
+    let row = &frame_lines(&p, 0)[3];
+    assert_eq!(row.chars().nth(8), Some(' '), "{row:?}");
+    assert_eq!(row.chars().nth(1), Some('#'));
+}
+
+#[test]
+fn sweep_draws_the_slices_in_over_the_frames() {
+    let p = render_json(&deck(pie(json!({ "sweep": true, "legend": false }))));
+    let filled = |f: usize| frame_lines(&p, f).concat().chars().filter(|&c| c == '#').count();
+    assert!(filled(0) > 0 && filled(0) < filled(1), "{} then {}", filled(0), filled(1));
+    assert_eq!(p.grid_at(0)[5][3].ch, ' ', "the left half comes in on the second frame");
+}
+
+#[test]
+fn shares_ignore_negative_values() {
+    let p: PieChart = serde_json::from_value(pie(json!({ "segments": [{ "value": 3 }, { "value": -1 }, { "value": 1 }] })))
+        .unwrap();
+    assert_eq!(p.shares(), [0.75, 0.0, 0.25]);
+}
+
+#[test]
+fn a_diameter_taller_than_the_canvas_is_drawn_at_the_canvas_height() {
+    let p = render_json(&deck(pie(json!({ "diameter": 65535, "legend": false }))));
+    let lines = frame_lines(&p, 0);
+    assert!(lines.first().unwrap().contains('#') && lines.last().unwrap().contains('#'), "{lines:?}");
+}
@@ -138,3 +138,37 @@ fn off_grid_object_is_clipped_not_panicked() {
     assert_eq!(lines.len(), 2);
     assert!(lines.iter().all(|l| l.trim().is_empty()));
 }
+
+#[test]
+fn frame_durations_reach_the_compiled_deck_and_survive_both_encodings() {
+    let json = r#"{"width":4,"height":1,"frame_count":3,"objects":[],"durations":[null,1500]}"#;
+    let source = bs::pipeline::parse_source(json, "deck").unwrap();
+    let p = bs::pipeline::compile(&source).unwrap();
+    // Padded to one entry per frame; the unset ones stay `None`.
+    assert_eq!(p.durations, vec![None, Some(1500), None]);
+    for bytes in [serde_json::to_vec(&p).unwrap(), bs::msgpack::to_msgpack(&p).unwrap()] {
+        let back = bs::pipeline::parse_playable(&bytes, "deck").unwrap();
+        assert_eq!(back.durations, p.durations);
+    }
+
+    // A deck without durations compiles (and serializes) without the field.
+    let plain = bs::pipeline::parse_source(r#"{"width":4,"height":1,"frame_count":2,"objects":[]}"#, "deck").unwrap();
+    let p = bs::pipeline::compile(&plain).unwrap();
+    assert!(p.durations.is_empty());
+    assert!(serde_json::to_value(&p).unwrap().get("durations").is_none());
+}
+
+#[test]
+fn on_enter_commands_reach_the_compiled_deck_padded_to_each_frame() {
+    let json = r#"{"width":4,"height":1,"frame_count":3,"objects":[],"on_enter":[[],["make demo","open http://localhost"]]}"#;
+    let source = bs::pipeline::parse_source(json, "deck").unwrap();
+    let p = bs::pipeline::compile(&source).unwrap();
+    assert_eq!(p.on_enter, vec![vec![], vec!["make demo".to_string(), "open http://localhost".to_string()], vec![]]);
+    for bytes in [serde_json::to_vec(&p).unwrap(), bs::msgpack::to_msgpack(&p).unwrap()] {
+        assert_eq!(bs::pipeline::parse_playable(&bytes, "deck").unwrap().on_enter, p.on_enter);
+    }
+
+    let plain = bs::pipeline::parse_source(r#"{"width":4,"height":1,"frame_count":2,"objects":[]}"#, "deck").unwrap();
+    let p = bs::pipeline::compile(&plain).unwrap();
+    assert!(serde_json::to_value(&p).unwrap().get("on_enter").is_none());
+}
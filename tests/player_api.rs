@@ -0,0 +1,319 @@
+//! The host-driven player API: `Player::step` applies inputs without a
+//! terminal, `Player::render_to` paints the screen into any writer, and
+//! `PlayerInput::Tick` fires the auto-advance timer the host polls for;
+//! `Player::on_frame_change` hooks see every frame actually shown, and the
+//! page `--tmux-notes` paints from them. A stream file plays the same read
+//! off disk, draw mode inks annotations over whatever frame is shown, and the
+//! number keys show prepared overlays. Clocks are painted with the time.
+
+#![cfg(feature = "terminal")]
+
+mod common;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use bs::player::{hyperlinks_supported, notes_page, Blank, EnterHooks, Player, PlayerInput};
+use bs::types::{Color, NamedColor};
+
+/// 4x1 deck, 12 frames, the frame number drawn at the origin.
+fn deck() -> Player {
+    Player::new(presentation())
+}
+
+fn presentation() -> bs::types::PlayablePresentation {
+    let objects: Vec<String> = (0..12)
+        .map(|i| {
+            format!(
+                r#"{{"type":"label","text":"{i}","position":{{"x":{{"fixed":0}},"y":{{"fixed":0}}}},"frames":{{"start":{i},"end":{}}}}}"#,
+                i + 1
+            )
+        })
+        .collect();
+    let json = format!(
+        r#"{{"width":4,"height":1,"frame_count":12,"objects":[{}]}}"#,
+        objects.join(",")
+    );
+    common::render_json(&json)
+}
+
+fn canvas(p: &Player) -> String {
+    p.grid()[0].iter().map(|c| c.ch).collect()
+}
+
+#[test]
+fn step_navigates_clamps_and_quits() {
+    let mut p = deck();
+    assert_eq!((p.current_frame(), p.frame_count()), (0, 12));
+    assert!(p.step(PlayerInput::Next).unwrap());
+    assert_eq!(canvas(&p), "1   ");
+    assert!(p.step(PlayerInput::JumpForward).unwrap());
+    assert_eq!(p.current_frame(), 11); // clamped to the last frame
+    p.step(PlayerInput::Prev).unwrap();
+    assert_eq!(canvas(&p), "10  ");
+    p.step(PlayerInput::GoTo(99)).unwrap();
+    assert_eq!(p.current_frame(), 11);
+    p.step(PlayerInput::First).unwrap();
+    assert_eq!(canvas(&p), "0   ");
+    assert!(!p.step(PlayerInput::Quit).unwrap());
+}
+
+#[test]
+fn render_to_paints_the_canvas_and_status_into_a_writer() {
+    let mut p = deck();
+    p.step(PlayerInput::Last).unwrap();
+    let mut out = Vec::new();
+    p.render_to(&mut out).unwrap();
+    let screen = String::from_utf8(out).unwrap();
+    assert!(screen.contains("[Esc]") && screen.contains("quit"));
+    assert!(screen.contains('1') && screen.contains("Frame 12/12"));
+
+    p.step(PlayerInput::ToggleFullscreen).unwrap();
+    assert!(p.is_fullscreen());
+    let mut out = Vec::new();
+    p.render_to(&mut out).unwrap();
+    let screen = String::from_utf8(out).unwrap();
+    assert!(!screen.contains("quit") && !screen.contains("Frame"));
+}
+
+#[test]
+fn the_status_bar_leads_with_the_deck_title() {
+    let mut presentation = presentation();
+    presentation.metadata.title = Some("Rust & Terminals".into());
+    let mut out = Vec::new();
+    Player::new(presentation).render_to(&mut out).unwrap();
+    let screen = String::from_utf8(out).unwrap();
+    assert!(screen.contains(" Rust & Terminals | Frame 1/12 |"), "{screen:?}");
+}
+
+#[test]
+fn drawing_inks_over_the_frame_until_it_changes() {
+    let shown = |p: &Player| -> Vec<String> {
+        p.annotated_grid().iter().map(|row| row.iter().map(|c| c.ch).collect()).collect()
+    };
+    // A 4x3 deck of two blank frames.
+    let mut p = Player::new(common::render_json(r#"{"width":4,"height":3,"frame_count":2,"objects":[]}"#));
+    p.step(PlayerInput::Pen { x: 3, y: 0, ink: true }).unwrap();
+    assert!(!p.is_drawing() && shown(&p)[0] == "    ", "no pen outside draw mode");
+
+    p.step(PlayerInput::ToggleDraw).unwrap();
+    assert!(p.is_drawing());
+    p.step(PlayerInput::Pen { x: 0, y: 0, ink: false }).unwrap();
+    p.step(PlayerInput::Pen { x: 9, y: 0, ink: true }).unwrap(); // clamped to the canvas
+    p.step(PlayerInput::Pen { x: 0, y: 1, ink: false }).unwrap();
+    p.step(PlayerInput::MarkBox).unwrap();
+    p.step(PlayerInput::Pen { x: 2, y: 2, ink: false }).unwrap();
+    p.step(PlayerInput::MarkBox).unwrap();
+    assert_eq!(shown(&p), ["████", "┌─┐ ", "└─┘ "]);
+    assert_eq!(p.annotated_grid()[2][2].style.bg, Some(Color::Named(NamedColor::Yellow)), "the pen is highlighted");
+    assert_eq!(canvas(&p), "    ", "ink stays out of the frame grid");
+
+    // Timers hold while drawing; leaving draw mode keeps the ink.
+    assert_eq!(p.next_deadline(), None);
+    p.step(PlayerInput::ToggleDraw).unwrap();
+    assert_eq!(shown(&p)[0], "████");
+    p.step(PlayerInput::ClearInk).unwrap();
+    assert_eq!(shown(&p), ["    "; 3]);
+
+    p.step(PlayerInput::ToggleDraw).unwrap();
+    p.step(PlayerInput::Pen { x: 0, y: 0, ink: true }).unwrap();
+    p.step(PlayerInput::Next).unwrap();
+    assert_eq!(shown(&p)[0], "    ", "a frame change wipes the ink");
+    assert!(p.is_drawing());
+}
+
+#[test]
+fn number_keys_toggle_overlays_until_the_frame_changes() {
+    // "x" on both frames; overlay 1 boxes it on frame 0 only, overlay 2 marks
+    // the corner on both.
+    let source: bs::engine::source::SourcePresentation = serde_json::from_str(
+        r#"{"width":3,"height":3,"frame_count":2,
+            "layers":[{"name":"box","overlay":true},{"name":"dot","overlay":true}],
+            "objects":[
+            {"type":"label","text":"x","position":{"x":{"fixed":1},"y":{"fixed":1}},"frames":{"start":0,"end":2}},
+            {"type":"rect","layer":"box","position":{"x":{"fixed":0},"y":{"fixed":0}},"width":3,"height":3,"frames":{"start":0,"end":1}},
+            {"type":"label","text":"*","layer":"dot","position":{"x":{"fixed":2},"y":{"fixed":2}},"frames":{"start":0,"end":2}}
+        ]}"#,
+    )
+    .unwrap();
+    let mut p = Player::new(bs::pipeline::compile(&source).unwrap());
+    let shown = |p: &Player| -> Vec<String> {
+        p.annotated_grid().iter().map(|row| row.iter().map(|c| c.ch).collect()).collect()
+    };
+    p.step(PlayerInput::ToggleOverlay(0)).unwrap();
+    assert_eq!(shown(&p), ["┌─┐", "│x│", "└─┘"]);
+    assert_eq!(p.grid()[0][0].ch, ' ', "overlays stay out of the frame grid");
+    p.step(PlayerInput::ToggleOverlay(1)).unwrap();
+    assert_eq!(shown(&p)[2], "└─*", "later overlays paint on top");
+    let mut out = Vec::new();
+    p.render_to(&mut out).unwrap();
+    assert!(String::from_utf8(out).unwrap().contains("Frame 1/2 + box, dot |"));
+    p.step(PlayerInput::ToggleOverlay(0)).unwrap();
+    assert_eq!(shown(&p), ["   ", " x ", "  *"]);
+    p.step(PlayerInput::ToggleOverlay(8)).unwrap(); // no ninth overlay
+
+    p.step(PlayerInput::Next).unwrap();
+    assert_eq!(shown(&p)[2], "   ", "a frame change hides them");
+    p.step(PlayerInput::ToggleOverlay(0)).unwrap();
+    assert_eq!(shown(&p), ["   ", " x ", "   "], "the box has nothing on frame 1");
+}
+
+#[test]
+fn tick_fires_the_auto_advance_deadline() {
+    let source = serde_json::from_str(
+        r#"{"width":4,"height":1,"frame_count":3,"objects":[
+            {"type":"auto_advance","frames":{"start":0,"end":3},"delay_ms":20}
+        ]}"#,
+    )
+    .unwrap();
+    let mut p = Player::new(bs::pipeline::compile(&source).unwrap());
+    assert_eq!(p.next_deadline(), None); // not started yet
+    p.step(PlayerInput::Tick).unwrap(); // starts frame 0, arming the timer
+    let deadline = p.next_deadline().expect("auto-advance armed");
+    assert_eq!(p.current_frame(), 0);
+    std::thread::sleep(deadline.saturating_duration_since(Instant::now()) + Duration::from_millis(5));
+    p.step(PlayerInput::Tick).unwrap();
+    assert_eq!(p.current_frame(), 1);
+}
+
+#[test]
+fn blanking_hides_the_frame_and_holds_the_timers_until_the_next_input() {
+    let source = serde_json::from_str(
+        r#"{"width":4,"height":1,"frame_count":3,"objects":[
+            {"type":"label","text":"ab","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":3}},
+            {"type":"auto_advance","frames":{"start":0,"end":3},"delay_ms":20}
+        ]}"#,
+    )
+    .unwrap();
+    let mut p = Player::new(bs::pipeline::compile(&source).unwrap());
+    let screen = |p: &Player| {
+        let mut out = Vec::new();
+        p.render_to(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    };
+    p.step(PlayerInput::Blank(Blank::White)).unwrap();
+    assert_eq!(p.blanked(), Some(Blank::White));
+    assert!(!screen(&p).contains("ab"), "nothing of the frame shows");
+    assert!(!screen(&p).contains("Frame 1/3"), "nor the bars");
+    assert_eq!(p.next_deadline(), None, "auto-advance holds");
+
+    p.step(PlayerInput::Next).unwrap(); // only brings the frame back
+    assert_eq!(p.blanked(), None);
+    assert_eq!(p.current_frame(), 0);
+    assert!(screen(&p).contains("ab"));
+    assert!(p.next_deadline().is_some(), "auto-advance rearmed");
+}
+
+#[test]
+fn clocks_show_the_time_over_their_placeholder_and_tick() {
+    let source = serde_json::from_str(
+        r#"{"width":10,"height":1,"frame_count":2,"objects":[
+            {"type":"clock","seconds":true,"position":{"x":{"fixed":1},"y":{"fixed":0}},"frames":{"start":0,"end":1}}
+        ]}"#,
+    )
+    .unwrap();
+    let mut p = Player::new(bs::pipeline::compile(&source).unwrap()).with_utc_offset(0);
+    assert_eq!(canvas(&p), " --:--:-- ", "the placeholder until it is shown");
+    let utc = || {
+        let secs = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() % 86_400;
+        format!(" {:02}:{:02}:{:02} ", secs / 3600, secs / 60 % 60, secs % 60)
+    };
+    let before = utc();
+    p.step(PlayerInput::Tick).unwrap();
+    let shown = canvas(&p);
+    assert!(shown == before || shown == utc(), "{shown:?} is the time");
+    let tick = p.next_deadline().expect("the clock ticks");
+    assert!(tick <= Instant::now() + Duration::from_secs(1));
+
+    p.step(PlayerInput::Next).unwrap();
+    assert_eq!(canvas(&p), "          ", "no clock on frame 2");
+    assert_eq!(p.next_deadline(), None);
+}
+
+#[test]
+fn frame_hooks_fire_once_per_frame_shown() {
+    let mut p = deck();
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let log = Rc::clone(&seen);
+    p.on_frame_change(move |f| log.borrow_mut().push(f));
+    p.step(PlayerInput::Next).unwrap(); // starts on 0, then shows 1
+    p.step(PlayerInput::Last).unwrap();
+    p.step(PlayerInput::Next).unwrap(); // already last: nothing shown
+    p.step(PlayerInput::ToggleFullscreen).unwrap();
+    p.step(PlayerInput::GoTo(3)).unwrap();
+    assert_eq!(*seen.borrow(), vec![0, 1, 11, 3]);
+}
+
+#[test]
+fn linked_cells_paint_as_osc8_hyperlinks_unless_turned_off() {
+    let json = r#"{"width":4,"height":1,"frame_count":1,"objects":[
+        {"type":"label","text":"go","link":"https://a.example",
+         "position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":1}}]}"#;
+    let paint = |p: Player| {
+        let mut out = Vec::new();
+        p.render_to(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    };
+    let on = paint(Player::new(common::render_json(json)));
+    assert_eq!(on.matches("\x1b]8;;https://a.example\x1b\\").count(), 1, "one per linked run");
+    let off = paint(Player::new(common::render_json(json)).with_hyperlinks(false));
+    assert!(!off.contains("\x1b]8;"));
+    assert!(!paint(Player::new(common::render_json(json)).with_color(false)).contains("\x1b]8;"));
+
+    assert!(hyperlinks_supported(Some("xterm-256color")));
+    assert!(!hyperlinks_supported(Some("dumb")) && !hyperlinks_supported(None));
+}
+
+#[test]
+fn notes_page_shows_the_notes_and_a_preview_of_the_next_frame() {
+    let p = deck();
+    let next = p.grid();
+    assert_eq!(notes_page(0, 12, "Open strong", Some(next)), "Frame 1/12\n\nOpen strong\n\nNext:\n0\n");
+    assert_eq!(notes_page(11, 12, "", None), "Frame 12/12\n\n(no notes)\n\n(last frame)\n");
+}
+
+#[test]
+fn enter_hooks_run_a_frames_commands_into_the_log_as_it_is_shown() {
+    let source = bs::pipeline::parse_source(
+        r#"{"width":4,"height":1,"frame_count":3,"objects":[],"on_enter":[[],["echo started"]]}"#,
+        "deck",
+    )
+    .unwrap();
+    let mut p = Player::new(bs::pipeline::compile(&source).unwrap());
+    assert_eq!(p.enter_commands(), [vec![], vec!["echo started".to_string()], vec![]]);
+    let log = std::env::temp_dir().join(format!("bs-enter-{}.log", std::process::id()));
+    let mut hooks = EnterHooks::new(p.enter_commands().to_vec(), Some(&log)).unwrap();
+    let shown = Rc::new(RefCell::new(Vec::new()));
+    let seen = Rc::clone(&shown);
+    p.on_frame_change(move |f| seen.borrow_mut().push(f));
+    p.step(PlayerInput::Next).unwrap();
+    for &frame in shown.borrow().iter() {
+        hooks.run(frame);
+    }
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let text = loop {
+        let text = std::fs::read_to_string(&log).unwrap();
+        if text.ends_with("\nstarted\n") || Instant::now() > deadline {
+            break text;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    };
+    assert_eq!(text, "# frame 2: echo started\nstarted\n", "frame 1 runs nothing");
+    std::fs::remove_file(&log).unwrap();
+}
+
+#[test]
+fn a_streamed_deck_plays_like_a_loaded_one() {
+    let path = std::env::temp_dir().join(format!("bs-stream-{}.bss", std::process::id()));
+    std::fs::write(&path, bs::stream::to_stream(&presentation()).unwrap()).unwrap();
+    let mut streamed = Player::streamed(bs::stream::FrameStream::open(&path).unwrap()).unwrap();
+    let mut loaded = deck();
+    assert_eq!(streamed.frame_count(), 12);
+    for input in [PlayerInput::Next, PlayerInput::JumpForward, PlayerInput::Prev, PlayerInput::GoTo(4), PlayerInput::Last] {
+        streamed.step(input).unwrap();
+        loaded.step(input).unwrap();
+        assert_eq!(canvas(&streamed), canvas(&loaded), "after {input:?}");
+    }
+    std::fs::remove_file(&path).unwrap();
+}
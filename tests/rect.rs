@@ -1,8 +1,11 @@
-//! `Rect` object: a box-drawing border (interior left untouched) with an
-//! optional title rendered on the top edge.
+//! `Rect` object: a box-drawing border (interior left untouched) in one of
+//! the `border` character sets, with an optional title rendered on the top edge
+//! and an optional drop `shadow`.
 
 mod common;
-use common::{char_at, render_json};
+use bs::builder::Presentation;
+use bs::engine::source::{BorderStyle, Rect, Shadow};
+use common::{char_at, frame_lines, render_json};
 
 #[test]
 fn border_draws_corners_edges_and_leaves_interior_blank() {
@@ -48,3 +51,61 @@ fn title_is_drawn_on_the_top_edge() {
     assert_eq!(char_at(&p, 0, 3, 0), 'i', "title char 1");
     assert_eq!(char_at(&p, 0, 1, 0), '─', "border still shows left of the title");
 }
+
+#[test]
+fn border_selects_the_box_drawing_character_set() {
+    let rect = |border: &str| {
+        let json = format!(
+            r#"{{"width":3,"height":3,"frame_count":1,"objects":[
+                {{"type":"rect","position":{{"x":{{"fixed":0}},"y":{{"fixed":0}}}},"width":3,"height":3,
+                  "border":"{border}","frames":{{"start":0,"end":1}}}}]}}"#
+        );
+        frame_lines(&render_json(&json), 0)
+    };
+    assert_eq!(rect("double"), ["╔═╗", "║ ║", "╚═╝"]);
+    assert_eq!(rect("heavy"), ["┏━┓", "┃ ┃", "┗━┛"]);
+    assert_eq!(rect("rounded"), ["╭─╮", "│ │", "╰─╯"]);
+    assert_eq!(rect("ascii"), ["+-+", "| |", "+-+"]);
+    assert_eq!(rect("dashed"), ["┌┄┐", "┆ ┆", "└┄┘"]);
+    assert_eq!(rect("single"), ["┌─┐", "│ │", "└─┘"]);
+
+    let p = Presentation::new(3, 3).rect(3, 3).border(BorderStyle::Double).compile().unwrap();
+    assert_eq!(frame_lines(&p, 0), ["╔═╗", "║ ║", "╚═╝"]);
+}
+
+#[test]
+fn shadow_is_drawn_dimmed_down_right_behind_the_box() {
+    let rect = |shadow: &str| {
+        format!(
+            r#"{{"width":5,"height":4,"frame_count":1,"objects":[
+                {{"type":"rect","position":{{"x":{{"fixed":0}},"y":{{"fixed":0}}}},"width":3,"height":3,
+                  "shadow":{shadow},"frames":{{"start":0,"end":1}}}}]}}"#
+        )
+    };
+    let p = render_json(&rect("true"));
+    assert_eq!(frame_lines(&p, 0), ["┌─┐  ", "│ │░ ", "└─┘░ ", " ░░░ "]);
+    let bs::types::Frame::Full { cells } = &p.frames[0] else { panic!("frame 0 must be Full") };
+    assert!(cells[3][1].style.dim, "the default shadow is dimmed");
+
+    let p = render_json(&rect(r#"{"dx":2,"dy":0}"#));
+    assert_eq!(frame_lines(&p, 0), ["┌─┐░░", "│ │░░", "└─┘░░", "     "]);
+    assert_eq!(frame_lines(&render_json(&rect("false")), 0), ["┌─┐  ", "│ │  ", "└─┘  ", "     "]);
+
+    let p = Presentation::new(5, 4).rect(3, 3).shadow().compile().unwrap();
+    assert_eq!(frame_lines(&p, 0)[3], " ░░░ ");
+}
+
+#[test]
+fn a_default_shadow_is_written_back_as_true() {
+    let rect = |shadow: &str| -> Rect {
+        serde_json::from_str(&format!(
+            r#"{{"position":{{"x":{{"fixed":0}},"y":{{"fixed":0}}}},"width":3,"height":3,"frames":{{"start":0,"end":1}},"shadow":{shadow}}}"#
+        ))
+        .unwrap()
+    };
+    let written = |r: &Rect| serde_json::to_value(r).unwrap()["shadow"].clone();
+    assert_eq!(rect("true").shadow, Some(Shadow::default()));
+    assert_eq!(written(&rect("true")), serde_json::json!(true));
+    assert_eq!(written(&rect(r#"{"dx":2}"#)), serde_json::json!({"dx":2,"dy":1,"style":{"dim":true}}));
+    assert!(serde_json::to_value(rect("false")).unwrap().get("shadow").is_none());
+}
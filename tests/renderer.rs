@@ -1,7 +1,10 @@
 //! Renderer rasterization details and `PlayablePresentation::grid_at` replay.
 
 mod common;
-use bs::types::{Cell, CellChange, Frame, PlayablePresentation, Style, TerminalContract};
+use bs::types::{
+    Cell, CellChange, Color, Frame, NamedColor, PlayablePresentation, Style, StyleRef,
+    TerminalContract,
+};
 use common::{char_at, render_json};
 
 #[test]
@@ -28,22 +31,27 @@ fn equal_z_order_keeps_source_order() {
 /// diff can carry an out-of-bounds change.
 fn two_frame_presentation(diff: Vec<CellChange>) -> PlayablePresentation {
     PlayablePresentation {
-        contract: TerminalContract { width: 2, height: 2 },
+        contract: TerminalContract { width: 2, height: 2, background: None },
         frames: vec![
             Frame::Full { cells: vec![vec![Cell::default(); 2]; 2] },
             Frame::Diff { changes: diff },
         ],
         markers: Vec::new(),
         commands: Vec::new(),
+        clocks: Vec::new(),
         loops: Vec::new(),
         animations: Vec::new(),
         auto_advances: Vec::new(),
+        durations: Vec::new(),
+        on_enter: Vec::new(),
+        metadata: Default::default(),
+        overlays: Vec::new(),
     }
 }
 
 #[test]
 fn grid_at_clamps_a_frame_index_past_the_end() {
-    let q = Cell { ch: 'Q', style: Style::default() };
+    let q = Cell { ch: 'Q', style: Style::default().into() };
     let p = two_frame_presentation(vec![CellChange { x: 0, y: 0, cell: q }]);
 
     let last = p.grid_at(1);
@@ -55,10 +63,77 @@ fn grid_at_clamps_a_frame_index_past_the_end() {
 #[test]
 fn grid_at_skips_out_of_bounds_diff_changes() {
     // A diff change at x=99 (outside the 2×2 grid) must be ignored, not panic.
-    let z = Cell { ch: 'Z', style: Style::default() };
+    let z = Cell { ch: 'Z', style: Style::default().into() };
     let p = two_frame_presentation(vec![CellChange { x: 99, y: 0, cell: z }]);
 
     let grid = p.grid_at(1);
     assert_eq!(grid.len(), 2);
     assert!(grid.iter().all(|row| row.iter().all(|c| c.ch == ' ')));
 }
+
+#[test]
+fn keyframes_recur_at_the_interval_without_changing_any_frame() {
+    // "x" moves one cell right per frame, so every frame differs.
+    let source: bs::engine::source::SourcePresentation = serde_json::from_str(
+        r#"{"width":8,"height":1,"frame_count":7,"objects":[
+            {"type":"animation","id":1,"frames":{"start":0,"end":7}},
+            {"type":"label","text":"x","frames":{"start":0,"end":7},
+             "position":{"x":{"animated":{"from":0,"to":6,"anim":1}},"y":{"fixed":0}}}
+        ]}"#,
+    )
+    .unwrap();
+    let scenes = bs::engine::Engine::compile(&source);
+    let contract = TerminalContract { width: 8, height: 1, background: None };
+    let keyed = bs::renderer::Renderer::render_with_keyframes(&scenes, contract.clone(), 3);
+    let plain = bs::renderer::Renderer::render_with_keyframes(&scenes, contract, 0);
+    let full: Vec<bool> = keyed.frames.iter().map(|f| matches!(f, Frame::Full { .. })).collect();
+    assert_eq!(full, [true, false, false, true, false, false, true]);
+    assert_eq!(plain.frames.iter().filter(|f| matches!(f, Frame::Full { .. })).count(), 1);
+    for f in 0..7 {
+        assert_eq!(keyed.grid_at(f), plain.grid_at(f));
+    }
+    assert_eq!(char_at(&keyed, 5, 5, 0), 'x');
+}
+
+#[test]
+fn deck_background_fills_empty_cells_and_sits_behind_unset_bg() {
+    let p = render_json(
+        r#"{
+            "width": 3, "height": 1, "frame_count": 1, "background": "blue",
+            "objects": [
+                { "type": "label", "text": "a",
+                  "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                  "frames": { "start": 0, "end": 1 } },
+                { "type": "label", "text": "b", "style": { "bg": "red" },
+                  "position": { "x": { "fixed": 1 }, "y": { "fixed": 0 } },
+                  "frames": { "start": 0, "end": 1 } }
+            ]
+        }"#,
+    );
+    let blue = Some(Color::Named(NamedColor::Blue));
+    let grid = p.grid_at(0);
+    assert_eq!(grid[0][0].style.bg, blue, "text without a bg gets the deck background");
+    assert_eq!(grid[0][1].style.bg, Some(Color::Named(NamedColor::Red)), "explicit bg wins");
+    assert_eq!(grid[0][2], Cell { ch: ' ', style: Style { bg: blue.clone(), ..Style::default() }.into() });
+    assert_eq!(p.contract.background, blue, "carried into the compiled contract");
+}
+
+#[test]
+fn cells_share_one_interned_copy_of_each_style() {
+    let red = Style { fg: Some(Color::Named(NamedColor::Red)), ..Style::default() };
+    let (a, b) = (StyleRef::new(red.clone()), StyleRef::from(&red));
+    assert!(a == b && std::ptr::eq(&*a, &*b));
+    assert_ne!(a, StyleRef::default());
+    assert_eq!(a.fg, red.fg, "reads go through to the style");
+    assert!(std::mem::size_of::<Cell>() <= 16);
+
+    let p = render_json(
+        r#"{"width":3,"height":1,"frame_count":1,"objects":[
+            {"type":"label","text":"abc","position":{"x":{"fixed":0},"y":{"fixed":0}},
+             "style":{"fg":"red"},"frames":{"start":0,"end":1}}]}"#,
+    );
+    let row = &p.grid_at(0)[0];
+    assert!(row.iter().all(|c| c.style == a));
+    // Serialized, a cell still spells its style out.
+    assert_eq!(serde_json::to_string(&row[0]).unwrap(), r#"{"ch":"a","style":{"fg":"red"}}"#);
+}
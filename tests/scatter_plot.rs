@@ -0,0 +1,62 @@
+//! Tests for the `scatter_plot` object — points in braille dots inside a box
+//! with ticked, labelled axes.
+
+mod common;
+
+use bs::engine::source::ScatterPlot;
+use bs::types::{Color, NamedColor};
+use common::{frame_lines, object, render_json};
+use serde_json::json;
+
+fn plot(extra: serde_json::Value) -> serde_json::Value {
+    object(json!({ "type": "scatter_plot", "width": 12, "height": 8,
+                   "points": [[0, 0], [10, 10]],
+                   "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                   "frames": { "start": 0, "end": 1 } }), extra)
+}
+
+fn deck(plot: serde_json::Value) -> String {
+    common::deck(16, 9, 1, &[plot]).to_string()
+}
+
+#[test]
+fn axes_ticks_and_labels_fit_the_box() {
+    let p = render_json(&deck(plot(json!({ "x_label": "time", "y_label": "load" }))));
+    let lines = frame_lines(&p, 0);
+    assert_eq!(lines[0].trim_end(), "load");
+    assert_eq!(lines[1].trim_end(), "10┤        ⠈");
+    assert_eq!(lines[2].trim_end(), "  │");
+    assert_eq!(lines[3].trim_end(), " 5┤");
+    assert_eq!(lines[4].trim_end(), " 0┤⡀");
+    assert_eq!(lines[5].trim_end(), "  └┬───┬───┬");
+    assert_eq!(lines[6].trim_end(), "   0   5  10", "the last tick label is pulled back inside the box");
+    assert_eq!(lines[7].trim_end(), "     time");
+}
+
+#[test]
+fn points_pack_into_braille_cells() {
+    // Four points a dot apart land in one cell: the left column's top two
+    // dots and the right column's bottom two.
+    let points = json!([[0, 3], [0, 2], [1, 1], [1, 0]]);
+    let p = render_json(&deck(plot(json!({ "points": points, "width": 2, "height": 2, "ticks": 0 }))));
+    let lines = frame_lines(&p, 0);
+    assert_eq!(lines[0].trim_end(), "│⢣");
+    assert_eq!(lines[1].trim_end(), "└─");
+}
+
+#[test]
+fn points_take_their_colour_and_the_axes_the_style() {
+    let p = render_json(&deck(plot(json!({ "color": "green", "style": { "fg": "white" } }))));
+    let fg = |row: usize, col: usize| p.grid_at(0)[row][col].style.fg.clone();
+    assert_eq!(fg(0, 11), Some(Color::Named(NamedColor::Green)));
+    assert_eq!(fg(0, 2), Some(Color::Named(NamedColor::White)));
+}
+
+#[test]
+fn explicit_ranges_override_the_data_and_drop_points_outside() {
+    let sp: ScatterPlot = serde_json::from_value(plot(json!({ "x_min": -10, "y_max": 5 }))).unwrap();
+    assert_eq!(sp.x_range(), (-10.0, 10.0));
+    assert_eq!(sp.y_range(), (0.0, 5.0));
+    let single: ScatterPlot = serde_json::from_value(plot(json!({ "points": [[3, 3]] }))).unwrap();
+    assert_eq!(single.x_range(), (2.0, 4.0), "a single value is widened so the axis has a span");
+}
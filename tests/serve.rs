@@ -0,0 +1,146 @@
+//! `bs serve`: the viewer page, the WebSocket handshake, and frames broadcast
+//! to every browser and telnet viewer — including the current one for a
+//! viewer that joins late — without a stalled viewer holding the presenter up.
+
+mod common;
+
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::time::{Duration, Instant};
+
+use bs::serve::{accept_key, frame_message, ws_text_frame, Server, TELNET_GREETING};
+use bs::types::{Cell, Color, Style, TerminalContract};
+
+fn request(server: &Server, head: &str) -> TcpStream {
+    let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+    stream.write_all(head.as_bytes()).unwrap();
+    stream
+}
+
+/// Read the response head, up to the blank line.
+fn read_head(stream: &mut TcpStream) -> String {
+    let mut head = Vec::new();
+    let mut byte = [0];
+    while !head.ends_with(b"\r\n\r\n") {
+        stream.read_exact(&mut byte).unwrap();
+        head.push(byte[0]);
+    }
+    String::from_utf8(head).unwrap()
+}
+
+fn read_frame(stream: &mut TcpStream, expected: &str) -> Vec<u8> {
+    let mut frame = vec![0; ws_text_frame(expected).len()];
+    stream.read_exact(&mut frame).unwrap();
+    frame
+}
+
+const UPGRADE: &str = "GET /ws HTTP/1.1\r\nHost: x\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+    Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n\r\n";
+
+#[test]
+fn handshake_and_frames_follow_rfc_6455() {
+    assert_eq!(accept_key("dGhlIHNhbXBsZSBub25jZQ=="), "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+    assert_eq!(ws_text_frame("hi"), [0x81, 2, b'h', b'i']);
+    let long = "x".repeat(300);
+    assert_eq!(ws_text_frame(&long)[..4], [0x81, 126, 1, 44]);
+}
+
+#[test]
+fn browsers_get_the_page_and_every_frame_shown() {
+    let p = common::render_json(
+        r#"{"width":3,"height":2,"frame_count":2,"objects":[
+            {"type":"label","text":"A","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":1}},
+            {"type":"label","text":"B","position":{"x":{"fixed":0},"y":{"fixed":1}},"frames":{"start":1,"end":2}}
+        ]}"#,
+    );
+    let server = Server::bind("127.0.0.1:0", &p.contract).unwrap();
+
+    let mut page = request(&server, "GET / HTTP/1.1\r\nHost: x\r\n\r\n");
+    let mut body = String::new();
+    page.read_to_string(&mut body).unwrap();
+    assert!(body.starts_with("HTTP/1.1 200 OK"));
+    assert!(body.contains("new Terminal({ cols: 3, rows: 2"));
+    let mut missing = request(&server, "GET /nope HTTP/1.1\r\n\r\n");
+    assert!(read_head(&mut missing).starts_with("HTTP/1.1 404"));
+
+    let mut early = request(&server, UPGRADE);
+    let head = read_head(&mut early);
+    assert!(head.starts_with("HTTP/1.1 101"));
+    assert!(head.contains("Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo=\r\n"));
+
+    let first = frame_message(&p.grid_at(0));
+    assert_eq!(first, "\x1b[H\x1b[2JA  \r\n   ");
+    server.show(&p.grid_at(0));
+    assert_eq!(read_frame(&mut early, &first), ws_text_frame(&first));
+
+    // A late browser is sent the current frame on connecting.
+    let mut late = request(&server, UPGRADE);
+    read_head(&mut late);
+    assert_eq!(read_frame(&mut late, &first), ws_text_frame(&first));
+
+    let second = frame_message(&p.grid_at(1));
+    server.show(&p.grid_at(1));
+    for browser in [&mut early, &mut late] {
+        assert_eq!(read_frame(browser, &second), ws_text_frame(&second));
+    }
+}
+
+#[test]
+fn telnet_viewers_get_the_current_frame_and_every_one_after() {
+    let p = common::render_json(
+        r#"{"width":2,"height":1,"frame_count":2,"objects":[
+            {"type":"label","text":"A","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":1}},
+            {"type":"label","text":"B","position":{"x":{"fixed":1},"y":{"fixed":0}},"frames":{"start":1,"end":2}}
+        ]}"#,
+    );
+    let server = Server::bind("127.0.0.1:0", &p.contract).unwrap();
+    let addr = server.listen_telnet("127.0.0.1:0").unwrap();
+    server.show(&p.grid_at(0));
+
+    let mut viewer = TcpStream::connect(addr).unwrap();
+    let first = frame_message(&p.grid_at(0));
+    let mut greeting = vec![0; TELNET_GREETING.len() + first.len()];
+    viewer.read_exact(&mut greeting).unwrap();
+    assert_eq!(greeting, [TELNET_GREETING, first.as_bytes()].concat());
+
+    let second = frame_message(&p.grid_at(1));
+    server.show(&p.grid_at(1));
+    let mut frame = vec![0; second.len()];
+    viewer.read_exact(&mut frame).unwrap();
+    assert_eq!(frame, second.as_bytes());
+}
+
+#[test]
+fn a_viewer_that_stops_reading_never_stalls_the_presenter() {
+    // Every cell changes colour, so each frame is a few hundred kilobytes —
+    // more than the socket buffers hold after a few.
+    let contract = TerminalContract { width: 200, height: 60, background: None };
+    let grid: Vec<Vec<Cell>> = (0..60)
+        .map(|y| {
+            (0..200)
+                .map(|x| Cell { ch: 'x', style: Style { fg: Some(Color::Rgb { r: x as u8, g: y as u8, b: 0 }), ..Style::default() }.into() })
+                .collect()
+        })
+        .collect();
+    let server = Server::bind("127.0.0.1:0", &contract).unwrap();
+    let addr = server.listen_telnet("127.0.0.1:0").unwrap();
+    let mut stalled = TcpStream::connect(addr).unwrap();
+    // Let the accept loop register the viewer.
+    std::thread::sleep(Duration::from_millis(100));
+
+    // A blocking write would hold a frame up for the two-second write timeout.
+    let frames = 40;
+    for _ in 0..frames {
+        let started = Instant::now();
+        server.show(&grid);
+        assert!(started.elapsed() < Duration::from_secs(1), "show waited on the socket: {:?}", started.elapsed());
+    }
+
+    // Fallen a full backlog behind, the viewer was disconnected: its
+    // connection ends after what was already on its way, short of every frame.
+    stalled.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+    let mut received = Vec::new();
+    stalled.read_to_end(&mut received).unwrap();
+    let whole = frame_message(&grid).len();
+    assert!(received.len() < frames * whole, "{} of {} bytes", received.len(), frames * whole);
+}
@@ -0,0 +1,89 @@
+//! `slides`: slide-and-build authoring lowered into the flat frame model —
+//! builds accumulating one frame apart, slides laid end to end, frames given
+//! relative to a slide, and objects a slide can't hold.
+
+mod common;
+
+use bs::engine::source::{SceneObject, SourcePresentation};
+use bs::pipeline;
+use common::frame_lines;
+
+fn label(text: &str, y: u16) -> String {
+    format!(r#"{{"type":"label","text":"{text}","position":{{"x":{{"fixed":0}},"y":{{"fixed":{y}}}}}}}"#)
+}
+
+#[test]
+fn builds_accumulate_and_slides_follow_each_other() {
+    let json = format!(
+        r#"{{"width":4,"height":3,"slides":[
+            {{"objects":[{}],"builds":[[{}],[{}]]}},
+            {{"objects":[{}]}}
+        ]}}"#,
+        label("T", 0),
+        label("a", 1),
+        label("b", 2),
+        label("U", 0),
+    );
+    let source = pipeline::parse_source(&json, "deck.json").unwrap();
+    assert_eq!(source.frame_count, 4, "three frames for the built slide, one for the next");
+    assert!(source.slides.is_empty());
+
+    let p = pipeline::compile(&source).unwrap();
+    assert_eq!(frame_lines(&p, 0), ["T   ", "    ", "    "]);
+    assert_eq!(frame_lines(&p, 1), ["T   ", "a   ", "    "]);
+    assert_eq!(frame_lines(&p, 2), ["T   ", "a   ", "b   "]);
+    assert_eq!(frame_lines(&p, 3), ["U   ", "    ", "    "]);
+}
+
+#[test]
+fn explicit_frames_count_from_the_slide_and_lengthen_it() {
+    let held = r#"{"type":"label","text":"H","position":{"x":{"fixed":1},"y":{"fixed":0}},"frames":{"start":1,"end":3}}"#;
+    let json = format!(
+        r#"{{"width":2,"height":1,"slides":[{{"objects":[{}]}},{{"objects":[{}, {held}]}}]}}"#,
+        label("A", 0),
+        label("B", 0),
+    );
+    let source = pipeline::parse_source(&json, "deck.json").unwrap();
+    assert_eq!(source.frame_count, 4, "the second slide runs to its held label's end");
+    let ranges: Vec<_> = source
+        .objects
+        .iter()
+        .map(|o| o.declared_frame_range().map(|r| (r.start, r.end)).unwrap())
+        .collect();
+    assert_eq!(ranges, [(0, 1), (1, 4), (2, 4)]);
+
+    let p = pipeline::compile(&source).unwrap();
+    assert_eq!(frame_lines(&p, 1), ["B "]);
+    assert_eq!(frame_lines(&p, 2), ["BH"]);
+}
+
+#[test]
+fn flat_objects_keep_their_indices_and_frames_alongside_slides() {
+    let footer = r#"{"type":"label","text":"F","position":{"x":{"fixed":1},"y":{"fixed":0}},"frames":{"start":0,"end":5}}"#;
+    let json = format!(
+        r#"{{"width":2,"height":1,"frame_count":5,"objects":[{footer}],"slides":[{{"objects":[{}]}}]}}"#,
+        label("S", 0),
+    );
+    let source = pipeline::parse_source(&json, "deck.json").unwrap();
+    assert_eq!(source.frame_count, 5, "a longer frame_count is kept");
+    assert!(matches!(&source.objects[0], SceneObject::Label(l) if l.text == "F"));
+    assert!(matches!(&source.objects[1], SceneObject::Label(l) if l.text == "S"));
+
+    // A deck built in memory is lowered when compiled.
+    let unlowered: SourcePresentation = serde_json::from_str(&json).unwrap();
+    let p = pipeline::compile(&unlowered).unwrap();
+    assert_eq!(frame_lines(&p, 0), ["SF"]);
+    assert_eq!(frame_lines(&p, 1), [" F"]);
+}
+
+#[test]
+fn objects_a_slide_cannot_hold_fail_with_their_path() {
+    let group = r#"{"type":"group","members":[0]}"#;
+    let json = format!(r#"{{"width":2,"height":1,"slides":[{{"builds":[[{}, {group}]]}}]}}"#, label("A", 0));
+    let err = format!("{:#}", pipeline::parse_source(&json, "deck.json").unwrap_err());
+    assert!(err.contains("slides[0].builds[0][1]: a slide can't hold a group"), "{err}");
+
+    let json = r#"{"width":2,"height":1,"slides":[{"objects":[{"type":"label"}]}]}"#;
+    let err = format!("{:#}", pipeline::parse_source(json, "deck.json").unwrap_err());
+    assert!(err.contains("slides[0].objects[0]: missing field"), "{err}");
+}
@@ -0,0 +1,46 @@
+//! Tests for the `spinner` object — a glyph sequence stepping once per frame,
+//! with optional text and a `done` glyph on the last frame.
+
+mod common;
+
+use common::{frame_lines, object, render_json};
+use serde_json::json;
+
+fn spinner(extra: serde_json::Value) -> serde_json::Value {
+    object(json!({ "type": "spinner", "glyphs": "|/-\\",
+                   "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                   "frames": { "start": 1, "end": 7 } }), extra)
+}
+
+fn rows(spinner: serde_json::Value) -> Vec<String> {
+    let deck = object(common::deck(16, 1, 7, &[spinner]), json!({ "vars": { "tool": "cargo" } }));
+    let p = render_json(&deck.to_string());
+    (0..7).map(|f| frame_lines(&p, f)[0].trim_end().replace('\0', "")).collect()
+}
+
+#[test]
+fn glyphs_step_once_per_frame_from_the_start_of_the_range() {
+    assert_eq!(rows(spinner(json!({}))), ["", "|", "/", "-", "\\", "|", "/"]);
+}
+
+#[test]
+fn done_replaces_the_spinner_on_the_last_frame() {
+    let r = rows(spinner(json!({ "done": "✔", "text": "Built" })));
+    assert_eq!(r[5], "| Built");
+    assert_eq!(r[6], "✔ Built");
+}
+
+#[test]
+fn text_stays_put_past_the_widest_glyph_and_takes_vars() {
+    // "🌑" is two cells wide, so the text starts at column 3 on every frame.
+    let r = rows(spinner(json!({ "glyphs": "🌑*", "text": "${tool} build" })));
+    assert_eq!(r[1], "🌑 cargo build");
+    assert_eq!(r[2], "*  cargo build");
+}
+
+#[test]
+fn the_default_glyphs_are_braille_dots() {
+    let r = rows(json!({ "type": "spinner", "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                         "frames": { "start": 0, "end": 7 } }));
+    assert_eq!(r[..3], ["⠋", "⠙", "⠹"]);
+}
@@ -0,0 +1,62 @@
+//! Stacking: equal-z objects paint in author order on every frame, and
+//! `auto_z` stacks by author order alone.
+
+mod common;
+
+use bs::builder::Presentation;
+use bs::engine::source::SourcePresentation;
+use bs::engine::Engine;
+use common::{char_at, frame_lines, render_json};
+
+fn label(text: &str, z: i32) -> String {
+    label_at(text, 0, z)
+}
+
+fn label_at(text: &str, x: u16, z: i32) -> String {
+    format!(
+        r#"{{"type":"label","text":"{text}","position":{{"x":{{"fixed":{x}}},"y":{{"fixed":0}}}},"frames":{{"start":0,"end":2}},"z_order":{z}}}"#
+    )
+}
+
+fn deck(extra: &str, objects: &[String]) -> String {
+    format!(r#"{{"width":2,"height":1,"frame_count":2,{extra}"objects":[{}]}}"#, objects.join(","))
+}
+
+#[test]
+fn equal_z_objects_stack_in_author_order_and_ops_come_in_paint_order() {
+    let p = render_json(&deck("", &[label("a", 0), label("b", 0)]));
+    assert_eq!((char_at(&p, 0, 0, 0), char_at(&p, 1, 0, 0)), ('b', 'b'));
+    let p = render_json(&deck("", &[label("b", 0), label("a", 0)]));
+    assert_eq!((char_at(&p, 0, 0, 0), char_at(&p, 1, 0, 0)), ('a', 'a'), "reordering restacks");
+
+    let source: SourcePresentation =
+        serde_json::from_str(&deck("", &[label("x", 2), label("a", 0), label("b", 0)])).unwrap();
+    let scene = &Engine::compile(&source)[0];
+    let drawn: Vec<_> = scene.ops.iter().map(|op| (op.z_order, op.ch)).collect();
+    assert_eq!(drawn, [(0, 'a'), (0, 'b'), (2, 'x')]);
+}
+
+#[test]
+fn auto_z_puts_later_objects_on_top_whatever_their_z_order() {
+    let objects = [label("a", 5), label("b", 0)];
+    assert_eq!(char_at(&render_json(&deck("", &objects)), 0, 0, 0), 'a');
+    assert_eq!(char_at(&render_json(&deck(r#""auto_z":true,"#, &objects)), 0, 0, 0), 'b');
+
+    let p = Presentation::new(2, 1).auto_z().label("a").z(5).label("b").compile().unwrap();
+    assert_eq!(char_at(&p, 0, 0, 0), 'b');
+}
+
+#[test]
+fn auto_z_keeps_an_instances_own_parts_in_z_order() {
+    // The component puts "u" under "o" by z_order; a later plain label with a
+    // lower z_order still draws over the whole instance.
+    let json = format!(
+        r#"{{"width":2,"height":1,"frame_count":2,"auto_z":true,
+            "components":{{"pair":{{"params":{{}},"objects":[{},{}]}}}},
+            "objects":[{{"type":"instance","component":"pair","args":{{}},"frames":{{"start":0,"end":2}}}},{}]}}"#,
+        label("o", 1),
+        label("u", 0),
+        label_at("-", 1, -5),
+    );
+    assert_eq!(frame_lines(&render_json(&json), 0), ["o-"]);
+}
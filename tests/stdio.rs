@@ -0,0 +1,52 @@
+//! `-` as a path: the CLI reads sources/presentations from stdin and writes
+//! compiled output to stdout, so it composes in a shell pipeline.
+
+#![cfg(feature = "terminal")]
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use bs::types::PlayablePresentation;
+
+const DECK: &str = r#"{"width":4,"height":1,"frame_count":2,"objects":[
+    {"type":"label","text":"hi","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":2}}
+]}"#;
+
+/// Run `bs <args>` with `stdin` piped in; returns (success, stdout).
+fn bs(args: &[&str], stdin: &str) -> (bool, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_bs"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("bs should start");
+    child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+    let out = child.wait_with_output().unwrap();
+    (out.status.success(), String::from_utf8(out.stdout).unwrap())
+}
+
+#[test]
+fn compile_reads_stdin_and_writes_stdout() {
+    let (ok, stdout) = bs(&["compile", "-", "-"], DECK);
+    assert!(ok);
+    let p: PlayablePresentation = serde_json::from_str(&stdout).expect("stdout is the compiled deck");
+    assert_eq!(p.frames.len(), 2);
+    assert_eq!(p.grid_at(0)[0][1].ch, 'i');
+}
+
+#[test]
+fn compiled_output_can_be_piped_into_another_command() {
+    let (_, compiled) = bs(&["compile", "-", "-"], DECK);
+    let (ok, stdout) = bs(&["info", "-"], &compiled);
+    assert!(ok);
+    assert!(stdout.starts_with("- (compiled)\n"));
+    assert!(stdout.contains("frames:     2"));
+}
+
+#[test]
+fn malformed_stdin_fails() {
+    let (ok, stdout) = bs(&["compile", "-", "-"], "{not json");
+    assert!(!ok);
+    assert!(stdout.is_empty());
+}
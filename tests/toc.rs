@@ -0,0 +1,54 @@
+//! Tests for the deck's named sections and the `toc` object that lists them —
+//! entries built at compile time, the current one highlighted, and the
+//! sections compiled onto the deck's markers.
+
+mod common;
+
+use common::{frame_lines, object, render_json};
+use serde_json::json;
+
+fn deck(toc: serde_json::Value) -> String {
+    let sections = json!([null, "Intro", null, null, null, null, null, null, null, "Wrap-up"]);
+    object(common::deck(30, 4, 12, &[toc]), json!({ "sections": sections })).to_string()
+}
+
+#[test]
+fn toc_lists_each_section_after_its_slide_number() {
+    let p = render_json(&deck(json!({ "type": "toc",
+        "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+        "frames": { "start": 0, "end": 1 } })));
+    let lines = frame_lines(&p, 0);
+    assert_eq!(lines[0].trim_end(), " 2  Intro");
+    assert_eq!(lines[1].trim_end(), "10  Wrap-up");
+}
+
+#[test]
+fn toc_highlights_the_section_the_frame_is_in() {
+    let p = render_json(&deck(json!({ "type": "toc", "numbers": false,
+        "highlight": { "bold": true },
+        "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+        "frames": { "start": 0, "end": 12 } })));
+    let bold_rows = |f: usize| -> Vec<usize> {
+        (0..2).filter(|&row| p.grid_at(f)[row][0].style.bold).collect()
+    };
+    assert!(bold_rows(0).is_empty(), "frame 0 comes before any section");
+    assert_eq!(bold_rows(4), [0]);
+    assert_eq!(bold_rows(11), [1]);
+    assert_eq!(frame_lines(&p, 4)[0].trim_end(), "Intro");
+}
+
+#[test]
+fn slide_sections_lower_onto_their_first_frame_and_compile_to_markers() {
+    let source = serde_json::from_value(json!({
+        "width": 20, "height": 2,
+        "slides": [
+            { "section": "Intro", "objects": [] },
+            { "objects": [], "builds": [[], []] },
+            { "section": "Demo", "objects": [] },
+        ],
+    }))
+    .unwrap();
+    let p = bs::pipeline::compile(&source).unwrap();
+    let markers: Vec<_> = p.markers.iter().map(|m| (m.frame_index, m.label.as_str())).collect();
+    assert_eq!(markers, [(0, "Intro"), (4, "Demo")]);
+}
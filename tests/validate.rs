@@ -0,0 +1,316 @@
+//! `bs validate`: the semantic checks over a parsed source (ranges vs. the deck,
+//! dangling/empty animations, group members, off-canvas objects, table column
+//! fractions, component instances, casts, tocs without sections), the compile-time clipping and truncation
+//! warnings, the derived `frame_count`, and the JSON line locator that points
+//! findings at the file.
+
+use bs::engine::source::SourcePresentation;
+use bs::validate::{object_lines, validate, Severity};
+
+fn source(json: &str) -> SourcePresentation {
+    serde_json::from_str(json).expect("source JSON should parse")
+}
+
+/// A 20x5, `frame_count`-frame deck holding the given object JSON snippets.
+fn deck(frame_count: usize, objects: &[&str]) -> SourcePresentation {
+    source(&format!(
+        r#"{{"width":20,"height":5,"frame_count":{frame_count},"objects":[{}]}}"#,
+        objects.join(",")
+    ))
+}
+
+fn label(x: u16, start: usize, end: usize) -> String {
+    format!(
+        r#"{{"type":"label","text":"hi","position":{{"x":{{"fixed":{x}}},"y":{{"fixed":0}}}},"frames":{{"start":{start},"end":{end}}}}}"#
+    )
+}
+
+fn paths(s: &SourcePresentation) -> Vec<(Severity, String)> {
+    validate(s).into_iter().map(|d| (d.severity, d.path)).collect()
+}
+
+#[test]
+fn a_well_formed_deck_has_no_findings() {
+    let s = deck(3, &[&label(0, 0, 3)]);
+    assert!(validate(&s).is_empty());
+}
+
+#[test]
+fn range_past_the_deck_and_empty_range_are_errors() {
+    let s = deck(3, &[&label(0, 1, 5), &label(0, 2, 2)]);
+    assert_eq!(
+        paths(&s),
+        vec![
+            (Severity::Error, "objects[0].frames".to_string()),
+            (Severity::Error, "objects[1].frames".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn dangling_and_empty_animations_are_reported_on_the_coordinate() {
+    let s = deck(
+        4,
+        &[
+            r#"{"type":"label","text":"a","position":{"x":{"animated":{"from":0,"to":5,"anim":9}},"y":{"fixed":0}},"frames":{"start":0,"end":4}}"#,
+            r#"{"type":"label","text":"b","position":{"x":{"fixed":0},"y":{"animated":{"from":0,"to":3,"anim":1}}},"frames":{"start":0,"end":4}}"#,
+            r#"{"type":"animation","id":1,"frames":{"start":3,"end":3}}"#,
+        ],
+    );
+    let found = paths(&s);
+    assert!(found.contains(&(Severity::Error, "objects[0].position.x".to_string())));
+    assert!(found.contains(&(Severity::Error, "objects[1].position.y".to_string())));
+    // The empty animation's own range is flagged too.
+    assert!(found.contains(&(Severity::Error, "objects[2].frames".to_string())));
+}
+
+#[test]
+fn out_of_bounds_and_self_referencing_group_members_are_errors() {
+    let s = deck(2, &[&label(0, 0, 2), r#"{"type":"group","members":[0,7,1]}"#]);
+    assert_eq!(
+        paths(&s),
+        vec![
+            (Severity::Error, "objects[1].members[1]".to_string()),
+            (Severity::Error, "objects[1].members[2]".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn an_object_entirely_off_canvas_is_a_warning_but_a_clipped_one_is_not() {
+    // x=30 on a 20-wide canvas: nothing visible. x=19: "h" is still on screen.
+    let s = deck(1, &[&label(30, 0, 1), &label(19, 0, 1)]);
+    assert_eq!(paths(&s), vec![(Severity::Warning, "objects[0]".to_string())]);
+}
+
+#[test]
+fn compiling_warns_about_clipped_objects_with_their_frame_ranges() {
+    // "hi" at x=19 loses its "i" on frames 0..2 and 3..5; x=0 fits.
+    let s = deck(5, &[&label(0, 0, 5), &label(19, 0, 2), &label(19, 3, 5)]);
+    let compiled = bs::pipeline::compile_checked(&s, 50).unwrap();
+    let found: Vec<_> = compiled.warnings.iter().map(|d| (d.path.as_str(), d.message.as_str())).collect();
+    assert_eq!(
+        found,
+        [
+            ("objects[1]", "label draws outside the 20x5 canvas on frames 0..2"),
+            ("objects[2]", "label draws outside the 20x5 canvas on frames 3..5"),
+        ]
+    );
+
+    let (_, clipped) = bs::engine::Engine::compile_reporting(&deck(4, &[&label(19, 0, 4)]));
+    assert_eq!(clipped[0].frames, [0, 1, 2, 3]);
+}
+
+#[test]
+fn compiling_warns_about_frames_cut_off_by_frame_count() {
+    let s = deck(3, &[&label(0, 0, 3), &label(0, 1, 6)]);
+    let compiled = bs::pipeline::compile_checked(&s, 50).unwrap();
+    assert_eq!(compiled.presentation.frames.len(), 3);
+    let found: Vec<_> = compiled.warnings.iter().map(|d| (d.path.as_str(), d.message.as_str())).collect();
+    assert_eq!(
+        found,
+        [(
+            "objects[1].frames",
+            "label frames 1..6 run past the 3-frame deck and are cut off (omit `frame_count` to derive it)"
+        )]
+    );
+}
+
+#[test]
+fn an_omitted_frame_count_is_derived_from_the_objects() {
+    let json = format!(r#"{{"width":20,"height":5,"objects":[{},{}]}}"#, label(0, 0, 2), label(0, 1, 6));
+    let loaded = bs::pipeline::parse_source(&json, "deck.json").unwrap();
+    assert_eq!(loaded.frame_count, 6);
+    assert!(validate(&loaded).is_empty());
+
+    // In memory, compiling derives it too; an explicit count is trusted.
+    let compiled = bs::pipeline::compile_checked(&source(&json), 50).unwrap();
+    assert_eq!(compiled.presentation.frames.len(), 6);
+    assert!(compiled.warnings.is_empty());
+    assert_eq!(deck(2, &[&label(0, 0, 6)]).frame_count, 2);
+}
+
+#[test]
+fn entrance_and_exit_longer_than_the_range_are_a_warning() {
+    let with = |motion: &str| label(0, 0, 5).replacen("{", &format!("{{{motion},"), 1);
+    let s = deck(5, &[&with(r#""enter":{"preset":"slide_from_left","frames":3},"exit":"slide_down""#)]);
+    assert_eq!(paths(&s), vec![(Severity::Warning, "objects[0].enter".to_string())]);
+    assert!(validate(&s)[0].message.contains("spends 7 frames entering/exiting but is only shown for 5"));
+    assert!(validate(&deck(5, &[&with(r#""exit":"slide_down""#)])).is_empty());
+}
+
+#[test]
+fn instances_that_do_not_expand_are_errors_on_the_offending_field() {
+    let mut s = deck(1, &[
+        r#"{"type":"instance","component":"box","frames":{"start":0,"end":1}}"#,
+        r#"{"type":"instance","component":"card","args":{"size":3},"frames":{"start":0,"end":1}}"#,
+        r#"{"type":"instance","component":"card","frames":{"start":0,"end":1}}"#,
+    ]);
+    s.components.insert("card".into(), serde_json::from_str(r#"{"objects":[]}"#).unwrap());
+    assert_eq!(
+        paths(&s),
+        vec![
+            (Severity::Error, "objects[0].component".to_string()),
+            (Severity::Error, "objects[1].args".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn table_col_widths_must_sum_to_about_one() {
+    let table = |widths: &str| {
+        format!(
+            r#"{{"type":"table","position":{{"x":{{"fixed":0}},"y":{{"fixed":0}}}},"width":10,"col_widths":[{widths}],"rows":1,"frames":{{"start":0,"end":1}}}}"#
+        )
+    };
+    assert!(validate(&deck(1, &[&table("0.333,0.333,0.334")])).is_empty());
+    assert_eq!(
+        paths(&deck(1, &[&table("0.5,0.7")])),
+        vec![(Severity::Warning, "objects[0].col_widths".to_string())]
+    );
+}
+
+#[test]
+fn a_toc_in_a_deck_without_sections_is_a_warning() {
+    let toc = r#"{"type":"toc","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":1}}"#;
+    assert_eq!(paths(&deck(1, &[toc])), vec![(Severity::Warning, "objects[0]".to_string())]);
+    let mut s = deck(1, &[toc]);
+    s.set_frame_section(0, Some("Intro".into()));
+    assert!(validate(&s).is_empty());
+}
+
+#[test]
+fn object_lines_locates_each_object_in_the_text() {
+    let json = "{\n  \"width\": 20, \"height\": 5,\n  \"frame_count\": 1,\n  \"objects\": [\n    {\"type\": \"group\", \"members\": []},\n\n    {\n      \"type\": \"group\",\n      \"members\": [\"]\"]\n    }\n  ]\n}";
+    assert_eq!(object_lines(json), vec![5, 7]);
+}
+
+#[test]
+fn a_cast_that_does_not_parse_or_starts_after_it_ends_is_an_error() {
+    let cast = |cast: &str, start: f64| {
+        serde_json::json!({ "type": "cast", "cast": cast, "start": start,
+            "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } }, "frames": { "start": 0, "end": 1 } })
+        .to_string()
+    };
+    let good = "{\"version\": 2, \"width\": 4, \"height\": 1}\n[1.0, \"o\", \"hi\"]\n";
+    let s = deck(1, &[&cast(good, 0.5), &cast("{\"version\": 1}", 0.0), &cast(good, 3.0)]);
+    assert_eq!(
+        paths(&s),
+        vec![
+            (Severity::Error, "objects[1].cast".to_string()),
+            (Severity::Error, "objects[2].start".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn an_empty_heatmap_is_an_error_and_extra_labels_a_warning() {
+    let heatmap = |values: &str, rows: &str| {
+        format!(r#"{{"type":"heatmap","values":{values},"row_labels":{rows},"position":{{"x":{{"fixed":0}},"y":{{"fixed":0}}}},"frames":{{"start":0,"end":1}}}}"#)
+    };
+    let s = deck(1, &[&heatmap("[]", "[]"), &heatmap("[[1, 2]]", r#"["a", "b"]"#), &heatmap("[[1]]", r#"["a"]"#)]);
+    assert_eq!(
+        paths(&s),
+        vec![(Severity::Error, "objects[0].values".to_string()), (Severity::Warning, "objects[1].row_labels".to_string())]
+    );
+}
+
+#[test]
+fn a_diff_without_changes_or_with_too_few_frames_to_reveal_is_a_warning() {
+    let diff = |fields: &str| {
+        format!(r#"{{"type":"diff",{fields},"position":{{"x":{{"fixed":0}},"y":{{"fixed":0}}}},"frames":{{"start":0,"end":1}}}}"#)
+    };
+    let two_hunks = r#""diff":"@@ -1 +1 @@\n-a\n+b\n@@ -9 +9 @@\n-y\n+z""#;
+    let s = deck(1, &[&diff(r#""before":"same","after":"same""#), &diff(&format!("{two_hunks},\"reveal\":true")), &diff(two_hunks)]);
+    assert_eq!(
+        paths(&s),
+        vec![(Severity::Warning, "objects[0]".to_string()), (Severity::Warning, "objects[1].frames".to_string())]
+    );
+}
+
+#[test]
+fn a_gantt_task_ending_before_it_starts_is_an_error() {
+    let gantt = r#"{"type":"gantt","tasks":[{"start":0,"end":4},{"label":"x","start":5,"end":2}],"position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":1}}"#;
+    assert_eq!(paths(&deck(1, &[gantt])), vec![(Severity::Error, "objects[0].tasks[1]".to_string())]);
+}
+
+#[test]
+fn a_pie_chart_without_positive_values_is_a_warning() {
+    let pie = |values: &str| {
+        format!(r#"{{"type":"pie_chart","diameter":2,"segments":{values},"position":{{"x":{{"fixed":0}},"y":{{"fixed":0}}}},"frames":{{"start":0,"end":1}}}}"#)
+    };
+    let s = deck(1, &[&pie("[]"), &pie(r#"[{"value":0},{"value":-2}]"#), &pie(r#"[{"value":0},{"value":2}]"#)]);
+    assert_eq!(
+        paths(&s),
+        vec![(Severity::Warning, "objects[0].segments".to_string()), (Severity::Warning, "objects[1].segments".to_string())]
+    );
+}
+
+#[test]
+fn a_scatter_plot_without_points_or_with_points_off_its_axes_is_a_warning() {
+    let plot = |extra: &str| {
+        format!(r#"{{"type":"scatter_plot","width":20,"height":5,{extra}"position":{{"x":{{"fixed":0}},"y":{{"fixed":0}}}},"frames":{{"start":0,"end":1}}}}"#)
+    };
+    let s = deck(1, &[&plot(r#""points":[],"#), &plot(r#""points":[[1,1],[5,1]],"x_max":4,"#), &plot(r#""points":[[1,1],[5,1]],"#)]);
+    assert_eq!(
+        paths(&s),
+        vec![(Severity::Warning, "objects[0].points".to_string()), (Severity::Warning, "objects[1].points".to_string())]
+    );
+}
+
+#[test]
+fn a_background_effect_density_outside_zero_to_one_is_a_warning() {
+    let effect = |density: &str| {
+        format!(r#"{{"type":"background_effect","density":{density},"position":{{"x":{{"fixed":0}},"y":{{"fixed":0}}}},"frames":{{"start":0,"end":1}}}}"#)
+    };
+    let s = deck(1, &[&effect("0"), &effect("1.5"), &effect("0.2")]);
+    assert_eq!(
+        paths(&s),
+        vec![(Severity::Warning, "objects[0].density".to_string()), (Severity::Warning, "objects[1].density".to_string())]
+    );
+}
+
+#[test]
+fn a_marquee_that_does_not_move_is_a_warning() {
+    let marquee = |speed: &str| {
+        format!(r#"{{"type":"marquee","text":"news","width":8,"speed":{speed},"position":{{"x":{{"fixed":0}},"y":{{"fixed":0}}}},"frames":{{"start":0,"end":1}}}}"#)
+    };
+    let s = deck(1, &[&marquee("0"), &marquee("-0.5")]);
+    assert_eq!(paths(&s), vec![(Severity::Warning, "objects[0].speed".to_string())]);
+}
+
+#[test]
+fn a_spinner_with_fewer_than_two_glyphs_is_a_warning() {
+    let spinner = |glyphs: &str| {
+        format!(r#"{{"type":"spinner","glyphs":"{glyphs}","position":{{"x":{{"fixed":0}},"y":{{"fixed":0}}}},"frames":{{"start":0,"end":1}}}}"#)
+    };
+    let s = deck(1, &[&spinner(""), &spinner("*"), &spinner("|/-")]);
+    assert_eq!(
+        paths(&s),
+        vec![(Severity::Warning, "objects[0].glyphs".to_string()), (Severity::Warning, "objects[1].glyphs".to_string())]
+    );
+}
+
+#[test]
+fn a_connector_with_fewer_than_two_points_is_a_warning() {
+    let connector = |points: &str| {
+        format!(r#"{{"type":"connector","points":{points},"position":{{"x":{{"fixed":0}},"y":{{"fixed":0}}}},"frames":{{"start":0,"end":1}}}}"#)
+    };
+    let s = deck(1, &[&connector("[]"), &connector("[[1,1]]"), &connector("[[0,0],[4,2]]")]);
+    assert_eq!(
+        paths(&s),
+        vec![(Severity::Warning, "objects[0].points".to_string()), (Severity::Warning, "objects[1].points".to_string())]
+    );
+}
+
+#[test]
+fn a_keycap_without_keys_is_a_warning() {
+    let keycap = |keys: &str| {
+        format!(r#"{{"type":"keycap","keys":{keys},"position":{{"x":{{"fixed":0}},"y":{{"fixed":0}}}},"frames":{{"start":0,"end":1}}}}"#)
+    };
+    let s = deck(1, &[&keycap("[]"), &keycap(r#"[" "]"#), &keycap(r#"["Esc"]"#)]);
+    assert_eq!(
+        paths(&s),
+        vec![(Severity::Warning, "objects[0].keys".to_string()), (Severity::Warning, "objects[1].keys".to_string())]
+    );
+}
@@ -0,0 +1,74 @@
+//! Deck `vars`: `${name}` interpolation in label, header and table text at
+//! compile time, the `$${` escape, and warnings for undefined names.
+
+mod common;
+
+use bs::builder::Presentation;
+use bs::engine::source::SourcePresentation;
+use bs::pipeline;
+use bs::validate::validate;
+use common::{frame_lines, render_json};
+
+fn label(text: &str) -> String {
+    format!(
+        r#"{{"type":"label","text":"{text}","position":{{"x":{{"fixed":0}},"y":{{"fixed":0}}}},"frames":{{"start":0,"end":1}}}}"#
+    )
+}
+
+#[test]
+fn label_and_table_text_take_the_decks_vars() {
+    let json = format!(
+        r#"{{"width":16,"height":4,"frame_count":1,"vars":{{"event":"RustConf","v":"2.1"}},"objects":[
+            {},
+            {{"type":"table","position":{{"x":{{"fixed":0}},"y":{{"fixed":1}}}},"width":8,"col_widths":[1.0],"rows":1,"borders":false,
+              "cells":[[{{"content":"v${{ v }}"}}]],"frames":{{"start":0,"end":1}}}}
+        ]}}"#,
+        label("${event} ${v}!")
+    );
+    let p = render_json(&json);
+    assert_eq!(frame_lines(&p, 0)[0], "RustConf 2.1!   ");
+    assert!(frame_lines(&p, 0)[1].starts_with("v2.1"));
+}
+
+#[test]
+fn unknown_names_and_escapes_stay_literal() {
+    let source: SourcePresentation = serde_json::from_str(&format!(
+        r#"{{"width":1,"height":1,"frame_count":1,"vars":{{"a":"x"}},"objects":[{}]}}"#,
+        label("")
+    ))
+    .unwrap();
+    assert_eq!(source.interpolate("${a}-${b}-$${a}-${a"), "x-${b}-${a}-${a");
+}
+
+#[test]
+fn undefined_names_warn_only_in_decks_that_use_vars() {
+    let deck = |vars: &str| -> SourcePresentation {
+        serde_json::from_str(&format!(
+            r#"{{"width":20,"height":1,"frame_count":1{vars},"objects":[{}]}}"#,
+            label("echo ${HOME} $${a}")
+        ))
+        .unwrap()
+    };
+    let plain = deck("");
+    assert!(validate(&plain).is_empty());
+    assert!(pipeline::compile_checked(&plain, 0).unwrap().warnings.is_empty());
+    assert_eq!(frame_lines(&pipeline::compile(&plain).unwrap(), 0), ["echo ${HOME} $${a}  "]);
+
+    let with_vars = deck(r#","vars":{"a":"1"}"#);
+    let warnings = pipeline::compile_checked(&with_vars, 0).unwrap().warnings;
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].path, "objects[0].text");
+    assert!(warnings[0].message.contains("`${HOME}` is not defined"));
+    assert_eq!(validate(&with_vars), warnings);
+}
+
+#[test]
+fn the_builder_and_component_instances_see_vars() {
+    let p = Presentation::new(6, 1).var("who", "team").label("${who}").compile().unwrap();
+    assert_eq!(frame_lines(&p, 0), ["team  "]);
+
+    let json = r#"{"width":6,"height":1,"frame_count":1,"vars":{"who":"all"},
+        "components":{"hi":{"objects":[{"type":"label","text":"hi ${who}","position":{"x":{"fixed":0},"y":{"fixed":0}}}]}},
+        "objects":[{"type":"instance","component":"hi","frames":{"start":0,"end":1}}]}"#;
+    assert_eq!(frame_lines(&render_json(json), 0), ["hi all"]);
+}
@@ -0,0 +1,33 @@
+//! The `wasm` feature's bindings, exercised natively: compile to playable JSON,
+//! load a deck from either form, and iterate web-terminal repaints. (Error
+//! paths build a `JsError`, which only works on wasm32.)
+
+#![cfg(feature = "wasm")]
+
+use bs::wasm::{compile, Deck};
+
+/// 3x1, 2 frames: "ab" on frame 0 only.
+const DECK: &str = r#"{"width":3,"height":1,"frame_count":2,"objects":[
+    {"type":"label","text":"ab","position":{"x":{"fixed":0},"y":{"fixed":0}},"frames":{"start":0,"end":1}}
+]}"#;
+
+#[test]
+fn compile_output_loads_like_the_source() {
+    let compiled = compile(DECK).unwrap();
+    assert!(compiled.contains("\"contract\""));
+    let (a, b) = (Deck::new(DECK).unwrap(), Deck::new(&compiled).unwrap());
+    assert_eq!((a.width(), a.height(), a.frame_count()), (3, 1, 2));
+    assert_eq!(a.frame_text(0), b.frame_text(0));
+    assert_eq!(a.frame_text(0), "ab ");
+}
+
+#[test]
+fn frames_yield_one_repaint_per_frame_then_stop() {
+    let deck = Deck::new(DECK).unwrap();
+    let mut frames = deck.frames();
+    assert_eq!(frames.next().as_deref(), Some("\x1b[Hab "));
+    assert_eq!(frames.position(), 1);
+    assert_eq!(frames.next().as_deref(), Some("\x1b[H   "));
+    assert_eq!(frames.next(), None);
+    assert_eq!(deck.screen(5), "\x1b[H   ");
+}
@@ -0,0 +1,31 @@
+//! Tests for the `watermark` object — dim background text under everything
+//! else on the frames it spans.
+
+mod common;
+
+use bs::builder::Presentation;
+use common::frame_lines;
+
+#[test]
+fn watermark_is_dim_and_sits_under_the_slide() {
+    let p = Presentation::new(10, 2)
+        .frames(2)
+        .label("hi")
+        .at(1, 0)
+        .watermark("DRAFT")
+        .appears(0..2)
+        .compile()
+        .unwrap();
+    assert_eq!(frame_lines(&p, 0)[0], "DhiFT     ", "the label added first still covers it");
+    assert_eq!(frame_lines(&p, 1)[0], "DRAFT     ");
+    let grid = p.grid_at(1);
+    assert!(grid[0][0].style.dim);
+}
+
+#[test]
+fn watermark_blanks_leave_what_is_under_them() {
+    let p = Presentation::new(6, 1).label("xxxxxx").z(-2000).watermark("a  b").compile().unwrap();
+    assert_eq!(frame_lines(&p, 0)[0], "axxbxx", "blanks don't paint over what's below");
+    let grid = p.grid_at(0);
+    assert!(grid[0][0].style.dim && !grid[0][1].style.dim);
+}
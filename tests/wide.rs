@@ -0,0 +1,83 @@
+//! Double-width characters (CJK, emoji): each glyph takes two columns, the
+//! second holding a continuation cell that every output skips; wrapping and
+//! alignment count columns, and a glyph that loses its other half is blanked.
+
+mod common;
+use bs::renderer::ansi::{grid_to_plain, row_to_ansi};
+use common::render_json;
+
+fn label(text: &str, extra: &str, width: u16) -> String {
+    format!(
+        r#"{{ "width": {width}, "height": 3, "frame_count": 1,
+             "objects": [
+               {{ "type": "label", "text": "{text}", {extra}
+                  "position": {{ "x": {{ "fixed": 0 }}, "y": {{ "fixed": 0 }} }},
+                  "frames": {{ "start": 0, "end": 1 }} }}
+             ] }}"#
+    )
+}
+
+#[test]
+fn a_wide_glyph_fills_its_cell_and_a_continuation() {
+    let p = render_json(&label("日本a", "", 6));
+    let grid = p.grid_at(0);
+    assert_eq!(grid[0][0].ch, '日');
+    assert!(grid[0][1].is_continuation());
+    assert_eq!(grid[0][2].ch, '本');
+    assert!(grid[0][3].is_continuation());
+    assert_eq!(grid[0][4].ch, 'a', "ASCII after the wide glyphs starts at column 4");
+}
+
+#[test]
+fn text_outputs_never_contain_continuation_cells() {
+    let p = render_json(&label("日本", "", 5));
+    let grid = p.grid_at(0);
+    assert_eq!(grid_to_plain(&grid).lines().next().unwrap(), "日本 ");
+    assert!(!row_to_ansi(&grid[0]).contains('\0'));
+}
+
+#[test]
+fn wrapping_counts_columns_not_chars() {
+    // Three wide glyphs are six columns: a width-4 box fits two per row.
+    let p = render_json(&label("一二三", r#""width": 4,"#, 4));
+    let grid = p.grid_at(0);
+    assert_eq!(grid[0][0].ch, '一');
+    assert_eq!(grid[0][2].ch, '二');
+    assert_eq!(grid[1][0].ch, '三');
+}
+
+#[test]
+fn centring_uses_display_width() {
+    // "日本" is four columns; centred in eight it starts at column 2.
+    let p = render_json(&label("日本", r#""width": { "fixed": 8 }, "align": "center","#, 8));
+    let grid = p.grid_at(0);
+    assert_eq!(grid[0][1].ch, ' ');
+    assert_eq!(grid[0][2].ch, '日');
+    assert_eq!(grid[0][4].ch, '本');
+}
+
+#[test]
+fn a_glyph_whose_half_is_overdrawn_becomes_a_space() {
+    // "X" lands on the continuation of "日": the orphaned head is blanked.
+    let p = render_json(
+        r#"{ "width": 4, "height": 1, "frame_count": 1,
+             "objects": [
+               { "type": "label", "text": "日",
+                 "position": { "x": { "fixed": 0 }, "y": { "fixed": 0 } },
+                 "frames": { "start": 0, "end": 1 } },
+               { "type": "label", "text": "X", "z_order": 1,
+                 "position": { "x": { "fixed": 1 }, "y": { "fixed": 0 } },
+                 "frames": { "start": 0, "end": 1 } }
+             ] }"#,
+    );
+    let grid = p.grid_at(0);
+    assert_eq!(grid[0][0].ch, ' ');
+    assert_eq!(grid[0][1].ch, 'X');
+}
+
+#[test]
+fn a_glyph_hanging_off_the_right_edge_is_blanked() {
+    let p = render_json(&label("ab日", "", 3));
+    let grid = p.grid_at(0);
+    assert_eq!(grid_to_plain(&grid).lines().next().unwrap(), "ab ");
+}
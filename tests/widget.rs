@@ -0,0 +1,61 @@
+//! `widget::PresentationWidget` (feature `ratatui`): frames painted into a
+//! ratatui `Buffer`, clipped to the area, with the stateful cache stepping
+//! through diffs.
+#![cfg(feature = "ratatui")]
+
+mod common;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use ratatui::widgets::{StatefulWidget, Widget};
+
+use bs::widget::{PresentationState, PresentationWidget};
+
+/// 4x2, 3 frames: "ab" throughout (red), "c" on frame 1, "d" on frame 2.
+const DECK: &str = r#"{"width":4,"height":2,"frame_count":3,"objects":[
+    {"type":"label","text":"ab","position":{"x":{"fixed":0},"y":{"fixed":0}},"style":{"fg":"red"},"frames":{"start":0,"end":3}},
+    {"type":"label","text":"c","position":{"x":{"fixed":0},"y":{"fixed":1}},"frames":{"start":1,"end":2}},
+    {"type":"label","text":"d","position":{"x":{"fixed":1},"y":{"fixed":1}},"frames":{"start":2,"end":3}}
+]}"#;
+
+fn lines(buf: &Buffer) -> Vec<String> {
+    let area = buf.area;
+    (0..area.height)
+        .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol().to_string()).collect())
+        .collect()
+}
+
+#[test]
+fn renders_a_frame_offset_and_clipped_to_the_area() {
+    let p = common::render_json(DECK);
+    let mut buf = Buffer::empty(Rect::new(0, 0, 4, 2));
+    Widget::render(PresentationWidget::new(&p).frame(1), Rect::new(1, 0, 3, 2), &mut buf);
+    assert_eq!(lines(&buf), vec![" ab ", " c  "]);
+    assert_eq!(buf[(1, 0)].fg, Color::Indexed(9));
+}
+
+#[test]
+fn stateful_render_steps_through_frames_and_clamps() {
+    let p = common::render_json(DECK);
+    let area = Rect::new(0, 0, 4, 2);
+    let mut state = PresentationState::default();
+    let mut expected = vec![
+        vec!["ab  ", "    "],
+        vec!["ab  ", "c   "],
+        vec!["ab  ", " d  "],
+        vec!["ab  ", " d  "],
+    ]
+    .into_iter();
+    for _ in 0..4 {
+        let mut buf = Buffer::empty(area);
+        StatefulWidget::render(PresentationWidget::new(&p), area, &mut buf, &mut state);
+        assert_eq!(lines(&buf), expected.next().unwrap());
+        state.next();
+    }
+    assert_eq!(state.frame, 3, "advanced past the end; clamped on the next render");
+    state.frame = 0;
+    let mut buf = Buffer::empty(area);
+    StatefulWidget::render(PresentationWidget::new(&p), area, &mut buf, &mut state);
+    assert_eq!(lines(&buf), vec!["ab  ", "    "]);
+}